@@ -0,0 +1,2624 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::ops::Deref;
+use std::sync::{Arc, Mutex, RwLock};
+
+use chrono::{DateTime, NaiveDate, Utc};
+use sqlx::{Pool, Sqlite};
+use tokio::sync::{OwnedRwLockReadGuard, RwLock as SwitchLock};
+
+use super::migrations::create_database;
+use crate::models::*;
+use crate::services::UpstreamEvent;
+
+/// Database wrapper for all data access
+#[derive(Clone)]
+pub struct Database {
+    pool: Arc<RwLock<Pool<Sqlite>>>,
+    in_memory: Arc<AtomicBool>,
+    /// Held shared by every query, exclusively while the pool is switched to another file
+    queries: Arc<SwitchLock<()>>,
+    /// Held shared by each journaled write until it completes, so a switch waits for them
+    journaled: Arc<SwitchLock<()>>,
+    journal_guards: Arc<Mutex<HashMap<i64, OwnedRwLockReadGuard<()>>>>,
+}
+
+/// Pool of the current database file; it stays current until the guard is dropped
+struct PoolGuard {
+    pool: Pool<Sqlite>,
+    _switch: OwnedRwLockReadGuard<()>,
+}
+
+impl Deref for PoolGuard {
+    type Target = Pool<Sqlite>;
+
+    fn deref(&self) -> &Pool<Sqlite> {
+        &self.pool
+    }
+}
+
+impl Database {
+    pub fn new(pool: Pool<Sqlite>) -> Self {
+        Self {
+            pool: Arc::new(RwLock::new(pool)),
+            in_memory: Arc::new(AtomicBool::new(false)),
+            queries: Arc::new(SwitchLock::new(())),
+            journaled: Arc::new(SwitchLock::new(())),
+            journal_guards: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Session-only database used when the real file can't be opened
+    pub fn in_memory_fallback(pool: Pool<Sqlite>) -> Self {
+        let db = Self::new(pool);
+        db.in_memory.store(true, Ordering::SeqCst);
+        db
+    }
+
+    /// Whether writes are going to the in-memory fallback and will be lost on exit
+    pub fn is_in_memory(&self) -> bool {
+        self.in_memory.load(Ordering::SeqCst)
+    }
+
+    fn current_pool(&self) -> Pool<Sqlite> {
+        self.pool.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Current pool, waiting out a switch in progress. Methods must not hold one
+    /// guard while taking another: a queued switch would block the second forever
+    async fn pool(&self) -> PoolGuard {
+        let switch = self.queries.clone().read_owned().await;
+        PoolGuard {
+            pool: self.current_pool(),
+            _switch: switch,
+        }
+    }
+
+    fn release_journal_guard(&self, id: i64) {
+        self.journal_guards.lock().unwrap_or_else(|e| e.into_inner()).remove(&id);
+    }
+
+    /// Open the database file at `db_path`, replay everything written to the in-memory
+    /// fallback into it and switch over. Returns the number of rows replayed.
+    pub async fn recover_to(&self, db_path: &str) -> anyhow::Result<u64> {
+        if !self.is_in_memory() {
+            return Ok(0);
+        }
+
+        let disk = create_database(db_path).await?;
+
+        // Journaled writes in flight finish first and new ones wait: their journal ids are
+        // renumbered by the replay, so one straddling the switch would clear the wrong entry.
+        // Then every query in flight drains and the rest wait for the new pool
+        let _journaled = self.journaled.write().await;
+        let _queries = self.queries.write().await;
+        if !self.is_in_memory() {
+            // Another recovery switched while this one waited
+            disk.close().await;
+            return Ok(0);
+        }
+
+        let memory = self.current_pool();
+        let mut conn = memory.acquire().await?;
+        let replayed = replay_into(&mut conn, &disk).await?;
+
+        *self.pool.write().unwrap_or_else(|e| e.into_inner()) = disk;
+        self.in_memory.store(false, Ordering::SeqCst);
+        drop(conn);
+        memory.close().await;
+
+        Ok(replayed)
+    }
+
+    /// Copy the database into a new file at `db_path` and switch to it.
+    /// The old file is left in place as a backup.
+    pub async fn move_to(&self, db_path: &str) -> anyhow::Result<()> {
+        if self.is_in_memory() {
+            anyhow::bail!("Database is running in memory; it can't be moved until the file is recovered");
+        }
+        if std::path::Path::new(db_path).exists() {
+            anyhow::bail!("{} already exists", db_path);
+        }
+        if let Some(parent) = std::path::Path::new(db_path).parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        // Queries wait from the copy until the switch, so nothing written in between is left behind
+        let _queries = self.queries.write().await;
+        let current = self.current_pool();
+        // VACUUM INTO writes a consistent snapshot, even with WAL pages not yet checkpointed.
+        // It goes to a side file renamed into place once complete, so a crash midway can't
+        // leave a truncated copy at `db_path`; a side file left by an earlier crash is overwritten
+        let partial = format!("{}.partial", db_path);
+        std::fs::remove_file(&partial).ok();
+        sqlx::query("VACUUM INTO ?").bind(&partial).execute(&current).await?;
+        std::fs::rename(&partial, db_path)?;
+
+        let moved = create_database(db_path).await?;
+        let (check,): (String,) = sqlx::query_as("PRAGMA integrity_check")
+            .fetch_one(&moved)
+            .await?;
+        if check != "ok" {
+            moved.close().await;
+            std::fs::remove_file(db_path).ok();
+            anyhow::bail!("Copied database failed integrity check: {}", check);
+        }
+
+        *self.pool.write().unwrap_or_else(|e| e.into_inner()) = moved;
+        current.close().await;
+
+        Ok(())
+    }
+
+    // Mosque operations
+
+    pub async fn save_mosque(&self, mosque: &Mosque) -> anyhow::Result<()> {
+        insert_mosque(&*self.pool().await, mosque).await
+    }
+
+    pub async fn get_mosque(&self, id: &str) -> anyhow::Result<Option<Mosque>> {
+        let row = sqlx::query_as::<_, MosqueRow>(
+            r#"
+            SELECT m.id, m.name, m.address, m.city, m.country, m.latitude, m.longitude,
+                   m.is_favorite, m.last_accessed, m.facilities, f.facilities AS user_facilities
+            FROM mosques m LEFT JOIN mosque_facilities f ON f.mosque_id = m.id WHERE m.id = ?1
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&*self.pool().await)
+        .await?;
+
+        Ok(row.map(|r| r.into()))
+    }
+
+    pub async fn get_favorite_mosques(&self) -> anyhow::Result<Vec<Mosque>> {
+        let rows = sqlx::query_as::<_, MosqueRow>(
+            r#"
+            SELECT m.id, m.name, m.address, m.city, m.country, m.latitude, m.longitude,
+                   m.is_favorite, m.last_accessed, m.facilities, f.facilities AS user_facilities
+            FROM mosques m LEFT JOIN mosque_facilities f ON f.mosque_id = m.id WHERE m.is_favorite = 1 ORDER BY m.last_accessed DESC
+            "#,
+        )
+        .fetch_all(&*self.pool().await)
+        .await?;
+
+        Ok(rows.into_iter().map(|r| r.into()).collect())
+    }
+
+    /// Facilities the user entered for each mosque, keyed by mosque id
+    pub async fn get_user_facilities(&self) -> anyhow::Result<HashMap<String, MosqueFacilities>> {
+        let rows: Vec<(String, String)> =
+            sqlx::query_as("SELECT mosque_id, facilities FROM mosque_facilities")
+                .fetch_all(&*self.pool().await)
+                .await?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|(id, json)| Some((id, serde_json::from_str(&json).ok()?)))
+            .collect())
+    }
+
+    /// Store the user's own facility information for a mosque
+    pub async fn set_user_facilities(
+        &self,
+        mosque_id: &str,
+        facilities: &MosqueFacilities,
+    ) -> anyhow::Result<()> {
+        sqlx::query(
+            r#"
+            INSERT OR REPLACE INTO mosque_facilities (mosque_id, facilities, updated_at)
+            VALUES (?1, ?2, CURRENT_TIMESTAMP)
+            "#,
+        )
+        .bind(mosque_id)
+        .bind(serde_json::to_string(facilities)?)
+        .execute(&*self.pool().await)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn set_favorite(&self, id: &str, favorite: bool) -> anyhow::Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE mosques SET is_favorite = ?1 WHERE id = ?2
+            "#,
+        )
+        .bind(favorite as i32)
+        .bind(id)
+        .execute(&*self.pool().await)
+        .await?;
+
+        Ok(())
+    }
+
+    // Prayer times operations
+
+    pub async fn save_prayer_times(&self, prayer_times: &PrayerTimes) -> anyhow::Result<()> {
+        insert_prayer_times(&*self.pool().await, prayer_times).await
+    }
+
+    /// Save many days at once in a single transaction, reusing the prepared insert
+    pub async fn save_prayer_times_bulk(&self, days: &[PrayerTimes]) -> anyhow::Result<()> {
+        let pool = self.pool().await;
+        let mut tx = pool.begin().await?;
+
+        for prayer_times in days {
+            insert_prayer_times(&mut *tx, prayer_times).await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    pub async fn get_prayer_times(
+        &self,
+        mosque_id: &str,
+        date: NaiveDate,
+    ) -> anyhow::Result<Option<PrayerTimes>> {
+        let row = sqlx::query_as::<_, PrayerTimesRow>(
+            r#"
+            SELECT * FROM prayer_times WHERE mosque_id = ?1 AND date = ?2
+            "#,
+        )
+        .bind(mosque_id)
+        .bind(date_to_epoch(date))
+        .fetch_optional(&*self.pool().await)
+        .await?;
+
+        match row {
+            Some(row) => Ok(self.load_day(&row, mosque_id).await),
+            None => Ok(None),
+        }
+    }
+
+    /// Whether each mosque with a cached schedule publishes iqama times on at least one day
+    /// Mosques never fetched are absent, as nothing is known about them either way
+    pub async fn get_mosque_iqama_publishing(&self) -> anyhow::Result<HashMap<String, bool>> {
+        let rows: Vec<(String, bool)> = sqlx::query_as(
+            r#"
+            SELECT mosque_id,
+                   MAX(fajr_iqama IS NOT NULL OR dhuhr_iqama IS NOT NULL OR asr_iqama IS NOT NULL
+                       OR maghrib_iqama IS NOT NULL OR isha_iqama IS NOT NULL)
+            FROM prayer_times GROUP BY mosque_id
+            "#,
+        )
+        .fetch_all(&*self.pool().await)
+        .await?;
+
+        Ok(rows.into_iter().collect())
+    }
+
+    /// Get cached prayer times for an inclusive date range, ordered by date
+    pub async fn get_prayer_times_range(
+        &self,
+        mosque_id: &str,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> anyhow::Result<Vec<PrayerTimes>> {
+        let rows = sqlx::query_as::<_, PrayerTimesRow>(
+            r#"
+            SELECT * FROM prayer_times WHERE mosque_id = ?1 AND date BETWEEN ?2 AND ?3 ORDER BY date
+            "#,
+        )
+        .bind(mosque_id)
+        .bind(date_to_epoch(start))
+        .bind(date_to_epoch(end))
+        .fetch_all(&*self.pool().await)
+        .await?;
+
+        let mut days = Vec::with_capacity(rows.len());
+        for row in &rows {
+            days.extend(self.load_day(row, mosque_id).await);
+        }
+        Ok(days)
+    }
+
+    /// Days cached before warnings were stored get theirs here, for the prayers the mosque holds
+    async fn load_day(&self, row: &PrayerTimesRow, mosque_id: &str) -> Option<PrayerTimes> {
+        let mut times = row.to_prayer_times(mosque_id).ok()?;
+        if row.warnings.is_none() {
+            let user = self.get_prayer_availability(mosque_id).await.ok().flatten();
+            times.refresh_warnings(&PrayerAvailability::for_schedule(&times, user.as_ref()));
+        }
+        Some(times)
+    }
+
+    // Settings operations
+
+    /// The user's open/closed and per-prayer flags for a mosque, kept with the settings
+    pub async fn get_prayer_availability(&self, mosque_id: &str) -> anyhow::Result<Option<PrayerAvailability>> {
+        match self.get_setting(&availability_key(mosque_id)).await? {
+            Some(json) => Ok(Some(serde_json::from_str(&json)?)),
+            None => Ok(None),
+        }
+    }
+
+    pub async fn set_prayer_availability(&self, mosque_id: &str, availability: &PrayerAvailability) -> anyhow::Result<()> {
+        self.set_setting(&availability_key(mosque_id), &serde_json::to_string(availability)?)
+            .await
+    }
+
+    pub async fn set_setting(&self, key: &str, value: &str) -> anyhow::Result<()> {
+        sqlx::query(
+            r#"
+            INSERT OR REPLACE INTO settings (key, value, updated_at)
+            VALUES (?1, ?2, ?3)
+            "#,
+        )
+        .bind(key)
+        .bind(value)
+        .bind(Utc::now().to_rfc3339())
+        .execute(&*self.pool().await)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn get_setting(&self, key: &str) -> anyhow::Result<Option<String>> {
+        let row: Option<(String,)> = sqlx::query_as(
+            r#"
+            SELECT value FROM settings WHERE key = ?1
+            "#,
+        )
+        .bind(key)
+        .fetch_optional(&*self.pool().await)
+        .await?;
+
+        Ok(row.map(|r| r.0))
+    }
+
+    // Provider config operations
+
+    pub async fn save_provider_config(&self, config: &ProviderConfig) -> anyhow::Result<()> {
+        sqlx::query(
+            r#"
+            INSERT OR REPLACE INTO provider_configs (provider_id, settings, updated_at)
+            VALUES (?1, ?2, ?3)
+            "#,
+        )
+        .bind(&config.provider_id)
+        .bind(serde_json::to_string(&config.settings)?)
+        .bind(Utc::now().to_rfc3339())
+        .execute(&*self.pool().await)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn get_provider_config(&self, provider_id: &str) -> anyhow::Result<Option<ProviderConfig>> {
+        let row: Option<(String, String)> = sqlx::query_as(
+            r#"
+            SELECT provider_id, settings FROM provider_configs WHERE provider_id = ?1
+            "#,
+        )
+        .bind(provider_id)
+        .fetch_optional(&*self.pool().await)
+        .await?;
+
+        match row {
+            Some((id, settings)) => {
+                let settings: serde_json::Value = serde_json::from_str(&settings)?;
+                Ok(Some(ProviderConfig {
+                    provider_id: id,
+                    settings,
+                }))
+            }
+            None => Ok(None),
+        }
+    }
+
+    // Selected mosque operations
+
+    /// Replace a profile's selected mosque; the mosque must already be saved
+    pub async fn save_selected_mosque(&self, selected: &SelectedMosque) -> anyhow::Result<()> {
+        insert_selected_mosque(&*self.pool().await, selected).await
+    }
+
+    /// Store the newly picked mosque and point the profile at it in one transaction
+    /// A crash in between can't leave the profile on a mosque that was never saved
+    pub async fn switch_selected_mosque(&self, selected: &SelectedMosque) -> anyhow::Result<()> {
+        let pool = self.pool().await;
+        let mut tx = pool.begin().await?;
+
+        insert_mosque(&mut *tx, &selected.mosque).await?;
+        insert_selected_mosque(&mut *tx, selected).await?;
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    pub async fn get_selected_mosque(&self, profile: &str) -> anyhow::Result<Option<SelectedMosque>> {
+        let row: Option<(String, i64)> = sqlx::query_as(
+            r#"
+            SELECT mosque_id, selected_at FROM selected_mosques
+            WHERE profile = ?1
+            "#,
+        )
+        .bind(profile)
+        .fetch_optional(&*self.pool().await)
+        .await?;
+
+        let Some((mosque_id, selected_at)) = row else {
+            return Ok(None);
+        };
+        let Some(mosque) = self.get_mosque(&mosque_id).await? else {
+            log::warn!("Selected mosque {} of profile {} is no longer saved", mosque_id, profile);
+            return Ok(None);
+        };
+
+        Ok(Some(SelectedMosque {
+            profile: profile.to_string(),
+            mosque,
+            selected_at: epoch_to_datetime(selected_at)?,
+        }))
+    }
+
+    // Travel time operations
+
+    /// Save a mosque's travel time for a mode, `None` to forget it
+    pub async fn set_travel_time(
+        &self,
+        mosque_id: &str,
+        mode: TravelMode,
+        travel_time_seconds: Option<i64>,
+    ) -> anyhow::Result<()> {
+        let Some(seconds) = travel_time_seconds else {
+            sqlx::query("DELETE FROM travel_times WHERE mosque_id = ?1 AND mode = ?2")
+                .bind(mosque_id)
+                .bind(mode.as_str())
+                .execute(&*self.pool().await)
+                .await?;
+            return Ok(());
+        };
+
+        sqlx::query(
+            r#"
+            INSERT OR REPLACE INTO travel_times (mosque_id, mode, travel_time_seconds, updated_at)
+            VALUES (?1, ?2, ?3, ?4)
+            "#,
+        )
+        .bind(mosque_id)
+        .bind(mode.as_str())
+        .bind(seconds)
+        .bind(Utc::now().timestamp())
+        .execute(&*self.pool().await)
+        .await?;
+
+        Ok(())
+    }
+
+    /// A mosque's travel times, most recently set first
+    pub async fn get_travel_times(&self, mosque_id: &str) -> anyhow::Result<Vec<TravelTime>> {
+        let rows: Vec<(String, i64, i64)> = sqlx::query_as(
+            r#"
+            SELECT mode, travel_time_seconds, updated_at FROM travel_times
+            WHERE mosque_id = ?1
+            ORDER BY updated_at DESC, mode
+            "#,
+        )
+        .bind(mosque_id)
+        .fetch_all(&*self.pool().await)
+        .await?;
+
+        rows.into_iter()
+            .filter_map(|(mode, seconds, updated_at)| Some((mode.parse().ok()?, seconds, updated_at)))
+            .map(|(mode, travel_time_seconds, updated_at)| {
+                Ok(TravelTime {
+                    mosque_id: mosque_id.to_string(),
+                    mode,
+                    travel_time_seconds,
+                    updated_at: epoch_to_datetime(updated_at)?,
+                })
+            })
+            .collect()
+    }
+
+    /// Travel time for `mode`, or for the mode set last when `None`
+    pub async fn get_travel_time(
+        &self,
+        mosque_id: &str,
+        mode: Option<TravelMode>,
+    ) -> anyhow::Result<Option<i64>> {
+        Ok(self
+            .get_travel_times(mosque_id)
+            .await?
+            .into_iter()
+            .find(|travel| mode.is_none_or(|mode| travel.mode == mode))
+            .map(|travel| travel.travel_time_seconds))
+    }
+
+    /// Log a trip timed from departure to arrival at the mosque
+    pub async fn save_travel_trip(
+        &self,
+        mosque_id: &str,
+        mode: TravelMode,
+        started_at: DateTime<Utc>,
+        travel_time_seconds: i64,
+    ) -> anyhow::Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO travel_trips (mosque_id, mode, started_at, travel_time_seconds)
+            VALUES (?1, ?2, ?3, ?4)
+            "#,
+        )
+        .bind(mosque_id)
+        .bind(mode.as_str())
+        .bind(started_at.timestamp())
+        .bind(travel_time_seconds)
+        .execute(&*self.pool().await)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Durations of the latest `limit` trips to a mosque with a mode, most recent first
+    pub async fn get_recent_travel_trips(
+        &self,
+        mosque_id: &str,
+        mode: TravelMode,
+        limit: usize,
+    ) -> anyhow::Result<Vec<i64>> {
+        let durations = sqlx::query_scalar(
+            r#"
+            SELECT travel_time_seconds FROM travel_trips
+            WHERE mosque_id = ?1 AND mode = ?2
+            ORDER BY started_at DESC
+            LIMIT ?3
+            "#,
+        )
+        .bind(mosque_id)
+        .bind(mode.as_str())
+        .bind(limit as i64)
+        .fetch_all(&*self.pool().await)
+        .await?;
+
+        Ok(durations)
+    }
+
+    // Mosque catalog operations
+
+    /// Store the compressed mosque list for a country
+    pub async fn save_mosque_catalog(
+        &self,
+        country: &str,
+        data: &[u8],
+        mosque_count: usize,
+    ) -> anyhow::Result<()> {
+        sqlx::query(
+            r#"
+            INSERT OR REPLACE INTO mosque_catalog (country, data, mosque_count, fetched_at)
+            VALUES (?1, ?2, ?3, ?4)
+            "#,
+        )
+        .bind(country)
+        .bind(data)
+        .bind(mosque_count as i64)
+        .bind(Utc::now().to_rfc3339())
+        .execute(&*self.pool().await)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Mark a country catalog as freshly fetched without rewriting it
+    pub async fn touch_mosque_catalog(&self, country: &str) -> anyhow::Result<()> {
+        sqlx::query("UPDATE mosque_catalog SET fetched_at = ?2 WHERE country = ?1")
+            .bind(country)
+            .bind(Utc::now().to_rfc3339())
+            .execute(&*self.pool().await)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Get the compressed mosque list for a country along with its fetch time
+    pub async fn get_mosque_catalog(
+        &self,
+        country: &str,
+    ) -> anyhow::Result<Option<(Vec<u8>, DateTime<Utc>)>> {
+        let row: Option<(Vec<u8>, String)> = sqlx::query_as(
+            r#"
+            SELECT data, fetched_at FROM mosque_catalog WHERE country = ?1
+            "#,
+        )
+        .bind(country)
+        .fetch_optional(&*self.pool().await)
+        .await?;
+
+        match row {
+            Some((data, fetched_at)) => {
+                let fetched_at = DateTime::parse_from_rfc3339(&fetched_at)?.with_timezone(&Utc);
+                Ok(Some((data, fetched_at)))
+            }
+            None => Ok(None),
+        }
+    }
+
+    // Event operations
+
+    /// Replace the events a source published for a mosque, keeping reminders the user set
+    /// A moved event is reminded about again
+    pub async fn save_events(
+        &self,
+        mosque_id: &str,
+        source: &str,
+        events: &[MosqueEvent],
+    ) -> anyhow::Result<()> {
+        let pool = self.pool().await;
+        let mut tx = pool.begin().await?;
+        let fetched_at = Utc::now().timestamp();
+
+        for event in events {
+            sqlx::query(
+                r#"
+                INSERT INTO events
+                (id, mosque_id, source, kind, title, description, location, url, starts_at, ends_at, fetched_at)
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)
+                ON CONFLICT(id) DO UPDATE SET
+                    kind = excluded.kind, title = excluded.title, description = excluded.description,
+                    location = excluded.location, url = excluded.url, starts_at = excluded.starts_at,
+                    ends_at = excluded.ends_at, fetched_at = excluded.fetched_at,
+                    reminded_at = CASE WHEN events.starts_at = excluded.starts_at THEN events.reminded_at END
+                "#,
+            )
+            .bind(&event.id)
+            .bind(mosque_id)
+            .bind(source)
+            .bind(event.kind.as_str())
+            .bind(&event.title)
+            .bind(&event.description)
+            .bind(&event.location)
+            .bind(&event.url)
+            .bind(event.starts_at.timestamp())
+            .bind(event.ends_at.map(|d| d.timestamp()))
+            .bind(fetched_at)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        // Events no longer published by this source were cancelled upstream
+        let ids: Vec<&str> = events.iter().map(|e| e.id.as_str()).collect();
+        sqlx::query(
+            r#"
+            DELETE FROM events
+            WHERE mosque_id = ?1 AND source = ?2 AND id NOT IN (SELECT value FROM json_each(?3))
+            "#,
+        )
+        .bind(mosque_id)
+        .bind(source)
+        .bind(serde_json::to_string(&ids)?)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Events for a mosque that haven't finished by `from`, soonest first
+    pub async fn get_events(
+        &self,
+        mosque_id: &str,
+        from: DateTime<Utc>,
+    ) -> anyhow::Result<Vec<MosqueEvent>> {
+        let rows: Vec<EventRow> = sqlx::query_as(
+            r#"
+            SELECT * FROM events
+            WHERE mosque_id = ?1 AND COALESCE(ends_at, starts_at) >= ?2
+            ORDER BY starts_at
+            "#,
+        )
+        .bind(mosque_id)
+        .bind(from.timestamp())
+        .fetch_all(&*self.pool().await)
+        .await?;
+
+        rows.into_iter().map(EventRow::into_event).collect()
+    }
+
+    /// Set or clear the reminder for an event; returns false if the event doesn't exist
+    pub async fn set_event_reminder(
+        &self,
+        event_id: &str,
+        minutes_before: Option<i64>,
+    ) -> anyhow::Result<bool> {
+        let result = sqlx::query("UPDATE events SET reminder_minutes = ?1, reminded_at = NULL WHERE id = ?2")
+            .bind(minutes_before)
+            .bind(event_id)
+            .execute(&*self.pool().await)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Events not reminded about yet whose reminder time is before `by`, for events that start after `now`
+    /// Reminders missed while the computer slept are still due until their event starts
+    pub async fn get_pending_event_reminders(
+        &self,
+        now: DateTime<Utc>,
+        by: DateTime<Utc>,
+    ) -> anyhow::Result<Vec<MosqueEvent>> {
+        let rows: Vec<EventRow> = sqlx::query_as(
+            r#"
+            SELECT * FROM events
+            WHERE reminder_minutes IS NOT NULL
+              AND reminded_at IS NULL
+              AND starts_at - reminder_minutes * 60 < ?2
+              AND starts_at > ?1
+            ORDER BY starts_at
+            "#,
+        )
+        .bind(now.timestamp())
+        .bind(by.timestamp())
+        .fetch_all(&*self.pool().await)
+        .await?;
+
+        rows.into_iter().map(EventRow::into_event).collect()
+    }
+
+    /// Record that an event's reminder was shown
+    pub async fn mark_event_reminded(&self, event_id: &str, at: DateTime<Utc>) -> anyhow::Result<()> {
+        sqlx::query("UPDATE events SET reminded_at = ?1 WHERE id = ?2")
+            .bind(at.timestamp())
+            .bind(event_id)
+            .execute(&*self.pool().await)
+            .await?;
+        Ok(())
+    }
+
+    // Crowd report operations
+
+    /// Record a crowd report; a second report for the same prayer and day replaces the first
+    pub async fn save_crowd_report(&self, report: &CrowdReport) -> anyhow::Result<()> {
+        sqlx::query(
+            r#"
+            INSERT OR REPLACE INTO crowd_reports (mosque_id, prayer, date, level, reported_at)
+            VALUES (?1, ?2, ?3, ?4, ?5)
+            "#,
+        )
+        .bind(&report.mosque_id)
+        .bind(report.prayer.as_str())
+        .bind(date_to_epoch(report.date))
+        .bind(report.level.score())
+        .bind(report.reported_at.timestamp())
+        .execute(&*self.pool().await)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Average crowd score per prayer for a mosque since `since`
+    pub async fn get_crowd_summary(
+        &self,
+        mosque_id: &str,
+        since: NaiveDate,
+    ) -> anyhow::Result<Vec<CrowdSummary>> {
+        let rows: Vec<(String, f64, i64)> = sqlx::query_as(
+            r#"
+            SELECT prayer, AVG(level), COUNT(*) FROM crowd_reports
+            WHERE mosque_id = ?1 AND date >= ?2
+            GROUP BY prayer
+            "#,
+        )
+        .bind(mosque_id)
+        .bind(date_to_epoch(since))
+        .fetch_all(&*self.pool().await)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|(prayer, average, count)| {
+                Some(CrowdSummary {
+                    prayer: prayer.parse().ok()?,
+                    average_level: Some(average),
+                    report_count: count as u32,
+                })
+            })
+            .collect())
+    }
+
+    // Iqama report operations
+
+    /// Record when a prayer actually started; a second report for the same prayer and day replaces the first
+    pub async fn save_iqama_report(&self, report: &IqamaReport) -> anyhow::Result<()> {
+        sqlx::query(
+            r#"
+            INSERT OR REPLACE INTO iqama_reports
+            (mosque_id, prayer, date, scheduled_iqama, actual_iqama, reported_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+            "#,
+        )
+        .bind(&report.mosque_id)
+        .bind(report.prayer.as_str())
+        .bind(date_to_epoch(report.date))
+        .bind(report.scheduled_iqama.timestamp())
+        .bind(report.actual_iqama.timestamp())
+        .bind(report.reported_at.timestamp())
+        .execute(&*self.pool().await)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Reported start delays per prayer for a mosque since `since`
+    pub async fn get_iqama_report_delays(
+        &self,
+        mosque_id: &str,
+        since: NaiveDate,
+    ) -> anyhow::Result<Vec<(PrayerName, i64)>> {
+        let rows: Vec<(String, i64)> = sqlx::query_as(
+            r#"
+            SELECT prayer, actual_iqama - scheduled_iqama FROM iqama_reports
+            WHERE mosque_id = ?1 AND date >= ?2
+            ORDER BY date
+            "#,
+        )
+        .bind(mosque_id)
+        .bind(date_to_epoch(since))
+        .fetch_all(&*self.pool().await)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|(prayer, delay)| Some((prayer.parse().ok()?, delay)))
+            .collect())
+    }
+
+    // Prayer log operations
+
+    /// Record a logged prayer; logging the same prayer and day again replaces the entry
+    pub async fn save_prayer_log_entry(&self, entry: &PrayerLogEntry) -> anyhow::Result<()> {
+        let tags: Vec<&str> = entry.tags.iter().map(|t| t.as_str()).collect();
+
+        sqlx::query(
+            r#"
+            INSERT OR REPLACE INTO prayer_log (profile, prayer, date, mosque_id, tags, note, logged_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+            "#,
+        )
+        .bind(&entry.profile)
+        .bind(entry.prayer.as_str())
+        .bind(date_to_epoch(entry.date))
+        .bind(entry.mosque_id.as_deref())
+        .bind(tags.join(","))
+        .bind(entry.note.as_deref())
+        .bind(entry.logged_at.timestamp())
+        .execute(&*self.pool().await)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn get_prayer_log_entry(
+        &self,
+        profile: &str,
+        prayer: PrayerName,
+        date: NaiveDate,
+    ) -> anyhow::Result<Option<PrayerLogEntry>> {
+        let row = sqlx::query_as::<_, PrayerLogRow>(
+            "SELECT * FROM prayer_log WHERE profile = ?1 AND prayer = ?2 AND date = ?3",
+        )
+        .bind(profile)
+        .bind(prayer.as_str())
+        .bind(date_to_epoch(date))
+        .fetch_optional(&*self.pool().await)
+        .await?;
+
+        row.map(PrayerLogRow::into_entry).transpose()
+    }
+
+    /// Log entries matching `query`, oldest first
+    pub async fn get_prayer_log(&self, query: &PrayerLogQuery) -> anyhow::Result<Vec<PrayerLogEntry>> {
+        let rows = sqlx::query_as::<_, PrayerLogRow>(
+            r#"
+            SELECT * FROM prayer_log
+            WHERE (?1 IS NULL OR profile = ?1)
+              AND (?2 IS NULL OR date >= ?2)
+              AND (?3 IS NULL OR date <= ?3)
+              AND (?4 IS NULL OR prayer = ?4)
+            ORDER BY date, logged_at
+            "#,
+        )
+        .bind(query.profile.as_deref())
+        .bind(query.from.map(date_to_epoch))
+        .bind(query.to.map(date_to_epoch))
+        .bind(query.prayer.map(|p| p.as_str()))
+        .fetch_all(&*self.pool().await)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|r| r.into_entry().ok())
+            .filter(|e| query.tag.is_none_or(|tag| e.has_tag(tag)))
+            .filter(|e| query.with_note.is_none_or(|with_note| e.note.is_some() == with_note))
+            .collect())
+    }
+
+    /// Returns whether an entry was removed
+    pub async fn delete_prayer_log_entry(
+        &self,
+        profile: &str,
+        prayer: PrayerName,
+        date: NaiveDate,
+    ) -> anyhow::Result<bool> {
+        let result = sqlx::query("DELETE FROM prayer_log WHERE profile = ?1 AND prayer = ?2 AND date = ?3")
+            .bind(profile)
+            .bind(prayer.as_str())
+            .bind(date_to_epoch(date))
+            .execute(&*self.pool().await)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    // Goal operations
+
+    /// Store a new goal; returns its id
+    pub async fn save_prayer_goal(&self, goal: &PrayerGoal) -> anyhow::Result<i64> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO prayer_goals (profile, prayer, tag, target_per_week, created_at)
+            VALUES (?1, ?2, ?3, ?4, ?5)
+            "#,
+        )
+        .bind(&goal.profile)
+        .bind(goal.prayer.map(|p| p.as_str()))
+        .bind(goal.tag.map(|t| t.as_str()))
+        .bind(goal.target_per_week)
+        .bind(goal.created_at.timestamp())
+        .execute(&*self.pool().await)
+        .await?;
+
+        Ok(result.last_insert_rowid())
+    }
+
+    /// Goals of a profile, or of every profile when `None`, oldest first
+    pub async fn get_prayer_goals(&self, profile: Option<&str>) -> anyhow::Result<Vec<PrayerGoal>> {
+        let rows = sqlx::query_as::<_, PrayerGoalRow>(
+            "SELECT * FROM prayer_goals WHERE (?1 IS NULL OR profile = ?1) ORDER BY created_at, id",
+        )
+        .bind(profile)
+        .fetch_all(&*self.pool().await)
+        .await?;
+
+        Ok(rows.into_iter().filter_map(|r| r.into_goal().ok()).collect())
+    }
+
+    /// Remove a goal and its weekly history; returns whether there was one
+    pub async fn delete_prayer_goal(&self, id: i64) -> anyhow::Result<bool> {
+        let pool = self.pool().await;
+        let mut tx = pool.begin().await?;
+
+        sqlx::query("DELETE FROM goal_weeks WHERE goal_id = ?1")
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+        let result = sqlx::query("DELETE FROM prayer_goals WHERE id = ?1")
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Record how a goal's week went; evaluating the week again replaces the result
+    pub async fn save_goal_week(&self, progress: &GoalProgress, evaluated_at: DateTime<Utc>) -> anyhow::Result<()> {
+        sqlx::query(
+            r#"
+            INSERT OR REPLACE INTO goal_weeks (goal_id, week_start, completed, achieved, evaluated_at)
+            VALUES (?1, ?2, ?3, ?4, ?5)
+            "#,
+        )
+        .bind(progress.goal.id)
+        .bind(date_to_epoch(progress.week_start))
+        .bind(progress.completed)
+        .bind(progress.achieved)
+        .bind(evaluated_at.timestamp())
+        .execute(&*self.pool().await)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Achieved weeks in a row just before the week starting `week_start`
+    pub async fn get_goal_streak(&self, goal_id: i64, week_start: NaiveDate) -> anyhow::Result<u32> {
+        let rows: Vec<(i64, bool)> = sqlx::query_as(
+            r#"
+            SELECT week_start, achieved FROM goal_weeks
+            WHERE goal_id = ?1 AND week_start < ?2
+            ORDER BY week_start DESC
+            "#,
+        )
+        .bind(goal_id)
+        .bind(date_to_epoch(week_start))
+        .fetch_all(&*self.pool().await)
+        .await?;
+
+        let mut streak = 0;
+        let mut expected = week_start - chrono::Duration::days(7);
+        for (week, achieved) in rows {
+            if !achieved || epoch_to_datetime(week)?.date_naive() != expected {
+                break;
+            }
+            streak += 1;
+            expected -= chrono::Duration::days(7);
+        }
+
+        Ok(streak)
+    }
+
+    // Schedule change operations
+
+    pub async fn save_schedule_changes(&self, changes: &[ScheduleChange]) -> anyhow::Result<()> {
+        let pool = self.pool().await;
+        let mut tx = pool.begin().await?;
+
+        for change in changes {
+            insert_schedule_change(&mut *tx, change).await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    /// Save refetched days and the changes found in them together, completing `journal` if given,
+    /// so a crash leaves either the old schedule or the whole new one
+    pub async fn save_schedule_refresh(
+        &self,
+        days: &[PrayerTimes],
+        changes: &[ScheduleChange],
+        journal: Option<i64>,
+    ) -> anyhow::Result<()> {
+        let pool = self.pool().await;
+        let mut tx = pool.begin().await?;
+
+        for prayer_times in days {
+            insert_prayer_times(&mut *tx, prayer_times).await?;
+        }
+        for change in changes {
+            insert_schedule_change(&mut *tx, change).await?;
+        }
+        if let Some(id) = journal {
+            sqlx::query("DELETE FROM write_journal WHERE id = ?1")
+                .bind(id)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        tx.commit().await?;
+        if let Some(id) = journal {
+            self.release_journal_guard(id);
+        }
+
+        Ok(())
+    }
+
+    /// Most recently detected changes first, optionally for one mosque
+    pub async fn get_schedule_changes(
+        &self,
+        mosque_id: Option<&str>,
+        limit: i64,
+    ) -> anyhow::Result<Vec<ScheduleChange>> {
+        let rows = sqlx::query_as::<_, ScheduleChangeRow>(
+            r#"
+            SELECT mosque_id, prayer, date, old_iqama, new_iqama, detected_at FROM schedule_changes
+            WHERE ?1 IS NULL OR mosque_id = ?1
+            ORDER BY detected_at DESC, id DESC
+            LIMIT ?2
+            "#,
+        )
+        .bind(mosque_id)
+        .bind(limit)
+        .fetch_all(&*self.pool().await)
+        .await?;
+
+        Ok(rows.into_iter().filter_map(|r| r.to_change().ok()).collect())
+    }
+
+    // Upstream issue operations
+
+    /// Apply provider reports in order: failures are counted, recoveries clear their context
+    pub async fn save_upstream_events(&self, events: &[UpstreamEvent]) -> anyhow::Result<()> {
+        let pool = self.pool().await;
+        let mut tx = pool.begin().await?;
+
+        for event in events {
+            match event {
+                UpstreamEvent::Failure(issue) => {
+                    sqlx::query(
+                        r#"
+                        INSERT INTO upstream_issues
+                        (provider_id, context, fingerprint, message, degraded, occurrences, first_seen, last_seen)
+                        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+                        ON CONFLICT(provider_id, fingerprint) DO UPDATE SET
+                            message = excluded.message,
+                            degraded = excluded.degraded,
+                            occurrences = occurrences + excluded.occurrences,
+                            last_seen = excluded.last_seen
+                        "#,
+                    )
+                    .bind(&issue.provider_id)
+                    .bind(&issue.context)
+                    .bind(&issue.fingerprint)
+                    .bind(&issue.message)
+                    .bind(issue.degraded)
+                    .bind(issue.occurrences)
+                    .bind(issue.first_seen.timestamp())
+                    .bind(issue.last_seen.timestamp())
+                    .execute(&mut *tx)
+                    .await?;
+                }
+                UpstreamEvent::Recovered { provider_id, context } => {
+                    sqlx::query("DELETE FROM upstream_issues WHERE provider_id = ?1 AND context = ?2")
+                        .bind(provider_id)
+                        .bind(context)
+                        .execute(&mut *tx)
+                        .await?;
+                }
+            }
+        }
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    /// Unresolved upstream issues, most recently seen first
+    pub async fn get_upstream_issues(&self) -> anyhow::Result<Vec<UpstreamIssue>> {
+        let rows = sqlx::query_as::<_, UpstreamIssueRow>(
+            r#"
+            SELECT provider_id, context, fingerprint, message, degraded, occurrences, first_seen, last_seen
+            FROM upstream_issues
+            ORDER BY last_seen DESC
+            "#,
+        )
+        .fetch_all(&*self.pool().await)
+        .await?;
+
+        Ok(rows.into_iter().filter_map(|r| r.into_issue().ok()).collect())
+    }
+
+    // Raw payload archive operations
+
+    /// Store fetched responses, then keep only the latest `keep` of each mosque they belong to
+    pub async fn save_raw_payloads(&self, payloads: &[RawPayload], keep: usize) -> anyhow::Result<()> {
+        let pool = self.pool().await;
+        let mut tx = pool.begin().await?;
+
+        for payload in payloads {
+            sqlx::query(
+                r#"
+                INSERT INTO raw_payloads (mosque_id, provider_id, url, fetched_at, size_bytes, body)
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+                "#,
+            )
+            .bind(&payload.mosque_id)
+            .bind(&payload.provider_id)
+            .bind(&payload.url)
+            .bind(payload.fetched_at.timestamp())
+            .bind(payload.size_bytes)
+            .bind(&payload.body)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        let mosque_ids: HashSet<&str> = payloads.iter().map(|p| p.mosque_id.as_str()).collect();
+        for mosque_id in mosque_ids {
+            sqlx::query(
+                r#"
+                DELETE FROM raw_payloads WHERE mosque_id = ?1 AND id NOT IN (
+                    SELECT id FROM raw_payloads WHERE mosque_id = ?1
+                    ORDER BY fetched_at DESC, id DESC LIMIT ?2
+                )
+                "#,
+            )
+            .bind(mosque_id)
+            .bind(keep as i64)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    /// Archived responses, of one mosque or all, newest first
+    pub async fn get_raw_payloads(&self, mosque_id: Option<&str>) -> anyhow::Result<Vec<RawPayloadInfo>> {
+        let rows = sqlx::query_as::<_, RawPayloadRow>(
+            r#"
+            SELECT id, mosque_id, provider_id, url, fetched_at, size_bytes, LENGTH(body) AS compressed_bytes
+            FROM raw_payloads
+            WHERE ?1 IS NULL OR mosque_id = ?1
+            ORDER BY fetched_at DESC, id DESC
+            "#,
+        )
+        .bind(mosque_id)
+        .fetch_all(&*self.pool().await)
+        .await?;
+
+        Ok(rows.into_iter().filter_map(|r| r.into_info().ok()).collect())
+    }
+
+    /// One archived response with its compressed body
+    pub async fn get_raw_payload(&self, id: i64) -> anyhow::Result<Option<(RawPayloadInfo, Vec<u8>)>> {
+        let row = sqlx::query_as::<_, RawPayloadRow>(
+            r#"
+            SELECT id, mosque_id, provider_id, url, fetched_at, size_bytes, LENGTH(body) AS compressed_bytes
+            FROM raw_payloads WHERE id = ?1
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&*self.pool().await)
+        .await?;
+        let Some(row) = row else {
+            return Ok(None);
+        };
+
+        let body: Vec<u8> = sqlx::query_scalar("SELECT body FROM raw_payloads WHERE id = ?1")
+            .bind(id)
+            .fetch_one(&*self.pool().await)
+            .await?;
+
+        Ok(Some((row.into_info()?, body)))
+    }
+
+    /// Delete archived responses, of one mosque or all; returns how many were removed
+    pub async fn clear_raw_payloads(&self, mosque_id: Option<&str>) -> anyhow::Result<u64> {
+        let result = sqlx::query("DELETE FROM raw_payloads WHERE ?1 IS NULL OR mosque_id = ?1")
+            .bind(mosque_id)
+            .execute(&*self.pool().await)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    // Mosque notification overrides
+
+    pub async fn set_mosque_notification_override(&self, mosque: &MosqueNotificationOverride) -> anyhow::Result<()> {
+        let join = |values: Vec<String>| values.join(",");
+
+        sqlx::query(
+            r#"
+            INSERT OR REPLACE INTO mosque_notification_overrides
+            (mosque_id, enabled, sound, speech, prayers, reminder_minutes, updated_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+            "#,
+        )
+        .bind(&mosque.mosque_id)
+        .bind(mosque.enabled)
+        .bind(mosque.sound)
+        .bind(mosque.speech)
+        .bind(mosque.prayers.as_ref().map(|p| join(p.iter().map(|p| p.as_str().to_string()).collect())))
+        .bind(mosque.reminder_minutes_before.as_ref().map(|m| join(m.iter().map(|m| m.to_string()).collect())))
+        .bind(Utc::now().timestamp())
+        .execute(&*self.pool().await)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn get_mosque_notification_override(
+        &self,
+        mosque_id: &str,
+    ) -> anyhow::Result<Option<MosqueNotificationOverride>> {
+        let row = sqlx::query_as::<_, MosqueNotificationOverrideRow>(
+            r#"
+            SELECT mosque_id, enabled, sound, speech, prayers, reminder_minutes
+            FROM mosque_notification_overrides WHERE mosque_id = ?1
+            "#,
+        )
+        .bind(mosque_id)
+        .fetch_optional(&*self.pool().await)
+        .await?;
+
+        Ok(row.map(MosqueNotificationOverrideRow::into_override))
+    }
+
+    pub async fn get_mosque_notification_overrides(&self) -> anyhow::Result<Vec<MosqueNotificationOverride>> {
+        let rows = sqlx::query_as::<_, MosqueNotificationOverrideRow>(
+            r#"
+            SELECT mosque_id, enabled, sound, speech, prayers, reminder_minutes
+            FROM mosque_notification_overrides ORDER BY mosque_id
+            "#,
+        )
+        .fetch_all(&*self.pool().await)
+        .await?;
+
+        Ok(rows.into_iter().map(MosqueNotificationOverrideRow::into_override).collect())
+    }
+
+    /// Back to the profile's settings for a mosque; false if it had no override
+    pub async fn delete_mosque_notification_override(&self, mosque_id: &str) -> anyhow::Result<bool> {
+        let result = sqlx::query("DELETE FROM mosque_notification_overrides WHERE mosque_id = ?1")
+            .bind(mosque_id)
+            .execute(&*self.pool().await)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    // Write journal
+
+    /// Record a multi-step write before starting it; returns the entry its final transaction clears
+    /// The database file isn't switched until the entry is completed or discarded
+    pub async fn begin_write(&self, write: &PendingWrite) -> anyhow::Result<i64> {
+        let journaled = self.journaled.clone().read_owned().await;
+        let id = sqlx::query("INSERT INTO write_journal (kind, payload, started_at) VALUES (?1, ?2, ?3)")
+            .bind(write.kind())
+            .bind(serde_json::to_string(write)?)
+            .bind(Utc::now().timestamp())
+            .execute(&*self.pool().await)
+            .await?
+            .last_insert_rowid();
+
+        self.journal_guards
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(id, journaled);
+
+        Ok(id)
+    }
+
+    /// Writes started but never completed, oldest first
+    /// Entries this version can't read are dropped
+    pub async fn pending_writes(&self) -> anyhow::Result<Vec<JournalEntry>> {
+        let rows: Vec<(i64, String, i64)> =
+            sqlx::query_as("SELECT id, payload, started_at FROM write_journal ORDER BY id")
+                .fetch_all(&*self.pool().await)
+                .await?;
+
+        let mut entries = Vec::with_capacity(rows.len());
+        for (id, payload, started_at) in rows {
+            match (serde_json::from_str(&payload), epoch_to_datetime(started_at)) {
+                (Ok(write), Ok(started_at)) => entries.push(JournalEntry { id, write, started_at }),
+                _ => {
+                    log::warn!("Dropping unreadable write journal entry {}", id);
+                    self.discard_write(id).await?;
+                }
+            }
+        }
+
+        Ok(entries)
+    }
+
+    /// Forget a journaled write that failed before changing anything, or was given up on
+    pub async fn discard_write(&self, id: i64) -> anyhow::Result<()> {
+        let result = sqlx::query("DELETE FROM write_journal WHERE id = ?1")
+            .bind(id)
+            .execute(&*self.pool().await)
+            .await;
+        self.release_journal_guard(id);
+        result?;
+
+        Ok(())
+    }
+
+    // Storage statistics
+
+    /// Bytes taken by the database: pages in use times the page size
+    pub async fn size_bytes(&self) -> anyhow::Result<u64> {
+        let pool = self.pool().await;
+        let pages: i64 = sqlx::query_scalar("PRAGMA page_count").fetch_one(&*pool).await?;
+        let page_size: i64 = sqlx::query_scalar("PRAGMA page_size").fetch_one(&*pool).await?;
+        Ok((pages * page_size).max(0) as u64)
+    }
+
+    /// Row count of every table, by name
+    pub async fn table_sizes(&self) -> anyhow::Result<Vec<TableSize>> {
+        let pool = self.pool().await;
+        let tables: Vec<String> = sqlx::query_scalar(
+            r#"
+            SELECT name FROM sqlite_master
+            WHERE type = 'table' AND name NOT LIKE 'sqlite_%'
+            ORDER BY name
+            "#,
+        )
+        .fetch_all(&*pool)
+        .await?;
+
+        let mut sizes = Vec::with_capacity(tables.len());
+        for table in tables {
+            // Names come from sqlite_master, quoted in case one needs it
+            let query = format!("SELECT COUNT(*) FROM \"{}\"", table.replace('"', "\"\""));
+            let rows: i64 = sqlx::query_scalar(&query).fetch_one(&*pool).await?;
+            sizes.push(TableSize {
+                table,
+                rows: rows.max(0) as u64,
+            });
+        }
+        Ok(sizes)
+    }
+}
+
+// Database row structs
+
+#[derive(sqlx::FromRow)]
+#[allow(dead_code)]
+struct EventRow {
+    id: String,
+    mosque_id: String,
+    source: String,
+    kind: String,
+    title: String,
+    description: Option<String>,
+    location: Option<String>,
+    url: Option<String>,
+    starts_at: i64,
+    ends_at: Option<i64>,
+    reminder_minutes: Option<i64>,
+    fetched_at: i64,
+}
+
+impl EventRow {
+    fn into_event(self) -> anyhow::Result<MosqueEvent> {
+        Ok(MosqueEvent {
+            id: self.id,
+            mosque_id: self.mosque_id,
+            kind: self.kind.parse().map_err(anyhow::Error::msg)?,
+            title: self.title,
+            description: self.description,
+            location: self.location,
+            url: self.url,
+            starts_at: epoch_to_datetime(self.starts_at)?,
+            ends_at: self.ends_at.map(epoch_to_datetime).transpose()?,
+            reminder_minutes: self.reminder_minutes,
+        })
+    }
+}
+
+#[derive(sqlx::FromRow)]
+pub(super) struct PrayerLogRow {
+    profile: String,
+    prayer: String,
+    date: i64,
+    mosque_id: Option<String>,
+    tags: String,
+    note: Option<String>,
+    logged_at: i64,
+}
+
+impl PrayerLogRow {
+    pub(super) fn into_entry(self) -> anyhow::Result<PrayerLogEntry> {
+        Ok(PrayerLogEntry {
+            profile: self.profile,
+            prayer: self.prayer.parse().map_err(anyhow::Error::msg)?,
+            date: epoch_to_datetime(self.date)?.date_naive(),
+            mosque_id: self.mosque_id,
+            tags: self.tags.split(',').filter_map(|t| t.parse().ok()).collect(),
+            note: self.note,
+            logged_at: epoch_to_datetime(self.logged_at)?,
+        })
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct MosqueNotificationOverrideRow {
+    mosque_id: String,
+    enabled: bool,
+    sound: bool,
+    speech: bool,
+    prayers: Option<String>,
+    reminder_minutes: Option<String>,
+}
+
+impl MosqueNotificationOverrideRow {
+    /// Unknown prayers or minutes, e.g. written by a newer version, are left out
+    fn into_override(self) -> MosqueNotificationOverride {
+        MosqueNotificationOverride {
+            mosque_id: self.mosque_id,
+            enabled: self.enabled,
+            sound: self.sound,
+            speech: self.speech,
+            prayers: self
+                .prayers
+                .map(|p| p.split(',').filter_map(|p| p.parse().ok()).collect()),
+            reminder_minutes_before: self
+                .reminder_minutes
+                .map(|m| m.split(',').filter_map(|m| m.parse().ok()).collect()),
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct PrayerGoalRow {
+    id: i64,
+    profile: String,
+    prayer: Option<String>,
+    tag: Option<String>,
+    target_per_week: u32,
+    created_at: i64,
+}
+
+impl PrayerGoalRow {
+    fn into_goal(self) -> anyhow::Result<PrayerGoal> {
+        Ok(PrayerGoal {
+            id: self.id,
+            profile: self.profile,
+            prayer: self.prayer.map(|p| p.parse()).transpose().map_err(anyhow::Error::msg)?,
+            tag: self.tag.map(|t| t.parse()).transpose().map_err(anyhow::Error::msg)?,
+            target_per_week: self.target_per_week,
+            created_at: epoch_to_datetime(self.created_at)?,
+        })
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct UpstreamIssueRow {
+    provider_id: String,
+    context: String,
+    fingerprint: String,
+    message: String,
+    degraded: bool,
+    occurrences: u32,
+    first_seen: i64,
+    last_seen: i64,
+}
+
+impl UpstreamIssueRow {
+    fn into_issue(self) -> anyhow::Result<UpstreamIssue> {
+        Ok(UpstreamIssue {
+            provider_id: self.provider_id,
+            context: self.context,
+            fingerprint: self.fingerprint,
+            message: self.message,
+            degraded: self.degraded,
+            occurrences: self.occurrences,
+            first_seen: epoch_to_datetime(self.first_seen)?,
+            last_seen: epoch_to_datetime(self.last_seen)?,
+        })
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct RawPayloadRow {
+    id: i64,
+    mosque_id: String,
+    provider_id: String,
+    url: String,
+    fetched_at: i64,
+    size_bytes: i64,
+    compressed_bytes: i64,
+}
+
+impl RawPayloadRow {
+    fn into_info(self) -> anyhow::Result<RawPayloadInfo> {
+        Ok(RawPayloadInfo {
+            id: self.id,
+            provider_id: self.provider_id,
+            mosque_id: self.mosque_id,
+            url: self.url,
+            fetched_at: epoch_to_datetime(self.fetched_at)?,
+            size_bytes: self.size_bytes,
+            compressed_bytes: self.compressed_bytes,
+        })
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct MosqueRow {
+    id: String,
+    name: String,
+    address: Option<String>,
+    city: Option<String>,
+    country: Option<String>,
+    latitude: Option<f64>,
+    longitude: Option<f64>,
+    is_favorite: i32,
+    last_accessed: Option<String>,
+    facilities: Option<String>,
+    user_facilities: Option<String>,
+}
+
+impl From<MosqueRow> for Mosque {
+    fn from(row: MosqueRow) -> Self {
+        Self {
+            id: row.id,
+            name: row.name,
+            address: row.address,
+            city: row.city,
+            country: row.country,
+            latitude: row.latitude,
+            longitude: row.longitude,
+            is_favorite: row.is_favorite != 0,
+            last_accessed: row.last_accessed.and_then(|d| DateTime::parse_from_rfc3339(&d).ok().map(|dt| dt.with_timezone(&Utc))),
+            facilities: parse_facilities(row.facilities.as_deref())
+                .merged(&parse_facilities(row.user_facilities.as_deref())),
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+#[allow(dead_code)]
+struct PrayerTimesRow {
+    mosque_id: String,
+    date: i64,
+    fajr_adhan: i64,
+    fajr_iqama: Option<i64>,
+    fajr_rakah: Option<i32>,
+    dhuhr_adhan: i64,
+    dhuhr_iqama: Option<i64>,
+    dhuhr_rakah: Option<i32>,
+    asr_adhan: i64,
+    asr_iqama: Option<i64>,
+    asr_rakah: Option<i32>,
+    maghrib_adhan: i64,
+    maghrib_iqama: Option<i64>,
+    maghrib_rakah: Option<i32>,
+    isha_adhan: i64,
+    isha_iqama: Option<i64>,
+    isha_rakah: Option<i32>,
+    jumuah_adhan: Option<i64>,
+    jumuah_iqama: Option<i64>,
+    jumuah_rakah: Option<i32>,
+    mosque_name: Option<String>,
+    cached_at: i64,
+    imsak: Option<i64>,
+    shuruq: Option<i64>,
+    asr_method: Option<String>,
+    provider: Option<String>,
+    iqama_offsets: Option<String>,
+    warnings: Option<String>,
+}
+
+impl PrayerTimesRow {
+    fn to_prayer_times(&self, default_mosque_id: &str) -> anyhow::Result<PrayerTimes> {
+        let offsets: HashMap<PrayerName, i32> = self
+            .iqama_offsets
+            .as_deref()
+            .and_then(|json| serde_json::from_str(json).ok())
+            .unwrap_or_default();
+        let prayer = |name: PrayerName, adhan: i64, iqama: Option<i64>, rakah: Option<i32>| -> anyhow::Result<Prayer> {
+            let iqama = iqama.and_then(|t| epoch_to_datetime(t).ok());
+            Ok(Prayer {
+                name,
+                adhan: epoch_to_datetime(adhan)?,
+                iqama_offset_minutes: iqama.and(offsets.get(&name).copied()),
+                iqama,
+                custom_rakah_count: rakah,
+            })
+        };
+
+        Ok(PrayerTimes {
+            date: epoch_to_datetime(self.date)?,
+            fajr: prayer(PrayerName::Fajr, self.fajr_adhan, self.fajr_iqama, self.fajr_rakah)?,
+            dhuhr: prayer(PrayerName::Dhuhr, self.dhuhr_adhan, self.dhuhr_iqama, self.dhuhr_rakah)?,
+            asr: prayer(PrayerName::Asr, self.asr_adhan, self.asr_iqama, self.asr_rakah)?,
+            maghrib: prayer(PrayerName::Maghrib, self.maghrib_adhan, self.maghrib_iqama, self.maghrib_rakah)?,
+            isha: prayer(PrayerName::Isha, self.isha_adhan, self.isha_iqama, self.isha_rakah)?,
+            jumuah: match self.jumuah_adhan {
+                Some(adhan) => Some(prayer(PrayerName::Jumuah, adhan, self.jumuah_iqama, self.jumuah_rakah)?),
+                None => None,
+            },
+            mosque_id: Some(default_mosque_id.to_string()),
+            mosque_name: self.mosque_name.clone(),
+            cached_at: epoch_to_datetime(self.cached_at).ok(),
+            imsak: self.imsak.and_then(|t| epoch_to_datetime(t).ok()),
+            shuruq: self.shuruq.and_then(|t| epoch_to_datetime(t).ok()),
+            asr_method: self.asr_method.as_deref().and_then(|m| m.parse().ok()),
+            provider: self.provider.clone(),
+            freshness: None,
+            warnings: self
+                .warnings
+                .as_deref()
+                .and_then(|json| serde_json::from_str(json).ok())
+                .unwrap_or_default(),
+        })
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct ScheduleChangeRow {
+    mosque_id: String,
+    prayer: String,
+    date: i64,
+    old_iqama: Option<i64>,
+    new_iqama: Option<i64>,
+    detected_at: i64,
+}
+
+impl ScheduleChangeRow {
+    fn to_change(&self) -> anyhow::Result<ScheduleChange> {
+        Ok(ScheduleChange {
+            mosque_id: self.mosque_id.clone(),
+            prayer: self.prayer.parse().map_err(anyhow::Error::msg)?,
+            date: epoch_to_datetime(self.date)?.date_naive(),
+            old_iqama: self.old_iqama.and_then(|t| epoch_to_datetime(t).ok()),
+            new_iqama: self.new_iqama.and_then(|t| epoch_to_datetime(t).ok()),
+            detected_at: epoch_to_datetime(self.detected_at)?,
+        })
+    }
+}
+
+/// Insert or replace one day of prayer times
+/// The query text is constant so sqlx reuses the prepared statement on the connection
+async fn insert_mosque<'e, E>(executor: E, mosque: &Mosque) -> anyhow::Result<()>
+where
+    E: sqlx::Executor<'e, Database = Sqlite>,
+{
+    sqlx::query(
+        r#"
+        INSERT OR REPLACE INTO mosques
+        (id, name, address, city, country, latitude, longitude, is_favorite, last_accessed, facilities)
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
+        "#,
+    )
+    .bind(&mosque.id)
+    .bind(&mosque.name)
+    .bind(&mosque.address)
+    .bind(&mosque.city)
+    .bind(&mosque.country)
+    .bind(mosque.latitude)
+    .bind(mosque.longitude)
+    .bind(mosque.is_favorite as i32)
+    .bind(mosque.last_accessed.map(|d| d.to_rfc3339()))
+    .bind(serde_json::to_string(&mosque.facilities)?)
+    .execute(executor)
+    .await?;
+
+    Ok(())
+}
+
+async fn insert_selected_mosque<'e, E>(executor: E, selected: &SelectedMosque) -> anyhow::Result<()>
+where
+    E: sqlx::Executor<'e, Database = Sqlite>,
+{
+    sqlx::query(
+        r#"
+        INSERT OR REPLACE INTO selected_mosques
+        (profile, mosque_id, selected_at)
+        VALUES (?1, ?2, ?3)
+        "#,
+    )
+    .bind(&selected.profile)
+    .bind(&selected.mosque.id)
+    .bind(selected.selected_at.timestamp())
+    .execute(executor)
+    .await?;
+
+    Ok(())
+}
+
+async fn insert_prayer_times<'e, E>(executor: E, prayer_times: &PrayerTimes) -> anyhow::Result<()>
+where
+    E: sqlx::Executor<'e, Database = Sqlite>,
+{
+    let date = date_to_epoch(prayer_times.date.date_naive());
+    let mosque_id = prayer_times.mosque_id.as_deref().unwrap_or("unknown");
+    let offsets: HashMap<PrayerName, i32> = prayer_times
+        .all_prayers()
+        .into_iter()
+        .chain(prayer_times.jumuah.as_ref())
+        .filter_map(|prayer| Some((prayer.name, prayer.iqama_offset_minutes?)))
+        .collect();
+    let offsets = if offsets.is_empty() { None } else { Some(serde_json::to_string(&offsets)?) };
+    // Stored even when empty: NULL marks days cached before warnings existed
+    let warnings = serde_json::to_string(&prayer_times.warnings)?;
+
+    sqlx::query(
+        r#"
+        INSERT OR REPLACE INTO prayer_times 
+        (mosque_id, date, 
+         fajr_adhan, fajr_iqama, fajr_rakah,
+         dhuhr_adhan, dhuhr_iqama, dhuhr_rakah,
+         asr_adhan, asr_iqama, asr_rakah,
+         maghrib_adhan, maghrib_iqama, maghrib_rakah,
+         isha_adhan, isha_iqama, isha_rakah,
+         jumuah_adhan, jumuah_iqama, jumuah_rakah,
+         mosque_name, cached_at, imsak, shuruq, asr_method, provider, iqama_offsets, warnings)
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22, ?23, ?24, ?25, ?26, ?27, ?28)
+        "#,
+    )
+    .bind(mosque_id)
+    .bind(date)
+    .bind(prayer_times.fajr.adhan.timestamp())
+    .bind(prayer_times.fajr.iqama.map(|d| d.timestamp()))
+    .bind(prayer_times.fajr.custom_rakah_count)
+    .bind(prayer_times.dhuhr.adhan.timestamp())
+    .bind(prayer_times.dhuhr.iqama.map(|d| d.timestamp()))
+    .bind(prayer_times.dhuhr.custom_rakah_count)
+    .bind(prayer_times.asr.adhan.timestamp())
+    .bind(prayer_times.asr.iqama.map(|d| d.timestamp()))
+    .bind(prayer_times.asr.custom_rakah_count)
+    .bind(prayer_times.maghrib.adhan.timestamp())
+    .bind(prayer_times.maghrib.iqama.map(|d| d.timestamp()))
+    .bind(prayer_times.maghrib.custom_rakah_count)
+    .bind(prayer_times.isha.adhan.timestamp())
+    .bind(prayer_times.isha.iqama.map(|d| d.timestamp()))
+    .bind(prayer_times.isha.custom_rakah_count)
+    .bind(prayer_times.jumuah.as_ref().map(|p| p.adhan.timestamp()))
+    .bind(prayer_times.jumuah.as_ref().and_then(|p| p.iqama.map(|d| d.timestamp())))
+    .bind(prayer_times.jumuah.as_ref().and_then(|p| p.custom_rakah_count))
+    .bind(prayer_times.mosque_name.as_deref())
+    .bind(Utc::now().timestamp())
+    .bind(prayer_times.imsak.map(|d| d.timestamp()))
+    .bind(prayer_times.shuruq.map(|d| d.timestamp()))
+    .bind(prayer_times.asr_method.map(|m| m.as_str()))
+    .bind(prayer_times.provider.as_deref())
+    .bind(offsets)
+    .bind(warnings)
+    .execute(executor)
+    .await?;
+
+    Ok(())
+}
+
+async fn insert_schedule_change<'e, E>(executor: E, change: &ScheduleChange) -> anyhow::Result<()>
+where
+    E: sqlx::Executor<'e, Database = Sqlite>,
+{
+    sqlx::query(
+        r#"
+        INSERT INTO schedule_changes (mosque_id, prayer, date, old_iqama, new_iqama, detected_at)
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+        "#,
+    )
+    .bind(&change.mosque_id)
+    .bind(change.prayer.as_str())
+    .bind(date_to_epoch(change.date))
+    .bind(change.old_iqama.map(|d| d.timestamp()))
+    .bind(change.new_iqama.map(|d| d.timestamp()))
+    .bind(change.detected_at.timestamp())
+    .execute(executor)
+    .await?;
+
+    Ok(())
+}
+
+/// Copy every row of the in-memory connection into `disk` in one transaction
+async fn replay_into(memory: &mut sqlx::SqliteConnection, disk: &Pool<Sqlite>) -> anyhow::Result<u64> {
+    let tables: Vec<(String,)> = sqlx::query_as(
+        "SELECT name FROM sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite_%'",
+    )
+    .fetch_all(&mut *memory)
+    .await?;
+
+    let mut tx = disk.begin().await?;
+    let mut replayed = 0;
+
+    for (table,) in tables {
+        let disk_columns: Vec<(String,)> = sqlx::query_as("SELECT name FROM pragma_table_info(?)")
+            .bind(&table)
+            .fetch_all(&mut *tx)
+            .await?;
+        let memory_columns: Vec<(String, String, i64)> =
+            sqlx::query_as("SELECT name, type, pk FROM pragma_table_info(?)")
+                .bind(&table)
+                .fetch_all(&mut *memory)
+                .await?;
+
+        // Name columns explicitly: ALTER-added columns may sit in a different order on disk.
+        // Integer primary keys are rowids, renumbered so they don't overwrite existing rows.
+        let columns: Vec<String> = memory_columns
+            .into_iter()
+            .filter(|(name, ty, pk)| {
+                disk_columns.iter().any(|(disk,)| disk == name)
+                    && !(*pk > 0 && ty.eq_ignore_ascii_case("INTEGER"))
+            })
+            .map(|(name, _, _)| format!("\"{}\"", name))
+            .collect();
+        if columns.is_empty() {
+            continue;
+        }
+
+        // Rows travel as JSON arrays so any column mix binds as a single parameter
+        let rows: Vec<(String,)> = sqlx::query_as(&format!(
+            "SELECT json_array({}) FROM \"{}\"",
+            columns.join(", "),
+            table
+        ))
+        .fetch_all(&mut *memory)
+        .await?;
+
+        let values: Vec<String> = (0..columns.len())
+            .map(|i| format!("json_extract(?1, '$[{}]')", i))
+            .collect();
+        let insert = format!(
+            "INSERT OR REPLACE INTO \"{}\" ({}) VALUES ({})",
+            table,
+            columns.join(", "),
+            values.join(", ")
+        );
+
+        for (row,) in rows {
+            replayed += sqlx::query(&insert)
+                .bind(row)
+                .execute(&mut *tx)
+                .await?
+                .rows_affected();
+        }
+    }
+
+    tx.commit().await?;
+    Ok(replayed)
+}
+
+fn parse_facilities(json: Option<&str>) -> MosqueFacilities {
+    json.and_then(|j| serde_json::from_str(j).ok()).unwrap_or_default()
+}
+
+/// Epoch seconds of midnight UTC for a calendar date (the `prayer_times.date` key)
+fn availability_key(mosque_id: &str) -> String {
+    format!("prayer_availability:{}", mosque_id)
+}
+
+fn date_to_epoch(date: NaiveDate) -> i64 {
+    date.and_hms_opt(0, 0, 0).unwrap().and_utc().timestamp()
+}
+
+fn epoch_to_datetime(secs: i64) -> anyhow::Result<DateTime<Utc>> {
+    DateTime::from_timestamp(secs, 0)
+        .ok_or_else(|| anyhow::anyhow!("Invalid timestamp: {}", secs))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::migrations::create_database;
+    use crate::services::{compress_payload, decompress_payload, failure_fingerprint};
+    use chrono::TimeZone;
+    use chrono::Duration;
+
+    fn prayer(name: PrayerName, adhan: DateTime<Utc>) -> Prayer {
+        Prayer {
+            name,
+            adhan,
+            iqama: Some(adhan + Duration::minutes(10)),
+            custom_rakah_count: None,
+            iqama_offset_minutes: None,
+        }
+    }
+
+    fn schedule(date: NaiveDate) -> PrayerTimes {
+        let midnight = date.and_hms_opt(0, 0, 0).unwrap().and_utc();
+
+        PrayerTimes {
+            date: midnight,
+            fajr: prayer(PrayerName::Fajr, midnight + Duration::hours(5)),
+            dhuhr: prayer(PrayerName::Dhuhr, midnight + Duration::hours(12)),
+            asr: prayer(PrayerName::Asr, midnight + Duration::hours(15)),
+            maghrib: prayer(PrayerName::Maghrib, midnight + Duration::hours(18)),
+            isha: prayer(PrayerName::Isha, midnight + Duration::hours(20)),
+            jumuah: None,
+            mosque_id: Some("test-mosque".to_string()),
+            mosque_name: Some("Test Mosque".to_string()),
+            cached_at: None,
+            imsak: None,
+            shuruq: None,
+            asr_method: None,
+            provider: None,
+            freshness: None,
+            warnings: Vec::new(),
+        }
+    }
+
+    async fn test_db() -> Database {
+        let db = Database::new(create_database(":memory:").await.unwrap());
+        db.save_mosque(&Mosque::new("test-mosque".to_string(), "Test Mosque".to_string()))
+            .await
+            .unwrap();
+        db
+    }
+
+    #[tokio::test]
+    async fn test_iqama_publishing_per_mosque() {
+        let db = test_db().await;
+        let date = NaiveDate::from_ymd_opt(2026, 3, 10).unwrap();
+        db.save_prayer_times(&schedule(date)).await.unwrap();
+
+        db.save_mosque(&Mosque::new("adhan-only".to_string(), "Adhan Only".to_string()))
+            .await
+            .unwrap();
+        let mut adhan_only = schedule(date);
+        adhan_only.mosque_id = Some("adhan-only".to_string());
+        adhan_only.fajr.iqama = None;
+        adhan_only.dhuhr.iqama = None;
+        adhan_only.asr.iqama = None;
+        adhan_only.maghrib.iqama = None;
+        adhan_only.isha.iqama = None;
+        db.save_prayer_times(&adhan_only).await.unwrap();
+        db.save_mosque(&Mosque::new("never-fetched".to_string(), "Never Fetched".to_string()))
+            .await
+            .unwrap();
+
+        let publishing = db.get_mosque_iqama_publishing().await.unwrap();
+        assert_eq!(publishing.get("test-mosque"), Some(&true));
+        assert_eq!(publishing.get("adhan-only"), Some(&false));
+        assert!(!publishing.contains_key("never-fetched"));
+    }
+
+    #[tokio::test]
+    async fn test_prayer_times_roundtrip() {
+        let db = test_db().await;
+        let date = NaiveDate::from_ymd_opt(2026, 2, 5).unwrap();
+        let mut times = schedule(date);
+        times.isha.iqama_offset_minutes = Some(10);
+        times.warnings.push(ScheduleWarning::new(
+            ScheduleWarningKind::DefaultIqama,
+            Some(PrayerName::Fajr),
+            "Iqama missing for Fajr, using default offset (+30 min)",
+        ));
+
+        db.save_prayer_times(&times).await.unwrap();
+        let loaded = db.get_prayer_times("test-mosque", date).await.unwrap().unwrap();
+
+        assert_eq!(loaded.date, times.date);
+        assert_eq!(loaded.fajr.adhan, times.fajr.adhan);
+        assert_eq!(loaded.isha.iqama, times.isha.iqama);
+        assert_eq!(loaded.mosque_name.as_deref(), Some("Test Mosque"));
+        // The mosque's board shows "+10" for Isha and clock times for the rest
+        assert_eq!(loaded.isha.iqama_offset_minutes, Some(10));
+        assert_eq!(loaded.fajr.iqama_offset_minutes, None);
+        assert_eq!(loaded.warnings, times.warnings);
+    }
+
+    #[tokio::test]
+    async fn test_legacy_day_warnings_follow_user_availability() {
+        let db = test_db().await;
+        let date = NaiveDate::from_ymd_opt(2026, 2, 5).unwrap();
+        let mut times = schedule(date);
+        times.dhuhr.iqama = None;
+        db.save_prayer_times(&times).await.unwrap();
+
+        let stored: (Option<String>,) = sqlx::query_as("SELECT warnings FROM prayer_times")
+            .fetch_one(&db.current_pool())
+            .await
+            .unwrap();
+        assert_eq!(stored.0.as_deref(), Some("[]"));
+
+        // A day cached before warnings were stored
+        sqlx::query("UPDATE prayer_times SET warnings = NULL").execute(&db.current_pool()).await.unwrap();
+        let loaded = db.get_prayer_times("test-mosque", date).await.unwrap().unwrap();
+        assert!(loaded.warnings.is_empty());
+
+        let user = PrayerAvailability {
+            closed: false,
+            prayers: HashMap::from([(PrayerName::Dhuhr, true)]),
+        };
+        db.set_prayer_availability("test-mosque", &user).await.unwrap();
+        let loaded = db.get_prayer_times_range("test-mosque", date, date).await.unwrap();
+        assert_eq!(loaded[0].warnings.len(), 1);
+        assert_eq!(loaded[0].warnings[0].kind, ScheduleWarningKind::MissingIqama);
+        assert_eq!(loaded[0].warnings[0].prayer, Some(PrayerName::Dhuhr));
+    }
+
+    #[tokio::test]
+    async fn test_storage_statistics() {
+        let db = test_db().await;
+        db.save_prayer_times(&schedule(NaiveDate::from_ymd_opt(2026, 2, 5).unwrap()))
+            .await
+            .unwrap();
+
+        let tables = db.table_sizes().await.unwrap();
+        let rows = |name: &str| tables.iter().find(|t| t.table == name).map(|t| t.rows);
+        assert_eq!(rows("mosques"), Some(1));
+        assert_eq!(rows("prayer_times"), Some(1));
+        assert!(db.size_bytes().await.unwrap() > 0);
+    }
+
+    #[tokio::test]
+    async fn test_user_facilities_override_provider() {
+        let db = test_db().await;
+        let mut mosque = Mosque::new("test-mosque".to_string(), "Test Mosque".to_string());
+        mosque.facilities.wudu = Some(true);
+        mosque.facilities.parking = Some(true);
+        db.save_mosque(&mosque).await.unwrap();
+
+        let user = MosqueFacilities {
+            parking: Some(false),
+            womens_area: Some(true),
+            ..Default::default()
+        };
+        db.set_user_facilities("test-mosque", &user).await.unwrap();
+
+        // A provider refresh doesn't drop the user's edits
+        db.save_mosque(&mosque).await.unwrap();
+        let facilities = db.get_mosque("test-mosque").await.unwrap().unwrap().facilities;
+
+        assert_eq!(facilities.wudu, Some(true));
+        assert_eq!(facilities.parking, Some(false));
+        assert_eq!(facilities.womens_area, Some(true));
+        assert_eq!(facilities.wheelchair_access, None);
+    }
+
+    #[tokio::test]
+    async fn test_crowd_summary_averages_recent_reports() {
+        let db = test_db().await;
+        let report = |day: u32, level: CrowdLevel| CrowdReport {
+            mosque_id: "test-mosque".to_string(),
+            prayer: PrayerName::Jumuah,
+            date: NaiveDate::from_ymd_opt(2026, 3, day).unwrap(),
+            level,
+            reported_at: Utc::now(),
+        };
+
+        db.save_crowd_report(&report(6, CrowdLevel::Busy)).await.unwrap();
+        db.save_crowd_report(&report(13, CrowdLevel::Moderate)).await.unwrap();
+        // Same day again replaces the earlier report
+        db.save_crowd_report(&report(13, CrowdLevel::Packed)).await.unwrap();
+
+        let summary = db
+            .get_crowd_summary("test-mosque", NaiveDate::from_ymd_opt(2026, 3, 1).unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(summary.len(), 1);
+        assert_eq!(summary[0].report_count, 2);
+        assert_eq!(summary[0].average_level, Some(3.5));
+    }
+
+    #[tokio::test]
+    async fn test_schedule_changes_roundtrip() {
+        let db = test_db().await;
+        let date = NaiveDate::from_ymd_opt(2026, 3, 2).unwrap();
+        let old = schedule(date);
+        let mut fresh = schedule(date);
+        fresh.isha.iqama = fresh.isha.iqama.map(|t| t + Duration::minutes(15));
+
+        let changes = ScheduleChange::detect("test-mosque", &old, &fresh, Utc::now());
+        db.save_schedule_changes(&changes).await.unwrap();
+
+        let stored = db.get_schedule_changes(Some("test-mosque"), 10).await.unwrap();
+        assert_eq!(stored.len(), 1);
+        assert_eq!(stored[0].prayer, PrayerName::Isha);
+        assert_eq!(stored[0].date, date);
+        assert_eq!(stored[0].shift_minutes(), Some(15));
+        assert!(db.get_schedule_changes(Some("other"), 10).await.unwrap().is_empty());
+        assert_eq!(db.get_schedule_changes(None, 10).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_selected_mosque_per_profile() {
+        let db = test_db().await;
+        let mosque = Mosque::new("mosquee-paris".to_string(), "Grande Mosquée de Paris".to_string());
+
+        let selected = SelectedMosque {
+            profile: DEFAULT_PROFILE.to_string(),
+            mosque,
+            selected_at: Utc.with_ymd_and_hms(2026, 3, 2, 12, 0, 0).unwrap(),
+        };
+        db.switch_selected_mosque(&selected).await.unwrap();
+
+        let stored = db.get_selected_mosque(DEFAULT_PROFILE).await.unwrap().unwrap();
+        assert_eq!(stored.mosque.id, "mosquee-paris");
+        assert_eq!(stored.selected_at, selected.selected_at);
+        assert!(db.get_selected_mosque("kids").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_iqama_report_replaces_same_day() {
+        let db = test_db().await;
+        let date = NaiveDate::from_ymd_opt(2026, 3, 2).unwrap();
+        let scheduled = Utc.with_ymd_and_hms(2026, 3, 2, 19, 30, 0).unwrap();
+        let mut report = IqamaReport {
+            mosque_id: "test-mosque".to_string(),
+            prayer: PrayerName::Isha,
+            date,
+            scheduled_iqama: scheduled,
+            actual_iqama: scheduled + chrono::Duration::minutes(3),
+            reported_at: scheduled + chrono::Duration::minutes(20),
+        };
+        db.save_iqama_report(&report).await.unwrap();
+        report.actual_iqama = scheduled + chrono::Duration::minutes(5);
+        db.save_iqama_report(&report).await.unwrap();
+
+        let delays = db.get_iqama_report_delays("test-mosque", date).await.unwrap();
+        assert_eq!(delays, vec![(PrayerName::Isha, 300)]);
+        assert!(db.get_iqama_report_delays("test-mosque", date.succ_opt().unwrap()).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_prayer_log_query_by_tag_and_note() {
+        let db = test_db().await;
+        let date = NaiveDate::from_ymd_opt(2026, 3, 2).unwrap();
+        let entry = |prayer, tags: &[PrayerLogTag], note: Option<&str>| PrayerLogEntry {
+            profile: DEFAULT_PROFILE.to_string(),
+            prayer,
+            date,
+            mosque_id: None,
+            tags: tags.to_vec(),
+            note: note.map(str::to_string),
+            logged_at: Utc.with_ymd_and_hms(2026, 3, 2, 20, 0, 0).unwrap(),
+        };
+        db.save_prayer_log_entry(&entry(PrayerName::Fajr, &[PrayerLogTag::Jamaah], None)).await.unwrap();
+        db.save_prayer_log_entry(&entry(PrayerName::Isha, &[PrayerLogTag::Late], None)).await.unwrap();
+        // Logging again replaces the entry
+        let isha = entry(PrayerName::Isha, &[PrayerLogTag::Late, PrayerLogTag::WithFamily], Some("Rushed"));
+        db.save_prayer_log_entry(&isha).await.unwrap();
+
+        let all = db.get_prayer_log(&PrayerLogQuery::default()).await.unwrap();
+        assert_eq!(all.len(), 2);
+        let with_family = PrayerLogQuery { tag: Some(PrayerLogTag::WithFamily), ..Default::default() };
+        assert_eq!(db.get_prayer_log(&with_family).await.unwrap(), vec![isha]);
+        let noted = PrayerLogQuery { with_note: Some(true), ..Default::default() };
+        assert_eq!(db.get_prayer_log(&noted).await.unwrap().len(), 1);
+        let kids = PrayerLogQuery { profile: Some("kids".to_string()), ..Default::default() };
+        assert!(db.get_prayer_log(&kids).await.unwrap().is_empty());
+
+        assert!(db.delete_prayer_log_entry(DEFAULT_PROFILE, PrayerName::Fajr, date).await.unwrap());
+        assert!(db.get_prayer_log_entry(DEFAULT_PROFILE, PrayerName::Fajr, date).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_goal_weeks_streak() {
+        let db = test_db().await;
+        let mut goal = PrayerGoal {
+            id: 0,
+            profile: DEFAULT_PROFILE.to_string(),
+            prayer: Some(PrayerName::Fajr),
+            tag: Some(PrayerLogTag::Jamaah),
+            target_per_week: 5,
+            created_at: Utc.with_ymd_and_hms(2026, 2, 1, 20, 0, 0).unwrap(),
+        };
+        goal.id = db.save_prayer_goal(&goal).await.unwrap();
+        assert_eq!(db.get_prayer_goals(Some(DEFAULT_PROFILE)).await.unwrap(), vec![goal.clone()]);
+
+        let evaluated_at = Utc.with_ymd_and_hms(2026, 3, 1, 21, 0, 0).unwrap();
+        let week = |d, completed| GoalProgress {
+            goal: goal.clone(),
+            week_start: NaiveDate::from_ymd_opt(2026, 2, d).unwrap(),
+            completed,
+            achieved: completed >= 5,
+            days_left: 0,
+            reachable: completed >= 5,
+            streak_weeks: 0,
+        };
+        // Missed the first week of February, then three good weeks
+        for (day, completed) in [(2, 3), (9, 5), (16, 6), (23, 5)] {
+            db.save_goal_week(&week(day, completed), evaluated_at).await.unwrap();
+        }
+
+        let march = NaiveDate::from_ymd_opt(2026, 3, 2).unwrap();
+        assert_eq!(db.get_goal_streak(goal.id, march).await.unwrap(), 3);
+        // A week never evaluated breaks the streak
+        assert_eq!(db.get_goal_streak(goal.id, march + Duration::days(7)).await.unwrap(), 0);
+
+        assert!(db.delete_prayer_goal(goal.id).await.unwrap());
+        assert!(db.get_prayer_goals(None).await.unwrap().is_empty());
+        assert_eq!(db.get_goal_streak(goal.id, march).await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_travel_times_per_mode() {
+        let db = test_db().await;
+        db.set_travel_time("test-mosque", TravelMode::Walking, Some(1200)).await.unwrap();
+        db.set_travel_time("test-mosque", TravelMode::Driving, Some(300)).await.unwrap();
+
+        assert_eq!(db.get_travel_times("test-mosque").await.unwrap().len(), 2);
+        assert_eq!(db.get_travel_time("test-mosque", Some(TravelMode::Walking)).await.unwrap(), Some(1200));
+        assert_eq!(db.get_travel_time("test-mosque", Some(TravelMode::Transit)).await.unwrap(), None);
+
+        db.set_travel_time("test-mosque", TravelMode::Driving, None).await.unwrap();
+        assert_eq!(db.get_travel_time("test-mosque", None).await.unwrap(), Some(1200));
+    }
+
+    #[tokio::test]
+    async fn test_recent_travel_trips() {
+        let db = test_db().await;
+        let start = Utc::now() - Duration::days(3);
+        for (day, secs) in [600, 660, 720].into_iter().enumerate() {
+            db.save_travel_trip("test-mosque", TravelMode::Walking, start + Duration::days(day as i64), secs)
+                .await
+                .unwrap();
+        }
+        db.save_travel_trip("test-mosque", TravelMode::Driving, start, 240).await.unwrap();
+
+        let trips = db.get_recent_travel_trips("test-mosque", TravelMode::Walking, 2).await.unwrap();
+        assert_eq!(trips, vec![720, 660]);
+    }
+
+    #[tokio::test]
+    async fn test_upstream_issues_count_and_resolve() {
+        let db = test_db().await;
+        let failure = |provider: &str, context: &str| {
+            let now = Utc::now();
+            UpstreamEvent::Failure(UpstreamIssue {
+                provider_id: provider.to_string(),
+                context: context.to_string(),
+                fingerprint: failure_fingerprint(provider, context, "no confData"),
+                message: "no confData".to_string(),
+                degraded: true,
+                occurrences: 1,
+                first_seen: now,
+                last_seen: now,
+            })
+        };
+
+        let failures = [
+            failure("mawaqit", "confData"),
+            failure("mawaqit", "confData"),
+            failure("scraping", "prayer_table"),
+        ];
+        db.save_upstream_events(&failures).await.unwrap();
+        let issues = db.get_upstream_issues().await.unwrap();
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues.iter().find(|i| i.provider_id == "mawaqit").unwrap().occurrences, 2);
+
+        let recovered = UpstreamEvent::Recovered {
+            provider_id: "mawaqit".to_string(),
+            context: "confData".to_string(),
+        };
+        db.save_upstream_events(&[recovered]).await.unwrap();
+        let issues = db.get_upstream_issues().await.unwrap();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].provider_id, "scraping");
+    }
+
+    #[tokio::test]
+    async fn test_raw_payloads_keep_latest_per_mosque() {
+        let db = test_db().await;
+        let start = Utc::now() - Duration::hours(3);
+        let payload = |mosque_id: &str, hours: i64, body: &str| RawPayload {
+            provider_id: "mawaqit".to_string(),
+            mosque_id: mosque_id.to_string(),
+            url: format!("https://mawaqit.net/fr/{}", mosque_id),
+            fetched_at: start + Duration::hours(hours),
+            size_bytes: body.len() as i64,
+            body: compress_payload(body.as_bytes()).unwrap(),
+        };
+
+        db.save_raw_payloads(&[payload("m1", 0, "first"), payload("m1", 1, "second"), payload("m2", 0, "other")], 5)
+            .await
+            .unwrap();
+        db.save_raw_payloads(&[payload("m1", 2, "third")], 2).await.unwrap();
+
+        let infos = db.get_raw_payloads(Some("m1")).await.unwrap();
+        assert_eq!(infos.len(), 2);
+        let (info, body) = db.get_raw_payload(infos[0].id).await.unwrap().unwrap();
+        assert_eq!(info.size_bytes, 5);
+        assert_eq!(decompress_payload(&body).unwrap(), b"third");
+        assert_eq!(db.get_raw_payloads(None).await.unwrap().len(), 3);
+
+        assert_eq!(db.clear_raw_payloads(Some("m1")).await.unwrap(), 2);
+        assert_eq!(db.get_raw_payloads(None).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_events_keep_reminders_across_refresh() {
+        let db = test_db().await;
+        let start = Utc.with_ymd_and_hms(2026, 3, 1, 18, 0, 0).unwrap();
+        let event = |id: &str| MosqueEvent {
+            id: id.to_string(),
+            mosque_id: "test-mosque".to_string(),
+            kind: EventKind::Lecture,
+            title: format!("Lecture {}", id),
+            description: None,
+            location: None,
+            url: None,
+            starts_at: start,
+            ends_at: None,
+            reminder_minutes: None,
+        };
+
+        db.save_events("test-mosque", "ical", &[event("a"), event("b")]).await.unwrap();
+        assert!(db.set_event_reminder("a", Some(30)).await.unwrap());
+
+        db.save_events("test-mosque", "ical", &[event("a")]).await.unwrap();
+        let events = db.get_events("test-mosque", start).await.unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].reminder_minutes, Some(30));
+
+        // Due from 30 minutes before, and still due after a missed tick until the event starts
+        let now = start - Duration::minutes(31);
+        assert!(db.get_pending_event_reminders(now, now + Duration::minutes(1)).await.unwrap().is_empty());
+        let now = start - Duration::minutes(10);
+        let due = db.get_pending_event_reminders(now, now).await.unwrap();
+        assert_eq!(due.len(), 1);
+
+        db.mark_event_reminded("a", now).await.unwrap();
+        assert!(db.get_pending_event_reminders(now, now).await.unwrap().is_empty());
+
+        // A refresh keeps the marker, a moved event is reminded about again
+        db.save_events("test-mosque", "ical", &[event("a")]).await.unwrap();
+        assert!(db.get_pending_event_reminders(now, now).await.unwrap().is_empty());
+        let mut moved = event("a");
+        moved.starts_at = start + Duration::minutes(5);
+        db.save_events("test-mosque", "ical", &[moved]).await.unwrap();
+        assert_eq!(db.get_pending_event_reminders(now, now).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_prayer_times_bulk_insert() {
+        let db = test_db().await;
+        let start = NaiveDate::from_ymd_opt(2026, 3, 1).unwrap();
+        let month: Vec<PrayerTimes> = (0..31).map(|day| schedule(start + Duration::days(day))).collect();
+
+        db.save_prayer_times_bulk(&month).await.unwrap();
+        let loaded = db
+            .get_prayer_times_range("test-mosque", start, start + Duration::days(30))
+            .await
+            .unwrap();
+
+        assert_eq!(loaded.len(), 31);
+        assert_eq!(loaded[30].date, month[30].date);
+    }
+
+    #[tokio::test]
+    async fn test_mosque_notification_overrides() {
+        let db = test_db().await;
+        let mut home = MosqueNotificationOverride::muted("home");
+        home.enabled = true;
+        home.prayers = Some(vec![PrayerName::Fajr, PrayerName::Isha]);
+        home.reminder_minutes_before = Some(vec![15, 5]);
+
+        db.set_mosque_notification_override(&home).await.unwrap();
+        db.set_mosque_notification_override(&MosqueNotificationOverride::muted("work")).await.unwrap();
+        assert_eq!(db.get_mosque_notification_override("home").await.unwrap(), Some(home));
+        assert_eq!(db.get_mosque_notification_overrides().await.unwrap().len(), 2);
+
+        assert!(db.delete_mosque_notification_override("work").await.unwrap());
+        assert!(!db.delete_mosque_notification_override("work").await.unwrap());
+        assert_eq!(db.get_mosque_notification_override("work").await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_journaled_schedule_refresh() {
+        let db = test_db().await;
+        let start = NaiveDate::from_ymd_opt(2026, 3, 1).unwrap();
+        let write = PendingWrite::RefreshSchedule {
+            mosque_id: "test-mosque".to_string(),
+            from: start,
+            to: start + Duration::days(30),
+        };
+
+        // Interrupted before saving: the entry survives for the next launch
+        let interrupted = db.begin_write(&write).await.unwrap();
+        let completed = db.begin_write(&write).await.unwrap();
+        let month: Vec<PrayerTimes> = (0..31).map(|day| schedule(start + Duration::days(day))).collect();
+        let mut fresh = schedule(start);
+        fresh.isha.iqama = fresh.isha.iqama.map(|t| t + Duration::minutes(5));
+        let changes = ScheduleChange::detect("test-mosque", &month[0], &fresh, Utc::now());
+        db.save_schedule_refresh(&month, &changes, Some(completed)).await.unwrap();
+
+        let pending = db.pending_writes().await.unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].id, interrupted);
+        assert_eq!(pending[0].write, write);
+        assert_eq!(
+            db.get_prayer_times_range("test-mosque", start, start + Duration::days(30)).await.unwrap().len(),
+            31
+        );
+        assert_eq!(db.get_schedule_changes(Some("test-mosque"), 10).await.unwrap().len(), 1);
+
+        db.discard_write(interrupted).await.unwrap();
+        assert!(db.pending_writes().await.unwrap().is_empty());
+    }
+
+    /// Times the monthly range query; run with `cargo test -- --ignored --nocapture`
+    #[tokio::test]
+    #[ignore]
+    async fn bench_monthly_range_query() {
+        let db = test_db().await;
+        let start = NaiveDate::from_ymd_opt(2026, 1, 1).unwrap();
+
+        let year: Vec<PrayerTimes> = (0..365).map(|day| schedule(start + Duration::days(day))).collect();
+        db.save_prayer_times_bulk(&year).await.unwrap();
+
+        let month_start = NaiveDate::from_ymd_opt(2026, 3, 1).unwrap();
+        let month_end = NaiveDate::from_ymd_opt(2026, 3, 31).unwrap();
+        let iterations = 200;
+        let started = std::time::Instant::now();
+
+        for _ in 0..iterations {
+            let month = db
+                .get_prayer_times_range("test-mosque", month_start, month_end)
+                .await
+                .unwrap();
+            assert_eq!(month.len(), 31);
+        }
+
+        println!(
+            "monthly range query: {:?} per query",
+            started.elapsed() / iterations
+        );
+    }
+
+    #[tokio::test]
+    async fn test_recover_in_memory_writes_to_disk() {
+        let path = std::env::temp_dir().join(format!("iqamah-recover-{}.db", std::process::id()));
+        let path = path.to_string_lossy().to_string();
+        let _ = std::fs::remove_file(&path);
+
+        let disk = Database::new(create_database(&path).await.unwrap());
+        disk.set_setting("language", "ar").await.unwrap();
+        disk.set_setting("time_format", "24h").await.unwrap();
+        disk.current_pool().close().await;
+
+        let db = Database::in_memory_fallback(create_database(":memory:").await.unwrap());
+        db.set_setting("time_format", "12h").await.unwrap();
+        db.save_mosque(&Mosque::new("test-mosque".to_string(), "Test Mosque".to_string()))
+            .await
+            .unwrap();
+        db.save_prayer_times(&schedule(NaiveDate::from_ymd_opt(2026, 2, 5).unwrap()))
+            .await
+            .unwrap();
+
+        assert!(db.is_in_memory());
+        assert_eq!(db.recover_to(&path).await.unwrap(), 3);
+        assert!(!db.is_in_memory());
+
+        assert_eq!(db.get_setting("language").await.unwrap().as_deref(), Some("ar"));
+        assert_eq!(db.get_setting("time_format").await.unwrap().as_deref(), Some("12h"));
+        assert!(db
+            .get_prayer_times("test-mosque", NaiveDate::from_ymd_opt(2026, 2, 5).unwrap())
+            .await
+            .unwrap()
+            .is_some());
+
+        db.current_pool().close().await;
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn test_recovery_waits_for_journaled_writes() {
+        let path = std::env::temp_dir().join(format!("iqamah-recover-journal-{}.db", std::process::id()));
+        let path = path.to_string_lossy().to_string();
+        let _ = std::fs::remove_file(&path);
+
+        let db = Database::in_memory_fallback(create_database(":memory:").await.unwrap());
+        db.save_mosque(&Mosque::new("test-mosque".to_string(), "Test Mosque".to_string()))
+            .await
+            .unwrap();
+        let write = PendingWrite::RefreshSchedule {
+            mosque_id: "test-mosque".to_string(),
+            from: NaiveDate::from_ymd_opt(2026, 2, 5).unwrap(),
+            to: NaiveDate::from_ymd_opt(2026, 2, 6).unwrap(),
+        };
+        let id = db.begin_write(&write).await.unwrap();
+
+        let recovery = tokio::spawn({
+            let db = db.clone();
+            let path = path.clone();
+            async move { db.recover_to(&path).await }
+        });
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        assert!(db.is_in_memory());
+
+        db.save_schedule_refresh(&[schedule(NaiveDate::from_ymd_opt(2026, 2, 5).unwrap())], &[], Some(id))
+            .await
+            .unwrap();
+        recovery.await.unwrap().unwrap();
+
+        assert!(!db.is_in_memory());
+        assert!(db.pending_writes().await.unwrap().is_empty());
+        assert!(db
+            .get_prayer_times("test-mosque", NaiveDate::from_ymd_opt(2026, 2, 5).unwrap())
+            .await
+            .unwrap()
+            .is_some());
+
+        db.current_pool().close().await;
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn test_move_database_keeps_data() {
+        let dir = std::env::temp_dir().join(format!("iqamah-move-{}", std::process::id()));
+        let from = dir.join("from.db").to_string_lossy().to_string();
+        let to = dir.join("synced").join("iqamah.db").to_string_lossy().to_string();
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let db = Database::new(create_database(&from).await.unwrap());
+        db.set_setting("language", "fr").await.unwrap();
+
+        // Written while the copy is under way, so it must still land in the new file
+        let (moved, written) = tokio::join!(db.move_to(&to), db.set_setting("theme", "dark"));
+        moved.unwrap();
+        written.unwrap();
+        db.set_setting("time_format", "24h").await.unwrap();
+        assert!(db.move_to(&to).await.is_err());
+
+        let reopened = Database::new(create_database(&to).await.unwrap());
+        assert_eq!(reopened.get_setting("language").await.unwrap().as_deref(), Some("fr"));
+        assert_eq!(reopened.get_setting("theme").await.unwrap().as_deref(), Some("dark"));
+        assert_eq!(reopened.get_setting("time_format").await.unwrap().as_deref(), Some("24h"));
+
+        reopened.current_pool().close().await;
+        db.current_pool().close().await;
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,135 @@
+use std::collections::HashSet;
+use std::path::Path;
+
+use chrono::Local;
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+use sqlx::{Pool, Sqlite};
+
+use super::database::PrayerLogRow;
+use crate::models::*;
+use crate::services::parse_flutter_prayer_times;
+
+/// Mosque columns both the Flutter app and older releases had, with the Flutter favorite details
+type LegacyMosqueRow = (
+    String,
+    String,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+    Option<f64>,
+    Option<f64>,
+    Option<i64>,
+    Option<i64>,
+);
+
+/// Read favorites and history from another `iqamah.db`, leaving the file untouched
+/// Handles the Flutter app's schema (schedules stored as JSON) and older releases of this one
+pub async fn read_legacy_database(path: &Path) -> anyhow::Result<LegacyData> {
+    let options = SqliteConnectOptions::new().filename(path).read_only(true);
+    let pool = SqlitePoolOptions::new().max_connections(1).connect_with(options).await?;
+
+    let result = read_tables(&pool).await;
+    pool.close().await;
+    result
+}
+
+async fn read_tables(pool: &Pool<Sqlite>) -> anyhow::Result<LegacyData> {
+    let tables: HashSet<String> = sqlx::query_scalar("SELECT name FROM sqlite_master WHERE type = 'table'")
+        .fetch_all(pool)
+        .await?
+        .into_iter()
+        .collect();
+    if !tables.contains("mosques") {
+        anyhow::bail!("Not an Iqamah database: no mosques table");
+    }
+
+    let mut data = LegacyData::new(LegacySource::IkamaDatabase);
+    // Only the Flutter app had a favorites table
+    let flutter = tables.contains("favorites");
+
+    let rows: Vec<LegacyMosqueRow> = if flutter {
+        sqlx::query_as(
+            r#"
+            SELECT m.id, m.name, m.address, m.city, m.country, m.latitude, m.longitude,
+                   f.is_active, f.travel_time_seconds
+            FROM mosques m LEFT JOIN favorites f ON f.mosque_id = m.id
+            WHERE m.is_favorite = 1 OR f.mosque_id IS NOT NULL
+            "#,
+        )
+        .fetch_all(pool)
+        .await?
+    } else {
+        sqlx::query_as(
+            r#"
+            SELECT id, name, address, city, country, latitude, longitude, NULL, NULL
+            FROM mosques WHERE is_favorite = 1
+            "#,
+        )
+        .fetch_all(pool)
+        .await?
+    };
+
+    let active_id: Option<String> = if tables.contains("selected_mosques") {
+        sqlx::query_scalar("SELECT mosque_id FROM selected_mosques WHERE profile = ?1")
+            .bind(DEFAULT_PROFILE)
+            .fetch_optional(pool)
+            .await?
+    } else {
+        None
+    };
+
+    for (id, name, address, city, country, latitude, longitude, is_active, travel_time_secs) in rows {
+        let mut mosque = Mosque::new(id, name);
+        mosque.address = address;
+        mosque.city = city;
+        mosque.country = country;
+        mosque.latitude = latitude;
+        mosque.longitude = longitude;
+        mosque.is_favorite = true;
+
+        data.favorites.push(LegacyFavorite {
+            active: is_active == Some(1) || active_id.as_deref() == Some(mosque.id.as_str()),
+            // The Flutter app stored 0 for "not set"
+            travel_time_secs: travel_time_secs.filter(|secs| *secs > 0),
+            mosque,
+        });
+    }
+
+    if flutter && tables.contains("prayer_times") {
+        let favorite_ids: HashSet<&str> = data.favorites.iter().map(|f| f.mosque.id.as_str()).collect();
+        let rows: Vec<(String, String)> = sqlx::query_as("SELECT mosque_id, data FROM prayer_times")
+            .fetch_all(pool)
+            .await?;
+
+        let mut schedules = Vec::new();
+        for (mosque_id, json) in rows.iter().filter(|(id, _)| favorite_ids.contains(id.as_str())) {
+            // The Flutter app wrote times in the device's timezone without an offset
+            match parse_flutter_prayer_times(json, mosque_id, &Local) {
+                Ok(times) => schedules.push(times),
+                Err(e) => {
+                    log::debug!("Skipping unreadable legacy schedule of {}: {}", mosque_id, e);
+                    data.skipped += 1;
+                }
+            }
+        }
+        data.schedules = schedules;
+    }
+
+    // Schedules of older releases are a cache in an outdated layout: refetched rather than read
+    if tables.contains("prayer_log") {
+        let rows = sqlx::query_as::<_, PrayerLogRow>(
+            "SELECT profile, prayer, date, mosque_id, tags, note, logged_at FROM prayer_log",
+        )
+        .fetch_all(pool)
+        .await?;
+
+        for row in rows {
+            match row.into_entry() {
+                Ok(entry) => data.log_entries.push(entry),
+                Err(_) => data.skipped += 1,
+            }
+        }
+    }
+
+    Ok(data)
+}
@@ -0,0 +1,151 @@
+use std::path::{Path, PathBuf};
+
+use once_cell::sync::Lazy;
+
+/// File in the app directory remembering a relocated database
+const LOCATION_FILE: &str = "database_location";
+const DATABASE_FILE: &str = "iqamah.db";
+
+/// Empty file next to the executable that turns on portable mode
+pub const PORTABLE_MARKER: &str = "portable";
+/// Launch argument that turns on portable mode without the marker file
+pub const PORTABLE_ARG: &str = "--portable";
+/// Folder next to the executable holding all data in portable mode
+const PORTABLE_DATA_DIR: &str = "iqamah-data";
+
+// Decided once per launch so every path agrees for the whole session
+static PORTABLE_DIR: Lazy<Option<PathBuf>> = Lazy::new(|| {
+    let exe = std::env::current_exe().ok()?;
+    portable_dir(exe.parent()?, std::env::args().any(|arg| arg == PORTABLE_ARG))
+});
+
+fn portable_dir(exe_dir: &Path, flagged: bool) -> Option<PathBuf> {
+    (flagged || exe_dir.join(PORTABLE_MARKER).is_file()).then(|| exe_dir.join(PORTABLE_DATA_DIR))
+}
+
+/// Whether data lives next to the executable, e.g. when run from a USB stick
+pub fn is_portable() -> bool {
+    PORTABLE_DIR.is_some()
+}
+
+/// App data directory: holds the default database, logs, the location pointer and `config.toml`
+/// Next to the executable in portable mode, the user's data directory otherwise
+pub fn app_dir() -> Option<PathBuf> {
+    match PORTABLE_DIR.as_ref() {
+        Some(dir) => Some(dir.clone()),
+        None => dirs::data_dir().map(|dir| dir.join("iqamah")),
+    }
+}
+
+/// Folder of the daily log files
+pub fn log_dir() -> Option<PathBuf> {
+    app_dir().map(|dir| dir.join("logs"))
+}
+
+/// Cache directory, e.g. for provider responses; next to the executable in portable mode too,
+/// so a portable copy leaves nothing behind on the host
+pub fn cache_dir() -> Option<PathBuf> {
+    match PORTABLE_DIR.as_ref() {
+        Some(dir) => Some(dir.join("cache")),
+        None => dirs::cache_dir().map(|dir| dir.join("iqamah")),
+    }
+}
+
+/// Where the database lives: the relocated path if one was saved, the app directory otherwise
+pub fn database_path() -> Option<PathBuf> {
+    let app_dir = app_dir()?;
+    let saved = std::fs::read_to_string(app_dir.join(LOCATION_FILE))
+        .ok()
+        .and_then(|saved| resolve_saved(&app_dir, &saved));
+
+    Some(saved.unwrap_or_else(|| app_dir.join(DATABASE_FILE)))
+}
+
+/// Relative locations are kept relative to the app directory, so a portable copy
+/// still finds its database when the stick mounts under another drive letter
+fn resolve_saved(app_dir: &Path, saved: &str) -> Option<PathBuf> {
+    let path = PathBuf::from(saved.trim());
+    if path.as_os_str().is_empty() {
+        None
+    } else if path.is_relative() {
+        Some(app_dir.join(path))
+    } else {
+        Some(path)
+    }
+}
+
+/// Remember `path` as the database location for future launches
+/// In portable mode a location inside the app directory is saved relative to it
+pub fn save_database_path(path: &Path) -> anyhow::Result<()> {
+    let app_dir = app_dir().ok_or_else(|| anyhow::anyhow!("Could not find data directory"))?;
+    std::fs::create_dir_all(&app_dir)?;
+    let saved = match path.strip_prefix(&app_dir) {
+        Ok(relative) if is_portable() => relative,
+        _ => path,
+    };
+    std::fs::write(app_dir.join(LOCATION_FILE), saved.to_string_lossy().as_bytes())?;
+    Ok(())
+}
+
+/// Database file for a user-chosen location: a `.db` path as is, anything else as a folder
+pub fn resolve_target(location: &str) -> PathBuf {
+    let path = PathBuf::from(location);
+    if path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("db")) {
+        path
+    } else {
+        path.join(DATABASE_FILE)
+    }
+}
+
+/// Data folders of earlier installs: Tauri's per-app folder used by the first releases, then the `ikama` folder
+const LEGACY_APP_DIRS: [&str; 2] = ["com.iqamah.app", "ikama"];
+
+/// Databases left by earlier installs that `migrate_legacy_data` can import, excluding the current one
+pub fn legacy_database_paths() -> Vec<PathBuf> {
+    let Some(data_dir) = dirs::data_dir() else {
+        return Vec::new();
+    };
+    let current = database_path();
+
+    LEGACY_APP_DIRS
+        .iter()
+        .map(|dir| data_dir.join(dir).join(DATABASE_FILE))
+        .filter(|path| path.is_file() && Some(path) != current.as_ref())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_target() {
+        assert_eq!(resolve_target("/sync/prayers"), PathBuf::from("/sync/prayers/iqamah.db"));
+        assert_eq!(resolve_target("/sync/mine.db"), PathBuf::from("/sync/mine.db"));
+    }
+
+    #[test]
+    fn test_portable_dir() {
+        let exe_dir = std::env::temp_dir().join("iqamah-portable-test");
+        std::fs::create_dir_all(&exe_dir).unwrap();
+        let _ = std::fs::remove_file(exe_dir.join(PORTABLE_MARKER));
+
+        assert_eq!(portable_dir(&exe_dir, false), None);
+        assert_eq!(portable_dir(&exe_dir, true), Some(exe_dir.join(PORTABLE_DATA_DIR)));
+
+        std::fs::write(exe_dir.join(PORTABLE_MARKER), "").unwrap();
+        assert_eq!(portable_dir(&exe_dir, false), Some(exe_dir.join(PORTABLE_DATA_DIR)));
+        std::fs::remove_dir_all(&exe_dir).unwrap();
+    }
+
+    #[test]
+    fn test_resolve_saved_location() {
+        let app_dir = PathBuf::from("/media/stick/iqamah-data");
+        assert_eq!(
+            resolve_saved(&app_dir, "backup/iqamah.db\n"),
+            Some(PathBuf::from("/media/stick/iqamah-data/backup/iqamah.db"))
+        );
+        assert_eq!(resolve_saved(&app_dir, "/sync/mine.db"), Some(PathBuf::from("/sync/mine.db")));
+        assert_eq!(resolve_saved(&app_dir, "  "), None);
+    }
+}
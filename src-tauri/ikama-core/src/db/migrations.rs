@@ -0,0 +1,576 @@
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+use sqlx::{Pool, Sqlite, SqlitePool};
+use std::path::Path;
+use std::str::FromStr;
+
+/// Prayer times are stored as epoch seconds (UTC); `date` is the epoch of midnight UTC of the day
+const PRAYER_TIMES_SCHEMA: &str = r#"
+    CREATE TABLE IF NOT EXISTS prayer_times (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        mosque_id TEXT NOT NULL,
+        date INTEGER NOT NULL,
+        fajr_adhan INTEGER NOT NULL,
+        fajr_iqama INTEGER,
+        fajr_rakah INTEGER,
+        dhuhr_adhan INTEGER NOT NULL,
+        dhuhr_iqama INTEGER,
+        dhuhr_rakah INTEGER,
+        asr_adhan INTEGER NOT NULL,
+        asr_iqama INTEGER,
+        asr_rakah INTEGER,
+        maghrib_adhan INTEGER NOT NULL,
+        maghrib_iqama INTEGER,
+        maghrib_rakah INTEGER,
+        isha_adhan INTEGER NOT NULL,
+        isha_iqama INTEGER,
+        isha_rakah INTEGER,
+        jumuah_adhan INTEGER,
+        jumuah_iqama INTEGER,
+        jumuah_rakah INTEGER,
+        mosque_name TEXT,
+        cached_at INTEGER NOT NULL DEFAULT (strftime('%s', 'now')),
+        FOREIGN KEY (mosque_id) REFERENCES mosques(id),
+        UNIQUE(mosque_id, date)
+    )
+"#;
+
+pub async fn run_migrations(pool: &Pool<Sqlite>) -> anyhow::Result<()> {
+    // Create tables if they don't exist
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS mosques (
+            id TEXT PRIMARY KEY,
+            name TEXT NOT NULL,
+            address TEXT,
+            city TEXT,
+            country TEXT,
+            latitude REAL,
+            longitude REAL,
+            is_favorite INTEGER DEFAULT 0,
+            last_accessed TEXT,
+            created_at TEXT DEFAULT CURRENT_TIMESTAMP
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    sqlx::query(PRAYER_TIMES_SCHEMA).execute(pool).await?;
+
+    // Migration: Add mosque_name column if it doesn't exist
+    sqlx::query(
+        r#"
+        ALTER TABLE prayer_times ADD COLUMN mosque_name TEXT
+        "#,
+    )
+    .execute(pool)
+    .await.ok(); // Ignore error if column already exists
+
+    migrate_prayer_times_to_epoch(pool).await?;
+
+    // Migration: provider-supplied imsak time
+    sqlx::query(
+        r#"
+        ALTER TABLE prayer_times ADD COLUMN imsak INTEGER
+        "#,
+    )
+    .execute(pool)
+    .await.ok(); // Ignore error if column already exists
+
+    // Migration: provider-supplied sunrise
+    sqlx::query(
+        r#"
+        ALTER TABLE prayer_times ADD COLUMN shuruq INTEGER
+        "#,
+    )
+    .execute(pool)
+    .await.ok(); // Ignore error if column already exists
+
+    // Migration: Asr convention of calculated schedules
+    sqlx::query(
+        r#"
+        ALTER TABLE prayer_times ADD COLUMN asr_method TEXT
+        "#,
+    )
+    .execute(pool)
+    .await.ok(); // Ignore error if column already exists
+
+    // Migration: provider each cached day was fetched from
+    sqlx::query(
+        r#"
+        ALTER TABLE prayer_times ADD COLUMN provider TEXT
+        "#,
+    )
+    .execute(pool)
+    .await.ok(); // Ignore error if column already exists
+
+    // Migration: iqamas the mosque publishes as minutes after the adhan, as JSON
+    sqlx::query(
+        r#"
+        ALTER TABLE prayer_times ADD COLUMN iqama_offsets TEXT
+        "#,
+    )
+    .execute(pool)
+    .await.ok(); // Ignore error if column already exists
+
+    // Migration: data quality warnings raised when the day was fetched, as JSON
+    sqlx::query(
+        r#"
+        ALTER TABLE prayer_times ADD COLUMN warnings TEXT
+        "#,
+    )
+    .execute(pool)
+    .await.ok(); // Ignore error if column already exists
+
+    // Migration: facilities reported by the provider, as JSON
+    sqlx::query(
+        r#"
+        ALTER TABLE mosques ADD COLUMN facilities TEXT
+        "#,
+    )
+    .execute(pool)
+    .await.ok(); // Ignore error if column already exists
+
+    // Facilities entered by the user, kept apart so provider refreshes don't overwrite them
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS mosque_facilities (
+            mosque_id TEXT PRIMARY KEY,
+            facilities TEXT NOT NULL,
+            updated_at TEXT DEFAULT CURRENT_TIMESTAMP
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS settings (
+            key TEXT PRIMARY KEY,
+            value TEXT NOT NULL,
+            updated_at TEXT DEFAULT CURRENT_TIMESTAMP
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS provider_configs (
+            provider_id TEXT PRIMARY KEY,
+            settings TEXT NOT NULL,
+            is_active INTEGER DEFAULT 0,
+            updated_at TEXT DEFAULT CURRENT_TIMESTAMP
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS mosque_catalog (
+            country TEXT PRIMARY KEY,
+            data BLOB NOT NULL,
+            mosque_count INTEGER NOT NULL,
+            fetched_at TEXT NOT NULL
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS events (
+            id TEXT PRIMARY KEY,
+            mosque_id TEXT NOT NULL,
+            source TEXT NOT NULL,
+            kind TEXT NOT NULL,
+            title TEXT NOT NULL,
+            description TEXT,
+            location TEXT,
+            url TEXT,
+            starts_at INTEGER NOT NULL,
+            ends_at INTEGER,
+            reminder_minutes INTEGER,
+            fetched_at INTEGER NOT NULL
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Migration: when an event's reminder was shown, so it is delivered once even after sleep
+    sqlx::query(
+        r#"
+        ALTER TABLE events ADD COLUMN reminded_at INTEGER
+        "#,
+    )
+    .execute(pool)
+    .await.ok(); // Ignore error if column already exists
+
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS crowd_reports (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            mosque_id TEXT NOT NULL,
+            prayer TEXT NOT NULL,
+            date INTEGER NOT NULL,
+            level INTEGER NOT NULL,
+            reported_at INTEGER NOT NULL,
+            UNIQUE(mosque_id, prayer, date)
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // When congregations actually started, as reported by the user
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS iqama_reports (
+            mosque_id TEXT NOT NULL,
+            prayer TEXT NOT NULL,
+            date INTEGER NOT NULL,
+            scheduled_iqama INTEGER NOT NULL,
+            actual_iqama INTEGER NOT NULL,
+            reported_at INTEGER NOT NULL,
+            PRIMARY KEY (mosque_id, prayer, date)
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Iqama changes found when refreshing cached schedules
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS schedule_changes (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            mosque_id TEXT NOT NULL,
+            prayer TEXT NOT NULL,
+            date INTEGER NOT NULL,
+            old_iqama INTEGER,
+            new_iqama INTEGER,
+            detected_at INTEGER NOT NULL
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Parse failures reported by providers, one row per distinct failure
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS upstream_issues (
+            provider_id TEXT NOT NULL,
+            context TEXT NOT NULL,
+            fingerprint TEXT NOT NULL,
+            message TEXT NOT NULL,
+            degraded INTEGER NOT NULL,
+            occurrences INTEGER NOT NULL,
+            first_seen INTEGER NOT NULL,
+            last_seen INTEGER NOT NULL,
+            PRIMARY KEY (provider_id, fingerprint)
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Mosque each profile follows, replacing the `selected_mosque_id` setting
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS selected_mosques (
+            profile TEXT PRIMARY KEY,
+            mosque_id TEXT NOT NULL REFERENCES mosques(id),
+            travel_time_secs INTEGER,
+            selected_at INTEGER NOT NULL
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Migration: move the legacy setting into the default profile's selection
+    sqlx::query(
+        r#"
+        INSERT OR IGNORE INTO selected_mosques (profile, mosque_id, selected_at)
+        SELECT 'default', value, CAST(strftime('%s', 'now') AS INTEGER) FROM settings
+        WHERE key = 'selected_mosque_id' AND value IN (SELECT id FROM mosques)
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    sqlx::query("DELETE FROM settings WHERE key = 'selected_mosque_id'")
+        .execute(pool)
+        .await?;
+
+    // Travel time from home to favorite mosques, per travel mode
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS travel_times (
+            mosque_id TEXT NOT NULL,
+            mode TEXT NOT NULL,
+            travel_time_seconds INTEGER NOT NULL,
+            updated_at INTEGER NOT NULL,
+            PRIMARY KEY (mosque_id, mode)
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Migration: travel times now live only in travel_times; the selection's column is left unused
+    sqlx::query(
+        r#"
+        INSERT OR IGNORE INTO travel_times (mosque_id, mode, travel_time_seconds, updated_at)
+        SELECT mosque_id, 'walking', travel_time_secs, selected_at FROM selected_mosques
+        WHERE travel_time_secs IS NOT NULL
+          AND mosque_id NOT IN (SELECT mosque_id FROM travel_times)
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    sqlx::query("UPDATE selected_mosques SET travel_time_secs = NULL")
+        .execute(pool)
+        .await?;
+
+    // Prayers the user logged per profile, with tags and reflections
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS prayer_log (
+            profile TEXT NOT NULL,
+            prayer TEXT NOT NULL,
+            date INTEGER NOT NULL,
+            mosque_id TEXT,
+            tags TEXT NOT NULL DEFAULT '',
+            note TEXT,
+            logged_at INTEGER NOT NULL,
+            PRIMARY KEY (profile, prayer, date)
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Weekly prayer goals, and how each week went when evaluated at night
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS prayer_goals (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            profile TEXT NOT NULL,
+            prayer TEXT,
+            tag TEXT,
+            target_per_week INTEGER NOT NULL,
+            created_at INTEGER NOT NULL
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS goal_weeks (
+            goal_id INTEGER NOT NULL,
+            week_start INTEGER NOT NULL,
+            completed INTEGER NOT NULL,
+            achieved INTEGER NOT NULL,
+            evaluated_at INTEGER NOT NULL,
+            PRIMARY KEY (goal_id, week_start)
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Multi-step writes in progress, cleared by the transaction that completes them
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS write_journal (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            kind TEXT NOT NULL,
+            payload TEXT NOT NULL,
+            started_at INTEGER NOT NULL
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Per-mosque reminder settings layered on the notification profile
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS mosque_notification_overrides (
+            mosque_id TEXT PRIMARY KEY,
+            enabled INTEGER NOT NULL,
+            sound INTEGER NOT NULL,
+            speech INTEGER NOT NULL,
+            prayers TEXT,
+            reminder_minutes TEXT,
+            updated_at INTEGER NOT NULL
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Trips timed by "on my way" sessions, learned from for travel predictions
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS travel_trips (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            mosque_id TEXT NOT NULL,
+            mode TEXT NOT NULL,
+            started_at INTEGER NOT NULL,
+            travel_time_seconds INTEGER NOT NULL
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Provider responses as fetched, kept on request so support can see what a provider returned
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS raw_payloads (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            mosque_id TEXT NOT NULL,
+            provider_id TEXT NOT NULL,
+            url TEXT NOT NULL,
+            fetched_at INTEGER NOT NULL,
+            size_bytes INTEGER NOT NULL,
+            body BLOB NOT NULL
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Create indexes
+    sqlx::query(
+        r#"
+        CREATE INDEX IF NOT EXISTS idx_mosques_favorite ON mosques(is_favorite)
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    sqlx::query(
+        r#"
+        CREATE INDEX IF NOT EXISTS idx_prayer_times_mosque_date ON prayer_times(mosque_id, date)
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    sqlx::query(
+        r#"
+        CREATE INDEX IF NOT EXISTS idx_events_mosque_start ON events(mosque_id, starts_at)
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    sqlx::query(
+        r#"
+        CREATE INDEX IF NOT EXISTS idx_raw_payloads_mosque ON raw_payloads(mosque_id, fetched_at)
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Convert a legacy `prayer_times` table (RFC3339 TEXT columns) to epoch-second integers
+async fn migrate_prayer_times_to_epoch(pool: &Pool<Sqlite>) -> anyhow::Result<()> {
+    let date_type: Option<(String,)> = sqlx::query_as(
+        r#"
+        SELECT type FROM pragma_table_info('prayer_times') WHERE name = 'date'
+        "#,
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    if !matches!(date_type, Some((ref t,)) if t.eq_ignore_ascii_case("TEXT")) {
+        return Ok(());
+    }
+
+    log::info!("Migrating prayer_times to integer timestamps");
+
+    let mut tx = pool.begin().await?;
+
+    sqlx::query("ALTER TABLE prayer_times RENAME TO prayer_times_legacy")
+        .execute(&mut *tx)
+        .await?;
+
+    sqlx::query(PRAYER_TIMES_SCHEMA).execute(&mut *tx).await?;
+
+    sqlx::query(
+        r#"
+        INSERT OR REPLACE INTO prayer_times
+        (mosque_id, date,
+         fajr_adhan, fajr_iqama, fajr_rakah,
+         dhuhr_adhan, dhuhr_iqama, dhuhr_rakah,
+         asr_adhan, asr_iqama, asr_rakah,
+         maghrib_adhan, maghrib_iqama, maghrib_rakah,
+         isha_adhan, isha_iqama, isha_rakah,
+         jumuah_adhan, jumuah_iqama, jumuah_rakah,
+         mosque_name, cached_at)
+        SELECT mosque_id, CAST(strftime('%s', date) AS INTEGER),
+         CAST(strftime('%s', fajr_adhan) AS INTEGER), CAST(strftime('%s', fajr_iqama) AS INTEGER), fajr_rakah,
+         CAST(strftime('%s', dhuhr_adhan) AS INTEGER), CAST(strftime('%s', dhuhr_iqama) AS INTEGER), dhuhr_rakah,
+         CAST(strftime('%s', asr_adhan) AS INTEGER), CAST(strftime('%s', asr_iqama) AS INTEGER), asr_rakah,
+         CAST(strftime('%s', maghrib_adhan) AS INTEGER), CAST(strftime('%s', maghrib_iqama) AS INTEGER), maghrib_rakah,
+         CAST(strftime('%s', isha_adhan) AS INTEGER), CAST(strftime('%s', isha_iqama) AS INTEGER), isha_rakah,
+         CAST(strftime('%s', jumuah_adhan) AS INTEGER), CAST(strftime('%s', jumuah_iqama) AS INTEGER), jumuah_rakah,
+         mosque_name, COALESCE(CAST(strftime('%s', cached_at) AS INTEGER), strftime('%s', 'now'))
+        FROM prayer_times_legacy
+        WHERE strftime('%s', date) IS NOT NULL
+        "#,
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query("DROP TABLE prayer_times_legacy")
+        .execute(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+
+    Ok(())
+}
+
+pub async fn create_database(db_path: &str) -> anyhow::Result<Pool<Sqlite>> {
+    // Ensure parent directory exists
+    if let Some(parent) = Path::new(db_path).parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    // Create the database connection
+    let database_url = format!("sqlite:{}", db_path);
+    let options = SqliteConnectOptions::from_str(&database_url)?.create_if_missing(true);
+    let pool = if db_path == ":memory:" {
+        // The in-memory database lives as long as its connection: keep a single one open
+        SqlitePoolOptions::new()
+            .max_connections(1)
+            .idle_timeout(None)
+            .max_lifetime(None)
+            .connect_with(options)
+            .await?
+    } else {
+        SqlitePool::connect_with(options).await?
+    };
+    
+    // Run migrations
+    run_migrations(&pool).await?;
+
+    Ok(pool)
+}
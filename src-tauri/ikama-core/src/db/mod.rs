@@ -1,4 +1,7 @@
 pub mod database;
+pub mod legacy;
+pub mod location;
 pub mod migrations;
 
 pub use database::*;
+pub use legacy::*;
@@ -0,0 +1,6 @@
+// Core prayer-time logic shared by the desktop app and other frontends
+// Nothing in this crate depends on tauri
+pub mod db;
+pub mod models;
+pub mod providers;
+pub mod services;
@@ -0,0 +1,170 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use super::PrayerName;
+
+/// Sound id that plays nothing, to keep a prayer silent
+pub const SILENT_SOUND: &str = "none";
+
+/// A sound that can be played when the adhan is called
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct AdhanSound {
+    /// `user:<file name>`
+    pub id: String,
+    pub name: String,
+    pub path: String,
+    /// The file was there when the sounds folder was last scanned
+    pub available: bool,
+}
+
+/// A sound card, headset or other output the adhan can be played on
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct AudioOutputDevice {
+    pub id: String,
+    pub name: String,
+    pub is_default: bool,
+}
+
+/// Adhan played at each prayer's time, e.g. a short tone for Dhuhr at work and the full adhan at home
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct AdhanSoundConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Sound for prayers without their own; no sound ships with the app, so `none` until the user picks one
+    #[serde(default = "default_sound")]
+    pub default_sound: String,
+    /// Sound id per prayer, `none` to keep one silent
+    #[serde(default)]
+    pub per_prayer: HashMap<PrayerName, String>,
+    /// Output device id from the device list; the system default when unset
+    #[serde(default)]
+    pub output_device: Option<String>,
+    /// Turn other applications down while the adhan plays, where the platform allows it
+    #[serde(default = "default_true")]
+    pub duck_others: bool,
+    /// Volume other applications are turned down to, in percent of their own
+    #[serde(default = "default_duck_volume")]
+    pub duck_volume_percent: u32,
+}
+
+fn default_sound() -> String {
+    SILENT_SOUND.to_string()
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_duck_volume() -> u32 {
+    20
+}
+
+impl Default for AdhanSoundConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            default_sound: default_sound(),
+            per_prayer: HashMap::new(),
+            output_device: None,
+            duck_others: true,
+            duck_volume_percent: default_duck_volume(),
+        }
+    }
+}
+
+impl AdhanSoundConfig {
+    /// Sound to play for a prayer, `None` when adhans are off or the prayer is silent
+    pub fn sound_for(&self, prayer: PrayerName) -> Option<&str> {
+        if !self.enabled {
+            return None;
+        }
+        let id = self.per_prayer.get(&prayer).unwrap_or(&self.default_sound);
+        (id != SILENT_SOUND).then_some(id.as_str())
+    }
+
+    /// Volume to duck other applications to, `None` when ducking is off
+    pub fn duck_percent(&self) -> Option<u32> {
+        self.duck_others.then_some(self.duck_volume_percent)
+    }
+
+    /// The ducked volume must be a percentage, and every assigned sound an available one of `sounds` or `none`
+    pub fn validate(&self, sounds: &[AdhanSound]) -> Result<(), String> {
+        if self.duck_volume_percent > 100 {
+            return Err(format!(
+                "Ducked volume must be between 0 and 100%, got {}",
+                self.duck_volume_percent
+            ));
+        }
+        let assigned = std::iter::once(&self.default_sound).chain(self.per_prayer.values());
+        for id in assigned {
+            if id == SILENT_SOUND {
+                continue;
+            }
+            match sounds.iter().find(|s| &s.id == id) {
+                Some(sound) if sound.available => {}
+                Some(_) => return Err(format!("Adhan sound file is missing: {}", id)),
+                None => return Err(format!("Unknown adhan sound: {}", id)),
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sound(id: &str) -> AdhanSound {
+        AdhanSound {
+            id: id.to_string(),
+            name: id.to_string(),
+            path: format!("/sounds/{}", id),
+            available: true,
+        }
+    }
+
+    #[test]
+    fn test_sound_per_prayer() {
+        let mut config = AdhanSoundConfig {
+            enabled: true,
+            default_sound: "user:adhan.mp3".to_string(),
+            ..Default::default()
+        };
+        config.per_prayer.insert(PrayerName::Dhuhr, "user:tone.wav".to_string());
+        config.per_prayer.insert(PrayerName::Asr, SILENT_SOUND.to_string());
+
+        assert_eq!(config.sound_for(PrayerName::Maghrib), Some("user:adhan.mp3"));
+        assert_eq!(config.sound_for(PrayerName::Dhuhr), Some("user:tone.wav"));
+        assert_eq!(config.sound_for(PrayerName::Asr), None);
+        assert_eq!(AdhanSoundConfig::default().sound_for(PrayerName::Maghrib), None);
+
+        let mut sounds = vec![sound("user:adhan.mp3"), sound("user:tone.wav")];
+        assert!(config.validate(&sounds).is_ok());
+        assert_eq!(config.duck_percent(), Some(20));
+
+        sounds[1].available = false;
+        assert!(config.validate(&sounds).is_err());
+        sounds[1].available = true;
+        config.per_prayer.insert(PrayerName::Isha, "user:gone.mp3".to_string());
+        assert!(config.validate(&sounds).is_err());
+
+        // Nothing ships with the app: adhans stay silent until a sound is picked
+        let enabled = AdhanSoundConfig {
+            enabled: true,
+            ..Default::default()
+        };
+        assert!(enabled.validate(&[]).is_ok());
+        assert_eq!(enabled.sound_for(PrayerName::Fajr), None);
+
+        let loud = AdhanSoundConfig {
+            duck_volume_percent: 150,
+            ..Default::default()
+        };
+        assert!(loud.validate(&sounds).is_err());
+    }
+}
@@ -0,0 +1,37 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+/// Days of iqama times written to the calendar, from today
+pub const CALENDAR_SYNC_DAYS: i64 = 7;
+
+/// File written next to the database unless another path is configured
+pub const CALENDAR_SYNC_FILE: &str = "iqamah-week.ics";
+
+/// Keeps next week's iqama times in the user's calendar app through a generated .ics file
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export, optional_fields)]
+pub struct CalendarSyncConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Mosque whose times are written; the selected mosque when unset
+    #[serde(default)]
+    pub mosque_id: Option<String>,
+    /// Where the .ics file is written; the app directory when unset
+    #[serde(default)]
+    pub path: Option<String>,
+    /// Open the file in the default calendar app whenever its events change, so it imports them
+    #[serde(default)]
+    pub auto_import: bool,
+}
+
+/// The calendar integration's settings and its last write
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, optional_fields)]
+pub struct CalendarSyncStatus {
+    pub config: CalendarSyncConfig,
+    /// The .ics file the events are written to
+    pub file_path: Option<String>,
+    /// When the file was last written
+    pub last_synced: Option<DateTime<Utc>>,
+}
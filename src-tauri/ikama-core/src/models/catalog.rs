@@ -0,0 +1,41 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+/// Step of a country catalog import
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum CatalogImportPhase {
+    Downloading,
+    /// Comparing the download with the stored catalog by slug
+    Diffing,
+    Storing,
+    Done,
+}
+
+/// Progress of `download_country_catalog`, emitted as `catalog-import-progress`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export, optional_fields)]
+pub struct CatalogImportProgress {
+    pub country: String,
+    pub phase: CatalogImportPhase,
+    /// Mosques handled so far in this phase
+    pub processed: u32,
+    /// Mosques in the download, once known
+    pub total: Option<u32>,
+}
+
+/// What an import changed in the stored catalog of a country
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct CatalogImportSummary {
+    pub country: String,
+    /// Mosques in the catalog after the import
+    pub total: u32,
+    pub added: u32,
+    pub updated: u32,
+    pub removed: u32,
+    pub unchanged: u32,
+    pub imported_at: DateTime<Utc>,
+}
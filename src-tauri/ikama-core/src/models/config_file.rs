@@ -0,0 +1,192 @@
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+
+use super::{HttpServerConfig, KioskConfig};
+
+/// Optional file in the app directory, for provisioning kiosk screens without clicking through the UI
+pub const CONFIG_FILE_NAME: &str = "config.toml";
+
+/// How values from the config file meet settings already saved in the database
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfigFileMode {
+    /// Written on every start, so changes made in the app don't survive a restart
+    #[default]
+    Override,
+    /// Only fills in settings that aren't saved yet
+    Seed,
+}
+
+/// Provider configuration to save, secrets included; they are moved to the keychain on save
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ConfigFileProvider {
+    pub id: String,
+    #[serde(default)]
+    pub settings: toml::Table,
+}
+
+/// Kiosk mode, on for the lobby screen unless `enabled = false`
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ConfigFileKiosk {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    #[serde(flatten)]
+    pub config: KioskConfig,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// `config.toml`, e.g.
+///
+/// ```toml
+/// mode = "override"
+/// language = "fr"
+/// default_country = "MA"
+///
+/// [provider]
+/// id = "official_api"
+/// settings = { base_url = "https://api.example.org", api_key = "..." }
+///
+/// [kiosk]
+/// mosque_id = "grande-mosquee-de-paris"
+/// announcements = ["Jumuah khutbah at 13:15"]
+///
+/// [http_server]
+/// enabled = true
+/// port = 8787
+/// allow_lan = true
+///
+/// [settings]
+/// theme = "dark"
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ConfigFile {
+    #[serde(default)]
+    pub mode: ConfigFileMode,
+    pub language: Option<String>,
+    /// Country searched and fetched from Mawaqit when none is given, as an ISO code
+    pub default_country: Option<String>,
+    pub provider: Option<ConfigFileProvider>,
+    pub kiosk: Option<ConfigFileKiosk>,
+    pub http_server: Option<HttpServerConfig>,
+    /// Any other setting by its key, e.g. `theme` or `notification_enabled`
+    #[serde(default)]
+    pub settings: BTreeMap<String, toml::Value>,
+}
+
+impl ConfigFile {
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let config: Self = toml::from_str(text).map_err(|e| e.to_string())?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if let Some(country) = &self.default_country {
+            if country.len() != 2 || !country.chars().all(|c| c.is_ascii_alphabetic()) {
+                return Err(format!("default_country must be a two-letter country code, got {:?}", country));
+            }
+        }
+        if let Some(server) = &self.http_server {
+            if server.port < 1024 {
+                return Err(format!("http_server.port must be between 1024 and 65535, got {}", server.port));
+            }
+        }
+        if self.provider.as_ref().is_some_and(|p| p.id.trim().is_empty()) {
+            return Err("provider.id is empty".to_string());
+        }
+        Ok(())
+    }
+
+    /// Provider settings as the JSON the provider configs store
+    pub fn provider_settings(&self) -> Option<serde_json::Value> {
+        let provider = self.provider.as_ref()?;
+        serde_json::to_value(&provider.settings).ok()
+    }
+
+    /// Entries of `[settings]` as saved in the database: strings as they are, other values in TOML form
+    pub fn raw_settings(&self) -> Vec<(String, String)> {
+        self.settings
+            .iter()
+            .map(|(key, value)| {
+                let value = match value {
+                    toml::Value::String(text) => text.clone(),
+                    other => other.to_string(),
+                };
+                (key.clone(), value)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_config_file() {
+        let config = ConfigFile::parse(
+            r#"
+            mode = "seed"
+            default_country = "MA"
+
+            [provider]
+            id = "official_api"
+            settings = { base_url = "https://api.example.org", timeout_secs = 10 }
+
+            [kiosk]
+            mosque_id = "mawaqit-123"
+            announcements = ["Jumuah at 13:15"]
+
+            [http_server]
+            port = 9000
+            allow_lan = true
+
+            [settings]
+            theme = "dark"
+            notification_enabled = false
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.mode, ConfigFileMode::Seed);
+        assert_eq!(config.default_country.as_deref(), Some("MA"));
+        assert_eq!(
+            config.provider_settings(),
+            Some(serde_json::json!({"base_url": "https://api.example.org", "timeout_secs": 10}))
+        );
+
+        let kiosk = config.kiosk.unwrap();
+        assert!(kiosk.enabled);
+        assert_eq!(kiosk.config.mosque_id.as_deref(), Some("mawaqit-123"));
+        assert!(kiosk.config.show_iqama_board);
+
+        let server = config.http_server.unwrap();
+        assert_eq!(server.port, 9000);
+        assert!(!server.enabled);
+
+        let raw = ConfigFile::parse("[settings]\ntheme = \"dark\"\nnotification_enabled = false\n")
+            .unwrap()
+            .raw_settings();
+        assert_eq!(
+            raw,
+            [
+                ("notification_enabled".to_string(), "false".to_string()),
+                ("theme".to_string(), "dark".to_string())
+            ]
+        );
+        assert_eq!(ConfigFile::parse("").unwrap().mode, ConfigFileMode::Override);
+    }
+
+    #[test]
+    fn test_reject_invalid_config_file() {
+        assert!(ConfigFile::parse("default_country = \"Morocco\"").is_err());
+        assert!(ConfigFile::parse("[http_server]\nport = 80").is_err());
+        assert!(ConfigFile::parse("unknown_key = 1").is_err());
+        assert!(ConfigFile::parse("mode = \"sometimes\"").is_err());
+    }
+}
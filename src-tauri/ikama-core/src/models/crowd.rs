@@ -0,0 +1,51 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use super::PrayerName;
+
+/// How full the mosque was, as reported by the user
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum CrowdLevel {
+    Empty,
+    Light,
+    Moderate,
+    Busy,
+    Packed,
+}
+
+impl CrowdLevel {
+    /// 0 (empty) to 4 (packed), the value stored and averaged
+    pub fn score(&self) -> i32 {
+        match self {
+            CrowdLevel::Empty => 0,
+            CrowdLevel::Light => 1,
+            CrowdLevel::Moderate => 2,
+            CrowdLevel::Busy => 3,
+            CrowdLevel::Packed => 4,
+        }
+    }
+}
+
+/// One crowd observation for a prayer at a mosque
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct CrowdReport {
+    pub mosque_id: String,
+    pub prayer: PrayerName,
+    pub date: NaiveDate,
+    pub level: CrowdLevel,
+    pub reported_at: DateTime<Utc>,
+}
+
+/// Historical crowd level for a prayer
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export, optional_fields)]
+pub struct CrowdSummary {
+    pub prayer: PrayerName,
+    /// Average score (0-4) over recent reports
+    pub average_level: Option<f64>,
+    pub report_count: u32,
+}
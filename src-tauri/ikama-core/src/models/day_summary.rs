@@ -0,0 +1,52 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use super::{
+    HijriDay, NextPrayerResult, Prayer, PrayerCountdown, PrayerName, PrayerTimes, PrayerWindow, UpcomingScheduleChange,
+};
+
+/// Where the day stands relative to the prayers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum DayPhase {
+    /// Waiting for the prayer's adhan
+    BeforeAdhan,
+    /// Adhan called, congregation not started yet
+    BeforeIqama,
+    /// Congregation expected to be praying
+    InCongregation,
+    /// Congregation over, the prayer can still be prayed until its window ends
+    PrayerWindow,
+}
+
+/// Current phase and the prayer it refers to
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export, optional_fields)]
+pub struct CurrentPhase {
+    pub phase: DayPhase,
+    pub prayer: PrayerName,
+    /// When the phase ends, if known
+    pub until: Option<DateTime<Utc>>,
+}
+
+/// Everything the dashboard shows for a day, in one payload
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, optional_fields)]
+pub struct DaySummary {
+    pub mosque_id: String,
+    pub date: NaiveDate,
+    pub prayer_times: PrayerTimes,
+    pub countdowns: Vec<PrayerCountdown>,
+    pub next_prayer: NextPrayerResult,
+    pub phase: CurrentPhase,
+    /// When each prayer can be prayed, following the user's Asr and Isha end settings
+    pub windows: Vec<PrayerWindow>,
+    pub shuruq: Option<DateTime<Utc>>,
+    pub hijri: HijriDay,
+    /// Jumuah sessions published for the day, first khutbah first
+    pub jumuah_sessions: Vec<Prayer>,
+    /// Next iqama jump found in the cached schedules
+    pub next_schedule_change: Option<UpcomingScheduleChange>,
+}
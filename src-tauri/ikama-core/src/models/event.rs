@@ -0,0 +1,74 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+/// Kind of mosque event, inferred from its title when the source doesn't say
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum EventKind {
+    Lecture,
+    Iftar,
+    Class,
+    Announcement,
+}
+
+impl EventKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EventKind::Lecture => "lecture",
+            EventKind::Iftar => "iftar",
+            EventKind::Class => "class",
+            EventKind::Announcement => "announcement",
+        }
+    }
+
+    /// Guess the kind from a title in English, French or Arabic
+    pub fn infer(title: &str) -> Self {
+        let title = title.to_lowercase();
+        let has = |words: &[&str]| words.iter().any(|w| title.contains(w));
+
+        if has(&["iftar", "ftour", "إفطار"]) {
+            EventKind::Iftar
+        } else if has(&["class", "cours", "course", "halaqa", "madrasa", "درس"]) {
+            EventKind::Class
+        } else if has(&["lecture", "conférence", "conference", "talk", "khatira", "محاضرة"]) {
+            EventKind::Lecture
+        } else {
+            EventKind::Announcement
+        }
+    }
+}
+
+impl std::str::FromStr for EventKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "lecture" => Ok(EventKind::Lecture),
+            "iftar" => Ok(EventKind::Iftar),
+            "class" => Ok(EventKind::Class),
+            "announcement" => Ok(EventKind::Announcement),
+            other => Err(format!("Unknown event kind: {}", other)),
+        }
+    }
+}
+
+/// Lecture, iftar, class or announcement published by a mosque
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export, optional_fields)]
+pub struct MosqueEvent {
+    /// Stable id from the source (e.g. "mawaqit:<slug>:<id>", "ical:<uid>")
+    pub id: String,
+    pub mosque_id: String,
+    pub kind: EventKind,
+    pub title: String,
+    pub description: Option<String>,
+    pub location: Option<String>,
+    pub url: Option<String>,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: Option<DateTime<Utc>>,
+    /// Remind this many minutes before `starts_at`; set by the user
+    #[ts(as = "Option<f64>")]
+    pub reminder_minutes: Option<i64>,
+}
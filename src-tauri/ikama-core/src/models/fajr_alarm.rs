@@ -0,0 +1,115 @@
+use chrono::{DateTime, Utc, Weekday};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, TS)]
+#[serde(rename_all = "lowercase")]
+#[ts(export)]
+pub enum DayOfWeek {
+    Mon,
+    Tue,
+    Wed,
+    Thu,
+    Fri,
+    Sat,
+    Sun,
+}
+
+impl From<Weekday> for DayOfWeek {
+    fn from(day: Weekday) -> Self {
+        match day {
+            Weekday::Mon => DayOfWeek::Mon,
+            Weekday::Tue => DayOfWeek::Tue,
+            Weekday::Wed => DayOfWeek::Wed,
+            Weekday::Thu => DayOfWeek::Thu,
+            Weekday::Fri => DayOfWeek::Fri,
+            Weekday::Sat => DayOfWeek::Sat,
+            Weekday::Sun => DayOfWeek::Sun,
+        }
+    }
+}
+
+/// Fajr wake-up alarm that keeps ringing, more urgently each time, until a challenge is solved
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct FajrAlarmConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Days the alarm rings on
+    #[serde(default = "every_day")]
+    pub days: Vec<DayOfWeek>,
+    /// First ring, in minutes before the Fajr adhan
+    #[serde(default = "default_minutes_before")]
+    pub minutes_before: i64,
+    /// Minutes between rings until the alarm is dismissed
+    #[serde(default = "default_repeat_minutes")]
+    pub repeat_minutes: i64,
+}
+
+fn every_day() -> Vec<DayOfWeek> {
+    vec![
+        DayOfWeek::Mon,
+        DayOfWeek::Tue,
+        DayOfWeek::Wed,
+        DayOfWeek::Thu,
+        DayOfWeek::Fri,
+        DayOfWeek::Sat,
+        DayOfWeek::Sun,
+    ]
+}
+
+fn default_minutes_before() -> i64 {
+    20
+}
+
+fn default_repeat_minutes() -> i64 {
+    2
+}
+
+impl Default for FajrAlarmConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            days: every_day(),
+            minutes_before: default_minutes_before(),
+            repeat_minutes: default_repeat_minutes(),
+        }
+    }
+}
+
+impl FajrAlarmConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if !(1..=120).contains(&self.minutes_before) {
+            return Err("Ring 1 to 120 minutes before Fajr".to_string());
+        }
+        if !(1..=15).contains(&self.repeat_minutes) {
+            return Err("Repeat every 1 to 15 minutes".to_string());
+        }
+        Ok(())
+    }
+
+    pub fn rings_on(&self, day: Weekday) -> bool {
+        self.enabled && self.days.contains(&day.into())
+    }
+}
+
+/// One ring of the Fajr alarm; `level` grows with each ring until dismissed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct FajrAlarmRing {
+    pub level: u32,
+    pub fajr_time: DateTime<Utc>,
+    /// Question to answer to stop the alarm, e.g. "17 + 26"
+    pub question: String,
+}
+
+/// The Fajr alarm's current state
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export, optional_fields)]
+pub struct FajrAlarmStatus {
+    pub ringing: bool,
+    /// The last ring while the alarm is ringing
+    pub ring: Option<FajrAlarmRing>,
+    /// Whether today's alarm was dismissed
+    pub dismissed: bool,
+}
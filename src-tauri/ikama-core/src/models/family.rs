@@ -0,0 +1,164 @@
+use chrono::{Duration, NaiveDate};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use super::{PrayerLogEntry, PrayerLogTag, PrayerName};
+
+/// One family member's attendance over the range
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export, optional_fields)]
+pub struct MemberAttendance {
+    pub profile: String,
+    /// Mosque the profile follows
+    pub mosque_id: Option<String>,
+    pub logged: u32,
+    pub jamaah: u32,
+}
+
+/// Who prayed one prayer of one day
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct FamilyPrayerDay {
+    pub date: NaiveDate,
+    pub prayer: PrayerName,
+    /// Profiles that logged the prayer
+    pub present: Vec<String>,
+    /// Profiles that prayed it in congregation
+    pub jamaah: Vec<String>,
+    /// Every selected profile logged it
+    pub everyone: bool,
+}
+
+/// Attendance of several profiles side by side, e.g. to follow the kids' Fajr
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export, optional_fields)]
+pub struct FamilyAttendance {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub members: Vec<MemberAttendance>,
+    /// Mosque followed by every member, when they share one
+    pub shared_mosque_id: Option<String>,
+    /// Prayers at least one member logged, oldest first
+    pub days: Vec<FamilyPrayerDay>,
+}
+
+impl FamilyAttendance {
+    /// Aggregate log entries of `members` between `from` and `to`, optionally for one prayer
+    /// Entries of other profiles, days or prayers are ignored
+    pub fn aggregate(
+        mut members: Vec<MemberAttendance>,
+        entries: &[PrayerLogEntry],
+        from: NaiveDate,
+        to: NaiveDate,
+        prayer: Option<PrayerName>,
+    ) -> Self {
+        let mut days: Vec<FamilyPrayerDay> = Vec::new();
+
+        let mut date = from;
+        while date <= to {
+            for &name in PrayerName::DAILY.iter().chain([PrayerName::Jumuah].iter()) {
+                if prayer.is_some_and(|p| p != name) {
+                    continue;
+                }
+
+                let logged: Vec<&PrayerLogEntry> = entries
+                    .iter()
+                    .filter(|e| e.date == date && e.prayer == name)
+                    .filter(|e| members.iter().any(|m| m.profile == e.profile))
+                    .collect();
+                if logged.is_empty() {
+                    continue;
+                }
+
+                for entry in &logged {
+                    if let Some(member) = members.iter_mut().find(|m| m.profile == entry.profile) {
+                        member.logged += 1;
+                        member.jamaah += entry.has_tag(PrayerLogTag::Jamaah) as u32;
+                    }
+                }
+
+                days.push(FamilyPrayerDay {
+                    date,
+                    prayer: name,
+                    present: logged.iter().map(|e| e.profile.clone()).collect(),
+                    jamaah: logged
+                        .iter()
+                        .filter(|e| e.has_tag(PrayerLogTag::Jamaah))
+                        .map(|e| e.profile.clone())
+                        .collect(),
+                    everyone: logged.len() == members.len(),
+                });
+            }
+            date += Duration::days(1);
+        }
+
+        let shared_mosque_id = match members.first().and_then(|m| m.mosque_id.clone()) {
+            Some(id) if members.iter().all(|m| m.mosque_id.as_deref() == Some(id.as_str())) => Some(id),
+            _ => None,
+        };
+
+        Self {
+            from,
+            to,
+            members,
+            shared_mosque_id,
+            days,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn member(profile: &str) -> MemberAttendance {
+        MemberAttendance {
+            profile: profile.to_string(),
+            mosque_id: Some("mosque-paris".to_string()),
+            logged: 0,
+            jamaah: 0,
+        }
+    }
+
+    fn entry(profile: &str, prayer: PrayerName, day: u32, jamaah: bool) -> PrayerLogEntry {
+        PrayerLogEntry {
+            profile: profile.to_string(),
+            prayer,
+            date: NaiveDate::from_ymd_opt(2026, 3, day).unwrap(),
+            mosque_id: None,
+            tags: if jamaah { vec![PrayerLogTag::Jamaah] } else { Vec::new() },
+            note: None,
+            logged_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_family_fajr_attendance() {
+        let entries = vec![
+            entry("default", PrayerName::Fajr, 2, true),
+            entry("kids", PrayerName::Fajr, 2, true),
+            entry("kids", PrayerName::Fajr, 3, false),
+            entry("kids", PrayerName::Isha, 3, false),
+            entry("guest", PrayerName::Fajr, 3, true),
+        ];
+        let from = NaiveDate::from_ymd_opt(2026, 3, 2).unwrap();
+        let to = NaiveDate::from_ymd_opt(2026, 3, 4).unwrap();
+
+        let family = FamilyAttendance::aggregate(
+            vec![member("default"), member("kids")],
+            &entries,
+            from,
+            to,
+            Some(PrayerName::Fajr),
+        );
+
+        assert_eq!(family.shared_mosque_id.as_deref(), Some("mosque-paris"));
+        assert_eq!(family.days.len(), 2);
+        assert!(family.days[0].everyone);
+        assert_eq!(family.days[1].present, vec!["kids".to_string()]);
+        assert!(family.days[1].jamaah.is_empty());
+        let kids = &family.members[1];
+        assert_eq!((kids.logged, kids.jamaah), (2, 1));
+    }
+}
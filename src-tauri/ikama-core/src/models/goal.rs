@@ -0,0 +1,147 @@
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc, Weekday};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use super::{PrayerLogEntry, PrayerLogTag, PrayerName};
+
+/// A weekly target for logged prayers, e.g. Fajr in jamaah 5 times a week
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export, optional_fields)]
+pub struct PrayerGoal {
+    #[ts(type = "number")]
+    pub id: i64,
+    pub profile: String,
+    /// Prayer counted; any daily prayer when unset
+    pub prayer: Option<PrayerName>,
+    /// Tag a logged prayer needs to count, e.g. jamaah
+    pub tag: Option<PrayerLogTag>,
+    pub target_per_week: u32,
+    pub created_at: DateTime<Utc>,
+}
+
+impl PrayerGoal {
+    /// Most prayers a week can hold for the goal's prayer
+    pub fn max_per_week(prayer: Option<PrayerName>) -> u32 {
+        match prayer {
+            Some(PrayerName::Jumuah) => 1,
+            Some(_) => 7,
+            None => 7 * PrayerName::DAILY.len() as u32,
+        }
+    }
+
+    /// Most prayers that can count towards the goal on `date`
+    fn max_on(&self, date: NaiveDate) -> u32 {
+        match self.prayer {
+            Some(PrayerName::Jumuah) => (date.weekday() == Weekday::Fri) as u32,
+            Some(_) => 1,
+            None => PrayerName::DAILY.len() as u32,
+        }
+    }
+
+    /// Whether a logged prayer counts towards the goal
+    /// Jumuah stands in for Dhuhr on Fridays
+    pub fn counts(&self, entry: &PrayerLogEntry) -> bool {
+        let prayer_matches = match self.prayer {
+            Some(PrayerName::Dhuhr) => matches!(entry.prayer, PrayerName::Dhuhr | PrayerName::Jumuah),
+            Some(prayer) => entry.prayer == prayer,
+            None => true,
+        };
+
+        entry.profile == self.profile && prayer_matches && self.tag.is_none_or(|tag| entry.has_tag(tag))
+    }
+}
+
+/// Monday of the week holding `date`; goals are counted Monday to Sunday
+pub fn goal_week_start(date: NaiveDate) -> NaiveDate {
+    date - Duration::days(date.weekday().num_days_from_monday() as i64)
+}
+
+/// How a goal stands for one week
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct GoalProgress {
+    pub goal: PrayerGoal,
+    pub week_start: NaiveDate,
+    pub completed: u32,
+    pub achieved: bool,
+    /// Days of the week after `today`
+    pub days_left: u32,
+    /// Whether the target can still be met this week
+    pub reachable: bool,
+    /// Achieved weeks in a row before this one
+    pub streak_weeks: u32,
+}
+
+impl GoalProgress {
+    /// Count the log entries of `today`'s week towards the goal
+    pub fn evaluate(goal: &PrayerGoal, entries: &[PrayerLogEntry], today: NaiveDate, streak_weeks: u32) -> Self {
+        let week_start = goal_week_start(today);
+        let week_end = week_start + Duration::days(6);
+        let completed = entries
+            .iter()
+            .filter(|e| e.date >= week_start && e.date <= week_end && goal.counts(e))
+            .count() as u32;
+
+        let days_left = (week_end - today).num_days() as u32;
+        let still_possible: u32 = (1..=days_left as i64).map(|d| goal.max_on(today + Duration::days(d))).sum();
+        let achieved = completed >= goal.target_per_week;
+
+        Self {
+            goal: goal.clone(),
+            week_start,
+            completed,
+            achieved,
+            days_left,
+            reachable: achieved || completed + still_possible >= goal.target_per_week,
+            streak_weeks,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(prayer: PrayerName, date: NaiveDate, tags: &[PrayerLogTag]) -> PrayerLogEntry {
+        PrayerLogEntry {
+            profile: "default".to_string(),
+            prayer,
+            date,
+            mosque_id: None,
+            tags: tags.to_vec(),
+            note: None,
+            logged_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_fajr_jamaah_goal_progress() {
+        let goal = PrayerGoal {
+            id: 1,
+            profile: "default".to_string(),
+            prayer: Some(PrayerName::Fajr),
+            tag: Some(PrayerLogTag::Jamaah),
+            target_per_week: 5,
+            created_at: Utc::now(),
+        };
+        // Thursday 2026-03-05, week of Monday 2026-03-02
+        let today = NaiveDate::from_ymd_opt(2026, 3, 5).unwrap();
+        let day = |d| NaiveDate::from_ymd_opt(2026, 3, d).unwrap();
+        let entries = vec![
+            entry(PrayerName::Fajr, day(1), &[PrayerLogTag::Jamaah]), // previous week
+            entry(PrayerName::Fajr, day(2), &[PrayerLogTag::Jamaah]),
+            entry(PrayerName::Fajr, day(3), &[PrayerLogTag::OnTime]), // prayed alone
+            entry(PrayerName::Isha, day(3), &[PrayerLogTag::Jamaah]),
+            entry(PrayerName::Fajr, day(4), &[PrayerLogTag::Jamaah, PrayerLogTag::WithFamily]),
+        ];
+
+        let progress = GoalProgress::evaluate(&goal, &entries, today, 2);
+        assert_eq!(progress.week_start, day(2));
+        assert_eq!((progress.completed, progress.days_left), (2, 3));
+        assert!(progress.reachable && !progress.achieved);
+
+        // One more missed morning and five is out of reach
+        let progress = GoalProgress::evaluate(&goal, &entries, day(6), 2);
+        assert!(!progress.reachable);
+    }
+}
@@ -0,0 +1,69 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+/// Date in the Hijri calendar
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct HijriDate {
+    pub year: i32,
+    /// 1 = Muharram ... 12 = Dhul Hijjah
+    pub month: u32,
+    pub day: u32,
+}
+
+/// Days of the Islamic year the app highlights
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum SpecialDay {
+    RamadanStart,
+    /// Odd night of Ramadan's last ten; the night begins at Maghrib the evening before
+    LaylatAlQadr,
+    EidAlFitr,
+    /// First of the ten days of Dhul Hijjah
+    DhulHijjahStart,
+    /// Second to eighth of the ten days, before Arafah
+    DhulHijjahDay,
+    DayOfArafah,
+    EidAlAdha,
+    Ashura,
+}
+
+/// Hijri settings announced by a mosque, overriding pure calculation
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct MosqueHijriInfo {
+    /// Days added to the calculated Hijri date (usually -1, 0 or 1)
+    pub adjustment_days: i32,
+    /// Mosque completes the current month to 30 days
+    pub force_month_30: bool,
+    /// Announced Eid prayer times ("HH:MM"), empty outside Eid
+    pub eid_prayer_times: Vec<String>,
+}
+
+/// Hijri date for a day, with the flags the UI needs
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export, optional_fields)]
+pub struct HijriDay {
+    pub date: HijriDate,
+    pub is_ramadan: bool,
+    pub special_day: Option<SpecialDay>,
+    /// Adjustment applied on top of the calculation
+    pub adjustment_days: i32,
+    /// True when the mosque's announcement was applied
+    pub from_mosque: bool,
+    pub eid_prayer_times: Vec<String>,
+}
+
+/// A special day coming up in the Islamic calendar
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct IslamicEvent {
+    pub day: SpecialDay,
+    pub date: NaiveDate,
+    pub hijri_date: HijriDate,
+    /// Days from the start of the search, 0 for today
+    #[ts(type = "number")]
+    pub days_until: i64,
+}
@@ -0,0 +1,100 @@
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+/// What a global shortcut (or a Stream Deck button bound to one) does
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum HotkeyAction {
+    /// Bring up the window with the next prayer's countdown
+    ShowMiniCountdown,
+    /// Mark the current prayer as attended at the selected mosque
+    MarkAttended,
+    /// Repeat the last prayer reminder a few minutes later
+    SnoozeReminder,
+}
+
+impl HotkeyAction {
+    pub const ALL: [HotkeyAction; 3] = [
+        HotkeyAction::ShowMiniCountdown,
+        HotkeyAction::MarkAttended,
+        HotkeyAction::SnoozeReminder,
+    ];
+}
+
+/// Global shortcuts in Tauri accelerator syntax, e.g. "CmdOrCtrl+Alt+P"; unset disables one
+/// None are bound until the user picks them, so the app never takes keys other apps use
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export, optional_fields)]
+pub struct HotkeyBindings {
+    pub show_mini_countdown: Option<String>,
+    pub mark_attended: Option<String>,
+    pub snooze_reminder: Option<String>,
+}
+
+impl HotkeyBindings {
+    /// Bound shortcuts with their actions
+    pub fn bindings(&self) -> Vec<(HotkeyAction, &str)> {
+        HotkeyAction::ALL
+            .into_iter()
+            .filter_map(|action| {
+                let accelerator = match action {
+                    HotkeyAction::ShowMiniCountdown => &self.show_mini_countdown,
+                    HotkeyAction::MarkAttended => &self.mark_attended,
+                    HotkeyAction::SnoozeReminder => &self.snooze_reminder,
+                };
+                accelerator.as_deref().map(|a| (action, a))
+            })
+            .collect()
+    }
+
+    /// Each shortcut needs a key after its modifiers, and no two actions may share one
+    pub fn validate(&self) -> Result<(), String> {
+        let mut seen = HashSet::new();
+        for (action, accelerator) in self.bindings() {
+            let key = accelerator.rsplit('+').next().unwrap_or_default().trim();
+            if key.is_empty() || is_modifier(key) {
+                return Err(format!("Shortcut {:?} for {:?} has no key", accelerator, action));
+            }
+            if !seen.insert(accelerator.to_ascii_lowercase().replace(' ', "")) {
+                return Err(format!("Shortcut {:?} is bound twice", accelerator));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn is_modifier(key: &str) -> bool {
+    [
+        "cmd", "command", "super", "ctrl", "control", "cmdorctrl", "commandorcontrol", "alt", "option", "shift",
+    ]
+    .contains(&key.to_ascii_lowercase().as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bindings_validation() {
+        assert!(HotkeyBindings::default().bindings().is_empty());
+
+        let bound = HotkeyBindings {
+            show_mini_countdown: Some("CmdOrCtrl+Alt+P".to_string()),
+            mark_attended: Some("CmdOrCtrl+Alt+A".to_string()),
+            snooze_reminder: Some("CmdOrCtrl+Alt+S".to_string()),
+        };
+        assert!(bound.validate().is_ok());
+        assert_eq!(bound.bindings().len(), 3);
+
+        let partial = HotkeyBindings { mark_attended: None, ..bound.clone() };
+        assert_eq!(partial.bindings().len(), 2);
+
+        let no_key = HotkeyBindings { snooze_reminder: Some("Ctrl+Shift".to_string()), ..bound.clone() };
+        assert!(no_key.validate().is_err());
+        let twice = HotkeyBindings { mark_attended: Some("cmdorctrl+alt+p".to_string()), ..bound };
+        assert!(twice.validate().is_err());
+    }
+}
@@ -0,0 +1,39 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use super::PrayerName;
+
+/// When a congregation actually started, as reported by the user after the prayer
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct IqamaReport {
+    pub mosque_id: String,
+    pub prayer: PrayerName,
+    pub date: NaiveDate,
+    /// Iqama time published by the mosque
+    pub scheduled_iqama: DateTime<Utc>,
+    pub actual_iqama: DateTime<Utc>,
+    pub reported_at: DateTime<Utc>,
+}
+
+impl IqamaReport {
+    /// Seconds the prayer started after the published iqama, negative when early
+    pub fn delay_secs(&self) -> i64 {
+        (self.actual_iqama - self.scheduled_iqama).num_seconds()
+    }
+}
+
+/// How late a mosque usually starts a prayer, learned from iqama reports
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export, optional_fields)]
+pub struct IqamaDelay {
+    pub prayer: PrayerName,
+    /// Typical delay, only once reports agree the mosque starts late
+    #[ts(as = "Option<f64>")]
+    pub delay_secs: Option<i64>,
+    /// How much the start varies between reports (standard deviation)
+    #[ts(as = "Option<f64>")]
+    pub spread_secs: Option<i64>,
+    pub report_count: u32,
+}
@@ -0,0 +1,104 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use super::PrayerCountdown;
+
+/// Default time each announcement stays on a kiosk screen
+pub const DEFAULT_ANNOUNCEMENT_SECS: u32 = 15;
+
+/// Layout of a mosque lobby screen running in kiosk mode
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export, optional_fields)]
+pub struct KioskConfig {
+    /// Mosque shown; the selected mosque when unset
+    pub mosque_id: Option<String>,
+    #[serde(default = "default_true")]
+    pub show_iqama_board: bool,
+    /// Messages shown in turn under the countdown
+    #[serde(default)]
+    pub announcements: Vec<String>,
+    /// Seconds each announcement stays on screen
+    #[serde(default = "default_announcement_secs")]
+    pub announcement_secs: u32,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_announcement_secs() -> u32 {
+    DEFAULT_ANNOUNCEMENT_SECS
+}
+
+impl KioskConfig {
+    /// Announcement on screen at `now`; every screen shows the same one at the same time
+    pub fn announcement_at(&self, now: DateTime<Utc>) -> Option<&str> {
+        if self.announcements.is_empty() {
+            return None;
+        }
+
+        let slot = now.timestamp().max(0) as u64 / self.announcement_secs.max(1) as u64;
+        let index = (slot % self.announcements.len() as u64) as usize;
+        Some(self.announcements[index].as_str())
+    }
+}
+
+/// Everything a kiosk screen draws, pushed by the backend every tick
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, optional_fields)]
+pub struct KioskFrame {
+    pub mosque_id: String,
+    pub mosque_name: Option<String>,
+    pub now: DateTime<Utc>,
+    /// Large countdown: the prayer in progress, else the next one held today
+    pub focus: Option<PrayerCountdown>,
+    /// Today's prayers for the iqama board; empty when the board is hidden or nothing is cached
+    pub board: Vec<PrayerCountdown>,
+    pub announcement: Option<String>,
+    /// False while the schedule can't be refreshed; the cached one stays on screen
+    pub online: bool,
+    pub synced_at: Option<DateTime<Utc>>,
+}
+
+/// Whether kiosk mode is on, and how its schedule sync is going
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, optional_fields)]
+pub struct KioskStatus {
+    pub enabled: bool,
+    pub config: Option<KioskConfig>,
+    pub online: bool,
+    pub last_error: Option<String>,
+    pub synced_at: Option<DateTime<Utc>>,
+}
+
+/// Countdown shown large on the screen
+pub fn kiosk_focus(countdowns: &[PrayerCountdown]) -> Option<PrayerCountdown> {
+    countdowns
+        .iter()
+        .filter(|c| !c.not_held)
+        .find(|c| c.is_active || c.time_until_adhan_secs > 0)
+        .cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_announcement_rotation() {
+        let config: KioskConfig = serde_json::from_str(r#"{"announcements": ["Jumuah at 13:30", "Quran class after Isha"]}"#).unwrap();
+        assert!(config.show_iqama_board);
+        assert_eq!(config.announcement_secs, DEFAULT_ANNOUNCEMENT_SECS);
+
+        let start = Utc.with_ymd_and_hms(2026, 3, 2, 12, 0, 0).unwrap();
+        assert_eq!(config.announcement_at(start), Some("Jumuah at 13:30"));
+        assert_eq!(config.announcement_at(start + chrono::Duration::seconds(14)), Some("Jumuah at 13:30"));
+        assert_eq!(config.announcement_at(start + chrono::Duration::seconds(15)), Some("Quran class after Isha"));
+        assert_eq!(config.announcement_at(start + chrono::Duration::seconds(30)), Some("Jumuah at 13:30"));
+
+        let quiet = KioskConfig { announcements: Vec::new(), ..config };
+        assert_eq!(quiet.announcement_at(start), None);
+    }
+}
@@ -0,0 +1,84 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use super::{Mosque, PrayerLogEntry, PrayerTimes};
+
+/// Kind of data `migrate_legacy_data` can import
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum LegacySource {
+    /// `iqamah.db` of the Flutter app or of an older release keeping data elsewhere
+    IkamaDatabase,
+    /// Favorites exported from the Mawaqit mobile app, as JSON
+    MawaqitExport,
+    /// A spreadsheet of favorite mosques or of logged prayers
+    Csv,
+}
+
+impl LegacySource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LegacySource::IkamaDatabase => "ikama_database",
+            LegacySource::MawaqitExport => "mawaqit_export",
+            LegacySource::Csv => "csv",
+        }
+    }
+}
+
+impl fmt::Display for LegacySource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A favorite as the old data recorded it
+#[derive(Debug, Clone)]
+pub struct LegacyFavorite {
+    pub mosque: Mosque,
+    /// Door-to-door travel time; the Flutter app kept one per mosque, without a travel mode
+    pub travel_time_secs: Option<i64>,
+    /// The mosque the old app showed on its home screen
+    pub active: bool,
+}
+
+/// Everything read from a legacy source, before it is merged into the database
+#[derive(Debug, Clone)]
+pub struct LegacyData {
+    pub source: LegacySource,
+    pub favorites: Vec<LegacyFavorite>,
+    /// Cached days of the favorites' schedules
+    pub schedules: Vec<PrayerTimes>,
+    pub log_entries: Vec<PrayerLogEntry>,
+    /// Rows or entries that could not be read
+    pub skipped: u32,
+}
+
+impl LegacyData {
+    pub fn new(source: LegacySource) -> Self {
+        Self {
+            source,
+            favorites: Vec::new(),
+            schedules: Vec::new(),
+            log_entries: Vec::new(),
+            skipped: 0,
+        }
+    }
+}
+
+/// What a legacy import added; data already present in the current database is left as is
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export, optional_fields)]
+pub struct LegacyImportSummary {
+    pub source: LegacySource,
+    pub path: String,
+    pub favorites: u32,
+    pub schedules: u32,
+    pub log_entries: u32,
+    /// Mosque now selected for the profile, when the old app had an active one and the profile had none
+    pub selected_mosque_id: Option<String>,
+    /// Entries that could not be read or were already present
+    pub skipped: u32,
+}
@@ -0,0 +1,147 @@
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use super::PrayerCountdown;
+
+/// Port of the local HTTP server unless configured otherwise
+pub const DEFAULT_HTTP_PORT: u16 = 8787;
+
+/// Optional HTTP server serving calendar feeds and widgets to other devices
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct HttpServerConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_port")]
+    pub port: u16,
+    /// Listen on every network interface so phones on the same network can connect,
+    /// each with an API token; only this computer can connect otherwise
+    #[serde(default)]
+    pub allow_lan: bool,
+    /// Serve `/metrics` in the Prometheus text format, for monitoring kiosk screens
+    #[serde(default)]
+    pub expose_metrics: bool,
+}
+
+fn default_port() -> u16 {
+    DEFAULT_HTTP_PORT
+}
+
+impl Default for HttpServerConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: DEFAULT_HTTP_PORT,
+            allow_lan: false,
+            expose_metrics: false,
+        }
+    }
+}
+
+impl HttpServerConfig {
+    pub fn bind_address(&self) -> String {
+        let host = if self.allow_lan { "0.0.0.0" } else { "127.0.0.1" };
+        format!("{}:{}", host, self.port)
+    }
+}
+
+/// Whether the local HTTP server is listening, and where
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, optional_fields)]
+pub struct HttpServerStatus {
+    pub config: HttpServerConfig,
+    pub running: bool,
+    /// Address the server listens on, e.g. "127.0.0.1:8787"
+    pub address: Option<String>,
+    /// Why the server could not start, e.g. the port is taken
+    pub last_error: Option<String>,
+    /// Dashboards connected to `/ws`
+    pub websocket_clients: u32,
+}
+
+/// Message pushed to WebSocket clients: an event the app window receives, with the same payload
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct ServerEvent {
+    /// Event name, e.g. "countdown" or "schedule-changed"
+    pub event: String,
+    pub payload: serde_json::Value,
+}
+
+/// The selected mosque's countdowns, pushed as the "countdown" event on every tick
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, optional_fields)]
+pub struct CountdownSnapshot {
+    pub mosque_id: String,
+    pub mosque_name: Option<String>,
+    pub now: DateTime<Utc>,
+    /// The prayer in progress, else the next one held today
+    pub focus: Option<PrayerCountdown>,
+    pub countdowns: Vec<PrayerCountdown>,
+}
+
+/// What a local API token lets another device do
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize, TS)]
+#[serde(rename_all = "kebab-case")]
+#[ts(export)]
+pub enum ApiScope {
+    /// Prayer times, countdowns, calendar feeds and the WebSocket event stream
+    ReadTimes,
+    /// Prayer goal progress
+    ReadStats,
+    /// Read and switch the user's notifications on or off
+    ControlNotifications,
+}
+
+impl ApiScope {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ApiScope::ReadTimes => "read-times",
+            ApiScope::ReadStats => "read-stats",
+            ApiScope::ControlNotifications => "control-notifications",
+        }
+    }
+}
+
+impl fmt::Display for ApiScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Token a device such as a wall tablet uses to call the local server
+/// The secret itself is only shown once, when the token is created
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct ApiToken {
+    pub id: String,
+    /// Label to tell devices apart, e.g. "Hallway tablet"
+    pub name: String,
+    pub scopes: Vec<ApiScope>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl ApiToken {
+    pub fn allows(&self, scope: ApiScope) -> bool {
+        self.scopes.contains(&scope)
+    }
+}
+
+/// A token just created, with the secret to copy to the device
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct NewApiToken {
+    pub token: ApiToken,
+    pub secret: String,
+}
+
+/// A token as saved in the settings: the hash of its secret, never the secret
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StoredApiToken {
+    #[serde(flatten)]
+    pub token: ApiToken,
+    pub secret_hash: String,
+}
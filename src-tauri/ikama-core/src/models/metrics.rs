@@ -0,0 +1,85 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+/// Schedule fetches from one provider since the app started
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct ProviderMetrics {
+    pub provider_id: String,
+    #[ts(type = "number")]
+    pub requests: u64,
+    #[ts(type = "number")]
+    pub failures: u64,
+    #[ts(type = "number")]
+    pub total_latency_ms: u64,
+    #[ts(type = "number")]
+    pub max_latency_ms: u64,
+    #[ts(type = "number")]
+    pub last_latency_ms: u64,
+}
+
+impl ProviderMetrics {
+    pub fn average_latency_ms(&self) -> Option<f64> {
+        (self.requests > 0).then(|| self.total_latency_ms as f64 / self.requests as f64)
+    }
+}
+
+/// Hits and misses of one cache since the app started
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export, optional_fields)]
+pub struct CacheMetrics {
+    pub name: String,
+    #[ts(type = "number")]
+    pub hits: u64,
+    #[ts(type = "number")]
+    pub misses: u64,
+    /// Entries held now, for in-memory caches
+    #[ts(type = "number")]
+    pub entries: Option<u64>,
+}
+
+impl CacheMetrics {
+    /// Share of lookups served from the cache, `None` before the first lookup
+    pub fn hit_rate(&self) -> Option<f64> {
+        let lookups = self.hits + self.misses;
+        (lookups > 0).then(|| self.hits as f64 / lookups as f64)
+    }
+}
+
+/// How late the background ticker wakes up compared to the interval it asked for
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct TickMetrics {
+    #[ts(type = "number")]
+    pub ticks: u64,
+    #[ts(type = "number")]
+    pub last_drift_ms: u64,
+    #[ts(type = "number")]
+    pub max_drift_ms: u64,
+}
+
+/// Rows in one database table
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct TableSize {
+    pub table: String,
+    #[ts(type = "number")]
+    pub rows: u64,
+}
+
+/// Everything monitored in kiosk deployments, also served as `/metrics` in the Prometheus text format
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct AppMetrics {
+    pub collected_at: DateTime<Utc>,
+    #[ts(type = "number")]
+    pub uptime_secs: u64,
+    pub providers: Vec<ProviderMetrics>,
+    pub caches: Vec<CacheMetrics>,
+    pub ticker: TickMetrics,
+    /// Size of the database, pages in use times page size
+    #[ts(type = "number")]
+    pub database_bytes: u64,
+    pub tables: Vec<TableSize>,
+}
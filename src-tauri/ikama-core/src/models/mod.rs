@@ -0,0 +1,75 @@
+pub mod prayer;
+pub mod prayer_window;
+pub mod mosque;
+pub mod mosque_compare;
+pub mod mosque_context;
+pub mod geo_location;
+pub mod provider;
+pub mod hijri;
+pub mod adhan_sound;
+pub mod hotkey;
+pub mod event;
+pub mod crowd;
+pub mod day_summary;
+pub mod family;
+pub mod fajr_alarm;
+pub mod calendar_sync;
+pub mod catalog;
+pub mod config_file;
+pub mod iqama_report;
+pub mod kiosk;
+pub mod legacy_import;
+pub mod local_server;
+pub mod metrics;
+pub mod notification_profile;
+pub mod notification_template;
+pub mod onboarding;
+pub mod prayer_log;
+pub mod raw_payload;
+pub mod goal;
+pub mod schedule_change;
+pub mod schedule_edit;
+pub mod selected_mosque;
+pub mod speech;
+pub mod travel;
+pub mod update;
+pub mod upstream_issue;
+pub mod write_journal;
+
+pub use prayer::*;
+pub use prayer_window::*;
+pub use mosque::*;
+pub use mosque_compare::*;
+pub use mosque_context::*;
+pub use geo_location::*;
+pub use provider::*;
+pub use hijri::*;
+pub use adhan_sound::*;
+pub use hotkey::*;
+pub use event::*;
+pub use crowd::*;
+pub use day_summary::*;
+pub use family::*;
+pub use fajr_alarm::*;
+pub use calendar_sync::*;
+pub use catalog::*;
+pub use config_file::*;
+pub use iqama_report::*;
+pub use kiosk::*;
+pub use legacy_import::*;
+pub use local_server::*;
+pub use metrics::*;
+pub use notification_profile::*;
+pub use notification_template::*;
+pub use onboarding::*;
+pub use prayer_log::*;
+pub use raw_payload::*;
+pub use goal::*;
+pub use schedule_change::*;
+pub use schedule_edit::*;
+pub use selected_mosque::*;
+pub use speech::*;
+pub use travel::*;
+pub use update::*;
+pub use upstream_issue::*;
+pub use write_journal::*;
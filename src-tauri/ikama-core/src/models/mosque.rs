@@ -0,0 +1,279 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use std::collections::HashMap;
+
+use super::{GeoLocation, PrayerName, PrayerTimes};
+
+/// Mosque entity
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, optional_fields)]
+pub struct Mosque {
+    pub id: String,
+    pub name: String,
+    pub address: Option<String>,
+    pub city: Option<String>,
+    pub country: Option<String>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub is_favorite: bool,
+    pub last_accessed: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub facilities: MosqueFacilities,
+}
+
+impl Mosque {
+    pub fn new(id: String, name: String) -> Self {
+        Self {
+            id,
+            name,
+            address: None,
+            city: None,
+            country: None,
+            latitude: None,
+            longitude: None,
+            is_favorite: false,
+            last_accessed: None,
+            facilities: MosqueFacilities::default(),
+        }
+    }
+}
+
+/// Facilities a mosque offers; `None` means unknown
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export, optional_fields)]
+pub struct MosqueFacilities {
+    pub womens_area: Option<bool>,
+    pub wheelchair_access: Option<bool>,
+    pub wudu: Option<bool>,
+    pub parking: Option<bool>,
+}
+
+impl MosqueFacilities {
+    /// Overlay values the user entered on top of what the provider reported
+    pub fn merged(self, user: &MosqueFacilities) -> Self {
+        Self {
+            womens_area: user.womens_area.or(self.womens_area),
+            wheelchair_access: user.wheelchair_access.or(self.wheelchair_access),
+            wudu: user.wudu.or(self.wudu),
+            parking: user.parking.or(self.parking),
+        }
+    }
+
+    /// True if every facility required (`Some(true)`) is known to be available
+    pub fn satisfies(&self, required: &MosqueFacilities) -> bool {
+        let ok = |required: Option<bool>, actual: Option<bool>| required != Some(true) || actual == Some(true);
+
+        ok(required.womens_area, self.womens_area)
+            && ok(required.wheelchair_access, self.wheelchair_access)
+            && ok(required.wudu, self.wudu)
+            && ok(required.parking, self.parking)
+    }
+}
+
+/// Which congregational prayers a mosque holds
+/// Musallas often only host some prayers, or only Jumuah
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct PrayerAvailability {
+    /// Temporarily closed: no prayer is held
+    #[serde(default)]
+    pub closed: bool,
+    /// Explicit per-prayer flags; prayers not listed are assumed held
+    #[serde(default)]
+    pub prayers: HashMap<PrayerName, bool>,
+}
+
+impl PrayerAvailability {
+    /// Infer from a schedule: when a mosque publishes iqamas for some prayers only,
+    /// the others aren't held there. Schedules without any iqama say nothing.
+    pub fn derive(schedule: &PrayerTimes) -> Self {
+        let prayers = schedule.all_prayers();
+        if prayers.iter().all(|p| !p.has_iqama()) {
+            return Self::default();
+        }
+
+        Self {
+            closed: false,
+            prayers: prayers.into_iter().map(|p| (p.name, p.has_iqama())).collect(),
+        }
+    }
+
+    /// What a schedule says, with the user's edits for the mosque on top when there are some
+    pub fn for_schedule(schedule: &PrayerTimes, user: Option<&PrayerAvailability>) -> Self {
+        let derived = Self::derive(schedule);
+        match user {
+            Some(user) => derived.merged(user),
+            None => derived,
+        }
+    }
+
+    /// Overlay the user's edits on top of derived flags
+    pub fn merged(mut self, user: &PrayerAvailability) -> Self {
+        self.closed = user.closed;
+        self.prayers.extend(user.prayers.iter().map(|(k, v)| (*k, *v)));
+        self
+    }
+
+    pub fn holds(&self, prayer: PrayerName) -> bool {
+        !self.closed && self.prayers.get(&prayer).copied().unwrap_or(true)
+    }
+
+    /// Holds Jumuah but none of the daily prayers
+    pub fn is_jumuah_only(&self) -> bool {
+        self.holds(PrayerName::Jumuah) && PrayerName::DAILY.iter().all(|p| !self.holds(*p))
+    }
+}
+
+/// How long after iqama a mosque's imam actually starts, as set by the user
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export, optional_fields)]
+pub struct MosqueStartLag {
+    /// Lag for prayers without their own, in seconds
+    #[serde(default)]
+    #[ts(as = "Option<f64>")]
+    pub default_secs: Option<i64>,
+    /// Per-prayer lag in seconds
+    #[serde(default)]
+    #[ts(as = "HashMap<PrayerName, f64>")]
+    pub prayers: HashMap<PrayerName, i64>,
+}
+
+impl MosqueStartLag {
+    pub const MAX_SECS: i64 = 30 * 60;
+
+    pub fn is_valid(&self) -> bool {
+        self.default_secs
+            .iter()
+            .chain(self.prayers.values())
+            .all(|secs| (0..=Self::MAX_SECS).contains(secs))
+    }
+
+    /// Lag per prayer: set for the prayer, else learned from iqama reports, else the mosque default
+    pub fn resolve(&self, learned: &HashMap<PrayerName, i64>) -> HashMap<PrayerName, i64> {
+        PrayerName::DAILY
+            .iter()
+            .chain([PrayerName::Jumuah].iter())
+            .filter_map(|prayer| {
+                let lag = self
+                    .prayers
+                    .get(prayer)
+                    .or_else(|| learned.get(prayer))
+                    .copied()
+                    .or(self.default_secs)?;
+                Some((*prayer, lag))
+            })
+            .collect()
+    }
+}
+
+/// Mosque with prayer times
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MosqueWithPrayerTimes {
+    #[serde(flatten)]
+    pub mosque: Mosque,
+    pub prayer_times: Option<PrayerTimes>,
+}
+
+/// Search result for mosques
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct MosqueSearchResult {
+    pub mosques: Vec<Mosque>,
+    pub total: usize,
+}
+
+/// Order of mosque search results
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum MosqueSort {
+    /// Best match first, see `rank_mosques`
+    #[default]
+    Relevance,
+    /// Nearest first; needs the user's location
+    Distance,
+    Name,
+}
+
+/// Paging, filters and order of a mosque search
+/// Pages start at 1; without `page` or `limit` every match is returned
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize, TS)]
+#[ts(export, optional_fields)]
+pub struct MosqueSearchParams {
+    pub page: Option<u32>,
+    pub limit: Option<u32>,
+    pub country: Option<String>,
+    pub city: Option<String>,
+    /// Keep only mosques whose cached schedules do (or don't) publish iqama times;
+    /// mosques without a cached schedule are left out either way
+    pub has_iqama: Option<bool>,
+    /// Keep only mosques within this many km of the user's location, when it is known
+    pub max_distance_km: Option<u32>,
+    pub sort: Option<MosqueSort>,
+}
+
+impl MosqueSearchParams {
+    pub const DEFAULT_LIMIT: u32 = 50;
+    pub const MAX_LIMIT: u32 = 200;
+
+    pub fn is_paged(&self) -> bool {
+        self.page.is_some() || self.limit.is_some()
+    }
+
+    pub fn page(&self) -> u32 {
+        self.page.unwrap_or(1).max(1)
+    }
+
+    pub fn limit(&self) -> u32 {
+        self.limit.unwrap_or(Self::DEFAULT_LIMIT).clamp(1, Self::MAX_LIMIT)
+    }
+
+    /// Whether a mosque passes the country and city filters
+    pub fn matches(&self, mosque: &Mosque) -> bool {
+        let field_matches = |filter: &Option<String>, value: &Option<String>| match filter {
+            Some(filter) => value
+                .as_deref()
+                .is_some_and(|v| v.trim().eq_ignore_ascii_case(filter.trim())),
+            None => true,
+        };
+        field_matches(&self.country, &mosque.country) && field_matches(&self.city, &mosque.city)
+    }
+
+    /// Whether a mosque is within `max_distance_km`; mosques without coordinates fail a distance filter
+    pub fn within_distance(&self, mosque: &Mosque, location: Option<&GeoLocation>) -> bool {
+        let (Some(max_km), Some(location)) = (self.max_distance_km, location) else {
+            return true;
+        };
+
+        match (mosque.latitude, mosque.longitude) {
+            (Some(latitude), Some(longitude)) => {
+                location.distance_to(&GeoLocation::new(latitude, longitude)) <= max_km as f64
+            }
+            _ => false,
+        }
+    }
+
+    /// Filter a full result list and cut out the requested page
+    pub fn paginate(&self, mosques: Vec<Mosque>) -> MosqueSearchResult {
+        let matching: Vec<Mosque> = mosques.into_iter().filter(|m| self.matches(m)).collect();
+        let total = matching.len();
+        let mosques = if self.is_paged() {
+            let skip = (self.page() - 1) as usize * self.limit() as usize;
+            matching.into_iter().skip(skip).take(self.limit() as usize).collect()
+        } else {
+            matching
+        };
+
+        MosqueSearchResult { mosques, total }
+    }
+}
+
+/// Favorite mosque entry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FavoriteMosque {
+    pub mosque: Mosque,
+    pub added_at: DateTime<Utc>,
+    pub is_active: bool,
+}
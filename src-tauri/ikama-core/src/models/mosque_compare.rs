@@ -0,0 +1,118 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use super::{GeoLocation, Mosque, PrayerName, PrayerTimes};
+
+/// One prayer at two mosques; differences are mosque B minus mosque A, in minutes
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export, optional_fields)]
+pub struct PrayerDiff {
+    pub prayer: PrayerName,
+    pub adhan_a: DateTime<Utc>,
+    pub adhan_b: DateTime<Utc>,
+    #[ts(type = "number")]
+    pub adhan_diff_minutes: i64,
+    pub iqama_a: Option<DateTime<Utc>>,
+    pub iqama_b: Option<DateTime<Utc>>,
+    /// Unset unless both mosques hold an iqama for the prayer
+    #[ts(type = "number")]
+    pub iqama_diff_minutes: Option<i64>,
+}
+
+/// Side-by-side schedules of two mosques on one day, to choose between nearby mosques
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, optional_fields)]
+pub struct MosqueComparison {
+    pub date: NaiveDate,
+    pub mosque_a: Mosque,
+    pub mosque_b: Mosque,
+    /// Distance between the two mosques in km, when both have coordinates
+    pub distance_km: Option<f64>,
+    /// Distance from the given location to each mosque in km
+    pub distance_a_km: Option<f64>,
+    pub distance_b_km: Option<f64>,
+    /// Jumuah is only compared when both mosques hold it
+    pub prayers: Vec<PrayerDiff>,
+}
+
+fn mosque_location(mosque: &Mosque) -> Option<GeoLocation> {
+    Some(GeoLocation::new(mosque.latitude?, mosque.longitude?))
+}
+
+impl MosqueComparison {
+    pub fn new(
+        date: NaiveDate,
+        mosque_a: Mosque,
+        times_a: &PrayerTimes,
+        mosque_b: Mosque,
+        times_b: &PrayerTimes,
+        location: Option<&GeoLocation>,
+    ) -> Self {
+        let (at_a, at_b) = (mosque_location(&mosque_a), mosque_location(&mosque_b));
+        let distance = |from: Option<&GeoLocation>, to: Option<GeoLocation>| Some(from?.distance_to(&to?));
+
+        Self {
+            date,
+            distance_km: distance(at_a.as_ref(), at_b),
+            distance_a_km: distance(location, at_a),
+            distance_b_km: distance(location, at_b),
+            prayers: diff_prayers(times_a, times_b),
+            mosque_a,
+            mosque_b,
+        }
+    }
+}
+
+/// Per-prayer differences between two schedules of the same day
+pub fn diff_prayers(a: &PrayerTimes, b: &PrayerTimes) -> Vec<PrayerDiff> {
+    let mut pairs: Vec<_> = a.all_prayers().into_iter().zip(b.all_prayers()).collect();
+    if let (Some(jumuah_a), Some(jumuah_b)) = (&a.jumuah, &b.jumuah) {
+        pairs.push((jumuah_a, jumuah_b));
+    }
+
+    pairs
+        .into_iter()
+        .map(|(pa, pb)| PrayerDiff {
+            prayer: pa.name,
+            adhan_a: pa.adhan,
+            adhan_b: pb.adhan,
+            adhan_diff_minutes: (pb.adhan - pa.adhan).num_minutes(),
+            iqama_a: pa.iqama,
+            iqama_b: pb.iqama,
+            iqama_diff_minutes: pa.iqama.zip(pb.iqama).map(|(ia, ib)| (ib - ia).num_minutes()),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_diff_and_distance() {
+        let now = Utc::now();
+        let date = NaiveDate::from_ymd_opt(2026, 3, 6).unwrap();
+        let times_a = crate::providers::MockProvider::new().schedule("mock-a", date, now);
+        let mut times_b = times_a.clone();
+        times_b.isha.iqama = times_b.isha.iqama.map(|t| t + Duration::minutes(20));
+        times_b.fajr.iqama = None;
+
+        let mut mosque_a = Mosque::new("mock-a".to_string(), "A".to_string());
+        (mosque_a.latitude, mosque_a.longitude) = (Some(48.8566), Some(2.3522));
+        let mosque_b = Mosque::new("mock-b".to_string(), "B".to_string());
+
+        let here = GeoLocation::new(48.8566, 2.3522);
+        let comparison = MosqueComparison::new(date, mosque_a, &times_a, mosque_b, &times_b, Some(&here));
+        assert_eq!(comparison.distance_km, None);
+        assert_eq!(comparison.distance_a_km.map(|d| d.round()), Some(0.0));
+        assert_eq!(comparison.distance_b_km, None);
+
+        let isha = comparison.prayers.iter().find(|p| p.prayer == PrayerName::Isha).unwrap();
+        assert_eq!((isha.adhan_diff_minutes, isha.iqama_diff_minutes), (0, Some(20)));
+        let fajr = comparison.prayers.iter().find(|p| p.prayer == PrayerName::Fajr).unwrap();
+        assert_eq!(fajr.iqama_diff_minutes, None);
+        assert_eq!(comparison.prayers.len(), 5 + times_a.jumuah.iter().count());
+    }
+}
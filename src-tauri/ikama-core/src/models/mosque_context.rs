@@ -0,0 +1,205 @@
+use std::collections::HashSet;
+
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use super::notification_profile::{weekly_window_covers, NotificationProfiles};
+use super::schedule_edit::parse_wall_clock;
+use super::{DayOfWeek, GeoLocation, NextPrayerResult, DEFAULT_PROFILE};
+
+/// Being this close to a place means being there, whatever the schedule says
+pub const CONTEXT_RADIUS_KM: f64 = 1.5;
+
+/// A named place with its own active mosque, e.g. "home" or "work"
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct MosqueContext {
+    pub label: String,
+    /// Profile whose selected mosque serves the place; home uses the default profile
+    pub profile: String,
+    /// Where the place is, so being there picks it without a schedule
+    pub location: Option<GeoLocation>,
+}
+
+/// When the user is at a place, e.g. weekday office hours at "work"
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct PlaceWindow {
+    /// Label of the place; read from `profile` in settings saved before windows named places
+    #[serde(alias = "profile")]
+    pub place: String,
+    pub days: Vec<DayOfWeek>,
+    /// "HH:MM"; a window ending before it starts runs past midnight
+    pub start: String,
+    pub end: String,
+}
+
+impl PlaceWindow {
+    fn covers(&self, local: NaiveDateTime) -> bool {
+        weekly_window_covers(&self.days, &self.start, &self.end, local)
+    }
+}
+
+/// Places with their own mosque and the weekly schedule of where the user is, stored in the `mosque_contexts` setting
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, optional_fields)]
+pub struct MosqueContexts {
+    #[serde(default)]
+    pub enabled: bool,
+    /// The first place is where the user is outside every window
+    pub places: Vec<MosqueContext>,
+    /// The first window covering a prayer picks the place
+    #[serde(default)]
+    pub schedule: Vec<PlaceWindow>,
+}
+
+/// Why a place's mosque was picked
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum ContextReason {
+    /// The user is near the place
+    Location,
+    /// A schedule window puts the user there at the prayer
+    Schedule,
+    /// No window applies, so the first place
+    Default,
+}
+
+/// Next prayer at the mosque of the place the user is expected to be
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct ContextualNextPrayer {
+    pub label: String,
+    pub profile: String,
+    pub mosque_id: String,
+    pub mosque_name: String,
+    pub reason: ContextReason,
+    pub next: NextPrayerResult,
+}
+
+impl Default for MosqueContexts {
+    fn default() -> Self {
+        let place = |label: &str, profile: &str| MosqueContext {
+            label: label.to_string(),
+            profile: profile.to_string(),
+            location: None,
+        };
+
+        Self {
+            enabled: false,
+            places: vec![place("home", DEFAULT_PROFILE), place("work", "work")],
+            // The work hours of the default notification profiles
+            schedule: NotificationProfiles::default()
+                .schedule
+                .into_iter()
+                .map(|window| PlaceWindow {
+                    place: window.profile,
+                    days: window.days,
+                    start: window.start,
+                    end: window.end,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl MosqueContexts {
+    /// Labels must be unique and profiles set, and windows must name a place with valid times
+    pub fn validate(&self) -> Result<(), String> {
+        let mut labels = HashSet::new();
+        for place in &self.places {
+            if place.label.trim().is_empty() {
+                return Err("A place needs a label".to_string());
+            }
+            if place.profile.trim().is_empty() {
+                return Err(format!("Place {:?} needs a profile", place.label));
+            }
+            if !labels.insert(place.label.as_str()) {
+                return Err(format!("Place {:?} is defined twice", place.label));
+            }
+        }
+
+        for window in &self.schedule {
+            if !labels.contains(window.place.as_str()) {
+                return Err(format!("Unknown place {:?}", window.place));
+            }
+            parse_wall_clock(&window.start)?;
+            parse_wall_clock(&window.end)?;
+        }
+        Ok(())
+    }
+
+    /// Nearest place within `CONTEXT_RADIUS_KM` of a location
+    pub fn place_near(&self, location: &GeoLocation) -> Option<&MosqueContext> {
+        self.places
+            .iter()
+            .filter_map(|place| Some((place, place.location?.distance_to(location))))
+            .filter(|(_, km)| *km <= CONTEXT_RADIUS_KM)
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(place, _)| place)
+    }
+
+    /// Place the schedule puts the user at a local time, the first place when no window covers it
+    pub fn place_at(&self, local: NaiveDateTime) -> Option<(&MosqueContext, ContextReason)> {
+        let scheduled = self
+            .schedule
+            .iter()
+            .filter(|window| window.covers(local))
+            .find_map(|window| self.places.iter().find(|p| p.label == window.place));
+
+        match scheduled {
+            Some(place) => Some((place, ContextReason::Schedule)),
+            None => self.places.first().map(|place| (place, ContextReason::Default)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn at(day: u32, hour: u32) -> NaiveDateTime {
+        // October 2026: the 12th is a Monday
+        NaiveDate::from_ymd_opt(2026, 10, day).unwrap().and_hms_opt(hour, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_place_follows_schedule_and_location() {
+        let mut contexts = MosqueContexts::default();
+        assert!(contexts.validate().is_ok());
+
+        let (place, reason) = contexts.place_at(at(12, 13)).unwrap();
+        assert_eq!((place.label.as_str(), reason), ("work", ContextReason::Schedule));
+        let (place, reason) = contexts.place_at(at(12, 19)).unwrap();
+        assert_eq!((place.label.as_str(), reason), ("home", ContextReason::Default));
+        assert_eq!(contexts.place_at(at(17, 13)).unwrap().0.label, "home");
+
+        let office = GeoLocation::new(48.8738, 2.2950);
+        assert!(contexts.place_near(&office).is_none());
+        contexts.places[1].location = Some(office);
+        contexts.places[0].location = Some(GeoLocation::new(48.8156, 2.3631));
+        assert_eq!(contexts.place_near(&GeoLocation::new(48.8745, 2.2960)).unwrap().label, "work");
+        assert!(contexts.place_near(&GeoLocation::new(48.8462, 2.3372)).is_none());
+    }
+
+    #[test]
+    fn test_contexts_validation() {
+        let mut contexts = MosqueContexts::default();
+        contexts.schedule[0].place = "gym".to_string();
+        assert!(contexts.validate().unwrap_err().contains("gym"));
+
+        let mut contexts = MosqueContexts::default();
+        contexts.places[1].label = "home".to_string();
+        assert!(contexts.validate().is_err());
+
+        let mut contexts = MosqueContexts::default();
+        contexts.schedule[0].end = "25:00".to_string();
+        assert!(contexts.validate().is_err());
+
+        let saved = r#"{"profile":"work","days":["mon"],"start":"09:00","end":"17:00"}"#;
+        assert_eq!(serde_json::from_str::<PlaceWindow>(saved).unwrap().place, "work");
+    }
+}
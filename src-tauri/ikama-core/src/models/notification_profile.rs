@@ -0,0 +1,271 @@
+use std::collections::HashSet;
+
+use chrono::{Datelike, Duration, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use super::schedule_edit::parse_wall_clock;
+use super::{DayOfWeek, PrayerName};
+
+/// How reminders behave while a profile is active
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct NotificationProfile {
+    /// e.g. "work", "weekend", "travel"
+    pub name: String,
+    /// Notifications play a sound; silent (vibrate-only on phones) otherwise
+    pub sound: bool,
+    /// Reminders are read aloud when spoken announcements are on
+    pub speech: bool,
+    /// Prayers that get reminders
+    pub prayers: Vec<PrayerName>,
+}
+
+/// When a profile applies automatically, e.g. work hours on weekdays
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct ProfileWindow {
+    pub profile: String,
+    pub days: Vec<DayOfWeek>,
+    /// "HH:MM"; a window ending before it starts runs past midnight
+    pub start: String,
+    pub end: String,
+}
+
+/// Notification profiles and the weekly schedule selecting them, stored in the `notification_profiles` setting
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export, optional_fields)]
+pub struct NotificationProfiles {
+    #[serde(default)]
+    pub enabled: bool,
+    pub profiles: Vec<NotificationProfile>,
+    /// The first window covering the current time picks the profile
+    #[serde(default)]
+    pub schedule: Vec<ProfileWindow>,
+    /// Profile chosen by hand, e.g. "travel"; overrides the schedule
+    #[serde(default)]
+    pub manual: Option<String>,
+}
+
+impl Default for NotificationProfiles {
+    fn default() -> Self {
+        let all = || {
+            let mut prayers = PrayerName::DAILY.to_vec();
+            prayers.push(PrayerName::Jumuah);
+            prayers
+        };
+        let profile = |name: &str, sound, speech, prayers| NotificationProfile {
+            name: name.to_string(),
+            sound,
+            speech,
+            prayers,
+        };
+
+        Self {
+            enabled: false,
+            profiles: vec![
+                profile("work", false, false, all()),
+                profile("weekend", true, true, all()),
+                profile("travel", false, false, all()),
+            ],
+            schedule: vec![ProfileWindow {
+                profile: "work".to_string(),
+                days: vec![
+                    DayOfWeek::Mon,
+                    DayOfWeek::Tue,
+                    DayOfWeek::Wed,
+                    DayOfWeek::Thu,
+                    DayOfWeek::Fri,
+                ],
+                start: "09:00".to_string(),
+                end: "17:00".to_string(),
+            }],
+            manual: None,
+        }
+    }
+}
+
+impl NotificationProfiles {
+    /// Profile names must be unique, and windows must name one of them with valid times
+    pub fn validate(&self) -> Result<(), String> {
+        let mut names = HashSet::new();
+        for profile in &self.profiles {
+            if profile.name.trim().is_empty() {
+                return Err("A profile needs a name".to_string());
+            }
+            if !names.insert(profile.name.as_str()) {
+                return Err(format!("Profile {:?} is defined twice", profile.name));
+            }
+        }
+
+        let known = |name: &str| {
+            names
+                .contains(name)
+                .then_some(())
+                .ok_or_else(|| format!("Unknown profile {:?}", name))
+        };
+        for window in &self.schedule {
+            known(&window.profile)?;
+            parse_wall_clock(&window.start)?;
+            parse_wall_clock(&window.end)?;
+        }
+        if let Some(manual) = &self.manual {
+            known(manual)?;
+        }
+        Ok(())
+    }
+
+    /// Profile in effect at a local time, `None` when profiles are off or none applies
+    pub fn active(&self, local: NaiveDateTime) -> Option<&NotificationProfile> {
+        if !self.enabled {
+            return None;
+        }
+        let name = match &self.manual {
+            Some(manual) => manual,
+            None => &self.schedule.iter().find(|w| w.covers(local))?.profile,
+        };
+        self.profiles.iter().find(|p| &p.name == name)
+    }
+}
+
+impl ProfileWindow {
+    pub(crate) fn covers(&self, local: NaiveDateTime) -> bool {
+        weekly_window_covers(&self.days, &self.start, &self.end, local)
+    }
+}
+
+/// Whether a weekly "HH:MM" window on `days` covers a local time; invalid times cover nothing
+pub(crate) fn weekly_window_covers(days: &[DayOfWeek], start: &str, end: &str, local: NaiveDateTime) -> bool {
+    let (Ok(start), Ok(end)) = (parse_wall_clock(start), parse_wall_clock(end)) else {
+        return false;
+    };
+    let on = |date: chrono::NaiveDate| days.contains(&date.weekday().into());
+    let time = local.time();
+
+    if start <= end {
+        on(local.date()) && time >= start && time < end
+    } else {
+        // Past midnight: the evening of a listed day, or the early hours after one
+        (on(local.date()) && time >= start) || (on(local.date() - Duration::days(1)) && time < end)
+    }
+}
+
+/// Reminder settings for one mosque, e.g. silence a mosque near work that is only browsed now and then
+/// Applied on top of the notification profile; overrides only narrow, so a silent profile stays silent
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export, optional_fields)]
+pub struct MosqueNotificationOverride {
+    pub mosque_id: String,
+    /// Any reminder at all while this mosque is selected
+    pub enabled: bool,
+    pub sound: bool,
+    pub speech: bool,
+    /// Prayers that get reminders at this mosque; unset keeps the profile's
+    pub prayers: Option<Vec<PrayerName>>,
+    /// Minutes before adhan and iqama to remind, replacing the usual ones
+    #[ts(as = "Option<Vec<f64>>")]
+    pub reminder_minutes_before: Option<Vec<i64>>,
+}
+
+impl MosqueNotificationOverride {
+    /// An override that mutes every reminder for a mosque
+    pub fn muted(mosque_id: impl Into<String>) -> Self {
+        Self {
+            mosque_id: mosque_id.into(),
+            enabled: false,
+            sound: false,
+            speech: false,
+            prayers: None,
+            reminder_minutes_before: None,
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.mosque_id.trim().is_empty() {
+            return Err("An override needs a mosque".to_string());
+        }
+        match &self.reminder_minutes_before {
+            Some(minutes) if minutes.is_empty() => {
+                Err("Set at least one reminder time, or none to keep the usual ones".to_string())
+            }
+            Some(minutes) => validate_reminder_minutes(minutes),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Reminder times, in minutes before adhan and iqama, must each be 1 to 120
+pub fn validate_reminder_minutes(minutes: &[i64]) -> Result<(), String> {
+    match minutes.iter().find(|m| !(1..=120).contains(*m)) {
+        Some(m) => Err(format!("Reminders are 1 to 120 minutes before, got {}", m)),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn at(day: u32, hour: u32) -> NaiveDateTime {
+        // October 2026: the 12th is a Monday
+        NaiveDate::from_ymd_opt(2026, 10, day).unwrap().and_hms_opt(hour, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_active_profile_follows_schedule() {
+        let mut profiles = NotificationProfiles {
+            enabled: true,
+            ..Default::default()
+        };
+        profiles.schedule.push(ProfileWindow {
+            profile: "weekend".to_string(),
+            days: vec![DayOfWeek::Fri],
+            start: "22:00".to_string(),
+            end: "06:00".to_string(),
+        });
+        assert!(profiles.validate().is_ok());
+
+        assert_eq!(profiles.active(at(12, 10)).unwrap().name, "work");
+        assert!(profiles.active(at(12, 18)).is_none());
+        assert!(profiles.active(at(17, 10)).is_none());
+        // Friday night runs into Saturday morning
+        assert_eq!(profiles.active(at(16, 23)).unwrap().name, "weekend");
+        assert_eq!(profiles.active(at(17, 5)).unwrap().name, "weekend");
+
+        profiles.manual = Some("travel".to_string());
+        assert_eq!(profiles.active(at(17, 10)).unwrap().name, "travel");
+        assert!(NotificationProfiles::default().active(at(12, 10)).is_none());
+    }
+
+    #[test]
+    fn test_profiles_validation() {
+        let profiles = NotificationProfiles {
+            manual: Some("holiday".to_string()),
+            ..Default::default()
+        };
+        assert!(profiles.validate().is_err());
+
+        let mut profiles = NotificationProfiles::default();
+        profiles.schedule[0].end = "25:00".to_string();
+        assert!(profiles.validate().is_err());
+
+        let mut profiles = NotificationProfiles::default();
+        profiles.profiles.push(profiles.profiles[0].clone());
+        assert!(profiles.validate().is_err());
+    }
+
+    #[test]
+    fn test_mosque_override_validation() {
+        let mut mosque = MosqueNotificationOverride::muted("work-area-mosque");
+        assert!(mosque.validate().is_ok());
+
+        mosque.reminder_minutes_before = Some(vec![10, 0]);
+        assert!(mosque.validate().is_err());
+        mosque.reminder_minutes_before = Some(Vec::new());
+        assert!(mosque.validate().is_err());
+        mosque.reminder_minutes_before = Some(vec![15, 5]);
+        assert!(mosque.validate().is_ok());
+        assert!(MosqueNotificationOverride::muted(" ").validate().is_err());
+    }
+}
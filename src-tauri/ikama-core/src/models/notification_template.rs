@@ -0,0 +1,112 @@
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+/// Placeholders a notification template may use
+pub const TEMPLATE_PLACEHOLDERS: [&str; 4] = ["prayer", "minutes", "mosque", "rakah"];
+
+/// Longest template accepted, in characters
+const MAX_TEMPLATE_LENGTH: usize = 200;
+
+/// Custom wording for one kind of notification; an unset part keeps the built-in text
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export, optional_fields)]
+pub struct NotificationTemplate {
+    pub title: Option<String>,
+    pub body: Option<String>,
+}
+
+/// Custom notification wording, stored in the `notification_templates` setting
+/// Templates use `{prayer}`, `{minutes}`, `{mosque}` and `{rakah}`, e.g. "{prayer} at {mosque} in {minutes} min"
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export, optional_fields)]
+pub struct NotificationTemplates {
+    #[serde(default)]
+    pub adhan_reminder: Option<NotificationTemplate>,
+    #[serde(default)]
+    pub iqama_reminder: Option<NotificationTemplate>,
+    #[serde(default)]
+    pub prayer_start: Option<NotificationTemplate>,
+}
+
+impl NotificationTemplates {
+    /// Every template must be non-empty, not too long, and use known placeholders only
+    pub fn validate(&self) -> Result<(), String> {
+        let templates = [
+            ("adhan_reminder", &self.adhan_reminder),
+            ("iqama_reminder", &self.iqama_reminder),
+            ("prayer_start", &self.prayer_start),
+        ];
+        for (kind, template) in templates {
+            let Some(template) = template else { continue };
+            for (part, text) in [("title", &template.title), ("body", &template.body)] {
+                if let Some(text) = text {
+                    validate_template(text).map_err(|e| format!("{} {}: {}", kind, part, e))?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn validate_template(text: &str) -> Result<(), String> {
+    if text.trim().is_empty() {
+        return Err("template is empty".to_string());
+    }
+    if text.chars().count() > MAX_TEMPLATE_LENGTH {
+        return Err(format!("template is longer than {} characters", MAX_TEMPLATE_LENGTH));
+    }
+
+    let mut rest = text;
+    while let Some(open) = rest.find(['{', '}']) {
+        if rest[open..].starts_with('}') {
+            return Err("unmatched '}'".to_string());
+        }
+        let after = &rest[open + 1..];
+        let close = after.find('}').ok_or("unclosed '{'")?;
+        let name = &after[..close];
+        if !TEMPLATE_PLACEHOLDERS.contains(&name) {
+            return Err(format!(
+                "unknown placeholder {{{}}}, use {}",
+                name,
+                TEMPLATE_PLACEHOLDERS.map(|p| format!("{{{}}}", p)).join(", ")
+            ));
+        }
+        rest = &after[close + 1..];
+    }
+    Ok(())
+}
+
+/// Fill a template's placeholders
+pub fn render_template(text: &str, values: &[(&str, String)]) -> String {
+    values
+        .iter()
+        .fold(text.to_string(), |out, (name, value)| out.replace(&format!("{{{}}}", name), value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_template_validation() {
+        let templates = |body: &str| NotificationTemplates {
+            iqama_reminder: Some(NotificationTemplate {
+                title: None,
+                body: Some(body.to_string()),
+            }),
+            ..Default::default()
+        };
+
+        assert!(templates("{prayer} at {mosque} in {minutes} min, {rakah} rakah").validate().is_ok());
+        assert!(templates("Plain text").validate().is_ok());
+        assert!(templates("  ").validate().is_err());
+        assert!(templates("{prayer} in {minute} min").validate().unwrap_err().contains("{minute}"));
+        assert!(templates("{prayer").validate().is_err());
+        assert!(templates("prayer}").validate().is_err());
+        assert!(templates(&"x".repeat(201)).validate().is_err());
+
+        let values = [("prayer", "Isha".to_string()), ("minutes", "10".to_string())];
+        let rendered = render_template("{prayer} in {minutes} min", &values);
+        assert_eq!(rendered, "Isha in 10 min");
+    }
+}
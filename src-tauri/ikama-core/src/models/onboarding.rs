@@ -0,0 +1,119 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use super::{GeoLocation, Mosque, SelectedMosque};
+
+/// How the onboarding region was found
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum RegionSource {
+    /// Position the app passed in, e.g. from the system's location services
+    Device,
+    /// Approximate location of the public IP address
+    Ip,
+    /// The saved default country, when the IP lookup failed
+    Settings,
+}
+
+impl RegionSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Device => "device",
+            Self::Ip => "ip",
+            Self::Settings => "settings",
+        }
+    }
+}
+
+impl fmt::Display for RegionSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Where the user probably is, to preselect the country and search nearby
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, optional_fields)]
+pub struct DetectedRegion {
+    /// Two-letter country code, upper case
+    pub country: String,
+    pub city: Option<String>,
+    /// City-level accuracy at best; unknown when falling back to settings
+    pub location: Option<GeoLocation>,
+    pub timezone: Option<String>,
+    pub source: RegionSource,
+}
+
+/// A catalog mosque suggested during onboarding, nearest first
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct NearbyMosque {
+    pub mosque: Mosque,
+    pub distance_km: f64,
+}
+
+/// Mosques around the detected or given location
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct OnboardingSuggestions {
+    pub region: DetectedRegion,
+    pub mosques: Vec<NearbyMosque>,
+}
+
+/// What finishing onboarding saved
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, optional_fields)]
+pub struct OnboardingResult {
+    pub selected: SelectedMosque,
+    pub default_country: String,
+    /// Today's schedule could not be fetched yet; the app retries on its own
+    pub fetch_error: Option<String>,
+}
+
+/// Catalog mosques within `radius_km` of `location`, nearest first, at most `limit`
+pub fn nearest_mosques(mosques: Vec<Mosque>, location: &GeoLocation, radius_km: f64, limit: usize) -> Vec<NearbyMosque> {
+    let mut nearby: Vec<NearbyMosque> = mosques
+        .into_iter()
+        .filter_map(|mosque| {
+            let distance_km = location.distance_to(&GeoLocation::new(mosque.latitude?, mosque.longitude?));
+            (distance_km <= radius_km).then_some(NearbyMosque { mosque, distance_km })
+        })
+        .collect();
+    nearby.sort_by(|a, b| a.distance_km.total_cmp(&b.distance_km));
+    nearby.truncate(limit);
+    nearby
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mosque(id: &str, latitude: f64, longitude: f64) -> Mosque {
+        Mosque {
+            latitude: Some(latitude),
+            longitude: Some(longitude),
+            ..Mosque::new(id.to_string(), id.to_string())
+        }
+    }
+
+    #[test]
+    fn test_nearest_mosques() {
+        let paris = GeoLocation::new(48.8566, 2.3522);
+        let mosques = vec![
+            mosque("lyon", 45.764, 4.8357),
+            mosque("saint-denis", 48.9362, 2.3574),
+            mosque("grande-mosquee", 48.8420, 2.3551),
+            Mosque::new("no-coordinates".to_string(), "No coordinates".to_string()),
+        ];
+
+        let nearby = nearest_mosques(mosques.clone(), &paris, 50.0, 10);
+        let ids: Vec<&str> = nearby.iter().map(|n| n.mosque.id.as_str()).collect();
+        assert_eq!(ids, ["grande-mosquee", "saint-denis"]);
+        assert!(nearby[0].distance_km < 2.0);
+
+        assert_eq!(nearest_mosques(mosques, &paris, 1000.0, 1).len(), 1);
+    }
+}
@@ -0,0 +1,757 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+use super::{AsrMethod, IshaEnd, PrayerAvailability};
+
+/// The daily prayers plus Jumuah
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub enum PrayerName {
+    Fajr,
+    Dhuhr,
+    Asr,
+    Maghrib,
+    Isha,
+    Jumuah,
+}
+
+impl PrayerName {
+    pub const DAILY: [PrayerName; 5] = [
+        PrayerName::Fajr,
+        PrayerName::Dhuhr,
+        PrayerName::Asr,
+        PrayerName::Maghrib,
+        PrayerName::Isha,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PrayerName::Fajr => "Fajr",
+            PrayerName::Dhuhr => "Dhuhr",
+            PrayerName::Asr => "Asr",
+            PrayerName::Maghrib => "Maghrib",
+            PrayerName::Isha => "Isha",
+            PrayerName::Jumuah => "Jumuah",
+        }
+    }
+
+    /// Canonical prayer for a name as providers spell it ("Zuhr", "Dohr", "الظهر", "Salat al-Asr")
+    pub fn normalize(raw: &str) -> Option<Self> {
+        let key: String = raw
+            .trim()
+            .to_lowercase()
+            .chars()
+            .filter(|c| !matches!(c, ' ' | '\'' | '’' | '`' | '-' | '_' | '.'))
+            .collect();
+
+        let lookup = |key: &str| {
+            PRAYER_NAME_ALIASES
+                .iter()
+                .find(|(_, aliases)| aliases.contains(&key))
+                .map(|(name, _)| *name)
+        };
+
+        lookup(&key).or_else(|| {
+            SALAT_PREFIXES
+                .iter()
+                .find_map(|prefix| lookup(key.strip_prefix(prefix)?))
+        })
+    }
+}
+
+/// Spellings seen across providers and languages, lowercased without spaces or punctuation
+const PRAYER_NAME_ALIASES: [(PrayerName, &[&str]); 6] = [
+    (
+        PrayerName::Fajr,
+        &["fajr", "fadjr", "fajar", "fejr", "subh", "sobh", "subuh", "sabah", "الفجر", "فجر", "الصبح"],
+    ),
+    (
+        PrayerName::Dhuhr,
+        &[
+            "dhuhr", "zuhr", "zohr", "dohr", "duhr", "thuhr", "dhuhur", "zuhur", "dhohr", "zuhar", "johor", "öğle",
+            "ogle", "الظهر", "ظهر",
+        ],
+    ),
+    (PrayerName::Asr, &["asr", "asar", "aser", "ikindi", "العصر", "عصر"]),
+    (
+        PrayerName::Maghrib,
+        &["maghrib", "magrib", "maghreb", "mughrib", "akşam", "aksam", "المغرب", "مغرب"],
+    ),
+    (PrayerName::Isha, &["isha", "ishaa", "esha", "icha", "isya", "yatsı", "yatsi", "العشاء", "عشاء"]),
+    (
+        PrayerName::Jumuah,
+        &["jumuah", "jumua", "jummah", "jumma", "jumah", "juma", "jumaa", "joumouaa", "cuma", "الجمعة", "جمعة"],
+    ),
+];
+
+/// Prefixes stripped before a second lookup, as in "Salat al-Zuhr" or "صلاة الظهر"
+const SALAT_PREFIXES: [&str; 6] = ["salatul", "salatal", "salat", "salah", "al", "صلاة"];
+
+impl fmt::Display for PrayerName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for PrayerName {
+    type Err = String;
+
+    /// Case-insensitive and accepts provider spellings, so "fajr", "FAJR" and "Subh" all parse
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        PrayerName::normalize(s).ok_or_else(|| format!("Unknown prayer: {}", s))
+    }
+}
+
+/// Live state of a congregational prayer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum RakahStatus {
+    NotStarted,
+    InProgress,
+    /// Ended within the post-prayer display window
+    RecentlyFinished,
+    LikelyFinished,
+    /// No iqama time, so progress can't be estimated
+    NotAvailable,
+}
+
+/// Where the prayer will be when the user arrives
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum ArrivalStatus {
+    BeforeStart,
+    InProgress,
+    AfterEstimatedEnd,
+    IqamaUnavailable,
+}
+
+/// Configuration for the PrayerEngine
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrayerEngineConfig {
+    pub rakah_duration_seconds: i64,
+    /// How much a rakah may be shorter or longer than `rakah_duration_seconds` (default: 30 seconds)
+    pub rakah_duration_spread_seconds: i64,
+    pub start_lag_seconds: i64,
+    /// How far the actual start may be from the expected one without iqama reports (default: 1 minute)
+    pub start_lag_spread_seconds: i64,
+    pub buffer_before_start_seconds: i64,
+    pub grace_seconds: i64,
+    /// How long to show "prayer ended" message after estimated end (default: 28 minutes)
+    pub post_prayer_display_minutes: i64,
+    /// Window where user might still catch the prayer after estimated end (default: 3 minutes)
+    pub catch_up_minutes: i64,
+    /// Extra arrival buffer per crowd level above "moderate" (default: 3 minutes)
+    pub crowd_buffer_seconds_per_level: i64,
+    /// Imsak offset when the provider doesn't supply one (default: 10 minutes)
+    pub imsak_minutes_before_fajr: i64,
+    /// Arrival before the khutbah to find a seat at Jumuah (default: 10 minutes)
+    pub jumuah_arrival_minutes: i64,
+    /// Extra minutes before the khutbah per crowd level above "moderate" (default: 5 minutes)
+    pub jumuah_crowd_minutes_per_level: i64,
+    /// Asr convention the user follows for prayer windows (default: standard)
+    pub asr_method: AsrMethod,
+    /// Whether Isha's window ends at Fajr or at Islamic midnight (default: Fajr)
+    pub isha_end: IshaEnd,
+    /// Countdowns at or under this show seconds, longer ones whole minutes (default: 10 minutes)
+    pub countdown_seconds_threshold_secs: i64,
+    /// Countdowns at or under this read as "now" (default: at zero)
+    pub countdown_now_threshold_secs: i64,
+    pub default_rakah_counts: HashMap<PrayerName, i32>,
+}
+
+impl Default for PrayerEngineConfig {
+    fn default() -> Self {
+        let mut default_rakah_counts = HashMap::new();
+        default_rakah_counts.insert(PrayerName::Fajr, 2);
+        default_rakah_counts.insert(PrayerName::Dhuhr, 4);
+        default_rakah_counts.insert(PrayerName::Asr, 4);
+        default_rakah_counts.insert(PrayerName::Maghrib, 3);
+        default_rakah_counts.insert(PrayerName::Isha, 4);
+        default_rakah_counts.insert(PrayerName::Jumuah, 2);
+
+        Self {
+            rakah_duration_seconds: 144, // 2.4 minutes
+            rakah_duration_spread_seconds: 30,
+            start_lag_seconds: 0,
+            start_lag_spread_seconds: 60,
+            buffer_before_start_seconds: 30,
+            grace_seconds: 60,
+            post_prayer_display_minutes: 28, // Show "ended" message for 28 minutes
+            catch_up_minutes: 3,             // ±3 min window to still catch prayer
+            crowd_buffer_seconds_per_level: 180,
+            imsak_minutes_before_fajr: 10,
+            jumuah_arrival_minutes: 10,
+            jumuah_crowd_minutes_per_level: 5,
+            asr_method: AsrMethod::Standard,
+            isha_end: IshaEnd::Fajr,
+            countdown_seconds_threshold_secs: 10 * 60,
+            countdown_now_threshold_secs: 0,
+            default_rakah_counts,
+        }
+    }
+}
+
+/// Prayer data structure
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, optional_fields)]
+pub struct Prayer {
+    pub name: PrayerName,
+    pub adhan: DateTime<Utc>,
+    pub iqama: Option<DateTime<Utc>>,
+    pub custom_rakah_count: Option<i32>,
+    /// Set when the mosque publishes the iqama as minutes after the adhan ("+15") rather than a clock time
+    #[serde(default)]
+    pub iqama_offset_minutes: Option<i32>,
+}
+
+impl Prayer {
+    pub fn has_iqama(&self) -> bool {
+        self.iqama.is_some()
+    }
+
+    pub fn get_rakah_count(&self, defaults: &HashMap<PrayerName, i32>) -> i32 {
+        self.custom_rakah_count
+            .or_else(|| defaults.get(&self.name).copied())
+            .unwrap_or(4)
+    }
+}
+
+/// Prayer times for a day
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, optional_fields)]
+pub struct PrayerTimes {
+    pub date: DateTime<Utc>,
+    pub fajr: Prayer,
+    pub dhuhr: Prayer,
+    pub asr: Prayer,
+    pub maghrib: Prayer,
+    pub isha: Prayer,
+    pub jumuah: Option<Prayer>,
+    pub mosque_id: Option<String>,
+    pub mosque_name: Option<String>,
+    pub cached_at: Option<DateTime<Utc>>,
+    /// Start of the fast, when the provider publishes it
+    #[serde(default)]
+    pub imsak: Option<DateTime<Utc>>,
+    /// Sunrise, which ends the Fajr window, when the provider publishes it
+    #[serde(default)]
+    pub shuruq: Option<DateTime<Utc>>,
+    /// Asr convention of calculated times; unset for times a mosque publishes
+    #[serde(default)]
+    pub asr_method: Option<AsrMethod>,
+    /// Provider the schedule was fetched from
+    #[serde(default)]
+    pub provider: Option<String>,
+    /// Age and source of the data, set on schedules returned to the UI
+    #[serde(default)]
+    pub freshness: Option<ScheduleFreshness>,
+    /// Data quality notes, e.g. an iqama filled in from the default offset
+    #[serde(default)]
+    pub warnings: Vec<ScheduleWarning>,
+}
+
+/// What may make a schedule differ from the mosque's board
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum ScheduleWarningKind {
+    /// The provider published no iqamas: the user's default offset after the adhan is shown
+    DefaultIqama,
+    /// The mosque publishes other iqamas but not this one
+    MissingIqama,
+    /// The iqama is before the adhan, usually a typo upstream
+    IqamaBeforeAdhan,
+    /// The adhan is before the previous prayer's
+    OutOfOrder,
+    /// Times are calculated from the location rather than published by a mosque
+    Calculated,
+}
+
+/// A data quality note on a schedule, shown so users know how far to trust it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export, optional_fields)]
+pub struct ScheduleWarning {
+    pub kind: ScheduleWarningKind,
+    /// Unset for warnings about the whole day
+    pub prayer: Option<PrayerName>,
+    /// English description for logs and support; the UI words it from `kind`
+    pub message: String,
+}
+
+impl ScheduleWarning {
+    pub fn new(kind: ScheduleWarningKind, prayer: Option<PrayerName>, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            prayer,
+            message: message.into(),
+        }
+    }
+}
+
+/// How old a schedule is, so the UI can flag stale iqama times
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export, optional_fields)]
+pub struct ScheduleFreshness {
+    /// Unknown for days cached before the provider was recorded
+    pub provider: Option<String>,
+    pub cached_at: Option<DateTime<Utc>>,
+    /// Seconds since the schedule was fetched
+    #[ts(type = "number")]
+    pub age_secs: i64,
+    /// Older than the maximum cache age
+    pub stale: bool,
+    /// A provider fetch for this day is running
+    pub refreshing: bool,
+}
+
+impl ScheduleFreshness {
+    /// Freshness of `times` at `now`; a schedule without a fetch time counts as just fetched
+    pub fn of(times: &PrayerTimes, now: DateTime<Utc>, max_age_hours: i64, refreshing: bool) -> Self {
+        let age_secs = times.cached_at.map(|at| (now - at).num_seconds().max(0)).unwrap_or(0);
+
+        Self {
+            provider: times.provider.clone(),
+            cached_at: times.cached_at,
+            age_secs,
+            // Admin-written days are the source of truth, never refetched
+            stale: age_secs > max_age_hours * 3600 && times.provider.as_deref() != Some(super::ADMIN_PROVIDER),
+            refreshing,
+        }
+    }
+}
+
+impl PrayerTimes {
+    pub fn all_prayers(&self) -> Vec<&Prayer> {
+        vec![&self.fajr, &self.dhuhr, &self.asr, &self.maghrib, &self.isha]
+    }
+
+    pub fn get_prayer_by_name(&self, name: PrayerName) -> Option<&Prayer> {
+        match name {
+            PrayerName::Fajr => Some(&self.fajr),
+            PrayerName::Dhuhr => Some(&self.dhuhr),
+            PrayerName::Asr => Some(&self.asr),
+            PrayerName::Maghrib => Some(&self.maghrib),
+            PrayerName::Isha => Some(&self.isha),
+            PrayerName::Jumuah => self.jumuah.as_ref(),
+        }
+    }
+
+    /// Recheck the schedule after a fetch or edit
+    /// Default iqama warnings are kept while that iqama is still the default; the others are recomputed.
+    /// A missing iqama is only flagged for prayers `availability` says the mosque holds
+    pub fn refresh_warnings(&mut self, availability: &PrayerAvailability) {
+        let mut warnings: Vec<ScheduleWarning> = self
+            .warnings
+            .iter()
+            .filter(|w| w.kind == ScheduleWarningKind::DefaultIqama)
+            .filter(|w| {
+                w.prayer
+                    .and_then(|name| self.get_prayer_by_name(name))
+                    .is_some_and(|p| p.has_iqama() && p.iqama_offset_minutes.is_none())
+            })
+            .cloned()
+            .collect();
+
+        if self.asr_method.is_some() {
+            warnings.push(ScheduleWarning::new(
+                ScheduleWarningKind::Calculated,
+                None,
+                "Times estimated by a calculation provider, not published by the mosque",
+            ));
+        }
+
+        let publishes_iqamas = self.all_prayers().iter().any(|p| p.has_iqama());
+        let mut previous: Option<&Prayer> = None;
+        for prayer in self.all_prayers() {
+            if publishes_iqamas && !prayer.has_iqama() && availability.holds(prayer.name) {
+                warnings.push(ScheduleWarning::new(
+                    ScheduleWarningKind::MissingIqama,
+                    Some(prayer.name),
+                    format!("No iqama published for {}", prayer.name.as_str()),
+                ));
+            }
+            if prayer.iqama.is_some_and(|iqama| iqama < prayer.adhan) {
+                warnings.push(ScheduleWarning::new(
+                    ScheduleWarningKind::IqamaBeforeAdhan,
+                    Some(prayer.name),
+                    format!("{} iqama is before its adhan", prayer.name.as_str()),
+                ));
+            }
+            if let Some(before) = previous.filter(|before| prayer.adhan <= before.adhan) {
+                warnings.push(ScheduleWarning::new(
+                    ScheduleWarningKind::OutOfOrder,
+                    Some(prayer.name),
+                    format!("{} adhan is not after {}", prayer.name.as_str(), before.name.as_str()),
+                ));
+            }
+            previous = Some(prayer);
+        }
+        if let Some(jumuah) = self.jumuah.as_ref().filter(|j| j.iqama.is_some_and(|iqama| iqama < j.adhan)) {
+            warnings.push(ScheduleWarning::new(
+                ScheduleWarningKind::IqamaBeforeAdhan,
+                Some(jumuah.name),
+                "Jumuah iqama is before its adhan",
+            ));
+        }
+
+        self.warnings = warnings;
+    }
+}
+
+/// Minutes after adhan used as iqama when a provider publishes adhan times only
+/// Stored in the `iqama_defaults` setting
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct IqamaDefaults {
+    #[ts(as = "HashMap<PrayerName, f64>")]
+    pub offsets: HashMap<PrayerName, i64>,
+}
+
+impl Default for IqamaDefaults {
+    fn default() -> Self {
+        let offsets = [
+            (PrayerName::Fajr, 30),
+            (PrayerName::Dhuhr, 15),
+            (PrayerName::Asr, 15),
+            (PrayerName::Maghrib, 10),
+            (PrayerName::Isha, 15),
+        ];
+
+        Self {
+            offsets: offsets.into_iter().collect(),
+        }
+    }
+}
+
+impl IqamaDefaults {
+    /// Fill in iqamas for a schedule that has none. Schedules with some iqamas are left
+    /// alone, since a missing iqama there means the mosque doesn't hold that prayer.
+    pub fn apply(&self, schedule: &mut PrayerTimes) {
+        if schedule.all_prayers().iter().any(|p| p.has_iqama()) {
+            return;
+        }
+
+        let prayers = [
+            Some(&mut schedule.fajr),
+            Some(&mut schedule.dhuhr),
+            Some(&mut schedule.asr),
+            Some(&mut schedule.maghrib),
+            Some(&mut schedule.isha),
+            schedule.jumuah.as_mut(),
+        ];
+
+        let mut warnings = Vec::new();
+        for prayer in prayers.into_iter().flatten() {
+            if let Some(&minutes) = self.offsets.get(&prayer.name) {
+                prayer.iqama = Some(prayer.adhan + chrono::Duration::minutes(minutes));
+                warnings.push(ScheduleWarning::new(
+                    ScheduleWarningKind::DefaultIqama,
+                    Some(prayer.name),
+                    format!("Iqama missing for {}, using default offset (+{} min)", prayer.name.as_str(), minutes),
+                ));
+            }
+        }
+        schedule.warnings.extend(warnings);
+    }
+}
+
+/// Result for next prayer calculation
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, optional_fields)]
+pub struct NextPrayerResult {
+    pub prayer: Prayer,
+    #[ts(type = "number")]
+    pub time_until_adhan_secs: i64,
+    #[ts(as = "Option<f64>")]
+    pub time_until_iqama_secs: Option<i64>,
+    /// When the mosque usually starts late, the time the congregation is expected to start
+    #[serde(default)]
+    pub expected_iqama: Option<DateTime<Utc>>,
+    pub is_tomorrow: bool,
+    #[serde(default)]
+    pub adhan_display: CountdownDisplay,
+    #[serde(default)]
+    pub iqama_display: Option<CountdownDisplay>,
+}
+
+/// How a countdown reads, decided once by the engine so the tray, widgets and HTTP API agree
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct CountdownDisplay {
+    /// Seconds to show: rounded up to whole minutes unless `show_seconds`
+    #[ts(type = "number")]
+    pub secs: i64,
+    /// Close enough to count down second by second
+    pub show_seconds: bool,
+    /// Close enough to read as "now"
+    pub is_now: bool,
+    /// Seconds until the displayed value changes, so surfaces can sleep between redraws
+    #[ts(type = "number")]
+    pub refresh_in_secs: i64,
+}
+
+impl NextPrayerResult {
+    pub fn has_iqama(&self) -> bool {
+        self.time_until_iqama_secs.is_some()
+    }
+}
+
+/// Rakah estimation result
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, optional_fields)]
+pub struct RakahEstimate {
+    pub status: RakahStatus,
+    pub current_rakah: Option<i32>,
+    pub total_rakah: i32,
+    #[ts(as = "Option<f64>")]
+    pub elapsed_secs: Option<i64>,
+    #[ts(as = "Option<f64>")]
+    pub remaining_secs: Option<i64>,
+    pub progress: f64,
+    pub is_estimate: bool,
+    /// Minutes since prayer ended (only for recently_finished status)
+    #[ts(as = "Option<f64>")]
+    pub ended_minutes_ago: Option<i64>,
+    /// Whether it's still possible to catch the prayer (within catch-up window)
+    pub can_still_catch: bool,
+    /// Plausible rakah range while in progress; widens as time passes and when start times vary
+    #[serde(default)]
+    pub min_rakah: Option<i32>,
+    #[serde(default)]
+    pub max_rakah: Option<i32>,
+}
+
+impl RakahEstimate {
+    pub fn not_available(total_rakah: i32) -> Self {
+        Self {
+            status: RakahStatus::NotAvailable,
+            current_rakah: None,
+            total_rakah,
+            elapsed_secs: None,
+            remaining_secs: None,
+            progress: 0.0,
+            is_estimate: false,
+            ended_minutes_ago: None,
+            can_still_catch: false,
+            min_rakah: None,
+            max_rakah: None,
+        }
+    }
+}
+
+/// Travel prediction result
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, optional_fields)]
+pub struct TravelPrediction {
+    pub recommended_leave_time: DateTime<Utc>,
+    pub arrival_time: DateTime<Utc>,
+    pub arrival_rakah: Option<i32>,
+    pub arrival_status: ArrivalStatus,
+    pub should_leave_now: bool,
+    #[ts(as = "Option<f64>")]
+    pub time_until_leave_secs: Option<i64>,
+    pub is_late: bool,
+    /// Extra time added to the arrival buffer because this prayer is usually crowded
+    #[serde(default)]
+    #[ts(type = "number")]
+    pub crowd_buffer_secs: i64,
+}
+
+/// Whether the user will find a seat for the khutbah
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum JumuahSeating {
+    /// Arriving early enough before the khutbah
+    SeatsLikely,
+    /// Arriving before the khutbah, but seats may be taken
+    ArriveEarlier,
+    KhutbahStarted,
+}
+
+/// Travel prediction for Jumuah, aiming for a seat before the khutbah rather than a rakah
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, optional_fields)]
+pub struct JumuahTravelPrediction {
+    pub khutbah_time: DateTime<Utc>,
+    /// Minutes before the khutbah the user aims to arrive
+    #[ts(type = "number")]
+    pub arrive_before_minutes: i64,
+    pub recommended_leave_time: DateTime<Utc>,
+    pub arrival_time: DateTime<Utc>,
+    pub should_leave_now: bool,
+    #[ts(as = "Option<f64>")]
+    pub time_until_leave_secs: Option<i64>,
+    pub seating: JumuahSeating,
+}
+
+/// Ramadan fasting progress for a day
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, optional_fields)]
+pub struct FastingStatus {
+    pub is_ramadan: bool,
+    /// Between imsak and Maghrib on a Ramadan day
+    pub is_fasting: bool,
+    pub imsak: DateTime<Utc>,
+    pub iftar: DateTime<Utc>,
+    #[ts(as = "Option<f64>")]
+    pub seconds_since_suhoor: Option<i64>,
+    #[ts(as = "Option<f64>")]
+    pub seconds_to_iftar: Option<i64>,
+    /// Time left for suhoor before the fast starts
+    #[ts(as = "Option<f64>")]
+    pub seconds_to_imsak: Option<i64>,
+}
+
+/// Prayer countdown info
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, optional_fields)]
+pub struct PrayerCountdown {
+    pub prayer_name: PrayerName,
+    pub adhan_time: DateTime<Utc>,
+    pub iqama_time: Option<DateTime<Utc>>,
+    /// The iqama as the mosque's board shows it, in minutes after the adhan, when published that way
+    #[serde(default)]
+    pub iqama_offset_minutes: Option<i32>,
+    #[ts(type = "number")]
+    pub time_until_adhan_secs: i64,
+    #[ts(as = "Option<f64>")]
+    pub time_until_iqama_secs: Option<i64>,
+    pub is_active: bool,
+    /// The mosque doesn't hold this prayer (closed, or the prayer is switched off); never active
+    #[serde(default)]
+    pub not_held: bool,
+    /// Sunrise, which ends Fajr's window; only on Fajr, when the provider publishes it
+    #[serde(default)]
+    pub shuruq_time: Option<DateTime<Utc>>,
+    /// Seconds left before sunrise, zero once it has passed
+    #[serde(default)]
+    #[ts(as = "Option<f64>")]
+    pub time_until_shuruq_secs: Option<i64>,
+    #[serde(default)]
+    pub adhan_display: CountdownDisplay,
+    #[serde(default)]
+    pub iqama_display: Option<CountdownDisplay>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn adhan_only() -> PrayerTimes {
+        let midnight = Utc::now().date_naive().and_hms_opt(0, 0, 0).unwrap().and_utc();
+        let prayer = |name, hours| Prayer {
+            name,
+            adhan: midnight + Duration::hours(hours),
+            iqama: None,
+            custom_rakah_count: None,
+            iqama_offset_minutes: None,
+        };
+
+        PrayerTimes {
+            date: midnight,
+            fajr: prayer(PrayerName::Fajr, 5),
+            dhuhr: prayer(PrayerName::Dhuhr, 12),
+            asr: prayer(PrayerName::Asr, 15),
+            maghrib: prayer(PrayerName::Maghrib, 18),
+            isha: prayer(PrayerName::Isha, 20),
+            jumuah: None,
+            mosque_id: None,
+            mosque_name: None,
+            cached_at: None,
+            imsak: None,
+            shuruq: None,
+            asr_method: None,
+            provider: None,
+            freshness: None,
+            warnings: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_prayer_name_normalization() {
+        let cases = [
+            ("Dhuhr", PrayerName::Dhuhr),
+            ("Zuhr", PrayerName::Dhuhr),
+            (" DOHR ", PrayerName::Dhuhr),
+            ("الظهر", PrayerName::Dhuhr),
+            ("Salat al-Asr", PrayerName::Asr),
+            ("صلاة العشاء", PrayerName::Isha),
+            ("Maghreb", PrayerName::Maghrib),
+            ("Jumu'ah", PrayerName::Jumuah),
+            ("Subh", PrayerName::Fajr),
+        ];
+        for (raw, expected) in cases {
+            assert_eq!(raw.parse::<PrayerName>(), Ok(expected), "{}", raw);
+        }
+
+        assert!(PrayerName::normalize("Sunrise").is_none());
+        assert!(PrayerName::normalize("").is_none());
+    }
+
+    #[test]
+    fn test_iqama_defaults_fill_adhan_only_schedules() {
+        let mut defaults = IqamaDefaults::default();
+        defaults.offsets.insert(PrayerName::Fajr, 20);
+        defaults.offsets.remove(&PrayerName::Asr);
+
+        let mut schedule = adhan_only();
+        defaults.apply(&mut schedule);
+        assert_eq!(schedule.fajr.iqama, Some(schedule.fajr.adhan + Duration::minutes(20)));
+        assert_eq!(schedule.maghrib.iqama, Some(schedule.maghrib.adhan + Duration::minutes(10)));
+        assert_eq!(schedule.asr.iqama, None);
+
+        // A mosque publishing only some iqamas keeps the others empty
+        let mut partial = adhan_only();
+        partial.isha.iqama = Some(partial.isha.adhan + Duration::minutes(5));
+        defaults.apply(&mut partial);
+        assert_eq!(partial.fajr.iqama, None);
+    }
+
+    #[test]
+    fn test_schedule_warnings() {
+        let kinds = |times: &PrayerTimes| -> Vec<(ScheduleWarningKind, Option<PrayerName>)> {
+            times.warnings.iter().map(|w| (w.kind, w.prayer)).collect()
+        };
+
+        let mut schedule = adhan_only();
+        schedule.asr_method = Some(AsrMethod::Standard);
+        IqamaDefaults::default().apply(&mut schedule);
+        schedule.refresh_warnings(&PrayerAvailability::derive(&schedule));
+        assert_eq!(kinds(&schedule).len(), 6);
+        assert!(kinds(&schedule).contains(&(ScheduleWarningKind::DefaultIqama, Some(PrayerName::Asr))));
+        assert!(kinds(&schedule).contains(&(ScheduleWarningKind::Calculated, None)));
+
+        // A default iqama replaced by a published offset is no longer flagged
+        schedule.asr.iqama_offset_minutes = Some(15);
+        schedule.maghrib.iqama = Some(schedule.maghrib.adhan - Duration::minutes(5));
+        schedule.isha.adhan = schedule.maghrib.adhan;
+        schedule.refresh_warnings(&PrayerAvailability::derive(&schedule));
+        let found = kinds(&schedule);
+        assert!(!found.contains(&(ScheduleWarningKind::DefaultIqama, Some(PrayerName::Asr))));
+        assert!(found.contains(&(ScheduleWarningKind::IqamaBeforeAdhan, Some(PrayerName::Maghrib))));
+        assert!(found.contains(&(ScheduleWarningKind::OutOfOrder, Some(PrayerName::Isha))));
+
+        // Only Isha has an iqama: the mosque doesn't hold the others, so nothing is missing
+        let mut partial = adhan_only();
+        partial.isha.iqama = Some(partial.isha.adhan + Duration::minutes(5));
+        let derived = PrayerAvailability::derive(&partial);
+        partial.refresh_warnings(&derived);
+        assert!(partial.warnings.is_empty());
+
+        // Unless the user says the mosque holds Dhuhr
+        let user = PrayerAvailability {
+            closed: false,
+            prayers: HashMap::from([(PrayerName::Dhuhr, true)]),
+        };
+        partial.refresh_warnings(&derived.merged(&user));
+        assert_eq!(kinds(&partial), vec![(ScheduleWarningKind::MissingIqama, Some(PrayerName::Dhuhr))]);
+    }
+}
@@ -0,0 +1,92 @@
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use super::PrayerName;
+
+/// Longest reflection kept with a log entry, in characters
+pub const MAX_PRAYER_NOTE_CHARS: usize = 2000;
+
+/// How a logged prayer was prayed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum PrayerLogTag {
+    /// Prayed in congregation
+    Jamaah,
+    OnTime,
+    Late,
+    WithFamily,
+}
+
+impl PrayerLogTag {
+    pub const ALL: [PrayerLogTag; 4] = [
+        PrayerLogTag::Jamaah,
+        PrayerLogTag::OnTime,
+        PrayerLogTag::Late,
+        PrayerLogTag::WithFamily,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PrayerLogTag::Jamaah => "jamaah",
+            PrayerLogTag::OnTime => "on_time",
+            PrayerLogTag::Late => "late",
+            PrayerLogTag::WithFamily => "with_family",
+        }
+    }
+}
+
+impl fmt::Display for PrayerLogTag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for PrayerLogTag {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        PrayerLogTag::ALL
+            .iter()
+            .find(|tag| tag.as_str() == s)
+            .copied()
+            .ok_or_else(|| format!("Unknown prayer log tag: {}", s))
+    }
+}
+
+/// A prayer the user logged, with optional tags and a private reflection (khushu notes)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export, optional_fields)]
+pub struct PrayerLogEntry {
+    pub profile: String,
+    pub prayer: PrayerName,
+    pub date: NaiveDate,
+    /// Mosque attended, when prayed there
+    pub mosque_id: Option<String>,
+    pub tags: Vec<PrayerLogTag>,
+    pub note: Option<String>,
+    pub logged_at: DateTime<Utc>,
+}
+
+impl PrayerLogEntry {
+    pub fn has_tag(&self, tag: PrayerLogTag) -> bool {
+        self.tags.contains(&tag)
+    }
+}
+
+/// Filter for reading the prayer log; unset fields match everything
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export, optional_fields)]
+pub struct PrayerLogQuery {
+    pub profile: Option<String>,
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+    pub prayer: Option<PrayerName>,
+    pub tag: Option<PrayerLogTag>,
+    /// Only entries with a reflection
+    pub with_note: Option<bool>,
+}
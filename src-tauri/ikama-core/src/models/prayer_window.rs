@@ -0,0 +1,118 @@
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use super::PrayerName;
+
+/// Juristic convention for the start of Asr
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum AsrMethod {
+    /// Shafi'i, Maliki and Hanbali: shadow equal to the object's length
+    #[default]
+    Standard,
+    /// Shadow twice the object's length, so Asr starts later
+    Hanafi,
+}
+
+impl AsrMethod {
+    pub const ALL: [AsrMethod; 2] = [AsrMethod::Standard, AsrMethod::Hanafi];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AsrMethod::Standard => "standard",
+            AsrMethod::Hanafi => "hanafi",
+        }
+    }
+
+    /// Shadow length, in object lengths, added to the noon shadow when Asr starts
+    pub fn shadow_factor(&self) -> f64 {
+        match self {
+            AsrMethod::Standard => 1.0,
+            AsrMethod::Hanafi => 2.0,
+        }
+    }
+}
+
+impl fmt::Display for AsrMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for AsrMethod {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        AsrMethod::ALL
+            .iter()
+            .find(|method| method.as_str() == s)
+            .copied()
+            .ok_or_else(|| format!("Unknown Asr method: {}", s))
+    }
+}
+
+/// When Isha's time runs out
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum IshaEnd {
+    /// Isha can be prayed until Fajr
+    #[default]
+    Fajr,
+    /// Halfway between Maghrib and Fajr
+    IslamicMidnight,
+}
+
+impl IshaEnd {
+    pub const ALL: [IshaEnd; 2] = [IshaEnd::Fajr, IshaEnd::IslamicMidnight];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IshaEnd::Fajr => "fajr",
+            IshaEnd::IslamicMidnight => "islamic_midnight",
+        }
+    }
+}
+
+impl fmt::Display for IshaEnd {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for IshaEnd {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        IshaEnd::ALL
+            .iter()
+            .find(|end| end.as_str() == s)
+            .copied()
+            .ok_or_else(|| format!("Unknown Isha end: {}", s))
+    }
+}
+
+/// Time during which a prayer can be prayed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct PrayerWindow {
+    pub prayer: PrayerName,
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+}
+
+impl PrayerWindow {
+    pub fn contains(&self, now: DateTime<Utc>) -> bool {
+        self.start <= now && now < self.end
+    }
+
+    /// The prayer's time is over; it is missed unless it was prayed
+    pub fn has_ended(&self, now: DateTime<Utc>) -> bool {
+        now >= self.end
+    }
+}
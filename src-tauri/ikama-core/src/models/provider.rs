@@ -1,8 +1,10 @@
 use serde::{Deserialize, Serialize};
+use ts_rs::TS;
 
 /// Configuration field types
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
 #[serde(rename_all = "snake_case")]
+#[ts(export)]
 pub enum ConfigFieldType {
     String,
     Password,
@@ -13,7 +15,8 @@ pub enum ConfigFieldType {
 }
 
 /// Configuration schema field for provider settings
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, optional_fields)]
 pub struct ConfigField {
     pub key: String,
     pub label: String,
@@ -60,7 +63,8 @@ impl ConfigField {
 }
 
 /// Provider metadata
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
 pub struct ProviderInfo {
     pub id: String,
     pub name: String,
@@ -69,21 +73,30 @@ pub struct ProviderInfo {
 }
 
 /// Provider configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
 pub struct ProviderConfig {
     pub provider_id: String,
+    #[ts(type = "Record<string, unknown>")]
     pub settings: serde_json::Value,
 }
 
 /// Provider test result
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, optional_fields)]
 pub struct ProviderTestResult {
     pub success: bool,
     pub message: String,
+    #[ts(as = "Option<f64>")]
     pub latency_ms: Option<u64>,
 }
 
 /// Available provider types
+pub const PROVIDER_MAWAQIT: &str = "mawaqit";
 pub const PROVIDER_OFFICIAL_API: &str = "official_api";
 pub const PROVIDER_COMMUNITY_WRAPPER: &str = "community_wrapper";
 pub const PROVIDER_SCRAPING: &str = "scraping";
+pub const PROVIDER_MOCK: &str = "mock";
+pub const PROVIDER_ISLAMICFINDER: &str = "islamicfinder";
+pub const PROVIDER_DIYANET: &str = "diyanet";
+pub const PROVIDER_MASJID_TIMETABLE: &str = "masjid_timetable";
@@ -0,0 +1,40 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+/// A provider response as fetched, gzip-compressed, kept for support to compare with the mosque's board
+#[derive(Debug, Clone, PartialEq)]
+pub struct RawPayload {
+    pub provider_id: String,
+    pub mosque_id: String,
+    pub url: String,
+    pub fetched_at: DateTime<Utc>,
+    /// Size of the response before compression
+    pub size_bytes: i64,
+    pub body: Vec<u8>,
+}
+
+/// An archived response without its body, for listing
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct RawPayloadInfo {
+    #[ts(type = "number")]
+    pub id: i64,
+    pub provider_id: String,
+    pub mosque_id: String,
+    pub url: String,
+    pub fetched_at: DateTime<Utc>,
+    #[ts(type = "number")]
+    pub size_bytes: i64,
+    #[ts(type = "number")]
+    pub compressed_bytes: i64,
+}
+
+/// An archived response with its body decompressed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct ArchivedPayload {
+    pub info: RawPayloadInfo,
+    /// The response as text; bytes that are not UTF-8 are replaced
+    pub body: String,
+}
@@ -0,0 +1,117 @@
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use super::{PrayerName, PrayerTimes};
+
+/// An iqama time that moved between the cached schedule and a provider refresh
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export, optional_fields)]
+pub struct ScheduleChange {
+    pub mosque_id: String,
+    pub prayer: PrayerName,
+    pub date: NaiveDate,
+    pub old_iqama: Option<DateTime<Utc>>,
+    pub new_iqama: Option<DateTime<Utc>>,
+    pub detected_at: DateTime<Utc>,
+}
+
+impl ScheduleChange {
+    /// Iqama differences between a cached day and the provider's fresh copy
+    pub fn detect(
+        mosque_id: &str,
+        cached: &PrayerTimes,
+        fresh: &PrayerTimes,
+        detected_at: DateTime<Utc>,
+    ) -> Vec<Self> {
+        let mut pairs: Vec<_> = cached.all_prayers().into_iter().zip(fresh.all_prayers()).collect();
+        if let (Some(old), Some(new)) = (&cached.jumuah, &fresh.jumuah) {
+            pairs.push((old, new));
+        }
+
+        pairs
+            .into_iter()
+            .filter(|(old, new)| old.iqama != new.iqama)
+            .map(|(old, new)| Self {
+                mosque_id: mosque_id.to_string(),
+                prayer: new.name,
+                date: fresh.date.date_naive(),
+                old_iqama: old.iqama,
+                new_iqama: new.iqama,
+                detected_at,
+            })
+            .collect()
+    }
+
+    /// Minutes the iqama moved, positive when later; `None` if it was added or removed
+    pub fn shift_minutes(&self) -> Option<i64> {
+        Some((self.new_iqama? - self.old_iqama?).num_minutes())
+    }
+}
+
+/// An iqama jump ahead in the mosque's published calendar
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct UpcomingScheduleChange {
+    pub prayer: PrayerName,
+    /// First day with the new time
+    pub date: NaiveDate,
+    /// Local wall-clock iqama the day before
+    pub previous_iqama: NaiveTime,
+    pub new_iqama: NaiveTime,
+    /// Positive when later
+    #[ts(type = "number")]
+    pub shift_minutes: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Prayer;
+    use chrono::Duration;
+
+    fn day(isha_iqama: Option<DateTime<Utc>>) -> PrayerTimes {
+        let midnight = NaiveDate::from_ymd_opt(2026, 3, 2).unwrap().and_hms_opt(0, 0, 0).unwrap().and_utc();
+        let prayer = |name, hours, iqama| Prayer {
+            name,
+            adhan: midnight + Duration::hours(hours),
+            iqama,
+            custom_rakah_count: None,
+            iqama_offset_minutes: None,
+        };
+
+        PrayerTimes {
+            date: midnight,
+            fajr: prayer(PrayerName::Fajr, 5, None),
+            dhuhr: prayer(PrayerName::Dhuhr, 12, None),
+            asr: prayer(PrayerName::Asr, 15, None),
+            maghrib: prayer(PrayerName::Maghrib, 18, None),
+            isha: prayer(PrayerName::Isha, 20, isha_iqama),
+            jumuah: None,
+            mosque_id: None,
+            mosque_name: None,
+            cached_at: None,
+            imsak: None,
+            shuruq: None,
+            asr_method: None,
+            provider: None,
+            freshness: None,
+            warnings: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_detect_iqama_shift() {
+        let iqama = day(None).date + Duration::hours(20) + Duration::minutes(10);
+        let cached = day(Some(iqama));
+        let fresh = day(Some(iqama + Duration::minutes(15)));
+
+        let changes = ScheduleChange::detect("m1", &cached, &fresh, Utc::now());
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].prayer, PrayerName::Isha);
+        assert_eq!(changes[0].shift_minutes(), Some(15));
+
+        assert!(ScheduleChange::detect("m1", &cached, &cached, Utc::now()).is_empty());
+        assert_eq!(ScheduleChange::detect("m1", &cached, &day(None), Utc::now())[0].shift_minutes(), None);
+    }
+}
@@ -0,0 +1,248 @@
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, NaiveTime, Utc, Weekday};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use super::{Prayer, PrayerAvailability, PrayerName, PrayerTimes};
+use crate::providers::time_utils::time_on_date_local;
+use crate::services::clock::resolve_local;
+
+/// Provider of schedules written in admin mode; refreshes never overwrite them
+pub const ADMIN_PROVIDER: &str = "admin";
+
+/// Longest range a single edit may cover
+pub const MAX_EDIT_DAYS: i64 = 366;
+
+/// New iqama for an edited prayer
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+#[ts(export)]
+pub enum IqamaEdit {
+    /// Fixed wall-clock time, "HH:MM"
+    At { time: String },
+    /// Minutes after the (edited) adhan
+    AfterAdhan {
+        #[ts(type = "number")]
+        minutes: i64,
+    },
+    /// The mosque holds no iqama for the prayer
+    Clear,
+}
+
+/// Change to one prayer over a range of days, e.g. Isha iqama at 21:30 for all of March
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export, optional_fields)]
+pub struct ScheduleEdit {
+    pub from: NaiveDate,
+    /// Last day edited, inclusive; only `from` when unset
+    pub to: Option<NaiveDate>,
+    pub prayer: PrayerName,
+    /// New adhan, "HH:MM"
+    pub adhan: Option<String>,
+    pub iqama: Option<IqamaEdit>,
+}
+
+pub(crate) fn parse_wall_clock(time: &str) -> Result<NaiveTime, String> {
+    NaiveTime::parse_from_str(time.trim(), "%H:%M").map_err(|_| format!("Invalid time {:?}, use HH:MM", time))
+}
+
+impl ScheduleEdit {
+    pub fn last_day(&self) -> NaiveDate {
+        self.to.unwrap_or(self.from)
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        let days = (self.last_day() - self.from).num_days();
+        if days < 0 {
+            return Err(format!("Edit of {} ends before it starts", self.prayer));
+        }
+        if days >= MAX_EDIT_DAYS {
+            return Err(format!("An edit covers at most {} days", MAX_EDIT_DAYS));
+        }
+        if self.adhan.is_none() && self.iqama.is_none() {
+            return Err(format!("Edit of {} changes neither adhan nor iqama", self.prayer));
+        }
+        if let Some(adhan) = &self.adhan {
+            parse_wall_clock(adhan)?;
+        }
+        match &self.iqama {
+            Some(IqamaEdit::At { time }) => {
+                parse_wall_clock(time)?;
+            }
+            Some(IqamaEdit::AfterAdhan { minutes }) if !(0..=180).contains(minutes) => {
+                return Err(format!("Iqama must follow the adhan by 0 to 180 minutes, got {}", minutes));
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Days the edit covers; Jumuah edits only cover Fridays
+    pub fn dates(&self) -> Vec<NaiveDate> {
+        let mut dates = Vec::new();
+        let mut date = self.from;
+        while date <= self.last_day() {
+            if self.prayer != PrayerName::Jumuah || date.weekday() == Weekday::Fri {
+                dates.push(date);
+            }
+            date += Duration::days(1);
+        }
+        dates
+    }
+
+    /// Apply the edit to one of its days and mark the day as admin-written
+    /// `user` is the availability the user set for the mosque, so warnings skip prayers it doesn't hold
+    pub fn apply(
+        &self,
+        times: &mut PrayerTimes,
+        user: Option<&PrayerAvailability>,
+        now: DateTime<Utc>,
+    ) -> Result<(), String> {
+        let date = times.date.with_timezone(&Local).date_naive();
+        let prayer = match self.prayer {
+            PrayerName::Fajr => &mut times.fajr,
+            PrayerName::Dhuhr => &mut times.dhuhr,
+            PrayerName::Asr => &mut times.asr,
+            PrayerName::Maghrib => &mut times.maghrib,
+            PrayerName::Isha => &mut times.isha,
+            PrayerName::Jumuah => {
+                if times.jumuah.is_none() && self.adhan.is_none() {
+                    return Err(format!("No Jumuah on {}, set its adhan first", date));
+                }
+                times.jumuah.get_or_insert_with(|| Prayer {
+                    name: PrayerName::Jumuah,
+                    adhan: time_on_date_local(date, "13:00"),
+                    iqama: None,
+                    custom_rakah_count: None,
+                    iqama_offset_minutes: None,
+                })
+            }
+        };
+
+        if let Some(adhan) = &self.adhan {
+            prayer.adhan = time_on_date_local(date, adhan);
+            // An iqama published as an offset follows the adhan
+            if let Some(minutes) = prayer.iqama_offset_minutes {
+                prayer.iqama = Some(prayer.adhan + Duration::minutes(minutes as i64));
+            }
+        }
+        match &self.iqama {
+            Some(IqamaEdit::At { time }) => {
+                prayer.iqama = Some(time_on_date_local(date, time));
+                prayer.iqama_offset_minutes = None;
+            }
+            Some(IqamaEdit::AfterAdhan { minutes }) => {
+                prayer.iqama = Some(prayer.adhan + Duration::minutes(*minutes));
+                prayer.iqama_offset_minutes = i32::try_from(*minutes).ok();
+            }
+            Some(IqamaEdit::Clear) => {
+                prayer.iqama = None;
+                prayer.iqama_offset_minutes = None;
+            }
+            None => {}
+        }
+
+        // An iqama set by hand is no longer the default offset
+        if self.iqama.is_some() {
+            times.warnings.retain(|w| w.prayer != Some(self.prayer));
+        }
+        times.refresh_warnings(&PrayerAvailability::for_schedule(times, user));
+        times.provider = Some(ADMIN_PROVIDER.to_string());
+        times.cached_at = Some(now);
+        Ok(())
+    }
+}
+
+/// The same wall-clock schedule on another day
+/// Jumuah is only kept when both days are Fridays
+pub fn shift_schedule(times: &PrayerTimes, date: NaiveDate) -> PrayerTimes {
+    let move_time = |time: DateTime<Utc>| resolve_local(&Local, date.and_time(time.with_timezone(&Local).time()));
+    let move_prayer = |prayer: &Prayer| Prayer {
+        adhan: move_time(prayer.adhan),
+        iqama: prayer.iqama.map(move_time),
+        ..prayer.clone()
+    };
+
+    PrayerTimes {
+        date: time_on_date_local(date, "00:00"),
+        fajr: move_prayer(&times.fajr),
+        dhuhr: move_prayer(&times.dhuhr),
+        asr: move_prayer(&times.asr),
+        maghrib: move_prayer(&times.maghrib),
+        isha: move_prayer(&times.isha),
+        jumuah: times.jumuah.as_ref().filter(|_| date.weekday() == Weekday::Fri).map(move_prayer),
+        imsak: times.imsak.map(move_time),
+        shuruq: times.shuruq.map(move_time),
+        freshness: None,
+        ..times.clone()
+    }
+}
+
+/// Copy source days onto target days in order, e.g. Ramadan day 1 onto Ramadan day 1
+/// A target month longer than the source repeats its last day
+pub fn copy_schedule_days(source: &[PrayerTimes], targets: &[NaiveDate], now: DateTime<Utc>) -> Vec<PrayerTimes> {
+    if source.is_empty() {
+        return Vec::new();
+    }
+
+    targets
+        .iter()
+        .enumerate()
+        .map(|(i, &date)| {
+            let mut copy = shift_schedule(&source[i.min(source.len() - 1)], date);
+            copy.provider = Some(ADMIN_PROVIDER.to_string());
+            copy.cached_at = Some(now);
+            copy
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::MockProvider;
+    use chrono::Timelike;
+
+    #[test]
+    fn test_month_iqama_edit() {
+        let edit = ScheduleEdit {
+            from: NaiveDate::from_ymd_opt(2026, 3, 1).unwrap(),
+            to: Some(NaiveDate::from_ymd_opt(2026, 3, 31).unwrap()),
+            prayer: PrayerName::Isha,
+            adhan: Some("20:10".to_string()),
+            iqama: Some(IqamaEdit::AfterAdhan { minutes: 15 }),
+        };
+        assert!(edit.validate().is_ok());
+        assert_eq!(edit.dates().len(), 31);
+        let fridays = ScheduleEdit { prayer: PrayerName::Jumuah, ..edit.clone() };
+        assert_eq!(fridays.dates().len(), 4);
+
+        let now = Utc::now();
+        let mut times = MockProvider::new().schedule("mock-admin", edit.from, now);
+        edit.apply(&mut times, None, now).unwrap();
+        let isha_iqama = times.isha.iqama.unwrap().with_timezone(&Local);
+        assert_eq!((isha_iqama.hour(), isha_iqama.minute()), (20, 25));
+        assert_eq!(times.isha.iqama_offset_minutes, Some(15));
+        assert_eq!(times.provider.as_deref(), Some(ADMIN_PROVIDER));
+
+        let bad = ScheduleEdit { adhan: Some("25:00".to_string()), ..edit.clone() };
+        assert!(bad.validate().is_err());
+        let backwards = ScheduleEdit { to: Some(NaiveDate::from_ymd_opt(2026, 2, 1).unwrap()), ..edit };
+        assert!(backwards.validate().is_err());
+    }
+
+    #[test]
+    fn test_copy_keeps_wall_clock_and_repeats_last_day() {
+        let now = Utc::now();
+        let mock = MockProvider::new();
+        let source_day = NaiveDate::from_ymd_opt(2025, 3, 1).unwrap();
+        let source = vec![mock.schedule("mock-admin", source_day, now)];
+        let targets = [NaiveDate::from_ymd_opt(2026, 2, 18).unwrap(), NaiveDate::from_ymd_opt(2026, 2, 19).unwrap()];
+
+        let copies = copy_schedule_days(&source, &targets, now);
+        assert_eq!(copies.len(), 2);
+        let wall_clock = |t: DateTime<Utc>| t.with_timezone(&Local).time();
+        assert_eq!(wall_clock(copies[1].maghrib.adhan), wall_clock(source[0].maghrib.adhan));
+        assert_eq!(copies[1].maghrib.adhan.with_timezone(&Local).date_naive(), targets[1]);
+        assert_eq!(copies[0].provider.as_deref(), Some(ADMIN_PROVIDER));
+    }
+}
@@ -0,0 +1,19 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use super::Mosque;
+
+/// Profile used until profiles can be chosen in the UI
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// The mosque a profile follows
+/// Countdowns, travel predictions and the refresh scheduler read it instead of taking a mosque id
+/// Travel times to it are kept per mode in `travel_times`
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct SelectedMosque {
+    pub profile: String,
+    pub mosque: Mosque,
+    pub selected_at: DateTime<Utc>,
+}
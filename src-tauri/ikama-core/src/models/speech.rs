@@ -0,0 +1,49 @@
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use crate::models::PrayerName;
+use crate::services::Language;
+
+/// Spoken prayer announcements through the system's text-to-speech engine
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export, optional_fields)]
+pub struct SpeechConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Prayers whose reminders are read aloud
+    #[serde(default = "default_prayers")]
+    pub prayers: Vec<PrayerName>,
+    /// Language spoken; the app's language when unset
+    #[serde(default)]
+    pub language: Option<Language>,
+    /// Show the notification too; announcements replace it otherwise
+    #[serde(default = "default_true")]
+    pub with_notification: bool,
+}
+
+fn default_prayers() -> Vec<PrayerName> {
+    let mut prayers = PrayerName::DAILY.to_vec();
+    prayers.push(PrayerName::Jumuah);
+    prayers
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl Default for SpeechConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            prayers: default_prayers(),
+            language: None,
+            with_notification: true,
+        }
+    }
+}
+
+impl SpeechConfig {
+    pub fn speaks(&self, prayer: PrayerName) -> bool {
+        self.enabled && self.prayers.contains(&prayer)
+    }
+}
@@ -0,0 +1,107 @@
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use super::{PrayerName, TravelPrediction};
+
+/// How the user gets to a mosque
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum TravelMode {
+    Walking,
+    Cycling,
+    Driving,
+    Transit,
+}
+
+impl TravelMode {
+    pub const ALL: [TravelMode; 4] = [
+        TravelMode::Walking,
+        TravelMode::Cycling,
+        TravelMode::Driving,
+        TravelMode::Transit,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TravelMode::Walking => "walking",
+            TravelMode::Cycling => "cycling",
+            TravelMode::Driving => "driving",
+            TravelMode::Transit => "transit",
+        }
+    }
+}
+
+impl fmt::Display for TravelMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for TravelMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        TravelMode::ALL
+            .iter()
+            .find(|mode| mode.as_str() == s)
+            .copied()
+            .ok_or_else(|| format!("Unknown travel mode: {}", s))
+    }
+}
+
+/// Saved travel time from home to a favorite mosque for one mode
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct TravelTime {
+    pub mosque_id: String,
+    pub mode: TravelMode,
+    #[ts(type = "number")]
+    pub travel_time_seconds: i64,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Where an "on my way" session stands
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum TravelSessionStatus {
+    Active,
+    /// Reached the mosque; the trip was logged
+    Arrived,
+    Cancelled,
+    /// Ran too long to be a trip to the mosque; not logged
+    TimedOut,
+}
+
+/// Live state of a trip to a mosque, sent as `travel-session-update` while it runs and once when it ends
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, optional_fields)]
+pub struct TravelSessionUpdate {
+    pub mosque_id: String,
+    pub mode: TravelMode,
+    /// Prayer the user is heading to
+    pub prayer: Option<PrayerName>,
+    pub status: TravelSessionStatus,
+    pub started_at: DateTime<Utc>,
+    #[ts(type = "number")]
+    pub elapsed_secs: i64,
+    /// Time left, from the last position or else from the expected travel time
+    #[ts(as = "Option<f64>")]
+    pub remaining_secs: Option<i64>,
+    pub eta: Option<DateTime<Utc>>,
+    /// Straight-line distance left from the last position
+    pub distance_m: Option<f64>,
+    /// Where the prayer will be on arrival, recalculated with every update
+    pub prediction: Option<TravelPrediction>,
+    /// Door-to-door time logged on arrival
+    #[ts(as = "Option<f64>")]
+    pub travel_time_secs: Option<i64>,
+    /// Typical travel time learned from the logged trips, saved for the mosque and mode
+    #[ts(as = "Option<f64>")]
+    pub learned_travel_time_secs: Option<i64>,
+}
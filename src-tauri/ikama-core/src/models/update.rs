@@ -0,0 +1,21 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+/// Latest published release compared with the running version
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export, optional_fields)]
+pub struct UpdateInfo {
+    pub current_version: String,
+    pub latest_version: String,
+    pub update_available: bool,
+    /// Installer for this platform, when the release has one
+    pub download_url: Option<String>,
+    /// Release page, for platforms without an installer asset
+    pub release_url: String,
+    /// Markdown notes of the release
+    pub release_notes: Option<String>,
+    pub published_at: Option<DateTime<Utc>>,
+    /// Tauri's updater is configured, so the UI can install in place instead of opening the download
+    pub self_update: bool,
+}
@@ -0,0 +1,20 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+/// A provider page or payload that stopped parsing, usually after the upstream site changed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct UpstreamIssue {
+    pub provider_id: String,
+    /// What failed to parse, e.g. "mosque_list", or "confData:<mosque>" for one mosque's page
+    pub context: String,
+    /// Stable hash of provider, context and error, so repeats of one failure are counted together
+    pub fingerprint: String,
+    pub message: String,
+    /// Data was still served by a fallback parser
+    pub degraded: bool,
+    pub occurrences: u32,
+    pub first_seen: DateTime<Utc>,
+    pub last_seen: DateTime<Utc>,
+}
@@ -0,0 +1,77 @@
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+/// A write spanning several steps, journaled before it starts and cleared by the transaction
+/// that completes it; entries found at startup belong to writes a crash interrupted
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum PendingWrite {
+    /// Days of a mosque's schedule fetched one by one, then saved together
+    RefreshSchedule {
+        mosque_id: String,
+        from: NaiveDate,
+        to: NaiveDate,
+    },
+}
+
+impl PendingWrite {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::RefreshSchedule { .. } => "refresh_schedule",
+        }
+    }
+}
+
+/// A journaled write with when it started
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JournalEntry {
+    pub id: i64,
+    pub write: PendingWrite,
+    pub started_at: DateTime<Utc>,
+}
+
+impl JournalEntry {
+    /// Whether replaying is still worth it: interrupted refreshes of past days are dropped
+    pub fn is_replayable(&self, today: NaiveDate, now: DateTime<Utc>, max_age: Duration) -> bool {
+        if now - self.started_at > max_age {
+            return false;
+        }
+        match &self.write {
+            PendingWrite::RefreshSchedule { to, .. } => *to >= today,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_replayable_entries() {
+        let today = NaiveDate::from_ymd_opt(2026, 3, 10).unwrap();
+        let now = Utc::now();
+        let entry = |from: NaiveDate, to: NaiveDate, age: Duration| JournalEntry {
+            id: 1,
+            write: PendingWrite::RefreshSchedule {
+                mosque_id: "m1".to_string(),
+                from,
+                to,
+            },
+            started_at: now - age,
+        };
+
+        let month_end = NaiveDate::from_ymd_opt(2026, 3, 31).unwrap();
+        assert!(entry(today, month_end, Duration::hours(1)).is_replayable(today, now, Duration::days(7)));
+        assert!(!entry(today, month_end, Duration::days(8)).is_replayable(today, now, Duration::days(7)));
+
+        let last_week = today - Duration::days(7);
+        assert!(!entry(last_week, today - Duration::days(1), Duration::hours(1)).is_replayable(
+            today,
+            now,
+            Duration::days(7)
+        ));
+
+        let json = serde_json::to_string(&entry(today, month_end, Duration::zero()).write).unwrap();
+        assert_eq!(json, r#"{"kind":"refresh_schedule","mosque_id":"m1","from":"2026-03-10","to":"2026-03-31"}"#);
+    }
+}
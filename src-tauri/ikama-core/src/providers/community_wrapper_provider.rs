@@ -0,0 +1,588 @@
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use chrono::{NaiveDate, Utc};
+use moka::future::Cache;
+use once_cell::sync::Lazy;
+use reqwest::{Client, RequestBuilder};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::models::*;
+use crate::providers::http_fixtures::{FixtureMode, FixtureResponse};
+use crate::providers::time_utils::time_on_date_utc;
+use crate::providers::{retry_after_secs, PrayerDataProvider, ProviderError, ProviderResult};
+use crate::services::{record_payload, SecretStore};
+
+/// Longest Retry-After we wait out before giving up with `RateLimited`
+const MAX_RETRY_WAIT: Duration = Duration::from_secs(10);
+
+/// Renew access tokens this long before they expire
+const TOKEN_EXPIRY_MARGIN: Duration = Duration::from_secs(60);
+
+// Access tokens by configured refresh token, shared across provider instances
+static TOKEN_CACHE: Lazy<Cache<String, AccessToken>> = Lazy::new(|| {
+    Cache::builder()
+        .max_capacity(8)
+        .time_to_live(Duration::from_secs(24 * 60 * 60))
+        .build()
+});
+
+/// Community Wrapper API Provider (Provider B)
+/// REST API wrapper that provides a simplified interface
+pub struct CommunityWrapperProvider {
+    client: Client,
+    base_url: Option<String>,
+    api_key: Option<String>,
+    oauth: Option<OAuthConfig>,
+    fixtures: FixtureMode,
+}
+
+/// OAuth2 refresh-token grant used to obtain bearer tokens
+#[derive(Debug, Clone)]
+struct OAuthConfig {
+    token_url: String,
+    client_id: String,
+    client_secret: Option<String>,
+    refresh_token: String,
+}
+
+#[derive(Debug, Clone)]
+struct AccessToken {
+    access_token: String,
+    /// Latest refresh token, when the server rotates them
+    refresh_token: String,
+    expires_at: Instant,
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    expires_in: Option<u64>,
+    refresh_token: Option<String>,
+}
+
+impl CommunityWrapperProvider {
+    pub fn new() -> Self {
+        Self {
+            client: Client::new(),
+            base_url: None,
+            api_key: None,
+            oauth: None,
+            fixtures: FixtureMode::from_env(),
+        }
+    }
+
+    /// Share a crowd report with other users of the wrapper API
+    pub async fn submit_crowd_report(&self, report: &CrowdReport) -> ProviderResult<()> {
+        let base_url = self.ensure_initialized()?;
+
+        let request = self
+            .client
+            .post(format!("{}/mosques/{}/crowd-reports", base_url, report.mosque_id))
+            .json(report);
+
+        let response = self.send(request, "submit crowd report").await?;
+        check_status(response, "submit crowd report")?;
+
+        Ok(())
+    }
+
+    fn ensure_initialized(&self) -> ProviderResult<&str> {
+        match &self.base_url {
+            Some(url) => Ok(url),
+            None => Err(ProviderError::InvalidConfig(
+                "Provider not initialized".to_string(),
+            )),
+        }
+    }
+
+    /// Bearer token from the refresh-token grant, `None` without OAuth settings
+    async fn access_token(&self, force_refresh: bool) -> ProviderResult<Option<String>> {
+        let Some(oauth) = &self.oauth else {
+            return Ok(None);
+        };
+
+        let cached = TOKEN_CACHE.get(&oauth.refresh_token).await;
+        if let Some(token) = cached.as_ref().filter(|t| !force_refresh && t.expires_at > Instant::now()) {
+            return Ok(Some(token.access_token.clone()));
+        }
+
+        let refresh_token = cached
+            .map(|t| t.refresh_token)
+            .unwrap_or_else(|| oauth.refresh_token.clone());
+        let mut form = vec![
+            ("grant_type", "refresh_token"),
+            ("refresh_token", refresh_token.as_str()),
+            ("client_id", oauth.client_id.as_str()),
+        ];
+        if let Some(secret) = &oauth.client_secret {
+            form.push(("client_secret", secret));
+        }
+
+        let request = self.client
+            .post(&oauth.token_url)
+            .form(&form);
+        let response = self.fixtures.send(request).await?;
+
+        // invalid_grant comes back as 400: the refresh token was revoked or expired
+        if matches!(response.status().as_u16(), 400 | 401) {
+            TOKEN_CACHE.invalidate(&oauth.refresh_token).await;
+            return Err(ProviderError::Unauthorized(
+                "Refresh token rejected, sign in to the community API again".to_string(),
+            ));
+        }
+
+        let token: TokenResponse = check_status(response, "refresh access token")?
+            .json()
+            .map_err(|e| ProviderError::Parse(format!("Failed to parse token response: {}", e)))?;
+
+        // The old refresh token may already be void, so a rotated one must outlive this session
+        if let Some(rotated) = token.refresh_token.as_deref().filter(|t| *t != refresh_token) {
+            if let Err(e) = SecretStore::new().update_field(PROVIDER_COMMUNITY_WRAPPER, "refresh_token", rotated) {
+                log::warn!("Failed to store the rotated community API refresh token: {}", e);
+            }
+        }
+
+        let lifetime = Duration::from_secs(token.expires_in.unwrap_or(3600));
+        TOKEN_CACHE
+            .insert(
+                oauth.refresh_token.clone(),
+                AccessToken {
+                    access_token: token.access_token.clone(),
+                    refresh_token: token.refresh_token.unwrap_or(refresh_token),
+                    expires_at: Instant::now() + lifetime.saturating_sub(TOKEN_EXPIRY_MARGIN),
+                },
+            )
+            .await;
+
+        Ok(Some(token.access_token))
+    }
+
+    /// Send an authenticated request
+    /// A 401 refreshes the access token and retries once; a 429 with a short Retry-After is waited out once
+    async fn send(&self, request: RequestBuilder, action: &str) -> ProviderResult<FixtureResponse> {
+        let mut refreshed = false;
+        let mut waited = false;
+
+        loop {
+            let Some(mut attempt) = request.try_clone() else {
+                return Err(ProviderError::Other(format!("Failed to {}: request can't be retried", action)));
+            };
+            if let Some(key) = &self.api_key {
+                attempt = attempt.header("X-API-Key", key);
+            }
+            if let Some(token) = self.access_token(refreshed).await? {
+                attempt = attempt.bearer_auth(token);
+            }
+
+            let response = self.fixtures.send(attempt).await?;
+
+            match response.status().as_u16() {
+                401 if self.oauth.is_some() && !refreshed => {
+                    log::info!("Community API rejected the access token, refreshing");
+                    refreshed = true;
+                }
+                429 if !waited => match retry_after_secs(response.headers()).map(Duration::from_secs) {
+                    Some(wait) if wait <= MAX_RETRY_WAIT => {
+                        log::info!("Community API rate limited, retrying in {:?}", wait);
+                        tokio::time::sleep(wait).await;
+                        waited = true;
+                    }
+                    _ => return Ok(response),
+                },
+                _ => return Ok(response),
+            }
+        }
+    }
+}
+
+/// Map an unsuccessful response to a specific `ProviderError`
+fn check_status(response: FixtureResponse, action: &str) -> ProviderResult<FixtureResponse> {
+    if response.status().is_success() {
+        return Ok(response);
+    }
+
+    Err(ProviderError::from_status(
+        response.status().as_u16(),
+        retry_after_secs(response.headers()),
+        format!("Failed to {}", action),
+    ))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct MosqueResponse {
+    id: String,
+    name: String,
+    address: Option<String>,
+    city: Option<String>,
+    country: Option<String>,
+    latitude: Option<f64>,
+    longitude: Option<f64>,
+    #[serde(default)]
+    facilities: MosqueFacilities,
+}
+
+impl From<MosqueResponse> for Mosque {
+    fn from(m: MosqueResponse) -> Self {
+        Mosque {
+            id: m.id,
+            name: m.name,
+            address: m.address,
+            city: m.city,
+            country: m.country,
+            latitude: m.latitude,
+            longitude: m.longitude,
+            is_favorite: false,
+            last_accessed: None,
+            facilities: m.facilities,
+        }
+    }
+}
+
+/// Search responses: a page envelope, or a bare list from wrappers without paging
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum SearchResponse {
+    Page {
+        #[serde(alias = "data", alias = "results")]
+        mosques: Vec<MosqueResponse>,
+        total: Option<usize>,
+    },
+    List(Vec<MosqueResponse>),
+}
+
+impl SearchResponse {
+    fn into_result(self, params: &MosqueSearchParams) -> MosqueSearchResult {
+        match self {
+            SearchResponse::Page { mosques, total } => {
+                let mosques: Vec<Mosque> = mosques.into_iter().map(Mosque::from).collect();
+                MosqueSearchResult {
+                    total: total.unwrap_or(mosques.len()),
+                    mosques,
+                }
+            }
+            // The wrapper ignored the paging parameters
+            SearchResponse::List(mosques) => {
+                params.paginate(mosques.into_iter().map(Mosque::from).collect())
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PrayerTimesResponse {
+    date: String,
+    fajr: PrayerResponse,
+    dhuhr: PrayerResponse,
+    asr: PrayerResponse,
+    maghrib: PrayerResponse,
+    isha: PrayerResponse,
+    jumuah: Option<PrayerResponse>,
+    #[serde(default)]
+    imsak: Option<String>,
+    #[serde(default)]
+    shuruq: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PrayerResponse {
+    adhan: String,
+    iqama: Option<String>,
+    rakah_count: Option<i32>,
+}
+
+#[async_trait]
+impl PrayerDataProvider for CommunityWrapperProvider {
+    fn id(&self) -> &str {
+        PROVIDER_COMMUNITY_WRAPPER
+    }
+
+    fn name(&self) -> &str {
+        "Community Wrapper API"
+    }
+
+    fn description(&self) -> &str {
+        "Community-provided REST API wrapper for prayer times data"
+    }
+
+    fn config_schema(&self) -> Vec<ConfigField> {
+        vec![
+            ConfigField::new("base_url", "API Base URL", ConfigFieldType::Url)
+                .required()
+                .description("The base URL of the community API"),
+            ConfigField::new("api_key", "API Key", ConfigFieldType::Password)
+                .description("Optional API key for authentication"),
+            ConfigField::new("token_url", "OAuth Token URL", ConfigFieldType::Url)
+                .description("Token endpoint, for wrappers that use OAuth2 instead of an API key"),
+            ConfigField::new("client_id", "OAuth Client ID", ConfigFieldType::String),
+            ConfigField::new("client_secret", "OAuth Client Secret", ConfigFieldType::Password),
+            ConfigField::new("refresh_token", "OAuth Refresh Token", ConfigFieldType::Password)
+                .description("Exchanged for short-lived access tokens, renewed automatically"),
+        ]
+    }
+
+    async fn initialize(&mut self, config: Value) -> ProviderResult<()> {
+        self.base_url = config["base_url"]
+            .as_str()
+            .map(|s| s.trim_end_matches('/').to_string());
+        self.api_key = config["api_key"].as_str().map(|s| s.to_string());
+
+        let field = |key: &str| {
+            config[key]
+                .as_str()
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(|s| s.to_string())
+        };
+        self.oauth = match (field("token_url"), field("refresh_token")) {
+            (Some(token_url), Some(refresh_token)) => Some(OAuthConfig {
+                token_url,
+                client_id: field("client_id").unwrap_or_default(),
+                client_secret: field("client_secret"),
+                refresh_token,
+            }),
+            (None, None) => None,
+            _ => {
+                return Err(ProviderError::InvalidConfig(
+                    "OAuth needs both a token URL and a refresh token".to_string(),
+                ))
+            }
+        };
+        Ok(())
+    }
+
+    async fn search_mosques(&self, query: &str, location: Option<&GeoLocation>) -> ProviderResult<Vec<Mosque>> {
+        let result = self
+            .search_mosques_page(query, location, &MosqueSearchParams::default())
+            .await?;
+        Ok(result.mosques)
+    }
+
+    async fn search_mosques_page(
+        &self,
+        query: &str,
+        _location: Option<&GeoLocation>,
+        params: &MosqueSearchParams,
+    ) -> ProviderResult<MosqueSearchResult> {
+        let base_url = self.ensure_initialized()?;
+
+        let mut request = self.client.get(format!("{}/mosques/search", base_url));
+        request = request.query(&[("q", query)]);
+
+        if params.is_paged() {
+            request = request.query(&[("page", params.page()), ("limit", params.limit())]);
+        }
+        if let Some(country) = &params.country {
+            request = request.query(&[("country", country)]);
+        }
+        if let Some(city) = &params.city {
+            request = request.query(&[("city", city)]);
+        }
+
+        let response = self.send(request, "search mosques").await?;
+
+        let response = check_status(response, "search mosques")?;
+
+        let body: SearchResponse = response.json().map_err(|e| {
+            ProviderError::Parse(format!("Failed to parse response: {}", e))
+        })?;
+
+        Ok(body.into_result(params))
+    }
+
+    async fn get_nearby_mosques(&self, location: &GeoLocation, radius_km: f64) -> ProviderResult<Vec<Mosque>> {
+        let base_url = self.ensure_initialized()?;
+
+        let mut request = self.client.get(format!("{}/mosques/nearby", base_url));
+        request = request.query(&[
+            ("lat", location.latitude.to_string()),
+            ("lng", location.longitude.to_string()),
+            ("radius", radius_km.to_string()),
+        ]);
+
+        let response = self.send(request, "get nearby mosques").await?;
+
+        let response = check_status(response, "get nearby mosques")?;
+
+        let mosques: Vec<MosqueResponse> = response.json().map_err(|e| {
+            ProviderError::Parse(format!("Failed to parse response: {}", e))
+        })?;
+
+        Ok(mosques
+            .into_iter()
+            .map(Mosque::from)
+            .collect())
+    }
+
+    async fn get_prayer_times(&self, mosque_id: &str, date: Option<NaiveDate>) -> ProviderResult<PrayerTimes> {
+        let base_url = self.ensure_initialized()?;
+
+        let mut request = self
+            .client
+            .get(format!("{}/mosques/{}/prayer-times", base_url, mosque_id));
+
+        if let Some(d) = date {
+            request = request.query(&[("date", d.format("%Y-%m-%d").to_string())]);
+        }
+
+        let response = self.send(request, "get prayer times").await?;
+
+        if response.status().as_u16() == 404 {
+            return Err(ProviderError::NotFound(format!(
+                "Mosque {} not found",
+                mosque_id
+            )));
+        }
+
+        let response = check_status(response, "get prayer times")?;
+
+        let url = response.url().to_string();
+        let body = response.bytes();
+        record_payload(PROVIDER_COMMUNITY_WRAPPER, mosque_id, &url, body);
+
+        let times: PrayerTimesResponse = serde_json::from_slice(body).map_err(|e| {
+            ProviderError::Parse(format!("Failed to parse response: {}", e))
+        })?;
+
+        let day = NaiveDate::parse_from_str(&times.date, "%Y-%m-%d")
+            .map_err(|e| ProviderError::Parse(format!("Invalid date: {}", e)))?;
+        let date = time_on_date_utc(day, "00:00");
+
+        fn parse_prayer(name: PrayerName, p: PrayerResponse, day: NaiveDate) -> Prayer {
+            Prayer {
+                name,
+                adhan: time_on_date_utc(day, &p.adhan),
+                iqama: p.iqama.as_ref().map(|t| time_on_date_utc(day, t)),
+                custom_rakah_count: p.rakah_count,
+                iqama_offset_minutes: None,
+            }
+        }
+
+        Ok(PrayerTimes {
+            date,
+            fajr: parse_prayer(PrayerName::Fajr, times.fajr, day),
+            dhuhr: parse_prayer(PrayerName::Dhuhr, times.dhuhr, day),
+            asr: parse_prayer(PrayerName::Asr, times.asr, day),
+            maghrib: parse_prayer(PrayerName::Maghrib, times.maghrib, day),
+            isha: parse_prayer(PrayerName::Isha, times.isha, day),
+            jumuah: times.jumuah.map(|j| parse_prayer(PrayerName::Jumuah, j, day)),
+            mosque_id: Some(mosque_id.to_string()),
+            mosque_name: None,
+            cached_at: Some(Utc::now()),
+            imsak: times.imsak.as_ref().map(|t| time_on_date_utc(day, t)),
+            shuruq: times.shuruq.as_ref().map(|t| time_on_date_utc(day, t)),
+            asr_method: None,
+            provider: Some(PROVIDER_COMMUNITY_WRAPPER.to_string()),
+            freshness: None,
+            warnings: Vec::new(),
+        })
+    }
+
+    async fn test_connection(&self) -> ProviderResult<ProviderTestResult> {
+        let start = std::time::Instant::now();
+
+        let base_url = self.ensure_initialized()?;
+
+        let request = self.client.get(format!("{}/health", base_url));
+
+        match self.send(request, "reach the API").await {
+            Ok(response) => {
+                let latency = start.elapsed().as_millis() as u64;
+                if response.status().is_success() {
+                    Ok(ProviderTestResult {
+                        success: true,
+                        message: "Connection successful".to_string(),
+                        latency_ms: Some(latency),
+                    })
+                } else {
+                    Ok(ProviderTestResult {
+                        success: false,
+                        message: format!("Server returned: {}", response.status()),
+                        latency_ms: Some(latency),
+                    })
+                }
+            }
+            Err(e) => Ok(ProviderTestResult {
+                success: false,
+                message: format!("Connection failed: {}", e),
+                latency_ms: None,
+            }),
+        }
+    }
+
+    async fn get_mosque_details(&self, mosque_id: &str) -> ProviderResult<Mosque> {
+        let base_url = self.ensure_initialized()?;
+
+        let request = self
+            .client
+            .get(format!("{}/mosques/{}", base_url, mosque_id));
+
+        let response = self.send(request, "get mosque details").await?;
+
+        if response.status().as_u16() == 404 {
+            return Err(ProviderError::NotFound(format!(
+                "Mosque {} not found",
+                mosque_id
+            )));
+        }
+
+        let response = check_status(response, "get mosque details")?;
+
+        let m: MosqueResponse = response.json().map_err(|e| {
+            ProviderError::Parse(format!("Failed to parse response: {}", e))
+        })?;
+
+        Ok(Mosque::from(m))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_search_response_shapes() {
+        let params = MosqueSearchParams {
+            page: Some(2),
+            limit: Some(1),
+            country: Some("gb".to_string()),
+            ..MosqueSearchParams::default()
+        };
+
+        let page: SearchResponse = serde_json::from_str(
+            r#"{"data":[{"id":"b","name":"B","address":null,"city":null,"country":"GB","latitude":null,"longitude":null}],"total":7}"#,
+        )
+        .unwrap();
+        let result = page.into_result(&params);
+        assert_eq!((result.mosques[0].id.as_str(), result.total), ("b", 7));
+
+        // A bare list is filtered and paged locally
+        let list: SearchResponse = serde_json::from_str(
+            r#"[{"id":"a","name":"A","address":null,"city":null,"country":"GB","latitude":null,"longitude":null},
+                {"id":"x","name":"X","address":null,"city":null,"country":"FR","latitude":null,"longitude":null},
+                {"id":"b","name":"B","address":null,"city":null,"country":"GB","latitude":null,"longitude":null}]"#,
+        )
+        .unwrap();
+        let result = list.into_result(&params);
+        assert_eq!((result.mosques[0].id.as_str(), result.total), ("b", 2));
+    }
+
+    #[test]
+    fn test_status_mapping() {
+        let mut headers = reqwest::header::HeaderMap::new();
+        headers.insert(reqwest::header::RETRY_AFTER, "30".parse().unwrap());
+        let retry_after = retry_after_secs(&headers);
+
+        assert!(matches!(
+            ProviderError::from_status(429, retry_after, "Failed to search mosques"),
+            ProviderError::RateLimited { retry_after_secs: Some(30), .. }
+        ));
+        assert!(matches!(ProviderError::from_status(401, None, ""), ProviderError::Unauthorized(_)));
+        assert!(matches!(ProviderError::from_status(403, None, ""), ProviderError::Forbidden(_)));
+        assert!(matches!(
+            ProviderError::from_status(502, None, ""),
+            ProviderError::Server { status_code: 502, .. }
+        ));
+    }
+}
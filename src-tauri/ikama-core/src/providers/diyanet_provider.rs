@@ -0,0 +1,402 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{NaiveDate, Utc};
+use moka::future::Cache;
+use once_cell::sync::Lazy;
+use reqwest::Client;
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::models::*;
+use crate::providers::http_fixtures::{FixtureMode, FixtureResponse};
+use crate::providers::time_utils::time_on_date_local;
+use crate::providers::{PrayerDataProvider, ProviderError, ProviderResult};
+use crate::services::{clock, record_payload};
+
+const DIYANET_BASE_URL: &str = "https://awqatsalah.diyanet.gov.tr";
+
+/// Diyanet's id for Türkiye in its place catalog
+const TURKEY_COUNTRY_ID: u32 = 2;
+
+/// Prefix of district ids served by this provider
+const ID_PREFIX: &str = "diyanet:";
+
+/// Access tokens expire after 45 minutes; renew a little earlier
+const TOKEN_TTL: Duration = Duration::from_secs(40 * 60);
+
+// Access tokens by account, shared by all provider instances
+static TOKEN_CACHE: Lazy<Cache<String, String>> = Lazy::new(|| {
+    Cache::builder()
+        .max_capacity(4)
+        .time_to_live(TOKEN_TTL)
+        .build()
+});
+
+/// Diyanet Provider
+/// Official prayer times of the Turkish Presidency of Religious Affairs (awqatsalah API).
+/// Times are published per district (ilçe), so each district is returned as a "mosque"
+/// with id `diyanet:<district id>`.
+pub struct DiyanetProvider {
+    client: Client,
+    base_url: String,
+    email: Option<String>,
+    password: Option<String>,
+    district_id: Option<u32>,
+    fixtures: FixtureMode,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiResponse<T> {
+    data: Option<T>,
+    success: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LoginData {
+    access_token: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct Place {
+    id: u32,
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DayResponse {
+    fajr: String,
+    dhuhr: String,
+    asr: String,
+    maghrib: String,
+    isha: String,
+    #[serde(default)]
+    sunrise: Option<String>,
+    /// "dd.MM.yyyy" on current deployments, ISO dates on some older ones
+    gregorian_date_short: String,
+}
+
+impl DayResponse {
+    fn date(&self) -> Option<NaiveDate> {
+        NaiveDate::parse_from_str(&self.gregorian_date_short, "%d.%m.%Y")
+            .or_else(|_| NaiveDate::parse_from_str(&self.gregorian_date_short, "%Y-%m-%d"))
+            .ok()
+    }
+}
+
+impl DiyanetProvider {
+    pub fn new() -> Self {
+        Self {
+            client: Client::new(),
+            base_url: DIYANET_BASE_URL.to_string(),
+            email: None,
+            password: None,
+            district_id: None,
+            fixtures: FixtureMode::from_env(),
+        }
+    }
+
+    async fn access_token(&self) -> ProviderResult<String> {
+        let (Some(email), Some(password)) = (&self.email, &self.password) else {
+            return Err(ProviderError::InvalidConfig(
+                "Diyanet account email and password required".to_string(),
+            ));
+        };
+
+        if let Some(token) = TOKEN_CACHE.get(email).await {
+            return Ok(token);
+        }
+
+        let request = self.client
+            .post(format!("{}/Auth/Login", self.base_url))
+            .json(&serde_json::json!({ "email": email, "password": password }));
+        let response = self.fixtures.send(request).await?;
+
+        if matches!(response.status().as_u16(), 400 | 401 | 403) {
+            return Err(ProviderError::InvalidConfig("Invalid Diyanet email or password".to_string()));
+        }
+
+        let login: LoginData = parse_response(response, None)?;
+        TOKEN_CACHE.insert(email.clone(), login.access_token.clone()).await;
+
+        Ok(login.access_token)
+    }
+
+    /// `mosque_id` archives the response as that mosque's schedule
+    async fn get<T: for<'de> Deserialize<'de>>(&self, path: &str, mosque_id: Option<&str>) -> ProviderResult<T> {
+        let token = self.access_token().await?;
+
+        let request = self.client
+            .get(format!("{}{}", self.base_url, path))
+            .header("Authorization", format!("Bearer {}", token));
+        let response = self.fixtures.send(request).await?;
+
+        if response.status().as_u16() == 404 {
+            return Err(ProviderError::NotFound(format!("Diyanet has no data for {}", path)));
+        }
+
+        parse_response(response, mosque_id)
+    }
+
+    /// Provinces (il) of Türkiye
+    async fn provinces(&self) -> ProviderResult<Vec<Place>> {
+        self.get(&format!("/api/Place/States/{}", TURKEY_COUNTRY_ID), None).await
+    }
+
+    /// Districts (ilçe) of a province
+    async fn districts(&self, province_id: u32) -> ProviderResult<Vec<Place>> {
+        self.get(&format!("/api/Place/Cities/{}", province_id), None).await
+    }
+}
+
+impl Default for DiyanetProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn parse_response<T: for<'de> Deserialize<'de>>(
+    response: FixtureResponse,
+    mosque_id: Option<&str>,
+) -> ProviderResult<T> {
+    if !response.status().is_success() {
+        return Err(ProviderError::Server {
+            status_code: response.status().as_u16(),
+            message: "Diyanet request failed".to_string(),
+        });
+    }
+
+    let url = response.url().to_string();
+    let bytes = response.bytes();
+    if let Some(mosque_id) = mosque_id {
+        record_payload(PROVIDER_DIYANET, mosque_id, &url, bytes);
+    }
+
+    let body: ApiResponse<T> = serde_json::from_slice(bytes).map_err(|e| {
+        ProviderError::Parse(format!("Failed to parse: {}", e))
+    })?;
+
+    match body.data {
+        Some(data) if body.success != Some(false) => Ok(data),
+        _ => Err(ProviderError::Parse("Diyanet response has no data".to_string())),
+    }
+}
+
+fn parse_district_id(mosque_id: &str) -> ProviderResult<u32> {
+    mosque_id
+        .strip_prefix(ID_PREFIX)
+        .and_then(|id| id.parse().ok())
+        .ok_or_else(|| ProviderError::NotFound(format!("{} is not a Diyanet district", mosque_id)))
+}
+
+fn district_mosque(district: &Place, province: &str) -> Mosque {
+    let mut mosque = Mosque::new(format!("{}{}", ID_PREFIX, district.id), district.name.clone());
+    mosque.city = Some(province.to_string());
+    mosque.country = Some("TR".to_string());
+    mosque
+}
+
+/// Case- and dotted-i-insensitive match, so "istanbul" finds "İSTANBUL"
+fn turkish_contains(haystack: &str, needle: &str) -> bool {
+    let fold = |s: &str| {
+        s.chars()
+            .map(|c| match c {
+                'İ' | 'I' | 'ı' => 'i',
+                'Ş' | 'ş' => 's',
+                'Ğ' | 'ğ' => 'g',
+                'Ü' | 'ü' => 'u',
+                'Ö' | 'ö' => 'o',
+                'Ç' | 'ç' => 'c',
+                c => c.to_ascii_lowercase(),
+            })
+            .collect::<String>()
+    };
+
+    fold(haystack).contains(&fold(needle))
+}
+
+fn to_prayer_times(day: &DayResponse, date: NaiveDate, mosque_id: &str) -> PrayerTimes {
+    let adhan = |name: PrayerName, time: &str| Prayer {
+        name,
+        adhan: time_on_date_local(date, time),
+        iqama: None,
+        custom_rakah_count: None,
+        iqama_offset_minutes: None,
+    };
+
+    PrayerTimes {
+        date: time_on_date_local(date, "00:00"),
+        fajr: adhan(PrayerName::Fajr, &day.fajr),
+        dhuhr: adhan(PrayerName::Dhuhr, &day.dhuhr),
+        asr: adhan(PrayerName::Asr, &day.asr),
+        maghrib: adhan(PrayerName::Maghrib, &day.maghrib),
+        isha: adhan(PrayerName::Isha, &day.isha),
+        jumuah: None,
+        mosque_id: Some(mosque_id.to_string()),
+        mosque_name: None,
+        cached_at: Some(Utc::now()),
+        imsak: None,
+        shuruq: day.sunrise.as_deref().map(|t| time_on_date_local(date, t)),
+        // Diyanet publishes the first Asr time (asr-ı evvel)
+        asr_method: Some(AsrMethod::Standard),
+        provider: Some(PROVIDER_DIYANET.to_string()),
+        freshness: None,
+        warnings: Vec::new(),
+    }
+}
+
+#[async_trait]
+impl PrayerDataProvider for DiyanetProvider {
+    fn id(&self) -> &str {
+        PROVIDER_DIYANET
+    }
+
+    fn name(&self) -> &str {
+        "Diyanet (Türkiye)"
+    }
+
+    fn description(&self) -> &str {
+        "Official prayer times of the Turkish Presidency of Religious Affairs, per district"
+    }
+
+    fn config_schema(&self) -> Vec<ConfigField> {
+        vec![
+            ConfigField::new("email", "Email", ConfigFieldType::String)
+                .required()
+                .description("Account registered at awqatsalah.diyanet.gov.tr"),
+            ConfigField::new("password", "Password", ConfigFieldType::Password)
+                .required(),
+            ConfigField::new("district_id", "District", ConfigFieldType::Number)
+                .description("Diyanet district (ilçe) id; search a province name to list its districts"),
+            ConfigField::new("base_url", "Base URL", ConfigFieldType::Url)
+                .default_value(DIYANET_BASE_URL)
+                .description("Optional: Custom API base URL"),
+        ]
+    }
+
+    async fn initialize(&mut self, config: Value) -> ProviderResult<()> {
+        self.email = config["email"].as_str().filter(|s| !s.is_empty()).map(|s| s.to_string());
+        self.password = config["password"].as_str().filter(|s| !s.is_empty()).map(|s| s.to_string());
+        self.district_id = match &config["district_id"] {
+            Value::Number(n) => n.as_u64().and_then(|n| u32::try_from(n).ok()),
+            Value::String(s) => s.trim().parse().ok(),
+            _ => None,
+        };
+        if let Some(url) = config["base_url"].as_str().filter(|u| !u.is_empty()) {
+            self.base_url = url.trim_end_matches('/').to_string();
+        }
+        Ok(())
+    }
+
+    /// Matches province names and lists their districts; the configured district comes first
+    async fn search_mosques(&self, query: &str, _location: Option<&GeoLocation>) -> ProviderResult<Vec<Mosque>> {
+        let mut mosques = Vec::new();
+
+        if let Some(id) = self.district_id {
+            if let Ok(mosque) = self.get_mosque_details(&format!("{}{}", ID_PREFIX, id)).await {
+                mosques.push(mosque);
+            }
+        }
+
+        let query = query.trim();
+        if query.is_empty() {
+            return Ok(mosques);
+        }
+
+        for province in self.provinces().await?.iter().filter(|p| turkish_contains(&p.name, query)) {
+            for district in self.districts(province.id).await? {
+                let mosque = district_mosque(&district, &province.name);
+                if !mosques.iter().any(|m| m.id == mosque.id) {
+                    mosques.push(mosque);
+                }
+            }
+        }
+
+        Ok(mosques)
+    }
+
+    async fn get_nearby_mosques(&self, _location: &GeoLocation, _radius_km: f64) -> ProviderResult<Vec<Mosque>> {
+        Err(ProviderError::Other("Diyanet districts can't be searched by location".to_string()))
+    }
+
+    async fn get_prayer_times(&self, mosque_id: &str, date: Option<NaiveDate>) -> ProviderResult<PrayerTimes> {
+        let district_id = parse_district_id(mosque_id)?;
+        let date = date.unwrap_or_else(clock::today);
+
+        // The monthly feed covers the next 30 days from today
+        let days: Vec<DayResponse> = self
+            .get(&format!("/api/PrayerTime/Monthly/{}", district_id), Some(mosque_id))
+            .await?;
+
+        days.iter()
+            .find(|day| day.date() == Some(date))
+            .map(|day| to_prayer_times(day, date, mosque_id))
+            .ok_or_else(|| ProviderError::NotFound(format!("Diyanet has no times for {} on {}", mosque_id, date)))
+    }
+
+    async fn test_connection(&self) -> ProviderResult<ProviderTestResult> {
+        let start = std::time::Instant::now();
+
+        match self.provinces().await {
+            Ok(provinces) => Ok(ProviderTestResult {
+                success: true,
+                message: format!("Connected! {} provinces available", provinces.len()),
+                latency_ms: Some(start.elapsed().as_millis() as u64),
+            }),
+            Err(e) => Ok(ProviderTestResult {
+                success: false,
+                message: format!("Connection failed: {}", e),
+                latency_ms: None,
+            }),
+        }
+    }
+
+    async fn get_mosque_details(&self, mosque_id: &str) -> ProviderResult<Mosque> {
+        let district_id = parse_district_id(mosque_id)?;
+        let district: Place = self.get(&format!("/api/Place/CityDetail/{}", district_id), None).await?;
+
+        let mut mosque = Mosque::new(mosque_id.to_string(), district.name);
+        mosque.country = Some("TR".to_string());
+        Ok(mosque)
+    }
+}
+
+/// Whether a mosque id refers to a Diyanet district
+pub fn is_diyanet_id(mosque_id: &str) -> bool {
+    mosque_id.starts_with(ID_PREFIX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_monthly_day_lookup() {
+        let json = r#"{"data":[
+            {"fajr":"06:41","sunrise":"08:08","dhuhr":"13:17","asr":"15:39","maghrib":"18:01","isha":"19:23",
+             "gregorianDateShort":"05.02.2026","hijriDateShort":"17.8.1447"},
+            {"fajr":"06:40","sunrise":"08:07","dhuhr":"13:17","asr":"15:40","maghrib":"18:03","isha":"19:24",
+             "gregorianDateShort":"06.02.2026","hijriDateShort":"18.8.1447"}
+        ],"success":true,"message":null}"#;
+        let body: ApiResponse<Vec<DayResponse>> = serde_json::from_str(json).unwrap();
+        let days = body.data.unwrap();
+        let date = NaiveDate::from_ymd_opt(2026, 2, 6).unwrap();
+
+        let day = days.iter().find(|d| d.date() == Some(date)).unwrap();
+        let times = to_prayer_times(day, date, "diyanet:9541");
+
+        assert_eq!(times.maghrib.adhan, time_on_date_local(date, "18:03"));
+        assert!(times.isha.iqama.is_none());
+    }
+
+    #[test]
+    fn test_district_ids_and_turkish_matching() {
+        assert_eq!(parse_district_id("diyanet:9541").unwrap(), 9541);
+        assert!(parse_district_id("mosquee-paris").is_err());
+        assert!(turkish_contains("İSTANBUL", "istanbul"));
+        assert!(turkish_contains("ŞANLIURFA", "sanliurfa"));
+        assert!(!turkish_contains("ANKARA", "izmir"));
+    }
+}
@@ -0,0 +1,357 @@
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use futures::future::{select_ok, BoxFuture};
+use serde_json::Value;
+use std::time::Duration;
+
+use crate::models::*;
+use crate::providers::{PrayerDataProvider, ProviderError, ProviderResult};
+
+/// How the chain dispatches an operation to its providers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FallbackMode {
+    /// Try each provider in order until one succeeds
+    Sequential,
+    /// Query all providers at once, first successful result wins and the rest are cancelled
+    Race,
+}
+
+/// Operations that can be configured independently
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FallbackOperation {
+    SearchMosques,
+    NearbyMosques,
+    PrayerTimes,
+    MosqueDetails,
+}
+
+/// Dispatch mode and per-provider deadline for one operation
+#[derive(Debug, Clone, Copy)]
+pub struct FallbackPolicy {
+    pub mode: FallbackMode,
+    pub provider_deadline: Option<Duration>,
+}
+
+impl FallbackPolicy {
+    pub fn sequential() -> Self {
+        Self {
+            mode: FallbackMode::Sequential,
+            provider_deadline: None,
+        }
+    }
+
+    pub fn race(provider_deadline: Duration) -> Self {
+        Self {
+            mode: FallbackMode::Race,
+            provider_deadline: Some(provider_deadline),
+        }
+    }
+
+    pub fn deadline(mut self, deadline: Duration) -> Self {
+        self.provider_deadline = Some(deadline);
+        self
+    }
+}
+
+impl Default for FallbackPolicy {
+    fn default() -> Self {
+        Self::sequential()
+    }
+}
+
+/// Fallback provider that chains multiple providers
+/// Tries each provider in order until one succeeds, or races them when configured
+pub struct FallbackProvider {
+    providers: Vec<Box<dyn PrayerDataProvider>>,
+    search_policy: FallbackPolicy,
+    nearby_policy: FallbackPolicy,
+    prayer_times_policy: FallbackPolicy,
+    details_policy: FallbackPolicy,
+}
+
+impl FallbackProvider {
+    pub fn new() -> Self {
+        Self {
+            providers: Vec::new(),
+            search_policy: FallbackPolicy::default(),
+            nearby_policy: FallbackPolicy::default(),
+            prayer_times_policy: FallbackPolicy::default(),
+            details_policy: FallbackPolicy::default(),
+        }
+    }
+
+    pub fn add_provider(&mut self, provider: Box<dyn PrayerDataProvider>) {
+        self.providers.push(provider);
+    }
+
+    pub fn set_policy(&mut self, operation: FallbackOperation, policy: FallbackPolicy) {
+        match operation {
+            FallbackOperation::SearchMosques => self.search_policy = policy,
+            FallbackOperation::NearbyMosques => self.nearby_policy = policy,
+            FallbackOperation::PrayerTimes => self.prayer_times_policy = policy,
+            FallbackOperation::MosqueDetails => self.details_policy = policy,
+        }
+    }
+
+    pub fn builder() -> FallbackProviderBuilder {
+        FallbackProviderBuilder::new()
+    }
+
+    /// Run an operation across the chain according to its policy
+    async fn dispatch<'a, T, F>(&'a self, policy: FallbackPolicy, call: F) -> ProviderResult<T>
+    where
+        T: Send + 'a,
+        F: Fn(&'a dyn PrayerDataProvider) -> BoxFuture<'a, ProviderResult<T>>,
+    {
+        if self.providers.is_empty() {
+            return Err(ProviderError::Other("No providers available".to_string()));
+        }
+
+        let calls = self.providers.iter().map(|provider| {
+            with_deadline(provider.name(), policy.provider_deadline, call(provider.as_ref()))
+        });
+
+        match policy.mode {
+            FallbackMode::Sequential => {
+                let mut last_error = None;
+
+                for call in calls {
+                    match call.await {
+                        Ok(result) => return Ok(result),
+                        Err(e) => last_error = Some(e),
+                    }
+                }
+
+                Err(last_error.unwrap_or_else(|| {
+                    ProviderError::Other("No providers available".to_string())
+                }))
+            }
+            FallbackMode::Race => select_ok(calls.map(Box::pin))
+                .await
+                .map(|(result, _remaining)| result),
+        }
+    }
+}
+
+impl Default for FallbackProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Fail a provider call with a network error once its deadline has passed
+async fn with_deadline<T>(
+    provider_name: &str,
+    deadline: Option<Duration>,
+    call: BoxFuture<'_, ProviderResult<T>>,
+) -> ProviderResult<T> {
+    match deadline {
+        Some(deadline) => tokio::time::timeout(deadline, call).await.unwrap_or_else(|_| {
+            Err(ProviderError::Network(format!(
+                "{} timed out after {} ms",
+                provider_name,
+                deadline.as_millis()
+            )))
+        }),
+        None => call.await,
+    }
+}
+
+pub struct FallbackProviderBuilder {
+    provider: FallbackProvider,
+}
+
+impl FallbackProviderBuilder {
+    pub fn new() -> Self {
+        Self {
+            provider: FallbackProvider::new(),
+        }
+    }
+
+    pub fn add(mut self, provider: Box<dyn PrayerDataProvider>) -> Self {
+        self.provider.add_provider(provider);
+        self
+    }
+
+    pub fn policy(mut self, operation: FallbackOperation, policy: FallbackPolicy) -> Self {
+        self.provider.set_policy(operation, policy);
+        self
+    }
+
+    pub fn build(self) -> FallbackProvider {
+        self.provider
+    }
+}
+
+impl Default for FallbackProviderBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl PrayerDataProvider for FallbackProvider {
+    fn id(&self) -> &str {
+        "fallback"
+    }
+
+    fn name(&self) -> &str {
+        "Fallback Chain"
+    }
+
+    fn description(&self) -> &str {
+        "Automatically tries multiple providers in order or races them"
+    }
+
+    fn config_schema(&self) -> Vec<ConfigField> {
+        vec![]
+    }
+
+    async fn initialize(&mut self, _config: Value) -> ProviderResult<()> {
+        // Individual providers should be initialized separately
+        Ok(())
+    }
+
+    async fn search_mosques(&self, query: &str, location: Option<&GeoLocation>) -> ProviderResult<Vec<Mosque>> {
+        self.dispatch(self.search_policy, |p| p.search_mosques(query, location)).await
+    }
+
+    async fn get_nearby_mosques(&self, location: &GeoLocation, radius_km: f64) -> ProviderResult<Vec<Mosque>> {
+        self.dispatch(self.nearby_policy, |p| p.get_nearby_mosques(location, radius_km)).await
+    }
+
+    async fn get_prayer_times(&self, mosque_id: &str, date: Option<NaiveDate>) -> ProviderResult<PrayerTimes> {
+        self.dispatch(self.prayer_times_policy, |p| p.get_prayer_times(mosque_id, date)).await
+    }
+
+    async fn test_connection(&self) -> ProviderResult<ProviderTestResult> {
+        let mut all_success = true;
+        let mut messages = Vec::new();
+
+        for provider in &self.providers {
+            match provider.test_connection().await {
+                Ok(result) => {
+                    messages.push(format!("{}: {}", provider.name(), result.message));
+                    if !result.success {
+                        all_success = false;
+                    }
+                }
+                Err(e) => {
+                    messages.push(format!("{}: Failed - {}", provider.name(), e));
+                    all_success = false;
+                }
+            }
+        }
+
+        Ok(ProviderTestResult {
+            success: all_success,
+            message: messages.join("; "),
+            latency_ms: None,
+        })
+    }
+
+    async fn get_mosque_details(&self, mosque_id: &str) -> ProviderResult<Mosque> {
+        self.dispatch(self.details_policy, |p| p.get_mosque_details(mosque_id)).await
+    }
+
+    async fn get_events(&self, mosque_id: &str) -> ProviderResult<Vec<MosqueEvent>> {
+        self.dispatch(self.details_policy, |p| p.get_events(mosque_id)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Provider that answers after a fixed delay
+    struct DelayedProvider {
+        name: &'static str,
+        delay: Duration,
+        fail: bool,
+    }
+
+    #[async_trait]
+    impl PrayerDataProvider for DelayedProvider {
+        fn id(&self) -> &str {
+            self.name
+        }
+
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn description(&self) -> &str {
+            "Test provider"
+        }
+
+        fn config_schema(&self) -> Vec<ConfigField> {
+            vec![]
+        }
+
+        async fn initialize(&mut self, _config: Value) -> ProviderResult<()> {
+            Ok(())
+        }
+
+        async fn search_mosques(&self, _query: &str, _location: Option<&GeoLocation>) -> ProviderResult<Vec<Mosque>> {
+            tokio::time::sleep(self.delay).await;
+            if self.fail {
+                return Err(ProviderError::Other(format!("{} failed", self.name)));
+            }
+            Ok(vec![Mosque::new(self.name.to_string(), self.name.to_string())])
+        }
+
+        async fn get_nearby_mosques(&self, _location: &GeoLocation, _radius_km: f64) -> ProviderResult<Vec<Mosque>> {
+            Err(ProviderError::Other("Not supported".to_string()))
+        }
+
+        async fn get_prayer_times(&self, _mosque_id: &str, _date: Option<NaiveDate>) -> ProviderResult<PrayerTimes> {
+            Err(ProviderError::Other("Not supported".to_string()))
+        }
+
+        async fn test_connection(&self) -> ProviderResult<ProviderTestResult> {
+            Err(ProviderError::Other("Not supported".to_string()))
+        }
+
+        async fn get_mosque_details(&self, _mosque_id: &str) -> ProviderResult<Mosque> {
+            Err(ProviderError::Other("Not supported".to_string()))
+        }
+    }
+
+    fn delayed(name: &'static str, delay_ms: u64, fail: bool) -> Box<dyn PrayerDataProvider> {
+        Box::new(DelayedProvider {
+            name,
+            delay: Duration::from_millis(delay_ms),
+            fail,
+        })
+    }
+
+    #[tokio::test]
+    async fn test_race_returns_fastest_success() {
+        let chain = FallbackProvider::builder()
+            .add(delayed("slow", 500, false))
+            .add(delayed("fast-failing", 1, true))
+            .add(delayed("fast", 20, false))
+            .policy(FallbackOperation::SearchMosques, FallbackPolicy::race(Duration::from_secs(2)))
+            .build();
+
+        let results = chain.search_mosques("", None).await.unwrap();
+
+        assert_eq!(results[0].id, "fast");
+    }
+
+    #[tokio::test]
+    async fn test_sequential_deadline_skips_slow_provider() {
+        let chain = FallbackProvider::builder()
+            .add(delayed("slow", 500, false))
+            .add(delayed("second", 1, false))
+            .policy(
+                FallbackOperation::SearchMosques,
+                FallbackPolicy::sequential().deadline(Duration::from_millis(50)),
+            )
+            .build();
+
+        let results = chain.search_mosques("", None).await.unwrap();
+
+        assert_eq!(results[0].id, "second");
+    }
+}
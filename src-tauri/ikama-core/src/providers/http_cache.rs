@@ -0,0 +1,244 @@
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use chrono::{DateTime, Utc};
+use futures::StreamExt;
+use reqwest::header::{HeaderName, CONTENT_TYPE, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED};
+use reqwest::{Client, StatusCode};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tokio::io::AsyncWriteExt;
+
+use crate::db::location;
+use crate::providers::http_fixtures::{Fixture, FixtureMode, FixtureStore};
+use crate::providers::{ProviderError, ProviderResult};
+
+// Distinguishes temp files of concurrent downloads of the same URL
+static DOWNLOAD_SEQ: AtomicU64 = AtomicU64::new(0);
+
+/// Validators stored next to a cached response body
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CacheEntry {
+    url: String,
+    etag: Option<String>,
+    last_modified: Option<String>,
+    stored_at: DateTime<Utc>,
+}
+
+/// A body fetched through the cache
+pub struct CachedText {
+    pub text: String,
+    /// Downloaded (or replayed from a fixture) rather than served from disk after a 304
+    pub from_network: bool,
+}
+
+/// On-disk cache for provider GET requests
+/// Sends If-None-Match / If-Modified-Since so unchanged resources cost a 304
+pub struct HttpCache {
+    dir: PathBuf,
+    fixtures: FixtureMode,
+}
+
+impl HttpCache {
+    pub fn new(dir: PathBuf) -> Self {
+        Self {
+            dir,
+            fixtures: FixtureMode::Off,
+        }
+    }
+
+    /// Cache under the app's cache directory (portable-aware), or the temp directory if unavailable
+    /// Records or replays fixtures when `IQAMAH_HTTP_FIXTURES` asks for it
+    pub fn default_location() -> Self {
+        let base = location::cache_dir().unwrap_or_else(|| std::env::temp_dir().join("iqamah"));
+        Self::new(base.join("http")).with_fixtures(FixtureMode::from_env())
+    }
+
+    pub fn with_fixtures(mut self, fixtures: FixtureMode) -> Self {
+        self.fixtures = fixtures;
+        self
+    }
+
+    /// GET a URL through the cache and return the path of the up-to-date body on disk
+    /// The body is streamed to disk, never held fully in memory
+    pub async fn fetch_to_file(&self, client: &Client, url: &str) -> ProviderResult<PathBuf> {
+        Ok(self.fetch(client, url).await?.0)
+    }
+
+    /// GET a URL through the cache and return the body as text
+    pub async fn get_text(&self, client: &Client, url: &str) -> ProviderResult<CachedText> {
+        let (path, from_network) = self.fetch(client, url).await?;
+
+        let text = tokio::fs::read_to_string(&path)
+            .await
+            .map_err(|e| ProviderError::Parse(format!("Failed to read response: {}", e)))?;
+        Ok(CachedText { text, from_network })
+    }
+
+    /// Body stored for a URL by an earlier fetch, without revalidating it
+    pub async fn cached_text(&self, url: &str) -> Option<String> {
+        let (_, body_path) = self.paths(url);
+        tokio::fs::read_to_string(body_path).await.ok()
+    }
+
+    /// Body path, and whether the body was downloaded rather than revalidated with a 304
+    async fn fetch(&self, client: &Client, url: &str) -> ProviderResult<(PathBuf, bool)> {
+        let (meta_path, body_path) = self.paths(url);
+
+        if let FixtureMode::Replay(dir) = &self.fixtures {
+            let path = self.replay(&FixtureStore::new(dir), url, &body_path).await?;
+            return Ok((path, true));
+        }
+        let cached = read_entry(&meta_path)
+            .await
+            .filter(|_| body_path.exists());
+
+        let mut request = client.get(url);
+        if let Some(entry) = &cached {
+            if let Some(etag) = &entry.etag {
+                request = request.header(IF_NONE_MATCH, etag);
+            }
+            if let Some(last_modified) = &entry.last_modified {
+                request = request.header(IF_MODIFIED_SINCE, last_modified);
+            }
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| ProviderError::Network(format!("Failed to fetch {}: {}", url, e)))?;
+
+        if response.status() == StatusCode::NOT_MODIFIED && cached.is_some() {
+            log::debug!("HTTP cache hit (304) for {}", url);
+            // The cached body is what a fresh download would have returned, so replay needs it too
+            self.record(url, None, &body_path).await?;
+            return Ok((body_path, false));
+        }
+
+        if !response.status().is_success() {
+            return Err(ProviderError::Server {
+                status_code: response.status().as_u16(),
+                message: format!("HTTP error: {}", response.status()),
+            });
+        }
+
+        let header = |name: HeaderName| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(|s| s.to_string())
+        };
+        let entry = CacheEntry {
+            url: url.to_string(),
+            etag: header(ETAG),
+            last_modified: header(LAST_MODIFIED),
+            stored_at: Utc::now(),
+        };
+        let content_type = header(CONTENT_TYPE);
+
+        tokio::fs::create_dir_all(&self.dir).await.map_err(io_error)?;
+
+        // Write to a temp file first so a failed download never replaces a good body
+        let tmp_path = body_path.with_extension(format!(
+            "{}.part",
+            DOWNLOAD_SEQ.fetch_add(1, Ordering::Relaxed)
+        ));
+        let mut file = tokio::fs::File::create(&tmp_path).await.map_err(io_error)?;
+        let mut stream = response.bytes_stream();
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk
+                .map_err(|e| ProviderError::Network(format!("Failed to read {}: {}", url, e)))?;
+            file.write_all(&chunk).await.map_err(io_error)?;
+        }
+        file.flush().await.map_err(io_error)?;
+        drop(file);
+
+        tokio::fs::rename(&tmp_path, &body_path).await.map_err(io_error)?;
+
+        self.record(url, content_type, &body_path).await?;
+
+        // Only remember validators if the server sent any
+        if entry.etag.is_some() || entry.last_modified.is_some() {
+            let meta = serde_json::to_vec(&entry)
+                .map_err(|e| ProviderError::Other(format!("Failed to encode cache entry: {}", e)))?;
+            tokio::fs::write(&meta_path, meta).await.map_err(io_error)?;
+        } else {
+            let _ = tokio::fs::remove_file(&meta_path).await;
+        }
+
+        Ok((body_path, true))
+    }
+
+    /// Save the body now on disk as the URL's fixture, when recording
+    async fn record(&self, url: &str, content_type: Option<String>, body_path: &Path) -> ProviderResult<()> {
+        let FixtureMode::Record(dir) = &self.fixtures else {
+            return Ok(());
+        };
+        let body = tokio::fs::read_to_string(body_path).await.map_err(io_error)?;
+        let fixture = Fixture {
+            url: url.to_string(),
+            method: "GET".to_string(),
+            status: StatusCode::OK.as_u16(),
+            content_type,
+            recorded_at: Utc::now(),
+            body,
+        };
+        FixtureStore::new(dir).save(&fixture).await?;
+        Ok(())
+    }
+
+    /// Serve a recorded response through the body file, as a download would
+    async fn replay(&self, store: &FixtureStore, url: &str, body_path: &Path) -> ProviderResult<PathBuf> {
+        let fixture = store.load("GET", url).await?;
+        if !StatusCode::from_u16(fixture.status).is_ok_and(|s| s.is_success()) {
+            return Err(ProviderError::from_status(
+                fixture.status,
+                None,
+                format!("HTTP error: {}", fixture.status),
+            ));
+        }
+
+        tokio::fs::create_dir_all(&self.dir).await.map_err(io_error)?;
+        tokio::fs::write(body_path, fixture.body).await.map_err(io_error)?;
+
+        Ok(body_path.to_path_buf())
+    }
+
+    /// Files are named by a hash of the URL that stays the same across Rust versions,
+    /// so an upgrade keeps the cache
+    fn paths(&self, url: &str) -> (PathBuf, PathBuf) {
+        let key: String = Sha256::digest(url.as_bytes())[..8]
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect();
+
+        (
+            self.dir.join(format!("{}.json", key)),
+            self.dir.join(format!("{}.body", key)),
+        )
+    }
+}
+
+async fn read_entry(path: &Path) -> Option<CacheEntry> {
+    let data = tokio::fs::read(path).await.ok()?;
+    serde_json::from_slice(&data).ok()
+}
+
+fn io_error(e: std::io::Error) -> ProviderError {
+    ProviderError::Other(format!("HTTP cache I/O error: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_paths_are_stable() {
+        let cache = HttpCache::new(PathBuf::from("/cache"));
+        let (meta, body) = cache.paths("https://mawaqit.net/en/mosquee-paris");
+        assert_eq!(meta, PathBuf::from("/cache/a3be013bb272a350.json"));
+        assert_eq!(body.parent(), meta.parent());
+        assert_ne!(cache.paths("https://mawaqit.net/en/mosquee-lyon").0, meta);
+    }
+}
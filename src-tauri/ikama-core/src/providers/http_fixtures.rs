@@ -0,0 +1,276 @@
+use std::path::PathBuf;
+
+use chrono::{DateTime, Utc};
+use reqwest::header::{HeaderMap, HeaderValue, CONTENT_TYPE};
+use reqwest::{RequestBuilder, StatusCode, Url};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::providers::{ProviderError, ProviderResult};
+
+/// Environment variable selecting the fixture mode: `record:<dir>` or `replay:<dir>`
+/// Only read by builds with the `http-fixtures` feature
+pub const FIXTURES_ENV: &str = "IQAMAH_HTTP_FIXTURES";
+
+/// Longest URL-derived part of a fixture file name
+const MAX_NAME_LEN: usize = 80;
+
+/// Whether provider HTTP traffic goes to the network, is recorded, or is replayed from disk
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum FixtureMode {
+    #[default]
+    Off,
+    /// Fetch from the network and save every response as a fixture
+    Record(PathBuf),
+    /// Serve recorded fixtures and never touch the network
+    Replay(PathBuf),
+}
+
+impl FixtureMode {
+    /// Mode from `IQAMAH_HTTP_FIXTURES`, `Off` when unset or malformed, and always in
+    /// builds without the `http-fixtures` feature
+    pub fn from_env() -> Self {
+        if !cfg!(feature = "http-fixtures") {
+            return FixtureMode::Off;
+        }
+        std::env::var(FIXTURES_ENV)
+            .ok()
+            .and_then(|value| Self::parse(&value))
+            .unwrap_or_default()
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        let (mode, dir) = value.split_once(':')?;
+        let dir = PathBuf::from(dir.trim());
+        match mode.trim() {
+            "record" => Some(FixtureMode::Record(dir)),
+            "replay" => Some(FixtureMode::Replay(dir)),
+            _ => {
+                log::warn!("Ignoring {}={}: expected record:<dir> or replay:<dir>", FIXTURES_ENV, value);
+                None
+            }
+        }
+    }
+
+    /// Send a provider request that bypasses `HttpCache`, e.g. an authenticated JSON call
+    /// Replay answers with the response recorded for the same method and URL without touching
+    /// the network; record saves every response, errors included
+    pub async fn send(&self, request: RequestBuilder) -> ProviderResult<FixtureResponse> {
+        let (client, request) = request.build_split();
+        let request = request.map_err(|e| ProviderError::Other(format!("Invalid request: {}", e)))?;
+        let method = request.method().to_string();
+        let url = request.url().clone();
+
+        if let FixtureMode::Replay(dir) = self {
+            let fixture = FixtureStore::new(dir).load(&method, url.as_str()).await?;
+            let mut headers = HeaderMap::new();
+            if let Some(value) = fixture.content_type.as_deref().and_then(|v| HeaderValue::from_str(v).ok()) {
+                headers.insert(CONTENT_TYPE, value);
+            }
+            return Ok(FixtureResponse {
+                status: StatusCode::from_u16(fixture.status)
+                    .map_err(|_| ProviderError::Parse(format!("Invalid status in fixture for {}", url)))?,
+                url,
+                headers,
+                body: fixture.body.into_bytes(),
+            });
+        }
+
+        let response = client
+            .execute(request)
+            .await
+            .map_err(|e| ProviderError::Network(format!("Failed to reach {}: {}", url, e)))?;
+        let status = response.status();
+        let headers = response.headers().clone();
+        let body = response
+            .bytes()
+            .await
+            .map_err(|e| ProviderError::Network(format!("Failed to read {}: {}", url, e)))?
+            .to_vec();
+
+        if let FixtureMode::Record(dir) = self {
+            let fixture = Fixture {
+                url: url.to_string(),
+                method,
+                status: status.as_u16(),
+                content_type: headers.get(CONTENT_TYPE).and_then(|v| v.to_str().ok()).map(str::to_string),
+                recorded_at: Utc::now(),
+                body: String::from_utf8_lossy(&body).into_owned(),
+            };
+            FixtureStore::new(dir).save(&fixture).await?;
+        }
+
+        Ok(FixtureResponse { status, url, headers, body })
+    }
+}
+
+/// A response sent through `FixtureMode::send`, read into memory
+#[derive(Debug)]
+pub struct FixtureResponse {
+    status: StatusCode,
+    url: Url,
+    headers: HeaderMap,
+    body: Vec<u8>,
+}
+
+impl FixtureResponse {
+    pub fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    /// URL that was requested
+    pub fn url(&self) -> &Url {
+        &self.url
+    }
+
+    pub fn headers(&self) -> &HeaderMap {
+        &self.headers
+    }
+
+    pub fn bytes(&self) -> &[u8] {
+        &self.body
+    }
+
+    pub fn text(&self) -> String {
+        String::from_utf8_lossy(&self.body).into_owned()
+    }
+
+    pub fn json<T: DeserializeOwned>(&self) -> serde_json::Result<T> {
+        serde_json::from_slice(&self.body)
+    }
+}
+
+/// One recorded HTTP response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Fixture {
+    pub url: String,
+    /// Fixtures recorded before other methods were supported are all GETs
+    #[serde(default = "default_method")]
+    pub method: String,
+    pub status: u16,
+    pub content_type: Option<String>,
+    pub recorded_at: DateTime<Utc>,
+    pub body: String,
+}
+
+/// Directory of fixtures, one JSON file per URL
+pub struct FixtureStore {
+    dir: PathBuf,
+}
+
+impl FixtureStore {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Readable file name from the URL, with a hash so distinct URLs never collide
+    pub fn path(&self, url: &str) -> PathBuf {
+        let readable: String = url
+            .split_once("://")
+            .map_or(url, |(_, rest)| rest)
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '.' { c } else { '_' })
+            .take(MAX_NAME_LEN)
+            .collect();
+
+        self.dir.join(format!("{}-{:08x}.json", readable, fnv1a(url)))
+    }
+
+    pub async fn load(&self, method: &str, url: &str) -> ProviderResult<Fixture> {
+        let path = self.path(&request_key(method, url));
+        let data = tokio::fs::read(&path).await.map_err(|_| {
+            ProviderError::NotFound(format!("No fixture recorded for {} {} ({})", method, url, path.display()))
+        })?;
+
+        serde_json::from_slice(&data)
+            .map_err(|e| ProviderError::Parse(format!("Invalid fixture {}: {}", path.display(), e)))
+    }
+
+    pub async fn save(&self, fixture: &Fixture) -> ProviderResult<PathBuf> {
+        let path = self.path(&request_key(&fixture.method, &fixture.url));
+        let data = serde_json::to_vec_pretty(fixture)
+            .map_err(|e| ProviderError::Other(format!("Failed to encode fixture: {}", e)))?;
+
+        tokio::fs::create_dir_all(&self.dir).await.map_err(io_error)?;
+        tokio::fs::write(&path, data).await.map_err(io_error)?;
+        log::info!("Recorded fixture for {} at {}", fixture.url, path.display());
+
+        Ok(path)
+    }
+}
+
+fn default_method() -> String {
+    "GET".to_string()
+}
+
+/// What a fixture is filed under: the URL alone for GETs, so existing fixture names stay valid
+fn request_key(method: &str, url: &str) -> String {
+    if method == "GET" {
+        url.to_string()
+    } else {
+        format!("{} {}", method, url)
+    }
+}
+
+/// FNV-1a, stable across toolchains unlike `DefaultHasher`, so committed fixture names stay valid
+pub(crate) fn fnv1a(text: &str) -> u32 {
+    text.bytes()
+        .fold(0x811c_9dc5, |hash, byte| (hash ^ byte as u32).wrapping_mul(0x0100_0193))
+}
+
+fn io_error(e: std::io::Error) -> ProviderError {
+    ProviderError::Other(format!("Fixture I/O error: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mode_and_paths() {
+        assert_eq!(
+            FixtureMode::parse("replay:/tmp/fixtures"),
+            Some(FixtureMode::Replay(PathBuf::from("/tmp/fixtures")))
+        );
+        assert_eq!(FixtureMode::parse("rewind:/tmp"), None);
+
+        let store = FixtureStore::new("/tmp/fixtures");
+        let page = store.path("https://mawaqit.net/en/mosquee-paris");
+        assert!(page
+            .file_name()
+            .unwrap()
+            .to_string_lossy()
+            .starts_with("mawaqit.net_en_mosquee-paris-"));
+        assert_ne!(page, store.path("https://mawaqit.net/en/mosquee-paris?x=1"));
+
+        let login = store.path(&request_key("POST", "https://mawaqit.net/api/auth/login"));
+        assert_ne!(login, store.path(&request_key("GET", "https://mawaqit.net/api/auth/login")));
+    }
+
+    #[tokio::test]
+    async fn test_send_records_then_replays() {
+        let dir = std::env::temp_dir().join(format!("iqamah-send-fixtures-{}", std::process::id()));
+        let url = "http://127.0.0.1:9/api/times?day=1";
+        let fixture = Fixture {
+            url: url.to_string(),
+            method: "POST".to_string(),
+            status: 404,
+            content_type: Some("application/json".to_string()),
+            recorded_at: Utc::now(),
+            body: r#"{"error": "unknown mosque"}"#.to_string(),
+        };
+        FixtureStore::new(&dir).save(&fixture).await.unwrap();
+
+        let client = reqwest::Client::new();
+        let replay = FixtureMode::Replay(dir.clone());
+        let response = replay.send(client.post(url)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(response.json::<serde_json::Value>().unwrap()["error"], "unknown mosque");
+        assert_eq!(response.headers()[CONTENT_TYPE], "application/json");
+
+        // Only the recorded method is served, and replay never falls back to the network
+        assert!(matches!(replay.send(client.get(url)).await, Err(ProviderError::NotFound(_))));
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
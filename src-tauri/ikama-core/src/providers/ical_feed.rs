@@ -0,0 +1,148 @@
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, Utc};
+use reqwest::Client;
+use std::time::Duration;
+
+use crate::models::{EventKind, MosqueEvent};
+use crate::providers::{ProviderError, ProviderResult};
+use crate::services::clock::resolve_local;
+
+/// Download an iCal feed and convert its VEVENTs into mosque events
+pub async fn fetch_ical_events(url: &str, mosque_id: &str) -> ProviderResult<Vec<MosqueEvent>> {
+    let client = Client::builder()
+        .timeout(Duration::from_secs(15))
+        .build()
+        .map_err(|e| ProviderError::Other(e.to_string()))?;
+
+    let response = client
+        .get(url)
+        .send()
+        .await
+        .map_err(|e| ProviderError::Network(e.to_string()))?;
+
+    if !response.status().is_success() {
+        return Err(ProviderError::Server {
+            status_code: response.status().as_u16(),
+            message: format!("Could not load events feed {}", url),
+        });
+    }
+
+    let body = response
+        .text()
+        .await
+        .map_err(|e| ProviderError::Network(e.to_string()))?;
+
+    Ok(parse_ical_events(&body, mosque_id))
+}
+
+/// Parse VEVENT blocks; events without a summary or start are skipped
+pub fn parse_ical_events(ics: &str, mosque_id: &str) -> Vec<MosqueEvent> {
+    let mut events = Vec::new();
+    let mut current: Option<Vec<(String, String)>> = None;
+
+    for line in unfold_lines(ics) {
+        match line.as_str() {
+            "BEGIN:VEVENT" => current = Some(Vec::new()),
+            "END:VEVENT" => {
+                if let Some(event) = current.take().and_then(|props| to_event(&props, mosque_id)) {
+                    events.push(event);
+                }
+            }
+            _ => {
+                if let (Some(props), Some((name, value))) = (current.as_mut(), line.split_once(':')) {
+                    props.push((name.to_string(), value.to_string()));
+                }
+            }
+        }
+    }
+
+    events
+}
+
+/// Join RFC 5545 continuation lines (starting with a space or tab)
+fn unfold_lines(ics: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+
+    for raw in ics.lines() {
+        let raw = raw.trim_end_matches('\r');
+        match (raw.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(rest), Some(last)) => last.push_str(rest),
+            _ => lines.push(raw.to_string()),
+        }
+    }
+
+    lines
+}
+
+fn to_event(props: &[(String, String)], mosque_id: &str) -> Option<MosqueEvent> {
+    // Property names may carry parameters, e.g. "DTSTART;TZID=Europe/Paris"
+    let get = |name: &str| {
+        props
+            .iter()
+            .find(|(key, _)| key.split(';').next() == Some(name))
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+    };
+
+    let title = unescape(get("SUMMARY")?.1);
+    let starts_at = get("DTSTART").and_then(|(key, value)| parse_ical_datetime(key, value))?;
+    let uid = get("UID").map(|(_, v)| v.to_string()).unwrap_or_else(|| format!("{}-{}", title, starts_at.timestamp()));
+
+    Some(MosqueEvent {
+        id: format!("ical:{}", uid),
+        mosque_id: mosque_id.to_string(),
+        kind: EventKind::infer(&title),
+        description: get("DESCRIPTION").map(|(_, v)| unescape(v)),
+        location: get("LOCATION").map(|(_, v)| unescape(v)),
+        url: get("URL").map(|(_, v)| v.to_string()),
+        starts_at,
+        ends_at: get("DTEND").and_then(|(key, value)| parse_ical_datetime(key, value)),
+        reminder_minutes: None,
+        title,
+    })
+}
+
+/// UTC ("...Z"), floating/TZID local times (read as local time), or all-day dates
+fn parse_ical_datetime(key: &str, value: &str) -> Option<DateTime<Utc>> {
+    if let Some(utc) = value.strip_suffix('Z') {
+        return NaiveDateTime::parse_from_str(utc, "%Y%m%dT%H%M%S")
+            .ok()
+            .map(|dt| dt.and_utc());
+    }
+
+    let naive = if key.contains("VALUE=DATE") || value.len() == 8 {
+        NaiveDate::parse_from_str(value, "%Y%m%d").ok()?.and_hms_opt(0, 0, 0)?
+    } else {
+        NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S").ok()?
+    };
+
+    Some(resolve_local(&Local, naive))
+}
+
+fn unescape(value: &str) -> String {
+    value
+        .replace("\\n", "\n")
+        .replace("\\N", "\n")
+        .replace("\\,", ",")
+        .replace("\\;", ";")
+        .replace("\\\\", "\\")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ical_events() {
+        let ics = "BEGIN:VCALENDAR\r\nBEGIN:VEVENT\r\nUID:abc-1\r\nSUMMARY:Weekly tafsir class\\, open\r\n  to all\r\n\
+                   DTSTART:20260305T190000Z\r\nDTEND:20260305T203000Z\r\nLOCATION:Main hall\r\nEND:VEVENT\r\n\
+                   BEGIN:VEVENT\r\nDESCRIPTION:no summary\r\nEND:VEVENT\r\nEND:VCALENDAR\r\n";
+
+        let events = parse_ical_events(ics, "mosque-1");
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].id, "ical:abc-1");
+        assert_eq!(events[0].title, "Weekly tafsir class, open to all");
+        assert_eq!(events[0].kind, EventKind::Class);
+        assert_eq!(events[0].starts_at.to_rfc3339(), "2026-03-05T19:00:00+00:00");
+        assert_eq!(events[0].location.as_deref(), Some("Main hall"));
+    }
+}
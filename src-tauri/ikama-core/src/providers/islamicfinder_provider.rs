@@ -0,0 +1,377 @@
+use async_trait::async_trait;
+use chrono::{NaiveDate, Utc};
+use reqwest::Client;
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::models::*;
+use crate::providers::http_fixtures::FixtureMode;
+use crate::providers::time_utils::{find_time_in_text, time_on_date_local};
+use crate::providers::{PrayerDataProvider, ProviderError, ProviderResult};
+use crate::services::{clock, record_payload};
+
+const ISLAMICFINDER_BASE_URL: &str = "https://www.islamicfinder.us/index.php/api";
+
+/// Prefix of location ids served by this provider
+const ID_PREFIX: &str = "islamicfinder:";
+
+/// IslamicFinder Provider
+/// Calculated adhan times for any location, for areas Mawaqit doesn't cover.
+/// There are no mosques: each "mosque" is a location, either
+/// `islamicfinder:<lat>,<lon>` or `islamicfinder:<country>:<postal code>`.
+pub struct IslamicFinderProvider {
+    client: Client,
+    base_url: String,
+    default_country: String,
+    method: u8,
+    asr_method: AsrMethod,
+    fixtures: FixtureMode,
+}
+
+/// Where to calculate prayer times for
+#[derive(Debug, Clone, PartialEq)]
+enum Location {
+    Coordinates { latitude: f64, longitude: f64 },
+    PostalCode { country: String, zipcode: String },
+}
+
+impl Location {
+    fn parse_id(id: &str) -> ProviderResult<Self> {
+        let rest = id.strip_prefix(ID_PREFIX).ok_or_else(|| {
+            ProviderError::NotFound(format!("{} is not an IslamicFinder location", id))
+        })?;
+
+        if let Some((country, zipcode)) = rest.split_once(':') {
+            return Ok(Location::PostalCode {
+                country: country.to_string(),
+                zipcode: zipcode.to_string(),
+            });
+        }
+
+        let (latitude, longitude) = rest
+            .split_once(',')
+            .and_then(|(lat, lon)| Some((lat.parse().ok()?, lon.parse().ok()?)))
+            .ok_or_else(|| ProviderError::NotFound(format!("Invalid IslamicFinder location {}", id)))?;
+
+        Ok(Location::Coordinates { latitude, longitude })
+    }
+
+    fn id(&self) -> String {
+        match self {
+            Location::Coordinates { latitude, longitude } => {
+                format!("{}{:.4},{:.4}", ID_PREFIX, latitude, longitude)
+            }
+            Location::PostalCode { country, zipcode } => format!("{}{}:{}", ID_PREFIX, country, zipcode),
+        }
+    }
+
+    fn query(&self) -> Vec<(&'static str, String)> {
+        match self {
+            Location::Coordinates { latitude, longitude } => vec![
+                ("latitude", latitude.to_string()),
+                ("longitude", longitude.to_string()),
+            ],
+            Location::PostalCode { country, zipcode } => {
+                vec![("country", country.clone()), ("zipcode", zipcode.clone())]
+            }
+        }
+    }
+
+    fn to_mosque(&self) -> Mosque {
+        let mut mosque = Mosque::new(self.id(), String::new());
+        match self {
+            Location::Coordinates { latitude, longitude } => {
+                mosque.name = format!("Prayer times at {:.4}, {:.4}", latitude, longitude);
+                mosque.latitude = Some(*latitude);
+                mosque.longitude = Some(*longitude);
+            }
+            Location::PostalCode { country, zipcode } => {
+                mosque.name = format!("Prayer times for {} {}", zipcode, country);
+                mosque.country = Some(country.clone());
+            }
+        }
+        mosque
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct PrayerTimesResponse {
+    results: Option<ResultsResponse>,
+    success: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ResultsResponse {
+    fajr: String,
+    dhuhr: String,
+    asr: String,
+    maghrib: String,
+    isha: String,
+}
+
+impl IslamicFinderProvider {
+    pub fn new() -> Self {
+        Self {
+            client: Client::new(),
+            base_url: ISLAMICFINDER_BASE_URL.to_string(),
+            default_country: "US".to_string(),
+            method: 2,
+            asr_method: AsrMethod::Standard,
+            fixtures: FixtureMode::from_env(),
+        }
+    }
+
+    async fn fetch_times(&self, location: &Location, date: NaiveDate) -> ProviderResult<ResultsResponse> {
+        let juristic = match self.asr_method {
+            AsrMethod::Standard => "0",
+            AsrMethod::Hanafi => "1",
+        };
+        let mut query = location.query();
+        query.extend([
+            ("date", date.format("%Y-%m-%d").to_string()),
+            ("method", self.method.to_string()),
+            ("juristic", juristic.to_string()),
+            ("time_format", "0".to_string()),
+            ("show_entries", "0".to_string()),
+        ]);
+
+        let request = self.client
+            .get(format!("{}/prayer_times", self.base_url))
+            .query(&query);
+        let response = self.fixtures.send(request).await?;
+
+        if !response.status().is_success() {
+            return Err(ProviderError::Server {
+                status_code: response.status().as_u16(),
+                message: "Failed to get prayer times".to_string(),
+            });
+        }
+
+        let url = response.url().to_string();
+        let body = response.bytes();
+        record_payload(PROVIDER_ISLAMICFINDER, &location.id(), &url, body);
+
+        let data: PrayerTimesResponse = serde_json::from_slice(body).map_err(|e| {
+            ProviderError::Parse(format!("Failed to parse: {}", e))
+        })?;
+
+        match data.results {
+            Some(results) if data.success != Some(false) => Ok(results),
+            _ => Err(ProviderError::NotFound(format!("No prayer times for {}", location.id()))),
+        }
+    }
+}
+
+impl Default for IslamicFinderProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Build an adhan-only schedule; iqama defaults are applied by the caller
+fn to_prayer_times(
+    results: &ResultsResponse,
+    date: NaiveDate,
+    mosque: &Mosque,
+    asr_method: AsrMethod,
+) -> ProviderResult<PrayerTimes> {
+    let adhan = |name: PrayerName, text: &str| -> ProviderResult<Prayer> {
+        let time = find_time_in_text(text)
+            .ok_or_else(|| ProviderError::Parse(format!("Invalid {} time: {}", name, text)))?;
+        Ok(Prayer {
+            name,
+            adhan: time_on_date_local(date, &time),
+            iqama: None,
+            custom_rakah_count: None,
+            iqama_offset_minutes: None,
+        })
+    };
+
+    Ok(PrayerTimes {
+        date: time_on_date_local(date, "00:00"),
+        fajr: adhan(PrayerName::Fajr, &results.fajr)?,
+        dhuhr: adhan(PrayerName::Dhuhr, &results.dhuhr)?,
+        asr: adhan(PrayerName::Asr, &results.asr)?,
+        maghrib: adhan(PrayerName::Maghrib, &results.maghrib)?,
+        isha: adhan(PrayerName::Isha, &results.isha)?,
+        jumuah: None,
+        mosque_id: Some(mosque.id.clone()),
+        mosque_name: Some(mosque.name.clone()),
+        cached_at: Some(Utc::now()),
+        imsak: None,
+        shuruq: None,
+        asr_method: Some(asr_method),
+        provider: Some(PROVIDER_ISLAMICFINDER.to_string()),
+        freshness: None,
+        warnings: Vec::new(),
+    })
+}
+
+#[async_trait]
+impl PrayerDataProvider for IslamicFinderProvider {
+    fn id(&self) -> &str {
+        PROVIDER_ISLAMICFINDER
+    }
+
+    fn name(&self) -> &str {
+        "IslamicFinder"
+    }
+
+    fn description(&self) -> &str {
+        "Calculated adhan times for any location worldwide, for areas without Mawaqit mosques"
+    }
+
+    fn config_schema(&self) -> Vec<ConfigField> {
+        vec![
+            ConfigField::new("default_country", "Country", ConfigFieldType::String)
+                .default_value("US")
+                .description("Country code used when searching by postal code"),
+            ConfigField::new("method", "Calculation Method", ConfigFieldType::Number)
+                .default_value("2")
+                .description("IslamicFinder calculation method (2 = ISNA, 3 = MWL, 4 = Umm al-Qura)"),
+            ConfigField::new("juristic", "Asr Method", ConfigFieldType::Select)
+                .default_value("0")
+                .options(vec!["0".to_string(), "1".to_string()])
+                .description("0 = Shafi'i, Maliki, Hanbali; 1 = Hanafi"),
+            ConfigField::new("base_url", "Base URL", ConfigFieldType::Url)
+                .default_value(ISLAMICFINDER_BASE_URL)
+                .description("Optional: Custom API base URL"),
+        ]
+    }
+
+    async fn initialize(&mut self, config: Value) -> ProviderResult<()> {
+        if let Some(country) = config["default_country"].as_str().filter(|c| !c.is_empty()) {
+            self.default_country = country.to_uppercase();
+        }
+        if let Some(method) = config_number(&config["method"]) {
+            self.method = method;
+        }
+        if let Some(juristic) = config_number(&config["juristic"]) {
+            if juristic > 1 {
+                return Err(ProviderError::InvalidConfig("Asr method must be 0 or 1".to_string()));
+            }
+            self.asr_method = if juristic == 1 { AsrMethod::Hanafi } else { AsrMethod::Standard };
+        }
+        if let Some(url) = config["base_url"].as_str().filter(|u| !u.is_empty()) {
+            self.base_url = url.trim_end_matches('/').to_string();
+        }
+        Ok(())
+    }
+
+    /// The query is a postal code in the default country, or the given location is used
+    async fn search_mosques(&self, query: &str, location: Option<&GeoLocation>) -> ProviderResult<Vec<Mosque>> {
+        let query = query.trim();
+        let location = match location {
+            Some(geo) if query.is_empty() => Location::Coordinates {
+                latitude: geo.latitude,
+                longitude: geo.longitude,
+            },
+            _ if !query.is_empty() => Location::PostalCode {
+                country: self.default_country.clone(),
+                zipcode: query.to_string(),
+            },
+            _ => return Ok(Vec::new()),
+        };
+
+        Ok(vec![location.to_mosque()])
+    }
+
+    async fn get_nearby_mosques(&self, location: &GeoLocation, _radius_km: f64) -> ProviderResult<Vec<Mosque>> {
+        let location = Location::Coordinates {
+            latitude: location.latitude,
+            longitude: location.longitude,
+        };
+
+        Ok(vec![location.to_mosque()])
+    }
+
+    async fn get_prayer_times(&self, mosque_id: &str, date: Option<NaiveDate>) -> ProviderResult<PrayerTimes> {
+        let location = Location::parse_id(mosque_id)?;
+        let date = date.unwrap_or_else(clock::today);
+        let results = self.fetch_times(&location, date).await?;
+
+        to_prayer_times(&results, date, &location.to_mosque(), self.asr_method)
+    }
+
+    async fn test_connection(&self) -> ProviderResult<ProviderTestResult> {
+        let start = std::time::Instant::now();
+        // Mecca: always has a schedule
+        let location = Location::Coordinates {
+            latitude: 21.4225,
+            longitude: 39.8262,
+        };
+
+        match self.fetch_times(&location, clock::today()).await {
+            Ok(_) => Ok(ProviderTestResult {
+                success: true,
+                message: "Connection successful".to_string(),
+                latency_ms: Some(start.elapsed().as_millis() as u64),
+            }),
+            Err(e) => Ok(ProviderTestResult {
+                success: false,
+                message: format!("Connection failed: {}", e),
+                latency_ms: None,
+            }),
+        }
+    }
+
+    async fn get_mosque_details(&self, mosque_id: &str) -> ProviderResult<Mosque> {
+        Ok(Location::parse_id(mosque_id)?.to_mosque())
+    }
+}
+
+/// Whether a mosque id refers to an IslamicFinder location
+pub fn is_islamicfinder_id(mosque_id: &str) -> bool {
+    mosque_id.starts_with(ID_PREFIX)
+}
+
+/// Number settings arrive as numbers or strings from the settings form
+fn config_number(value: &Value) -> Option<u8> {
+    match value {
+        Value::Number(n) => n.as_u64().and_then(|n| u8::try_from(n).ok()),
+        Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_location_ids_roundtrip() {
+        let coords = Location::Coordinates {
+            latitude: 33.5731,
+            longitude: -7.5898,
+        };
+        assert_eq!(coords.id(), "islamicfinder:33.5731,-7.5898");
+        assert_eq!(Location::parse_id(&coords.id()).unwrap(), coords);
+
+        let postal = Location::parse_id("islamicfinder:GB:E1 6AN").unwrap();
+        assert_eq!(
+            postal,
+            Location::PostalCode {
+                country: "GB".to_string(),
+                zipcode: "E1 6AN".to_string()
+            }
+        );
+        assert!(Location::parse_id("mosquee-paris").is_err());
+    }
+
+    #[test]
+    fn test_parse_prayer_times_response() {
+        let json = r#"{"results":{"Fajr":"05:12","Duha":"06:40","Dhuhr":"12:31","Asr":"15:47",
+            "Maghrib":"18:19","Isha":"19:37"},"settings":{"method":2},"success":true}"#;
+        let data: PrayerTimesResponse = serde_json::from_str(json).unwrap();
+        let date = NaiveDate::from_ymd_opt(2026, 3, 10).unwrap();
+        let mosque = Location::parse_id("islamicfinder:33.5731,-7.5898").unwrap().to_mosque();
+
+        let times = to_prayer_times(&data.results.unwrap(), date, &mosque, AsrMethod::Hanafi).unwrap();
+
+        assert_eq!(times.maghrib.adhan, time_on_date_local(date, "18:19"));
+        assert!(times.fajr.iqama.is_none());
+        assert_eq!(times.asr_method, Some(AsrMethod::Hanafi));
+        assert_eq!(times.mosque_id.as_deref(), Some("islamicfinder:33.5731,-7.5898"));
+    }
+}
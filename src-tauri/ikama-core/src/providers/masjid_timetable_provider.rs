@@ -0,0 +1,398 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{Datelike, NaiveDate, Utc};
+use moka::future::Cache;
+use once_cell::sync::Lazy;
+use reqwest::Client;
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::models::*;
+use crate::providers::http_fixtures::FixtureMode;
+use crate::providers::time_utils::{find_time_in_text, time_on_date_local};
+use crate::providers::{PrayerDataProvider, ProviderError, ProviderResult};
+use crate::services::{clock, record_payload};
+
+/// Prefix of mosque ids served by this provider; the rest of the id is the timetable URL
+const ID_PREFIX: &str = "timetable:";
+
+const TIMETABLE_CACHE_TTL: Duration = Duration::from_secs(60 * 60);
+
+// Monthly feeds by URL: a month of lookups costs one request
+static TIMETABLE_CACHE: Lazy<Cache<String, Timetable>> = Lazy::new(|| {
+    Cache::builder()
+        .max_capacity(16)
+        .time_to_live(TIMETABLE_CACHE_TTL)
+        .build()
+});
+
+/// Masjid Timetable Provider
+/// Monthly JSON timetables published through MyMasjid and MasjidNow, common in UK mosques.
+/// Each timetable URL is one mosque, with id `timetable:<url>`.
+pub struct MasjidTimetableProvider {
+    client: Client,
+    timetable_url: Option<String>,
+    fixtures: FixtureMode,
+}
+
+/// A month of adhan and jamaah times, whatever platform it came from
+#[derive(Debug, Clone)]
+struct Timetable {
+    name: Option<String>,
+    days: Vec<TimetableDay>,
+}
+
+#[derive(Debug, Clone)]
+struct TimetableDay {
+    month: u32,
+    day: u32,
+    /// Fajr, Dhuhr, Asr, Maghrib, Isha
+    adhans: [String; 5],
+    jamaahs: [Option<String>; 5],
+}
+
+/// `GetMasjidTimings` feed from time.my-masjid.com
+#[derive(Debug, Deserialize)]
+struct MyMasjidFeed {
+    model: MyMasjidModel,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MyMasjidModel {
+    masjid_details: Option<MyMasjidDetails>,
+    salah_timings: Vec<MyMasjidDay>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MyMasjidDetails {
+    name: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MyMasjidDay {
+    day: u32,
+    month: u32,
+    fajr: String,
+    zuhr: String,
+    asr: String,
+    maghrib: String,
+    isha: String,
+    #[serde(rename = "iqamah_Fajr")]
+    iqamah_fajr: Option<String>,
+    #[serde(rename = "iqamah_Zuhr")]
+    iqamah_zuhr: Option<String>,
+    #[serde(rename = "iqamah_Asr")]
+    iqamah_asr: Option<String>,
+    #[serde(rename = "iqamah_Maghrib")]
+    iqamah_maghrib: Option<String>,
+    #[serde(rename = "iqamah_Isha")]
+    iqamah_isha: Option<String>,
+}
+
+/// `salah_timings` feed from masjidnow.com; the bare prayer fields are the jamaah times
+#[derive(Debug, Deserialize)]
+struct MasjidNowFeed {
+    masjid: MasjidNowMasjid,
+}
+
+#[derive(Debug, Deserialize)]
+struct MasjidNowMasjid {
+    name: Option<String>,
+    salah_timings: Vec<MasjidNowDay>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MasjidNowDay {
+    day: u32,
+    month: u32,
+    fajr_adhan: String,
+    fajr: Option<String>,
+    dhuhr_adhan: String,
+    dhuhr: Option<String>,
+    asr_adhan: String,
+    asr: Option<String>,
+    maghrib_adhan: String,
+    maghrib: Option<String>,
+    isha_adhan: String,
+    isha: Option<String>,
+}
+
+impl From<MyMasjidFeed> for Timetable {
+    fn from(feed: MyMasjidFeed) -> Self {
+        Self {
+            name: feed.model.masjid_details.and_then(|d| d.name),
+            days: feed
+                .model
+                .salah_timings
+                .into_iter()
+                .map(|d| TimetableDay {
+                    month: d.month,
+                    day: d.day,
+                    adhans: [d.fajr, d.zuhr, d.asr, d.maghrib, d.isha],
+                    jamaahs: [d.iqamah_fajr, d.iqamah_zuhr, d.iqamah_asr, d.iqamah_maghrib, d.iqamah_isha],
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<MasjidNowFeed> for Timetable {
+    fn from(feed: MasjidNowFeed) -> Self {
+        Self {
+            name: feed.masjid.name,
+            days: feed
+                .masjid
+                .salah_timings
+                .into_iter()
+                .map(|d| TimetableDay {
+                    month: d.month,
+                    day: d.day,
+                    adhans: [d.fajr_adhan, d.dhuhr_adhan, d.asr_adhan, d.maghrib_adhan, d.isha_adhan],
+                    jamaahs: [d.fajr, d.dhuhr, d.asr, d.maghrib, d.isha],
+                })
+                .collect(),
+        }
+    }
+}
+
+impl Timetable {
+    fn parse(body: &str) -> ProviderResult<Self> {
+        if let Ok(feed) = serde_json::from_str::<MyMasjidFeed>(body) {
+            return Ok(feed.into());
+        }
+        serde_json::from_str::<MasjidNowFeed>(body)
+            .map(Into::into)
+            .map_err(|e| ProviderError::Parse(format!("Not a MyMasjid or MasjidNow timetable: {}", e)))
+    }
+
+    fn prayer_times(&self, date: NaiveDate, mosque_id: &str) -> ProviderResult<PrayerTimes> {
+        let day = self
+            .days
+            .iter()
+            .find(|d| d.month == date.month() && d.day == date.day())
+            .ok_or_else(|| ProviderError::NotFound(format!("Timetable has no entry for {}", date)))?;
+
+        let time = |text: &str| find_time_in_text(text).map(|t| time_on_date_local(date, &t));
+        let prayer = |i: usize| {
+            let name = PrayerName::DAILY[i];
+            let adhan = time(&day.adhans[i])
+                .ok_or_else(|| ProviderError::Parse(format!("Invalid {} time: {}", name, day.adhans[i])))?;
+            Ok(Prayer {
+                name,
+                adhan,
+                iqama: day.jamaahs[i].as_deref().and_then(time),
+                custom_rakah_count: None,
+                iqama_offset_minutes: None,
+            })
+        };
+
+        Ok(PrayerTimes {
+            date: time_on_date_local(date, "00:00"),
+            fajr: prayer(0)?,
+            dhuhr: prayer(1)?,
+            asr: prayer(2)?,
+            maghrib: prayer(3)?,
+            isha: prayer(4)?,
+            jumuah: None,
+            mosque_id: Some(mosque_id.to_string()),
+            mosque_name: self.name.clone(),
+            cached_at: Some(Utc::now()),
+            imsak: None,
+            shuruq: None,
+            asr_method: None,
+            provider: Some(PROVIDER_MASJID_TIMETABLE.to_string()),
+            freshness: None,
+            warnings: Vec::new(),
+        })
+    }
+}
+
+impl MasjidTimetableProvider {
+    pub fn new() -> Self {
+        Self {
+            client: Client::new(),
+            timetable_url: None,
+            fixtures: FixtureMode::from_env(),
+        }
+    }
+
+    async fn fetch_timetable(&self, url: &str) -> ProviderResult<Timetable> {
+        if let Some(timetable) = TIMETABLE_CACHE.get(url).await {
+            return Ok(timetable);
+        }
+
+        let request = self.client
+            .get(url);
+        let response = self.fixtures.send(request).await?;
+
+        if response.status().as_u16() == 404 {
+            return Err(ProviderError::NotFound(format!("No timetable at {}", url)));
+        }
+
+        if !response.status().is_success() {
+            return Err(ProviderError::Server {
+                status_code: response.status().as_u16(),
+                message: "Failed to get timetable".to_string(),
+            });
+        }
+
+        let body = response.text();
+        record_payload(PROVIDER_MASJID_TIMETABLE, &format!("{}{}", ID_PREFIX, url), url, body.as_bytes());
+        let timetable = Timetable::parse(&body)?;
+        TIMETABLE_CACHE.insert(url.to_string(), timetable.clone()).await;
+
+        Ok(timetable)
+    }
+
+    async fn timetable_mosque(&self, url: &str) -> ProviderResult<Mosque> {
+        let timetable = self.fetch_timetable(url).await?;
+        let name = timetable.name.unwrap_or_else(|| "Masjid timetable".to_string());
+
+        let mut mosque = Mosque::new(format!("{}{}", ID_PREFIX, url), name);
+        mosque.country = Some("GB".to_string());
+        Ok(mosque)
+    }
+}
+
+impl Default for MasjidTimetableProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn timetable_url(mosque_id: &str) -> ProviderResult<&str> {
+    mosque_id
+        .strip_prefix(ID_PREFIX)
+        .ok_or_else(|| ProviderError::NotFound(format!("{} is not a masjid timetable", mosque_id)))
+}
+
+/// Whether a mosque id refers to a MyMasjid/MasjidNow timetable
+pub fn is_timetable_id(mosque_id: &str) -> bool {
+    mosque_id.starts_with(ID_PREFIX)
+}
+
+#[async_trait]
+impl PrayerDataProvider for MasjidTimetableProvider {
+    fn id(&self) -> &str {
+        PROVIDER_MASJID_TIMETABLE
+    }
+
+    fn name(&self) -> &str {
+        "MyMasjid / MasjidNow"
+    }
+
+    fn description(&self) -> &str {
+        "Monthly adhan and jamaah timetables published by UK mosques on MyMasjid or MasjidNow"
+    }
+
+    fn config_schema(&self) -> Vec<ConfigField> {
+        vec![
+            ConfigField::new("timetable_url", "Timetable URL", ConfigFieldType::Url)
+                .required()
+                .description("JSON timetable feed of the mosque on MyMasjid or MasjidNow"),
+        ]
+    }
+
+    async fn initialize(&mut self, config: Value) -> ProviderResult<()> {
+        self.timetable_url = config["timetable_url"]
+            .as_str()
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(|s| s.to_string());
+        Ok(())
+    }
+
+    /// A pasted timetable URL, or the configured one
+    async fn search_mosques(&self, query: &str, _location: Option<&GeoLocation>) -> ProviderResult<Vec<Mosque>> {
+        let query = query.trim();
+        let url = if query.starts_with("http://") || query.starts_with("https://") {
+            query
+        } else {
+            match &self.timetable_url {
+                Some(url) => url.as_str(),
+                None => return Ok(Vec::new()),
+            }
+        };
+
+        Ok(vec![self.timetable_mosque(url).await?])
+    }
+
+    async fn get_nearby_mosques(&self, _location: &GeoLocation, _radius_km: f64) -> ProviderResult<Vec<Mosque>> {
+        Err(ProviderError::Other("Timetables can't be searched by location".to_string()))
+    }
+
+    async fn get_prayer_times(&self, mosque_id: &str, date: Option<NaiveDate>) -> ProviderResult<PrayerTimes> {
+        let url = timetable_url(mosque_id)?;
+        let date = date.unwrap_or_else(clock::today);
+
+        self.fetch_timetable(url).await?.prayer_times(date, mosque_id)
+    }
+
+    async fn test_connection(&self) -> ProviderResult<ProviderTestResult> {
+        let start = std::time::Instant::now();
+        let Some(url) = &self.timetable_url else {
+            return Ok(ProviderTestResult {
+                success: false,
+                message: "Not configured: timetable URL required".to_string(),
+                latency_ms: None,
+            });
+        };
+
+        match self.fetch_timetable(url).await {
+            Ok(timetable) => Ok(ProviderTestResult {
+                success: true,
+                message: format!("Connected! Timetable has {} days", timetable.days.len()),
+                latency_ms: Some(start.elapsed().as_millis() as u64),
+            }),
+            Err(e) => Ok(ProviderTestResult {
+                success: false,
+                message: format!("Connection failed: {}", e),
+                latency_ms: None,
+            }),
+        }
+    }
+
+    async fn get_mosque_details(&self, mosque_id: &str) -> ProviderResult<Mosque> {
+        self.timetable_mosque(timetable_url(mosque_id)?).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_mymasjid_feed() {
+        let json = r#"{"model":{"masjidDetails":{"name":"East London Mosque","website":""},
+            "salahTimings":[{"day":6,"month":2,"fajr":"05:41","shouruq":"07:25","zuhr":"12:20",
+            "asr":"14:47","maghrib":"17:02","isha":"18:32","iqamah_Fajr":"06:15","iqamah_Zuhr":"13:00",
+            "iqamah_Asr":"15:15","iqamah_Maghrib":"17:07","iqamah_Isha":"19:30"}]}}"#;
+        let timetable = Timetable::parse(json).unwrap();
+        let date = NaiveDate::from_ymd_opt(2026, 2, 6).unwrap();
+
+        let times = timetable.prayer_times(date, "timetable:https://example.org/feed").unwrap();
+
+        assert_eq!(times.mosque_name.as_deref(), Some("East London Mosque"));
+        assert_eq!(times.dhuhr.adhan, time_on_date_local(date, "12:20"));
+        assert_eq!(times.isha.iqama, Some(time_on_date_local(date, "19:30")));
+        assert!(timetable.prayer_times(date.succ_opt().unwrap(), "x").is_err());
+    }
+
+    #[test]
+    fn test_parse_masjidnow_feed() {
+        let json = r#"{"masjid":{"name":"Masjid Al-Tawhid","salah_timings":[{"day":6,"month":2,
+            "fajr_adhan":"5:41 AM","fajr":"6:15 AM","dhuhr_adhan":"12:20 PM","dhuhr":"1:00 PM",
+            "asr_adhan":"2:47 PM","asr":"3:15 PM","maghrib_adhan":"5:02 PM","maghrib":null,
+            "isha_adhan":"6:32 PM","isha":"7:30 PM"}]}}"#;
+        let timetable = Timetable::parse(json).unwrap();
+        let date = NaiveDate::from_ymd_opt(2026, 2, 6).unwrap();
+
+        let times = timetable.prayer_times(date, "timetable:https://example.org/feed").unwrap();
+
+        assert_eq!(times.asr.adhan, time_on_date_local(date, "14:47"));
+        assert_eq!(times.dhuhr.iqama, Some(time_on_date_local(date, "13:00")));
+        assert!(times.maghrib.iqama.is_none());
+    }
+}
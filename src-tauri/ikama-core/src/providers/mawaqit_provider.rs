@@ -0,0 +1,1284 @@
+use async_trait::async_trait;
+use chrono::{Datelike, Local, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use reqwest::Client;
+use serde::de::{Deserializer, SeqAccess, Visitor};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+use moka::future::Cache;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use scraper::{ElementRef, Html, Selector};
+
+use crate::models::*;
+use crate::providers::http_cache::HttpCache;
+use crate::providers::time_utils::{
+    find_time_in_text, iqama_offset, resolve_iqama, time_on_date_local, time_on_date_utc,
+};
+use crate::providers::{PrayerDataProvider, ProviderError, ProviderResult};
+use crate::services::clock::resolve_local;
+use crate::services::{mosque_context, record_payload, report_parse_failure, report_parse_success, DayIqamas, SearchQuery};
+
+const MAWAQIT_BASE_URL: &str = "https://mawaqit.net";
+
+/// Maximum number of mosques kept from a streamed search
+const MAX_SEARCH_RESULTS: usize = 200;
+
+static CONF_DATA_PATTERN: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"let\s+confData\s*=\s*(\{[\s\S]+?\});").unwrap());
+
+const MOSQUE_CACHE_CAPACITY: u64 = 64;
+const MOSQUE_CACHE_TTL: Duration = Duration::from_secs(60 * 60);
+
+// In-memory cache for mosque data, bounded and evicted after 1 hour
+static MOSQUE_CACHE: Lazy<Cache<String, MawaqitConfData>> = Lazy::new(|| {
+    Cache::builder()
+        .max_capacity(MOSQUE_CACHE_CAPACITY)
+        .time_to_live(MOSQUE_CACHE_TTL)
+        .build()
+});
+
+static CACHE_HITS: AtomicU64 = AtomicU64::new(0);
+static CACHE_MISSES: AtomicU64 = AtomicU64::new(0);
+
+/// Hit/miss counters for the mosque page cache
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct CacheStats {
+    #[ts(type = "number")]
+    pub hits: u64,
+    #[ts(type = "number")]
+    pub misses: u64,
+    #[ts(type = "number")]
+    pub entries: u64,
+}
+
+/// Get current mosque page cache statistics
+pub fn mosque_cache_stats() -> CacheStats {
+    CacheStats {
+        hits: CACHE_HITS.load(Ordering::Relaxed),
+        misses: CACHE_MISSES.load(Ordering::Relaxed),
+        entries: MOSQUE_CACHE.entry_count(),
+    }
+}
+
+pub struct MawaqitProvider {
+    client: Client,
+    http_cache: HttpCache,
+    default_country: String,
+}
+
+impl MawaqitProvider {
+    pub fn new() -> Self {
+        Self {
+            client: Client::builder()
+                .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36")
+                .build()
+                .unwrap(),
+            http_cache: HttpCache::default_location(),
+            default_country: "FR".to_string(),
+        }
+    }
+
+    /// Provider fetching through a specific cache, e.g. one replaying recorded fixtures
+    pub fn with_http_cache(http_cache: HttpCache) -> Self {
+        Self {
+            http_cache,
+            ..Self::new()
+        }
+    }
+
+    async fn fetch_country_mosques(
+        &self,
+        country: &str,
+        filter: MosqueFilter,
+    ) -> ProviderResult<Vec<MawaqitMosque>> {
+        let url = format!("{}/api/2.0/mosque/map/{}", MAWAQIT_BASE_URL, country);
+
+        // The cached body is parsed from disk so only matching mosques are held in memory
+        let path = self.http_cache.fetch_to_file(&self.client, &url).await?;
+
+        let mosques = tokio::task::spawn_blocking(move || {
+            let file = std::fs::File::open(&path)?;
+            parse_mosques_filtered(std::io::BufReader::new(file), &filter).map_err(std::io::Error::from)
+        })
+        .await
+        .map_err(|e| ProviderError::Other(format!("Mosque list parser failed: {}", e)))?;
+
+        match mosques {
+            Ok(mosques) => {
+                report_parse_success(PROVIDER_MAWAQIT, "mosque_list");
+                Ok(mosques)
+            }
+            Err(e) => {
+                report_parse_failure(PROVIDER_MAWAQIT, "mosque_list", &e.to_string(), false);
+                Err(ProviderError::Parse(format!("Failed to parse response: {}", e)))
+            }
+        }
+    }
+
+    /// confData of a mosque page from memory or the HTTP cache, without touching the network
+    async fn cached_conf_data(&self, slug: &str) -> Option<MawaqitConfData> {
+        if let Some(conf_data) = MOSQUE_CACHE.get(slug).await {
+            CACHE_HITS.fetch_add(1, Ordering::Relaxed);
+            return Some(conf_data);
+        }
+
+        let html = self.http_cache.cached_text(&mosque_page_url(slug)).await?;
+        let conf_data = extract_conf_data(&html)?;
+        MOSQUE_CACHE.insert(slug.to_string(), conf_data.clone()).await;
+        Some(conf_data)
+    }
+
+    async fn scrape_mosque_page(&self, slug: &str) -> ProviderResult<MawaqitConfData> {
+        // Check cache first
+        if let Some(conf_data) = MOSQUE_CACHE.get(slug).await {
+            CACHE_HITS.fetch_add(1, Ordering::Relaxed);
+            log::info!("Using cached mosque data for: {}", slug);
+            return Ok(conf_data);
+        }
+        CACHE_MISSES.fetch_add(1, Ordering::Relaxed);
+
+        let url = mosque_page_url(slug);
+        log::info!("Scraping mosque page: {}", url);
+
+        let page = self.http_cache.get_text(&self.client, &url).await?;
+        let html = page.text;
+
+        log::info!("Got page content, length: {} bytes", html.len());
+        // A page revalidated with a 304 was archived when it was downloaded
+        if page.from_network {
+            record_payload(PROVIDER_MAWAQIT, slug, &url, html.as_bytes());
+        }
+
+        let context = mosque_context("confData", slug);
+        let conf_data = match extract_conf_data(&html) {
+            Some(conf_data) if conf_data.missing_fields.is_empty() => {
+                report_parse_success(PROVIDER_MAWAQIT, &context);
+                conf_data
+            }
+            Some(conf_data) => {
+                let message = format!("confData is missing {}", conf_data.missing_fields.join(", "));
+                report_parse_failure(PROVIDER_MAWAQIT, &context, &message, true);
+                conf_data
+            }
+            None => {
+                log::warn!("No usable confData in {}, reading the times shown on the page", url);
+                let fallback = scrape_visible_times(&html, Local::now().date_naive());
+                report_parse_failure(
+                    PROVIDER_MAWAQIT,
+                    &context,
+                    "Could not find prayer times data (confData) in page",
+                    fallback.is_some(),
+                );
+                fallback.ok_or_else(|| {
+                    ProviderError::Parse("Could not find prayer times data (confData) in page".to_string())
+                })?
+            }
+        };
+
+        log::info!("Successfully extracted confData");
+        log::info!("Mosque Name: {}", conf_data.name);
+        log::info!("Times (today): {:?}", conf_data.times);
+        log::info!("Calendar has {} months of data", conf_data.calendar.len());
+        log::info!("Iqama calendar has {} months of data", conf_data.iqama_calendar.len());
+        log::info!("Timezone: {}", conf_data.timezone);
+
+        // Cache the data
+        MOSQUE_CACHE.insert(slug.to_string(), conf_data.clone()).await;
+
+        Ok(conf_data)
+    }
+
+    /// Get prayer times for a specific date from the calendar
+    fn get_prayer_times_for_date(
+        &self,
+        conf_data: &MawaqitConfData,
+        date: NaiveDate,
+    ) -> ProviderResult<(Vec<String>, Vec<String>)> {
+        // Calendar is a Vec of months, each month is a HashMap of day -> [Fajr, Shuruq, Dhuhr, Asr, Maghrib, Isha]
+        let times = calendar_day(&conf_data.calendar, date)
+            .map_err(|e| ProviderError::Parse(format!("No prayer times found for {}: {}", date, e)))?;
+
+        // Without offsets the schedule is adhan-only; the user's iqama defaults fill it in
+        let iqama_offsets = calendar_day(&conf_data.iqama_calendar, date).unwrap_or_else(|e| {
+            log::info!("No iqama offsets for {}: {}", date, e);
+            Vec::new()
+        });
+
+        log::info!("Found prayer times for {}: {:?}", date, times);
+        Ok((times, iqama_offsets))
+    }
+
+    /// Get today's prayer times from the "times" property
+    fn get_today_times_from_conf(&self, conf_data: &MawaqitConfData) -> Option<(Vec<String>, Vec<String>)> {
+        if conf_data.times.len() < 5 {
+            log::warn!("'times' array has insufficient data: {:?}", conf_data.times);
+            return None;
+        }
+
+        // Get today's iqama offsets
+        let iqama_offsets = calendar_day(&conf_data.iqama_calendar, Local::now().date_naive()).unwrap_or_default();
+
+        // times format: [Fajr, Dhuhr, Asr, Maghrib, Isha] (5 elements)
+        // Need to expand to 6 elements for consistency with calendar format
+        let mut expanded_times = conf_data.times.clone();
+        if expanded_times.len() == 5 {
+            // Insert Shuruq (sunrise) placeholder - will use fajr time + offset or from shuruq field
+            let shuruq_time = conf_data.shuruq.clone().unwrap_or_else(|| {
+                // Estimate shuruq as ~1 hour after fajr
+                let fajr_parts: Vec<&str> = expanded_times[0].split(':').collect();
+                if fajr_parts.len() == 2 {
+                    let hour: i32 = fajr_parts[0].parse().unwrap_or(5);
+                    let minute: i32 = fajr_parts[1].parse().unwrap_or(0);
+                    let total = hour * 60 + minute + 60; // +60 minutes
+                    format!("{:02}:{:02}", total / 60, total % 60)
+                } else {
+                    "06:00".to_string()
+                }
+            });
+            expanded_times.insert(1, shuruq_time);
+        }
+
+        log::info!("Using 'times' property for today: {:?}", expanded_times);
+        Some((expanded_times, iqama_offsets))
+    }
+
+    /// Fetch the full mosque list for a country, converted to app mosques
+    pub async fn fetch_catalog(&self, country: &str) -> ProviderResult<Vec<Mosque>> {
+        let mosques = self.fetch_country_mosques(country, MosqueFilter::default()).await?;
+
+        Ok(mosques.iter().map(|m| self.convert_mosque(m)).collect())
+    }
+
+    /// Hijri adjustment and Eid prayer times announced on the mosque's page
+    pub async fn get_hijri_info(&self, slug: &str) -> ProviderResult<MosqueHijriInfo> {
+        Ok(self.scrape_mosque_page(slug).await?.hijri_info())
+    }
+
+    /// Local iqama times for `days` days from `start`, read from the yearly calendar
+    /// The calendar of the page already cached is used; the page is only scraped when none is
+    pub async fn get_iqama_calendar(&self, slug: &str, start: NaiveDate, days: u32) -> ProviderResult<Vec<DayIqamas>> {
+        let conf_data = match self.cached_conf_data(slug).await {
+            Some(conf_data) => conf_data,
+            None => self.scrape_mosque_page(slug).await?,
+        };
+
+        Ok(start
+            .iter_days()
+            .take(days as usize)
+            .filter_map(|date| conf_data.day_iqamas(date))
+            .collect())
+    }
+
+    fn convert_mosque(&self, m: &MawaqitMosque) -> Mosque {
+        Mosque {
+            id: m.slug.clone(),
+            name: m.name.clone(),
+            address: Some(m.address.clone()),
+            city: Some(m.city.clone()),
+            country: Some(m.country_full_name.clone()),
+            latitude: Some(m.lat),
+            longitude: Some(m.lng),
+            is_favorite: false,
+            last_accessed: None,
+            facilities: MosqueFacilities {
+                womens_area: m.women_space,
+                wheelchair_access: m.handicap_accessibility,
+                wudu: m.ablutions,
+                parking: m.parking,
+            },
+        }
+    }
+
+}
+
+#[async_trait]
+impl PrayerDataProvider for MawaqitProvider {
+    fn id(&self) -> &str {
+        PROVIDER_MAWAQIT
+    }
+
+    fn name(&self) -> &str {
+        "Mawaqit"
+    }
+
+    fn description(&self) -> &str {
+        "Official Mawaqit.net integration with full calendar support"
+    }
+
+    fn config_schema(&self) -> Vec<ConfigField> {
+        vec![ConfigField::new(
+            "default_country",
+            "Default Country",
+            ConfigFieldType::Select,
+        )
+        .description("Default country to search in")
+        .default_value("FR")
+        .options(vec![
+            "FR".to_string(),
+            "TN".to_string(),
+            "MA".to_string(),
+            "DZ".to_string(),
+            "US".to_string(),
+            "GB".to_string(),
+            "CA".to_string(),
+        ])]
+    }
+
+    async fn initialize(&mut self, config: Value) -> ProviderResult<()> {
+        if let Some(country) = config["default_country"].as_str() {
+            self.default_country = country.to_string();
+        }
+        Ok(())
+    }
+
+    async fn search_mosques(
+        &self,
+        query: &str,
+        _location: Option<&GeoLocation>,
+    ) -> ProviderResult<Vec<Mosque>> {
+        let filter = MosqueFilter {
+            query: Some(SearchQuery::new(query)),
+            limit: Some(MAX_SEARCH_RESULTS),
+            ..MosqueFilter::default()
+        };
+        let mosques = self.fetch_country_mosques(&self.default_country, filter).await?;
+
+        Ok(mosques.iter().map(|m| self.convert_mosque(m)).collect())
+    }
+
+    async fn get_nearby_mosques(
+        &self,
+        location: &GeoLocation,
+        radius_km: f64,
+    ) -> ProviderResult<Vec<Mosque>> {
+        let filter = MosqueFilter {
+            near: Some((*location, radius_km)),
+            ..MosqueFilter::default()
+        };
+        let mut mosques = self.fetch_country_mosques(&self.default_country, filter).await?;
+
+        mosques.sort_by(|a, b| {
+            let da = location.distance_to(&GeoLocation::new(a.lat, a.lng));
+            let db = location.distance_to(&GeoLocation::new(b.lat, b.lng));
+            da.total_cmp(&db)
+        });
+
+        Ok(mosques.iter().map(|m| self.convert_mosque(m)).collect())
+    }
+
+    async fn get_prayer_times(
+        &self,
+        mosque_id: &str,
+        date: Option<NaiveDate>,
+    ) -> ProviderResult<PrayerTimes> {
+        let conf_data = self.scrape_mosque_page(mosque_id).await?;
+
+        // Use provided date or today
+        let target_date = date.unwrap_or_else(|| Local::now().date_naive());
+        let today = Local::now().date_naive();
+        
+        // Use "times" property for today, "calendar" for other dates
+        let (prayer_times, iqama_offsets) = if target_date == today {
+            log::info!("Using 'times' property for today's prayer times");
+            match self.get_today_times_from_conf(&conf_data) {
+                Some(times) => times,
+                None => self.get_prayer_times_for_date(&conf_data, target_date)?,
+            }
+        } else {
+            log::info!("Using 'calendar' property for date: {}", target_date);
+            self.get_prayer_times_for_date(&conf_data, target_date)?
+        };
+
+        // prayer_times format: [Fajr, Shuruq, Dhuhr, Asr, Maghrib, Isha]
+        if prayer_times.len() < 6 {
+            return Err(ProviderError::Parse(format!(
+                "Invalid prayer times data: expected 6 times (Fajr, Shuruq, Dhuhr, Asr, Maghrib, Isha), got {}. Times: {:?}",
+                prayer_times.len(), prayer_times
+            )));
+        }
+
+        log::info!("Prayer times for {}: Fajr={}, Shuruq={}, Dhuhr={}, Asr={}, Maghrib={}, Isha={}",
+            target_date,
+            prayer_times[0], prayer_times[1], prayer_times[2], 
+            prayer_times[3], prayer_times[4], prayer_times[5]);
+        log::info!("Iqama offsets: {:?}", iqama_offsets);
+
+        // Extract individual prayer times
+        let fajr_time = &prayer_times[0];
+        let shuruq_time = &prayer_times[1];
+        let dhuhr_time = &prayer_times[2];
+        let asr_time = &prayer_times[3];
+        let maghrib_time = &prayer_times[4];
+        let isha_time = &prayer_times[5];
+
+        // Calculate iqama times
+        let fajr_iqama = if conf_data.iqama_enabled.unwrap_or(true) && iqama_offsets.len() > 0 {
+            resolve_iqama(fajr_time, &iqama_offsets[0])
+        } else { None };
+        
+        let dhuhr_iqama = if conf_data.iqama_enabled.unwrap_or(true) && iqama_offsets.len() > 1 {
+            resolve_iqama(dhuhr_time, &iqama_offsets[1])
+        } else { None };
+        
+        let asr_iqama = if conf_data.iqama_enabled.unwrap_or(true) && iqama_offsets.len() > 2 {
+            resolve_iqama(asr_time, &iqama_offsets[2])
+        } else { None };
+        
+        let maghrib_iqama = if conf_data.iqama_enabled.unwrap_or(true) && iqama_offsets.len() > 3 {
+            resolve_iqama(maghrib_time, &iqama_offsets[3])
+        } else { None };
+        
+        let isha_iqama = if conf_data.iqama_enabled.unwrap_or(true) && iqama_offsets.len() > 4 {
+            resolve_iqama(isha_time, &iqama_offsets[4])
+        } else { None };
+
+        // Offsets are kept so displays can show "+15" the way the mosque's board does
+        let published_offset = |i: usize| iqama_offsets.get(i).and_then(|entry| iqama_offset(entry));
+
+        log::info!("Calculated iqama times - Fajr: {:?}, Dhuhr: {:?}, Asr: {:?}, Maghrib: {:?}, Isha: {:?}",
+            fajr_iqama, dhuhr_iqama, asr_iqama, maghrib_iqama, isha_iqama);
+
+        // Create prayers - use local timezone to avoid 1-hour offset
+        let base_date = Local::now().date_naive();
+        
+        let fajr = Prayer {
+            name: PrayerName::Fajr,
+            adhan: time_on_date_local(base_date, fajr_time),
+            iqama: fajr_iqama.as_ref().map(|t| time_on_date_local(base_date, t)),
+            custom_rakah_count: Some(2),
+            iqama_offset_minutes: fajr_iqama.as_ref().and(published_offset(0)),
+        };
+
+        let dhuhr = Prayer {
+            name: PrayerName::Dhuhr,
+            adhan: time_on_date_local(base_date, dhuhr_time),
+            iqama: dhuhr_iqama.as_ref().map(|t| time_on_date_local(base_date, t)),
+            custom_rakah_count: Some(4),
+            iqama_offset_minutes: dhuhr_iqama.as_ref().and(published_offset(1)),
+        };
+
+        let asr = Prayer {
+            name: PrayerName::Asr,
+            adhan: time_on_date_local(base_date, asr_time),
+            iqama: asr_iqama.as_ref().map(|t| time_on_date_local(base_date, t)),
+            custom_rakah_count: Some(4),
+            iqama_offset_minutes: asr_iqama.as_ref().and(published_offset(2)),
+        };
+
+        let maghrib = Prayer {
+            name: PrayerName::Maghrib,
+            adhan: time_on_date_local(base_date, maghrib_time),
+            iqama: maghrib_iqama.as_ref().map(|t| time_on_date_local(base_date, t)),
+            custom_rakah_count: Some(3),
+            iqama_offset_minutes: maghrib_iqama.as_ref().and(published_offset(3)),
+        };
+
+        let isha = Prayer {
+            name: PrayerName::Isha,
+            adhan: time_on_date_local(base_date, isha_time),
+            iqama: isha_iqama.as_ref().map(|t| time_on_date_local(base_date, t)),
+            custom_rakah_count: Some(4),
+            iqama_offset_minutes: isha_iqama.as_ref().and(published_offset(4)),
+        };
+
+        // Jumuah prayer - check if target date is Friday
+        let jumuah = if target_date.weekday().num_days_from_monday() == 4 { // Friday = 4
+            conf_data.jumua.as_ref().map(|time| Prayer {
+                name: PrayerName::Jumuah,
+                adhan: time_on_date_local(base_date, time),
+                iqama: None,
+                custom_rakah_count: Some(2),
+                iqama_offset_minutes: None,
+            })
+        } else {
+            None
+        };
+
+        let imsak = conf_data
+            .imsak_minutes_before_fajr
+            .filter(|&minutes| minutes > 0)
+            .map(|minutes| fajr.adhan - chrono::Duration::minutes(minutes));
+
+        let prayer_times = PrayerTimes {
+            date: time_on_date_utc(base_date, "00:00"),
+            fajr,
+            dhuhr,
+            asr,
+            maghrib,
+            isha,
+            jumuah,
+            mosque_id: Some(mosque_id.to_string()),
+            mosque_name: Some(conf_data.name.clone()),
+            cached_at: Some(Utc::now()),
+            imsak,
+            shuruq: Some(time_on_date_local(base_date, shuruq_time)),
+            asr_method: None,
+            provider: Some(PROVIDER_MAWAQIT.to_string()),
+            freshness: None,
+            warnings: Vec::new(),
+        };
+
+        log::info!("Successfully created PrayerTimes for {} on {}", conf_data.name, target_date);
+
+        Ok(prayer_times)
+    }
+
+    async fn test_connection(&self) -> ProviderResult<ProviderTestResult> {
+        let start = std::time::Instant::now();
+
+        match self.fetch_country_mosques(&self.default_country, MosqueFilter::default()).await {
+            Ok(mosques) => {
+                let latency = start.elapsed().as_millis() as u64;
+                Ok(ProviderTestResult {
+                    success: true,
+                    message: format!(
+                        "Connected! Found {} mosques in {}",
+                        mosques.len(), self.default_country
+                    ),
+                    latency_ms: Some(latency),
+                })
+            }
+            Err(e) => Ok(ProviderTestResult {
+                success: false,
+                message: format!("Connection failed: {}", e),
+                latency_ms: None,
+            }),
+        }
+    }
+
+    async fn get_mosque_details(&self, mosque_id: &str) -> ProviderResult<Mosque> {
+        let conf_data = self.scrape_mosque_page(mosque_id).await?;
+
+        Ok(Mosque {
+            id: mosque_id.to_string(),
+            name: conf_data.name,
+            address: Some(conf_data.url),
+            city: Some(conf_data.label),
+            country: Some(conf_data.country_code),
+            latitude: conf_data.latitude,
+            longitude: conf_data.longitude,
+            is_favorite: false,
+            last_accessed: None,
+            facilities: MosqueFacilities::default(),
+        })
+    }
+
+    async fn get_events(&self, mosque_id: &str) -> ProviderResult<Vec<MosqueEvent>> {
+        let conf_data = self.scrape_mosque_page(mosque_id).await?;
+
+        Ok(conf_data
+            .announcements
+            .iter()
+            .filter_map(|a| a.to_event(mosque_id))
+            .collect())
+    }
+}
+
+/// Filter mosques whose name, city or address contains the query (case-insensitive)
+pub fn filter_mosques(mosques: Vec<Mosque>, query: &str) -> Vec<Mosque> {
+    let query = SearchQuery::new(query);
+
+    mosques
+        .into_iter()
+        .filter(|m| {
+            query.matches(&[
+                m.name.as_str(),
+                m.city.as_deref().unwrap_or_default(),
+                m.address.as_deref().unwrap_or_default(),
+            ])
+        })
+        .collect()
+}
+
+/// Entry of a yearly confData calendar (months of day -> times) for a date
+/// Calendars carry no year, so Dec 31 -> Jan 1 wraps within the same data and a calendar
+/// published for the previous year still matches by month and day; Feb 29 reads Feb 28
+/// when the calendar was published for a common year
+fn mosque_page_url(slug: &str) -> String {
+    format!("{}/en/{}", MAWAQIT_BASE_URL, slug)
+}
+
+fn calendar_day(calendar: &[HashMap<String, Vec<String>>], date: NaiveDate) -> Result<Vec<String>, String> {
+    let month = calendar.get(date.month0() as usize).ok_or_else(|| {
+        format!("calendar has no month {} ({} months published)", date.month(), calendar.len())
+    })?;
+
+    if let Some(times) = month.get(&date.day().to_string()) {
+        return Ok(times.clone());
+    }
+    if date.month() == 2 && date.day() == 29 {
+        if let Some(times) = month.get("28") {
+            log::info!("Calendar has no Feb 29, using Feb 28 for {}", date);
+            return Ok(times.clone());
+        }
+    }
+
+    Err(format!("calendar month {} has no day {} ({} days published)", date.month(), date.day(), month.len()))
+}
+
+fn extract_conf_data(html: &str) -> Option<MawaqitConfData> {
+    let captures = CONF_DATA_PATTERN.captures(html)?;
+    let json_str = captures.get(1)?.as_str();
+
+    log::debug!("Extracted confData JSON, length: {} bytes", json_str.len());
+
+    let value: Value = match serde_json::from_str(json_str) {
+        Ok(value) => value,
+        Err(e) => {
+            log::error!("Failed to parse confData JSON: {}", e);
+            return None;
+        }
+    };
+
+    let mut data = match MawaqitConfData::deserialize(&value) {
+        Ok(mut data) => {
+            data.missing_fields = CONF_DATA_FIELDS
+                .iter()
+                .filter(|(key, aliases)| {
+                    std::iter::once(key)
+                        .chain(aliases.iter())
+                        .all(|k| value.get(*k).is_none())
+                })
+                .map(|(key, _)| key.to_string())
+                .collect();
+            data
+        }
+        Err(e) => {
+            log::warn!("confData no longer matches the expected schema ({}), parsing field by field", e);
+            MawaqitConfData::from_value_lenient(&value)
+        }
+    };
+
+    if data.times.len() < 5 && data.calendar.is_empty() {
+        log::error!("confData has neither today's times nor a calendar");
+        return None;
+    }
+    if data.name.is_empty() {
+        data.name = data.label.clone();
+    }
+    if !data.missing_fields.is_empty() {
+        log::warn!("confData is partial, missing or unreadable: {}", data.missing_fields.join(", "));
+    }
+
+    Some(data)
+}
+
+/// Prayer rows of the page, tried in order until one yields five times
+const PRAYER_ROW_SELECTORS: &[&str] = &[".prayers > div", ".prayer", "[data-prayer]"];
+
+/// Today's times from the page's visible prayer list, for when confData can't be read
+/// Only `today` gets times; the rest of the calendar is reported missing
+fn scrape_visible_times(html: &str, today: NaiveDate) -> Option<MawaqitConfData> {
+    let document = Html::parse_document(html);
+    let select_time = |scope: ElementRef, selector: &str| {
+        let selector = Selector::parse(selector).ok()?;
+        let element = scope.select(&selector).next()?;
+        find_time_in_text(&element.text().collect::<String>())
+    };
+    let text_of = |selector: &str| {
+        let selector = Selector::parse(selector).ok()?;
+        let text: String = document.select(&selector).next()?.text().collect();
+        Some(text.trim().to_string()).filter(|t| !t.is_empty())
+    };
+
+    let rows: Vec<(String, Option<String>)> = PRAYER_ROW_SELECTORS.iter().find_map(|selector| {
+        let selector = Selector::parse(selector).ok()?;
+        let rows: Vec<_> = document
+            .select(&selector)
+            .filter_map(|row| {
+                let adhan = select_time(row, ".time").or_else(|| find_time_in_text(&row.text().collect::<String>()))?;
+                let wait = Selector::parse(".wait").ok().and_then(|wait| {
+                    let text: String = row.select(&wait).next()?.text().collect();
+                    Some(text.trim().to_string()).filter(|t| !t.is_empty())
+                });
+                Some((adhan, wait))
+            })
+            .collect();
+        (rows.len() >= 5).then_some(rows)
+    })?;
+    let rows = &rows[..5];
+
+    let root = document.root_element();
+    let mut data = MawaqitConfData {
+        name: text_of("h1")
+            .or_else(|| text_of("title").map(|t| t.split(" - ").next().unwrap_or(&t).trim().to_string()))
+            .unwrap_or_default(),
+        times: rows.iter().map(|(adhan, _)| adhan.clone()).collect(),
+        shuruq: select_time(root, ".shuruq"),
+        jumua: select_time(root, ".jumua"),
+        ..MawaqitConfData::default()
+    };
+
+    // Iqamas only when every row shows one, so they stay aligned with the prayers
+    if rows.iter().all(|(_, wait)| wait.is_some()) {
+        let mut months = vec![HashMap::new(); today.month0() as usize + 1];
+        months[today.month0() as usize].insert(
+            today.day().to_string(),
+            rows.iter().filter_map(|(_, wait)| wait.clone()).collect(),
+        );
+        data.iqama_calendar = months;
+    }
+
+    data.missing_fields = CONF_DATA_FIELDS
+        .iter()
+        .map(|(key, _)| *key)
+        .filter(|key| match *key {
+            "name" => data.name.is_empty(),
+            "times" => false,
+            "shuruq" => data.shuruq.is_none(),
+            "jumua" => data.jumua.is_none(),
+            "iqamaCalendar" => data.iqama_calendar.is_empty(),
+            _ => true,
+        })
+        .map(str::to_string)
+        .collect();
+
+    Some(data)
+}
+
+/// Filter applied while streaming the country mosque list
+#[derive(Debug, Clone, Default)]
+struct MosqueFilter {
+    /// Fuzzy match on name, city or address
+    query: Option<SearchQuery>,
+    /// Center and radius in km
+    near: Option<(GeoLocation, f64)>,
+    /// Stop keeping results after this many matches
+    limit: Option<usize>,
+}
+
+impl MosqueFilter {
+    fn matches(&self, m: &MawaqitMosque) -> bool {
+        if let Some(query) = &self.query {
+            if !query.matches(&[&m.name, &m.city, &m.address]) {
+                return false;
+            }
+        }
+
+        if let Some((center, radius_km)) = &self.near {
+            if center.distance_to(&GeoLocation::new(m.lat, m.lng)) > *radius_km {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+/// Deserialize a JSON array of mosques one element at a time, keeping only matches
+fn parse_mosques_filtered<R: std::io::Read>(
+    reader: R,
+    filter: &MosqueFilter,
+) -> serde_json::Result<Vec<MawaqitMosque>> {
+    struct FilteredSeq<'a>(&'a MosqueFilter);
+
+    impl<'de, 'a> Visitor<'de> for FilteredSeq<'a> {
+        type Value = Vec<MawaqitMosque>;
+
+        fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            f.write_str("an array of mosques")
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+            let mut kept = Vec::new();
+
+            while let Some(mosque) = seq.next_element::<MawaqitMosque>()? {
+                if self.0.limit.map(|l| kept.len() >= l).unwrap_or(false) {
+                    continue;
+                }
+                if self.0.matches(&mosque) {
+                    kept.push(mosque);
+                }
+            }
+
+            Ok(kept)
+        }
+    }
+
+    let mut deserializer = serde_json::Deserializer::from_reader(reader);
+    let mosques = deserializer.deserialize_seq(FilteredSeq(filter))?;
+    deserializer.end()?;
+
+    Ok(mosques)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct MawaqitMosque {
+    slug: String,
+    name: String,
+    #[serde(rename = "image1")]
+    image: String,
+    address: String,
+    city: String,
+    #[serde(rename = "zipcode")]
+    zip_code: String,
+    #[serde(rename = "countryFullName")]
+    country_full_name: String,
+    lng: f64,
+    lat: f64,
+    #[serde(rename = "womenSpace", default)]
+    women_space: Option<bool>,
+    #[serde(rename = "handicapAccessibility", default)]
+    handicap_accessibility: Option<bool>,
+    #[serde(default)]
+    ablutions: Option<bool>,
+    #[serde(default)]
+    parking: Option<bool>,
+}
+
+/// Mosque configuration embedded in Mawaqit pages
+/// Only the times are essential; everything else has a default so a renamed or dropped field
+/// degrades the data instead of failing the parse
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct MawaqitConfData {
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    label: String,
+    #[serde(rename = "countryCode", alias = "country_code", default)]
+    country_code: String,
+    #[serde(default)]
+    latitude: Option<f64>,
+    #[serde(default)]
+    longitude: Option<f64>,
+    #[serde(default)]
+    url: String,
+    #[serde(default)]
+    times: Vec<String>, // Today's times: [Fajr, Dhuhr, Asr, Maghrib, Isha]
+    #[serde(default)]
+    shuruq: Option<String>,
+    #[serde(default)]
+    jumua: Option<String>,
+    #[serde(rename = "iqamaCalendar", alias = "iqama_calendar", default)]
+    iqama_calendar: Vec<HashMap<String, Vec<String>>>,
+    #[serde(rename = "calendar", default)]
+    calendar: Vec<HashMap<String, Vec<String>>>, // Full year calendar
+    #[serde(rename = "iqamaEnabled", alias = "iqama_enabled", default)]
+    iqama_enabled: Option<bool>,
+    #[serde(rename = "timeDisplayFormat", alias = "timeFormat", default)]
+    time_display_format: String,
+    #[serde(default)]
+    timezone: String,
+    #[serde(rename = "hijriAdjustment", default)]
+    hijri_adjustment: Option<i32>,
+    #[serde(rename = "hijriDateForceTo30", default)]
+    hijri_date_force_to_30: Option<bool>,
+    #[serde(rename = "aidPrayerTime", default)]
+    aid_prayer_time: Option<String>,
+    #[serde(rename = "aidPrayerTime2", default)]
+    aid_prayer_time_2: Option<String>,
+    #[serde(default)]
+    announcements: Vec<MawaqitAnnouncement>,
+    #[serde(rename = "imsakNbMinBeforeFajr", default)]
+    imsak_minutes_before_fajr: Option<i64>,
+    /// Expected fields that were absent or unreadable; empty when the page parsed fully
+    #[serde(skip)]
+    missing_fields: Vec<String>,
+}
+
+/// confData keys we read and their aliases, checked to report partial data
+const CONF_DATA_FIELDS: &[(&str, &[&str])] = &[
+    ("name", &[]),
+    ("label", &[]),
+    ("countryCode", &["country_code"]),
+    ("latitude", &[]),
+    ("longitude", &[]),
+    ("url", &[]),
+    ("times", &[]),
+    ("shuruq", &[]),
+    ("jumua", &[]),
+    ("iqamaCalendar", &["iqama_calendar"]),
+    ("calendar", &[]),
+    ("iqamaEnabled", &["iqama_enabled"]),
+    ("timeDisplayFormat", &["timeFormat"]),
+    ("timezone", &[]),
+];
+
+/// Message published on a mosque's Mawaqit screen
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct MawaqitAnnouncement {
+    id: Value,
+    title: Option<String>,
+    content: Option<String>,
+    #[serde(rename = "startDate")]
+    start_date: Option<String>,
+    #[serde(rename = "endDate")]
+    end_date: Option<String>,
+}
+
+impl MawaqitAnnouncement {
+    /// Announcements without a title or start date can't be scheduled and are skipped
+    fn to_event(&self, slug: &str) -> Option<MosqueEvent> {
+        let title = self.title.as_deref().map(str::trim).filter(|t| !t.is_empty())?;
+        let id = match &self.id {
+            Value::String(s) => s.clone(),
+            other => other.to_string(),
+        };
+
+        Some(MosqueEvent {
+            id: format!("mawaqit:{}:{}", slug, id),
+            mosque_id: slug.to_string(),
+            kind: EventKind::infer(title),
+            title: title.to_string(),
+            description: self.content.clone().filter(|c| !c.trim().is_empty()),
+            location: None,
+            url: None,
+            starts_at: parse_announcement_date(self.start_date.as_deref()?)?,
+            ends_at: self.end_date.as_deref().and_then(parse_announcement_date),
+            reminder_minutes: None,
+        })
+    }
+}
+
+/// Field of a lenient parse; absent or mistyped fields are recorded and defaulted
+/// A null is only mistyped for fields that can't be empty, e.g. `jumua` is null when the mosque holds none
+fn lenient_field<T: serde::de::DeserializeOwned + Default>(value: &Value, key: &str, missing: &mut Vec<String>) -> T {
+    match value.get(key).map(T::deserialize) {
+        Some(Ok(v)) => v,
+        _ => {
+            missing.push(key.to_string());
+            T::default()
+        }
+    }
+}
+
+/// Number from a JSON number or numeric string
+fn number(v: &Value) -> Option<f64> {
+    v.as_f64().or_else(|| v.as_str()?.trim().parse().ok())
+}
+
+/// Strings of a JSON array, skipping entries of any other type
+fn string_list(v: &Value) -> Vec<String> {
+    v.as_array()
+        .map(|list| list.iter().filter_map(|s| s.as_str().map(str::to_string)).collect())
+        .unwrap_or_default()
+}
+
+/// Months of day -> times; a month given as an array is indexed from day 1
+fn calendar_months(v: &Value) -> Vec<HashMap<String, Vec<String>>> {
+    let Some(months) = v.as_array() else {
+        return Vec::new();
+    };
+
+    months
+        .iter()
+        .map(|month| match month {
+            Value::Object(days) => days.iter().map(|(day, times)| (day.clone(), string_list(times))).collect(),
+            Value::Array(days) => days
+                .iter()
+                .enumerate()
+                .map(|(i, times)| ((i + 1).to_string(), string_list(times)))
+                .collect(),
+            _ => HashMap::new(),
+        })
+        .collect()
+}
+
+/// Mawaqit dates are "YYYY-MM-DD" or "YYYY-MM-DD HH:MM[:SS]" in the mosque's local time
+fn parse_announcement_date(value: &str) -> Option<chrono::DateTime<Utc>> {
+    use chrono::NaiveDateTime;
+
+    let naive = NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S")
+        .or_else(|_| NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M"))
+        .ok()
+        .or_else(|| NaiveDate::parse_from_str(value, "%Y-%m-%d").ok()?.and_hms_opt(0, 0, 0))?;
+
+    Some(resolve_local(&Local, naive))
+}
+
+impl MawaqitConfData {
+    /// Read each field on its own, defaulting the ones whose type changed
+    fn from_value_lenient(value: &Value) -> Self {
+        let mut missing = Vec::new();
+        let get = |key: &str| value.get(key).unwrap_or(&Value::Null);
+
+        let mut data = Self {
+            name: lenient_field(value, "name", &mut missing),
+            label: lenient_field(value, "label", &mut missing),
+            country_code: lenient_field(value, "countryCode", &mut missing),
+            // Coordinates sometimes arrive as strings
+            latitude: number(get("latitude")),
+            longitude: number(get("longitude")),
+            url: lenient_field(value, "url", &mut missing),
+            times: string_list(get("times")),
+            shuruq: lenient_field(value, "shuruq", &mut missing),
+            jumua: lenient_field(value, "jumua", &mut missing),
+            iqama_calendar: calendar_months(get("iqamaCalendar")),
+            calendar: calendar_months(get("calendar")),
+            iqama_enabled: lenient_field(value, "iqamaEnabled", &mut missing),
+            time_display_format: lenient_field(value, "timeDisplayFormat", &mut missing),
+            timezone: lenient_field(value, "timezone", &mut missing),
+            hijri_adjustment: number(get("hijriAdjustment")).map(|n| n as i32),
+            hijri_date_force_to_30: get("hijriDateForceTo30").as_bool(),
+            aid_prayer_time: get("aidPrayerTime").as_str().map(str::to_string),
+            aid_prayer_time_2: get("aidPrayerTime2").as_str().map(str::to_string),
+            announcements: get("announcements")
+                .as_array()
+                .map(|list| list.iter().filter_map(|a| MawaqitAnnouncement::deserialize(a).ok()).collect())
+                .unwrap_or_default(),
+            imsak_minutes_before_fajr: number(get("imsakNbMinBeforeFajr")).map(|n| n as i64),
+            missing_fields: Vec::new(),
+        };
+
+        let unreadable = [
+            ("latitude", data.latitude.is_none()),
+            ("longitude", data.longitude.is_none()),
+            ("times", data.times.is_empty()),
+            ("iqamaCalendar", data.iqama_calendar.is_empty()),
+            ("calendar", data.calendar.is_empty()),
+        ];
+        missing.extend(unreadable.iter().filter(|(_, bad)| *bad).map(|(key, _)| key.to_string()));
+        missing.sort();
+        data.missing_fields = missing;
+        data
+    }
+
+    /// Iqamas for a calendar day; entries are offsets ("+15") or fixed times ("20:30")
+    fn day_iqamas(&self, date: NaiveDate) -> Option<DayIqamas> {
+        if !self.iqama_enabled.unwrap_or(true) {
+            return None;
+        }
+
+        let times = calendar_day(&self.calendar, date).ok()?;
+        let entries = calendar_day(&self.iqama_calendar, date).ok()?;
+
+        // calendar: [Fajr, Shuruq, Dhuhr, Asr, Maghrib, Isha]; iqamaCalendar skips Shuruq
+        let adhans = [0, 2, 3, 4, 5].map(|i| times.get(i));
+        let mut iqamas: Vec<(PrayerName, NaiveDateTime)> = Vec::new();
+        for ((&prayer, adhan), entry) in PrayerName::DAILY.iter().zip(adhans).zip(&entries) {
+            let Some(time) = adhan
+                .and_then(|adhan| resolve_iqama(adhan, entry))
+                .and_then(|time| NaiveTime::parse_from_str(&time, "%H:%M").ok())
+            else {
+                continue;
+            };
+            // Prayers are in order, so a time earlier than the one before is past midnight
+            let mut iqama = date.and_time(time);
+            if iqamas.last().is_some_and(|(_, previous)| iqama < *previous) {
+                iqama += chrono::Duration::days(1);
+            }
+            iqamas.push((prayer, iqama));
+        }
+
+        Some(DayIqamas { date, iqamas })
+    }
+
+    fn hijri_info(&self) -> MosqueHijriInfo {
+        MosqueHijriInfo {
+            adjustment_days: self.hijri_adjustment.unwrap_or(0),
+            force_month_30: self.hijri_date_force_to_30.unwrap_or(false),
+            eid_prayer_times: [&self.aid_prayer_time, &self.aid_prayer_time_2]
+                .into_iter()
+                .flatten()
+                .filter(|t| !t.trim().is_empty())
+                .cloned()
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COUNTRY_JSON: &str = r#"[
+        {"slug": "mosquee-paris", "name": "Grande Mosquée de Paris", "image1": "", "address": "2bis Place du Puits de l'Ermite",
+         "city": "Paris", "zipcode": "75005", "countryFullName": "France", "lng": 2.3551, "lat": 48.8420},
+        {"slug": "mosquee-lyon", "name": "Grande Mosquée de Lyon", "image1": "", "address": "146 Boulevard Pinel",
+         "city": "Lyon", "zipcode": "69008", "countryFullName": "France", "lng": 4.8867, "lat": 45.7380},
+        {"slug": "mosquee-evry", "name": "Mosquée d'Évry", "image1": "", "address": "Rue Georges Brassens",
+         "city": "Évry", "zipcode": "91000", "countryFullName": "France", "lng": 2.4304, "lat": 48.6238}
+    ]"#;
+
+    #[test]
+    fn test_streamed_parse_filters_by_query() {
+        let filter = MosqueFilter {
+            query: Some(SearchQuery::new("lyon")),
+            ..MosqueFilter::default()
+        };
+
+        let mosques = parse_mosques_filtered(COUNTRY_JSON.as_bytes(), &filter).unwrap();
+
+        assert_eq!(mosques.len(), 1);
+        assert_eq!(mosques[0].slug, "mosquee-lyon");
+    }
+
+    #[test]
+    fn test_streamed_parse_filters_by_radius_and_limit() {
+        let paris = GeoLocation::new(48.8566, 2.3522);
+        let filter = MosqueFilter {
+            near: Some((paris, 50.0)),
+            ..MosqueFilter::default()
+        };
+
+        let mosques = parse_mosques_filtered(COUNTRY_JSON.as_bytes(), &filter).unwrap();
+        assert_eq!(mosques.len(), 2);
+
+        let limited = MosqueFilter {
+            limit: Some(1),
+            ..filter
+        };
+        let mosques = parse_mosques_filtered(COUNTRY_JSON.as_bytes(), &limited).unwrap();
+        assert_eq!(mosques.len(), 1);
+    }
+
+    #[test]
+    fn test_conf_data_hijri_info_and_announcements() {
+        let html = r#"<script>let confData = {"name": "Mosquée Test", "label": "Paris", "countryCode": "FR",
+            "latitude": 48.8, "longitude": 2.3, "url": "https://mawaqit.net/fr/m/test",
+            "times": ["06:00", "13:00", "16:00", "18:30", "20:00"], "shuruq": "07:30", "jumua": "13:30",
+            "iqamaCalendar": [], "calendar": [], "iqamaEnabled": true, "timeDisplayFormat": "24",
+            "timezone": "Europe/Paris", "hijriAdjustment": -1, "hijriDateForceTo30": true,
+            "aidPrayerTime": "08:30", "aidPrayerTime2": "",
+            "announcements": [{"id": 12, "title": "Iftar collectif", "content": "Ouvert à tous", "startDate": "2026-03-01 19:00"},
+                              {"id": 13, "title": "", "startDate": "2026-03-02"}]};</script>"#;
+
+        let conf_data = extract_conf_data(html).unwrap();
+        let events: Vec<MosqueEvent> = conf_data.announcements.iter().filter_map(|a| a.to_event("test")).collect();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].id, "mawaqit:test:12");
+        assert_eq!(events[0].kind, EventKind::Iftar);
+
+        let info = conf_data.hijri_info();
+
+        assert_eq!(info.adjustment_days, -1);
+        assert!(info.force_month_30);
+        assert_eq!(info.eid_prayer_times, vec!["08:30".to_string()]);
+    }
+
+    #[test]
+    fn test_day_iqamas_offsets_and_fixed_times() {
+        let html = r#"<script>let confData = {"name": "Mosquée Test", "label": "Paris", "countryCode": "FR",
+            "latitude": 48.8, "longitude": 2.3, "url": "https://mawaqit.net/fr/m/test",
+            "times": ["06:00", "13:00", "16:00", "18:30", "20:00"],
+            "calendar": [{"1": ["06:00", "07:30", "13:00", "16:00", "18:30", "20:00"]}],
+            "iqamaCalendar": [{"1": ["+20", "+10", "+10", "+5", "20:30"]}],
+            "timeDisplayFormat": "24", "timezone": "Europe/Paris"};</script>"#;
+
+        let conf_data = extract_conf_data(html).unwrap();
+        let day = conf_data.day_iqamas(NaiveDate::from_ymd_opt(2026, 1, 1).unwrap()).unwrap();
+        let time = |s| NaiveTime::parse_from_str(s, "%H:%M").unwrap();
+
+        assert_eq!(day.iqamas[0].1.time(), time("06:20"));
+        assert_eq!(day.iqamas[4], (PrayerName::Isha, day.date.and_time(time("20:30"))));
+        assert!(conf_data.day_iqamas(NaiveDate::from_ymd_opt(2026, 1, 2).unwrap()).is_none());
+    }
+
+    #[test]
+    fn test_day_iqamas_after_midnight() {
+        let conf_data = MawaqitConfData {
+            calendar: vec![HashMap::from([("21".to_string(), ["02:50", "05:20", "13:50", "18:00", "21:55", "23:45"].map(String::from).to_vec())])],
+            iqama_calendar: vec![HashMap::from([("21".to_string(), ["+10", "+10", "+10", "+5", "+20"].map(String::from).to_vec())])],
+            ..MawaqitConfData::default()
+        };
+
+        // Isha's iqama is on the 22nd, after the adhan on the 21st
+        let day = conf_data.day_iqamas(NaiveDate::from_ymd_opt(2026, 1, 21).unwrap()).unwrap();
+        assert_eq!(day.iqamas[4].1, NaiveDate::from_ymd_opt(2026, 1, 22).unwrap().and_hms_opt(0, 5, 0).unwrap());
+        assert_eq!(day.iqamas[3].1.date(), day.date);
+    }
+
+    #[test]
+    fn test_calendar_boundaries() {
+        // Calendar published for 2025: no Feb 29, days numbered by month
+        let month = |days: u32, fajr: &str| -> HashMap<String, Vec<String>> {
+            (1..=days).map(|d| (d.to_string(), vec![fajr.to_string(); 6])).collect()
+        };
+        let lengths = [31, 28, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31];
+        let calendar: Vec<_> = lengths
+            .iter()
+            .enumerate()
+            .map(|(i, &days)| month(days, &format!("{:02}:00", i + 1)))
+            .collect();
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+
+        // Feb 29 of a leap year reads Feb 28
+        assert_eq!(calendar_day(&calendar, date(2028, 2, 29)).unwrap()[0], "02:00");
+
+        // Dec 31 -> Jan 1 wraps to the first month of the same calendar
+        let conf_data = MawaqitConfData {
+            iqama_calendar: calendar.iter().map(|m| m.keys().map(|d| (d.clone(), vec!["+10".to_string(); 5])).collect()).collect(),
+            calendar: calendar.clone(),
+            ..MawaqitConfData::default()
+        };
+        let days: Vec<_> = date(2026, 12, 30).iter_days().take(4).filter_map(|d| conf_data.day_iqamas(d)).collect();
+        assert_eq!(days.len(), 4);
+        assert_eq!(days[2].date, date(2027, 1, 1));
+        assert_eq!(days[2].iqamas[0].1, date(2027, 1, 1).and_hms_opt(1, 10, 0).unwrap());
+
+        // Missing months and days are named in the error
+        let error = calendar_day(&calendar[..6], date(2026, 7, 1)).unwrap_err();
+        assert!(error.contains("no month 7") && error.contains("6 months"), "{}", error);
+        let error = calendar_day(&[month(30, "01:00")], date(2026, 1, 31)).unwrap_err();
+        assert!(error.contains("no day 31") && error.contains("30 days"), "{}", error);
+    }
+
+    #[test]
+    fn test_conf_data_missing_optional_fields() {
+        // No timeDisplayFormat, timezone, url or coordinates; no Jumuah is published, not missing
+        let html = r#"<script>let confData = {"name": "Mosquée Test", "label": "Paris", "countryCode": "FR",
+            "times": ["06:00", "13:00", "16:00", "18:30", "20:00"], "shuruq": "07:30", "jumua": null,
+            "calendar": [], "iqamaCalendar": [], "iqamaEnabled": true};</script>"#;
+
+        let conf_data = extract_conf_data(html).unwrap();
+
+        assert_eq!(conf_data.times.len(), 5);
+        assert!(conf_data.missing_fields.contains(&"timeDisplayFormat".to_string()));
+        assert!(conf_data.missing_fields.contains(&"latitude".to_string()));
+        assert!(!conf_data.missing_fields.contains(&"jumua".to_string()));
+    }
+
+    #[test]
+    fn test_conf_data_lenient_parse_of_changed_types() {
+        // Coordinates as strings, a number where a string was, and an array-shaped calendar month
+        let html = r#"<script>let confData = {"name": "Mosquée Test", "label": "Paris", "countryCode": "FR",
+            "latitude": "48.8", "longitude": "2.3", "url": "https://mawaqit.net/fr/m/test",
+            "times": ["06:00", "13:00", "16:00", "18:30", "20:00"], "shuruq": "07:30", "jumua": null,
+            "calendar": [[["06:00", "07:30", "13:00", "16:00", "18:30", "20:00"]]],
+            "iqamaCalendar": [{"1": ["+20", "+10", "+10", "+5", "+10"]}], "iqamaEnabled": true,
+            "timeDisplayFormat": 24, "timezone": "Europe/Paris"};</script>"#;
+
+        let conf_data = extract_conf_data(html).unwrap();
+
+        assert_eq!(conf_data.latitude, Some(48.8));
+        assert_eq!(conf_data.jumua, None);
+        assert_eq!(conf_data.missing_fields, vec!["timeDisplayFormat".to_string()]);
+        let day = conf_data.day_iqamas(NaiveDate::from_ymd_opt(2026, 1, 1).unwrap()).unwrap();
+        assert_eq!(day.iqamas.len(), 5);
+    }
+
+    #[test]
+    fn test_visible_times_fallback() {
+        let html = r#"<html><head><title>Mosquée Test - Mawaqit</title></head><body>
+            <div class="shuruq"><div class="name">Shuruq</div><div class="time">07:30</div></div>
+            <div class="prayers">
+                <div><div class="name">Fajr</div><div class="time"><div>06:00</div></div><div class="wait">+20</div></div>
+                <div><div class="name">Dhuhr</div><div class="time"><div>13:00</div></div><div class="wait">+10</div></div>
+                <div><div class="name">Asr</div><div class="time"><div>16:00</div></div><div class="wait">+10</div></div>
+                <div><div class="name">Maghrib</div><div class="time"><div>18:30</div></div><div class="wait">+5</div></div>
+                <div><div class="name">Isha</div><div class="time"><div>20:00</div></div><div class="wait">20:30</div></div>
+            </div></body></html>"#;
+        let today = NaiveDate::from_ymd_opt(2026, 3, 10).unwrap();
+
+        assert!(extract_conf_data(html).is_none());
+        let conf_data = scrape_visible_times(html, today).unwrap();
+
+        assert_eq!(conf_data.name, "Mosquée Test");
+        assert_eq!(conf_data.times, vec!["06:00", "13:00", "16:00", "18:30", "20:00"]);
+        assert_eq!(conf_data.shuruq.as_deref(), Some("07:30"));
+        assert!(conf_data.missing_fields.contains(&"calendar".to_string()));
+        assert_eq!(conf_data.iqama_calendar[2]["10"][4], "20:30");
+    }
+
+    #[test]
+    fn test_conf_data_without_times_is_rejected() {
+        let html = r#"<script>let confData = {"name": "Mosquée Test", "calendar": []};</script>"#;
+
+        assert!(extract_conf_data(html).is_none());
+    }
+}
@@ -0,0 +1,241 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc, Weekday};
+use serde_json::Value;
+
+use crate::models::*;
+use crate::providers::time_utils::time_on_date_local;
+use crate::providers::{PrayerDataProvider, ProviderError, ProviderResult};
+use crate::services::clock;
+
+/// Fixed adhan time and iqama delay (minutes) of each mock prayer
+const MOCK_SCHEDULE: [(PrayerName, &str, i64); 5] = [
+    (PrayerName::Fajr, "05:30", 20),
+    (PrayerName::Dhuhr, "13:00", 15),
+    (PrayerName::Asr, "16:30", 10),
+    (PrayerName::Maghrib, "19:00", 5),
+    (PrayerName::Isha, "20:30", 15),
+];
+
+const MOCK_JUMUAH: (&str, i64) = ("13:30", 15);
+
+const ID_PREFIX: &str = "mock-";
+
+/// Mock mosques have no real provider behind them, e.g. no Hijri info or events to fetch
+pub fn is_mock_mosque(mosque_id: &str) -> bool {
+    mosque_id.starts_with(ID_PREFIX)
+}
+
+/// How the mock provider places prayers in time
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MockMode {
+    /// Same wall-clock times every day
+    Fixed,
+    /// Dhuhr adhan one minute from now, other prayers keep their spacing around it
+    Soon,
+}
+
+/// Mock Provider
+/// Deterministic schedules for development and testing, no network access
+pub struct MockProvider {
+    mode: MockMode,
+}
+
+impl MockProvider {
+    pub fn new() -> Self {
+        Self {
+            mode: MockMode::Fixed,
+        }
+    }
+
+    pub fn with_mode(mode: MockMode) -> Self {
+        Self { mode }
+    }
+
+    fn mosques() -> Vec<Mosque> {
+        [
+            ("mock-central", "Mock Central Mosque", "Testville"),
+            ("mock-riverside", "Mock Riverside Masjid", "Testville"),
+            ("mock-no-iqama", "Mock Adhan-Only Mosque", "Sampleton"),
+        ]
+        .into_iter()
+        .enumerate()
+        .map(|(i, (id, name, city))| {
+            let mut mosque = Mosque::new(id.to_string(), name.to_string());
+            mosque.city = Some(city.to_string());
+            mosque.country = Some("XX".to_string());
+            mosque.latitude = Some(48.85 + i as f64 * 0.01);
+            mosque.longitude = Some(2.35 + i as f64 * 0.01);
+            mosque
+        })
+        .collect()
+    }
+
+    fn find_mosque(mosque_id: &str) -> ProviderResult<Mosque> {
+        Self::mosques()
+            .into_iter()
+            .find(|m| m.id == mosque_id)
+            .ok_or_else(|| ProviderError::NotFound(format!("Mock mosque {} not found", mosque_id)))
+    }
+
+    /// Build the schedule of a mock mosque for a date
+    pub fn schedule(&self, mosque_id: &str, date: NaiveDate, now: DateTime<Utc>) -> PrayerTimes {
+        let with_iqama = mosque_id != "mock-no-iqama";
+
+        let prayer = |name: PrayerName, adhan: DateTime<Utc>, iqama_delay: i64| Prayer {
+            name,
+            adhan,
+            iqama: with_iqama.then(|| adhan + Duration::minutes(iqama_delay)),
+            custom_rakah_count: None,
+            iqama_offset_minutes: with_iqama.then_some(iqama_delay as i32),
+        };
+
+        let adhans: Vec<DateTime<Utc>> = match self.mode {
+            MockMode::Fixed => MOCK_SCHEDULE
+                .iter()
+                .map(|(_, time, _)| time_on_date_local(date, time))
+                .collect(),
+            MockMode::Soon => {
+                // Keep the fixed spacing between prayers, shifted so Dhuhr is imminent
+                let dhuhr_fixed = time_on_date_local(date, MOCK_SCHEDULE[1].1);
+                let shift = now + Duration::minutes(1) - dhuhr_fixed;
+                MOCK_SCHEDULE
+                    .iter()
+                    .map(|(_, time, _)| time_on_date_local(date, time) + shift)
+                    .collect()
+            }
+        };
+
+        let mut prayers = MOCK_SCHEDULE
+            .iter()
+            .zip(adhans)
+            .map(|((name, _, delay), adhan)| prayer(*name, adhan, *delay));
+
+        let jumuah = (date.weekday() == Weekday::Fri).then(|| {
+            prayer(
+                PrayerName::Jumuah,
+                time_on_date_local(date, MOCK_JUMUAH.0),
+                MOCK_JUMUAH.1,
+            )
+        });
+
+        PrayerTimes {
+            date: time_on_date_local(date, "00:00"),
+            fajr: prayers.next().unwrap(),
+            dhuhr: prayers.next().unwrap(),
+            asr: prayers.next().unwrap(),
+            maghrib: prayers.next().unwrap(),
+            isha: prayers.next().unwrap(),
+            jumuah,
+            mosque_id: Some(mosque_id.to_string()),
+            mosque_name: Self::find_mosque(mosque_id).ok().map(|m| m.name),
+            cached_at: Some(now),
+            imsak: None,
+            shuruq: None,
+            asr_method: None,
+            provider: Some(PROVIDER_MOCK.to_string()),
+            freshness: None,
+            warnings: Vec::new(),
+        }
+    }
+}
+
+impl Default for MockProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl PrayerDataProvider for MockProvider {
+    fn id(&self) -> &str {
+        PROVIDER_MOCK
+    }
+
+    fn name(&self) -> &str {
+        "Mock (Development)"
+    }
+
+    fn description(&self) -> &str {
+        "Deterministic offline schedules for development and testing"
+    }
+
+    fn config_schema(&self) -> Vec<ConfigField> {
+        vec![ConfigField::new("mode", "Schedule Mode", ConfigFieldType::Select)
+            .options(vec!["fixed".to_string(), "soon".to_string()])
+            .default_value("fixed")
+            .description("\"soon\" puts Dhuhr one minute from now")]
+    }
+
+    async fn initialize(&mut self, config: Value) -> ProviderResult<()> {
+        self.mode = match config["mode"].as_str() {
+            None | Some("fixed") => MockMode::Fixed,
+            Some("soon") => MockMode::Soon,
+            Some(other) => {
+                return Err(ProviderError::InvalidConfig(format!("Unknown mock mode: {}", other)))
+            }
+        };
+        Ok(())
+    }
+
+    async fn search_mosques(&self, query: &str, _location: Option<&GeoLocation>) -> ProviderResult<Vec<Mosque>> {
+        let query = query.to_lowercase();
+
+        Ok(Self::mosques()
+            .into_iter()
+            .filter(|m| {
+                m.name.to_lowercase().contains(&query)
+                    || m.city.as_deref().unwrap_or("").to_lowercase().contains(&query)
+            })
+            .collect())
+    }
+
+    async fn get_nearby_mosques(&self, _location: &GeoLocation, _radius_km: f64) -> ProviderResult<Vec<Mosque>> {
+        Ok(Self::mosques())
+    }
+
+    async fn get_prayer_times(&self, mosque_id: &str, date: Option<NaiveDate>) -> ProviderResult<PrayerTimes> {
+        Self::find_mosque(mosque_id)?;
+
+        let date = date.unwrap_or_else(clock::today);
+        Ok(self.schedule(mosque_id, date, clock::now()))
+    }
+
+    async fn test_connection(&self) -> ProviderResult<ProviderTestResult> {
+        Ok(ProviderTestResult {
+            success: true,
+            message: "Mock provider is always available".to_string(),
+            latency_ms: Some(0),
+        })
+    }
+
+    async fn get_mosque_details(&self, mosque_id: &str) -> ProviderResult<Mosque> {
+        Self::find_mosque(mosque_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_soon_mode_puts_dhuhr_one_minute_ahead() {
+        let provider = MockProvider::with_mode(MockMode::Soon);
+        let now = Utc::now();
+        let schedule = provider.schedule("mock-central", clock::today(), now);
+
+        assert_eq!(schedule.dhuhr.adhan, now + Duration::minutes(1));
+        assert_eq!(schedule.dhuhr.iqama, Some(now + Duration::minutes(16)));
+        assert!(schedule.fajr.adhan < schedule.dhuhr.adhan);
+        assert!(schedule.isha.adhan > schedule.maghrib.adhan);
+    }
+
+    #[test]
+    fn test_adhan_only_mosque_has_no_iqama() {
+        let provider = MockProvider::new();
+        let date = NaiveDate::from_ymd_opt(2026, 2, 6).unwrap(); // a Friday
+        let schedule = provider.schedule("mock-no-iqama", date, Utc::now());
+
+        assert!(schedule.all_prayers().iter().all(|p| p.iqama.is_none()));
+        assert!(schedule.jumuah.is_some());
+    }
+}
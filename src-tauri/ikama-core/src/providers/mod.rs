@@ -0,0 +1,48 @@
+pub mod prayer_data_provider;
+pub mod official_api_provider;
+pub mod official_api_schema;
+pub mod community_wrapper_provider;
+pub mod scraping_provider;
+pub mod fallback_provider;
+pub mod mawaqit_provider;
+pub mod time_utils;
+pub mod http_cache;
+pub mod http_fixtures;
+pub mod mock_provider;
+pub mod ical_feed;
+pub mod diyanet_provider;
+pub mod islamicfinder_provider;
+pub mod masjid_timetable_provider;
+
+pub use prayer_data_provider::*;
+pub use official_api_provider::*;
+pub use community_wrapper_provider::*;
+pub use scraping_provider::*;
+#[allow(unused_imports)]
+pub use fallback_provider::*;
+pub use mawaqit_provider::*;
+pub use mock_provider::*;
+pub use ical_feed::*;
+pub use diyanet_provider::*;
+pub use islamicfinder_provider::*;
+pub use masjid_timetable_provider::*;
+
+use crate::models::{
+    ConfigField, PROVIDER_COMMUNITY_WRAPPER, PROVIDER_DIYANET, PROVIDER_ISLAMICFINDER, PROVIDER_MASJID_TIMETABLE,
+    PROVIDER_MAWAQIT, PROVIDER_MOCK, PROVIDER_OFFICIAL_API, PROVIDER_SCRAPING,
+};
+
+/// Config schema of a provider by id, empty for unknown providers
+pub fn provider_config_schema(provider_id: &str) -> Vec<ConfigField> {
+    match provider_id {
+        PROVIDER_MAWAQIT => MawaqitProvider::new().config_schema(),
+        PROVIDER_OFFICIAL_API => OfficialApiProvider::new().config_schema(),
+        PROVIDER_COMMUNITY_WRAPPER => CommunityWrapperProvider::new().config_schema(),
+        PROVIDER_SCRAPING => ScrapingProvider::new().config_schema(),
+        PROVIDER_ISLAMICFINDER => IslamicFinderProvider::new().config_schema(),
+        PROVIDER_DIYANET => DiyanetProvider::new().config_schema(),
+        PROVIDER_MASJID_TIMETABLE => MasjidTimetableProvider::new().config_schema(),
+        PROVIDER_MOCK => MockProvider::new().config_schema(),
+        _ => Vec::new(),
+    }
+}
@@ -1,10 +1,13 @@
 use async_trait::async_trait;
-use chrono::{NaiveDate, Utc};
+use chrono::NaiveDate;
 use reqwest::Client;
 use serde_json::Value;
 
 use crate::models::*;
+use crate::providers::http_fixtures::FixtureMode;
+use crate::providers::official_api_schema::{parse_mosque, parse_mosque_list, ApiMosque, PrayerTimesResponse};
 use crate::providers::{PrayerDataProvider, ProviderError, ProviderResult};
+use crate::services::{clock, record_payload};
 
 /// Official API Provider (Provider A)
 /// Direct Mawaqit API access (requires token)
@@ -12,6 +15,7 @@ pub struct OfficialApiProvider {
     client: Client,
     base_url: String,
     api_token: Option<String>,
+    fixtures: FixtureMode,
 }
 
 impl OfficialApiProvider {
@@ -20,6 +24,7 @@ impl OfficialApiProvider {
             client: Client::new(),
             base_url: "https://mawaqit.net/api".to_string(),
             api_token: None,
+            fixtures: FixtureMode::from_env(),
         }
     }
 
@@ -28,6 +33,84 @@ impl OfficialApiProvider {
             ProviderError::InvalidConfig("API token required".to_string())
         })
     }
+
+    /// Exchange Mawaqit account credentials for an API token
+    pub async fn login(&self, email: &str, password: &str) -> ProviderResult<String> {
+        let request = self.client
+            .post(format!("{}/auth/login", self.base_url))
+            .json(&serde_json::json!({ "username": email, "password": password }));
+        let response = self.fixtures.send(request).await?;
+
+        if matches!(response.status().as_u16(), 401 | 403) {
+            return Err(ProviderError::InvalidConfig("Invalid Mawaqit email or password".to_string()));
+        }
+
+        if !response.status().is_success() {
+            return Err(ProviderError::Server {
+                status_code: response.status().as_u16(),
+                message: "Login failed".to_string(),
+            });
+        }
+
+        let data: serde_json::Value = response.json().map_err(|e| {
+            ProviderError::Parse(format!("Failed to parse: {}", e))
+        })?;
+
+        data.get("apiAccessToken")
+            .or_else(|| data.get("token"))
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string())
+            .ok_or_else(|| ProviderError::Parse("Login response has no token".to_string()))
+    }
+
+    /// Favorite and preferred mosques of the logged-in account
+    pub async fn get_account(&self) -> ProviderResult<MawaqitAccount> {
+        let token = self.ensure_initialized()?;
+
+        let request = self.client
+            .get(format!("{}/user/me", self.base_url))
+            .header("Authorization", format!("Bearer {}", token));
+        let response = self.fixtures.send(request).await?;
+
+        if !response.status().is_success() {
+            return Err(ProviderError::Server {
+                status_code: response.status().as_u16(),
+                message: "Failed to get account".to_string(),
+            });
+        }
+
+        let data: serde_json::Value = response.json().map_err(|e| {
+            ProviderError::Parse(format!("Failed to parse: {}", e))
+        })?;
+
+        Ok(MawaqitAccount::from_json(&data))
+    }
+}
+
+/// Mosques a Mawaqit account follows
+#[derive(Debug, Clone, Default)]
+pub struct MawaqitAccount {
+    pub favorites: Vec<Mosque>,
+    /// Id of the mosque the account shows by default
+    pub preferred_mosque_id: Option<String>,
+}
+
+impl MawaqitAccount {
+    fn from_json(data: &serde_json::Value) -> Self {
+        let favorites = data
+            .get("favorites")
+            .and_then(|v| v.as_array())
+            .map(|list| list.iter().filter_map(parse_mosque).collect())
+            .unwrap_or_default();
+
+        // Either a bare id or a full mosque object
+        let preferred_mosque_id = data.get("preferred_mosque").and_then(|v| match v.as_str() {
+            Some(id) => Some(id.to_string()),
+            None => parse_mosque(v).map(|m| m.id),
+        });
+
+        Self { favorites, preferred_mosque_id }
+    }
 }
 
 #[async_trait]
@@ -68,13 +151,11 @@ impl PrayerDataProvider for OfficialApiProvider {
     async fn search_mosques(&self, query: &str, _location: Option<&GeoLocation>) -> ProviderResult<Vec<Mosque>> {
         let token = self.ensure_initialized()?;
 
-        let response = self.client
+        let request = self.client
             .get(format!("{}/mosques/search", self.base_url))
             .query(&[("word", query)])
-            .header("Authorization", format!("Bearer {}", token))
-            .send()
-            .await
-            .map_err(|e| ProviderError::Network(format!("Failed to search: {}", e)))?;
+            .header("Authorization", format!("Bearer {}", token));
+        let response = self.fixtures.send(request).await?;
 
         if !response.status().is_success() {
             return Err(ProviderError::Server {
@@ -83,44 +164,25 @@ impl PrayerDataProvider for OfficialApiProvider {
             });
         }
 
-        let data: Vec<serde_json::Value> = response.json().await.map_err(|e| {
+        let data: serde_json::Value = response.json().map_err(|e| {
             ProviderError::Parse(format!("Failed to parse: {}", e))
         })?;
 
-        let mosques: Vec<Mosque> = data
-            .into_iter()
-            .filter_map(|v| {
-                Some(Mosque {
-                    id: v.get("id")?.as_str()?.to_string(),
-                    name: v.get("name")?.as_str()?.to_string(),
-                    address: v.get("address")?.as_str().map(|s| s.to_string()),
-                    city: v.get("city")?.as_str().map(|s| s.to_string()),
-                    country: v.get("country")?.as_str().map(|s| s.to_string()),
-                    latitude: v.get("latitude")?.as_f64(),
-                    longitude: v.get("longitude")?.as_f64(),
-                    is_favorite: false,
-                    last_accessed: None,
-                })
-            })
-            .collect();
-
-        Ok(mosques)
+        Ok(parse_mosque_list(&data))
     }
 
     async fn get_nearby_mosques(&self, location: &GeoLocation, radius_km: f64) -> ProviderResult<Vec<Mosque>> {
         let token = self.ensure_initialized()?;
 
-        let response = self.client
+        let request = self.client
             .get(format!("{}/mosques/nearby", self.base_url))
             .query(&[
                 ("lat", location.latitude.to_string()),
                 ("lon", location.longitude.to_string()),
                 ("radius", radius_km.to_string()),
             ])
-            .header("Authorization", format!("Bearer {}", token))
-            .send()
-            .await
-            .map_err(|e| ProviderError::Network(format!("Failed to get nearby: {}", e)))?;
+            .header("Authorization", format!("Bearer {}", token));
+        let response = self.fixtures.send(request).await?;
 
         if !response.status().is_success() {
             return Err(ProviderError::Server {
@@ -129,28 +191,11 @@ impl PrayerDataProvider for OfficialApiProvider {
             });
         }
 
-        let data: Vec<serde_json::Value> = response.json().await.map_err(|e| {
+        let data: serde_json::Value = response.json().map_err(|e| {
             ProviderError::Parse(format!("Failed to parse: {}", e))
         })?;
 
-        let mosques: Vec<Mosque> = data
-            .into_iter()
-            .filter_map(|v| {
-                Some(Mosque {
-                    id: v.get("id")?.as_str()?.to_string(),
-                    name: v.get("name")?.as_str()?.to_string(),
-                    address: v.get("address")?.as_str().map(|s| s.to_string()),
-                    city: v.get("city")?.as_str().map(|s| s.to_string()),
-                    country: v.get("country")?.as_str().map(|s| s.to_string()),
-                    latitude: v.get("latitude")?.as_f64(),
-                    longitude: v.get("longitude")?.as_f64(),
-                    is_favorite: false,
-                    last_accessed: None,
-                })
-            })
-            .collect();
-
-        Ok(mosques)
+        Ok(parse_mosque_list(&data))
     }
 
     async fn get_prayer_times(&self, mosque_id: &str, date: Option<NaiveDate>) -> ProviderResult<PrayerTimes> {
@@ -163,11 +208,8 @@ impl PrayerDataProvider for OfficialApiProvider {
             request = request.query(&[("date", d.format("%Y-%m-%d").to_string())]);
         }
 
-        let response = request
-            .header("Authorization", format!("Bearer {}", token))
-            .send()
-            .await
-            .map_err(|e| ProviderError::Network(format!("Failed to get times: {}", e)))?;
+        let request = request.header("Authorization", format!("Bearer {}", token));
+        let response = self.fixtures.send(request).await?;
 
         if response.status().as_u16() == 404 {
             return Err(ProviderError::NotFound(format!("Mosque {} not found", mosque_id)));
@@ -180,68 +222,16 @@ impl PrayerDataProvider for OfficialApiProvider {
             });
         }
 
-        let data: serde_json::Value = response.json().await.map_err(|e| {
-            ProviderError::Parse(format!("Failed to parse: {}", e))
-        })?;
-
-        // Parse prayer times from API response
-        // This is a simplified implementation - adjust according to actual Mawaqit API
-        let date = date.unwrap_or_else(|| Utc::now().date_naive());
-        let base_date = date.and_hms_opt(0, 0, 0).unwrap().and_local_timezone(Utc).unwrap();
+        let url = response.url().to_string();
+        let body = response.bytes();
+        record_payload(PROVIDER_OFFICIAL_API, mosque_id, &url, body);
 
-        fn parse_time(date: chrono::DateTime<Utc>, time_str: &str) -> chrono::DateTime<Utc> {
-            let parts: Vec<&str> = time_str.split(':').collect();
-            let hour: u32 = parts.get(0).unwrap_or(&"0").parse().unwrap_or(0);
-            let minute: u32 = parts.get(1).unwrap_or(&"0").parse().unwrap_or(0);
-            date.date_naive().and_hms_opt(hour, minute, 0).unwrap().and_local_timezone(Utc).unwrap()
-        }
+        let data: PrayerTimesResponse = serde_json::from_slice(body).map_err(|e| {
+            ProviderError::Parse(format!("Unrecognized prayer times payload: {}", e))
+        })?;
 
-        let get_time = |key: &str| -> chrono::DateTime<Utc> {
-            let time_str = data.get(key).and_then(|v| v.as_str()).unwrap_or("00:00");
-            parse_time(base_date, time_str)
-        };
-
-        let get_iqama = |key: &str| -> Option<chrono::DateTime<Utc>> {
-            data.get(key).and_then(|v| v.as_str()).map(|t| parse_time(base_date, t))
-        };
-
-        Ok(PrayerTimes {
-            date: base_date,
-            fajr: Prayer {
-                name: "Fajr".to_string(),
-                adhan: get_time("fajr"),
-                iqama: get_iqama("fajr_iqama"),
-                custom_rakah_count: None,
-            },
-            dhuhr: Prayer {
-                name: "Dhuhr".to_string(),
-                adhan: get_time("dhuhr"),
-                iqama: get_iqama("dhuhr_iqama"),
-                custom_rakah_count: None,
-            },
-            asr: Prayer {
-                name: "Asr".to_string(),
-                adhan: get_time("asr"),
-                iqama: get_iqama("asr_iqama"),
-                custom_rakah_count: None,
-            },
-            maghrib: Prayer {
-                name: "Maghrib".to_string(),
-                adhan: get_time("maghrib"),
-                iqama: get_iqama("maghrib_iqama"),
-                custom_rakah_count: None,
-            },
-            isha: Prayer {
-                name: "Isha".to_string(),
-                adhan: get_time("isha"),
-                iqama: get_iqama("isha_iqama"),
-                custom_rakah_count: None,
-            },
-            jumuah: None,
-            mosque_id: Some(mosque_id.to_string()),
-            mosque_name: None,
-            cached_at: Some(Utc::now()),
-        })
+        let today = clock::today();
+        data.into_prayer_times(mosque_id, date.unwrap_or(today), today)
     }
 
     async fn test_connection(&self) -> ProviderResult<ProviderTestResult> {
@@ -249,11 +239,10 @@ impl PrayerDataProvider for OfficialApiProvider {
 
         match self.ensure_initialized() {
             Ok(token) => {
-                let response = self.client
+                let request = self.client
                     .get(format!("{}/user/me", self.base_url))
-                    .header("Authorization", format!("Bearer {}", token))
-                    .send()
-                    .await;
+                    .header("Authorization", format!("Bearer {}", token));
+                let response = self.fixtures.send(request).await;
 
                 match response {
                     Ok(resp) => {
@@ -290,12 +279,10 @@ impl PrayerDataProvider for OfficialApiProvider {
     async fn get_mosque_details(&self, mosque_id: &str) -> ProviderResult<Mosque> {
         let token = self.ensure_initialized()?;
 
-        let response = self.client
+        let request = self.client
             .get(format!("{}/mosques/{}", self.base_url, mosque_id))
-            .header("Authorization", format!("Bearer {}", token))
-            .send()
-            .await
-            .map_err(|e| ProviderError::Network(format!("Failed to get details: {}", e)))?;
+            .header("Authorization", format!("Bearer {}", token));
+        let response = self.fixtures.send(request).await?;
 
         if response.status().as_u16() == 404 {
             return Err(ProviderError::NotFound(format!("Mosque {} not found", mosque_id)));
@@ -308,20 +295,36 @@ impl PrayerDataProvider for OfficialApiProvider {
             });
         }
 
-        let v: serde_json::Value = response.json().await.map_err(|e| {
+        let data: ApiMosque = response.json().map_err(|e| {
             ProviderError::Parse(format!("Failed to parse: {}", e))
         })?;
 
-        Ok(Mosque {
-            id: v.get("id").and_then(|v| v.as_str()).unwrap_or(mosque_id).to_string(),
-            name: v.get("name").and_then(|v| v.as_str()).unwrap_or("Unknown").to_string(),
-            address: v.get("address").and_then(|v| v.as_str()).map(|s| s.to_string()),
-            city: v.get("city").and_then(|v| v.as_str()).map(|s| s.to_string()),
-            country: v.get("country").and_then(|v| v.as_str()).map(|s| s.to_string()),
-            latitude: v.get("latitude").and_then(|v| v.as_f64()),
-            longitude: v.get("longitude").and_then(|v| v.as_f64()),
-            is_favorite: false,
-            last_accessed: None,
-        })
+        data.into_mosque()
+            .ok_or_else(|| ProviderError::Parse(format!("Mosque {} has no id", mosque_id)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_account_favorites_keep_api_ids() {
+        let data = serde_json::json!({
+            "email": "user@example.com",
+            "favorites": [
+                {"id": "7f3c", "slug": "mosquee-paris", "name": "Grande Mosquée de Paris", "city": "Paris"},
+                {"id": "9a1b", "name": "Masjid Al-Noor"},
+                {"slug": "no-name"}
+            ],
+            "preferred_mosque": {"id": "7f3c", "slug": "mosquee-paris", "name": "Grande Mosquée de Paris"}
+        });
+
+        let account = MawaqitAccount::from_json(&data);
+        let ids: Vec<_> = account.favorites.iter().map(|m| m.id.as_str()).collect();
+
+        assert_eq!(ids, vec!["7f3c", "9a1b"]);
+        assert_eq!(account.favorites[0].city.as_deref(), Some("Paris"));
+        assert_eq!(account.preferred_mosque_id.as_deref(), Some("7f3c"));
     }
 }
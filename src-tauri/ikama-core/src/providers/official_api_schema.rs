@@ -0,0 +1,324 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc, Weekday};
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::models::*;
+use crate::providers::time_utils::{iqama_offset, resolve_iqama, time_on_date_local, time_on_date_utc};
+use crate::providers::{ProviderError, ProviderResult};
+
+/// Mosque as returned by the Mawaqit search, nearby, detail and account endpoints
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiMosque {
+    slug: Option<String>,
+    uuid: Option<String>,
+    id: Option<Value>,
+    name: String,
+    /// Full postal address on v3 payloads
+    localisation: Option<String>,
+    address: Option<String>,
+    city: Option<String>,
+    country_full_name: Option<String>,
+    country: Option<String>,
+    #[serde(alias = "lat")]
+    latitude: Option<f64>,
+    #[serde(alias = "lng")]
+    longitude: Option<f64>,
+    women_space: Option<bool>,
+    handicap_accessibility: Option<bool>,
+    ablutions: Option<bool>,
+    parking: Option<bool>,
+    /// Facilities object of older payloads
+    facilities: Option<MosqueFacilities>,
+}
+
+impl ApiMosque {
+    /// The API's own id, which saved favorites and cached schedules are keyed by;
+    /// v3 payloads without one fall back to the uuid, then the slug
+    fn id(&self) -> Option<String> {
+        let id = match self.id.as_ref() {
+            Some(Value::String(s)) => Some(s.clone()),
+            Some(Value::Number(n)) => Some(n.to_string()),
+            _ => None,
+        };
+        id.or_else(|| self.uuid.clone()).or_else(|| self.slug.clone())
+    }
+
+    /// `None` for payloads without any usable id
+    pub fn into_mosque(self) -> Option<Mosque> {
+        let id = self.id()?;
+        let facilities = self.facilities.unwrap_or(MosqueFacilities {
+            womens_area: self.women_space,
+            wheelchair_access: self.handicap_accessibility,
+            wudu: self.ablutions,
+            parking: self.parking,
+        });
+
+        Some(Mosque {
+            id,
+            name: self.name,
+            address: self.localisation.or(self.address),
+            city: self.city,
+            country: self.country_full_name.or(self.country),
+            latitude: self.latitude,
+            longitude: self.longitude,
+            is_favorite: false,
+            last_accessed: None,
+            facilities,
+        })
+    }
+}
+
+/// Mosques from a list payload, skipping entries that don't parse
+pub fn parse_mosque_list(data: &Value) -> Vec<Mosque> {
+    data.as_array()
+        .map(|list| list.iter().filter_map(parse_mosque).collect())
+        .unwrap_or_default()
+}
+
+pub fn parse_mosque(v: &Value) -> Option<Mosque> {
+    ApiMosque::deserialize(v).ok()?.into_mosque()
+}
+
+/// `prayer-times` payload of the Mawaqit API, with the yearly calendars
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiPrayerTimes {
+    /// Today's Fajr, Dhuhr, Asr, Maghrib, Isha
+    times: Vec<String>,
+    /// Today's sunrise
+    shuruq: Option<String>,
+    jumua: Option<String>,
+    /// Months of day -> [Fajr, Shuruq, Dhuhr, Asr, Maghrib, Isha]
+    #[serde(default)]
+    calendar: Vec<HashMap<String, Vec<String>>>,
+    /// Months of day -> iqama per prayer, as offsets ("+10") or fixed times ("20:30")
+    #[serde(default)]
+    iqama_calendar: Vec<HashMap<String, Vec<String>>>,
+    iqama_enabled: Option<bool>,
+    #[serde(rename = "imsakNbMinBeforeFajr")]
+    imsak_minutes_before_fajr: Option<i64>,
+    name: Option<String>,
+}
+
+/// Flat payload of older API deployments: `fajr`, `fajr_iqama`, ... for the requested date
+#[derive(Debug, Clone, Deserialize)]
+pub struct FlatPrayerTimes {
+    fajr: String,
+    dhuhr: String,
+    asr: String,
+    maghrib: String,
+    isha: String,
+    fajr_iqama: Option<String>,
+    dhuhr_iqama: Option<String>,
+    asr_iqama: Option<String>,
+    maghrib_iqama: Option<String>,
+    isha_iqama: Option<String>,
+    imsak: Option<String>,
+    shuruq: Option<String>,
+}
+
+/// Prayer times response in any of the known shapes
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum PrayerTimesResponse {
+    Calendar(ApiPrayerTimes),
+    Flat(FlatPrayerTimes),
+}
+
+impl PrayerTimesResponse {
+    /// `today` is the date `times` refers to on calendar payloads
+    pub fn into_prayer_times(self, mosque_id: &str, date: NaiveDate, today: NaiveDate) -> ProviderResult<PrayerTimes> {
+        match self {
+            PrayerTimesResponse::Calendar(payload) => payload.prayer_times(mosque_id, date, today),
+            PrayerTimesResponse::Flat(payload) => Ok(payload.prayer_times(mosque_id, date)),
+        }
+    }
+}
+
+impl ApiPrayerTimes {
+    fn day_entry(months: &[HashMap<String, Vec<String>>], date: NaiveDate) -> Option<&Vec<String>> {
+        months.get(date.month0() as usize)?.get(&date.day().to_string())
+    }
+
+    /// Adhans for a date, from the calendar or from `times` for today
+    fn adhans(&self, date: NaiveDate, today: NaiveDate) -> Option<Vec<String>> {
+        if let Some(day) = Self::day_entry(&self.calendar, date).filter(|d| d.len() >= 6) {
+            return Some([0, 2, 3, 4, 5].iter().map(|&i| day[i].clone()).collect());
+        }
+        (date == today && self.times.len() >= 5).then(|| self.times[..5].to_vec())
+    }
+
+    fn prayer_times(self, mosque_id: &str, date: NaiveDate, today: NaiveDate) -> ProviderResult<PrayerTimes> {
+        let adhans = self.adhans(date, today).ok_or_else(|| {
+            ProviderError::Parse(format!(
+                "No prayer times for {}; calendar has {} months",
+                date,
+                self.calendar.len()
+            ))
+        })?;
+
+        let iqama_entries = Self::day_entry(&self.iqama_calendar, date)
+            .filter(|_| self.iqama_enabled.unwrap_or(true))
+            .cloned()
+            .unwrap_or_default();
+
+        let prayer = |i: usize| {
+            let adhan = &adhans[i];
+            let entry = iqama_entries.get(i);
+            let iqama = entry.and_then(|entry| resolve_iqama(adhan, entry));
+
+            Prayer {
+                name: PrayerName::DAILY[i],
+                adhan: time_on_date_local(date, adhan),
+                iqama_offset_minutes: iqama.as_ref().and(entry).and_then(|entry| iqama_offset(entry)),
+                iqama: iqama.map(|t| time_on_date_local(date, &t)),
+                custom_rakah_count: None,
+            }
+        };
+
+        let shuruq = Self::day_entry(&self.calendar, date)
+            .and_then(|day| day.get(1))
+            .or(self.shuruq.as_ref().filter(|_| date == today))
+            .map(|t| time_on_date_local(date, t));
+
+        let fajr = prayer(0);
+        let imsak: Option<DateTime<Utc>> = self
+            .imsak_minutes_before_fajr
+            .filter(|&minutes| minutes > 0)
+            .map(|minutes| fajr.adhan - Duration::minutes(minutes));
+
+        let jumuah = self
+            .jumua
+            .as_deref()
+            .filter(|_| date.weekday() == Weekday::Fri)
+            .map(|time| Prayer {
+                name: PrayerName::Jumuah,
+                adhan: time_on_date_local(date, time),
+                iqama: None,
+                custom_rakah_count: None,
+                iqama_offset_minutes: None,
+            });
+
+        Ok(PrayerTimes {
+            date: time_on_date_local(date, "00:00"),
+            fajr,
+            dhuhr: prayer(1),
+            asr: prayer(2),
+            maghrib: prayer(3),
+            isha: prayer(4),
+            jumuah,
+            mosque_id: Some(mosque_id.to_string()),
+            mosque_name: self.name,
+            cached_at: Some(Utc::now()),
+            imsak,
+            shuruq,
+            asr_method: None,
+            provider: Some(PROVIDER_OFFICIAL_API.to_string()),
+            freshness: None,
+            warnings: Vec::new(),
+        })
+    }
+}
+
+impl FlatPrayerTimes {
+    fn prayer_times(self, mosque_id: &str, date: NaiveDate) -> PrayerTimes {
+        let prayer = |name: PrayerName, adhan: &str, iqama: Option<&String>| Prayer {
+            name,
+            adhan: time_on_date_utc(date, adhan),
+            iqama: iqama.map(|t| time_on_date_utc(date, t)),
+            custom_rakah_count: None,
+            iqama_offset_minutes: None,
+        };
+
+        PrayerTimes {
+            date: time_on_date_utc(date, "00:00"),
+            fajr: prayer(PrayerName::Fajr, &self.fajr, self.fajr_iqama.as_ref()),
+            dhuhr: prayer(PrayerName::Dhuhr, &self.dhuhr, self.dhuhr_iqama.as_ref()),
+            asr: prayer(PrayerName::Asr, &self.asr, self.asr_iqama.as_ref()),
+            maghrib: prayer(PrayerName::Maghrib, &self.maghrib, self.maghrib_iqama.as_ref()),
+            isha: prayer(PrayerName::Isha, &self.isha, self.isha_iqama.as_ref()),
+            jumuah: None,
+            mosque_id: Some(mosque_id.to_string()),
+            mosque_name: None,
+            cached_at: Some(Utc::now()),
+            imsak: self.imsak.as_deref().map(|t| time_on_date_utc(date, t)),
+            shuruq: self.shuruq.as_deref().map(|t| time_on_date_utc(date, t)),
+            asr_method: None,
+            provider: Some(PROVIDER_OFFICIAL_API.to_string()),
+            freshness: None,
+            warnings: Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PRAYER_TIMES_FIXTURE: &str = include_str!("../../tests/fixtures/official_api/prayer_times.json");
+    const SEARCH_FIXTURE: &str = include_str!("../../tests/fixtures/official_api/search.json");
+
+    fn day(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_calendar_payload_fixture() {
+        let response: PrayerTimesResponse = serde_json::from_str(PRAYER_TIMES_FIXTURE).unwrap();
+        assert!(matches!(response, PrayerTimesResponse::Calendar(_)));
+
+        // 2026-01-02 is a Friday
+        let date = day(2026, 1, 2);
+        let times = response.into_prayer_times("mosquee-paris", date, day(2026, 3, 10)).unwrap();
+
+        assert_eq!(times.fajr.adhan, time_on_date_local(date, "06:57"));
+        assert_eq!(times.fajr.iqama, Some(time_on_date_local(date, "07:17")));
+        // Fixed iqama time rather than an offset
+        assert_eq!(times.isha.iqama, Some(time_on_date_local(date, "19:30")));
+        assert_eq!(times.jumuah.unwrap().adhan, time_on_date_local(date, "13:30"));
+        assert_eq!(times.imsak, Some(time_on_date_local(date, "06:47")));
+        assert_eq!(times.mosque_name.as_deref(), Some("Grande Mosquée de Paris"));
+    }
+
+    #[test]
+    fn test_calendar_payload_today_and_missing_days() {
+        let response: PrayerTimesResponse = serde_json::from_str(PRAYER_TIMES_FIXTURE).unwrap();
+
+        // Not in the calendar, but `times` covers today
+        let today = day(2026, 3, 10);
+        let times = response.clone().into_prayer_times("mosquee-paris", today, today).unwrap();
+        assert_eq!(times.asr.adhan, time_on_date_local(today, "16:35"));
+
+        assert!(response.into_prayer_times("mosquee-paris", day(2026, 3, 11), today).is_err());
+    }
+
+    #[test]
+    fn test_flat_payload_still_supported() {
+        let json = r#"{"fajr": "05:30", "fajr_iqama": "05:50", "dhuhr": "13:00", "asr": "16:00",
+            "maghrib": "19:00", "isha": "20:30"}"#;
+        let response: PrayerTimesResponse = serde_json::from_str(json).unwrap();
+        let date = day(2026, 6, 1);
+
+        let times = response.into_prayer_times("42", date, date).unwrap();
+
+        assert_eq!(times.fajr.iqama, Some(time_on_date_utc(date, "05:50")));
+        assert!(times.dhuhr.iqama.is_none());
+    }
+
+    #[test]
+    fn test_search_fixture() {
+        let data: Value = serde_json::from_str(SEARCH_FIXTURE).unwrap();
+        let mosques = parse_mosque_list(&data);
+
+        let ids: Vec<_> = mosques.iter().map(|m| m.id.as_str()).collect();
+        assert_eq!(ids, vec!["9b2f0c1e-3a4d-4e5f-8a7b-6c5d4e3f2a1b", "5d1e2c3b-8f7a-4b6c-9d0e-1f2a3b4c5d6e"]);
+        assert_eq!(mosques[0].facilities.womens_area, Some(true));
+        assert_eq!(
+            mosques[0].address.as_deref(),
+            Some("2bis Place du Puits de l'Ermite, 75005 Paris")
+        );
+    }
+}
@@ -0,0 +1,139 @@
+use async_trait::async_trait;
+use serde_json::Value;
+
+use crate::models::*;
+
+/// Error type for provider operations
+#[derive(Debug, thiserror::Error)]
+pub enum ProviderError {
+    #[error("Network error: {0}")]
+    Network(String),
+    #[error("Server error: {status_code} - {message}")]
+    Server { status_code: u16, message: String },
+    #[error("Parse error: {0}")]
+    Parse(String),
+    #[error("Not found: {0}")]
+    NotFound(String),
+    #[error("Invalid configuration: {0}")]
+    InvalidConfig(String),
+    /// Credentials missing, expired or rejected (HTTP 401)
+    #[error("Unauthorized: {0}")]
+    Unauthorized(String),
+    /// Credentials valid but not allowed to access the resource (HTTP 403)
+    #[error("Forbidden: {0}")]
+    Forbidden(String),
+    /// Too many requests (HTTP 429); `retry_after_secs` comes from the Retry-After header
+    #[error("Rate limited: {message}")]
+    RateLimited { retry_after_secs: Option<u64>, message: String },
+    #[error("Other: {0}")]
+    Other(String),
+}
+
+pub type ProviderResult<T> = Result<T, ProviderError>;
+
+impl ProviderError {
+    /// Specific error for an unsuccessful HTTP response
+    pub fn from_status(status_code: u16, retry_after_secs: Option<u64>, message: impl Into<String>) -> Self {
+        let message = message.into();
+        match status_code {
+            401 => ProviderError::Unauthorized(message),
+            403 => ProviderError::Forbidden(message),
+            429 => ProviderError::RateLimited { retry_after_secs, message },
+            404 => ProviderError::NotFound(message),
+            _ => ProviderError::Server { status_code, message },
+        }
+    }
+}
+
+/// Seconds to wait from a Retry-After header, given as seconds or as an HTTP date
+pub fn retry_after_secs(headers: &reqwest::header::HeaderMap) -> Option<u64> {
+    let value = headers.get(reqwest::header::RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(secs);
+    }
+
+    let at = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    Some((at.timestamp() - chrono::Utc::now().timestamp()).max(0) as u64)
+}
+
+/// Abstract interface for all prayer data providers
+#[async_trait]
+pub trait PrayerDataProvider: Send + Sync {
+    /// Provider unique identifier
+    fn id(&self) -> &str;
+
+    /// Provider display name
+    fn name(&self) -> &str;
+
+    /// Provider description
+    fn description(&self) -> &str;
+
+    /// Configuration schema for settings UI
+    fn config_schema(&self) -> Vec<ConfigField>;
+
+    /// Initialize provider with configuration
+    async fn initialize(&mut self, config: Value) -> ProviderResult<()>;
+
+    /// Search mosques by query string
+    async fn search_mosques(&self, query: &str, location: Option<&GeoLocation>) -> ProviderResult<Vec<Mosque>>;
+
+    /// One page of a filtered search; providers without server-side paging filter and page locally
+    async fn search_mosques_page(
+        &self,
+        query: &str,
+        location: Option<&GeoLocation>,
+        params: &MosqueSearchParams,
+    ) -> ProviderResult<MosqueSearchResult> {
+        Ok(params.paginate(self.search_mosques(query, location).await?))
+    }
+
+    /// Get mosques near a location
+    async fn get_nearby_mosques(&self, location: &GeoLocation, radius_km: f64) -> ProviderResult<Vec<Mosque>>;
+
+    /// Fetch prayer times for a specific mosque
+    async fn get_prayer_times(&self, mosque_id: &str, date: Option<chrono::NaiveDate>) -> ProviderResult<PrayerTimes>;
+
+    /// Test connectivity with current configuration
+    async fn test_connection(&self) -> ProviderResult<ProviderTestResult>;
+
+    /// Get mosque details
+    async fn get_mosque_details(&self, mosque_id: &str) -> ProviderResult<Mosque>;
+
+    /// Lectures, iftars and classes announced by the mosque; most providers have none
+    async fn get_events(&self, _mosque_id: &str) -> ProviderResult<Vec<MosqueEvent>> {
+        Ok(Vec::new())
+    }
+}
+
+/// Provider factory
+pub struct ProviderFactory;
+
+impl ProviderFactory {
+    pub fn create_official_api() -> Box<dyn PrayerDataProvider> {
+        Box::new(crate::providers::OfficialApiProvider::new())
+    }
+
+    pub fn create_community_wrapper() -> Box<dyn PrayerDataProvider> {
+        Box::new(crate::providers::CommunityWrapperProvider::new())
+    }
+
+    pub fn create_scraping() -> Box<dyn PrayerDataProvider> {
+        Box::new(crate::providers::ScrapingProvider::new())
+    }
+
+    pub fn create_islamicfinder() -> Box<dyn PrayerDataProvider> {
+        Box::new(crate::providers::IslamicFinderProvider::new())
+    }
+
+    pub fn create_diyanet() -> Box<dyn PrayerDataProvider> {
+        Box::new(crate::providers::DiyanetProvider::new())
+    }
+
+    pub fn create_masjid_timetable() -> Box<dyn PrayerDataProvider> {
+        Box::new(crate::providers::MasjidTimetableProvider::new())
+    }
+
+    pub fn create_mock() -> Box<dyn PrayerDataProvider> {
+        Box::new(crate::providers::MockProvider::new())
+    }
+}
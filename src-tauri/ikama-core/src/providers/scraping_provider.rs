@@ -1,11 +1,29 @@
 use async_trait::async_trait;
 use chrono::{NaiveDate, Utc};
 use reqwest::Client;
-use scraper::{Html, Selector};
+use scraper::{ElementRef, Html, Selector};
 use serde_json::Value;
 
 use crate::models::*;
+use crate::providers::time_utils::{find_time_in_text, time_on_date_utc};
 use crate::providers::{PrayerDataProvider, ProviderError, ProviderResult};
+use crate::services::{mosque_context, record_payload, report_parse_failure, report_parse_success};
+
+/// Times of elements labelled with a prayer, via `data-prayer` or a name in their text ("Zuhr 12:31")
+fn labelled_times(elements: &[ElementRef]) -> Vec<(PrayerName, String)> {
+    elements
+        .iter()
+        .filter_map(|element| {
+            let text = element.text().collect::<String>();
+            let name = element
+                .value()
+                .attr("data-prayer")
+                .and_then(PrayerName::normalize)
+                .or_else(|| text.split_whitespace().find_map(PrayerName::normalize))?;
+            Some((name, find_time_in_text(&text)?))
+        })
+        .collect()
+}
 
 /// HTML Scraping Provider (Provider C)
 /// Fallback that scrapes prayer times from mosque websites
@@ -30,16 +48,6 @@ impl ScrapingProvider {
     async fn rate_limit(&self) {
         tokio::time::sleep(tokio::time::Duration::from_millis(self.rate_limit_delay_ms)).await;
     }
-
-    fn parse_time_from_text(&self, text: &str) -> Option<String> {
-        // Try to find time patterns like "05:30", "5:30 AM", "17:30", etc.
-        let re = regex::Regex::new(r"(\d{1,2}):(\d{2})\s*(AM|PM|am|pm)?").ok()?;
-        re.captures(text).map(|cap| {
-            let hour = cap.get(1)?.as_str();
-            let minute = cap.get(2)?.as_str();
-            Some(format!("{}:{}", hour, minute))
-        })?
-    }
 }
 
 #[async_trait]
@@ -88,7 +96,7 @@ impl PrayerDataProvider for ScrapingProvider {
         ))
     }
 
-    async fn get_prayer_times(&self, _mosque_id: &str, date: Option<NaiveDate>) -> ProviderResult<PrayerTimes> {
+    async fn get_prayer_times(&self, mosque_id: &str, date: Option<NaiveDate>) -> ProviderResult<PrayerTimes> {
         let base_url = self.base_url.as_ref().ok_or_else(|| {
             ProviderError::InvalidConfig("Base URL not configured".to_string())
         })?;
@@ -117,13 +125,14 @@ impl PrayerDataProvider for ScrapingProvider {
         let html = response.text().await.map_err(|e| {
             ProviderError::Parse(format!("Failed to read response: {}", e))
         })?;
+        record_payload(PROVIDER_SCRAPING, mosque_id, &url, html.as_bytes());
 
         let document = Html::parse_document(&html);
 
         // Try to find prayer times in the HTML
         // This is a generic implementation - specific selectors would depend on the site structure
-        let prayer_names = ["Fajr", "Dhuhr", "Asr", "Maghrib", "Isha"];
-        let mut prayers: Vec<(String, Option<String>)> = Vec::new();
+        let prayer_names = PrayerName::DAILY;
+        let mut prayers: Vec<(PrayerName, Option<String>)> = Vec::new();
 
         // Try common CSS selectors for prayer times
         let selectors_to_try = [
@@ -138,10 +147,19 @@ impl PrayerDataProvider for ScrapingProvider {
             if let Ok(selector) = Selector::parse(selector_str) {
                 let elements: Vec<_> = document.select(&selector).collect();
                 if elements.len() >= 5 {
-                    for (i, element) in elements.iter().enumerate().take(5) {
-                        let text = element.text().collect::<String>();
-                        if let Some(time) = self.parse_time_from_text(&text) {
-                            prayers.push((prayer_names[i].to_string(), Some(time)));
+                    let labelled = labelled_times(&elements);
+                    if prayer_names.iter().all(|name| labelled.iter().any(|(n, _)| n == name)) {
+                        // Elements name their prayer, so their order doesn't matter
+                        for name in prayer_names {
+                            let time = labelled.iter().find(|(n, _)| *n == name).map(|(_, t)| t.clone());
+                            prayers.push((name, time));
+                        }
+                    } else {
+                        for (i, element) in elements.iter().enumerate().take(5) {
+                            let text = element.text().collect::<String>();
+                            if let Some(time) = find_time_in_text(&text) {
+                                prayers.push((prayer_names[i], Some(time)));
+                            }
                         }
                     }
                     break;
@@ -149,58 +167,65 @@ impl PrayerDataProvider for ScrapingProvider {
             }
         }
 
+        let context = mosque_context("prayer_table", mosque_id);
         if prayers.len() < 5 {
+            report_parse_failure(PROVIDER_SCRAPING, &context, "Could not find prayer times in page", false);
             return Err(ProviderError::Parse(
                 "Could not find prayer times in page".to_string(),
             ));
         }
+        report_parse_success(PROVIDER_SCRAPING, &context);
 
         let date = date.unwrap_or_else(|| Utc::now().date_naive());
-        let base_date = date.and_hms_opt(0, 0, 0).unwrap().and_local_timezone(Utc).unwrap();
-
-        fn parse_time(date: chrono::DateTime<Utc>, time_str: &str) -> chrono::DateTime<Utc> {
-            let parts: Vec<&str> = time_str.split(':').collect();
-            let hour: u32 = parts.get(0).unwrap_or(&"0").parse().unwrap_or(0);
-            let minute: u32 = parts.get(1).unwrap_or(&"0").parse().unwrap_or(0);
-            date.date_naive().and_hms_opt(hour, minute, 0).unwrap().and_local_timezone(Utc).unwrap()
-        }
+        let base_date = time_on_date_utc(date, "00:00");
 
         Ok(PrayerTimes {
             date: base_date,
             fajr: Prayer {
-                name: "Fajr".to_string(),
-                adhan: parse_time(base_date, prayers[0].1.as_deref().unwrap_or("05:00")),
+                name: PrayerName::Fajr,
+                adhan: time_on_date_utc(date, prayers[0].1.as_deref().unwrap_or("05:00")),
                 iqama: None,
                 custom_rakah_count: None,
+                iqama_offset_minutes: None,
             },
             dhuhr: Prayer {
-                name: "Dhuhr".to_string(),
-                adhan: parse_time(base_date, prayers[1].1.as_deref().unwrap_or("12:00")),
+                name: PrayerName::Dhuhr,
+                adhan: time_on_date_utc(date, prayers[1].1.as_deref().unwrap_or("12:00")),
                 iqama: None,
                 custom_rakah_count: None,
+                iqama_offset_minutes: None,
             },
             asr: Prayer {
-                name: "Asr".to_string(),
-                adhan: parse_time(base_date, prayers[2].1.as_deref().unwrap_or("15:00")),
+                name: PrayerName::Asr,
+                adhan: time_on_date_utc(date, prayers[2].1.as_deref().unwrap_or("15:00")),
                 iqama: None,
                 custom_rakah_count: None,
+                iqama_offset_minutes: None,
             },
             maghrib: Prayer {
-                name: "Maghrib".to_string(),
-                adhan: parse_time(base_date, prayers[3].1.as_deref().unwrap_or("18:00")),
+                name: PrayerName::Maghrib,
+                adhan: time_on_date_utc(date, prayers[3].1.as_deref().unwrap_or("18:00")),
                 iqama: None,
                 custom_rakah_count: None,
+                iqama_offset_minutes: None,
             },
             isha: Prayer {
-                name: "Isha".to_string(),
-                adhan: parse_time(base_date, prayers[4].1.as_deref().unwrap_or("19:30")),
+                name: PrayerName::Isha,
+                adhan: time_on_date_utc(date, prayers[4].1.as_deref().unwrap_or("19:30")),
                 iqama: None,
                 custom_rakah_count: None,
+                iqama_offset_minutes: None,
             },
             jumuah: None,
             mosque_id: Some("scraped".to_string()),
             mosque_name: None,
             cached_at: Some(Utc::now()),
+            imsak: None,
+            shuruq: None,
+            asr_method: None,
+            provider: Some(PROVIDER_SCRAPING.to_string()),
+            freshness: None,
+            warnings: Vec::new(),
         })
     }
 
@@ -0,0 +1,112 @@
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, Utc};
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use crate::services::clock::resolve_local;
+
+/// Matches times like "05:30", "5:30 AM", "17:30"
+static TIME_IN_TEXT: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(\d{1,2}):(\d{2})\s*(AM|PM|am|pm)?").unwrap());
+
+/// Parse "HH:MM" into hour and minute; missing or invalid parts default to 0
+pub fn parse_hh_mm(time_str: &str) -> (u32, u32) {
+    let mut parts = time_str.trim().split(':');
+    let hour = parts.next().and_then(|s| s.trim().parse().ok()).unwrap_or(0);
+    let minute = parts.next().and_then(|s| s.trim().parse().ok()).unwrap_or(0);
+    (hour, minute)
+}
+
+fn naive_time_on_date(date: NaiveDate, time_str: &str) -> NaiveDateTime {
+    let (hour, minute) = parse_hh_mm(time_str);
+    date.and_hms_opt(hour, minute, 0)
+        .unwrap_or_else(|| date.and_hms_opt(0, 0, 0).unwrap())
+}
+
+/// Combine a date and "HH:MM" wall-clock time, keeping the wall-clock value as UTC
+pub fn time_on_date_utc(date: NaiveDate, time_str: &str) -> DateTime<Utc> {
+    naive_time_on_date(date, time_str).and_utc()
+}
+
+/// Combine a date and "HH:MM" wall-clock time in the local timezone, converted to UTC
+/// This preserves the exact hour:minute as shown by the mosque, including on DST change nights
+pub fn time_on_date_local(date: NaiveDate, time_str: &str) -> DateTime<Utc> {
+    resolve_local(&Local, naive_time_on_date(date, time_str))
+}
+
+/// Find the first time in free text, normalized to 24-hour "HH:MM"
+pub fn find_time_in_text(text: &str) -> Option<String> {
+    let cap = TIME_IN_TEXT.captures(text)?;
+    let mut hour: u32 = cap.get(1)?.as_str().parse().ok()?;
+    let minute = cap.get(2)?.as_str();
+
+    match cap.get(3).map(|m| m.as_str().to_lowercase()) {
+        Some(ref suffix) if suffix == "pm" && hour < 12 => hour += 12,
+        Some(ref suffix) if suffix == "am" && hour == 12 => hour = 0,
+        _ => {}
+    }
+
+    Some(format!("{:02}:{}", hour, minute))
+}
+
+/// Add an offset like "+15" (minutes) to an "HH:MM" time, wrapping past midnight
+pub fn add_minutes(time_str: &str, offset_str: &str) -> Option<String> {
+    let offset_minutes: i32 = offset_str.trim().trim_start_matches('+').parse().ok()?;
+
+    let mut parts = time_str.split(':');
+    let hour: i32 = parts.next()?.trim().parse().ok()?;
+    let minute: i32 = parts.next()?.trim().parse().ok()?;
+
+    let total_minutes = (hour * 60 + minute + offset_minutes).rem_euclid(24 * 60);
+
+    Some(format!("{:02}:{:02}", total_minutes / 60, total_minutes % 60))
+}
+
+/// Minutes of an iqama entry published as an offset ("+15"); `None` for a fixed time ("20:30")
+pub fn iqama_offset(entry: &str) -> Option<i32> {
+    let entry = entry.trim();
+    if entry.contains(':') {
+        return None;
+    }
+    entry.trim_start_matches('+').parse().ok()
+}
+
+/// "HH:MM" iqama for an entry that is either an offset from `adhan` or a fixed time
+pub fn resolve_iqama(adhan: &str, entry: &str) -> Option<String> {
+    if entry.contains(':') {
+        Some(entry.trim().to_string())
+    } else {
+        add_minutes(adhan, entry)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_time_in_text_normalizes_12_hour() {
+        assert_eq!(find_time_in_text("Fajr 5:30 AM").as_deref(), Some("05:30"));
+        assert_eq!(find_time_in_text("Maghrib 6:45 pm").as_deref(), Some("18:45"));
+        assert_eq!(find_time_in_text("12:10 AM").as_deref(), Some("00:10"));
+        assert_eq!(find_time_in_text("Isha 19:30").as_deref(), Some("19:30"));
+        assert_eq!(find_time_in_text("no time here"), None);
+    }
+
+    #[test]
+    fn test_add_minutes_wraps_midnight() {
+        assert_eq!(add_minutes("05:50", "+15").as_deref(), Some("06:05"));
+        assert_eq!(add_minutes("23:50", "+20").as_deref(), Some("00:10"));
+        assert_eq!(resolve_iqama("20:10", "20:30").as_deref(), Some("20:30"));
+        assert_eq!(iqama_offset("+15"), Some(15));
+        assert_eq!(iqama_offset(" 20:30"), None);
+        assert_eq!(add_minutes("12:00", "abc"), None);
+    }
+
+    #[test]
+    fn test_time_on_date_utc() {
+        let date = NaiveDate::from_ymd_opt(2026, 2, 5).unwrap();
+        let time = time_on_date_utc(date, "13:07");
+
+        assert_eq!(time.to_rfc3339(), "2026-02-05T13:07:00+00:00");
+    }
+}
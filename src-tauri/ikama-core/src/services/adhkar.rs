@@ -0,0 +1,279 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use crate::models::PrayerName;
+
+/// One counted dhikr, e.g. 33 x SubhanAllah
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct AdhkarStep {
+    pub label: String,
+    pub target: u32,
+}
+
+/// Post-prayer adhkar settings, stored in the `adhkar_config` setting
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export, optional_fields)]
+pub struct AdhkarConfig {
+    pub enabled: bool,
+    /// Length of the adhkar window after the prayer ends
+    #[ts(type = "number")]
+    pub duration_minutes: i64,
+    /// Nudge if the counter isn't finished this many minutes into the window
+    #[ts(as = "Option<f64>")]
+    pub reminder_after_minutes: Option<i64>,
+    pub steps: Vec<AdhkarStep>,
+}
+
+impl Default for AdhkarConfig {
+    fn default() -> Self {
+        let step = |label: &str, target| AdhkarStep {
+            label: label.to_string(),
+            target,
+        };
+
+        Self {
+            enabled: true,
+            duration_minutes: 10,
+            reminder_after_minutes: Some(5),
+            steps: vec![
+                step("SubhanAllah", 33),
+                step("Alhamdulillah", 33),
+                step("Allahu Akbar", 34),
+            ],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum AdhkarPhase {
+    Idle,
+    /// Attendance marked, waiting for the prayer to finish
+    Waiting,
+    Active,
+    Completed,
+    Expired,
+}
+
+/// Transitions emitted by the adhkar timer
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[ts(export)]
+pub enum AdhkarEvent {
+    Started { prayer: PrayerName },
+    StepCompleted { prayer: PrayerName, step_index: u32 },
+    Reminder { prayer: PrayerName },
+    Completed { prayer: PrayerName },
+    Expired { prayer: PrayerName },
+}
+
+/// Snapshot of the adhkar timer for the UI
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export, optional_fields)]
+pub struct AdhkarStatus {
+    pub phase: AdhkarPhase,
+    pub prayer: Option<PrayerName>,
+    pub starts_at: Option<DateTime<Utc>>,
+    pub ends_at: Option<DateTime<Utc>>,
+    #[ts(as = "Option<f64>")]
+    pub remaining_secs: Option<i64>,
+    pub step_index: u32,
+    pub step_count: u32,
+    pub steps: Vec<AdhkarStep>,
+}
+
+#[derive(Debug, Clone)]
+struct Session {
+    prayer: PrayerName,
+    starts_at: DateTime<Utc>,
+    ends_at: DateTime<Utc>,
+    step_index: usize,
+    step_count: u32,
+    reminded: bool,
+}
+
+/// State machine for the post-prayer adhkar window
+/// Idle -> Waiting (attendance marked) -> Active (prayer finished) -> Completed | Expired
+#[derive(Debug, Clone, Default)]
+pub struct AdhkarTimer {
+    config: AdhkarConfig,
+    phase: Option<AdhkarPhase>,
+    session: Option<Session>,
+    events: Vec<AdhkarEvent>,
+}
+
+impl AdhkarTimer {
+    pub fn new(config: AdhkarConfig) -> Self {
+        Self {
+            config,
+            ..Self::default()
+        }
+    }
+
+    pub fn set_config(&mut self, config: AdhkarConfig) {
+        self.config = config;
+    }
+
+    pub fn phase(&self) -> AdhkarPhase {
+        self.phase.unwrap_or(AdhkarPhase::Idle)
+    }
+
+    /// The user is attending `prayer`; adhkar start once it ends (or now if it already has)
+    pub fn attend(&mut self, prayer: PrayerName, prayer_end: DateTime<Utc>, now: DateTime<Utc>) {
+        if !self.config.enabled || self.config.steps.is_empty() {
+            self.stop();
+            return;
+        }
+
+        let starts_at = prayer_end.max(now);
+        self.session = Some(Session {
+            prayer,
+            starts_at,
+            ends_at: starts_at + Duration::minutes(self.config.duration_minutes),
+            step_index: 0,
+            step_count: 0,
+            reminded: false,
+        });
+        self.phase = Some(AdhkarPhase::Waiting);
+        self.tick(now);
+    }
+
+    /// Advance time-based transitions
+    pub fn tick(&mut self, now: DateTime<Utc>) {
+        let Some(session) = self.session.as_mut() else {
+            return;
+        };
+        let prayer = session.prayer;
+
+        if self.phase == Some(AdhkarPhase::Waiting) && now >= session.starts_at {
+            self.phase = Some(AdhkarPhase::Active);
+            self.events.push(AdhkarEvent::Started { prayer });
+        }
+
+        if self.phase != Some(AdhkarPhase::Active) {
+            return;
+        }
+
+        if now >= session.ends_at {
+            self.phase = Some(AdhkarPhase::Expired);
+            self.events.push(AdhkarEvent::Expired { prayer });
+        } else if let Some(after) = self.config.reminder_after_minutes {
+            if !session.reminded && now >= session.starts_at + Duration::minutes(after) {
+                session.reminded = true;
+                self.events.push(AdhkarEvent::Reminder { prayer });
+            }
+        }
+    }
+
+    /// Count one dhikr; moves to the next step once the current target is reached
+    pub fn increment(&mut self, now: DateTime<Utc>) {
+        self.tick(now);
+        if self.phase != Some(AdhkarPhase::Active) {
+            return;
+        }
+        let Some(session) = self.session.as_mut() else {
+            return;
+        };
+
+        session.step_count += 1;
+        if session.step_count < self.config.steps[session.step_index].target {
+            return;
+        }
+
+        self.events.push(AdhkarEvent::StepCompleted {
+            prayer: session.prayer,
+            step_index: session.step_index as u32,
+        });
+        session.step_index += 1;
+        session.step_count = 0;
+
+        if session.step_index >= self.config.steps.len() {
+            self.phase = Some(AdhkarPhase::Completed);
+            self.events.push(AdhkarEvent::Completed { prayer: session.prayer });
+        }
+    }
+
+    pub fn stop(&mut self) {
+        self.phase = Some(AdhkarPhase::Idle);
+        self.session = None;
+    }
+
+    /// Events produced since the last call
+    pub fn drain_events(&mut self) -> Vec<AdhkarEvent> {
+        std::mem::take(&mut self.events)
+    }
+
+    pub fn status(&self, now: DateTime<Utc>) -> AdhkarStatus {
+        let session = self.session.as_ref();
+
+        AdhkarStatus {
+            phase: self.phase(),
+            prayer: session.map(|s| s.prayer),
+            starts_at: session.map(|s| s.starts_at),
+            ends_at: session.map(|s| s.ends_at),
+            remaining_secs: session
+                .filter(|_| self.phase() == AdhkarPhase::Active)
+                .map(|s| (s.ends_at - now).num_seconds().max(0)),
+            step_index: session.map(|s| s.step_index as u32).unwrap_or(0),
+            step_count: session.map(|s| s.step_count).unwrap_or(0),
+            steps: self.config.steps.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_adhkar_session_lifecycle() {
+        let end = Utc::now();
+        let mut timer = AdhkarTimer::new(AdhkarConfig {
+            steps: vec![AdhkarStep { label: "SubhanAllah".into(), target: 2 }],
+            ..AdhkarConfig::default()
+        });
+
+        timer.attend(PrayerName::Asr, end, end - Duration::minutes(5));
+        assert_eq!(timer.phase(), AdhkarPhase::Waiting);
+
+        timer.tick(end);
+        timer.increment(end + Duration::seconds(10));
+        timer.increment(end + Duration::seconds(20));
+
+        assert_eq!(timer.phase(), AdhkarPhase::Completed);
+        assert_eq!(
+            timer.drain_events(),
+            vec![
+                AdhkarEvent::Started { prayer: PrayerName::Asr },
+                AdhkarEvent::StepCompleted { prayer: PrayerName::Asr, step_index: 0 },
+                AdhkarEvent::Completed { prayer: PrayerName::Asr },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_adhkar_reminder_and_expiry() {
+        let now = Utc::now();
+        let mut timer = AdhkarTimer::new(AdhkarConfig::default());
+
+        // Marked after the prayer ended: starts immediately
+        timer.attend(PrayerName::Isha, now - Duration::minutes(2), now);
+        assert_eq!(timer.phase(), AdhkarPhase::Active);
+
+        timer.tick(now + Duration::minutes(6));
+        timer.tick(now + Duration::minutes(11));
+
+        assert_eq!(timer.phase(), AdhkarPhase::Expired);
+        assert_eq!(
+            timer.drain_events(),
+            vec![
+                AdhkarEvent::Started { prayer: PrayerName::Isha },
+                AdhkarEvent::Reminder { prayer: PrayerName::Isha },
+                AdhkarEvent::Expired { prayer: PrayerName::Isha },
+            ]
+        );
+    }
+}
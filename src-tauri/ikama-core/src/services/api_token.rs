@@ -0,0 +1,105 @@
+use base64::Engine;
+use sha2::{Digest, Sha256};
+
+use crate::models::{ApiScope, StoredApiToken};
+
+/// Prefix of every token secret, so a leaked one is easy to recognise
+pub const TOKEN_PREFIX: &str = "iqm_";
+
+/// Random bytes in a token secret
+const SECRET_BYTES: usize = 32;
+
+/// Outcome of checking a request's token against the saved ones
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ApiAccess {
+    Granted,
+    /// No token, or one that doesn't exist (any more)
+    Unauthorized,
+    /// A valid token without the scope the request needs
+    Forbidden,
+}
+
+/// A new token secret from the operating system's random source
+pub fn generate_secret() -> Result<String, getrandom::Error> {
+    let mut bytes = [0u8; SECRET_BYTES];
+    getrandom::getrandom(&mut bytes)?;
+    Ok(format!(
+        "{}{}",
+        TOKEN_PREFIX,
+        base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(bytes)
+    ))
+}
+
+/// Hex SHA-256 of a secret, the only form tokens are saved in
+pub fn hash_secret(secret: &str) -> String {
+    Sha256::digest(secret.trim().as_bytes())
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// Check a presented secret for `scope`
+/// Until the first token is created the server reads stay open to this computer, so existing
+/// calendar subscriptions keep working; controlling notifications, and anything at all once
+/// the server listens on the network (`allow_lan`), always takes a token
+pub fn authorize(tokens: &[StoredApiToken], presented: Option<&str>, scope: ApiScope, allow_lan: bool) -> ApiAccess {
+    if tokens.is_empty() && !allow_lan && scope != ApiScope::ControlNotifications {
+        return ApiAccess::Granted;
+    }
+    let Some(secret) = presented.filter(|s| !s.is_empty()) else {
+        return ApiAccess::Unauthorized;
+    };
+
+    let hash = hash_secret(secret);
+    match tokens.iter().find(|stored| stored.secret_hash == hash) {
+        Some(stored) if stored.token.allows(scope) => ApiAccess::Granted,
+        Some(_) => ApiAccess::Forbidden,
+        None => ApiAccess::Unauthorized,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ApiToken;
+
+    fn stored(secret: &str, scopes: Vec<ApiScope>) -> StoredApiToken {
+        StoredApiToken {
+            token: ApiToken {
+                id: "t1".to_string(),
+                name: "Hallway tablet".to_string(),
+                scopes,
+                created_at: chrono::Utc::now(),
+            },
+            secret_hash: hash_secret(secret),
+        }
+    }
+
+    #[test]
+    fn test_generate_secret() {
+        let secret = generate_secret().unwrap();
+        assert!(secret.starts_with(TOKEN_PREFIX));
+        assert_eq!(secret.len(), TOKEN_PREFIX.len() + 43);
+        assert_ne!(secret, generate_secret().unwrap());
+        assert_eq!(hash_secret(&secret).len(), 64);
+    }
+
+    #[test]
+    fn test_authorize_scopes() {
+        // No tokens yet: reads stay open, control doesn't
+        assert_eq!(authorize(&[], None, ApiScope::ReadTimes, false), ApiAccess::Granted);
+        assert_eq!(authorize(&[], None, ApiScope::ControlNotifications, false), ApiAccess::Unauthorized);
+        // Unless other devices can connect
+        assert_eq!(authorize(&[], None, ApiScope::ReadTimes, true), ApiAccess::Unauthorized);
+
+        let tokens = [stored("iqm_tablet", vec![ApiScope::ReadTimes])];
+        assert_eq!(authorize(&tokens, Some("iqm_tablet"), ApiScope::ReadTimes, false), ApiAccess::Granted);
+        assert_eq!(authorize(&tokens, Some("iqm_tablet"), ApiScope::ReadTimes, true), ApiAccess::Granted);
+        assert_eq!(
+            authorize(&tokens, Some("iqm_tablet"), ApiScope::ControlNotifications, false),
+            ApiAccess::Forbidden
+        );
+        assert_eq!(authorize(&tokens, Some("iqm_other"), ApiScope::ReadTimes, false), ApiAccess::Unauthorized);
+        assert_eq!(authorize(&tokens, None, ApiScope::ReadTimes, false), ApiAccess::Unauthorized);
+    }
+}
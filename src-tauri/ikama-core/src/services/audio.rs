@@ -0,0 +1,321 @@
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+use std::sync::Mutex;
+
+use once_cell::sync::Lazy;
+
+use crate::models::{AdhanSound, AudioOutputDevice};
+
+/// File types picked up from the user's sounds folder
+const SOUND_EXTENSIONS: [&str; 6] = ["mp3", "wav", "ogg", "m4a", "aac", "flac"];
+
+/// Folder users drop their own adhan recordings into
+pub fn user_sounds_dir() -> Option<PathBuf> {
+    crate::db::location::app_dir().map(|dir| dir.join("sounds"))
+}
+
+/// Sound files in `dir`, by name; a missing folder has none
+pub fn scan_user_sounds(dir: &Path) -> Vec<AdhanSound> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut sounds: Vec<AdhanSound> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.is_file()
+                && path
+                    .extension()
+                    .and_then(|ext| ext.to_str())
+                    .is_some_and(|ext| SOUND_EXTENSIONS.contains(&ext.to_lowercase().as_str()))
+        })
+        .filter_map(|path| {
+            let file = path.file_name()?.to_string_lossy().to_string();
+            let name = path.file_stem()?.to_string_lossy().to_string();
+            Some(AdhanSound {
+                id: format!("user:{}", file),
+                name,
+                path: path.to_string_lossy().to_string(),
+                available: true,
+            })
+        })
+        .collect();
+    sounds.sort_by_key(|s| s.name.to_lowercase());
+    sounds
+}
+
+/// Where and how loud to play, as set in the adhan sound settings
+#[derive(Debug, Clone, Default)]
+pub struct PlaybackOptions {
+    /// Output device id; the system default when unset
+    pub device: Option<String>,
+    /// Percent other applications are turned down to while the sound plays; no ducking when unset
+    pub duck_percent: Option<u32>,
+}
+
+/// Output devices the adhan can be sent to
+/// Only PulseAudio and PipeWire (Linux) let one application pick its output; empty elsewhere
+pub fn list_output_devices() -> std::io::Result<Vec<AudioOutputDevice>> {
+    if cfg!(any(target_os = "macos", target_os = "windows")) {
+        return Ok(Vec::new());
+    }
+
+    // Without pactl there is no PulseAudio or PipeWire to pick an output on
+    let (sinks, default) = match pactl_list_sinks() {
+        Ok(outputs) => outputs,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+    Ok(parse_pactl_sinks(
+        &String::from_utf8_lossy(&sinks.stdout),
+        String::from_utf8_lossy(&default.stdout).trim(),
+    ))
+}
+
+fn pactl_list_sinks() -> std::io::Result<(Output, Output)> {
+    let sinks = Command::new("pactl").args(["list", "sinks"]).output()?;
+    let default = Command::new("pactl").arg("get-default-sink").output()?;
+    Ok((sinks, default))
+}
+
+/// Devices from `pactl list sinks`, named by their description
+pub fn parse_pactl_sinks(output: &str, default_sink: &str) -> Vec<AudioOutputDevice> {
+    let mut devices: Vec<AudioOutputDevice> = Vec::new();
+    for line in output.lines().map(str::trim) {
+        if let Some(id) = line.strip_prefix("Name:") {
+            let id = id.trim().to_string();
+            devices.push(AudioOutputDevice {
+                name: id.clone(),
+                is_default: id == default_sink,
+                id,
+            });
+        } else if let Some(description) = line.strip_prefix("Description:") {
+            if let Some(device) = devices.last_mut() {
+                device.name = description.trim().to_string();
+            }
+        }
+    }
+    devices
+}
+
+/// Another application's audio stream, with the volume to give back after the adhan
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DuckedStream {
+    pub id: u32,
+    pub volume_percent: u32,
+}
+
+/// Streams from `pactl list sink-inputs`, with the volume of their first channel
+pub fn parse_pactl_sink_inputs(output: &str) -> Vec<DuckedStream> {
+    let mut streams = Vec::new();
+    let mut current = None;
+    for line in output.lines().map(str::trim) {
+        if let Some(id) = line.strip_prefix("Sink Input #") {
+            current = id.trim().parse().ok();
+        } else if let (Some(id), Some(volume)) = (current, line.strip_prefix("Volume:")) {
+            // "front-left: 65536 / 100% / 0.00 dB, ..."
+            let percent = volume
+                .split('/')
+                .nth(1)
+                .and_then(|p| p.trim().trim_end_matches('%').parse().ok());
+            if let Some(volume_percent) = percent {
+                streams.push(DuckedStream { id, volume_percent });
+            }
+            current = None;
+        }
+    }
+    streams
+}
+
+/// Turn every other application's stream down to `percent` of its volume; streams that can't be
+/// read or set are left alone
+fn duck_streams(percent: u32) -> Vec<DuckedStream> {
+    if cfg!(any(target_os = "macos", target_os = "windows")) {
+        return Vec::new();
+    }
+    let Ok(output) = Command::new("pactl").args(["list", "sink-inputs"]).output() else {
+        return Vec::new();
+    };
+
+    parse_pactl_sink_inputs(&String::from_utf8_lossy(&output.stdout))
+        .into_iter()
+        .filter(|stream| set_stream_volume(stream.id, stream.volume_percent * percent / 100))
+        .collect()
+}
+
+fn set_stream_volume(id: u32, percent: u32) -> bool {
+    Command::new("pactl")
+        .args(["set-sink-input-volume", &id.to_string(), &format!("{}%", percent)])
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+fn restore_streams(streams: &[DuckedStream]) {
+    for stream in streams {
+        set_stream_volume(stream.id, stream.volume_percent);
+    }
+}
+
+/// Other applications stay turned down while any sound plays; only the last one to end restores them
+/// Overlapping sounds would otherwise save each other's lowered volumes as the ones to restore
+#[derive(Debug, Default)]
+pub struct Ducking {
+    playing: usize,
+    streams: Vec<DuckedStream>,
+}
+
+impl Ducking {
+    /// A sound starts: the first one ducks with `duck`, later ones share its streams
+    pub fn acquire(&mut self, duck: impl FnOnce() -> Vec<DuckedStream>) {
+        if self.playing == 0 {
+            self.streams = duck();
+        }
+        self.playing += 1;
+    }
+
+    /// A sound ended; the streams to give their volume back once none is left playing
+    pub fn release(&mut self) -> Option<Vec<DuckedStream>> {
+        self.playing = self.playing.saturating_sub(1);
+        (self.playing == 0).then(|| std::mem::take(&mut self.streams))
+    }
+}
+
+// Ducking shared by every sound playing, whatever started it
+static DUCKING: Lazy<Mutex<Ducking>> = Lazy::new(|| Mutex::new(Ducking::default()));
+
+fn start_ducking(percent: u32) {
+    DUCKING.lock().unwrap_or_else(|e| e.into_inner()).acquire(|| duck_streams(percent));
+}
+
+fn stop_ducking() {
+    let restore = DUCKING.lock().unwrap_or_else(|e| e.into_inner()).release();
+    if let Some(streams) = restore {
+        restore_streams(&streams);
+    }
+}
+
+/// The platform's audio player: `afplay` on macOS, Windows Media Player's engine on Windows,
+/// GStreamer (which the webview already needs) elsewhere, on the chosen PulseAudio/PipeWire sink
+/// The path goes through an environment variable on Windows so it is never parsed as a script
+pub fn play_command(path: &Path, device: Option<&str>) -> Command {
+    let mut command;
+    if cfg!(target_os = "macos") {
+        command = Command::new("afplay");
+        command.arg(path);
+    } else if cfg!(target_os = "windows") {
+        command = Command::new("powershell");
+        command.args([
+            "-NoProfile",
+            "-NonInteractive",
+            "-Command",
+            "Add-Type -AssemblyName PresentationCore; \
+             $p = New-Object System.Windows.Media.MediaPlayer; \
+             $p.Open([Uri]$env:IQAMAH_SOUND_PATH); \
+             while (-not $p.NaturalDuration.HasTimeSpan) { Start-Sleep -Milliseconds 100 }; \
+             $p.Play(); \
+             Start-Sleep -Seconds ([Math]::Ceiling($p.NaturalDuration.TimeSpan.TotalSeconds))",
+        ]);
+        command.env("IQAMAH_SOUND_PATH", path);
+    } else {
+        command = Command::new("gst-play-1.0");
+        command.args(["--no-interactive", "-q"]).arg(path);
+        if let Some(device) = device {
+            command.env("PULSE_SINK", device);
+        }
+    }
+    command
+}
+
+/// Start playing in the background, ducking other applications until the sound is over
+/// Errors when the file or a player is missing; blocks on pactl, so async callers use `spawn_blocking`
+pub fn play_sound(path: &Path, options: &PlaybackOptions) -> std::io::Result<()> {
+    if !path.is_file() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            format!("No sound file at {}", path.display()),
+        ));
+    }
+    // Ducked before the player starts, so its own stream isn't turned down
+    let ducks = options.duck_percent.is_some();
+    if let Some(percent) = options.duck_percent {
+        start_ducking(percent);
+    }
+    let mut child = match play_command(path, options.device.as_deref()).spawn() {
+        Ok(child) => child,
+        Err(e) => {
+            if ducks {
+                stop_ducking();
+            }
+            return Err(e);
+        }
+    };
+    // Reap the process and bring the other applications back once the last sound is over
+    std::thread::spawn(move || {
+        let _ = child.wait();
+        if ducks {
+            stop_ducking();
+        }
+    });
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scan_user_sounds() {
+        let dir = std::env::temp_dir().join(format!("iqamah-sounds-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        for file in ["Istanbul.MP3", "alaqsa.wav", "notes.txt"] {
+            std::fs::write(dir.join(file), b"").unwrap();
+        }
+
+        let sounds = scan_user_sounds(&dir);
+        let ids: Vec<&str> = sounds.iter().map(|s| s.id.as_str()).collect();
+        assert_eq!(ids, ["user:alaqsa.wav", "user:Istanbul.MP3"]);
+        assert!(sounds.iter().all(|s| s.available));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert!(scan_user_sounds(&dir).is_empty());
+    }
+
+    #[test]
+    fn test_overlapping_sounds_restore_once() {
+        let stream = DuckedStream { id: 42, volume_percent: 80 };
+        let mut ducking = Ducking::default();
+
+        ducking.acquire(|| vec![stream.clone()]);
+        // The second sound would read the lowered volume; it must not duck again
+        ducking.acquire(|| panic!("already ducked"));
+        assert_eq!(ducking.release(), None);
+        assert_eq!(ducking.release(), Some(vec![stream]));
+        assert_eq!(ducking.release(), Some(Vec::new()));
+    }
+
+    #[test]
+    fn test_parse_pactl_output() {
+        let sinks = "Sink #0\n\tState: RUNNING\n\tName: alsa_output.pci.analog-stereo\n\
+                     \tDescription: Built-in Audio Analog Stereo\n\
+                     Sink #3\n\tName: bluez_output.headset\n\tDescription: WH-1000XM4\n";
+        let devices = parse_pactl_sinks(sinks, "bluez_output.headset");
+        assert_eq!(devices.len(), 2);
+        assert_eq!(devices[0].name, "Built-in Audio Analog Stereo");
+        assert!(!devices[0].is_default);
+        assert_eq!(devices[1].id, "bluez_output.headset");
+        assert!(devices[1].is_default);
+
+        let inputs = "Sink Input #42\n\tDriver: protocol-native.c\n\
+                      \tVolume: front-left: 52429 /  80% / -5.81 dB,   front-right: 52429 /  80% / -5.81 dB\n\
+                      Sink Input #57\n\tVolume: mono: 65536 / 100% / 0.00 dB\n";
+        assert_eq!(
+            parse_pactl_sink_inputs(inputs),
+            [
+                DuckedStream { id: 42, volume_percent: 80 },
+                DuckedStream { id: 57, volume_percent: 100 },
+            ]
+        );
+    }
+}
@@ -0,0 +1,174 @@
+use std::sync::RwLock;
+use std::time::Instant;
+
+use chrono::{DateTime, Duration, FixedOffset, Local, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use once_cell::sync::Lazy;
+
+/// A simulated "now" that keeps ticking from the moment it was set
+struct Simulation {
+    start: DateTime<Utc>,
+    set_at: Instant,
+}
+
+static SIMULATION: Lazy<RwLock<Option<Simulation>>> = Lazy::new(|| RwLock::new(None));
+
+/// Current time as seen by the engine and commands
+/// Returns the simulated time when time-travel is active
+pub fn now() -> DateTime<Utc> {
+    match SIMULATION.read().unwrap().as_ref() {
+        Some(sim) => {
+            let elapsed = Duration::from_std(sim.set_at.elapsed()).unwrap_or_else(|_| Duration::zero());
+            sim.start + elapsed
+        }
+        None => Utc::now(),
+    }
+}
+
+/// Today's date in the local timezone, following the simulated clock
+pub fn today() -> NaiveDate {
+    now().with_timezone(&Local).date_naive()
+}
+
+/// Local date of `now`; `utc_offset` replaces the system timezone when given
+pub fn date_at(now: DateTime<Utc>, utc_offset: Option<FixedOffset>) -> NaiveDate {
+    match utc_offset {
+        Some(offset) => now.with_timezone(&offset).date_naive(),
+        None => now.with_timezone(&Local).date_naive(),
+    }
+}
+
+/// A wall-clock time in `tz` as UTC
+/// Ambiguous times (clocks going back) take the first occurrence, skipped ones (clocks going forward)
+/// land after the gap, as a wall clock would show them
+pub fn resolve_local<Tz: TimeZone>(tz: &Tz, local: NaiveDateTime) -> DateTime<Utc> {
+    tz.from_local_datetime(&local)
+        .earliest()
+        .or_else(|| tz.from_local_datetime(&(local + Duration::hours(1))).earliest())
+        .map(|time| time.with_timezone(&Utc))
+        .unwrap_or_else(|| local.and_utc())
+}
+
+/// The same wall-clock time a day later in `tz`: 23 or 25 hours later across a DST change
+pub fn same_time_next_day<Tz: TimeZone>(tz: &Tz, time: DateTime<Utc>) -> DateTime<Utc> {
+    resolve_local(tz, time.with_timezone(tz).naive_local() + Duration::days(1))
+}
+
+/// Override the clock; `None` returns to real time
+pub fn set_simulated_now(start: Option<DateTime<Utc>>) {
+    *SIMULATION.write().unwrap() = start.map(|start| Simulation {
+        start,
+        set_at: Instant::now(),
+    });
+}
+
+pub fn is_simulated() -> bool {
+    SIMULATION.read().unwrap().is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::LocalResult;
+
+    /// Europe/London in 2026: BST from 29 March 01:00 UTC to 25 October 01:00 UTC
+    #[derive(Debug, Clone, Copy)]
+    struct London;
+
+    impl London {
+        fn offset_at(utc: NaiveDateTime) -> FixedOffset {
+            let bst_start = NaiveDate::from_ymd_opt(2026, 3, 29).unwrap().and_hms_opt(1, 0, 0).unwrap();
+            let bst_end = NaiveDate::from_ymd_opt(2026, 10, 25).unwrap().and_hms_opt(1, 0, 0).unwrap();
+            let hours = if (bst_start..bst_end).contains(&utc) { 1 } else { 0 };
+            FixedOffset::east_opt(hours * 3600).unwrap()
+        }
+    }
+
+    impl TimeZone for London {
+        type Offset = FixedOffset;
+
+        fn from_offset(_: &FixedOffset) -> Self {
+            London
+        }
+
+        fn offset_from_local_date(&self, local: &NaiveDate) -> LocalResult<FixedOffset> {
+            self.offset_from_local_datetime(&local.and_hms_opt(0, 0, 0).unwrap())
+        }
+
+        fn offset_from_local_datetime(&self, local: &NaiveDateTime) -> LocalResult<FixedOffset> {
+            let offsets: Vec<FixedOffset> = [0, 3600]
+                .into_iter()
+                .map(|secs| FixedOffset::east_opt(secs).unwrap())
+                .filter(|offset| London::offset_at(*local - Duration::seconds(offset.local_minus_utc() as i64)) == *offset)
+                .collect();
+
+            match offsets[..] {
+                [offset] => LocalResult::Single(offset),
+                [first, second] => LocalResult::Ambiguous(second, first),
+                _ => LocalResult::None,
+            }
+        }
+
+        fn offset_from_utc_date(&self, utc: &NaiveDate) -> FixedOffset {
+            London::offset_at(utc.and_hms_opt(0, 0, 0).unwrap())
+        }
+
+        fn offset_from_utc_datetime(&self, utc: &NaiveDateTime) -> FixedOffset {
+            London::offset_at(*utc)
+        }
+    }
+
+    fn local(month: u32, day: u32, hour: u32, minute: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2026, month, day).unwrap().and_hms_opt(hour, minute, 0).unwrap()
+    }
+
+    fn utc(month: u32, day: u32, hour: u32, minute: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, month, day, hour, minute, 0).unwrap()
+    }
+
+    #[test]
+    fn test_spring_forward_night() {
+        // Fajr at 05:00 GMT, then 05:00 BST: only 23 hours apart
+        let fajr = resolve_local(&London, local(3, 28, 5, 0));
+        assert_eq!(fajr, utc(3, 28, 5, 0));
+        assert_eq!(same_time_next_day(&London, fajr), utc(3, 29, 4, 0));
+
+        // 01:00-02:00 doesn't exist; an iqama 15 minutes after a 00:50 adhan reads 02:05
+        assert_eq!(resolve_local(&London, local(3, 29, 1, 5)), utc(3, 29, 1, 5));
+        assert_eq!(resolve_local(&London, local(3, 29, 0, 50)), utc(3, 29, 0, 50));
+    }
+
+    #[test]
+    fn test_fall_back_night() {
+        // Fajr at 05:00 BST, then 05:00 GMT: 25 hours apart
+        let fajr = resolve_local(&London, local(10, 24, 5, 0));
+        assert_eq!(fajr, utc(10, 24, 4, 0));
+        assert_eq!(same_time_next_day(&London, fajr), utc(10, 25, 5, 0));
+
+        // 01:30 happens twice; the first one (BST) is taken
+        assert_eq!(resolve_local(&London, local(10, 25, 1, 30)), utc(10, 25, 0, 30));
+        assert_eq!(resolve_local(&London, local(10, 25, 2, 30)), utc(10, 25, 2, 30));
+    }
+
+    #[test]
+    fn test_simulated_clock_keeps_ticking() {
+        let start = Utc::now() - Duration::days(30);
+        set_simulated_now(Some(start));
+
+        let simulated = now();
+        assert!(is_simulated());
+        assert!(simulated >= start && simulated - start < Duration::seconds(5));
+
+        set_simulated_now(None);
+        assert!(!is_simulated());
+        assert!(Utc::now() - now() < Duration::seconds(5));
+    }
+
+    #[test]
+    fn test_date_at_follows_offset() {
+        let now = Utc.with_ymd_and_hms(2026, 3, 1, 22, 30, 0).unwrap();
+
+        assert_eq!(date_at(now, FixedOffset::east_opt(0)), NaiveDate::from_ymd_opt(2026, 3, 1).unwrap());
+        assert_eq!(date_at(now, FixedOffset::east_opt(2 * 3600)), NaiveDate::from_ymd_opt(2026, 3, 2).unwrap());
+        assert_eq!(date_at(now, FixedOffset::west_opt(5 * 3600)), NaiveDate::from_ymd_opt(2026, 3, 1).unwrap());
+    }
+}
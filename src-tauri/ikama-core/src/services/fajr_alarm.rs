@@ -0,0 +1,198 @@
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc};
+
+use crate::models::{FajrAlarmConfig, FajrAlarmRing, FajrAlarmStatus};
+
+/// Rings stop this long after the Fajr adhan, dismissed or not
+pub const FAJR_ALARM_CUTOFF_MINUTES: i64 = 60;
+
+/// Arithmetic question that stops the alarm; harder at higher urgency levels
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MathChallenge {
+    pub question: String,
+    answer: i64,
+}
+
+impl MathChallenge {
+    /// A question for `level`, drawn from `seed`
+    pub fn generate(level: u32, seed: u64) -> Self {
+        // splitmix64, so neighbouring seeds give unrelated numbers
+        let mut state = seed;
+        let mut next = |low: i64, high: i64| {
+            state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+            let mut z = state;
+            z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+            z ^= z >> 31;
+            low + (z % (high - low + 1) as u64) as i64
+        };
+
+        let (a, b, c) = (next(11, 49), next(3, 9), next(11, 99));
+        match level {
+            0 | 1 => Self {
+                question: format!("{} + {}", a, c),
+                answer: a + c,
+            },
+            2 => Self {
+                question: format!("{} × {}", a, b),
+                answer: a * b,
+            },
+            _ => Self {
+                question: format!("{} × {} + {}", a, b, c),
+                answer: a * b + c,
+            },
+        }
+    }
+
+    pub fn is_answer(&self, answer: i64) -> bool {
+        self.answer == answer
+    }
+}
+
+/// Ringing state of the Fajr alarm across ticks
+#[derive(Debug, Default)]
+pub struct FajrAlarm {
+    /// Fajr the alarm is ringing for
+    fajr_time: Option<DateTime<Utc>>,
+    level: u32,
+    last_ring: Option<DateTime<Utc>>,
+    challenge: Option<MathChallenge>,
+    dismissed_on: Option<NaiveDate>,
+}
+
+impl FajrAlarm {
+    /// Ring again when it is time to; `today` is the local date of `fajr_time`
+    pub fn tick(
+        &mut self,
+        config: &FajrAlarmConfig,
+        today: NaiveDate,
+        fajr_time: DateTime<Utc>,
+        now: DateTime<Utc>,
+        seed: u64,
+    ) -> Option<FajrAlarmRing> {
+        let start = fajr_time - Duration::minutes(config.minutes_before);
+        let cutoff = fajr_time + Duration::minutes(FAJR_ALARM_CUTOFF_MINUTES);
+        let active = config.rings_on(today.weekday())
+            && self.dismissed_on != Some(today)
+            && now >= start
+            && now < cutoff;
+        if !active {
+            self.stop();
+            return None;
+        }
+
+        if self.fajr_time != Some(fajr_time) {
+            self.stop();
+            self.fajr_time = Some(fajr_time);
+        }
+        let due = self
+            .last_ring
+            .is_none_or(|last| now - last >= Duration::minutes(config.repeat_minutes));
+        if !due {
+            return None;
+        }
+
+        self.level += 1;
+        self.last_ring = Some(now);
+        // Harder questions up to level 3; after that the question on screen stays
+        if self.challenge.is_none() || self.level <= 3 {
+            self.challenge = Some(MathChallenge::generate(self.level, seed));
+        }
+        self.ring()
+    }
+
+    /// Stop the alarm for today with the challenge's answer
+    /// A wrong answer keeps it ringing with a new question
+    pub fn dismiss(&mut self, today: NaiveDate, answer: i64, seed: u64) -> Result<(), String> {
+        let challenge = self.challenge.as_ref().ok_or("The Fajr alarm isn't ringing")?;
+        if !challenge.is_answer(answer) {
+            self.challenge = Some(MathChallenge::generate(self.level, seed));
+            return Err("Wrong answer, try the new question".to_string());
+        }
+
+        self.stop();
+        self.dismissed_on = Some(today);
+        Ok(())
+    }
+
+    pub fn status(&self, today: NaiveDate) -> FajrAlarmStatus {
+        FajrAlarmStatus {
+            ringing: self.challenge.is_some(),
+            ring: self.ring(),
+            dismissed: self.dismissed_on == Some(today),
+        }
+    }
+
+    fn ring(&self) -> Option<FajrAlarmRing> {
+        Some(FajrAlarmRing {
+            level: self.level,
+            fajr_time: self.fajr_time?,
+            question: self.challenge.as_ref()?.question.clone(),
+        })
+    }
+
+    fn stop(&mut self) {
+        self.fajr_time = None;
+        self.level = 0;
+        self.last_ring = None;
+        self.challenge = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solve(question: &str) -> i64 {
+        let parts: Vec<&str> = question.split(' ').collect();
+        let (a, b): (i64, i64) = (parts[0].parse().unwrap(), parts[2].parse().unwrap());
+        let first = if parts[1] == "×" { a * b } else { a + b };
+        match parts.get(4) {
+            Some(c) => first + c.parse::<i64>().unwrap(),
+            None => first,
+        }
+    }
+
+    #[test]
+    fn test_alarm_escalates_until_solved() {
+        let today = NaiveDate::from_ymd_opt(2026, 10, 16).unwrap(); // a Friday
+        let fajr = today.and_hms_opt(5, 30, 0).unwrap().and_utc();
+        let config = FajrAlarmConfig {
+            enabled: true,
+            ..Default::default()
+        };
+        let mut alarm = FajrAlarm::default();
+
+        assert!(alarm.tick(&config, today, fajr, fajr - Duration::minutes(21), 1).is_none());
+        let first = alarm.tick(&config, today, fajr, fajr - Duration::minutes(20), 1).unwrap();
+        assert_eq!(first.level, 1);
+        assert!(first.question.contains('+'));
+        assert!(alarm.tick(&config, today, fajr, fajr - Duration::minutes(19), 2).is_none());
+
+        let second = alarm.tick(&config, today, fajr, fajr - Duration::minutes(18), 2).unwrap();
+        assert_eq!(second.level, 2);
+        assert!(second.question.contains('×'));
+
+        assert!(alarm.dismiss(today, solve(&second.question) + 1, 3).is_err());
+        let question = alarm.status(today).ring.unwrap().question;
+        alarm.dismiss(today, solve(&question), 4).unwrap();
+
+        let status = alarm.status(today);
+        assert!(!status.ringing && status.dismissed);
+        assert!(alarm.tick(&config, today, fajr, fajr - Duration::minutes(10), 5).is_none());
+    }
+
+    #[test]
+    fn test_alarm_respects_days() {
+        let today = NaiveDate::from_ymd_opt(2026, 10, 16).unwrap();
+        let fajr = today.and_hms_opt(5, 30, 0).unwrap().and_utc();
+        let weekends = FajrAlarmConfig {
+            enabled: true,
+            days: vec![crate::models::DayOfWeek::Sat, crate::models::DayOfWeek::Sun],
+            ..Default::default()
+        };
+
+        let mut alarm = FajrAlarm::default();
+        assert!(alarm.tick(&weekends, today, fajr, fajr - Duration::minutes(5), 1).is_none());
+        assert!(alarm.tick(&FajrAlarmConfig::default(), today, fajr, fajr, 1).is_none());
+    }
+}
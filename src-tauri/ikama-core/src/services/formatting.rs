@@ -0,0 +1,167 @@
+use chrono::{DateTime, Local, TimeZone, Timelike, Utc};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use crate::models::{CountdownDisplay, NextPrayerResult};
+use crate::services::i18n::Language;
+
+/// Clock style for displayed times, stored in the `time_format` setting
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub enum TimeFormat {
+    #[serde(rename = "12h")]
+    TwelveHour,
+    #[serde(rename = "24h")]
+    TwentyFourHour,
+}
+
+impl TimeFormat {
+    /// Parse the setting value; anything unrecognised uses the language's convention
+    pub fn from_setting(value: Option<&str>, language: Language) -> Self {
+        match value.map(str::trim) {
+            Some("12h") => TimeFormat::TwelveHour,
+            Some("24h") => TimeFormat::TwentyFourHour,
+            _ => Self::default_for(language),
+        }
+    }
+
+    pub fn default_for(language: Language) -> Self {
+        match language {
+            Language::En => TimeFormat::TwelveHour,
+            _ => TimeFormat::TwentyFourHour,
+        }
+    }
+}
+
+/// Formats times and durations consistently for notifications, the tray and the UI
+#[derive(Debug, Clone, Copy)]
+pub struct DisplayFormatter {
+    pub language: Language,
+    pub time_format: TimeFormat,
+}
+
+impl DisplayFormatter {
+    pub fn new(language: Language, time_format: TimeFormat) -> Self {
+        Self {
+            language,
+            time_format,
+        }
+    }
+
+    /// Build from the raw `language` and `time_format` settings
+    pub fn from_settings(language: Option<&str>, time_format: Option<&str>) -> Self {
+        let language = language.map(Language::from_code).unwrap_or_default();
+        Self::new(language, TimeFormat::from_setting(time_format, language))
+    }
+
+    /// Format a time in the local timezone, e.g. "5:07 PM" or "17:07"
+    pub fn format_time(&self, time: DateTime<Utc>) -> String {
+        self.format_time_in(&time.with_timezone(&Local))
+    }
+
+    pub fn format_time_in<Tz: TimeZone>(&self, time: &DateTime<Tz>) -> String {
+        let (hour, minute) = (time.hour(), time.minute());
+
+        match self.time_format {
+            TimeFormat::TwentyFourHour => format!("{:02}:{:02}", hour, minute),
+            TimeFormat::TwelveHour => {
+                let (is_pm, hour12) = time.hour12();
+                let period = self.language.day_period(is_pm);
+                format!("{}:{:02} {}", hour12, minute, period)
+            }
+        }
+    }
+
+    pub fn format_duration(&self, seconds: i64) -> String {
+        self.language.format_duration(seconds)
+    }
+
+    /// Countdown as the engine decided it reads: whole minutes, or seconds once close
+    pub fn format_countdown(&self, display: &CountdownDisplay) -> String {
+        if display.show_seconds {
+            self.format_duration(display.secs)
+        } else {
+            self.language.format_minutes(display.secs)
+        }
+    }
+
+    /// Tray title for the next prayer, e.g. "Asr 1h 5m"
+    /// Counts to the iqama once the adhan is called, and shows the time when it is due now
+    pub fn tray_title(&self, next: &NextPrayerResult) -> String {
+        let name = self.language.prayer_name(next.prayer.name);
+        let (display, time) = match (next.iqama_display, next.prayer.iqama) {
+            (Some(display), Some(iqama)) if next.time_until_adhan_secs <= 0 => (display, iqama),
+            _ => (next.adhan_display, next.prayer.adhan),
+        };
+
+        if display.is_now {
+            format!("{} {}", name, self.format_time(time))
+        } else {
+            format!("{} {}", name, self.format_countdown(&display))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Prayer, PrayerName};
+    use chrono::FixedOffset;
+
+    #[test]
+    fn test_tray_title() {
+        let adhan = Utc::now();
+        let display = |secs, show_seconds| CountdownDisplay {
+            secs,
+            show_seconds,
+            is_now: secs == 0,
+            refresh_in_secs: 1,
+        };
+        let mut next = NextPrayerResult {
+            prayer: Prayer {
+                name: PrayerName::Asr,
+                adhan,
+                iqama: Some(adhan + chrono::Duration::minutes(10)),
+                custom_rakah_count: None,
+                iqama_offset_minutes: Some(10),
+            },
+            time_until_adhan_secs: 65 * 60,
+            time_until_iqama_secs: Some(75 * 60),
+            expected_iqama: None,
+            is_tomorrow: false,
+            adhan_display: display(65 * 60, false),
+            iqama_display: Some(display(75 * 60, false)),
+        };
+        let en = DisplayFormatter::from_settings(Some("en"), Some("24h"));
+        assert_eq!(en.tray_title(&next), "Asr 1h 5m");
+
+        // Called: the iqama is what's left
+        next.time_until_adhan_secs = -30;
+        next.adhan_display = display(0, true);
+        next.iqama_display = Some(display(9 * 60 + 30, true));
+        assert_eq!(en.tray_title(&next), "Asr 9m 30s");
+
+        next.iqama_display = Some(display(0, true));
+        assert_eq!(en.tray_title(&next), format!("Asr {}", en.format_time(adhan + chrono::Duration::minutes(10))));
+    }
+
+    #[test]
+    fn test_format_time() {
+        let time = FixedOffset::east_opt(0)
+            .unwrap()
+            .with_ymd_and_hms(2026, 3, 1, 17, 7, 0)
+            .unwrap();
+
+        let en = DisplayFormatter::from_settings(Some("en"), None);
+        assert_eq!(en.format_time_in(&time), "5:07 PM");
+
+        let fr = DisplayFormatter::from_settings(Some("fr"), None);
+        assert_eq!(fr.format_time_in(&time), "17:07");
+
+        let ar = DisplayFormatter::from_settings(Some("ar"), Some("12h"));
+        assert_eq!(ar.format_time_in(&time), "5:07 م");
+
+        let en24 = DisplayFormatter::from_settings(None, Some("24h"));
+        assert_eq!(en24.format_time_in(&time), "17:07");
+    }
+}
@@ -0,0 +1,154 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use once_cell::sync::Lazy;
+
+use crate::models::{PrayerTimes, ScheduleFreshness};
+
+/// Cached schedules older than this are reported stale
+pub const DEFAULT_MAX_CACHE_AGE_HOURS: i64 = 12;
+
+/// Wait after a mosque's first failed background refresh, doubled for each failure in a row
+const REFRESH_BACKOFF_MINUTES: i64 = 2;
+
+/// Longest wait between background refreshes of a failing mosque
+const MAX_REFRESH_BACKOFF_MINUTES: i64 = 60;
+
+// Mosque days with a provider fetch running, and how many
+static FETCHING: Lazy<Mutex<HashMap<(String, NaiveDate), usize>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+// Mosques whose background refreshes keep failing
+static BACKOFF: Lazy<Mutex<HashMap<String, Backoff>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Failed background refreshes of a mosque in a row, and when to try again
+struct Backoff {
+    failures: u32,
+    retry_at: DateTime<Utc>,
+}
+
+/// Marks a mosque day as being fetched until dropped
+pub struct FetchGuard {
+    key: (String, NaiveDate),
+}
+
+impl Drop for FetchGuard {
+    fn drop(&mut self) {
+        let mut fetching = FETCHING.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(count) = fetching.get_mut(&self.key) {
+            *count -= 1;
+            if *count == 0 {
+                fetching.remove(&self.key);
+            }
+        }
+    }
+}
+
+/// Record that a provider fetch for the day started; it ends when the guard is dropped
+pub fn begin_fetch(mosque_id: &str, date: NaiveDate) -> FetchGuard {
+    let key = (mosque_id.to_string(), date);
+    *FETCHING.lock().unwrap_or_else(|e| e.into_inner()).entry(key.clone()).or_insert(0) += 1;
+    FetchGuard { key }
+}
+
+/// Like `begin_fetch`, but `None` when a fetch for the day is already running
+pub fn try_begin_fetch(mosque_id: &str, date: NaiveDate) -> Option<FetchGuard> {
+    let key = (mosque_id.to_string(), date);
+    let mut fetching = FETCHING.lock().unwrap_or_else(|e| e.into_inner());
+    if fetching.contains_key(&key) {
+        return None;
+    }
+    fetching.insert(key.clone(), 1);
+    Some(FetchGuard { key })
+}
+
+pub fn is_fetching(mosque_id: &str, date: NaiveDate) -> bool {
+    FETCHING
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .contains_key(&(mosque_id.to_string(), date))
+}
+
+/// Wait before the next background refresh after `failures` failed ones in a row
+pub fn refresh_backoff(failures: u32) -> Duration {
+    let doublings = failures.saturating_sub(1).min(6);
+    Duration::minutes((REFRESH_BACKOFF_MINUTES << doublings).min(MAX_REFRESH_BACKOFF_MINUTES))
+}
+
+/// Whether a background refresh of the mosque may start, i.e. it isn't backing off from failures
+pub fn refresh_allowed(mosque_id: &str, now: DateTime<Utc>) -> bool {
+    BACKOFF
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(mosque_id)
+        .is_none_or(|backoff| now >= backoff.retry_at)
+}
+
+/// Record a failed background refresh; returns when the next one may start
+pub fn record_refresh_failure(mosque_id: &str, now: DateTime<Utc>) -> DateTime<Utc> {
+    let mut backoff = BACKOFF.lock().unwrap_or_else(|e| e.into_inner());
+    let entry = backoff.entry(mosque_id.to_string()).or_insert(Backoff { failures: 0, retry_at: now });
+    entry.failures += 1;
+    entry.retry_at = now + refresh_backoff(entry.failures);
+    entry.retry_at
+}
+
+/// Forget the failures of a mosque once a refresh succeeds
+pub fn record_refresh_success(mosque_id: &str) {
+    BACKOFF.lock().unwrap_or_else(|e| e.into_inner()).remove(mosque_id);
+}
+
+/// Attach freshness metadata to a schedule about to be returned
+pub fn annotate_freshness(times: &mut PrayerTimes, now: DateTime<Utc>, max_age_hours: i64) {
+    let refreshing = times
+        .mosque_id
+        .as_deref()
+        .is_some_and(|id| is_fetching(id, times.date.date_naive()));
+    times.freshness = Some(ScheduleFreshness::of(times, now, max_age_hours, refreshing));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::MockProvider;
+
+    #[test]
+    fn test_freshness_age_and_fetches() {
+        let now = Utc::now();
+        let date = now.date_naive();
+        let mut times = MockProvider::new().schedule("mock-freshness", date, now);
+        times.cached_at = Some(now - Duration::hours(DEFAULT_MAX_CACHE_AGE_HOURS + 1));
+
+        let guard = try_begin_fetch("mock-freshness", date).unwrap();
+        assert!(try_begin_fetch("mock-freshness", date).is_none());
+        drop(begin_fetch("mock-freshness", date));
+        annotate_freshness(&mut times, now, DEFAULT_MAX_CACHE_AGE_HOURS);
+        let freshness = times.freshness.clone().unwrap();
+        assert!(freshness.stale && freshness.refreshing);
+        assert_eq!(freshness.provider.as_deref(), Some("mock"));
+
+        drop(guard);
+        times.cached_at = Some(now - Duration::minutes(5));
+        annotate_freshness(&mut times, now, DEFAULT_MAX_CACHE_AGE_HOURS);
+        let freshness = times.freshness.unwrap();
+        assert_eq!(freshness.age_secs, 300);
+        assert!(!freshness.stale && !freshness.refreshing);
+    }
+
+    #[test]
+    fn test_refresh_backoff_after_failures() {
+        assert_eq!(refresh_backoff(1), Duration::minutes(2));
+        assert_eq!(refresh_backoff(3), Duration::minutes(8));
+        assert_eq!(refresh_backoff(40), Duration::minutes(MAX_REFRESH_BACKOFF_MINUTES));
+
+        let now = Utc::now();
+        assert!(refresh_allowed("mock-backoff", now));
+        assert_eq!(record_refresh_failure("mock-backoff", now), now + Duration::minutes(2));
+        assert!(!refresh_allowed("mock-backoff", now + Duration::minutes(1)));
+        assert_eq!(record_refresh_failure("mock-backoff", now), now + Duration::minutes(4));
+        assert!(refresh_allowed("mock-backoff", now + Duration::minutes(4)));
+
+        record_refresh_success("mock-backoff");
+        assert!(refresh_allowed("mock-backoff", now));
+    }
+}
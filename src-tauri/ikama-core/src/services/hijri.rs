@@ -0,0 +1,221 @@
+use chrono::{Datelike, Duration, NaiveDate};
+
+use crate::models::{HijriDate, HijriDay, IslamicEvent, MosqueHijriInfo, SpecialDay};
+
+/// Julian day number of 0001-01-01 minus one, so `num_days_from_ce() + offset` is the JDN
+const JDN_CE_OFFSET: i64 = 1_721_425;
+
+/// Convert a Gregorian date with the tabular (civil) Islamic calendar
+pub fn to_hijri(date: NaiveDate) -> HijriDate {
+    let jd = date.num_days_from_ce() as i64 + JDN_CE_OFFSET;
+
+    let mut l = jd - 1_948_440 + 10_632;
+    let n = (l - 1) / 10_631;
+    l = l - 10_631 * n + 354;
+    let j = ((10_985 - l) / 5_316) * ((50 * l) / 17_719) + (l / 5_670) * ((43 * l) / 15_238);
+    l = l - ((30 - j) / 15) * ((17_719 * j) / 50) - (j / 16) * ((15_238 * j) / 43) + 29;
+    let month = (24 * l) / 709;
+    let day = l - (709 * month) / 24;
+    let year = 30 * n + j - 30;
+
+    HijriDate {
+        year: year as i32,
+        month: month as u32,
+        day: day as u32,
+    }
+}
+
+/// Hijri dates following a mosque's announcements when known, calculation otherwise
+#[derive(Debug, Clone, Default)]
+pub struct HijriService {
+    mosque: Option<MosqueHijriInfo>,
+}
+
+impl HijriService {
+    pub fn new(mosque: Option<MosqueHijriInfo>) -> Self {
+        Self { mosque }
+    }
+
+    pub fn day(&self, date: NaiveDate) -> HijriDay {
+        let info = self.mosque.clone().unwrap_or_default();
+        let adjusted = date + Duration::days(info.adjustment_days as i64);
+        let mut hijri = to_hijri(adjusted);
+
+        // A 29-day month the mosque completes to 30: the next month starts a day later,
+        // so every day of it is shifted back and its first day is still day 30
+        if info.force_month_30 {
+            let month_start = adjusted - Duration::days(hijri.day as i64 - 1);
+            let previous = to_hijri(month_start - Duration::days(1));
+            if previous.day == 29 {
+                hijri = match hijri.day {
+                    1 => HijriDate { day: 30, ..previous },
+                    _ => to_hijri(adjusted - Duration::days(1)),
+                };
+            }
+        }
+
+        let special_day = special_day(&hijri);
+        let eid_prayer_times = match special_day {
+            Some(SpecialDay::EidAlFitr | SpecialDay::EidAlAdha) => info.eid_prayer_times.clone(),
+            _ => Vec::new(),
+        };
+
+        HijriDay {
+            date: hijri,
+            is_ramadan: hijri.month == 9,
+            special_day,
+            adjustment_days: info.adjustment_days,
+            from_mosque: self.mosque.is_some(),
+            eid_prayer_times,
+        }
+    }
+
+    /// Gregorian days of a Hijri month in order, e.g. every day of Ramadan 1447
+    pub fn month_dates(&self, year: i32, month: u32) -> Vec<NaiveDate> {
+        // 1 Muharram 1 AH is JDN 1948440; scan around the estimated start of the month
+        let estimate = 1_948_440 + (year as i64 - 1) * 10_631 / 30 + (month as i64 - 1) * 2_953 / 100;
+        let start = match NaiveDate::from_num_days_from_ce_opt((estimate - JDN_CE_OFFSET - 10) as i32) {
+            Some(start) => start,
+            None => return Vec::new(),
+        };
+
+        (0..60)
+            .map(|offset| start + Duration::days(offset))
+            .filter(|&date| {
+                let hijri = self.day(date).date;
+                hijri.year == year && hijri.month == month
+            })
+            .collect()
+    }
+
+    /// Special days within `days` days from `from` (inclusive), in date order
+    pub fn upcoming_events(&self, from: NaiveDate, days: i64) -> Vec<IslamicEvent> {
+        (0..days)
+            .filter_map(|offset| {
+                let date = from + Duration::days(offset);
+                let day = self.day(date);
+                day.special_day.map(|special| IslamicEvent {
+                    day: special,
+                    date,
+                    hijri_date: day.date,
+                    days_until: offset,
+                })
+            })
+            .collect()
+    }
+}
+
+fn special_day(date: &HijriDate) -> Option<SpecialDay> {
+    match (date.month, date.day) {
+        (1, 10) => Some(SpecialDay::Ashura),
+        (9, 1) => Some(SpecialDay::RamadanStart),
+        (9, 21 | 23 | 25 | 27 | 29) => Some(SpecialDay::LaylatAlQadr),
+        (10, 1) => Some(SpecialDay::EidAlFitr),
+        (12, 1) => Some(SpecialDay::DhulHijjahStart),
+        (12, 2..=8) => Some(SpecialDay::DhulHijjahDay),
+        (12, 9) => Some(SpecialDay::DayOfArafah),
+        (12, 10) => Some(SpecialDay::EidAlAdha),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tabular_conversion() {
+        let date = NaiveDate::from_ymd_opt(2024, 3, 11).unwrap();
+        assert_eq!(to_hijri(date), HijriDate { year: 1445, month: 9, day: 1 });
+    }
+
+    #[test]
+    fn test_ramadan_dates_follow_adjustment() {
+        let ramadan = HijriService::default().month_dates(1445, 9);
+        assert_eq!(ramadan.first(), NaiveDate::from_ymd_opt(2024, 3, 11).as_ref());
+        assert_eq!(ramadan.len(), 30);
+
+        let announced = HijriService::new(Some(MosqueHijriInfo {
+            adjustment_days: 1,
+            ..Default::default()
+        }))
+        .month_dates(1445, 9);
+        assert_eq!(announced.first(), NaiveDate::from_ymd_opt(2024, 3, 10).as_ref());
+    }
+
+    #[test]
+    fn test_mosque_adjustment_and_flags() {
+        let date = NaiveDate::from_ymd_opt(2024, 3, 10).unwrap();
+
+        let calculated = HijriService::default().day(date);
+        assert_eq!(calculated.date.month, 8);
+        assert!(!calculated.from_mosque);
+
+        let announced = HijriService::new(Some(MosqueHijriInfo {
+            adjustment_days: 1,
+            ..Default::default()
+        }))
+        .day(date);
+        assert_eq!(announced.special_day, Some(SpecialDay::RamadanStart));
+        assert!(announced.is_ramadan);
+        assert!(announced.from_mosque);
+    }
+
+    #[test]
+    fn test_force_month_30_shifts_next_month() {
+        let service = HijriService::new(Some(MosqueHijriInfo {
+            force_month_30: true,
+            ..Default::default()
+        }));
+        let day = |m, d| service.day(NaiveDate::from_ymd_opt(2024, m, d).unwrap());
+        let hijri = |month, day| HijriDate { year: 1445, month, day };
+
+        // Sha'ban 1445 has 29 days: completed to 30, Ramadan starts a day later and stays shifted
+        assert_eq!(day(3, 11).date, hijri(8, 30));
+        assert_eq!(day(3, 12).special_day, Some(SpecialDay::RamadanStart));
+        assert_eq!(day(3, 13).date, hijri(9, 2));
+        assert_eq!(day(3, 14).date, hijri(9, 3));
+        assert_eq!(day(4, 9).date, hijri(9, 29));
+
+        // Ramadan has 30 days in the calendar, so Eid and the rest of Shawwal are not moved
+        assert_eq!(day(4, 10).special_day, Some(SpecialDay::EidAlFitr));
+        assert_eq!(day(4, 11).date, hijri(10, 2));
+        assert_eq!(day(4, 12).date, hijri(10, 3));
+
+        // Shawwal has 29 days: Dhul Qa'dah is shifted as a whole
+        assert_eq!(day(5, 9).date, hijri(10, 30));
+        assert_eq!(day(5, 10).date, hijri(11, 1));
+        assert_eq!(day(5, 12).date, hijri(11, 3));
+    }
+
+    #[test]
+    fn test_upcoming_events() {
+        // 1 Ramadan 1445 is 2024-03-11 in the tabular calendar
+        let from = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+        let events = HijriService::default().upcoming_events(from, 45);
+        let days: Vec<SpecialDay> = events.iter().map(|e| e.day).collect();
+        assert_eq!(
+            days,
+            [
+                SpecialDay::RamadanStart,
+                SpecialDay::LaylatAlQadr,
+                SpecialDay::LaylatAlQadr,
+                SpecialDay::LaylatAlQadr,
+                SpecialDay::LaylatAlQadr,
+                SpecialDay::LaylatAlQadr,
+                SpecialDay::EidAlFitr,
+            ]
+        );
+        assert_eq!(events[0].days_until, 10);
+        assert_eq!(events[0].date, NaiveDate::from_ymd_opt(2024, 3, 11).unwrap());
+        assert_eq!(events[1].hijri_date.day, 21);
+
+        // Each of the ten days of Dhul Hijjah, ending with Eid al-Adha
+        let dhul_hijjah = HijriService::default().upcoming_events(NaiveDate::from_ymd_opt(2024, 6, 8).unwrap(), 10);
+        let days: Vec<(u32, SpecialDay)> = dhul_hijjah.iter().map(|e| (e.hijri_date.day, e.day)).collect();
+        assert_eq!(days.len(), 10);
+        assert_eq!(days[0], (1, SpecialDay::DhulHijjahStart));
+        assert!(days[1..8].iter().all(|(_, day)| *day == SpecialDay::DhulHijjahDay));
+        assert_eq!(days[9], (10, SpecialDay::EidAlAdha));
+    }
+}
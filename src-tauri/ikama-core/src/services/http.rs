@@ -0,0 +1,175 @@
+use std::collections::HashMap;
+
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+/// Largest request head the local server reads; it serves no request bodies
+pub const MAX_REQUEST_HEAD: usize = 8 * 1024;
+
+/// Request line and headers of an HTTP/1.1 request
+#[derive(Debug, Clone, PartialEq)]
+pub struct HttpRequest {
+    pub method: String,
+    /// Percent-decoded path without the query string
+    pub path: String,
+    pub query: HashMap<String, String>,
+    /// Header names in lowercase
+    pub headers: HashMap<String, String>,
+}
+
+impl HttpRequest {
+    pub fn parse(head: &str) -> Result<Self, String> {
+        let mut lines = head.split("\r\n");
+        let request_line = lines.next().unwrap_or_default();
+        let mut parts = request_line.split(' ');
+        let (Some(method), Some(target), Some(version)) = (parts.next(), parts.next(), parts.next()) else {
+            return Err(format!("Malformed request line {:?}", request_line));
+        };
+        if !version.starts_with("HTTP/1.") {
+            return Err(format!("Unsupported protocol {}", version));
+        }
+
+        let (path, query) = target.split_once('?').unwrap_or((target, ""));
+        let headers = lines
+            .take_while(|line| !line.is_empty())
+            .filter_map(|line| line.split_once(':'))
+            .map(|(name, value)| (name.trim().to_ascii_lowercase(), value.trim().to_string()))
+            .collect();
+
+        Ok(Self {
+            method: method.to_string(),
+            path: percent_decode(path),
+            query: url::form_urlencoded::parse(query.as_bytes()).into_owned().collect(),
+            headers,
+        })
+    }
+
+    /// Read and parse a request head from a connection
+    pub async fn read<R: AsyncRead + Unpin>(reader: &mut R) -> Result<Self, String> {
+        let mut head = Vec::with_capacity(1024);
+        let mut buf = [0u8; 1024];
+        while !head.windows(4).any(|w| w == b"\r\n\r\n") {
+            if head.len() > MAX_REQUEST_HEAD {
+                return Err("Request head too large".to_string());
+            }
+            let read = reader.read(&mut buf).await.map_err(|e| e.to_string())?;
+            if read == 0 {
+                return Err("Connection closed before the request was complete".to_string());
+            }
+            head.extend_from_slice(&buf[..read]);
+        }
+
+        Self::parse(&String::from_utf8_lossy(&head))
+    }
+
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(name).map(String::as_str)
+    }
+}
+
+fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes.get(i + 1..i + 3).and_then(|h| std::str::from_utf8(h).ok());
+        match (bytes[i], hex.and_then(|h| u8::from_str_radix(h, 16).ok())) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Response of the local server; connections close after each one
+#[derive(Debug, Clone, PartialEq)]
+pub struct HttpResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl HttpResponse {
+    pub fn new(status: u16, content_type: &str, body: impl Into<Vec<u8>>) -> Self {
+        Self {
+            status,
+            headers: vec![("Content-Type".to_string(), content_type.to_string())],
+            body: body.into(),
+        }
+    }
+
+    pub fn text(status: u16, body: impl Into<String>) -> Self {
+        Self::new(status, "text/plain; charset=utf-8", body.into())
+    }
+
+    pub fn json<T: serde::Serialize>(value: &T) -> Self {
+        match serde_json::to_vec(value) {
+            Ok(body) => Self::new(200, "application/json", body),
+            Err(e) => Self::text(500, e.to_string()),
+        }
+    }
+
+    pub fn with_header(mut self, name: &str, value: impl Into<String>) -> Self {
+        self.headers.push((name.to_string(), value.into()));
+        self
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut head = format!("HTTP/1.1 {} {}\r\n", self.status, reason_phrase(self.status));
+        for (name, value) in &self.headers {
+            head.push_str(&format!("{}: {}\r\n", name, value));
+        }
+        head.push_str(&format!("Content-Length: {}\r\nConnection: close\r\n\r\n", self.body.len()));
+
+        let mut bytes = head.into_bytes();
+        bytes.extend_from_slice(&self.body);
+        bytes
+    }
+}
+
+fn reason_phrase(status: u16) -> &'static str {
+    match status {
+        101 => "Switching Protocols",
+        200 => "OK",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        429 => "Too Many Requests",
+        _ => "Internal Server Error",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_read_request() {
+        let raw = b"GET /calendar/mosqu%C3%A9e-paris.ics?days=30&name=a+b HTTP/1.1\r\nHost: 192.168.1.2:8787\r\nUser-Agent: Calendar\r\n\r\n";
+        let request = HttpRequest::read(&mut &raw[..]).await.unwrap();
+        assert_eq!(request.method, "GET");
+        assert_eq!(request.path, "/calendar/mosquée-paris.ics");
+        assert_eq!(request.query.get("days").map(String::as_str), Some("30"));
+        assert_eq!(request.query.get("name").map(String::as_str), Some("a b"));
+        assert_eq!(request.header("user-agent"), Some("Calendar"));
+
+        assert!(HttpRequest::read(&mut &b"GET / HTTP/1.1\r\n"[..]).await.is_err());
+        assert!(HttpRequest::parse("GARBAGE\r\n\r\n").is_err());
+    }
+
+    #[test]
+    fn test_response_bytes() {
+        let response = HttpResponse::text(404, "No schedule").with_header("Cache-Control", "no-store");
+        let text = String::from_utf8(response.to_bytes()).unwrap();
+        assert!(text.starts_with("HTTP/1.1 404 Not Found\r\n"));
+        assert!(text.contains("Cache-Control: no-store\r\nContent-Length: 11\r\n"));
+        assert!(text.ends_with("\r\n\r\nNo schedule"));
+    }
+}
@@ -0,0 +1,480 @@
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use crate::models::{PrayerName, SpecialDay};
+
+/// Languages backend-generated text is available in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize, TS)]
+#[serde(rename_all = "lowercase")]
+#[ts(export)]
+pub enum Language {
+    #[default]
+    En,
+    Ar,
+    Fr,
+    Tr,
+    Ur,
+}
+
+/// Translated fragments for one language
+struct Messages {
+    /// Fajr, Dhuhr, Asr, Maghrib, Isha, Jumuah
+    prayers: [&'static str; 6],
+    hours: &'static str,
+    minutes: &'static str,
+    seconds: &'static str,
+    /// `{prayer}`, `{minutes}`
+    adhan_reminder: &'static str,
+    iqama_reminder: &'static str,
+    prayer_start: &'static str,
+    /// `{prayer}`, `{minutes}` for a changed iqama
+    iqama_later: &'static str,
+    iqama_earlier: &'static str,
+    /// Title of a goal covering every daily prayer
+    goal_any_prayer: &'static str,
+    /// `{count}`, `{target}` for the end-of-week goal summary
+    goal_met: &'static str,
+    goal_missed: &'static str,
+    /// `{prayer}`, confirmation of a hotkey marking attendance
+    marked_attended: &'static str,
+    /// `{minutes}`
+    reminder_snoozed: &'static str,
+    nothing_to_snooze: &'static str,
+    /// `{prayer}`, `{minutes}`, spoken aloud so units are written out
+    adhan_announcement: &'static str,
+    iqama_announcement: &'static str,
+    /// Ramadan start, Laylat al-Qadr, Eid al-Fitr, Dhul Hijjah start and days, Arafah, Eid al-Adha, Ashura
+    special_days: [&'static str; 8],
+    /// `{event}` starting at this evening's Maghrib
+    special_day_tonight: &'static str,
+    /// `{minutes}` left before sunrise ends Fajr's window
+    fajr_ending: &'static str,
+    /// Fajr alarm rings, gentle to urgent; `{minutes}` to the adhan in the first
+    fajr_alarm: [&'static str; 3],
+    /// `{prayer}`, `{time}`, `{mosque}` for a snippet shared to a group chat, by iqama or adhan time
+    share_iqama: &'static str,
+    share_adhan: &'static str,
+    /// `{time}` to leave by, appended to the shared snippet
+    share_leave_by: &'static str,
+    am: &'static str,
+    pm: &'static str,
+}
+
+const EN: Messages = Messages {
+    prayers: ["Fajr", "Dhuhr", "Asr", "Maghrib", "Isha", "Jumuah"],
+    hours: "h",
+    minutes: "m",
+    seconds: "s",
+    adhan_reminder: "{prayer} adhan in {minutes} min",
+    iqama_reminder: "{prayer} iqama in {minutes} min",
+    prayer_start: "{prayer} prayer is starting",
+    iqama_later: "{prayer} iqama moved {minutes} min later",
+    iqama_earlier: "{prayer} iqama moved {minutes} min earlier",
+    goal_any_prayer: "Prayers",
+    goal_met: "Goal reached this week: {count} of {target}. May Allah accept it!",
+    goal_missed: "{count} of {target} this week. Every prayer counts, a new week starts tomorrow",
+    marked_attended: "{prayer} marked as prayed in jamaah",
+    reminder_snoozed: "Reminder snoozed for {minutes} min",
+    nothing_to_snooze: "No reminder to snooze",
+    adhan_announcement: "{prayer} adhan in {minutes} minutes",
+    iqama_announcement: "{prayer} iqama in {minutes} minutes",
+    special_days: ["Start of Ramadan", "Laylat al-Qadr", "Eid al-Fitr", "First ten days of Dhul Hijjah", "Ten days of Dhul Hijjah", "Day of Arafah", "Eid al-Adha", "Ashura"],
+    special_day_tonight: "{event} begins tonight at Maghrib",
+    fajr_ending: "Fajr time ends in {minutes} minutes, pray before sunrise",
+    fajr_alarm: [
+        "Fajr adhan in {minutes} min, time to wake up",
+        "Wake up for Fajr! Answer the question to stop the alarm",
+        "Get up now, Fajr is here! Answer the question to stop the alarm",
+    ],
+    share_iqama: "{prayer} iqama {time} at {mosque}",
+    share_adhan: "{prayer} adhan {time} at {mosque}",
+    share_leave_by: ", leave by {time}",
+    am: "AM",
+    pm: "PM",
+};
+
+const AR: Messages = Messages {
+    prayers: ["الفجر", "الظهر", "العصر", "المغرب", "العشاء", "الجمعة"],
+    hours: "س",
+    minutes: "د",
+    seconds: "ث",
+    adhan_reminder: "أذان {prayer} بعد {minutes} دقيقة",
+    iqama_reminder: "إقامة {prayer} بعد {minutes} دقيقة",
+    prayer_start: "صلاة {prayer} تبدأ الآن",
+    iqama_later: "تأخرت إقامة {prayer} {minutes} دقيقة",
+    iqama_earlier: "تقدمت إقامة {prayer} {minutes} دقيقة",
+    goal_any_prayer: "الصلوات",
+    goal_met: "بلغت هدفك هذا الأسبوع: {count} من {target}. تقبل الله منك",
+    goal_missed: "{count} من {target} هذا الأسبوع. كل صلاة لها أجرها، وأسبوع جديد يبدأ غدًا",
+    marked_attended: "سُجّلت صلاة {prayer} جماعة",
+    reminder_snoozed: "تم تأجيل التذكير {minutes} دقيقة",
+    nothing_to_snooze: "لا يوجد تذكير لتأجيله",
+    adhan_announcement: "أذان {prayer} بعد {minutes} دقيقة",
+    iqama_announcement: "إقامة {prayer} بعد {minutes} دقيقة",
+    special_days: ["بداية رمضان", "ليلة القدر", "عيد الفطر", "العشر الأوائل من ذي الحجة", "العشر من ذي الحجة", "يوم عرفة", "عيد الأضحى", "عاشوراء"],
+    special_day_tonight: "{event} يبدأ الليلة مع المغرب",
+    fajr_ending: "ينتهي وقت الفجر بعد {minutes} دقيقة، صلِّ قبل الشروق",
+    fajr_alarm: [
+        "أذان الفجر بعد {minutes} دقيقة، حان وقت الاستيقاظ",
+        "استيقظ لصلاة الفجر! أجب عن السؤال لإيقاف المنبه",
+        "قم الآن، حان الفجر! أجب عن السؤال لإيقاف المنبه",
+    ],
+    share_iqama: "إقامة {prayer} {time} في {mosque}",
+    share_adhan: "أذان {prayer} {time} في {mosque}",
+    share_leave_by: "، الانطلاق قبل {time}",
+    am: "ص",
+    pm: "م",
+};
+
+const FR: Messages = Messages {
+    prayers: ["Fajr", "Dhohr", "Asr", "Maghreb", "Icha", "Joumou'a"],
+    hours: "h",
+    minutes: "min",
+    seconds: "s",
+    adhan_reminder: "Adhan de {prayer} dans {minutes} min",
+    iqama_reminder: "Iqama de {prayer} dans {minutes} min",
+    prayer_start: "La prière de {prayer} commence",
+    iqama_later: "Iqama de {prayer} décalée de {minutes} min plus tard",
+    iqama_earlier: "Iqama de {prayer} avancée de {minutes} min",
+    goal_any_prayer: "Prières",
+    goal_met: "Objectif atteint cette semaine : {count} sur {target}. Qu'Allah l'accepte !",
+    goal_missed: "{count} sur {target} cette semaine. Chaque prière compte, une nouvelle semaine commence demain",
+    marked_attended: "{prayer} notée comme priée en jamaah",
+    reminder_snoozed: "Rappel reporté de {minutes} min",
+    nothing_to_snooze: "Aucun rappel à reporter",
+    adhan_announcement: "Adhan de {prayer} dans {minutes} minutes",
+    iqama_announcement: "Iqama de {prayer} dans {minutes} minutes",
+    special_days: ["Début du Ramadan", "Laylat al-Qadr", "Aïd al-Fitr", "Les dix premiers jours de Dhoul Hijja", "Les dix jours de Dhoul Hijja", "Jour de Arafat", "Aïd al-Adha", "Achoura"],
+    special_day_tonight: "{event} commence ce soir au Maghreb",
+    fajr_ending: "Le temps de Fajr se termine dans {minutes} minutes, priez avant le lever du soleil",
+    fajr_alarm: [
+        "Adhan de Fajr dans {minutes} min, il est temps de se lever",
+        "Réveillez-vous pour Fajr ! Répondez à la question pour arrêter l'alarme",
+        "Levez-vous maintenant, c'est Fajr ! Répondez à la question pour arrêter l'alarme",
+    ],
+    share_iqama: "Iqama de {prayer} à {time} à {mosque}",
+    share_adhan: "Adhan de {prayer} à {time} à {mosque}",
+    share_leave_by: ", départ à {time}",
+    am: "AM",
+    pm: "PM",
+};
+
+const TR: Messages = Messages {
+    prayers: ["Sabah", "Öğle", "İkindi", "Akşam", "Yatsı", "Cuma"],
+    hours: "sa",
+    minutes: "dk",
+    seconds: "sn",
+    adhan_reminder: "{prayer} ezanına {minutes} dk",
+    iqama_reminder: "{prayer} kametine {minutes} dk",
+    prayer_start: "{prayer} namazı başlıyor",
+    iqama_later: "{prayer} kameti {minutes} dk ertelendi",
+    iqama_earlier: "{prayer} kameti {minutes} dk öne alındı",
+    goal_any_prayer: "Namazlar",
+    goal_met: "Bu haftaki hedefine ulaştın: {count}/{target}. Allah kabul etsin!",
+    goal_missed: "Bu hafta {count}/{target}. Her namaz değerlidir, yarın yeni bir hafta başlıyor",
+    marked_attended: "{prayer} cemaatle kılındı olarak işaretlendi",
+    reminder_snoozed: "Hatırlatma {minutes} dk ertelendi",
+    nothing_to_snooze: "Ertelenecek hatırlatma yok",
+    adhan_announcement: "{prayer} ezanına {minutes} dakika var",
+    iqama_announcement: "{prayer} kametine {minutes} dakika var",
+    special_days: ["Ramazan başlangıcı", "Kadir Gecesi", "Ramazan Bayramı", "Zilhicce'nin ilk on günü", "Zilhicce'nin on günü", "Arefe günü", "Kurban Bayramı", "Aşure"],
+    special_day_tonight: "{event} bu akşam akşam ezanıyla başlıyor",
+    fajr_ending: "Sabah namazı vaktinin çıkmasına {minutes} dakika var, güneş doğmadan kılın",
+    fajr_alarm: [
+        "Sabah ezanına {minutes} dk, uyanma vakti",
+        "Sabah namazı için uyanın! Alarmı durdurmak için soruyu cevaplayın",
+        "Hemen kalkın, sabah vakti geldi! Alarmı durdurmak için soruyu cevaplayın",
+    ],
+    share_iqama: "{mosque}: {prayer} kameti {time}",
+    share_adhan: "{mosque}: {prayer} ezanı {time}",
+    share_leave_by: ", çıkış saati {time}",
+    am: "ÖÖ",
+    pm: "ÖS",
+};
+
+const UR: Messages = Messages {
+    prayers: ["فجر", "ظہر", "عصر", "مغرب", "عشاء", "جمعہ"],
+    hours: "گھنٹے",
+    minutes: "منٹ",
+    seconds: "سیکنڈ",
+    adhan_reminder: "{prayer} کی اذان {minutes} منٹ میں",
+    iqama_reminder: "{prayer} کی اقامت {minutes} منٹ میں",
+    prayer_start: "{prayer} کی نماز شروع ہو رہی ہے",
+    iqama_later: "{prayer} کی اقامت {minutes} منٹ مؤخر ہو گئی",
+    iqama_earlier: "{prayer} کی اقامت {minutes} منٹ پہلے ہو گئی",
+    goal_any_prayer: "نمازیں",
+    goal_met: "اس ہفتے آپ نے اپنا ہدف پورا کر لیا: {target} میں سے {count}۔ اللہ قبول فرمائے",
+    goal_missed: "اس ہفتے {target} میں سے {count}۔ ہر نماز قیمتی ہے، کل نیا ہفتہ شروع ہو رہا ہے",
+    marked_attended: "{prayer} باجماعت ادا شدہ درج ہو گئی",
+    reminder_snoozed: "یاد دہانی {minutes} منٹ کے لیے مؤخر",
+    nothing_to_snooze: "مؤخر کرنے کے لیے کوئی یاد دہانی نہیں",
+    adhan_announcement: "{prayer} کی اذان {minutes} منٹ میں",
+    iqama_announcement: "{prayer} کی اقامت {minutes} منٹ میں",
+    special_days: ["رمضان کا آغاز", "لیلۃ القدر", "عید الفطر", "ذوالحجہ کے پہلے دس دن", "ذوالحجہ کے دس دن", "یوم عرفہ", "عید الاضحی", "عاشورہ"],
+    special_day_tonight: "{event} آج رات مغرب سے شروع ہو رہا ہے",
+    fajr_ending: "فجر کا وقت {minutes} منٹ میں ختم ہو رہا ہے، طلوع آفتاب سے پہلے نماز پڑھ لیں",
+    fajr_alarm: [
+        "فجر کی اذان {minutes} منٹ میں، اٹھنے کا وقت ہے",
+        "فجر کے لیے جاگیں! الارم بند کرنے کے لیے سوال کا جواب دیں",
+        "ابھی اٹھیں، فجر کا وقت ہو گیا! الارم بند کرنے کے لیے سوال کا جواب دیں",
+    ],
+    share_iqama: "{mosque} میں {prayer} کی اقامت {time}",
+    share_adhan: "{mosque} میں {prayer} کی اذان {time}",
+    share_leave_by: "، {time} تک روانہ ہوں",
+    am: "صبح",
+    pm: "شام",
+};
+
+impl Language {
+    /// Parse a language code like "fr" or "fr-FR"; unknown codes fall back to English
+    pub fn from_code(code: &str) -> Self {
+        let primary = code.trim().split(['-', '_']).next().unwrap_or("").to_lowercase();
+
+        match primary.as_str() {
+            "ar" => Language::Ar,
+            "fr" => Language::Fr,
+            "tr" => Language::Tr,
+            "ur" => Language::Ur,
+            _ => Language::En,
+        }
+    }
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            Language::En => "en",
+            Language::Ar => "ar",
+            Language::Fr => "fr",
+            Language::Tr => "tr",
+            Language::Ur => "ur",
+        }
+    }
+
+    fn messages(&self) -> &'static Messages {
+        match self {
+            Language::En => &EN,
+            Language::Ar => &AR,
+            Language::Fr => &FR,
+            Language::Tr => &TR,
+            Language::Ur => &UR,
+        }
+    }
+
+    /// Display name of a prayer
+    pub fn prayer_name(&self, prayer: PrayerName) -> &'static str {
+        let index = match prayer {
+            PrayerName::Fajr => 0,
+            PrayerName::Dhuhr => 1,
+            PrayerName::Asr => 2,
+            PrayerName::Maghrib => 3,
+            PrayerName::Isha => 4,
+            PrayerName::Jumuah => 5,
+        };
+        self.messages().prayers[index]
+    }
+
+    /// Compact duration like "1h 5m" / "1 س 5 د", same rules as `PrayerEngine::format_duration`
+    pub fn format_duration(&self, seconds: i64) -> String {
+        let m = self.messages();
+        let hours = seconds / 3600;
+        let minutes = (seconds % 3600) / 60;
+        let secs = seconds % 60;
+
+        if hours > 0 {
+            format!("{} {}", self.unit(hours, m.hours), self.unit(minutes, m.minutes))
+        } else if minutes > 0 {
+            format!("{} {}", self.unit(minutes, m.minutes), self.unit(secs, m.seconds))
+        } else {
+            self.unit(secs, m.seconds)
+        }
+    }
+
+    /// Duration already rounded to whole minutes, like "46m" rather than "46m 0s"
+    pub fn format_minutes(&self, seconds: i64) -> String {
+        if seconds >= 3600 {
+            return self.format_duration(seconds);
+        }
+        self.unit(seconds / 60, self.messages().minutes)
+    }
+
+    /// Latin-script units attach to the number, others are separate words
+    fn unit(&self, value: i64, unit: &str) -> String {
+        match self {
+            Language::Ar | Language::Ur => format!("{} {}", value, unit),
+            _ => format!("{}{}", value, unit),
+        }
+    }
+
+    pub fn adhan_reminder(&self, prayer: PrayerName, minutes: i64) -> String {
+        self.fill(self.messages().adhan_reminder, prayer, minutes)
+    }
+
+    pub fn iqama_reminder(&self, prayer: PrayerName, minutes: i64) -> String {
+        self.fill(self.messages().iqama_reminder, prayer, minutes)
+    }
+
+    /// Reminder worded to be read aloud, e.g. "Isha iqama in 10 minutes"
+    pub fn adhan_announcement(&self, prayer: PrayerName, minutes: i64) -> String {
+        self.fill(self.messages().adhan_announcement, prayer, minutes)
+    }
+
+    pub fn iqama_announcement(&self, prayer: PrayerName, minutes: i64) -> String {
+        self.fill(self.messages().iqama_announcement, prayer, minutes)
+    }
+
+    pub fn special_day(&self, day: SpecialDay) -> &'static str {
+        let index = match day {
+            SpecialDay::RamadanStart => 0,
+            SpecialDay::LaylatAlQadr => 1,
+            SpecialDay::EidAlFitr => 2,
+            SpecialDay::DhulHijjahStart => 3,
+            SpecialDay::DhulHijjahDay => 4,
+            SpecialDay::DayOfArafah => 5,
+            SpecialDay::EidAlAdha => 6,
+            SpecialDay::Ashura => 7,
+        };
+        self.messages().special_days[index]
+    }
+
+    /// Reminder on the evening a special day's night begins, as Islamic days start at Maghrib
+    pub fn special_day_tonight(&self, day: SpecialDay) -> String {
+        self.messages().special_day_tonight.replace("{event}", self.special_day(day))
+    }
+
+    /// Warning that Fajr's window closes at sunrise, worded to be read aloud as well
+    pub fn fajr_ending(&self, minutes: i64) -> String {
+        self.fill(self.messages().fajr_ending, PrayerName::Fajr, minutes)
+    }
+
+    /// Fajr alarm text for a ring `level` (1 is the first), more urgent as it grows
+    pub fn fajr_alarm(&self, level: u32, minutes_to_fajr: i64) -> String {
+        let texts = self.messages().fajr_alarm;
+        let text = texts[(level.max(1) as usize - 1).min(texts.len() - 1)];
+        text.replace("{minutes}", &minutes_to_fajr.max(0).to_string())
+    }
+
+    pub fn prayer_start(&self, prayer: PrayerName) -> String {
+        self.fill(self.messages().prayer_start, prayer, 0)
+    }
+
+    /// Iqama moved by `shift_minutes`, positive when later
+    pub fn iqama_moved(&self, prayer: PrayerName, shift_minutes: i64) -> String {
+        let m = self.messages();
+        let template = if shift_minutes >= 0 { m.iqama_later } else { m.iqama_earlier };
+        self.fill(template, prayer, shift_minutes.abs())
+    }
+
+    /// Name of the prayer a goal counts, or a word for all prayers
+    pub fn goal_subject(&self, prayer: Option<PrayerName>) -> &'static str {
+        match prayer {
+            Some(prayer) => self.prayer_name(prayer),
+            None => self.messages().goal_any_prayer,
+        }
+    }
+
+    /// Encouraging end-of-week summary of a goal
+    pub fn goal_summary(&self, completed: u32, target: u32) -> String {
+        let m = self.messages();
+        let template = if completed >= target { m.goal_met } else { m.goal_missed };
+        template
+            .replace("{count}", &completed.to_string())
+            .replace("{target}", &target.to_string())
+    }
+
+    pub fn marked_attended(&self, prayer: PrayerName) -> String {
+        self.fill(self.messages().marked_attended, prayer, 0)
+    }
+
+    /// Confirmation of a snoozed reminder, or that there was none
+    pub fn reminder_snoozed(&self, minutes: Option<i64>) -> String {
+        let m = self.messages();
+        match minutes {
+            Some(minutes) => m.reminder_snoozed.replace("{minutes}", &minutes.to_string()),
+            None => m.nothing_to_snooze.to_string(),
+        }
+    }
+
+    /// Snippet for a group chat, e.g. "Isha iqama 21:45 at An-Nour, leave by 21:25"
+    /// Uses the adhan wording when `time` is the adhan because the mosque posts no iqama
+    pub fn share_text(
+        &self,
+        prayer: PrayerName,
+        is_iqama: bool,
+        time: &str,
+        mosque: &str,
+        leave_by: Option<&str>,
+    ) -> String {
+        let m = self.messages();
+        let template = if is_iqama { m.share_iqama } else { m.share_adhan };
+        let mut text = template
+            .replace("{prayer}", self.prayer_name(prayer))
+            .replace("{time}", time)
+            .replace("{mosque}", mosque);
+        if let Some(leave_by) = leave_by {
+            text.push_str(&m.share_leave_by.replace("{time}", leave_by));
+        }
+        text
+    }
+
+    /// AM/PM marker used by 12-hour times
+    pub fn day_period(&self, is_pm: bool) -> &'static str {
+        let m = self.messages();
+        if is_pm {
+            m.pm
+        } else {
+            m.am
+        }
+    }
+
+    fn fill(&self, template: &str, prayer: PrayerName, minutes: i64) -> String {
+        template
+            .replace("{prayer}", self.prayer_name(prayer))
+            .replace("{minutes}", &minutes.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_language_from_code() {
+        assert_eq!(Language::from_code("fr-FR"), Language::Fr);
+        assert_eq!(Language::from_code("AR"), Language::Ar);
+        assert_eq!(Language::from_code("tr_TR"), Language::Tr);
+        assert_eq!(Language::from_code("de"), Language::En);
+        assert_eq!(Language::from_code(""), Language::En);
+    }
+
+    #[test]
+    fn test_localized_strings() {
+        assert_eq!(Language::Fr.prayer_name(PrayerName::Dhuhr), "Dhohr");
+        assert_eq!(Language::En.format_duration(3900), "1h 5m");
+        assert_eq!(Language::Fr.format_duration(125), "2min 5s");
+        assert_eq!(Language::Ar.format_duration(45), "45 ث");
+        assert_eq!(Language::En.format_minutes(46 * 60), "46m");
+        assert_eq!(
+            Language::Tr.iqama_reminder(PrayerName::Asr, 10),
+            "İkindi kametine 10 dk"
+        );
+        assert_eq!(
+            Language::En.iqama_moved(PrayerName::Isha, 15),
+            "Isha iqama moved 15 min later"
+        );
+        assert_eq!(
+            Language::Fr.iqama_moved(PrayerName::Isha, -5),
+            "Iqama de Icha avancée de 5 min"
+        );
+        assert_eq!(
+            Language::En.goal_summary(3, 5),
+            "3 of 5 this week. Every prayer counts, a new week starts tomorrow"
+        );
+        assert_eq!(
+            Language::En.share_text(PrayerName::Isha, true, "21:45", "An-Nour", Some("21:25")),
+            "Isha iqama 21:45 at An-Nour, leave by 21:25"
+        );
+        assert_eq!(
+            Language::Fr.share_text(PrayerName::Maghrib, false, "19:02", "Mosquée de Lyon", None),
+            "Adhan de Maghreb à 19:02 à Mosquée de Lyon"
+        );
+    }
+}
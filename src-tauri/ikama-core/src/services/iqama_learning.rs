@@ -0,0 +1,121 @@
+use std::collections::HashMap;
+
+use crate::models::{IqamaDelay, PrayerName};
+
+/// Reports older than this no longer describe when the imam starts
+pub const IQAMA_LEARNING_DAYS: i64 = 60;
+
+/// Reports needed before a delay is trusted
+const MIN_REPORTS: usize = 3;
+/// Delays below this are reading the clock differently, not starting late
+const MIN_DELAY_SECS: i64 = 60;
+const MAX_DELAY_SECS: i64 = 15 * 60;
+
+/// Typical delay of a prayer when the mosque consistently starts late
+/// Needs at least `MIN_REPORTS` and two thirds of them late by a minute or more
+pub fn learn_iqama_delay(delays: &[i64]) -> Option<i64> {
+    if delays.len() < MIN_REPORTS {
+        return None;
+    }
+
+    let late = delays.iter().filter(|&&d| d >= MIN_DELAY_SECS).count();
+    if late * 3 < delays.len() * 2 {
+        return None;
+    }
+
+    let mut sorted = delays.to_vec();
+    sorted.sort_unstable();
+    let median = sorted[sorted.len() / 2];
+
+    (median >= MIN_DELAY_SECS).then_some(median.min(MAX_DELAY_SECS))
+}
+
+/// Standard deviation of reported delays, how much the start varies from day to day
+pub fn learn_delay_spread(delays: &[i64]) -> Option<i64> {
+    if delays.len() < MIN_REPORTS {
+        return None;
+    }
+
+    let mean = delays.iter().sum::<i64>() as f64 / delays.len() as f64;
+    let variance = delays.iter().map(|&d| (d as f64 - mean).powi(2)).sum::<f64>() / delays.len() as f64;
+    Some(variance.sqrt().round() as i64)
+}
+
+/// Learned delay per prayer from `(prayer, delay_secs)` reports
+pub fn learn_iqama_delays(reports: &[(PrayerName, i64)]) -> Vec<IqamaDelay> {
+    let mut by_prayer: HashMap<PrayerName, Vec<i64>> = HashMap::new();
+    for &(prayer, delay) in reports {
+        by_prayer.entry(prayer).or_default().push(delay);
+    }
+
+    PrayerName::DAILY
+        .iter()
+        .chain([PrayerName::Jumuah].iter())
+        .filter_map(|prayer| {
+            let delays = by_prayer.get(prayer)?;
+            Some(IqamaDelay {
+                prayer: *prayer,
+                delay_secs: learn_iqama_delay(delays),
+                spread_secs: learn_delay_spread(delays),
+                report_count: delays.len() as u32,
+            })
+        })
+        .collect()
+}
+
+/// Start lag per prayer for `PrayerEngine::with_start_lags`
+pub fn learned_start_lags(delays: &[IqamaDelay]) -> HashMap<PrayerName, i64> {
+    delays
+        .iter()
+        .filter_map(|d| Some((d.prayer, d.delay_secs?)))
+        .collect()
+}
+
+/// Start uncertainty per prayer for `PrayerEngine::with_start_spreads`
+pub fn learned_start_spreads(delays: &[IqamaDelay]) -> HashMap<PrayerName, i64> {
+    delays
+        .iter()
+        .filter_map(|d| Some((d.prayer, d.spread_secs?)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::MosqueStartLag;
+
+    #[test]
+    fn test_learns_only_consistent_delays() {
+        assert_eq!(learn_iqama_delay(&[240, 300]), None);
+        assert_eq!(learn_iqama_delay(&[240, 300, 180, 0]), Some(240));
+        assert_eq!(learn_iqama_delay(&[240, 0, 0, 30]), None);
+        assert_eq!(learn_iqama_delay(&[3600, 3600, 3600]), Some(MAX_DELAY_SECS));
+        assert_eq!(learn_delay_spread(&[120, 240, 360]), Some(98));
+        assert_eq!(learn_delay_spread(&[120, 240]), None);
+
+        let delays = learn_iqama_delays(&[
+            (PrayerName::Isha, 300),
+            (PrayerName::Fajr, 0),
+            (PrayerName::Isha, 240),
+            (PrayerName::Isha, 360),
+        ]);
+        let lags = learned_start_lags(&delays);
+        assert_eq!(lags.get(&PrayerName::Isha), Some(&300));
+        assert!(!lags.contains_key(&PrayerName::Fajr));
+    }
+
+    #[test]
+    fn test_configured_lag_overrides_learned() {
+        let learned = [(PrayerName::Isha, 300), (PrayerName::Fajr, 120)].into();
+        let config = MosqueStartLag {
+            default_secs: Some(60),
+            prayers: [(PrayerName::Isha, 180)].into(),
+        };
+
+        let lags = config.resolve(&learned);
+        assert_eq!(lags[&PrayerName::Isha], 180);
+        assert_eq!(lags[&PrayerName::Fajr], 120);
+        assert_eq!(lags[&PrayerName::Asr], 60);
+        assert!(MosqueStartLag::default().resolve(&HashMap::new()).is_empty());
+    }
+}
@@ -0,0 +1,544 @@
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+use anyhow::{anyhow, bail};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::db::{read_legacy_database, Database};
+use crate::models::*;
+use crate::services::clock;
+
+const SQLITE_HEADER: &[u8] = b"SQLite format 3\0";
+
+/// Tell a legacy file's format from its first bytes, then its extension
+pub fn detect_source(path: &Path) -> anyhow::Result<LegacySource> {
+    let mut header = [0u8; 16];
+    let read = File::open(path)?.read(&mut header)?;
+    if &header[..read] == SQLITE_HEADER {
+        return Ok(LegacySource::IkamaDatabase);
+    }
+
+    let extension = path.extension().and_then(|ext| ext.to_str()).map(str::to_ascii_lowercase);
+    match extension.as_deref() {
+        Some("json") => Ok(LegacySource::MawaqitExport),
+        Some("csv") => Ok(LegacySource::Csv),
+        Some("db" | "sqlite") => bail!("{} is not a SQLite database", path.display()),
+        _ => match header[..read].iter().find(|byte| !byte.is_ascii_whitespace()) {
+            Some(b'{' | b'[') => Ok(LegacySource::MawaqitExport),
+            Some(_) => Ok(LegacySource::Csv),
+            None => bail!("{} is empty", path.display()),
+        },
+    }
+}
+
+/// Read a legacy file of any supported format; CSV history without a profile column goes to `profile`
+pub async fn read_legacy_source(path: &Path, profile: &str) -> anyhow::Result<LegacyData> {
+    match detect_source(path)? {
+        LegacySource::IkamaDatabase => read_legacy_database(path).await,
+        LegacySource::MawaqitExport => parse_mawaqit_export(&std::fs::read_to_string(path)?),
+        LegacySource::Csv => parse_legacy_csv(&std::fs::read_to_string(path)?, profile),
+    }
+}
+
+/// Merge legacy data into the database without overwriting anything already there:
+/// known favorites, cached days and logged prayers are kept and counted as skipped
+pub async fn import_legacy_data(
+    db: &Database,
+    data: LegacyData,
+    path: &Path,
+    profile: &str,
+) -> anyhow::Result<LegacyImportSummary> {
+    let mut summary = LegacyImportSummary {
+        source: data.source,
+        path: path.display().to_string(),
+        favorites: 0,
+        schedules: 0,
+        log_entries: 0,
+        selected_mosque_id: None,
+        skipped: data.skipped,
+    };
+
+    let mut active = None;
+    for favorite in data.favorites {
+        let mosque = match db.get_mosque(&favorite.mosque.id).await? {
+            Some(existing) if existing.is_favorite => {
+                summary.skipped += 1;
+                existing
+            }
+            Some(mut existing) => {
+                db.set_favorite(&existing.id, true).await?;
+                existing.is_favorite = true;
+                summary.favorites += 1;
+                existing
+            }
+            None => {
+                db.save_mosque(&favorite.mosque).await?;
+                summary.favorites += 1;
+                favorite.mosque
+            }
+        };
+
+        if let Some(secs) = favorite.travel_time_secs {
+            if db.get_travel_times(&mosque.id).await?.is_empty() {
+                // The mode wasn't recorded; walking is what most users timed
+                db.set_travel_time(&mosque.id, TravelMode::Walking, Some(secs)).await?;
+            }
+        }
+        if favorite.active {
+            active = Some(mosque);
+        }
+    }
+
+    if let Some(mosque) = active {
+        if db.get_selected_mosque(profile).await?.is_none() {
+            summary.selected_mosque_id = Some(mosque.id.clone());
+            db.save_selected_mosque(&SelectedMosque {
+                profile: profile.to_string(),
+                mosque,
+                selected_at: clock::now(),
+            })
+            .await?;
+        }
+    }
+
+    let mut schedules = Vec::new();
+    for times in data.schedules {
+        let cached = match times.mosque_id.as_deref() {
+            Some(mosque_id) => db.get_prayer_times(mosque_id, times.date.date_naive()).await?.is_some(),
+            None => true,
+        };
+        if cached {
+            summary.skipped += 1;
+        } else {
+            schedules.push(times);
+        }
+    }
+    db.save_prayer_times_bulk(&schedules).await?;
+    summary.schedules = schedules.len() as u32;
+
+    for entry in data.log_entries {
+        if db.get_prayer_log_entry(&entry.profile, entry.prayer, entry.date).await?.is_some() {
+            summary.skipped += 1;
+        } else {
+            db.save_prayer_log_entry(&entry).await?;
+            summary.log_entries += 1;
+        }
+    }
+
+    Ok(summary)
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FlutterPrayerTimes {
+    date: String,
+    fajr: FlutterPrayer,
+    dhuhr: FlutterPrayer,
+    asr: FlutterPrayer,
+    maghrib: FlutterPrayer,
+    isha: FlutterPrayer,
+    jumuah: Option<FlutterPrayer>,
+    cached_at: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FlutterPrayer {
+    adhan: String,
+    iqama: Option<String>,
+    custom_rakah_count: Option<i32>,
+}
+
+/// A day cached by the Flutter app, whose times without an offset are wall-clock times in `tz`
+pub fn parse_flutter_prayer_times<Tz: TimeZone>(json: &str, mosque_id: &str, tz: &Tz) -> anyhow::Result<PrayerTimes> {
+    let raw: FlutterPrayerTimes = serde_json::from_str(json)?;
+    // The calendar day as written, not shifted to UTC
+    let date = raw
+        .date
+        .get(..10)
+        .and_then(|day| NaiveDate::parse_from_str(day, "%Y-%m-%d").ok())
+        .ok_or_else(|| anyhow!("Invalid date: {}", raw.date))?;
+
+    let prayer = |name: PrayerName, prayer: &FlutterPrayer| -> anyhow::Result<Prayer> {
+        Ok(Prayer {
+            name,
+            adhan: parse_dart_time(&prayer.adhan, tz)?,
+            iqama: prayer.iqama.as_deref().map(|iqama| parse_dart_time(iqama, tz)).transpose()?,
+            custom_rakah_count: prayer.custom_rakah_count,
+            iqama_offset_minutes: None,
+        })
+    };
+
+    Ok(PrayerTimes {
+        date: date.and_hms_opt(0, 0, 0).unwrap().and_utc(),
+        fajr: prayer(PrayerName::Fajr, &raw.fajr)?,
+        dhuhr: prayer(PrayerName::Dhuhr, &raw.dhuhr)?,
+        asr: prayer(PrayerName::Asr, &raw.asr)?,
+        maghrib: prayer(PrayerName::Maghrib, &raw.maghrib)?,
+        isha: prayer(PrayerName::Isha, &raw.isha)?,
+        jumuah: raw.jumuah.as_ref().map(|jumuah| prayer(PrayerName::Jumuah, jumuah)).transpose()?,
+        mosque_id: Some(mosque_id.to_string()),
+        mosque_name: None,
+        cached_at: raw.cached_at.as_deref().and_then(|at| parse_dart_time(at, tz).ok()),
+        imsak: None,
+        shuruq: None,
+        asr_method: None,
+        provider: None,
+        freshness: None,
+        warnings: Vec::new(),
+    })
+}
+
+/// Dart's `toIso8601String()`: `Z`-suffixed for UTC times, without an offset for local ones
+fn parse_dart_time<Tz: TimeZone>(raw: &str, tz: &Tz) -> anyhow::Result<DateTime<Utc>> {
+    if let Ok(time) = DateTime::parse_from_rfc3339(raw) {
+        return Ok(time.with_timezone(&Utc));
+    }
+    let local = NaiveDateTime::parse_from_str(raw, "%Y-%m-%dT%H:%M:%S%.f")
+        .map_err(|e| anyhow!("Invalid time {}: {}", raw, e))?;
+    Ok(clock::resolve_local(tz, local))
+}
+
+/// A mosque as the Mawaqit mobile app exports it; only the slug is needed to fetch its times
+#[derive(Debug, Deserialize)]
+struct ExportedMosque {
+    slug: Option<String>,
+    name: Option<String>,
+    address: Option<String>,
+    city: Option<String>,
+    #[serde(rename = "countryCode")]
+    country_code: Option<String>,
+    #[serde(rename = "countryFullName")]
+    country_full_name: Option<String>,
+    #[serde(alias = "lat")]
+    latitude: Option<f64>,
+    #[serde(alias = "lng", alias = "lon")]
+    longitude: Option<f64>,
+}
+
+impl ExportedMosque {
+    fn into_mosque(self) -> Option<Mosque> {
+        let slug = self.slug.filter(|slug| !slug.trim().is_empty())?;
+        let name = self.name.filter(|name| !name.trim().is_empty()).unwrap_or_else(|| slug.clone());
+
+        let mut mosque = Mosque::new(slug, name);
+        mosque.address = self.address;
+        mosque.city = self.city;
+        mosque.country = self.country_code.or(self.country_full_name);
+        mosque.latitude = self.latitude;
+        mosque.longitude = self.longitude;
+        mosque.is_favorite = true;
+        Some(mosque)
+    }
+}
+
+/// Favorites from a Mawaqit export: a list of mosques, or an object holding one
+pub fn parse_mawaqit_export(json: &str) -> anyhow::Result<LegacyData> {
+    let entries = match serde_json::from_str(json)? {
+        Value::Array(entries) => entries,
+        Value::Object(mut object) => ["favorites", "favoriteMosques", "mosques"]
+            .iter()
+            .find_map(|key| match object.remove(*key) {
+                Some(Value::Array(entries)) => Some(entries),
+                _ => None,
+            })
+            .ok_or_else(|| anyhow!("No favorite mosques in the Mawaqit export"))?,
+        _ => bail!("Unrecognized Mawaqit export"),
+    };
+
+    let mut data = LegacyData::new(LegacySource::MawaqitExport);
+    for entry in entries {
+        match serde_json::from_value::<ExportedMosque>(entry).ok().and_then(ExportedMosque::into_mosque) {
+            Some(mosque) => data.favorites.push(LegacyFavorite {
+                mosque,
+                travel_time_secs: None,
+                active: false,
+            }),
+            None => data.skipped += 1,
+        }
+    }
+
+    Ok(data)
+}
+
+/// Favorites (`id`, `name`, ...) or logged prayers (`date`, `prayer`, ...), told apart by the header
+/// One record per line; fields may be quoted, separated by commas or semicolons
+pub fn parse_legacy_csv(text: &str, profile: &str) -> anyhow::Result<LegacyData> {
+    let mut lines = text.trim_start_matches('\u{feff}').lines().filter(|line| !line.trim().is_empty());
+    let header_line = lines.next().ok_or_else(|| anyhow!("The CSV file is empty"))?;
+    // Spreadsheets in many locales save with semicolons
+    let delimiter = if header_line.matches(';').count() > header_line.matches(',').count() { ';' } else { ',' };
+    let header: Vec<String> = split_csv_line(header_line, delimiter)
+        .iter()
+        .map(|column| column.trim().to_lowercase().replace([' ', '-'], "_"))
+        .collect();
+    let column = |names: &[&str]| header.iter().position(|column| names.contains(&column.as_str()));
+
+    let mut data = LegacyData::new(LegacySource::Csv);
+    if let (Some(date), Some(prayer)) = (column(&["date"]), column(&["prayer"])) {
+        let columns = LogColumns {
+            date,
+            prayer,
+            mosque: column(&["mosque_id", "mosque"]),
+            tags: column(&["tags"]),
+            note: column(&["note", "reflection"]),
+            profile: column(&["profile"]),
+        };
+
+        for line in lines {
+            match columns.entry(&split_csv_line(line, delimiter), profile) {
+                Some(entry) => data.log_entries.push(entry),
+                None => data.skipped += 1,
+            }
+        }
+    } else if let (Some(id), Some(name)) = (column(&["id", "slug", "mosque_id"]), column(&["name"])) {
+        let address = column(&["address"]);
+        let city = column(&["city"]);
+        let country = column(&["country", "country_code"]);
+        let latitude = column(&["latitude", "lat"]);
+        let longitude = column(&["longitude", "lng", "lon"]);
+        let travel_time = column(&["travel_time_secs", "travel_time_seconds"]);
+
+        for line in lines {
+            let fields = split_csv_line(line, delimiter);
+            let Some(id) = field(&fields, Some(id)) else {
+                data.skipped += 1;
+                continue;
+            };
+
+            let mut mosque = Mosque::new(id.to_string(), field(&fields, Some(name)).unwrap_or(id).to_string());
+            mosque.address = field(&fields, address).map(str::to_string);
+            mosque.city = field(&fields, city).map(str::to_string);
+            mosque.country = field(&fields, country).map(str::to_string);
+            mosque.latitude = field(&fields, latitude).and_then(|lat| lat.parse().ok());
+            mosque.longitude = field(&fields, longitude).and_then(|lng| lng.parse().ok());
+            mosque.is_favorite = true;
+
+            data.favorites.push(LegacyFavorite {
+                mosque,
+                travel_time_secs: field(&fields, travel_time)
+                    .and_then(|secs| secs.parse().ok())
+                    .filter(|secs| *secs > 0),
+                active: false,
+            });
+        }
+    } else {
+        bail!("Unrecognized CSV: expected date and prayer columns for history, or id and name columns for favorites");
+    }
+
+    Ok(data)
+}
+
+/// Positions of the prayer log columns in a history CSV
+struct LogColumns {
+    date: usize,
+    prayer: usize,
+    mosque: Option<usize>,
+    tags: Option<usize>,
+    note: Option<usize>,
+    profile: Option<usize>,
+}
+
+impl LogColumns {
+    /// The record as a log entry, `None` when the date or the prayer can't be read
+    fn entry(&self, fields: &[String], profile: &str) -> Option<PrayerLogEntry> {
+        Some(PrayerLogEntry {
+            profile: field(fields, self.profile).unwrap_or(profile).to_string(),
+            prayer: field(fields, Some(self.prayer))?.parse().ok()?,
+            date: parse_csv_date(field(fields, Some(self.date))?)?,
+            mosque_id: field(fields, self.mosque).map(str::to_string),
+            tags: field(fields, self.tags)
+                .map(|tags| tags.split(['|', ';', ' ']).filter_map(|tag| tag.trim().parse().ok()).collect())
+                .unwrap_or_default(),
+            note: field(fields, self.note).map(|note| note.chars().take(MAX_PRAYER_NOTE_CHARS).collect()),
+            logged_at: clock::now(),
+        })
+    }
+}
+
+/// A trimmed, non-empty field
+fn field(fields: &[String], index: Option<usize>) -> Option<&str> {
+    fields.get(index?).map(|field| field.trim()).filter(|field| !field.is_empty())
+}
+
+fn parse_csv_date(raw: &str) -> Option<NaiveDate> {
+    ["%Y-%m-%d", "%d/%m/%Y"]
+        .iter()
+        .find_map(|format| NaiveDate::parse_from_str(raw, format).ok())
+}
+
+/// Split a CSV record; double quotes wrap fields holding the delimiter, `""` is a literal quote
+fn split_csv_line(line: &str, delimiter: char) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                current.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            c if c == delimiter && !quoted => fields.push(std::mem::take(&mut current)),
+            c => current.push(c),
+        }
+    }
+    fields.push(current);
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::migrations::create_database;
+
+    #[test]
+    fn test_parse_flutter_prayer_times() {
+        let json = r#"{
+            "date": "2026-02-05T00:00:00.000",
+            "fajr": {"name": "Fajr", "adhan": "2026-02-05T06:40:00.000", "iqama": "2026-02-05T07:00:00.000"},
+            "dhuhr": {"name": "Dhuhr", "adhan": "2026-02-05T12:50:00.000", "iqama": null},
+            "asr": {"name": "Asr", "adhan": "2026-02-05T15:20:00.000"},
+            "maghrib": {"name": "Maghrib", "adhan": "2026-02-05T17:45:00.000", "customRakahCount": 3},
+            "isha": {"name": "Isha", "adhan": "2026-02-05T19:15:00.000Z"},
+            "jumuah": null,
+            "mosqueId": "mosquee-de-paris",
+            "cachedAt": "2026-02-04T21:00:00.000"
+        }"#;
+        let paris = chrono::FixedOffset::east_opt(3600).unwrap();
+
+        let times = parse_flutter_prayer_times(json, "mosquee-de-paris", &paris).unwrap();
+        assert_eq!(times.date.date_naive(), NaiveDate::from_ymd_opt(2026, 2, 5).unwrap());
+        assert_eq!(times.fajr.adhan.to_rfc3339(), "2026-02-05T05:40:00+00:00");
+        assert_eq!(times.fajr.iqama.unwrap().to_rfc3339(), "2026-02-05T06:00:00+00:00");
+        assert!(times.dhuhr.iqama.is_none());
+        assert_eq!(times.maghrib.custom_rakah_count, Some(3));
+        // Times the app kept in UTC keep their offset
+        assert_eq!(times.isha.adhan.to_rfc3339(), "2026-02-05T19:15:00+00:00");
+        assert!(times.jumuah.is_none());
+        assert_eq!(times.mosque_id.as_deref(), Some("mosquee-de-paris"));
+
+        assert!(parse_flutter_prayer_times(r#"{"date": "soon"}"#, "m", &paris).is_err());
+    }
+
+    #[test]
+    fn test_parse_mawaqit_export() {
+        let json = r#"{"favorites": [
+            {"slug": "grande-mosquee-de-lyon", "name": "Grande Mosquée de Lyon", "city": "Lyon",
+             "countryCode": "FR", "latitude": 45.74, "longitude": 4.87},
+            {"uuid": "3f1c", "name": "No slug"},
+            {"slug": "masjid-al-noor", "lat": 51.5, "lng": -0.12}
+        ]}"#;
+
+        let data = parse_mawaqit_export(json).unwrap();
+        assert_eq!(data.source, LegacySource::MawaqitExport);
+        assert_eq!(data.favorites.len(), 2);
+        assert_eq!(data.skipped, 1);
+        let lyon = &data.favorites[0].mosque;
+        assert_eq!(lyon.id, "grande-mosquee-de-lyon");
+        assert_eq!(lyon.country.as_deref(), Some("FR"));
+        assert!(lyon.is_favorite);
+        let noor = &data.favorites[1].mosque;
+        assert_eq!(noor.name, "masjid-al-noor");
+        assert_eq!(noor.longitude, Some(-0.12));
+
+        assert_eq!(parse_mawaqit_export(r#"[{"slug": "a", "name": "A"}]"#).unwrap().favorites.len(), 1);
+        assert!(parse_mawaqit_export(r#"{"settings": {}}"#).is_err());
+    }
+
+    #[test]
+    fn test_parse_legacy_csv() {
+        let history = "\u{feff}Date;Prayer;Mosque;Tags;Note\n\
+                       2026-03-01;Fajr;grande-mosquee-de-lyon;jamaah|on_time;\"Cold; but worth it\"\n\
+                       02/03/2026;zuhr;;late;\n\
+                       2026-03-02;Tahajjud;;;\n";
+        let data = parse_legacy_csv(history, "default").unwrap();
+        assert_eq!(data.log_entries.len(), 2);
+        assert_eq!(data.skipped, 1);
+        let fajr = &data.log_entries[0];
+        assert_eq!(fajr.prayer, PrayerName::Fajr);
+        assert_eq!(fajr.mosque_id.as_deref(), Some("grande-mosquee-de-lyon"));
+        assert_eq!(fajr.tags, vec![PrayerLogTag::Jamaah, PrayerLogTag::OnTime]);
+        assert_eq!(fajr.note.as_deref(), Some("Cold; but worth it"));
+        let dhuhr = &data.log_entries[1];
+        assert_eq!(dhuhr.prayer, PrayerName::Dhuhr);
+        assert_eq!(dhuhr.date, NaiveDate::from_ymd_opt(2026, 3, 2).unwrap());
+        assert_eq!(dhuhr.profile, "default");
+
+        let favorites = "id,name,city,lat,lng,travel_time_secs\n\
+                         masjid-al-noor,\"Masjid Al-Noor, London\",London,51.5,-0.12,600\n\
+                         ,Nameless,,,,\n";
+        let data = parse_legacy_csv(favorites, "default").unwrap();
+        assert_eq!(data.favorites.len(), 1);
+        assert_eq!(data.skipped, 1);
+        assert_eq!(data.favorites[0].mosque.name, "Masjid Al-Noor, London");
+        assert_eq!(data.favorites[0].travel_time_secs, Some(600));
+
+        assert!(parse_legacy_csv("title,author\n", "default").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_migrate_flutter_database() {
+        let dir = std::env::temp_dir().join(format!("iqamah-legacy-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("iqamah.db");
+
+        // The Flutter app's schema
+        let options = sqlx::sqlite::SqliteConnectOptions::new().filename(&path).create_if_missing(true);
+        let legacy = sqlx::SqlitePool::connect_with(options).await.unwrap();
+        for statement in [
+            "CREATE TABLE mosques (id TEXT PRIMARY KEY, name TEXT NOT NULL, address TEXT, city TEXT, country TEXT, \
+             latitude REAL, longitude REAL, is_favorite INTEGER DEFAULT 0, last_accessed INTEGER, created_at INTEGER)",
+            "CREATE TABLE prayer_times (id INTEGER PRIMARY KEY AUTOINCREMENT, mosque_id TEXT NOT NULL, \
+             date TEXT NOT NULL, data TEXT NOT NULL, cached_at INTEGER)",
+            "CREATE TABLE favorites (mosque_id TEXT PRIMARY KEY, is_active INTEGER DEFAULT 0, \
+             travel_time_seconds INTEGER DEFAULT 0, custom_rakah_duration INTEGER, added_at INTEGER)",
+            "INSERT INTO mosques (id, name, city, is_favorite) VALUES ('lyon', 'Grande Mosquée de Lyon', 'Lyon', 1)",
+            "INSERT INTO mosques (id, name, is_favorite) VALUES ('searched', 'Only searched', 0)",
+            "INSERT INTO favorites (mosque_id, is_active, travel_time_seconds) VALUES ('lyon', 1, 420)",
+        ] {
+            sqlx::query(statement).execute(&legacy).await.unwrap();
+        }
+        let day = r#"{"date": "2026-02-05T00:00:00.000Z",
+            "fajr": {"adhan": "2026-02-05T06:40:00.000Z"}, "dhuhr": {"adhan": "2026-02-05T12:50:00.000Z"},
+            "asr": {"adhan": "2026-02-05T15:20:00.000Z"}, "maghrib": {"adhan": "2026-02-05T17:45:00.000Z"},
+            "isha": {"adhan": "2026-02-05T19:15:00.000Z"}}"#;
+        for (mosque_id, data) in [("lyon", day), ("lyon", "{broken"), ("searched", day)] {
+            sqlx::query("INSERT INTO prayer_times (mosque_id, date, data) VALUES (?1, '2026-02-05', ?2)")
+                .bind(mosque_id)
+                .bind(data)
+                .execute(&legacy)
+                .await
+                .unwrap();
+        }
+        legacy.close().await;
+
+        assert_eq!(detect_source(&path).unwrap(), LegacySource::IkamaDatabase);
+        let data = read_legacy_source(&path, DEFAULT_PROFILE).await.unwrap();
+        assert_eq!(data.favorites.len(), 1);
+        assert_eq!(data.schedules.len(), 1);
+        assert_eq!(data.skipped, 1);
+
+        let db = Database::new(create_database(":memory:").await.unwrap());
+        let summary = import_legacy_data(&db, data, &path, DEFAULT_PROFILE).await.unwrap();
+        assert_eq!(summary.favorites, 1);
+        assert_eq!(summary.schedules, 1);
+        assert_eq!(summary.selected_mosque_id.as_deref(), Some("lyon"));
+        assert!(db.get_mosque("lyon").await.unwrap().unwrap().is_favorite);
+        assert_eq!(db.get_travel_times("lyon").await.unwrap()[0].travel_time_seconds, 420);
+
+        // Importing again adds nothing
+        let again = read_legacy_source(&path, DEFAULT_PROFILE).await.unwrap();
+        let summary = import_legacy_data(&db, again, &path, DEFAULT_PROFILE).await.unwrap();
+        assert_eq!((summary.favorites, summary.schedules, summary.skipped), (0, 0, 3));
+        assert_eq!(summary.selected_mosque_id, None);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
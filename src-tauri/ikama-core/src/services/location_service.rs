@@ -0,0 +1,112 @@
+use std::time::Duration;
+
+use serde::Deserialize;
+
+use crate::models::{DetectedRegion, GeoLocation, RegionSource};
+
+/// Approximate location of the caller's public IP address, over HTTPS and without an API key
+const IP_LOCATION_URL: &str = "https://ipapi.co/json/";
+const IP_LOCATION_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Location service for getting current position
+pub struct LocationService;
+
+/// Fields of the IP location answer that onboarding uses
+#[derive(Debug, Deserialize)]
+struct IpLocationResponse {
+    #[serde(default)]
+    error: bool,
+    reason: Option<String>,
+    country_code: Option<String>,
+    city: Option<String>,
+    latitude: Option<f64>,
+    longitude: Option<f64>,
+    timezone: Option<String>,
+}
+
+impl LocationService {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Get current location, approximated from the public IP address
+    pub async fn get_current_location(&self) -> anyhow::Result<GeoLocation> {
+        self.detect_region()
+            .await?
+            .location
+            .ok_or_else(|| anyhow::anyhow!("IP location has no coordinates"))
+    }
+
+    /// Country, city and coordinates of the public IP address; city-level accuracy at best
+    pub async fn detect_region(&self) -> anyhow::Result<DetectedRegion> {
+        let client = reqwest::Client::builder().timeout(IP_LOCATION_TIMEOUT).build()?;
+        let body = client
+            .get(IP_LOCATION_URL)
+            .send()
+            .await?
+            .error_for_status()?
+            .text()
+            .await?;
+        parse_ip_location(&body)
+    }
+
+    /// Calculate distance between two points
+    pub fn calculate_distance(&self, loc1: &GeoLocation, loc2: &GeoLocation) -> f64 {
+        loc1.distance_to(loc2)
+    }
+}
+
+impl Default for LocationService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn parse_ip_location(body: &str) -> anyhow::Result<DetectedRegion> {
+    let response: IpLocationResponse = serde_json::from_str(body)?;
+    if response.error {
+        return Err(anyhow::anyhow!(
+            "IP location failed: {}",
+            response.reason.unwrap_or_else(|| "unknown reason".to_string())
+        ));
+    }
+
+    let country = response
+        .country_code
+        .filter(|code| code.len() == 2 && code.chars().all(|c| c.is_ascii_alphabetic()))
+        .ok_or_else(|| anyhow::anyhow!("IP location has no country"))?;
+    let location = match (response.latitude, response.longitude) {
+        (Some(latitude), Some(longitude)) => Some(GeoLocation::new(latitude, longitude)),
+        _ => None,
+    };
+
+    Ok(DetectedRegion {
+        country: country.to_ascii_uppercase(),
+        city: response.city.filter(|city| !city.is_empty()),
+        location,
+        timezone: response.timezone,
+        source: RegionSource::Ip,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ip_location() {
+        let region = parse_ip_location(
+            r#"{"ip": "203.0.113.7", "city": "Lyon", "region": "Auvergne-Rhone-Alpes", "country_code": "FR",
+                "latitude": 45.7485, "longitude": 4.8467, "timezone": "Europe/Paris"}"#,
+        )
+        .unwrap();
+        assert_eq!(region.country, "FR");
+        assert_eq!(region.city.as_deref(), Some("Lyon"));
+        assert_eq!(region.timezone.as_deref(), Some("Europe/Paris"));
+        assert_eq!(region.source, RegionSource::Ip);
+        assert!(region.location.is_some());
+
+        assert!(parse_ip_location(r#"{"error": true, "reason": "RateLimited"}"#).is_err());
+        assert!(parse_ip_location(r#"{"city": "Nowhere"}"#).is_err());
+    }
+}
@@ -0,0 +1,152 @@
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use once_cell::sync::OnceCell;
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use tracing::Level;
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{fmt, reload, EnvFilter, Registry};
+
+/// Log files kept on disk, one per day
+const MAX_LOG_FILES: usize = 7;
+
+const LOG_FILE_PREFIX: &str = "iqamah";
+const DEFAULT_FILTER: &str = "info";
+
+// Set once by `init_logging`
+static LOG_DIR: OnceCell<PathBuf> = OnceCell::new();
+static FILTER_HANDLE: OnceCell<reload::Handle<EnvFilter, Registry>> = OnceCell::new();
+static FILE_GUARD: OnceCell<WorkerGuard> = OnceCell::new();
+
+/// One line of the JSON log file
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct LogEntry {
+    pub timestamp: String,
+    pub level: String,
+    pub target: String,
+    pub message: String,
+}
+
+/// Install the global subscriber: stderr plus daily-rotated JSON files in `log_dir`
+/// `log` macros are bridged, so existing `log::info!` calls end up in the files too
+pub fn init_logging(log_dir: &Path) -> anyhow::Result<()> {
+    std::fs::create_dir_all(log_dir)?;
+
+    let appender = RollingFileAppender::builder()
+        .rotation(Rotation::DAILY)
+        .filename_prefix(LOG_FILE_PREFIX)
+        .filename_suffix("log")
+        .max_log_files(MAX_LOG_FILES)
+        .build(log_dir)?;
+    let (file_writer, guard) = tracing_appender::non_blocking(appender);
+
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(DEFAULT_FILTER));
+    let (filter, handle) = reload::Layer::new(filter);
+
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(fmt::layer().with_writer(std::io::stderr))
+        .with(fmt::layer().json().flatten_event(true).with_writer(file_writer))
+        .try_init()?;
+
+    let _ = LOG_DIR.set(log_dir.to_path_buf());
+    let _ = FILTER_HANDLE.set(handle);
+    let _ = FILE_GUARD.set(guard);
+
+    Ok(())
+}
+
+/// Directory the log files are written to, if file logging is active
+pub fn log_dir() -> Option<&'static Path> {
+    LOG_DIR.get().map(|p| p.as_path())
+}
+
+/// Change the log filter at runtime, e.g. "debug" or "info,iqamah_lib=trace"
+pub fn set_log_level(filter: &str) -> anyhow::Result<()> {
+    let filter = EnvFilter::try_new(filter)?;
+    let handle = FILTER_HANDLE
+        .get()
+        .ok_or_else(|| anyhow::anyhow!("Logging is not initialized"))?;
+
+    handle.reload(filter)?;
+    Ok(())
+}
+
+/// Most recent log entries at `level` or more severe, oldest first
+pub fn recent_logs(dir: &Path, level: Option<&str>, limit: usize) -> anyhow::Result<Vec<LogEntry>> {
+    let max_level = level.map(Level::from_str).transpose()?.unwrap_or(Level::TRACE);
+
+    // Rotated files are suffixed with the date, so name order is chronological
+    let mut files: Vec<PathBuf> = std::fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| {
+            path.file_name()
+                .and_then(|n| n.to_str())
+                .map(|n| n.starts_with(LOG_FILE_PREFIX))
+                .unwrap_or(false)
+        })
+        .collect();
+    files.sort();
+
+    let mut entries = Vec::new();
+    for path in files.iter().rev() {
+        let content = std::fs::read_to_string(path)?;
+        let matching = content
+            .lines()
+            .rev()
+            .filter_map(|line| serde_json::from_str::<LogEntry>(line).ok())
+            .filter(|entry| {
+                Level::from_str(&entry.level)
+                    .map(|l| l <= max_level)
+                    .unwrap_or(true)
+            });
+
+        for entry in matching {
+            entries.push(entry);
+            if entries.len() >= limit {
+                entries.reverse();
+                return Ok(entries);
+            }
+        }
+    }
+
+    entries.reverse();
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recent_logs_filters_level_and_limit() {
+        let dir = std::env::temp_dir().join(format!("iqamah-logs-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let line = |level: &str, message: &str| {
+            format!(
+                r#"{{"timestamp":"2026-02-05T12:00:00Z","level":"{}","target":"iqamah","message":"{}"}}"#,
+                level, message
+            )
+        };
+        let older = [line("INFO", "started"), line("ERROR", "db failed")].join("\n");
+        let newer = [line("WARN", "slow fetch"), line("DEBUG", "cache hit"), line("ERROR", "offline")].join("\n");
+        std::fs::write(dir.join("iqamah.2026-02-04.log"), older).unwrap();
+        std::fs::write(dir.join("iqamah.2026-02-05.log"), newer).unwrap();
+
+        let warnings = recent_logs(&dir, Some("warn"), 10).unwrap();
+        let messages: Vec<_> = warnings.iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(messages, ["db failed", "slow fetch", "offline"]);
+
+        let last_two = recent_logs(&dir, None, 2).unwrap();
+        let messages: Vec<_> = last_two.iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(messages, ["cache hit", "offline"]);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,235 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use once_cell::sync::Lazy;
+
+use crate::models::{AppMetrics, CacheMetrics, ProviderMetrics, TickMetrics};
+
+/// Name of the cache of fetched schedules in the database
+pub const SCHEDULE_CACHE: &str = "schedules";
+
+// Counters kept in memory since the app started; providers and the ticker have no database handle
+static STARTED: Lazy<Instant> = Lazy::new(Instant::now);
+static PROVIDERS: Lazy<Mutex<BTreeMap<String, ProviderMetrics>>> = Lazy::new(|| Mutex::new(BTreeMap::new()));
+static TICKER: Lazy<Mutex<TickMetrics>> = Lazy::new(|| Mutex::new(TickMetrics::default()));
+static SCHEDULE_HITS: AtomicU64 = AtomicU64::new(0);
+static SCHEDULE_MISSES: AtomicU64 = AtomicU64::new(0);
+
+/// Record one schedule fetch from a provider and how long it took
+pub fn record_provider_fetch(provider_id: &str, latency: Duration, ok: bool) {
+    let latency_ms = latency.as_millis() as u64;
+    let mut providers = PROVIDERS.lock().unwrap_or_else(|e| e.into_inner());
+    let metrics = providers
+        .entry(provider_id.to_string())
+        .or_insert_with(|| ProviderMetrics {
+            provider_id: provider_id.to_string(),
+            requests: 0,
+            failures: 0,
+            total_latency_ms: 0,
+            max_latency_ms: 0,
+            last_latency_ms: 0,
+        });
+
+    metrics.requests += 1;
+    if !ok {
+        metrics.failures += 1;
+    }
+    metrics.total_latency_ms += latency_ms;
+    metrics.max_latency_ms = metrics.max_latency_ms.max(latency_ms);
+    metrics.last_latency_ms = latency_ms;
+}
+
+/// Record whether a day's schedule was found in the database or had to be fetched
+pub fn record_schedule_lookup(hit: bool) {
+    let counter = if hit { &SCHEDULE_HITS } else { &SCHEDULE_MISSES };
+    counter.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Record a ticker wake-up: `slept` past the `requested` interval counts as drift
+pub fn record_tick(requested: Duration, slept: Duration) {
+    let drift_ms = slept.saturating_sub(requested).as_millis() as u64;
+    let mut ticker = TICKER.lock().unwrap_or_else(|e| e.into_inner());
+    ticker.ticks += 1;
+    ticker.last_drift_ms = drift_ms;
+    ticker.max_drift_ms = ticker.max_drift_ms.max(drift_ms);
+}
+
+pub fn provider_metrics() -> Vec<ProviderMetrics> {
+    PROVIDERS.lock().unwrap_or_else(|e| e.into_inner()).values().cloned().collect()
+}
+
+pub fn schedule_cache_metrics() -> CacheMetrics {
+    CacheMetrics {
+        name: SCHEDULE_CACHE.to_string(),
+        hits: SCHEDULE_HITS.load(Ordering::Relaxed),
+        misses: SCHEDULE_MISSES.load(Ordering::Relaxed),
+        entries: None,
+    }
+}
+
+pub fn tick_metrics() -> TickMetrics {
+    TICKER.lock().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// Time since the first metric was read or recorded, close enough to the app's start
+pub fn uptime() -> Duration {
+    STARTED.elapsed()
+}
+
+/// Metrics in the Prometheus text exposition format
+pub fn render_prometheus(metrics: &AppMetrics) -> String {
+    let mut out = String::new();
+    let seconds = |ms: u64| ms as f64 / 1000.0;
+
+    family(&mut out, "iqamah_uptime_seconds", "gauge", "Seconds since the app started");
+    sample(&mut out, "iqamah_uptime_seconds", &[], metrics.uptime_secs as f64);
+
+    family(&mut out, "iqamah_provider_requests_total", "counter", "Schedule fetches per provider");
+    for p in &metrics.providers {
+        sample(&mut out, "iqamah_provider_requests_total", &[("provider", &p.provider_id)], p.requests as f64);
+    }
+    family(&mut out, "iqamah_provider_failures_total", "counter", "Failed schedule fetches per provider");
+    for p in &metrics.providers {
+        sample(&mut out, "iqamah_provider_failures_total", &[("provider", &p.provider_id)], p.failures as f64);
+    }
+    family(&mut out, "iqamah_provider_latency_seconds", "summary", "Schedule fetch latency per provider");
+    for p in &metrics.providers {
+        let labels = [("provider", p.provider_id.as_str())];
+        sample(&mut out, "iqamah_provider_latency_seconds_sum", &labels, seconds(p.total_latency_ms));
+        sample(&mut out, "iqamah_provider_latency_seconds_count", &labels, p.requests as f64);
+    }
+    family(&mut out, "iqamah_provider_latency_max_seconds", "gauge", "Slowest schedule fetch per provider");
+    for p in &metrics.providers {
+        let labels = [("provider", p.provider_id.as_str())];
+        sample(&mut out, "iqamah_provider_latency_max_seconds", &labels, seconds(p.max_latency_ms));
+    }
+
+    family(&mut out, "iqamah_cache_hits_total", "counter", "Lookups served from a cache");
+    for c in &metrics.caches {
+        sample(&mut out, "iqamah_cache_hits_total", &[("cache", &c.name)], c.hits as f64);
+    }
+    family(&mut out, "iqamah_cache_misses_total", "counter", "Lookups a cache could not serve");
+    for c in &metrics.caches {
+        sample(&mut out, "iqamah_cache_misses_total", &[("cache", &c.name)], c.misses as f64);
+    }
+    family(&mut out, "iqamah_cache_hit_ratio", "gauge", "Share of lookups served from a cache");
+    for c in &metrics.caches {
+        if let Some(rate) = c.hit_rate() {
+            sample(&mut out, "iqamah_cache_hit_ratio", &[("cache", &c.name)], rate);
+        }
+    }
+    family(&mut out, "iqamah_cache_entries", "gauge", "Entries held by an in-memory cache");
+    for c in &metrics.caches {
+        if let Some(entries) = c.entries {
+            sample(&mut out, "iqamah_cache_entries", &[("cache", &c.name)], entries as f64);
+        }
+    }
+
+    family(&mut out, "iqamah_ticks_total", "counter", "Background ticker wake-ups");
+    sample(&mut out, "iqamah_ticks_total", &[], metrics.ticker.ticks as f64);
+    family(&mut out, "iqamah_tick_drift_seconds", "gauge", "How late the last tick woke up");
+    sample(&mut out, "iqamah_tick_drift_seconds", &[], seconds(metrics.ticker.last_drift_ms));
+    family(&mut out, "iqamah_tick_drift_max_seconds", "gauge", "Latest wake-up of any tick");
+    sample(&mut out, "iqamah_tick_drift_max_seconds", &[], seconds(metrics.ticker.max_drift_ms));
+
+    family(&mut out, "iqamah_database_bytes", "gauge", "Size of the database");
+    sample(&mut out, "iqamah_database_bytes", &[], metrics.database_bytes as f64);
+    family(&mut out, "iqamah_database_rows", "gauge", "Rows per database table");
+    for t in &metrics.tables {
+        sample(&mut out, "iqamah_database_rows", &[("table", &t.table)], t.rows as f64);
+    }
+
+    out
+}
+
+fn family(out: &mut String, name: &str, kind: &str, help: &str) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} {}", name, kind);
+}
+
+fn sample(out: &mut String, name: &str, labels: &[(&str, &str)], value: f64) {
+    out.push_str(name);
+    if !labels.is_empty() {
+        let labels: Vec<String> = labels
+            .iter()
+            .map(|(key, value)| format!("{}=\"{}\"", key, escape_label(value)))
+            .collect();
+        let _ = write!(out, "{{{}}}", labels.join(","));
+    }
+    let _ = writeln!(out, " {}", value);
+}
+
+fn escape_label(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::TableSize;
+
+    #[test]
+    fn test_record_provider_fetches() {
+        record_provider_fetch("metrics-test", Duration::from_millis(120), true);
+        record_provider_fetch("metrics-test", Duration::from_millis(480), false);
+
+        let metrics = provider_metrics().into_iter().find(|p| p.provider_id == "metrics-test").unwrap();
+        assert_eq!(metrics.requests, 2);
+        assert_eq!(metrics.failures, 1);
+        assert_eq!(metrics.max_latency_ms, 480);
+        assert_eq!(metrics.last_latency_ms, 480);
+        assert_eq!(metrics.average_latency_ms(), Some(300.0));
+    }
+
+    #[test]
+    fn test_render_prometheus() {
+        let metrics = AppMetrics {
+            collected_at: chrono::Utc::now(),
+            uptime_secs: 90,
+            providers: vec![ProviderMetrics {
+                provider_id: "mawaqit".to_string(),
+                requests: 4,
+                failures: 1,
+                total_latency_ms: 2000,
+                max_latency_ms: 900,
+                last_latency_ms: 300,
+            }],
+            caches: vec![
+                CacheMetrics {
+                    name: SCHEDULE_CACHE.to_string(),
+                    hits: 3,
+                    misses: 1,
+                    entries: None,
+                },
+                CacheMetrics {
+                    name: "mosque \"pages\"".to_string(),
+                    ..Default::default()
+                },
+            ],
+            ticker: TickMetrics {
+                ticks: 10,
+                last_drift_ms: 4,
+                max_drift_ms: 250,
+            },
+            database_bytes: 4096,
+            tables: vec![TableSize {
+                table: "prayer_times".to_string(),
+                rows: 60,
+            }],
+        };
+
+        let text = render_prometheus(&metrics);
+        assert!(text.contains("# TYPE iqamah_provider_latency_seconds summary\n"));
+        assert!(text.contains("iqamah_provider_latency_seconds_sum{provider=\"mawaqit\"} 2\n"));
+        assert!(text.contains("iqamah_provider_failures_total{provider=\"mawaqit\"} 1\n"));
+        assert!(text.contains("iqamah_cache_hit_ratio{cache=\"schedules\"} 0.75\n"));
+        assert!(text.contains("iqamah_cache_hits_total{cache=\"mosque \\\"pages\\\"\"} 0\n"));
+        // No ratio before the first lookup
+        assert!(!text.contains("iqamah_cache_hit_ratio{cache=\"mosque"));
+        assert!(text.contains("iqamah_tick_drift_max_seconds 0.25\n"));
+        assert!(text.contains("iqamah_database_rows{table=\"prayer_times\"} 60\n"));
+    }
+}
@@ -0,0 +1,64 @@
+pub mod prayer_engine;
+pub mod notification_service;
+pub mod location_service;
+pub mod mosque_catalog;
+pub mod secret_store;
+pub mod logging;
+pub mod clock;
+pub mod i18n;
+pub mod formatting;
+pub mod freshness;
+pub mod hijri;
+pub mod http;
+pub mod iqama_learning;
+pub mod legacy_import;
+pub mod metrics;
+pub mod payload_archive;
+pub mod adhkar;
+pub mod api_token;
+pub mod audio;
+pub mod fajr_alarm;
+pub mod schedule_export;
+pub mod schedule_forecast;
+pub mod ticker;
+pub mod travel_session;
+pub mod refresh_policy;
+pub mod reminder_tracker;
+pub mod search_ranking;
+pub mod search_text;
+pub mod solar;
+pub mod speech;
+pub mod update_check;
+pub mod upstream_health;
+pub mod websocket;
+
+pub use prayer_engine::*;
+pub use notification_service::*;
+pub use location_service::*;
+pub use mosque_catalog::*;
+pub use secret_store::*;
+pub use logging::*;
+pub use i18n::*;
+pub use formatting::*;
+pub use freshness::*;
+pub use hijri::*;
+pub use http::*;
+pub use iqama_learning::*;
+pub use legacy_import::*;
+pub use metrics::*;
+pub use payload_archive::*;
+pub use adhkar::*;
+pub use api_token::*;
+pub use audio::*;
+pub use fajr_alarm::*;
+pub use schedule_export::*;
+pub use schedule_forecast::*;
+pub use refresh_policy::*;
+pub use reminder_tracker::*;
+pub use search_ranking::*;
+pub use search_text::*;
+pub use solar::*;
+pub use speech::*;
+pub use travel_session::*;
+pub use update_check::*;
+pub use upstream_health::*;
@@ -0,0 +1,256 @@
+use std::collections::{HashMap, HashSet};
+use std::io::{Read, Write};
+use std::sync::Mutex;
+
+use chrono::{Duration, Utc};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use once_cell::sync::Lazy;
+
+use crate::db::Database;
+use crate::models::{
+    nearest_mosques, CatalogImportPhase, CatalogImportProgress, CatalogImportSummary, GeoLocation, Mosque, NearbyMosque,
+};
+use crate::providers::{filter_mosques, MawaqitProvider};
+
+/// How long a stored country catalog is considered fresh
+pub const CATALOG_TTL_HOURS: i64 = 24;
+
+// Countries with a background refresh currently running
+static REFRESHING: Lazy<Mutex<HashSet<String>>> = Lazy::new(|| Mutex::new(HashSet::new()));
+
+/// Offline-first mosque catalog backed by SQLite
+/// Keeps one gzip-compressed mosque list per country and searches it locally
+pub struct MosqueCatalog {
+    db: Database,
+}
+
+impl MosqueCatalog {
+    pub fn new(db: Database) -> Self {
+        Self { db }
+    }
+
+    /// Search the stored catalog for a country
+    /// Downloads the catalog if it is missing; refreshes it in the background once stale
+    pub async fn search(&self, country: &str, query: &str) -> anyhow::Result<Vec<Mosque>> {
+        Ok(filter_mosques(self.load(country).await?, query))
+    }
+
+    /// Catalog mosques within `radius_km`, nearest first, at most `limit`
+    pub async fn nearby(
+        &self,
+        country: &str,
+        location: &GeoLocation,
+        radius_km: f64,
+        limit: usize,
+    ) -> anyhow::Result<Vec<NearbyMosque>> {
+        Ok(nearest_mosques(self.load(country).await?, location, radius_km, limit))
+    }
+
+    /// Whether a country's catalog was downloaded before, fresh or not
+    pub async fn is_downloaded(&self, country: &str) -> anyhow::Result<bool> {
+        Ok(self.db.get_mosque_catalog(country).await?.is_some())
+    }
+
+    async fn load(&self, country: &str) -> anyhow::Result<Vec<Mosque>> {
+        match self.db.get_mosque_catalog(country).await? {
+            Some((data, fetched_at)) => {
+                if Utc::now() - fetched_at > Duration::hours(CATALOG_TTL_HOURS) {
+                    self.spawn_refresh(country);
+                }
+                decompress_mosques(&data)
+            }
+            None => self.refresh(country).await,
+        }
+    }
+
+    /// Download the country mosque list and store it compressed
+    pub async fn refresh(&self, country: &str) -> anyhow::Result<Vec<Mosque>> {
+        Ok(self.import_mosques(country, &|_| {}).await?.0)
+    }
+
+    /// Download the full country list and merge it into the stored catalog by slug
+    /// An unchanged catalog is only marked fresh, not rewritten
+    pub async fn import(
+        &self,
+        country: &str,
+        on_progress: &(dyn Fn(CatalogImportProgress) + Send + Sync),
+    ) -> anyhow::Result<CatalogImportSummary> {
+        Ok(self.import_mosques(country, on_progress).await?.1)
+    }
+
+    async fn import_mosques(
+        &self,
+        country: &str,
+        on_progress: &(dyn Fn(CatalogImportProgress) + Send + Sync),
+    ) -> anyhow::Result<(Vec<Mosque>, CatalogImportSummary)> {
+        let report = |phase, processed: usize, total: Option<usize>| {
+            on_progress(CatalogImportProgress {
+                country: country.to_string(),
+                phase,
+                processed: processed as u32,
+                total: total.map(|t| t as u32),
+            })
+        };
+
+        report(CatalogImportPhase::Downloading, 0, None);
+        let mosques = MawaqitProvider::new().fetch_catalog(country).await?;
+        let total = mosques.len();
+
+        let previous = self.db.get_mosque_catalog(country).await?.map(|(data, _)| {
+            decompress_mosques(&data).unwrap_or_else(|e| {
+                log::warn!("Stored catalog for {} is unreadable, replacing it: {}", country, e);
+                Vec::new()
+            })
+        });
+        let diff = diff_catalog(previous.as_deref().unwrap_or_default(), &mosques, |processed| {
+            report(CatalogImportPhase::Diffing, processed, Some(total))
+        });
+
+        report(CatalogImportPhase::Storing, 0, Some(total));
+        if previous.is_none() || diff.has_changes() {
+            let data = compress_mosques(&mosques)?;
+            log::info!(
+                "Storing mosque catalog for {}: {} mosques ({} added, {} updated, {} removed), {} bytes compressed",
+                country,
+                total,
+                diff.added,
+                diff.updated,
+                diff.removed,
+                data.len()
+            );
+            self.db.save_mosque_catalog(country, &data, total).await?;
+        } else {
+            log::info!("Mosque catalog for {} is unchanged ({} mosques)", country, total);
+            self.db.touch_mosque_catalog(country).await?;
+        }
+        report(CatalogImportPhase::Done, total, Some(total));
+
+        let summary = CatalogImportSummary {
+            country: country.to_string(),
+            total: total as u32,
+            added: diff.added,
+            updated: diff.updated,
+            removed: diff.removed,
+            unchanged: diff.unchanged,
+            imported_at: Utc::now(),
+        };
+
+        Ok((mosques, summary))
+    }
+
+    fn spawn_refresh(&self, country: &str) {
+        if !REFRESHING.lock().unwrap().insert(country.to_string()) {
+            return;
+        }
+
+        let catalog = MosqueCatalog::new(self.db.clone());
+        let country = country.to_string();
+
+        tokio::spawn(async move {
+            if let Err(e) = catalog.refresh(&country).await {
+                log::warn!("Background catalog refresh for {} failed: {}", country, e);
+            }
+            REFRESHING.lock().unwrap().remove(&country);
+        });
+    }
+}
+
+/// Mosques between two diffing progress reports
+const DIFF_PROGRESS_STEP: usize = 500;
+
+#[derive(Debug, Default, PartialEq)]
+struct CatalogDiff {
+    added: u32,
+    updated: u32,
+    removed: u32,
+    unchanged: u32,
+}
+
+impl CatalogDiff {
+    fn has_changes(&self) -> bool {
+        self.added + self.updated + self.removed > 0
+    }
+}
+
+/// Compare catalogs by slug; `progress` gets the number of new entries checked so far
+fn diff_catalog(previous: &[Mosque], current: &[Mosque], mut progress: impl FnMut(usize)) -> CatalogDiff {
+    let previous: HashMap<&str, &Mosque> = previous.iter().map(|m| (m.id.as_str(), m)).collect();
+    let mut diff = CatalogDiff::default();
+    let mut seen = 0;
+
+    for (i, mosque) in current.iter().enumerate() {
+        match previous.get(mosque.id.as_str()) {
+            Some(old) => {
+                seen += 1;
+                if catalog_entry_changed(old, mosque) {
+                    diff.updated += 1;
+                } else {
+                    diff.unchanged += 1;
+                }
+            }
+            None => diff.added += 1,
+        }
+        if (i + 1) % DIFF_PROGRESS_STEP == 0 || i + 1 == current.len() {
+            progress(i + 1);
+        }
+    }
+    diff.removed = (previous.len() - seen) as u32;
+
+    diff
+}
+
+fn catalog_entry_changed(old: &Mosque, new: &Mosque) -> bool {
+    old.name != new.name
+        || old.address != new.address
+        || old.city != new.city
+        || old.country != new.country
+        || old.latitude != new.latitude
+        || old.longitude != new.longitude
+        || old.facilities != new.facilities
+}
+
+fn compress_mosques(mosques: &[Mosque]) -> anyhow::Result<Vec<u8>> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(&serde_json::to_vec(mosques)?)?;
+    Ok(encoder.finish()?)
+}
+
+fn decompress_mosques(data: &[u8]) -> anyhow::Result<Vec<Mosque>> {
+    let mut json = Vec::new();
+    GzDecoder::new(data).read_to_end(&mut json)?;
+    Ok(serde_json::from_slice(&json)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_catalog_compression_roundtrip() {
+        let mut mosque = Mosque::new("mosquee-paris".to_string(), "Grande Mosquée de Paris".to_string());
+        mosque.city = Some("Paris".to_string());
+
+        let data = compress_mosques(&[mosque]).unwrap();
+        let restored = decompress_mosques(&data).unwrap();
+
+        assert_eq!(restored.len(), 1);
+        assert_eq!(restored[0].id, "mosquee-paris");
+        assert_eq!(restored[0].city.as_deref(), Some("Paris"));
+    }
+
+    #[test]
+    fn test_catalog_diff_by_slug() {
+        let mosque = |id: &str, name: &str| Mosque::new(id.to_string(), name.to_string());
+        let previous = vec![mosque("a", "Mosquée A"), mosque("b", "Mosquée B"), mosque("c", "Mosquée C")];
+        let current = vec![mosque("a", "Mosquée A"), mosque("b", "Grande Mosquée B"), mosque("d", "Mosquée D")];
+        let mut reported = Vec::new();
+
+        let diff = diff_catalog(&previous, &current, |processed| reported.push(processed));
+
+        assert_eq!(diff, CatalogDiff { added: 1, updated: 1, removed: 1, unchanged: 1 });
+        assert_eq!(reported, vec![3]);
+        assert!(!diff_catalog(&previous, &previous, |_| {}).has_changes());
+    }
+}
@@ -0,0 +1,507 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::models::{
+    render_template, GoalProgress, IslamicEvent, MosqueNotificationOverride, NotificationProfile, NotificationTemplates,
+    PrayerCountdown, PrayerEngineConfig, PrayerName, PrayerTimes, ScheduleChange,
+};
+use crate::services::formatting::DisplayFormatter;
+
+/// The one reminder given before adhan and iqama until the user picks others
+pub const DEFAULT_REMINDER_MINUTES: i64 = 10;
+
+/// Notification configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotificationConfig {
+    pub enabled: bool,
+    pub reminder_minutes_before: Vec<i64>, // e.g., [15, 5]
+    pub show_system_notifications: bool,
+    pub play_sound: bool,
+    /// Read reminders aloud when spoken announcements are on
+    #[serde(default = "default_true")]
+    pub speak: bool,
+    /// Prayers that get reminders; all of them when unset
+    #[serde(default)]
+    pub prayers: Option<Vec<PrayerName>>,
+    /// Minutes before shuruq to warn that Fajr's window is closing; off when unset
+    #[serde(default)]
+    pub fajr_ending_minutes: Option<i64>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl Default for NotificationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            reminder_minutes_before: vec![DEFAULT_REMINDER_MINUTES],
+            show_system_notifications: true,
+            play_sound: true,
+            speak: true,
+            prayers: None,
+            fajr_ending_minutes: None,
+        }
+    }
+}
+
+impl NotificationConfig {
+    /// Sound, speech and prayers as the active notification profile sets them
+    pub fn with_profile(mut self, profile: &NotificationProfile) -> Self {
+        self.play_sound = profile.sound;
+        self.speak = profile.speech;
+        self.prayers = Some(profile.prayers.clone());
+        self
+    }
+
+    /// Narrow the settings for the mosque reminders are about: an override can mute, never unmute
+    pub fn with_mosque_override(mut self, mosque: &MosqueNotificationOverride) -> Self {
+        self.enabled &= mosque.enabled;
+        self.play_sound &= mosque.sound;
+        self.speak &= mosque.speech;
+        if let Some(prayers) = &mosque.prayers {
+            self.prayers = Some(match self.prayers {
+                Some(current) => current.into_iter().filter(|p| prayers.contains(p)).collect(),
+                None => prayers.clone(),
+            });
+        }
+        if let Some(minutes) = &mosque.reminder_minutes_before {
+            self.reminder_minutes_before = minutes.clone();
+        }
+        self
+    }
+
+    pub fn notifies(&self, prayer: PrayerName) -> bool {
+        self.enabled && self.prayers.as_ref().is_none_or(|prayers| prayers.contains(&prayer))
+    }
+}
+
+/// Notification data
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrayerNotification {
+    pub id: String,
+    pub title: String,
+    pub body: String,
+    pub prayer_name: String,
+    pub notification_type: NotificationType,
+    pub scheduled_time: DateTime<Utc>,
+    /// Minutes from `scheduled_time` to the prayer event it announces
+    pub minutes_before: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationType {
+    AdhanReminder,
+    IqamaReminder,
+    PrayerStart,
+    /// Fajr's window closes at sunrise
+    FajrEnding,
+    ScheduleChange,
+    GoalSummary,
+    IslamicEvent,
+}
+
+impl PrayerNotification {
+    /// Build a notification with its title and body in the user's language and clock style
+    /// The title shows when the prayer event itself happens, `minutes_before` after `scheduled_time`
+    pub fn localized(
+        formatter: &DisplayFormatter,
+        prayer: PrayerName,
+        notification_type: NotificationType,
+        scheduled_time: DateTime<Utc>,
+        minutes_before: i64,
+    ) -> Self {
+        let language = formatter.language;
+        let event_time = scheduled_time + chrono::Duration::minutes(minutes_before);
+        let body = match notification_type {
+            NotificationType::AdhanReminder => language.adhan_reminder(prayer, minutes_before),
+            NotificationType::IqamaReminder => language.iqama_reminder(prayer, minutes_before),
+            NotificationType::PrayerStart => language.prayer_start(prayer),
+            NotificationType::FajrEnding => language.fajr_ending(minutes_before),
+            NotificationType::ScheduleChange | NotificationType::GoalSummary | NotificationType::IslamicEvent => {
+                language.prayer_name(prayer).to_string()
+            }
+        };
+
+        Self {
+            id: format!("{}-{:?}-{}", prayer, notification_type, scheduled_time.timestamp()),
+            title: format!(
+                "{} · {}",
+                language.prayer_name(prayer),
+                formatter.format_time(event_time)
+            ),
+            body,
+            prayer_name: prayer.to_string(),
+            notification_type,
+            scheduled_time,
+            minutes_before,
+        }
+    }
+}
+
+impl PrayerNotification {
+    /// Alert for an iqama that moved; `None` when it was only added or removed
+    pub fn schedule_change(formatter: &DisplayFormatter, change: &ScheduleChange) -> Option<Self> {
+        let shift = change.shift_minutes().filter(|&m| m != 0)?;
+        let new_iqama = change.new_iqama?;
+        let language = formatter.language;
+
+        Some(Self {
+            id: format!("{}-{}-ScheduleChange-{}", change.mosque_id, change.prayer, change.date),
+            title: format!(
+                "{} · {}",
+                language.prayer_name(change.prayer),
+                formatter.format_time(new_iqama)
+            ),
+            body: language.iqama_moved(change.prayer, shift),
+            prayer_name: change.prayer.to_string(),
+            notification_type: NotificationType::ScheduleChange,
+            scheduled_time: change.detected_at,
+            minutes_before: 0,
+        })
+    }
+
+    /// End-of-week encouragement for a goal, met or not
+    pub fn goal_summary(formatter: &DisplayFormatter, progress: &GoalProgress, now: DateTime<Utc>) -> Self {
+        let language = formatter.language;
+        let goal = &progress.goal;
+
+        Self {
+            id: format!("goal-{}-{}", goal.id, progress.week_start),
+            title: format!(
+                "{} · {}/{}",
+                language.goal_subject(goal.prayer),
+                progress.completed,
+                goal.target_per_week
+            ),
+            body: language.goal_summary(progress.completed, goal.target_per_week),
+            prayer_name: goal.prayer.map(|p| p.to_string()).unwrap_or_default(),
+            notification_type: NotificationType::GoalSummary,
+            scheduled_time: now,
+            minutes_before: 0,
+        }
+    }
+}
+
+impl PrayerNotification {
+    /// Evening reminder that a special day's night begins at Maghrib
+    pub fn islamic_event(formatter: &DisplayFormatter, event: &IslamicEvent, now: DateTime<Utc>) -> Self {
+        let language = formatter.language;
+
+        Self {
+            id: format!("islamic-event-{:?}-{}", event.day, event.date),
+            title: format!(
+                "{} · {}/{}/{}",
+                language.special_day(event.day),
+                event.hijri_date.day,
+                event.hijri_date.month,
+                event.hijri_date.year
+            ),
+            body: language.special_day_tonight(event.day),
+            prayer_name: String::new(),
+            notification_type: NotificationType::IslamicEvent,
+            scheduled_time: now,
+            minutes_before: 0,
+        }
+    }
+}
+
+/// Notification service trait
+#[async_trait::async_trait]
+pub trait NotificationService: Send + Sync {
+    async fn schedule_notification(&self, notification: PrayerNotification) -> anyhow::Result<()>;
+    async fn cancel_notification(&self, id: &str) -> anyhow::Result<()>;
+    async fn cancel_all_notifications(&self) -> anyhow::Result<()>;
+}
+
+/// What `{mosque}` and `{rakah}` stand for in notification templates
+#[derive(Debug, Clone, Default)]
+pub struct TemplateValues {
+    pub mosque: String,
+    pub rakah_counts: HashMap<PrayerName, i32>,
+}
+
+impl TemplateValues {
+    /// Values for a mosque's day; rakah counts are the mosque's own where it sets them
+    pub fn new(mosque: impl Into<String>, times: &PrayerTimes) -> Self {
+        let defaults = PrayerEngineConfig::default().default_rakah_counts;
+        let mut rakah_counts = defaults.clone();
+        for prayer in times.all_prayers().into_iter().chain(times.jumuah.as_ref()) {
+            rakah_counts.insert(prayer.name, prayer.get_rakah_count(&defaults));
+        }
+
+        Self {
+            mosque: mosque.into(),
+            rakah_counts,
+        }
+    }
+}
+
+/// Simple in-memory notification scheduler
+pub struct NotificationScheduler {
+    config: NotificationConfig,
+    templates: NotificationTemplates,
+    values: TemplateValues,
+}
+
+impl NotificationScheduler {
+    pub fn new(config: NotificationConfig) -> Self {
+        Self {
+            config,
+            templates: NotificationTemplates::default(),
+            values: TemplateValues::default(),
+        }
+    }
+
+    /// Word reminders with the user's templates instead of the built-in text
+    pub fn with_templates(mut self, templates: NotificationTemplates, values: TemplateValues) -> Self {
+        self.templates = templates;
+        self.values = values;
+        self
+    }
+
+    /// Apply the template for the notification's kind, if the user set one
+    pub fn render(
+        &self,
+        formatter: &DisplayFormatter,
+        prayer: PrayerName,
+        mut notification: PrayerNotification,
+    ) -> PrayerNotification {
+        let template = match notification.notification_type {
+            NotificationType::AdhanReminder => &self.templates.adhan_reminder,
+            NotificationType::IqamaReminder => &self.templates.iqama_reminder,
+            NotificationType::PrayerStart => &self.templates.prayer_start,
+            NotificationType::FajrEnding
+            | NotificationType::ScheduleChange
+            | NotificationType::GoalSummary
+            | NotificationType::IslamicEvent => &None,
+        };
+        let Some(template) = template else {
+            return notification;
+        };
+
+        let values = [
+            ("prayer", formatter.language.prayer_name(prayer).to_string()),
+            ("minutes", notification.minutes_before.to_string()),
+            ("mosque", self.values.mosque.clone()),
+            ("rakah", self.values.rakah_counts.get(&prayer).copied().unwrap_or(4).to_string()),
+        ];
+        if let Some(title) = &template.title {
+            notification.title = render_template(title, &values);
+        }
+        if let Some(body) = &template.body {
+            notification.body = render_template(body, &values);
+        }
+        notification
+    }
+
+    pub fn should_notify(&self, minutes_until: i64) -> bool {
+        self.config.enabled && self.config.reminder_minutes_before.contains(&minutes_until)
+    }
+
+    /// Adhan and iqama reminders due for the current countdowns, plus the Fajr ending warning
+    /// Prayers the mosque doesn't hold, or the active profile mutes, get no reminders
+    pub fn due_reminders(&self, formatter: &DisplayFormatter, countdowns: &[PrayerCountdown]) -> Vec<PrayerNotification> {
+        let mut reminders = Vec::new();
+
+        for countdown in countdowns.iter().filter(|c| !c.not_held && self.config.notifies(c.prayer_name)) {
+            let events = [
+                (NotificationType::AdhanReminder, Some((countdown.adhan_time, countdown.time_until_adhan_secs))),
+                (NotificationType::IqamaReminder, countdown.iqama_time.zip(countdown.time_until_iqama_secs)),
+            ];
+            for (notification_type, event) in events {
+                if let Some((time, secs)) = event.filter(|(_, secs)| *secs > 0) {
+                    let minutes = secs / 60;
+                    if self.should_notify(minutes) {
+                        let notification = PrayerNotification::localized(
+                            formatter,
+                            countdown.prayer_name,
+                            notification_type,
+                            time - chrono::Duration::minutes(minutes),
+                            minutes,
+                        );
+                        reminders.push(self.render(formatter, countdown.prayer_name, notification));
+                    }
+                }
+            }
+        }
+        reminders.extend(self.fajr_ending(formatter, countdowns));
+
+        reminders
+    }
+
+    /// Warning before sunrise, for users who pray Fajr at home after the congregation
+    /// Sent even when the mosque doesn't hold Fajr, since the prayer itself is still due
+    fn fajr_ending(&self, formatter: &DisplayFormatter, countdowns: &[PrayerCountdown]) -> Option<PrayerNotification> {
+        let minutes = self.config.fajr_ending_minutes.filter(|_| self.config.notifies(PrayerName::Fajr))?;
+        let fajr = countdowns.iter().find(|c| c.prayer_name == PrayerName::Fajr)?;
+        let (shuruq, secs) = fajr.shuruq_time.zip(fajr.time_until_shuruq_secs).filter(|(_, secs)| *secs > 0)?;
+
+        (secs / 60 == minutes).then(|| {
+            PrayerNotification::localized(
+                formatter,
+                PrayerName::Fajr,
+                NotificationType::FajrEnding,
+                shuruq - chrono::Duration::minutes(minutes),
+                minutes,
+            )
+        })
+    }
+}
+
+use async_trait::async_trait;
+
+#[async_trait]
+impl NotificationService for NotificationScheduler {
+    async fn schedule_notification(&self, _notification: PrayerNotification) -> anyhow::Result<()> {
+        // Implementation would use tauri::api::notification
+        // For now, this is a placeholder
+        Ok(())
+    }
+
+    async fn cancel_notification(&self, _id: &str) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    async fn cancel_all_notifications(&self) -> anyhow::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::CountdownDisplay;
+
+    #[test]
+    fn test_due_reminders_skip_prayers_not_held() {
+        let now = Utc::now();
+        let countdown = |prayer_name, not_held| PrayerCountdown {
+            prayer_name,
+            adhan_time: now + chrono::Duration::seconds(10 * 60 + 30),
+            iqama_time: None,
+            iqama_offset_minutes: None,
+            time_until_adhan_secs: 10 * 60 + 30,
+            time_until_iqama_secs: None,
+            is_active: false,
+            not_held,
+            shuruq_time: None,
+            time_until_shuruq_secs: None,
+            adhan_display: CountdownDisplay::default(),
+            iqama_display: None,
+        };
+        let scheduler = NotificationScheduler::new(NotificationConfig::default());
+        let formatter = DisplayFormatter::from_settings(None, None);
+
+        let reminders = scheduler.due_reminders(
+            &formatter,
+            &[countdown(PrayerName::Dhuhr, true), countdown(PrayerName::Asr, false)],
+        );
+        assert_eq!(reminders.len(), 1);
+        assert_eq!(reminders[0].prayer_name, PrayerName::Asr.to_string());
+    }
+
+    #[test]
+    fn test_due_reminders_use_templates() {
+        let now = Utc::now();
+        let times = crate::providers::MockProvider::new().schedule("mock-template", now.date_naive(), now);
+        let countdown = PrayerCountdown {
+            prayer_name: PrayerName::Maghrib,
+            adhan_time: now,
+            iqama_time: Some(now + chrono::Duration::seconds(10 * 60 + 30)),
+            iqama_offset_minutes: None,
+            time_until_adhan_secs: 0,
+            time_until_iqama_secs: Some(10 * 60 + 30),
+            is_active: true,
+            not_held: false,
+            shuruq_time: None,
+            time_until_shuruq_secs: None,
+            adhan_display: CountdownDisplay::default(),
+            iqama_display: None,
+        };
+        let templates = NotificationTemplates {
+            iqama_reminder: Some(crate::models::NotificationTemplate {
+                title: Some("{mosque}".to_string()),
+                body: Some("{prayer} ({rakah} rakah) in {minutes} minutes".to_string()),
+            }),
+            ..Default::default()
+        };
+        let scheduler = NotificationScheduler::new(NotificationConfig::default())
+            .with_templates(templates, TemplateValues::new("Al-Noor", &times));
+        let formatter = DisplayFormatter::from_settings(None, None);
+
+        let reminders = scheduler.due_reminders(&formatter, &[countdown]);
+        assert_eq!(reminders.len(), 1);
+        assert_eq!(reminders[0].title, "Al-Noor");
+        assert_eq!(reminders[0].body, "Maghrib (3 rakah) in 10 minutes");
+    }
+
+    #[test]
+    fn test_fajr_ending_warning() {
+        let now = Utc::now();
+        let shuruq = now + chrono::Duration::seconds(20 * 60 + 30);
+        let fajr = PrayerCountdown {
+            prayer_name: PrayerName::Fajr,
+            adhan_time: now - chrono::Duration::minutes(60),
+            iqama_time: Some(now - chrono::Duration::minutes(40)),
+            iqama_offset_minutes: None,
+            time_until_adhan_secs: 0,
+            time_until_iqama_secs: Some(0),
+            is_active: false,
+            not_held: true,
+            shuruq_time: Some(shuruq),
+            time_until_shuruq_secs: Some(20 * 60 + 30),
+            adhan_display: CountdownDisplay::default(),
+            iqama_display: None,
+        };
+        let formatter = DisplayFormatter::from_settings(None, None);
+
+        let off = NotificationScheduler::new(NotificationConfig::default());
+        assert!(off.due_reminders(&formatter, std::slice::from_ref(&fajr)).is_empty());
+
+        let config = NotificationConfig {
+            fajr_ending_minutes: Some(20),
+            ..Default::default()
+        };
+        let reminders = NotificationScheduler::new(config).due_reminders(&formatter, &[fajr]);
+        assert_eq!(reminders.len(), 1);
+        assert!(matches!(reminders[0].notification_type, NotificationType::FajrEnding));
+        assert_eq!(reminders[0].scheduled_time, shuruq - chrono::Duration::minutes(20));
+        assert_eq!(reminders[0].body, "Fajr time ends in 20 minutes, pray before sunrise");
+    }
+
+    #[test]
+    fn test_mosque_override_only_narrows() {
+        let work = NotificationProfile {
+            name: "work".to_string(),
+            sound: false,
+            speech: false,
+            prayers: vec![PrayerName::Dhuhr, PrayerName::Asr],
+        };
+        let mosque = MosqueNotificationOverride {
+            mosque_id: "home".to_string(),
+            enabled: true,
+            sound: true,
+            speech: true,
+            prayers: Some(vec![PrayerName::Asr, PrayerName::Isha]),
+            reminder_minutes_before: Some(vec![20]),
+        };
+
+        let config = NotificationConfig::default().with_profile(&work).with_mosque_override(&mosque);
+        assert!(!config.play_sound && !config.speak);
+        assert!(config.notifies(PrayerName::Asr));
+        assert!(!config.notifies(PrayerName::Dhuhr));
+        assert!(!config.notifies(PrayerName::Isha));
+        assert!(NotificationScheduler::new(config).should_notify(20));
+
+        let config = NotificationConfig::default().with_mosque_override(&mosque);
+        assert!(config.play_sound && config.notifies(PrayerName::Isha));
+
+        let muted = NotificationConfig::default().with_mosque_override(&MosqueNotificationOverride::muted("work"));
+        assert!(!muted.notifies(PrayerName::Fajr));
+    }
+}
@@ -0,0 +1,115 @@
+use std::io::{Read, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use once_cell::sync::Lazy;
+
+use crate::models::RawPayload;
+use crate::services::clock;
+
+/// Responses kept per mosque unless the user picks another count
+pub const DEFAULT_ARCHIVED_PAYLOADS: usize = 5;
+/// Most responses a user can keep per mosque
+pub const MAX_ARCHIVED_PAYLOADS: usize = 50;
+
+/// Responses waiting for the next flush to the database; older ones are dropped first
+const MAX_PENDING_PAYLOADS: usize = 32;
+/// Larger responses are not archived: a schedule never comes close
+const MAX_PAYLOAD_BYTES: usize = 2 * 1024 * 1024;
+
+// Off by default: responses are only kept once the user opts in for a support request
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+// Responses from providers, which have no database handle, waiting to be stored
+static PENDING: Lazy<Mutex<Vec<RawPayload>>> = Lazy::new(|| Mutex::new(Vec::new()));
+
+/// Turn archiving of provider responses on or off; turning it off drops what is pending
+pub fn set_payload_archive_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+    if !enabled {
+        PENDING.lock().unwrap_or_else(|e| e.into_inner()).clear();
+    }
+}
+
+pub fn payload_archive_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Keep the response a provider fetched for a mosque's schedule, when archiving is on
+pub fn record_payload(provider_id: &str, mosque_id: &str, url: &str, body: &[u8]) {
+    if !payload_archive_enabled() || body.len() > MAX_PAYLOAD_BYTES {
+        return;
+    }
+
+    let compressed = match compress_payload(body) {
+        Ok(compressed) => compressed,
+        Err(e) => {
+            log::debug!("Failed to compress {} response for {}: {}", provider_id, mosque_id, e);
+            return;
+        }
+    };
+
+    let mut pending = PENDING.lock().unwrap_or_else(|e| e.into_inner());
+    if pending.len() >= MAX_PENDING_PAYLOADS {
+        pending.remove(0);
+    }
+    pending.push(RawPayload {
+        provider_id: provider_id.to_string(),
+        mosque_id: mosque_id.to_string(),
+        url: url.to_string(),
+        fetched_at: clock::now(),
+        size_bytes: body.len() as i64,
+        body: compressed,
+    });
+}
+
+/// Take every response recorded since the last call, oldest first
+pub fn take_raw_payloads() -> Vec<RawPayload> {
+    std::mem::take(&mut *PENDING.lock().unwrap_or_else(|e| e.into_inner()))
+}
+
+pub fn compress_payload(body: &[u8]) -> anyhow::Result<Vec<u8>> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(body)?;
+    Ok(encoder.finish()?)
+}
+
+pub fn decompress_payload(data: &[u8]) -> anyhow::Result<Vec<u8>> {
+    let mut body = Vec::new();
+    GzDecoder::new(data).read_to_end(&mut body)?;
+    Ok(body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_payload_roundtrip() {
+        let body = br#"{"times":["19:30","19:45"]}"#.repeat(20);
+        let compressed = compress_payload(&body).unwrap();
+
+        assert!(compressed.len() < body.len());
+        assert_eq!(decompress_payload(&compressed).unwrap(), body);
+    }
+
+    #[test]
+    fn test_record_only_when_enabled() {
+        set_payload_archive_enabled(false);
+        record_payload("mawaqit", "m1", "https://mawaqit.net/fr/m1", b"<html></html>");
+        assert!(take_raw_payloads().is_empty());
+
+        set_payload_archive_enabled(true);
+        record_payload("mawaqit", "m1", "https://mawaqit.net/fr/m1", b"<html></html>");
+        let payloads = take_raw_payloads();
+        set_payload_archive_enabled(false);
+
+        let recorded: Vec<_> = payloads.iter().filter(|p| p.mosque_id == "m1").collect();
+        assert_eq!(recorded.len(), 1);
+        assert_eq!(recorded[0].size_bytes, 13);
+        assert_eq!(decompress_payload(&recorded[0].body).unwrap(), b"<html></html>");
+    }
+}
@@ -0,0 +1,1345 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Duration, Local, Utc};
+
+use crate::models::*;
+use crate::services::clock::same_time_next_day;
+use crate::services::solar::asr_shift;
+
+/// Core prayer calculation engine - Pure, stateless, testable
+pub struct PrayerEngine {
+    config: PrayerEngineConfig,
+    availability: PrayerAvailability,
+    /// Average crowd score (0-4) of the prayer being predicted
+    crowd_level: Option<f64>,
+    /// Per-prayer start lag replacing the configured one
+    start_lags: HashMap<PrayerName, i64>,
+    /// Per-prayer uncertainty of the start time replacing the configured one
+    start_spreads: HashMap<PrayerName, i64>,
+    /// Mosque latitude, to move published Asr times to the user's convention
+    latitude: Option<f64>,
+    /// Fajr after the schedule's night, from the next day's schedule
+    next_fajr: Option<DateTime<Utc>>,
+}
+
+impl PrayerEngine {
+    pub fn new(config: PrayerEngineConfig) -> Self {
+        Self {
+            config,
+            availability: PrayerAvailability::default(),
+            crowd_level: None,
+            start_lags: HashMap::new(),
+            start_spreads: HashMap::new(),
+            latitude: None,
+            next_fajr: None,
+        }
+    }
+
+    pub fn with_defaults() -> Self {
+        Self::new(PrayerEngineConfig::default())
+    }
+
+    /// Skip prayers the mosque doesn't hold in next-prayer and countdown results
+    pub fn with_availability(mut self, availability: PrayerAvailability) -> Self {
+        self.availability = availability;
+        self
+    }
+
+    /// Leave earlier for prayers that are historically crowded
+    pub fn with_crowd_level(mut self, average_level: Option<f64>) -> Self {
+        self.crowd_level = average_level;
+        self
+    }
+
+    /// Expect prayers to start this many seconds after iqama, e.g. as learned from iqama reports
+    pub fn with_start_lags(mut self, start_lags: HashMap<PrayerName, i64>) -> Self {
+        self.start_lags = start_lags;
+        self
+    }
+
+    /// How far actual starts stray from the expected one, e.g. the spread of iqama reports
+    pub fn with_start_spreads(mut self, start_spreads: HashMap<PrayerName, i64>) -> Self {
+        self.start_spreads = start_spreads;
+        self
+    }
+
+    /// Mosque latitude, needed to follow an Asr convention the schedule wasn't published in
+    pub fn with_latitude(mut self, latitude: Option<f64>) -> Self {
+        self.latitude = latitude;
+        self
+    }
+
+    /// Tomorrow's Fajr, so the night ends on time; otherwise today's Fajr wall-clock time is assumed
+    pub fn with_next_fajr(mut self, next_fajr: Option<DateTime<Utc>>) -> Self {
+        self.next_fajr = next_fajr;
+        self
+    }
+
+    fn start_lag(&self, prayer: PrayerName) -> Duration {
+        Duration::seconds(
+            self.start_lags
+                .get(&prayer)
+                .copied()
+                .unwrap_or(self.config.start_lag_seconds),
+        )
+    }
+
+    /// When the congregation is expected to actually start, if later than the published iqama
+    fn expected_iqama(&self, prayer: &Prayer) -> Option<DateTime<Utc>> {
+        let lag = self.start_lag(prayer.name);
+        prayer.iqama.filter(|_| lag > Duration::zero()).map(|iqama| iqama + lag)
+    }
+
+    /// Extra arrival buffer for busy (3) and packed (4) prayers
+    fn crowd_buffer_secs(&self) -> i64 {
+        let above_moderate = self.crowd_level.map(|level| (level - 2.0).max(0.0)).unwrap_or(0.0);
+        (above_moderate * self.config.crowd_buffer_seconds_per_level as f64).round() as i64
+    }
+
+    /// Prayers of the day the mosque holds, with Jumuah standing in for Dhuhr when only it is held
+    fn held_prayers<'a>(&self, schedule: &'a PrayerTimes) -> Vec<&'a Prayer> {
+        let mut prayers: Vec<&Prayer> = schedule
+            .all_prayers()
+            .into_iter()
+            .filter(|p| self.availability.holds(p.name))
+            .collect();
+
+        if let Some(jumuah) = schedule.jumuah.as_ref() {
+            if !self.availability.holds(PrayerName::Dhuhr) && self.availability.holds(PrayerName::Jumuah) {
+                prayers.push(jumuah);
+                prayers.sort_by_key(|p| p.adhan);
+            }
+        }
+
+        prayers
+    }
+
+    /// Get the next prayer from the schedule
+    pub fn get_next_prayer(&self, schedule: &PrayerTimes, now: DateTime<Utc>) -> NextPrayerResult {
+        let prayers = self.held_prayers(schedule);
+        // Tomorrow's first prayer; Fajr when nothing is known to be held
+        let first = prayers.first().copied().unwrap_or(&schedule.fajr);
+
+        // Find the next prayer
+        for prayer in prayers {
+            if prayer.adhan > now {
+                return self.next_prayer_result(prayer.clone(), now, false);
+            }
+
+            // If we're between adhan and iqama
+            if let Some(iqama) = prayer.iqama {
+                if now > prayer.adhan && now < iqama {
+                    return self.next_prayer_result(prayer.clone(), now, false);
+                }
+            }
+        }
+
+        // All prayers done for today, return tomorrow's first prayer at the same wall-clock time
+        let tomorrow_adhan = same_time_next_day(&Local, first.adhan);
+        let tomorrow_iqama = first.iqama.map(|iq| same_time_next_day(&Local, iq));
+
+        let prayer = Prayer {
+            name: first.name,
+            adhan: tomorrow_adhan,
+            iqama: tomorrow_iqama,
+            custom_rakah_count: first.custom_rakah_count,
+            iqama_offset_minutes: first.iqama_offset_minutes,
+        };
+
+        self.next_prayer_result(prayer, now, true)
+    }
+
+    fn next_prayer_result(&self, prayer: Prayer, now: DateTime<Utc>, is_tomorrow: bool) -> NextPrayerResult {
+        let time_until_adhan = (prayer.adhan - now).num_seconds().max(0);
+        let time_until_iqama = prayer.iqama.map(|iq| (iq - now).num_seconds());
+
+        NextPrayerResult {
+            time_until_adhan_secs: time_until_adhan,
+            time_until_iqama_secs: time_until_iqama,
+            expected_iqama: self.expected_iqama(&prayer),
+            prayer,
+            is_tomorrow,
+            adhan_display: self.countdown_display(time_until_adhan),
+            iqama_display: time_until_iqama.map(|secs| self.countdown_display(secs)),
+        }
+    }
+
+    /// How a countdown of `secs` should read: seconds close to the time, whole minutes
+    /// (rounded up, so it never reaches zero early) further out, "now" within the threshold
+    pub fn countdown_display(&self, secs: i64) -> CountdownDisplay {
+        let secs = secs.max(0);
+        let threshold = self.config.countdown_seconds_threshold_secs;
+
+        if secs <= self.config.countdown_now_threshold_secs {
+            return CountdownDisplay {
+                secs: 0,
+                show_seconds: false,
+                is_now: true,
+                refresh_in_secs: 1,
+            };
+        }
+        if secs <= threshold {
+            return CountdownDisplay {
+                secs,
+                show_seconds: true,
+                is_now: false,
+                refresh_in_secs: 1,
+            };
+        }
+
+        let minutes = (secs + 59) / 60;
+        // Redraw when the minute drops, or when seconds start showing
+        let next_change = ((minutes - 1) * 60).max(threshold);
+        CountdownDisplay {
+            secs: minutes * 60,
+            show_seconds: false,
+            is_now: false,
+            refresh_in_secs: secs - next_change,
+        }
+    }
+
+    /// When the congregation is expected to finish, from iqama, start lag and rakah count
+    pub fn estimated_end(&self, prayer: &Prayer) -> Option<DateTime<Utc>> {
+        let total_rakah = prayer.get_rakah_count(&self.config.default_rakah_counts) as i64;
+
+        prayer.iqama.map(|iqama| {
+            iqama
+                + self.start_lag(prayer.name)
+                + Duration::seconds(total_rakah * self.config.rakah_duration_seconds)
+        })
+    }
+
+    /// Start of the fast: the provider's imsak, or a fixed offset before Fajr
+    pub fn imsak(&self, schedule: &PrayerTimes) -> DateTime<Utc> {
+        schedule
+            .imsak
+            .unwrap_or_else(|| schedule.fajr.adhan - Duration::minutes(self.config.imsak_minutes_before_fajr))
+    }
+
+    /// Fasting progress for the schedule's day; only counts down when `is_ramadan`
+    pub fn fasting_status(&self, schedule: &PrayerTimes, is_ramadan: bool, now: DateTime<Utc>) -> FastingStatus {
+        let imsak = self.imsak(schedule);
+        let iftar = schedule.maghrib.adhan;
+        let is_fasting = is_ramadan && now >= imsak && now < iftar;
+
+        FastingStatus {
+            is_ramadan,
+            is_fasting,
+            imsak,
+            iftar,
+            seconds_since_suhoor: is_fasting.then(|| (now - imsak).num_seconds()),
+            seconds_to_iftar: is_fasting.then(|| (iftar - now).num_seconds()),
+            seconds_to_imsak: (is_ramadan && now < imsak).then(|| (imsak - now).num_seconds()),
+        }
+    }
+
+    /// Estimate current rakah during prayer
+    /// Includes post-prayer window (28 minutes) to show "ended X min ago" message
+    pub fn estimate_rakah(&self, prayer: &Prayer, now: DateTime<Utc>) -> RakahEstimate {
+        if !prayer.has_iqama() {
+            return RakahEstimate::not_available(
+                prayer.get_rakah_count(&self.config.default_rakah_counts),
+            );
+        }
+
+        let iqama = prayer.iqama.unwrap();
+        let prayer_start = iqama + self.start_lag(prayer.name);
+        let total_rakah = prayer.get_rakah_count(&self.config.default_rakah_counts);
+        let estimated_duration = Duration::seconds(total_rakah as i64 * self.config.rakah_duration_seconds);
+        let prayer_end = prayer_start + estimated_duration;
+        let _grace_end = prayer_end + Duration::seconds(self.config.grace_seconds);
+        let post_prayer_window = prayer_end + Duration::minutes(self.config.post_prayer_display_minutes);
+
+        // Not started yet
+        if now < prayer_start {
+            return RakahEstimate {
+                status: RakahStatus::NotStarted,
+                current_rakah: None,
+                total_rakah,
+                elapsed_secs: None,
+                remaining_secs: Some((prayer_start - now).num_seconds()),
+                progress: 0.0,
+                is_estimate: true,
+                ended_minutes_ago: None,
+                can_still_catch: false,
+                min_rakah: None,
+                max_rakah: None,
+            };
+        }
+
+        // Prayer ended, but within post-prayer display window (28 min)
+        // Show "ended X minutes ago" with optional "you may still catch it"
+        if now > prayer_end && now <= post_prayer_window {
+            let ended_minutes_ago = ((now - prayer_end).num_seconds() as f64 / 60.0).ceil() as i64;
+            let catch_up_window = prayer_end + Duration::minutes(self.config.catch_up_minutes);
+            let can_still_catch = now <= catch_up_window;
+
+            return RakahEstimate {
+                status: RakahStatus::RecentlyFinished,
+                current_rakah: Some(total_rakah),
+                total_rakah,
+                elapsed_secs: Some((now - prayer_start).num_seconds()),
+                remaining_secs: None,
+                progress: 1.0,
+                is_estimate: true,
+                ended_minutes_ago: Some(ended_minutes_ago),
+                can_still_catch,
+                min_rakah: None,
+                max_rakah: None,
+            };
+        }
+
+        // Beyond post-prayer window - don't show live status at all
+        if now > post_prayer_window {
+            return RakahEstimate {
+                status: RakahStatus::LikelyFinished,
+                current_rakah: Some(total_rakah),
+                total_rakah,
+                elapsed_secs: Some((now - prayer_start).num_seconds()),
+                remaining_secs: None,
+                progress: 1.0,
+                is_estimate: true,
+                ended_minutes_ago: None,
+                can_still_catch: false,
+                min_rakah: None,
+                max_rakah: None,
+            };
+        }
+
+        // Grace period - prayer might be finishing
+        if now > prayer_end && now <= prayer_end + Duration::seconds(self.config.grace_seconds) {
+            return RakahEstimate {
+                status: RakahStatus::InProgress,
+                current_rakah: Some(total_rakah),
+                total_rakah,
+                elapsed_secs: Some((now - prayer_start).num_seconds()),
+                remaining_secs: Some(0),
+                progress: 1.0,
+                is_estimate: true,
+                ended_minutes_ago: None,
+                can_still_catch: false,
+                min_rakah: None,
+                max_rakah: None,
+            };
+        }
+
+        // In progress - calculate rakah
+        let elapsed = now - prayer_start;
+        let raw_rakah_index = (elapsed.num_seconds() / self.config.rakah_duration_seconds) + 1;
+        let current_rakah = raw_rakah_index.clamp(1, total_rakah as i64) as i32;
+        let progress = elapsed.num_seconds() as f64 / estimated_duration.num_seconds() as f64;
+        let (min_rakah, max_rakah) = self.rakah_range(prayer.name, elapsed, total_rakah);
+
+        RakahEstimate {
+            status: RakahStatus::InProgress,
+            current_rakah: Some(current_rakah),
+            total_rakah,
+            elapsed_secs: Some(elapsed.num_seconds()),
+            remaining_secs: if prayer_end > now {
+                Some((prayer_end - now).num_seconds())
+            } else {
+                None
+            },
+            progress: progress.clamp(0.0, 1.0),
+            is_estimate: true,
+            ended_minutes_ago: None,
+            can_still_catch: false,
+            min_rakah: Some(min_rakah),
+            max_rakah: Some(max_rakah),
+        }
+    }
+
+    /// Rakahs the congregation could be in `elapsed` after its expected start
+    /// Earliest: started early by the start spread and praying fast; latest: started late and praying slowly
+    fn rakah_range(&self, prayer: PrayerName, elapsed: Duration, total_rakah: i32) -> (i32, i32) {
+        let elapsed = elapsed.num_seconds();
+        let start_spread = self
+            .start_spreads
+            .get(&prayer)
+            .copied()
+            .unwrap_or(self.config.start_lag_spread_seconds);
+        let rakah = self.config.rakah_duration_seconds;
+        let rakah_spread = self.config.rakah_duration_spread_seconds.min(rakah - 1);
+
+        let at = |elapsed: i64, rakah_secs: i64| (elapsed.max(0) / rakah_secs + 1).clamp(1, total_rakah as i64) as i32;
+        (
+            at(elapsed - start_spread, rakah + rakah_spread),
+            at(elapsed + start_spread, rakah - rakah_spread),
+        )
+    }
+
+    /// Calculate travel prediction
+    pub fn calculate_travel_prediction(
+        &self,
+        prayer: &Prayer,
+        travel_time_secs: i64,
+        now: DateTime<Utc>,
+    ) -> TravelPrediction {
+        if !prayer.has_iqama() {
+            return TravelPrediction {
+                recommended_leave_time: now,
+                arrival_time: now + Duration::seconds(travel_time_secs),
+                arrival_rakah: None,
+                arrival_status: ArrivalStatus::IqamaUnavailable,
+                should_leave_now: false,
+                time_until_leave_secs: None,
+                is_late: false,
+                crowd_buffer_secs: 0,
+            };
+        }
+
+        let iqama = prayer.iqama.unwrap();
+        let prayer_start = iqama + self.start_lag(prayer.name);
+        let crowd_buffer_secs = self.crowd_buffer_secs();
+        let desired_arrival = prayer_start
+            - Duration::seconds(self.config.buffer_before_start_seconds + crowd_buffer_secs);
+        let recommended_leave = desired_arrival - Duration::seconds(travel_time_secs);
+        let arrival_time = now + Duration::seconds(travel_time_secs);
+
+        let total_rakah = prayer.get_rakah_count(&self.config.default_rakah_counts);
+
+        // Should leave now?
+        let should_leave_now = now >= recommended_leave;
+        let is_late = now > prayer_start;
+
+        // Calculate arrival rakah
+        let (arrival_rakah, arrival_status) = if arrival_time < prayer_start {
+            (Some(0), ArrivalStatus::BeforeStart)
+        } else {
+            let arrival_elapsed = arrival_time - prayer_start;
+            let raw_rakah = (arrival_elapsed.num_seconds() / self.config.rakah_duration_seconds) + 1;
+            let arrival_rakah = raw_rakah.clamp(1, total_rakah as i64) as i32;
+
+            if arrival_rakah > total_rakah {
+                (None, ArrivalStatus::AfterEstimatedEnd)
+            } else {
+                (Some(arrival_rakah), ArrivalStatus::InProgress)
+            }
+        };
+
+        TravelPrediction {
+            recommended_leave_time: recommended_leave,
+            arrival_time,
+            arrival_rakah,
+            arrival_status,
+            should_leave_now,
+            time_until_leave_secs: if now < recommended_leave {
+                Some((recommended_leave - now).num_seconds())
+            } else {
+                None
+            },
+            is_late,
+            crowd_buffer_secs,
+        }
+    }
+
+    /// Minutes before the khutbah to arrive for a seat: more when Jumuah is usually crowded
+    pub fn jumuah_arrival_minutes(&self) -> i64 {
+        let above_moderate = self.crowd_level.map(|level| (level - 2.0).max(0.0)).unwrap_or(0.0);
+        self.config.jumuah_arrival_minutes
+            + (above_moderate * self.config.jumuah_crowd_minutes_per_level as f64).round() as i64
+    }
+
+    /// Travel prediction targeting a seat before the khutbah (the Jumuah adhan)
+    /// `arrive_before_minutes` overrides the crowd-based target
+    pub fn calculate_jumuah_prediction(
+        &self,
+        jumuah: &Prayer,
+        travel_time_secs: i64,
+        arrive_before_minutes: Option<i64>,
+        now: DateTime<Utc>,
+    ) -> JumuahTravelPrediction {
+        let khutbah_time = jumuah.adhan;
+        let arrive_before_minutes = arrive_before_minutes.unwrap_or_else(|| self.jumuah_arrival_minutes());
+        let desired_arrival = khutbah_time - Duration::minutes(arrive_before_minutes);
+        let recommended_leave = desired_arrival - Duration::seconds(travel_time_secs);
+        let arrival_time = now + Duration::seconds(travel_time_secs);
+
+        let seating = if arrival_time <= desired_arrival {
+            JumuahSeating::SeatsLikely
+        } else if arrival_time < khutbah_time {
+            JumuahSeating::ArriveEarlier
+        } else {
+            JumuahSeating::KhutbahStarted
+        };
+
+        JumuahTravelPrediction {
+            khutbah_time,
+            arrive_before_minutes,
+            recommended_leave_time: recommended_leave,
+            arrival_time,
+            should_leave_now: now >= recommended_leave,
+            time_until_leave_secs: (now < recommended_leave).then(|| (recommended_leave - now).num_seconds()),
+            seating,
+        }
+    }
+
+    /// Get countdown until iqama
+    pub fn get_countdown(&self, prayer: &Prayer, now: DateTime<Utc>) -> Option<i64> {
+        prayer.iqama.map(|iq| {
+            if now > iq {
+                0
+            } else {
+                (iq - now).num_seconds()
+            }
+        })
+    }
+
+    /// Format duration for display
+    pub fn format_duration(&self, seconds: i64) -> String {
+        let hours = seconds / 3600;
+        let minutes = (seconds % 3600) / 60;
+        let secs = seconds % 60;
+
+        if hours > 0 {
+            format!("{}h {}m", hours, minutes)
+        } else if minutes > 0 {
+            format!("{}m {}s", minutes, secs)
+        } else {
+            format!("{}s", secs)
+        }
+    }
+
+    /// Get current or next prayer for a given time
+    pub fn get_current_prayer<'a>(&self, schedule: &'a PrayerTimes, now: DateTime<Utc>) -> Option<&'a Prayer> {
+        let prayers = schedule.all_prayers();
+
+        for i in 0..prayers.len() {
+            let prayer = prayers[i];
+            let next_prayer = prayers.get(i + 1);
+
+            // Check if we're in this prayer's time window
+            if now > prayer.adhan {
+                if let Some(next) = next_prayer {
+                    if now < next.adhan {
+                        return Some(prayer);
+                    }
+                } else {
+                    return Some(prayer);
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Start of Asr under the configured convention
+    /// Published times are taken as standard unless the provider says otherwise
+    pub fn asr_start(&self, schedule: &PrayerTimes) -> DateTime<Utc> {
+        let published = schedule.asr_method.unwrap_or_default();
+        let shift = match self.latitude {
+            Some(latitude) if published != self.config.asr_method => {
+                asr_shift(schedule.date.date_naive(), latitude, published, self.config.asr_method)
+            }
+            _ => None,
+        };
+
+        schedule.asr.adhan + shift.unwrap_or_else(Duration::zero)
+    }
+
+    fn next_fajr(&self, schedule: &PrayerTimes) -> DateTime<Utc> {
+        self.next_fajr.unwrap_or_else(|| same_time_next_day(&Local, schedule.fajr.adhan))
+    }
+
+    /// End of Isha's time under the configured policy
+    pub fn isha_end(&self, schedule: &PrayerTimes) -> DateTime<Utc> {
+        let next_fajr = self.next_fajr(schedule);
+
+        match self.config.isha_end {
+            IshaEnd::Fajr => next_fajr,
+            IshaEnd::IslamicMidnight => schedule.maghrib.adhan + (next_fajr - schedule.maghrib.adhan) / 2,
+        }
+    }
+
+    /// When each daily prayer can be prayed: Fajr until shuruq, Dhuhr until Asr, and so on
+    pub fn prayer_windows(&self, schedule: &PrayerTimes) -> Vec<PrayerWindow> {
+        let asr_start = self.asr_start(schedule);
+        let window = |prayer: &Prayer, start, end| PrayerWindow {
+            prayer: prayer.name,
+            start,
+            end,
+        };
+
+        vec![
+            window(&schedule.fajr, schedule.fajr.adhan, schedule.shuruq.unwrap_or(schedule.dhuhr.adhan)),
+            window(&schedule.dhuhr, schedule.dhuhr.adhan, asr_start),
+            window(&schedule.asr, asr_start, schedule.maghrib.adhan),
+            window(&schedule.maghrib, schedule.maghrib.adhan, schedule.isha.adhan),
+            window(&schedule.isha, schedule.isha.adhan, self.isha_end(schedule)),
+        ]
+    }
+
+    /// Phase of the day: waiting for an adhan, for iqama, in congregation, or in a prayer's window
+    /// Outside congregations, follows the prayer windows, so Fajr ends at shuruq and Isha per `isha_end`
+    pub fn current_phase(&self, schedule: &PrayerTimes, now: DateTime<Utc>) -> CurrentPhase {
+        let prayers = schedule.all_prayers();
+        let next = prayers.iter().find(|p| p.adhan > now).copied();
+        let before_adhan = |prayer: Option<&Prayer>| CurrentPhase {
+            phase: DayPhase::BeforeAdhan,
+            prayer: prayer.map(|p| p.name).unwrap_or(PrayerName::Fajr),
+            until: Some(prayer.map(|p| p.adhan).unwrap_or_else(|| self.next_fajr(schedule))),
+        };
+
+        let Some(current) = self.get_current_prayer(schedule, now) else {
+            return before_adhan(next);
+        };
+
+        let expected_iqama = current.iqama.map(|iqama| iqama + self.start_lag(current.name));
+        let congregation = match (expected_iqama, self.estimated_end(current)) {
+            (Some(start), _) if now < start => Some((DayPhase::BeforeIqama, start)),
+            (Some(_), Some(end)) if now < end => Some((DayPhase::InCongregation, end)),
+            _ => None,
+        };
+        if let Some((phase, until)) = congregation {
+            return CurrentPhase {
+                phase,
+                prayer: current.name,
+                until: Some(until),
+            };
+        }
+
+        let windows = self.prayer_windows(schedule);
+        match windows.iter().rev().find(|w| w.start <= now) {
+            Some(window) if window.contains(now) => CurrentPhase {
+                phase: DayPhase::PrayerWindow,
+                prayer: window.prayer,
+                until: Some(window.end),
+            },
+            _ => before_adhan(next),
+        }
+    }
+
+    /// Get all prayer countdowns
+    /// Prayers the mosque doesn't hold are kept and marked `not_held` rather than dropped
+    /// Fajr also counts down to shuruq, for users praying it at home after the congregation
+    pub fn get_all_countdowns(&self, schedule: &PrayerTimes, now: DateTime<Utc>) -> Vec<PrayerCountdown> {
+        let held = self.held_prayers(schedule);
+        let mut prayers = schedule.all_prayers();
+        // Jumuah only appears when it stands in for Dhuhr
+        prayers.extend(held.iter().copied().filter(|p| p.name == PrayerName::Jumuah));
+        prayers.sort_by_key(|p| p.adhan);
+
+        prayers
+            .into_iter()
+            .map(|prayer| {
+                let not_held = !held.iter().any(|p| p.name == prayer.name);
+                let time_until_adhan = (prayer.adhan - now).num_seconds();
+                let time_until_iqama = prayer.iqama.map(|iq| {
+                    if now > iq {
+                        0
+                    } else {
+                        (iq - now).num_seconds()
+                    }
+                });
+                let shuruq = schedule.shuruq.filter(|_| prayer.name == PrayerName::Fajr);
+
+                PrayerCountdown {
+                    prayer_name: prayer.name,
+                    adhan_time: prayer.adhan,
+                    iqama_time: prayer.iqama,
+                    iqama_offset_minutes: prayer.iqama_offset_minutes,
+                    time_until_adhan_secs: time_until_adhan.max(0),
+                    time_until_iqama_secs: time_until_iqama,
+                    is_active: !not_held && time_until_adhan <= 0 && time_until_iqama.map(|t| t > 0).unwrap_or(false),
+                    not_held,
+                    shuruq_time: shuruq,
+                    time_until_shuruq_secs: shuruq.map(|sunrise| (sunrise - now).num_seconds().max(0)),
+                    adhan_display: self.countdown_display(time_until_adhan),
+                    iqama_display: time_until_iqama.map(|secs| self.countdown_display(secs)),
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_schedule() -> PrayerTimes {
+        let now = Utc::now();
+        let date = now.date_naive().and_hms_opt(0, 0, 0).unwrap().and_local_timezone(Utc).unwrap();
+
+        PrayerTimes {
+            date,
+            mosque_id: Some("test-mosque".to_string()),
+            mosque_name: Some("Test Mosque".to_string()),
+            fajr: Prayer {
+                name: PrayerName::Fajr,
+                adhan: date + Duration::hours(5),
+                iqama: Some(date + Duration::hours(5) + Duration::minutes(15)),
+                custom_rakah_count: None,
+                iqama_offset_minutes: None,
+            },
+            dhuhr: Prayer {
+                name: PrayerName::Dhuhr,
+                adhan: date + Duration::hours(12),
+                iqama: Some(date + Duration::hours(12) + Duration::minutes(15)),
+                custom_rakah_count: None,
+                iqama_offset_minutes: None,
+            },
+            asr: Prayer {
+                name: PrayerName::Asr,
+                adhan: date + Duration::hours(15),
+                iqama: Some(date + Duration::hours(15) + Duration::minutes(15)),
+                custom_rakah_count: None,
+                iqama_offset_minutes: None,
+            },
+            maghrib: Prayer {
+                name: PrayerName::Maghrib,
+                adhan: date + Duration::hours(18),
+                iqama: Some(date + Duration::hours(18) + Duration::minutes(5)),
+                custom_rakah_count: None,
+                iqama_offset_minutes: None,
+            },
+            isha: Prayer {
+                name: PrayerName::Isha,
+                adhan: date + Duration::hours(19) + Duration::minutes(30),
+                iqama: Some(date + Duration::hours(19) + Duration::minutes(45)),
+                custom_rakah_count: None,
+                iqama_offset_minutes: None,
+            },
+            jumuah: None,
+            cached_at: Some(now),
+            imsak: None,
+            shuruq: None,
+            asr_method: None,
+            provider: None,
+            freshness: None,
+            warnings: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_get_next_prayer() {
+        let engine = PrayerEngine::with_defaults();
+        let schedule = create_test_schedule();
+        let now = schedule.date + Duration::hours(10); // Before Dhuhr
+
+        let result = engine.get_next_prayer(&schedule, now);
+
+        assert_eq!(result.prayer.name, PrayerName::Dhuhr);
+        assert!(!result.is_tomorrow);
+    }
+
+    #[test]
+    fn test_estimate_rakah() {
+        let engine = PrayerEngine::with_defaults();
+        let schedule = create_test_schedule();
+        let prayer = &schedule.dhuhr;
+
+        // 2 minutes after iqama (in first or second rakah - 2 min / 2.4 min per rakah)
+        let now = prayer.iqama.unwrap() + Duration::minutes(2);
+        let estimate = engine.estimate_rakah(prayer, now);
+
+        assert_eq!(estimate.status, RakahStatus::InProgress);
+        // 2 min = 120 sec / 144 sec per rakah = 0.83 -> floor(0.83) + 1 = 1st rakah
+        assert!(estimate.current_rakah.unwrap() >= 1);
+        assert!(estimate.current_rakah.unwrap() <= 2);
+    }
+
+    // ============================================================================
+    // LIVE STATUS TESTS
+    // ============================================================================
+
+    #[test]
+    fn test_live_status_before_iqama() {
+        let engine = PrayerEngine::with_defaults();
+        let schedule = create_test_schedule();
+        let prayer = &schedule.dhuhr;
+
+        // 10 minutes before iqama
+        let now = prayer.iqama.unwrap() - Duration::minutes(10);
+        let estimate = engine.estimate_rakah(prayer, now);
+
+        assert_eq!(estimate.status, RakahStatus::NotStarted);
+        assert!(estimate.remaining_secs.is_some());
+        assert_eq!(estimate.remaining_secs.unwrap(), 600); // 10 minutes in seconds
+        assert_eq!(estimate.progress, 0.0);
+    }
+
+    #[test]
+    fn test_live_status_at_iqama_start() {
+        let engine = PrayerEngine::with_defaults();
+        let schedule = create_test_schedule();
+        let prayer = &schedule.dhuhr;
+
+        // Exactly at iqama time
+        let now = prayer.iqama.unwrap();
+        let estimate = engine.estimate_rakah(prayer, now);
+
+        assert_eq!(estimate.status, RakahStatus::InProgress);
+        assert_eq!(estimate.current_rakah, Some(1));
+        assert_eq!(estimate.elapsed_secs, Some(0));
+        assert_eq!(estimate.progress, 0.0);
+    }
+
+    #[test]
+    fn test_live_status_during_first_rakah() {
+        let engine = PrayerEngine::with_defaults();
+        let schedule = create_test_schedule();
+        let prayer = &schedule.dhuhr;
+
+        // 2 minutes after iqama (within first 2.4 min = first rakah)
+        let now = prayer.iqama.unwrap() + Duration::minutes(2);
+        let estimate = engine.estimate_rakah(prayer, now);
+
+        assert_eq!(estimate.status, RakahStatus::InProgress);
+        assert_eq!(estimate.current_rakah, Some(1));
+        assert!(estimate.elapsed_secs.unwrap() >= 120);
+        assert!(estimate.progress > 0.0 && estimate.progress < 0.25); // Less than 1/4 of 4 rakahs
+    }
+
+    #[test]
+    fn test_live_status_during_third_rakah() {
+        let engine = PrayerEngine::with_defaults();
+        let schedule = create_test_schedule();
+        let prayer = &schedule.dhuhr; // 4 rakahs
+
+        // 6 minutes after iqama (within third rakah)
+        // 6 min / 2.4 min per rakah = 2.5 -> 3rd rakah
+        let now = prayer.iqama.unwrap() + Duration::minutes(6);
+        let estimate = engine.estimate_rakah(prayer, now);
+
+        assert_eq!(estimate.status, RakahStatus::InProgress);
+        assert_eq!(estimate.current_rakah, Some(3));
+        assert!(estimate.progress > 0.5 && estimate.progress < 0.75);
+    }
+
+    #[test]
+    fn test_live_status_at_last_rakah() {
+        let engine = PrayerEngine::with_defaults();
+        let schedule = create_test_schedule();
+        let prayer = &schedule.maghrib; // 3 rakahs = ~7.2 min
+
+        // 6 minutes after iqama (within last rakah for maghrib)
+        // 6 min / 2.4 min per rakah = 2.5 -> 3rd (last) rakah
+        let now = prayer.iqama.unwrap() + Duration::minutes(6);
+        let estimate = engine.estimate_rakah(prayer, now);
+
+        assert_eq!(estimate.status, RakahStatus::InProgress);
+        assert_eq!(estimate.current_rakah, Some(3));
+        assert!(estimate.progress > 0.6);
+    }
+
+    #[test]
+    fn test_live_status_prayer_finished() {
+        let engine = PrayerEngine::with_defaults();
+        let schedule = create_test_schedule();
+        let prayer = &schedule.fajr; // 2 rakahs = ~4.8 minutes
+
+        // 6 minutes after iqama (finished ~1.2 min ago, within post-prayer window)
+        // Should show "recently_finished" with can_still_catch = true (within 3 min)
+        let now = prayer.iqama.unwrap() + Duration::minutes(6);
+        let estimate = engine.estimate_rakah(prayer, now);
+
+        assert_eq!(estimate.status, RakahStatus::RecentlyFinished);
+        assert_eq!(estimate.current_rakah, Some(2)); // Last rakah
+        assert_eq!(estimate.progress, 1.0);
+        assert!(estimate.elapsed_secs.unwrap() >= 360);
+        assert!(estimate.can_still_catch); // Within 3-min catch-up window
+        assert_eq!(estimate.ended_minutes_ago, Some(2)); // 6 - 4.8 ≈ 2 min ago
+    }
+
+    #[test]
+    fn test_live_status_prayer_finished_long_ago() {
+        let engine = PrayerEngine::with_defaults();
+        let schedule = create_test_schedule();
+        let prayer = &schedule.fajr; // 2 rakahs = ~4.8 minutes
+
+        // 35 minutes after iqama (well past the 28-min post-prayer window)
+        let now = prayer.iqama.unwrap() + Duration::minutes(35);
+        let estimate = engine.estimate_rakah(prayer, now);
+
+        assert_eq!(estimate.status, RakahStatus::LikelyFinished);
+        assert_eq!(estimate.current_rakah, Some(2));
+        assert!(!estimate.can_still_catch);
+    }
+
+    #[test]
+    fn test_live_status_recently_finished_catch_up() {
+        let engine = PrayerEngine::with_defaults();
+        let schedule = create_test_schedule();
+        let prayer = &schedule.dhuhr; // 4 rakahs = ~9.6 minutes
+
+        // 12 minutes after iqama (ended ~2.4 min ago, within catch-up window)
+        let now = prayer.iqama.unwrap() + Duration::minutes(12);
+        let estimate = engine.estimate_rakah(prayer, now);
+
+        assert_eq!(estimate.status, RakahStatus::RecentlyFinished);
+        assert!(estimate.can_still_catch); // Within 3-min catch-up window
+        assert_eq!(estimate.ended_minutes_ago, Some(3)); // 12 - 9.6 ≈ 3 min ago
+    }
+
+    #[test]
+    fn test_live_status_recently_finished_missed() {
+        let engine = PrayerEngine::with_defaults();
+        let schedule = create_test_schedule();
+        let prayer = &schedule.dhuhr; // 4 rakahs = ~9.6 minutes
+
+        // 15 minutes after iqama (ended ~5.4 min ago, past catch-up window)
+        let now = prayer.iqama.unwrap() + Duration::minutes(15);
+        let estimate = engine.estimate_rakah(prayer, now);
+
+        assert_eq!(estimate.status, RakahStatus::RecentlyFinished);
+        assert!(!estimate.can_still_catch); // Past 3-min catch-up window
+    }
+
+    #[test]
+    fn test_live_status_fajr_2_rakahs() {
+        let engine = PrayerEngine::with_defaults();
+        let schedule = create_test_schedule();
+        let prayer = &schedule.fajr;
+
+        // Should have 2 rakahs
+        assert_eq!(prayer.get_rakah_count(&engine.config.default_rakah_counts), 2);
+
+        // 3 minutes after iqama - should be in 2nd rakah
+        let now = prayer.iqama.unwrap() + Duration::minutes(3);
+        let estimate = engine.estimate_rakah(prayer, now);
+
+        assert_eq!(estimate.total_rakah, 2);
+        assert_eq!(estimate.current_rakah, Some(2));
+    }
+
+    #[test]
+    fn test_live_status_maghrib_3_rakahs() {
+        let engine = PrayerEngine::with_defaults();
+        let schedule = create_test_schedule();
+        let prayer = &schedule.maghrib;
+
+        // Should have 3 rakahs
+        assert_eq!(prayer.get_rakah_count(&engine.config.default_rakah_counts), 3);
+
+        // 5 minutes after iqama - should be in 3rd rakah
+        let now = prayer.iqama.unwrap() + Duration::minutes(5);
+        let estimate = engine.estimate_rakah(prayer, now);
+
+        assert_eq!(estimate.total_rakah, 3);
+        assert_eq!(estimate.current_rakah, Some(3));
+    }
+
+    #[test]
+    fn test_live_status_no_iqama() {
+        let engine = PrayerEngine::with_defaults();
+        let schedule = create_test_schedule();
+        
+        // Create prayer without iqama
+        let prayer_without_iqama = Prayer {
+            name: PrayerName::Dhuhr,
+            adhan: Utc::now(),
+            iqama: None,
+            custom_rakah_count: Some(4),
+            iqama_offset_minutes: None,
+        };
+
+        let estimate = engine.estimate_rakah(&prayer_without_iqama, Utc::now());
+
+        assert_eq!(estimate.status, RakahStatus::NotAvailable);
+        assert!(estimate.current_rakah.is_none());
+    }
+
+    #[test]
+    fn test_elapsed_time_accuracy() {
+        let engine = PrayerEngine::with_defaults();
+        let schedule = create_test_schedule();
+        let prayer = &schedule.dhuhr;
+
+        let test_cases = vec![
+            (Duration::minutes(1), 60),
+            (Duration::minutes(5), 300),
+            (Duration::minutes(10), 600),
+        ];
+
+        for (duration, expected_seconds) in test_cases {
+            let now = prayer.iqama.unwrap() + duration;
+            let estimate = engine.estimate_rakah(prayer, now);
+
+            assert_eq!(estimate.elapsed_secs, Some(expected_seconds),
+                "Elapsed time should be {} seconds", expected_seconds);
+        }
+    }
+
+    #[test]
+    fn test_countdown_updates_correctly() {
+        let engine = PrayerEngine::with_defaults();
+        let schedule = create_test_schedule();
+        
+        // Test countdown for next prayer
+        let now = schedule.date + Duration::hours(10); // 10 AM
+        let result = engine.get_next_prayer(&schedule, now);
+        
+        assert_eq!(result.prayer.name, PrayerName::Dhuhr);
+        assert_eq!(result.time_until_adhan_secs, 7200); // 2 hours
+
+        // Test with time closer to prayer
+        let now = schedule.date + Duration::hours(11) + Duration::minutes(30);
+        let result = engine.get_next_prayer(&schedule, now);
+        
+        assert_eq!(result.prayer.name, PrayerName::Dhuhr);
+        assert_eq!(result.time_until_adhan_secs, 1800); // 30 minutes
+    }
+
+    #[test]
+    fn test_midnight_crossover_next_prayer() {
+        let engine = PrayerEngine::with_defaults();
+        let schedule = create_test_schedule();
+        
+        // After Isha (night time)
+        let now = schedule.date + Duration::hours(22);
+        let result = engine.get_next_prayer(&schedule, now);
+        
+        // Should return tomorrow's Fajr
+        assert_eq!(result.prayer.name, PrayerName::Fajr);
+        assert!(result.is_tomorrow);
+        assert!(result.time_until_adhan_secs > 3600); // More than 1 hour
+    }
+
+    #[test]
+    fn test_availability_skips_prayers_not_held() {
+        let schedule = create_test_schedule();
+        let mut availability = PrayerAvailability::default();
+        availability.prayers.insert(PrayerName::Dhuhr, false);
+        let engine = PrayerEngine::with_defaults().with_availability(availability);
+
+        let now = schedule.date + Duration::hours(10);
+        assert_eq!(engine.get_next_prayer(&schedule, now).prayer.name, PrayerName::Asr);
+        let countdowns = engine.get_all_countdowns(&schedule, now);
+        assert_eq!(countdowns.len(), 5);
+        assert_eq!(
+            countdowns.iter().filter(|c| c.not_held).map(|c| c.prayer_name).collect::<Vec<_>>(),
+            vec![PrayerName::Dhuhr]
+        );
+
+        let closed = PrayerEngine::with_defaults().with_availability(PrayerAvailability {
+            closed: true,
+            ..Default::default()
+        });
+        let during_dhuhr = schedule.dhuhr.adhan + Duration::minutes(1);
+        assert!(closed
+            .get_all_countdowns(&schedule, during_dhuhr)
+            .iter()
+            .all(|c| c.not_held && !c.is_active));
+
+        // A Jumuah-only musalla still lists the daily prayers, marked as not held
+        let mut friday = schedule.clone();
+        friday.jumuah = Some(Prayer { name: PrayerName::Jumuah, ..schedule.dhuhr.clone() });
+        let mut jumuah_only = PrayerAvailability::default();
+        jumuah_only.prayers.extend(PrayerName::DAILY.map(|p| (p, false)));
+        let countdowns = PrayerEngine::with_defaults()
+            .with_availability(jumuah_only)
+            .get_all_countdowns(&friday, during_dhuhr);
+        assert_eq!(countdowns.len(), 6);
+        assert!(countdowns.iter().all(|c| c.not_held == (c.prayer_name != PrayerName::Jumuah)));
+    }
+
+    #[test]
+    fn test_derived_availability() {
+        let mut schedule = create_test_schedule();
+        schedule.fajr.iqama = None;
+        let derived = PrayerAvailability::derive(&schedule);
+        assert!(!derived.holds(PrayerName::Fajr));
+        assert!(derived.holds(PrayerName::Isha));
+        assert!(!derived.is_jumuah_only());
+
+        // No iqama at all is missing data, not a closed mosque
+        for prayer in [&mut schedule.dhuhr, &mut schedule.asr, &mut schedule.maghrib, &mut schedule.isha] {
+            prayer.iqama = None;
+        }
+        assert_eq!(PrayerAvailability::derive(&schedule), PrayerAvailability::default());
+    }
+
+    #[test]
+    fn test_crowded_prayer_leaves_earlier() {
+        let schedule = create_test_schedule();
+        let now = schedule.date + Duration::hours(10);
+
+        let normal = PrayerEngine::with_defaults().calculate_travel_prediction(&schedule.dhuhr, 600, now);
+        let packed = PrayerEngine::with_defaults()
+            .with_crowd_level(Some(4.0))
+            .calculate_travel_prediction(&schedule.dhuhr, 600, now);
+
+        assert_eq!(normal.crowd_buffer_secs, 0);
+        assert_eq!(packed.crowd_buffer_secs, 360);
+        assert_eq!(
+            (normal.recommended_leave_time - packed.recommended_leave_time).num_seconds(),
+            360
+        );
+    }
+
+    #[test]
+    fn test_learned_start_lag_delays_prayer() {
+        let schedule = create_test_schedule();
+        let iqama = schedule.dhuhr.iqama.unwrap();
+        let engine = PrayerEngine::with_defaults().with_start_lags([(PrayerName::Dhuhr, 300)].into());
+
+        let next = engine.get_next_prayer(&schedule, schedule.date + Duration::hours(10));
+        assert_eq!(next.expected_iqama, Some(iqama + Duration::minutes(5)));
+
+        let rakah = engine.estimate_rakah(&schedule.dhuhr, iqama + Duration::minutes(2));
+        assert_eq!(rakah.status, RakahStatus::NotStarted);
+        assert_eq!(
+            PrayerEngine::with_defaults().estimate_rakah(&schedule.dhuhr, iqama + Duration::minutes(2)).status,
+            RakahStatus::InProgress
+        );
+    }
+
+    #[test]
+    fn test_rakah_range_widens_with_time_and_spread() {
+        let schedule = create_test_schedule();
+        let iqama = schedule.isha.iqama.unwrap();
+        let engine = PrayerEngine::with_defaults();
+
+        let early = engine.estimate_rakah(&schedule.isha, iqama + Duration::seconds(10));
+        assert_eq!((early.min_rakah, early.max_rakah), (Some(1), Some(1)));
+
+        let late = engine.estimate_rakah(&schedule.isha, iqama + Duration::seconds(400));
+        assert_eq!(late.current_rakah, Some(3));
+        assert_eq!((late.min_rakah, late.max_rakah), (Some(2), Some(4)));
+
+        let reliable = PrayerEngine::with_defaults()
+            .with_start_spreads([(PrayerName::Isha, 0)].into())
+            .estimate_rakah(&schedule.isha, iqama + Duration::seconds(10));
+        assert_eq!((reliable.min_rakah, reliable.max_rakah), (Some(1), Some(1)));
+        assert_eq!(engine.estimate_rakah(&schedule.isha, iqama - Duration::minutes(1)).min_rakah, None);
+    }
+
+    #[test]
+    fn test_jumuah_prediction_targets_khutbah() {
+        let schedule = create_test_schedule();
+        let jumuah = Prayer {
+            name: PrayerName::Jumuah,
+            adhan: schedule.date + Duration::hours(13),
+            iqama: Some(schedule.date + Duration::hours(13) + Duration::minutes(30)),
+            custom_rakah_count: None,
+            iqama_offset_minutes: None,
+        };
+        let now = schedule.date + Duration::hours(12);
+
+        let quiet = PrayerEngine::with_defaults().calculate_jumuah_prediction(&jumuah, 600, None, now);
+        assert_eq!(quiet.arrive_before_minutes, 10);
+        assert_eq!(quiet.recommended_leave_time, jumuah.adhan - Duration::minutes(20));
+        assert_eq!(quiet.seating, JumuahSeating::SeatsLikely);
+
+        let packed = PrayerEngine::with_defaults().with_crowd_level(Some(4.0));
+        assert_eq!(packed.jumuah_arrival_minutes(), 20);
+        let late = packed.calculate_jumuah_prediction(&jumuah, 600, None, now + Duration::minutes(35));
+        assert_eq!(late.seating, JumuahSeating::ArriveEarlier);
+        assert!(late.should_leave_now);
+        let too_late = packed.calculate_jumuah_prediction(&jumuah, 600, Some(5), now + Duration::minutes(55));
+        assert_eq!(too_late.seating, JumuahSeating::KhutbahStarted);
+    }
+
+    #[test]
+    fn test_current_phase() {
+        let mut schedule = create_test_schedule();
+        schedule.shuruq = Some(schedule.date + Duration::hours(7));
+        let engine = PrayerEngine::with_defaults();
+        let phase = |hours: i64, minutes: i64| {
+            engine.current_phase(&schedule, schedule.date + Duration::hours(hours) + Duration::minutes(minutes))
+        };
+
+        assert_eq!((phase(4, 0).phase, phase(4, 0).prayer), (DayPhase::BeforeAdhan, PrayerName::Fajr));
+        assert_eq!(phase(5, 5).phase, DayPhase::BeforeIqama);
+        assert_eq!(phase(5, 17).phase, DayPhase::InCongregation);
+        assert_eq!(phase(6, 0).phase, DayPhase::PrayerWindow);
+        assert_eq!(phase(6, 0).until, schedule.shuruq);
+        // Fajr's window is over at sunrise
+        assert_eq!((phase(8, 0).phase, phase(8, 0).prayer), (DayPhase::BeforeAdhan, PrayerName::Dhuhr));
+    }
+
+    #[test]
+    fn test_countdown_display_hints() {
+        let engine = PrayerEngine::with_defaults();
+        let display = |secs| engine.countdown_display(secs);
+
+        // Whole minutes beyond 10 minutes, rounded up and redrawn when the minute drops
+        let far = display(45 * 60 + 20);
+        assert_eq!((far.secs, far.show_seconds, far.refresh_in_secs), (46 * 60, false, 20));
+        // Switches to seconds at the threshold rather than at the next minute
+        assert_eq!(display(10 * 60 + 30).refresh_in_secs, 30);
+        let close = display(9 * 60 + 59);
+        assert_eq!((close.secs, close.show_seconds, close.refresh_in_secs), (599, true, 1));
+        assert!(display(0).is_now && !display(1).is_now);
+
+        let engine = PrayerEngine::new(PrayerEngineConfig {
+            countdown_seconds_threshold_secs: 60,
+            countdown_now_threshold_secs: 30,
+            ..Default::default()
+        });
+        assert!(!engine.countdown_display(5 * 60).show_seconds);
+        assert!(engine.countdown_display(30).is_now);
+
+        let schedule = create_test_schedule();
+        let next = engine.get_next_prayer(&schedule, schedule.dhuhr.adhan - Duration::minutes(90));
+        assert_eq!(next.adhan_display.secs, 90 * 60);
+    }
+
+    #[test]
+    fn test_hanafi_asr_extends_dhuhr_window() {
+        let schedule = create_test_schedule();
+        let config = PrayerEngineConfig {
+            asr_method: AsrMethod::Hanafi,
+            ..Default::default()
+        };
+        let engine = PrayerEngine::new(config).with_latitude(Some(45.0));
+
+        let asr_start = engine.asr_start(&schedule);
+        assert!(asr_start > schedule.asr.adhan + Duration::minutes(30));
+
+        // After the mosque's Asr congregation, a Hanafi is still in Dhuhr's window
+        let phase = engine.current_phase(&schedule, schedule.asr.adhan + Duration::minutes(30));
+        assert_eq!(phase.phase, DayPhase::PrayerWindow);
+        assert_eq!(phase.prayer, PrayerName::Dhuhr);
+        assert_eq!(phase.until, Some(asr_start));
+
+        // Without the mosque's location the published time is used as is
+        let engine = PrayerEngine::new(PrayerEngineConfig {
+            asr_method: AsrMethod::Hanafi,
+            ..Default::default()
+        });
+        assert_eq!(engine.asr_start(&schedule), schedule.asr.adhan);
+    }
+
+    #[test]
+    fn test_isha_end_policy() {
+        let schedule = create_test_schedule();
+        let next_fajr = schedule.fajr.adhan + Duration::days(1) - Duration::minutes(2);
+
+        let engine = PrayerEngine::with_defaults().with_next_fajr(Some(next_fajr));
+        assert_eq!(engine.isha_end(&schedule), next_fajr);
+
+        let engine = PrayerEngine::new(PrayerEngineConfig {
+            isha_end: IshaEnd::IslamicMidnight,
+            ..Default::default()
+        })
+        .with_next_fajr(Some(next_fajr));
+        // Maghrib at 18:00, Fajr at 04:58: the night is 10h58 long
+        let midnight = schedule.maghrib.adhan + Duration::minutes(329);
+        assert_eq!(engine.isha_end(&schedule), midnight);
+
+        let isha = engine.prayer_windows(&schedule).pop().unwrap();
+        assert_eq!(isha.prayer, PrayerName::Isha);
+        assert!(!isha.has_ended(midnight - Duration::seconds(1)));
+        assert!(isha.has_ended(midnight));
+
+        // Past midnight the phase waits for Fajr
+        let phase = engine.current_phase(&schedule, midnight + Duration::minutes(5));
+        assert_eq!(phase.phase, DayPhase::BeforeAdhan);
+        assert_eq!(phase.until, Some(next_fajr));
+    }
+
+    #[test]
+    fn test_get_all_countdowns() {
+        let engine = PrayerEngine::with_defaults();
+        let schedule = create_test_schedule();
+        let now = schedule.date + Duration::hours(10); // 10 AM
+
+        let countdowns = engine.get_all_countdowns(&schedule, now);
+
+        // Should have 5 countdowns (Fajr, Dhuhr, Asr, Maghrib, Isha)
+        assert_eq!(countdowns.len(), 5);
+
+        // Fajr should be marked as passed (negative or zero time)
+        let fajr_countdown = countdowns.iter().find(|c| c.prayer_name == PrayerName::Fajr).unwrap();
+        assert!(fajr_countdown.time_until_adhan_secs == 0);
+
+        // Dhuhr should have 2 hours remaining
+        let dhuhr_countdown = countdowns.iter().find(|c| c.prayer_name == PrayerName::Dhuhr).unwrap();
+        assert_eq!(dhuhr_countdown.time_until_adhan_secs, 7200);
+        assert!(countdowns.iter().all(|c| c.time_until_shuruq_secs.is_none()));
+    }
+
+    #[test]
+    fn test_fajr_counts_down_to_shuruq() {
+        let engine = PrayerEngine::with_defaults();
+        let mut schedule = create_test_schedule();
+        let shuruq = schedule.fajr.adhan + Duration::minutes(90);
+        schedule.shuruq = Some(shuruq);
+
+        let countdowns = engine.get_all_countdowns(&schedule, shuruq - Duration::minutes(20));
+        let fajr = countdowns.iter().find(|c| c.prayer_name == PrayerName::Fajr).unwrap();
+        assert_eq!(fajr.shuruq_time, Some(shuruq));
+        assert_eq!(fajr.time_until_shuruq_secs, Some(1200));
+        let dhuhr = countdowns.iter().find(|c| c.prayer_name == PrayerName::Dhuhr).unwrap();
+        assert_eq!(dhuhr.shuruq_time, None);
+
+        let after = engine.get_all_countdowns(&schedule, shuruq + Duration::minutes(5));
+        assert_eq!(after[0].time_until_shuruq_secs, Some(0));
+    }
+
+    #[test]
+    fn test_active_prayer_detection() {
+        let engine = PrayerEngine::with_defaults();
+        let schedule = create_test_schedule();
+        
+        // Between adhan and iqama of Dhuhr (active)
+        let now = schedule.dhuhr.adhan + Duration::minutes(5);
+        let countdowns = engine.get_all_countdowns(&schedule, now);
+        
+        let dhuhr_countdown = countdowns.iter().find(|c| c.prayer_name == PrayerName::Dhuhr).unwrap();
+        assert!(dhuhr_countdown.is_active);
+        assert_eq!(dhuhr_countdown.time_until_adhan_secs, 0);
+
+        // After iqama (not active anymore)
+        let now = schedule.dhuhr.iqama.unwrap() + Duration::minutes(5);
+        let countdowns = engine.get_all_countdowns(&schedule, now);
+        
+        let dhuhr_countdown = countdowns.iter().find(|c| c.prayer_name == PrayerName::Dhuhr).unwrap();
+        assert!(!dhuhr_countdown.is_active);
+    }
+
+    #[test]
+    fn test_rakah_progress_percentage() {
+        let engine = PrayerEngine::with_defaults();
+        let schedule = create_test_schedule();
+        let prayer = &schedule.dhuhr; // 4 rakahs
+
+        // Test progress at various points
+        let test_cases = vec![
+            (0, 0.0),                       // Start
+            (144, 0.25),                    // After 1st rakah (2.4 min)
+            (288, 0.5),                     // After 2nd rakah (4.8 min)
+            (432, 0.75),                    // After 3rd rakah (7.2 min)
+            (576, 1.0),                     // After 4th rakah (9.6 min)
+        ];
+
+        for (seconds, expected_progress) in test_cases {
+            let now = prayer.iqama.unwrap() + Duration::seconds(seconds);
+            let estimate = engine.estimate_rakah(prayer, now);
+
+            let tolerance = 0.01; // 1% tolerance
+            assert!(
+                (estimate.progress - expected_progress).abs() < tolerance,
+                "Progress should be approximately {} at {} seconds, got {}",
+                expected_progress, seconds, estimate.progress
+            );
+        }
+    }
+
+    #[test]
+    fn test_fasting_status() {
+        let engine = PrayerEngine::with_defaults();
+        let mut schedule = create_test_schedule();
+
+        let imsak = schedule.fajr.adhan - Duration::minutes(10);
+        assert_eq!(engine.imsak(&schedule), imsak);
+
+        let midday = schedule.dhuhr.adhan;
+        let status = engine.fasting_status(&schedule, true, midday);
+        assert!(status.is_fasting);
+        assert_eq!(status.seconds_since_suhoor, Some((midday - imsak).num_seconds()));
+        assert_eq!(status.seconds_to_iftar, Some((schedule.maghrib.adhan - midday).num_seconds()));
+
+        let suhoor = imsak - Duration::minutes(30);
+        let status = engine.fasting_status(&schedule, true, suhoor);
+        assert!(!status.is_fasting);
+        assert_eq!(status.seconds_to_imsak, Some(1800));
+
+        assert!(!engine.fasting_status(&schedule, false, midday).is_fasting);
+
+        // Provider-supplied imsak wins over the default offset
+        schedule.imsak = Some(schedule.fajr.adhan - Duration::minutes(15));
+        assert_eq!(engine.imsak(&schedule), schedule.fajr.adhan - Duration::minutes(15));
+    }
+}
@@ -0,0 +1,132 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+/// Iqama this close keeps 1-second ticks even when the window is hidden
+pub const NEAR_IQAMA_SECS: i64 = 10 * 60;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum RefreshMode {
+    /// 1-second ticks, network refreshes run on schedule
+    Realtime,
+    /// Slower ticks, network refreshes deferred
+    PowerSaving,
+}
+
+/// What the scheduler knows about the app's surroundings
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export, optional_fields)]
+pub struct RefreshContext {
+    /// `None` when the power source is unknown (treated as mains)
+    pub on_battery: Option<bool>,
+    pub window_visible: bool,
+    #[ts(as = "Option<f64>")]
+    pub seconds_to_iqama: Option<i64>,
+}
+
+/// How often the ticker runs and whether network work should wait
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct RefreshPolicy {
+    pub mode: RefreshMode,
+    #[ts(type = "number")]
+    pub tick_interval_secs: u64,
+    pub defer_network: bool,
+}
+
+impl RefreshPolicy {
+    /// Realtime while the countdown is visible or iqama is near; otherwise slow down,
+    /// more so on battery
+    pub fn for_context(context: &RefreshContext) -> Self {
+        let on_battery = context.on_battery.unwrap_or(false);
+        let near_iqama = context
+            .seconds_to_iqama
+            .is_some_and(|secs| (0..=NEAR_IQAMA_SECS).contains(&secs));
+
+        if near_iqama || (context.window_visible && !on_battery) {
+            return Self {
+                mode: RefreshMode::Realtime,
+                tick_interval_secs: 1,
+                defer_network: false,
+            };
+        }
+
+        let tick_interval_secs = match (context.window_visible, on_battery) {
+            // Visible on battery: the countdown still has to move every second
+            (true, _) => 1,
+            (false, true) => 30,
+            (false, false) => 10,
+        };
+
+        Self {
+            mode: RefreshMode::PowerSaving,
+            tick_interval_secs,
+            defer_network: true,
+        }
+    }
+
+    pub fn tick_interval(&self) -> Duration {
+        Duration::from_secs(self.tick_interval_secs)
+    }
+}
+
+/// Whether the machine runs on battery, from the OS power supply info
+/// `None` when it can't be told (desktops, unsupported platforms)
+pub fn system_on_battery() -> Option<bool> {
+    #[cfg(target_os = "linux")]
+    {
+        let supplies = std::fs::read_dir("/sys/class/power_supply").ok()?;
+        let mut mains_online = None;
+
+        for supply in supplies.flatten() {
+            let path = supply.path();
+            let kind = std::fs::read_to_string(path.join("type")).unwrap_or_default();
+            if kind.trim() != "Mains" {
+                continue;
+            }
+            let online = std::fs::read_to_string(path.join("online")).unwrap_or_default();
+            mains_online = Some(mains_online.unwrap_or(false) || online.trim() == "1");
+        }
+
+        mains_online.map(|online| !online)
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_refresh_policy_modes() {
+        let hidden_on_battery = RefreshContext {
+            on_battery: Some(true),
+            window_visible: false,
+            seconds_to_iqama: Some(3600),
+        };
+        let policy = RefreshPolicy::for_context(&hidden_on_battery);
+        assert_eq!(policy.mode, RefreshMode::PowerSaving);
+        assert_eq!(policy.tick_interval_secs, 30);
+        assert!(policy.defer_network);
+
+        let near_iqama = RefreshContext {
+            seconds_to_iqama: Some(300),
+            ..hidden_on_battery
+        };
+        assert_eq!(RefreshPolicy::for_context(&near_iqama).mode, RefreshMode::Realtime);
+
+        let visible = RefreshContext {
+            on_battery: None,
+            window_visible: true,
+            seconds_to_iqama: None,
+        };
+        assert_eq!(RefreshPolicy::for_context(&visible).tick_interval_secs, 1);
+    }
+}
@@ -0,0 +1,86 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::services::PrayerNotification;
+
+/// Minutes a reminder is put off when snoozed without a duration
+pub const DEFAULT_SNOOZE_MINUTES: i64 = 5;
+
+/// Delivers each prayer reminder once and brings a snoozed one back later
+#[derive(Debug, Default)]
+pub struct ReminderTracker {
+    /// Reminders shown, by id, with their scheduled time
+    delivered: HashMap<String, DateTime<Utc>>,
+    last: Option<PrayerNotification>,
+    snoozed: Option<(PrayerNotification, DateTime<Utc>)>,
+}
+
+impl ReminderTracker {
+    /// Reminders to show now: due ones not shown yet, and a snoozed one whose time came
+    pub fn take_due(&mut self, due: Vec<PrayerNotification>, now: DateTime<Utc>) -> Vec<PrayerNotification> {
+        // Reminders only repeat within a day; forget older ones
+        self.delivered.retain(|_, at| now - *at < Duration::days(1));
+
+        let mut fresh: Vec<PrayerNotification> = due
+            .into_iter()
+            .filter(|n| self.delivered.insert(n.id.clone(), n.scheduled_time).is_none())
+            .collect();
+        if self.snoozed.as_ref().is_some_and(|(_, until)| now >= *until) {
+            fresh.extend(self.snoozed.take().map(|(notification, _)| notification));
+        }
+
+        if let Some(last) = fresh.last() {
+            self.last = Some(last.clone());
+        }
+        fresh
+    }
+
+    /// Put off the last reminder shown; returns when it comes back, `None` when there is none
+    pub fn snooze(&mut self, minutes: i64, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let notification = self.last.take().or_else(|| self.snoozed.take().map(|(n, _)| n))?;
+        let until = now + Duration::minutes(minutes);
+        self.snoozed = Some((notification, until));
+        Some(until)
+    }
+
+    pub fn snoozed_until(&self) -> Option<DateTime<Utc>> {
+        self.snoozed.as_ref().map(|(_, until)| *until)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::PrayerName;
+    use crate::services::{DisplayFormatter, NotificationType};
+
+    #[test]
+    fn test_deliver_once_and_snooze() {
+        let now = Utc::now();
+        let formatter = DisplayFormatter::from_settings(None, None);
+        let reminder = PrayerNotification::localized(
+            &formatter,
+            PrayerName::Isha,
+            NotificationType::IqamaReminder,
+            now,
+            10,
+        );
+
+        let mut tracker = ReminderTracker::default();
+        assert_eq!(tracker.take_due(vec![reminder.clone()], now).len(), 1);
+        assert!(tracker.take_due(vec![reminder.clone()], now + Duration::seconds(1)).is_empty());
+
+        let until = tracker.snooze(DEFAULT_SNOOZE_MINUTES, now).unwrap();
+        assert_eq!(tracker.snoozed_until(), Some(until));
+        assert!(tracker.take_due(Vec::new(), now + Duration::minutes(4)).is_empty());
+        let back = tracker.take_due(Vec::new(), now + Duration::minutes(5));
+        assert_eq!(back.len(), 1);
+        assert_eq!(back[0].id, reminder.id);
+
+        // Snoozing again works on the reminder that just came back
+        assert!(tracker.snooze(1, now + Duration::minutes(5)).is_some());
+        let mut empty = ReminderTracker::default();
+        assert_eq!(empty.snooze(5, now), None);
+    }
+}
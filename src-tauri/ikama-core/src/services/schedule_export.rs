@@ -0,0 +1,410 @@
+use std::collections::HashSet;
+
+use chrono::{DateTime, Local, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use crate::models::{Prayer, PrayerTimes};
+
+/// File formats a timetable can be exported to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum ScheduleExportFormat {
+    Ics,
+    Pdf,
+}
+
+impl ScheduleExportFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ScheduleExportFormat::Ics => "ics",
+            ScheduleExportFormat::Pdf => "pdf",
+        }
+    }
+}
+
+/// Render a timetable in the given format
+pub fn export_schedule(format: ScheduleExportFormat, title: &str, days: &[PrayerTimes], now: DateTime<Utc>) -> Vec<u8> {
+    match format {
+        ScheduleExportFormat::Ics => schedule_to_ics(title, days, now).into_bytes(),
+        ScheduleExportFormat::Pdf => schedule_to_pdf(title, days),
+    }
+}
+
+fn day_prayers(times: &PrayerTimes) -> Vec<&Prayer> {
+    let mut prayers = times.all_prayers();
+    prayers.extend(times.jumuah.as_ref());
+    prayers
+}
+
+fn local_hh_mm(time: DateTime<Utc>) -> String {
+    time.with_timezone(&Local).format("%H:%M").to_string()
+}
+
+/// Iqama as the mosque publishes it: "+15" minutes after the adhan, or a clock time
+fn iqama_text(prayer: &Prayer) -> Option<String> {
+    match prayer.iqama_offset_minutes {
+        Some(minutes) => Some(format!("+{}", minutes)),
+        None => prayer.iqama.map(local_hh_mm),
+    }
+}
+
+// iCalendar (RFC 5545)
+
+fn ics_time(time: DateTime<Utc>) -> String {
+    time.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+fn ics_escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+/// Fold content lines longer than 75 octets, as calendar clients expect
+fn ics_line(out: &mut String, line: &str) {
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > 75 {
+            out.push_str("\r\n ");
+            width = 1;
+        }
+        out.push(c);
+        width += c.len_utf8();
+    }
+    out.push_str("\r\n");
+}
+
+/// One event per prayer, starting at the iqama (the adhan when there is none)
+pub fn schedule_to_ics(title: &str, days: &[PrayerTimes], now: DateTime<Utc>) -> String {
+    let mut out = String::new();
+    ics_line(&mut out, "BEGIN:VCALENDAR");
+    ics_line(&mut out, "VERSION:2.0");
+    ics_line(&mut out, "PRODID:-//Iqamah//Prayer Times//EN");
+    ics_line(&mut out, &format!("X-WR-CALNAME:{}", ics_escape(title)));
+
+    for times in days {
+        let mosque = times.mosque_id.as_deref().unwrap_or("mosque");
+        let date = times.date.with_timezone(&Local).date_naive();
+
+        for prayer in day_prayers(times) {
+            let start = prayer.iqama.unwrap_or(prayer.adhan);
+            let description = match (prayer.iqama, prayer.iqama_offset_minutes) {
+                (Some(iqama), Some(minutes)) => format!(
+                    "Adhan {}, iqama +{} min ({})",
+                    local_hh_mm(prayer.adhan),
+                    minutes,
+                    local_hh_mm(iqama)
+                ),
+                (Some(iqama), None) => format!("Adhan {}, iqama {}", local_hh_mm(prayer.adhan), local_hh_mm(iqama)),
+                (None, _) => format!("Adhan {}", local_hh_mm(prayer.adhan)),
+            };
+
+            ics_line(&mut out, "BEGIN:VEVENT");
+            ics_line(&mut out, &format!("UID:{}-{}-{}@iqamah", mosque, date.format("%Y%m%d"), prayer.name));
+            ics_line(&mut out, &format!("DTSTAMP:{}", ics_time(now)));
+            ics_line(&mut out, &format!("DTSTART:{}", ics_time(start)));
+            ics_line(&mut out, "DURATION:PT15M");
+            ics_line(&mut out, &format!("SUMMARY:{}", prayer.name));
+            ics_line(&mut out, &format!("DESCRIPTION:{}", ics_escape(&description)));
+            if let Some(name) = &times.mosque_name {
+                ics_line(&mut out, &format!("LOCATION:{}", ics_escape(name)));
+            }
+            ics_line(&mut out, "END:VEVENT");
+        }
+    }
+
+    ics_line(&mut out, "END:VCALENDAR");
+    out
+}
+
+/// Whether two calendars hold the same events, ignoring when each was generated
+pub fn same_ics_events(a: &str, b: &str) -> bool {
+    let events = |ics: &str| ics.lines().filter(|l| !l.starts_with("DTSTAMP:")).map(str::to_owned).collect::<Vec<_>>();
+    events(a) == events(b)
+}
+
+/// Add the upcoming events of `previous` that `ics` no longer has, as cancelled, so importing
+/// it removes them from calendars; events that already started just drop out
+pub fn cancel_dropped_events(previous: &str, ics: &str, now: DateTime<Utc>) -> String {
+    let kept: HashSet<String> = ics_events(ics)
+        .iter()
+        .filter_map(|event| ics_field(event, "UID:").map(str::to_owned))
+        .collect();
+
+    let mut cancelled = String::new();
+    for event in ics_events(previous) {
+        if ics_field(&event, "UID:").is_none_or(|uid| kept.contains(uid)) {
+            continue;
+        }
+        let upcoming = ics_field(&event, "DTSTART:")
+            .and_then(|start| NaiveDateTime::parse_from_str(start, "%Y%m%dT%H%M%SZ").ok())
+            .is_some_and(|start| start.and_utc() > now);
+        if !upcoming {
+            continue;
+        }
+
+        ics_line(&mut cancelled, "BEGIN:VEVENT");
+        for line in event
+            .iter()
+            .filter(|l| !["DTSTAMP:", "SEQUENCE:", "STATUS:"].iter().any(|p| l.starts_with(p)))
+        {
+            ics_line(&mut cancelled, line);
+        }
+        ics_line(&mut cancelled, &format!("DTSTAMP:{}", ics_time(now)));
+        ics_line(&mut cancelled, "SEQUENCE:1");
+        ics_line(&mut cancelled, "STATUS:CANCELLED");
+        ics_line(&mut cancelled, "END:VEVENT");
+    }
+
+    match ics.strip_suffix("END:VCALENDAR\r\n") {
+        Some(head) if !cancelled.is_empty() => format!("{}{}END:VCALENDAR\r\n", head, cancelled),
+        _ => ics.to_string(),
+    }
+}
+
+/// Unfolded content lines of each event, without BEGIN and END
+fn ics_events(ics: &str) -> Vec<Vec<String>> {
+    let mut lines: Vec<String> = Vec::new();
+    for line in ics.split("\r\n") {
+        match (line.strip_prefix(' '), lines.last_mut()) {
+            (Some(rest), Some(last)) => last.push_str(rest),
+            _ => lines.push(line.to_string()),
+        }
+    }
+
+    let mut events = Vec::new();
+    let mut current: Option<Vec<String>> = None;
+    for line in lines {
+        match line.as_str() {
+            "BEGIN:VEVENT" => current = Some(Vec::new()),
+            "END:VEVENT" => events.extend(current.take()),
+            _ => {
+                if let Some(event) = current.as_mut() {
+                    event.push(line);
+                }
+            }
+        }
+    }
+    events
+}
+
+fn ics_field<'a>(event: &'a [String], name: &str) -> Option<&'a str> {
+    event.iter().find_map(|line| line.strip_prefix(name))
+}
+
+// PDF
+
+/// Table rows per A4 page
+const PDF_ROWS_PER_PAGE: usize = 32;
+
+/// Text for a PDF string in WinAnsi encoding; characters it can't show become '?'
+fn pdf_text(text: &str) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '(' | ')' | '\\' => bytes.extend([b'\\', c as u8]),
+            ' '..='~' | '\u{a0}'..='\u{ff}' => bytes.push(c as u32 as u8),
+            _ => bytes.push(b'?'),
+        }
+    }
+    bytes
+}
+
+fn pdf_cell(prayer: Option<&Prayer>) -> String {
+    match prayer {
+        Some(prayer) => format!("{} {:<5}", local_hh_mm(prayer.adhan), iqama_text(prayer).unwrap_or_default()),
+        None => " ".repeat(11),
+    }
+}
+
+fn pdf_rows(days: &[PrayerTimes]) -> Vec<String> {
+    days.iter()
+        .map(|t| {
+            let cells: Vec<String> = [
+                Some(&t.fajr),
+                Some(&t.dhuhr),
+                Some(&t.asr),
+                Some(&t.maghrib),
+                Some(&t.isha),
+                t.jumuah.as_ref(),
+            ]
+            .into_iter()
+            .map(pdf_cell)
+            .collect();
+            format!("{}  {}", t.date.with_timezone(&Local).format("%a %d %b %Y"), cells.join("   "))
+        })
+        .collect()
+}
+
+/// A printable A4 timetable, adhan and iqama per prayer, in a monospaced table
+pub fn schedule_to_pdf(title: &str, days: &[PrayerTimes]) -> Vec<u8> {
+    let header = format!(
+        "{:<17}{}",
+        "Date",
+        ["Fajr", "Dhuhr", "Asr", "Maghrib", "Isha", "Jumuah"]
+            .iter()
+            .map(|p| format!("{:<14}", p))
+            .collect::<String>()
+    );
+    let rows = pdf_rows(days);
+    let pages: Vec<&[String]> = if rows.is_empty() {
+        vec![&[]]
+    } else {
+        rows.chunks(PDF_ROWS_PER_PAGE).collect()
+    };
+
+    // Objects: 1 catalog, 2 page tree, 3 font, then a page and its content stream per page
+    let mut objects: Vec<Vec<u8>> = Vec::new();
+    let page_ids: Vec<usize> = (0..pages.len()).map(|i| 4 + 2 * i).collect();
+    objects.push(b"<< /Type /Catalog /Pages 2 0 R >>".to_vec());
+    objects.push(
+        format!(
+            "<< /Type /Pages /Kids [{}] /Count {} >>",
+            page_ids.iter().map(|id| format!("{} 0 R", id)).collect::<Vec<_>>().join(" "),
+            pages.len()
+        )
+        .into_bytes(),
+    );
+    objects.push(b"<< /Type /Font /Subtype /Type1 /BaseFont /Courier /Encoding /WinAnsiEncoding >>".to_vec());
+
+    for (i, page) in pages.iter().enumerate() {
+        let mut content = Vec::new();
+        content.extend_from_slice(b"BT /F1 14 Tf 40 800 Td (");
+        content.extend(pdf_text(title));
+        content.extend_from_slice(b") Tj ET\nBT /F1 8 Tf 40 770 Td 11 TL (");
+        content.extend(pdf_text(&header));
+        content.extend_from_slice(b") Tj T*\n");
+        for row in page.iter() {
+            content.push(b'(');
+            content.extend(pdf_text(row));
+            content.extend_from_slice(b") Tj T*\n");
+        }
+        content.extend_from_slice(format!("ET\nBT /F1 8 Tf 40 30 Td (Page {} of {}) Tj ET", i + 1, pages.len()).as_bytes());
+
+        objects.push(
+            format!(
+                "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 595 842] /Resources << /Font << /F1 3 0 R >> >> /Contents {} 0 R >>",
+                page_ids[i] + 1
+            )
+            .into_bytes(),
+        );
+        let mut stream = format!("<< /Length {} >>\nstream\n", content.len()).into_bytes();
+        stream.extend(content);
+        stream.extend_from_slice(b"\nendstream");
+        objects.push(stream);
+    }
+
+    let mut pdf = b"%PDF-1.4\n".to_vec();
+    let mut offsets = Vec::with_capacity(objects.len());
+    for (i, object) in objects.iter().enumerate() {
+        offsets.push(pdf.len());
+        pdf.extend(format!("{} 0 obj\n", i + 1).into_bytes());
+        pdf.extend(object);
+        pdf.extend_from_slice(b"\nendobj\n");
+    }
+
+    let xref = pdf.len();
+    pdf.extend(format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1).into_bytes());
+    for offset in offsets {
+        pdf.extend(format!("{:010} 00000 n \n", offset).into_bytes());
+    }
+    pdf.extend(
+        format!(
+            "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n",
+            objects.len() + 1,
+            xref
+        )
+        .into_bytes(),
+    );
+    pdf
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::MockProvider;
+    use chrono::{Duration, NaiveDate};
+
+    fn month() -> Vec<PrayerTimes> {
+        let now = Utc::now();
+        let start = NaiveDate::from_ymd_opt(2026, 3, 1).unwrap();
+        (0..31)
+            .map(|d| MockProvider::new().schedule("mock-export", start + Duration::days(d), now))
+            .collect()
+    }
+
+    #[test]
+    fn test_ics_has_one_event_per_prayer() {
+        let days = month();
+        let ics = schedule_to_ics("Mosquée de Paris; horaires", &days, Utc::now());
+        let events = ics.matches("BEGIN:VEVENT").count();
+        let fridays = days.iter().filter(|d| d.jumuah.is_some()).count();
+        assert_eq!(events, 31 * 5 + fridays);
+        assert!(ics.contains("X-WR-CALNAME:Mosquée de Paris\\; horaires\r\n"));
+        assert!(ics.lines().all(|l| l.len() <= 76));
+        // Mock mosques publish iqamas as offsets, shown as such
+        assert!(ics.contains("\\, iqama +"));
+    }
+
+    #[test]
+    fn test_same_ics_events_ignores_stamp() {
+        let days = month();
+        let now = Utc::now();
+        let ics = schedule_to_ics("Week", &days[..7], now);
+        assert!(same_ics_events(&ics, &schedule_to_ics("Week", &days[..7], now + Duration::hours(1))));
+        assert!(!same_ics_events(&ics, &schedule_to_ics("Week", &days[1..8], now)));
+    }
+
+    #[test]
+    fn test_dropped_upcoming_events_are_cancelled() {
+        let days = month();
+        let now = days[1].fajr.adhan;
+        let week = schedule_to_ics("Week", &days[..7], now);
+        let later = [&days[1..2], &days[3..7]].concat();
+        let next = cancel_dropped_events(&week, &schedule_to_ics("Week", &later, now), now);
+
+        // Day three's events come back cancelled, the first day's have passed and just drop out
+        let cancelled = ics_events(&next)
+            .into_iter()
+            .filter(|event| ics_field(event, "STATUS:") == Some("CANCELLED"))
+            .collect::<Vec<_>>();
+        assert_eq!(cancelled.len(), day_prayers(&days[2]).len());
+        assert!(cancelled.iter().all(|event| ics_field(event, "UID:").unwrap().contains("20260303")));
+        assert!(next.ends_with("STATUS:CANCELLED\r\nEND:VEVENT\r\nEND:VCALENDAR\r\n"));
+
+        // Cancelling again is stable, so an unchanged schedule isn't re-imported
+        let again = cancel_dropped_events(&next, &schedule_to_ics("Week", &later, now), now);
+        assert!(same_ics_events(&next, &again));
+    }
+
+    #[test]
+    fn test_pdf_pages_and_xref() {
+        let pdf = schedule_to_pdf("Mosquée (Paris)", &month());
+        let text = String::from_utf8_lossy(&pdf);
+        assert!(text.starts_with("%PDF-1.4"));
+        assert!(text.contains("/Count 1"));
+        assert!(text.contains("Mosqu\u{fffd}e \\(Paris\\)"));
+
+        // The xref table points at the objects it lists
+        let start: usize = text.rsplit("startxref\n").next().unwrap().lines().next().unwrap().parse().unwrap();
+        assert!(pdf[start..].starts_with(b"xref"));
+        let xref = std::str::from_utf8(&pdf[start..]).unwrap();
+        let first_offset: usize = xref.lines().nth(3).unwrap()[..10].parse().unwrap();
+        assert!(pdf[first_offset..].starts_with(b"1 0 obj"));
+    }
+
+    #[test]
+    fn test_pdf_cell_keeps_published_iqama() {
+        let mut fajr = month()[0].fajr.clone();
+        fajr.iqama_offset_minutes = Some(15);
+        assert_eq!(&pdf_cell(Some(&fajr))[5..], " +15  ");
+
+        fajr.iqama_offset_minutes = None;
+        let cell = pdf_cell(Some(&fajr));
+        assert_eq!(cell.len(), 11);
+        assert_eq!(&cell[6..], local_hh_mm(fajr.iqama.unwrap()));
+    }
+}
@@ -0,0 +1,104 @@
+use chrono::{Local, NaiveDate, NaiveDateTime};
+
+use crate::models::{PrayerName, PrayerTimes, UpcomingScheduleChange};
+
+/// Smallest day-to-day iqama jump reported; smaller moves are the adhan's normal daily drift
+pub const MIN_FORECAST_SHIFT_MINUTES: i64 = 5;
+
+/// Local iqama times a mosque publishes for one day
+/// Times keep their own date, so an Isha after midnight falls on the next day
+#[derive(Debug, Clone, PartialEq)]
+pub struct DayIqamas {
+    pub date: NaiveDate,
+    pub iqamas: Vec<(PrayerName, NaiveDateTime)>,
+}
+
+impl DayIqamas {
+    /// Iqamas of a cached schedule in local wall-clock time, at the offset of each instant
+    pub fn from_schedule(schedule: &PrayerTimes) -> Self {
+        Self {
+            date: schedule.date.date_naive(),
+            iqamas: schedule
+                .all_prayers()
+                .into_iter()
+                .filter_map(|p| Some((p.name, p.iqama?.with_timezone(&Local).naive_local())))
+                .collect(),
+        }
+    }
+
+    /// Minutes from the day's local midnight to a prayer's iqama, past 1440 after midnight
+    fn minutes_into_day(&self, prayer: PrayerName) -> Option<i64> {
+        let (_, iqama) = self.iqamas.iter().find(|(p, _)| *p == prayer)?;
+        Some((*iqama - self.date.and_hms_opt(0, 0, 0)?).num_minutes())
+    }
+}
+
+/// Find the days where an iqama jumps compared to the day before, in date order
+/// `days` must be consecutive; gaps reset the comparison
+pub fn forecast_iqama_changes(days: &[DayIqamas], min_shift_minutes: i64) -> Vec<UpcomingScheduleChange> {
+    days.windows(2)
+        .filter(|pair| pair[1].date.pred_opt() == Some(pair[0].date))
+        .flat_map(|pair| {
+            let (before, after) = (&pair[0], &pair[1]);
+
+            after.iqamas.iter().filter_map(move |&(prayer, new_iqama)| {
+                let (_, previous_iqama) = *before.iqamas.iter().find(|(p, _)| *p == prayer)?;
+                // Wall-clock minutes, so a DST switch alone moves nothing
+                let shift_minutes = after.minutes_into_day(prayer)? - before.minutes_into_day(prayer)?;
+
+                (shift_minutes.abs() >= min_shift_minutes).then_some(UpcomingScheduleChange {
+                    prayer,
+                    date: after.date,
+                    previous_iqama: previous_iqama.time(),
+                    new_iqama: new_iqama.time(),
+                    shift_minutes,
+                })
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveTime;
+
+    fn day(d: u32, isha: &str, fajr: &str) -> DayIqamas {
+        let date = NaiveDate::from_ymd_opt(2026, 3, d).unwrap();
+        let t = |s| date.and_time(NaiveTime::parse_from_str(s, "%H:%M").unwrap());
+        DayIqamas {
+            date,
+            iqamas: vec![(PrayerName::Fajr, t(fajr)), (PrayerName::Isha, t(isha))],
+        }
+    }
+
+    #[test]
+    fn test_forecast_reports_jumps_not_drift() {
+        let days = vec![
+            day(1, "20:00", "05:40"),
+            day(2, "20:00", "05:38"),
+            day(3, "20:15", "05:36"),
+            // Gap: day 5 isn't compared with day 3
+            day(5, "21:00", "05:30"),
+        ];
+
+        let changes = forecast_iqama_changes(&days, MIN_FORECAST_SHIFT_MINUTES);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].prayer, PrayerName::Isha);
+        assert_eq!(changes[0].date, NaiveDate::from_ymd_opt(2026, 3, 3).unwrap());
+        assert_eq!(changes[0].shift_minutes, 15);
+    }
+
+    #[test]
+    fn test_forecast_across_midnight() {
+        let late = day(2, "23:50", "05:00");
+        // Isha moved past midnight, into the 4th
+        let mut after_midnight = day(3, "23:50", "05:00");
+        after_midnight.iqamas[1].1 = NaiveDate::from_ymd_opt(2026, 3, 4).unwrap().and_hms_opt(0, 5, 0).unwrap();
+
+        let changes = forecast_iqama_changes(&[late, after_midnight], MIN_FORECAST_SHIFT_MINUTES);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].shift_minutes, 15);
+        assert_eq!(changes[0].new_iqama, NaiveTime::from_hms_opt(0, 5, 0).unwrap());
+    }
+}
@@ -0,0 +1,154 @@
+use crate::models::{GeoLocation, Mosque, MosqueSort};
+use crate::services::SearchQuery;
+
+const EXACT_NAME_SCORE: f64 = 100.0;
+const NAME_PREFIX_SCORE: f64 = 75.0;
+const NAME_CONTAINS_SCORE: f64 = 50.0;
+/// Name matched only after transliteration or typo tolerance
+const NAME_FUZZY_SCORE: f64 = 45.0;
+const CITY_SCORE: f64 = 40.0;
+const ADDRESS_SCORE: f64 = 20.0;
+const OTHER_FUZZY_SCORE: f64 = 10.0;
+const FAVORITE_BOOST: f64 = 15.0;
+/// Boost of a mosque at the user's location, halved at `PROXIMITY_HALF_KM`
+const PROXIMITY_BOOST: f64 = 25.0;
+const PROXIMITY_HALF_KM: f64 = 5.0;
+
+/// Relevance of a mosque for a query: exact name > name prefix > name > city > address,
+/// boosted for favorites and, when `location` is known, for nearby mosques
+pub fn score_mosque(mosque: &Mosque, query: &str, location: Option<&GeoLocation>) -> f64 {
+    score(mosque, query, &SearchQuery::new(query), location)
+}
+
+/// Mosques sorted by relevance, best first; equal scores keep their order
+pub fn rank_mosques(mosques: Vec<Mosque>, query: &str, location: Option<&GeoLocation>) -> Vec<Mosque> {
+    let search = SearchQuery::new(query);
+    let mut scored: Vec<(f64, Mosque)> = mosques
+        .into_iter()
+        .map(|m| (score(&m, query, &search, location), m))
+        .collect();
+    scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+    scored.into_iter().map(|(_, m)| m).collect()
+}
+
+/// Mosques in the requested order, ties broken by relevance
+/// Distance order needs `location`; without it results stay in relevance order
+pub fn sort_mosques(mosques: Vec<Mosque>, query: &str, location: Option<&GeoLocation>, sort: MosqueSort) -> Vec<Mosque> {
+    let mut mosques = rank_mosques(mosques, query, location);
+
+    match (sort, location) {
+        (MosqueSort::Distance, Some(location)) => {
+            let distance = |m: &Mosque| match (m.latitude, m.longitude) {
+                (Some(latitude), Some(longitude)) => location.distance_to(&GeoLocation::new(latitude, longitude)),
+                _ => f64::INFINITY,
+            };
+            mosques.sort_by(|a, b| distance(a).total_cmp(&distance(b)));
+        }
+        (MosqueSort::Name, _) => mosques.sort_by_cached_key(|m| m.name.trim().to_lowercase()),
+        _ => {}
+    }
+
+    mosques
+}
+
+fn score(mosque: &Mosque, query: &str, search: &SearchQuery, location: Option<&GeoLocation>) -> f64 {
+    let query = query.trim().to_lowercase();
+    let name = mosque.name.trim().to_lowercase();
+    let city = mosque.city.as_deref().unwrap_or_default();
+    let address = mosque.address.as_deref().unwrap_or_default();
+
+    let mut score = if query.is_empty() {
+        0.0
+    } else if name == query {
+        EXACT_NAME_SCORE
+    } else if name.starts_with(&query) {
+        NAME_PREFIX_SCORE
+    } else if name.contains(&query) {
+        NAME_CONTAINS_SCORE
+    } else if search.matches(&[&mosque.name]) {
+        NAME_FUZZY_SCORE
+    } else if city.to_lowercase().contains(&query) {
+        CITY_SCORE
+    } else if address.to_lowercase().contains(&query) {
+        ADDRESS_SCORE
+    } else if search.matches(&[city, address]) {
+        OTHER_FUZZY_SCORE
+    } else {
+        0.0
+    };
+
+    if mosque.is_favorite {
+        score += FAVORITE_BOOST;
+    }
+    if let (Some(location), Some(latitude), Some(longitude)) = (location, mosque.latitude, mosque.longitude) {
+        let distance = location.distance_to(&GeoLocation::new(latitude, longitude));
+        score += PROXIMITY_BOOST / (1.0 + distance / PROXIMITY_HALF_KM);
+    }
+
+    score
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mosque(id: &str, name: &str, city: &str) -> Mosque {
+        let mut mosque = Mosque::new(id.to_string(), name.to_string());
+        mosque.city = Some(city.to_string());
+        mosque
+    }
+
+    #[test]
+    fn test_rank_by_match_quality() {
+        let mosques = vec![
+            mosque("city", "Mosquée Es-Salam", "Lyon"),
+            mosque("fuzzy", "Mosquée de Lion", "Villeurbanne"),
+            mosque("contains", "Grande Mosquée de Lyon", "Villeurbanne"),
+            mosque("prefix", "Lyon Centre", "Lyon"),
+            mosque("exact", "Lyon", "Paris"),
+        ];
+
+        let ranked = rank_mosques(mosques, "lyon", None);
+
+        let ids: Vec<_> = ranked.iter().map(|m| m.id.as_str()).collect();
+        assert_eq!(ids, vec!["exact", "prefix", "contains", "fuzzy", "city"]);
+    }
+
+    #[test]
+    fn test_sort_by_distance_and_name() {
+        let here = GeoLocation::new(48.85, 2.35);
+        let mut near = mosque("near", "Mosquée Omar", "Paris");
+        (near.latitude, near.longitude) = (Some(48.86), Some(2.36));
+        let mut far = mosque("far", "Mosquée Abou Bakr", "Marseille");
+        (far.latitude, far.longitude) = (Some(43.30), Some(5.37));
+        let unknown = mosque("unknown", "Mosquée Ali", "Lille");
+        let mosques = vec![unknown, far, near];
+
+        let ids = |mosques: Vec<Mosque>| mosques.into_iter().map(|m| m.id).collect::<Vec<_>>();
+        assert_eq!(
+            ids(sort_mosques(mosques.clone(), "", Some(&here), MosqueSort::Distance)),
+            vec!["near", "far", "unknown"]
+        );
+        assert_eq!(
+            ids(sort_mosques(mosques, "", None, MosqueSort::Name)),
+            vec!["far", "unknown", "near"]
+        );
+    }
+
+    #[test]
+    fn test_favorites_and_proximity_boost() {
+        let here = GeoLocation::new(48.85, 2.35);
+        let mut near = mosque("near", "Mosquée Al Fath", "Paris");
+        (near.latitude, near.longitude) = (Some(48.86), Some(2.36));
+        let mut far = mosque("far", "Mosquée Al Fath", "Marseille");
+        (far.latitude, far.longitude) = (Some(43.30), Some(5.37));
+        let mut favorite = mosque("favorite", "Mosquée Al Fath", "Lille");
+        favorite.is_favorite = true;
+
+        let ranked = rank_mosques(vec![far, favorite, near], "al fath", Some(&here));
+
+        let ids: Vec<_> = ranked.iter().map(|m| m.id.as_str()).collect();
+        assert_eq!(ids, vec!["near", "favorite", "far"]);
+    }
+}
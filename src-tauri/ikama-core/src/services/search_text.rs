@@ -0,0 +1,260 @@
+/// Words for "mosque" across the languages mosques are listed in, all searched as "masjid"
+const MOSQUE_WORDS: &[&str] = &[
+    "masjid", "masjed", "masged", "msjd", "mosque", "mosquee", "moschee", "moskee", "moske", "mosk", "mezquita",
+    "mesquita", "moschea", "meczet", "cami", "camii", "jami", "jamia", "jame",
+];
+const MOSQUE_WORD: &str = "masjid";
+
+/// Query words shorter than this only match word starts, never by sound or typo
+const MIN_FUZZY_LEN: usize = 3;
+
+/// A mosque search query, normalized once and matched against many mosques
+/// Tolerates accents, Arabic script, transliteration variants ("annour", "An-Nour", "النور") and typos
+#[derive(Debug, Clone, Default)]
+pub struct SearchQuery {
+    /// Lowercased, accent-free query for plain substring matches
+    folded: String,
+    words: Vec<Word>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Word {
+    text: String,
+    /// Consonants without the Arabic article, e.g. "nr" for "annour", "nour" and "النور"
+    skeleton: String,
+}
+
+impl Word {
+    fn new(text: String) -> Self {
+        let skeleton = skeleton(&text);
+        Self { text, skeleton }
+    }
+
+    fn matches(&self, candidate: &Word) -> bool {
+        if candidate.text.starts_with(&self.text) {
+            return true;
+        }
+        if self.text.chars().count() < MIN_FUZZY_LEN {
+            return false;
+        }
+
+        // Short outlines like "ln" (lyon) must match whole, or "lens" would match too
+        let same_sound = !self.skeleton.is_empty()
+            && (candidate.skeleton == self.skeleton
+                || (self.skeleton.len() >= MIN_FUZZY_LEN && candidate.skeleton.starts_with(&self.skeleton)));
+
+        same_sound
+            || levenshtein(&self.text, &candidate.text) <= max_typos(&self.text)
+    }
+}
+
+impl SearchQuery {
+    pub fn new(query: &str) -> Self {
+        let mut words: Vec<Word> = tokenize(query, false).into_iter().map(Word::new).collect();
+
+        // "masjid annour" should also find "Centre Islamique An-Nour"
+        if words.iter().any(|w| w.text != MOSQUE_WORD) {
+            words.retain(|w| w.text != MOSQUE_WORD);
+        }
+
+        Self {
+            folded: fold(query).trim().to_string(),
+            words,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.folded.is_empty()
+    }
+
+    /// Whether the query is a substring of a field, or each query word matches a word of the fields
+    pub fn matches(&self, fields: &[&str]) -> bool {
+        if self.is_empty() || fields.iter().any(|field| fold(field).contains(&self.folded)) {
+            return true;
+        }
+        if self.words.is_empty() {
+            return false;
+        }
+
+        let candidates: Vec<Word> = fields
+            .iter()
+            .flat_map(|field| tokenize(field, true))
+            .map(Word::new)
+            .collect();
+
+        self.words
+            .iter()
+            .all(|word| candidates.iter().any(|candidate| word.matches(candidate)))
+    }
+}
+
+/// Lowercase, with accents removed and Arabic letters transliterated to Latin
+fn fold(text: &str) -> String {
+    let mut folded = String::with_capacity(text.len());
+    for c in text.chars() {
+        match fold_char(c) {
+            Some(replacement) => folded.push_str(replacement),
+            None => folded.extend(c.to_lowercase()),
+        }
+    }
+    folded
+}
+
+fn fold_char(c: char) -> Option<&'static str> {
+    Some(match c {
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' | 'À' | 'Á' | 'Â' | 'Ã' | 'Ä' | 'Å' | 'Ā' => "a",
+        'ç' | 'ć' | 'č' | 'Ç' | 'Ć' | 'Č' => "c",
+        'è' | 'é' | 'ê' | 'ë' | 'ē' | 'È' | 'É' | 'Ê' | 'Ë' | 'Ē' => "e",
+        'ì' | 'í' | 'î' | 'ï' | 'ī' | 'ı' | 'Ì' | 'Í' | 'Î' | 'Ï' | 'Ī' | 'İ' => "i",
+        'ñ' | 'Ñ' => "n",
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' | 'Ò' | 'Ó' | 'Ô' | 'Õ' | 'Ö' | 'Ø' | 'Ō' => "o",
+        'ù' | 'ú' | 'û' | 'ü' | 'ū' | 'Ù' | 'Ú' | 'Û' | 'Ü' | 'Ū' => "u",
+        'ý' | 'ÿ' | 'Ý' => "y",
+        'ş' | 'š' | 'Ş' | 'Š' => "s",
+        'ğ' | 'Ğ' => "g",
+        'ž' | 'Ž' => "z",
+        'œ' | 'Œ' => "oe",
+        'æ' | 'Æ' => "ae",
+        'ß' => "ss",
+        // Arabic letters
+        'ا' | 'أ' | 'إ' | 'آ' | 'ٱ' | 'ى' | 'ة' => "a",
+        'ب' => "b",
+        'ت' => "t",
+        'ث' => "th",
+        'ج' => "j",
+        'ح' | 'ه' => "h",
+        'خ' => "kh",
+        'د' => "d",
+        'ذ' => "dh",
+        'ر' => "r",
+        'ز' => "z",
+        'س' | 'ص' => "s",
+        'ش' => "sh",
+        'ض' => "d",
+        'ط' => "t",
+        'ظ' => "z",
+        'غ' => "gh",
+        'ف' => "f",
+        'ق' => "q",
+        'ك' | 'ک' => "k",
+        'ل' => "l",
+        'م' => "m",
+        'ن' => "n",
+        'و' | 'ؤ' => "w",
+        'ي' | 'ئ' | 'ی' => "y",
+        // Ain, hamza, short vowel marks and tatweel are not written in Latin names
+        'ع' | 'ء' | '\u{0640}' | '\u{064B}'..='\u{0652}' => "",
+        _ => return None,
+    })
+}
+
+/// Words of a text, with words for "mosque" read as "masjid" and hyphenated words joined ("an-nour" -> "annour")
+/// Words of a searched `field` also keep their hyphenated parts and original mosque word
+fn tokenize(text: &str, field: bool) -> Vec<String> {
+    let folded: String = fold(text)
+        .chars()
+        .map(|c| match c {
+            '\'' | '’' | '`' => '-',
+            c if c.is_alphanumeric() || c == '-' => c,
+            _ => ' ',
+        })
+        .collect();
+
+    let mut words = Vec::new();
+    for word in folded.split_whitespace() {
+        let joined: String = word.chars().filter(|&c| c != '-').collect();
+        if field && word.contains('-') {
+            words.extend(word.split('-').filter(|p| !p.is_empty()).map(str::to_string));
+        }
+        if MOSQUE_WORDS.contains(&joined.as_str()) {
+            if field {
+                words.push(joined);
+            }
+            words.push(MOSQUE_WORD.to_string());
+        } else if !joined.is_empty() {
+            words.push(joined);
+        }
+    }
+
+    words
+}
+
+/// Consonant outline of a word, so spellings of one Arabic name share it
+fn skeleton(word: &str) -> String {
+    let word = word.replace("ch", "sh").replace("dj", "j").replace("ou", "u");
+    let chars: Vec<char> = word.chars().collect();
+
+    // Article: "al-", "el-", or assimilated as in "an-nour", "ash-shifa"
+    let start = match chars.as_slice() {
+        ['a' | 'e' | 'u', 'l', _, _, ..] => 2,
+        ['a' | 'e', a, b, c, d, _, ..] if a == c && b == d && *b == 'h' => 3,
+        ['a' | 'e', a, b, _, ..] if a == b && "tdrzsn".contains(*a) => 2,
+        _ => 0,
+    };
+
+    let mut skeleton = String::new();
+    for &c in &chars[start..] {
+        let c = match c {
+            'q' | 'c' => 'k',
+            c => c,
+        };
+        if "aeiouyw".contains(c) || skeleton.ends_with(c) {
+            continue;
+        }
+        skeleton.push(c);
+    }
+    skeleton
+}
+
+fn max_typos(word: &str) -> usize {
+    match word.chars().count() {
+        0..=3 => 0,
+        4..=7 => 1,
+        _ => 2,
+    }
+}
+
+/// Edit distance between two words
+pub fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transliteration_variants_match() {
+        let query = SearchQuery::new("masjid annour");
+
+        assert!(query.matches(&["Mosquée An-Nour", "Paris"]));
+        assert!(query.matches(&["مسجد النور"]));
+        assert!(query.matches(&["Centre Islamique An Nour"]));
+        assert!(!query.matches(&["Mosquée Al-Fath"]));
+
+        assert!(SearchQuery::new("النور").matches(&["Mosquée An-Nour"]));
+        assert!(SearchQuery::new("ash-shifa").matches(&["Masjid Al Shifa"]));
+    }
+
+    #[test]
+    fn test_typos_and_hyphenated_words() {
+        assert!(SearchQuery::new("grande mosqee").matches(&["Grande Mosquée de Paris"]));
+        assert!(SearchQuery::new("saint denis").matches(&["Mosquée de Saint-Denis"]));
+        assert!(SearchQuery::new("saint-denis").matches(&["Saint-Denis"]));
+        assert!(!SearchQuery::new("lyon").matches(&["Mosquée de Lille"]));
+        assert!(!SearchQuery::new("lyon").matches(&["Mosquée de Lens"]));
+        assert_eq!(levenshtein("hidaya", "hidaia"), 1);
+    }
+}
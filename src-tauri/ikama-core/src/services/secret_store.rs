@@ -0,0 +1,152 @@
+use serde_json::Value;
+
+use crate::models::{ConfigField, ConfigFieldType, ProviderConfig};
+
+/// Keychain service name all secrets are stored under
+const KEYCHAIN_SERVICE: &str = "iqamah";
+
+/// Prefix of setting values that point into the keychain instead of holding the secret
+const SECRET_REF_PREFIX: &str = "keychain:";
+
+/// Stores provider secrets (API tokens, keys) in the OS keychain
+/// SQLite only keeps a `keychain:<provider>/<field>` reference in their place
+pub struct SecretStore {
+    service: String,
+}
+
+impl SecretStore {
+    pub fn new() -> Self {
+        Self {
+            service: KEYCHAIN_SERVICE.to_string(),
+        }
+    }
+
+    pub fn set(&self, account: &str, secret: &str) -> anyhow::Result<()> {
+        keyring::Entry::new(&self.service, account)?.set_password(secret)?;
+        Ok(())
+    }
+
+    pub fn get(&self, account: &str) -> anyhow::Result<Option<String>> {
+        match keyring::Entry::new(&self.service, account)?.get_password() {
+            Ok(secret) => Ok(Some(secret)),
+            Err(keyring::Error::NoEntry) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn delete(&self, account: &str) -> anyhow::Result<()> {
+        match keyring::Entry::new(&self.service, account)?.delete_password() {
+            Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Replace the secret a sealed config field points to, e.g. a token the server rotated
+    pub fn update_field(&self, provider_id: &str, key: &str, secret: &str) -> anyhow::Result<()> {
+        self.set(&secret_account(provider_id, key), secret)
+    }
+
+    /// Move password-typed fields of a config into the keychain, replacing them with references
+    /// If the keychain is unavailable the value is kept as is, so saving never fails
+    pub fn seal(&self, mut config: ProviderConfig, schema: &[ConfigField]) -> ProviderConfig {
+        let Some(settings) = config.settings.as_object_mut() else {
+            return config;
+        };
+
+        for field in schema.iter().filter(|f| matches!(f.field_type, ConfigFieldType::Password)) {
+            let account = secret_account(&config.provider_id, &field.key);
+
+            match settings.get(&field.key).and_then(|v| v.as_str()) {
+                Some(value) if is_secret_ref(value) => {}
+                Some(value) if !value.is_empty() => match self.set(&account, value) {
+                    Ok(()) => {
+                        settings.insert(field.key.clone(), Value::String(secret_ref(&account)));
+                    }
+                    Err(e) => log::warn!("Keychain unavailable, storing {} in settings: {}", account, e),
+                },
+                _ => {
+                    if let Err(e) = self.delete(&account) {
+                        log::warn!("Failed to remove {} from keychain: {}", account, e);
+                    }
+                }
+            }
+        }
+
+        config
+    }
+
+    /// Replace keychain references in a config with the stored secrets
+    pub fn unseal(&self, mut config: ProviderConfig) -> ProviderConfig {
+        let Some(settings) = config.settings.as_object_mut() else {
+            return config;
+        };
+
+        for value in settings.values_mut() {
+            let Some(account) = value.as_str().and_then(|v| v.strip_prefix(SECRET_REF_PREFIX)) else {
+                continue;
+            };
+
+            *value = match self.get(account) {
+                Ok(Some(secret)) => Value::String(secret),
+                Ok(None) => {
+                    log::warn!("Secret {} is missing from the keychain", account);
+                    Value::Null
+                }
+                Err(e) => {
+                    log::warn!("Failed to read {} from keychain: {}", account, e);
+                    Value::Null
+                }
+            };
+        }
+
+        config
+    }
+}
+
+impl Default for SecretStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Whether a config still holds a password-typed field in plain text
+pub fn has_plaintext_secrets(config: &ProviderConfig, schema: &[ConfigField]) -> bool {
+    schema
+        .iter()
+        .filter(|f| matches!(f.field_type, ConfigFieldType::Password))
+        .filter_map(|f| config.settings.get(&f.key).and_then(|v| v.as_str()))
+        .any(|v| !v.is_empty() && !is_secret_ref(v))
+}
+
+fn secret_account(provider_id: &str, key: &str) -> String {
+    format!("{}/{}", provider_id, key)
+}
+
+fn secret_ref(account: &str) -> String {
+    format!("{}{}", SECRET_REF_PREFIX, account)
+}
+
+fn is_secret_ref(value: &str) -> bool {
+    value.starts_with(SECRET_REF_PREFIX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_has_plaintext_secrets() {
+        let schema = vec![
+            ConfigField::new("api_token", "API Token", ConfigFieldType::Password),
+            ConfigField::new("base_url", "Base URL", ConfigFieldType::Url),
+        ];
+        let config = |token: &str| ProviderConfig {
+            provider_id: "official_api".to_string(),
+            settings: serde_json::json!({"api_token": token, "base_url": "https://mawaqit.net/api"}),
+        };
+
+        assert!(has_plaintext_secrets(&config("abc123"), &schema));
+        assert!(!has_plaintext_secrets(&config("keychain:official_api/api_token"), &schema));
+        assert!(!has_plaintext_secrets(&config(""), &schema));
+    }
+}
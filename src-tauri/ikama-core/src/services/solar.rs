@@ -0,0 +1,68 @@
+use chrono::{Duration, NaiveDate};
+
+use crate::models::AsrMethod;
+
+/// Sun's declination in degrees at noon UT on a date (low-precision almanac formula)
+fn declination(date: NaiveDate) -> f64 {
+    let epoch = NaiveDate::from_ymd_opt(2000, 1, 1).expect("valid date");
+    let d = (date - epoch).num_days() as f64;
+
+    let mean_anomaly = (357.529 + 0.985_600_28 * d).to_radians();
+    let mean_longitude = 280.459 + 0.985_647_36 * d;
+    let ecliptic_longitude =
+        (mean_longitude + 1.915 * mean_anomaly.sin() + 0.020 * (2.0 * mean_anomaly).sin()).to_radians();
+    let obliquity = (23.439 - 0.000_000_36 * d).to_radians();
+
+    (obliquity.sin() * ecliptic_longitude.sin()).asin().to_degrees()
+}
+
+/// Hours after solar noon when Asr starts, or `None` where the sun never gets that low
+fn asr_hours_after_noon(date: NaiveDate, latitude: f64, method: AsrMethod) -> Option<f64> {
+    let latitude = latitude.to_radians();
+    let declination = declination(date).to_radians();
+
+    let altitude = (1.0 / (method.shadow_factor() + (latitude - declination).abs().tan())).atan();
+    let cos_hour_angle =
+        (altitude.sin() - latitude.sin() * declination.sin()) / (latitude.cos() * declination.cos());
+
+    (-1.0..=1.0)
+        .contains(&cos_hour_angle)
+        .then(|| cos_hour_angle.acos().to_degrees() / 15.0)
+}
+
+/// How much later Asr starts under `to` than under `from` at a latitude; negative when earlier
+/// Lets published times be moved to the user's convention without knowing the longitude
+pub fn asr_shift(date: NaiveDate, latitude: f64, from: AsrMethod, to: AsrMethod) -> Option<Duration> {
+    let hours = asr_hours_after_noon(date, latitude, to)? - asr_hours_after_noon(date, latitude, from)?;
+    Some(Duration::seconds((hours * 3600.0).round() as i64))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hanafi_asr_at_equator_on_equinox() {
+        let equinox = NaiveDate::from_ymd_opt(2026, 3, 20).unwrap();
+
+        // Shadow angles of 45° and 26.57° give hour angles of 45° and 63.43°
+        let shift = asr_shift(equinox, 0.0, AsrMethod::Standard, AsrMethod::Hanafi).unwrap();
+        assert!((shift.num_minutes() - 74).abs() <= 1, "{}", shift);
+
+        let back = asr_shift(equinox, 0.0, AsrMethod::Hanafi, AsrMethod::Standard).unwrap();
+        assert_eq!(back, -shift);
+        assert_eq!(asr_shift(equinox, 0.0, AsrMethod::Hanafi, AsrMethod::Hanafi), Some(Duration::zero()));
+    }
+
+    #[test]
+    fn test_hanafi_asr_shift_in_london() {
+        let winter = NaiveDate::from_ymd_opt(2026, 12, 21).unwrap();
+        let summer = NaiveDate::from_ymd_opt(2026, 6, 21).unwrap();
+
+        let winter_shift = asr_shift(winter, 51.5, AsrMethod::Standard, AsrMethod::Hanafi).unwrap();
+        let summer_shift = asr_shift(summer, 51.5, AsrMethod::Standard, AsrMethod::Hanafi).unwrap();
+
+        assert!((20..=45).contains(&winter_shift.num_minutes()), "{}", winter_shift);
+        assert!((60..=100).contains(&summer_shift.num_minutes()), "{}", summer_shift);
+    }
+}
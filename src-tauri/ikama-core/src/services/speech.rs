@@ -0,0 +1,116 @@
+use std::process::Command;
+
+use crate::models::{PrayerName, SpeechConfig};
+use crate::services::{Language, NotificationType, PrayerNotification};
+
+/// What to say for a reminder, `None` when it isn't announced
+pub fn announcement(config: &SpeechConfig, language: Language, notification: &PrayerNotification) -> Option<String> {
+    let prayer: PrayerName = notification.prayer_name.parse().ok()?;
+    if !config.speaks(prayer) {
+        return None;
+    }
+
+    let minutes = notification.minutes_before;
+    match notification.notification_type {
+        NotificationType::AdhanReminder => Some(language.adhan_announcement(prayer, minutes)),
+        NotificationType::IqamaReminder => Some(language.iqama_announcement(prayer, minutes)),
+        NotificationType::PrayerStart => Some(language.prayer_start(prayer)),
+        NotificationType::FajrEnding => Some(language.fajr_ending(minutes)),
+        NotificationType::ScheduleChange | NotificationType::GoalSummary | NotificationType::IslamicEvent => None,
+    }
+}
+
+/// The platform's text-to-speech command: `say` on macOS, System.Speech on Windows,
+/// speech-dispatcher elsewhere
+/// The text goes through an environment variable so it is never parsed as a script
+pub fn speech_command(text: &str, language: Language) -> Command {
+    let mut command;
+    if cfg!(target_os = "macos") {
+        command = Command::new("say");
+        if let Some(voice) = macos_voice(language) {
+            command.args(["-v", voice]);
+        }
+        command.arg("--").arg(text);
+    } else if cfg!(target_os = "windows") {
+        command = Command::new("powershell");
+        command.args([
+            "-NoProfile",
+            "-NonInteractive",
+            "-Command",
+            "Add-Type -AssemblyName System.Speech; \
+             $s = New-Object System.Speech.Synthesis.SpeechSynthesizer; \
+             $v = $s.GetInstalledVoices() | Where-Object { $_.VoiceInfo.Culture.TwoLetterISOLanguageName -eq $env:IQAMAH_SPEECH_LANG } | Select-Object -First 1; \
+             if ($v) { $s.SelectVoice($v.VoiceInfo.Name) }; \
+             $s.Speak($env:IQAMAH_SPEECH_TEXT)",
+        ]);
+        command.env("IQAMAH_SPEECH_LANG", language.code()).env("IQAMAH_SPEECH_TEXT", text);
+    } else {
+        command = Command::new("spd-say");
+        command.args(["-l", language.code(), "--"]).arg(text);
+    }
+    command
+}
+
+/// Default macOS voice for a language; English uses the system voice
+fn macos_voice(language: Language) -> Option<&'static str> {
+    match language {
+        Language::En => None,
+        Language::Ar => Some("Majed"),
+        Language::Fr => Some("Thomas"),
+        Language::Tr => Some("Yelda"),
+        Language::Ur => None,
+    }
+}
+
+/// Start speaking in the background; errors when no speech engine is installed
+pub fn speak(text: &str, language: Language) -> std::io::Result<()> {
+    let mut child = speech_command(text, language).spawn()?;
+    // Reap the process once it has finished speaking
+    std::thread::spawn(move || child.wait());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::DisplayFormatter;
+    use chrono::Utc;
+
+    #[test]
+    fn test_announcement_per_prayer_and_language() {
+        let formatter = DisplayFormatter::from_settings(None, None);
+        let reminder = PrayerNotification::localized(
+            &formatter,
+            PrayerName::Isha,
+            NotificationType::IqamaReminder,
+            Utc::now(),
+            10,
+        );
+
+        let config = SpeechConfig { enabled: true, ..Default::default() };
+        assert_eq!(
+            announcement(&config, Language::En, &reminder).as_deref(),
+            Some("Isha iqama in 10 minutes")
+        );
+        assert_eq!(
+            announcement(&config, Language::Fr, &reminder).as_deref(),
+            Some("Iqama de Icha dans 10 minutes")
+        );
+
+        let fajr_only = SpeechConfig { prayers: vec![PrayerName::Fajr], ..config };
+        assert_eq!(announcement(&fajr_only, Language::En, &reminder), None);
+        assert_eq!(announcement(&SpeechConfig::default(), Language::En, &reminder), None);
+    }
+
+    #[test]
+    fn test_speech_command_passes_text_as_data() {
+        let text = "Isha iqama in 10 minutes; rm -rf ~";
+        let command = speech_command(text, Language::Fr);
+        let args: Vec<_> = command.get_args().map(|a| a.to_string_lossy().to_string()).collect();
+        let envs: Vec<_> = command
+            .get_envs()
+            .filter_map(|(_, v)| v.map(|v| v.to_string_lossy().to_string()))
+            .collect();
+        assert!(args.iter().chain(&envs).any(|a| a == text));
+    }
+}
@@ -0,0 +1,106 @@
+use std::sync::Mutex;
+use std::time::Instant;
+
+use chrono::{DateTime, Duration, Utc};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use crate::services::clock;
+
+/// Wall-clock jumps at least this large re-anchor countdowns at once instead of being smoothed
+pub const RESYNC_THRESHOLD_MS: i64 = 2000;
+
+/// Largest correction applied per tick while smoothing small drift
+const MAX_SLEW_MS: i64 = 50;
+
+/// Countdowns were re-anchored after the system clock jumped (e.g. an NTP adjustment)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct ClockResync {
+    /// Positive when the wall clock jumped forward
+    #[ts(type = "number")]
+    pub correction_ms: i64,
+    pub now: DateTime<Utc>,
+}
+
+/// Time base for countdowns: advances with the monotonic clock and follows the
+/// wall clock through small slews, so clock adjustments don't make countdowns stutter
+#[derive(Debug, Clone)]
+pub struct MonotonicClock {
+    anchor_wall: DateTime<Utc>,
+    anchor_instant: Instant,
+}
+
+impl MonotonicClock {
+    pub fn new(wall: DateTime<Utc>, instant: Instant) -> Self {
+        Self {
+            anchor_wall: wall,
+            anchor_instant: instant,
+        }
+    }
+
+    pub fn now_at(&self, instant: Instant) -> DateTime<Utc> {
+        let elapsed = instant.saturating_duration_since(self.anchor_instant);
+        self.anchor_wall + Duration::from_std(elapsed).unwrap_or_else(|_| Duration::zero())
+    }
+
+    /// Follow the wall clock: slew toward small drift, re-anchor on large jumps
+    pub fn correct(&mut self, wall: DateTime<Utc>, instant: Instant) -> Option<ClockResync> {
+        let drift_ms = (wall - self.now_at(instant)).num_milliseconds();
+
+        if drift_ms.abs() >= RESYNC_THRESHOLD_MS {
+            *self = Self::new(wall, instant);
+            return Some(ClockResync {
+                correction_ms: drift_ms,
+                now: wall,
+            });
+        }
+
+        self.anchor_wall += Duration::milliseconds(drift_ms.clamp(-MAX_SLEW_MS, MAX_SLEW_MS));
+        None
+    }
+}
+
+static COUNTDOWN_CLOCK: Lazy<Mutex<MonotonicClock>> =
+    Lazy::new(|| Mutex::new(MonotonicClock::new(clock::now(), Instant::now())));
+
+/// Smoothed current time for countdowns
+pub fn countdown_now() -> DateTime<Utc> {
+    COUNTDOWN_CLOCK.lock().unwrap().now_at(Instant::now())
+}
+
+/// Check the countdown clock against the wall clock; the app calls this once a second
+pub fn tick() -> Option<ClockResync> {
+    COUNTDOWN_CLOCK.lock().unwrap().correct(clock::now(), Instant::now())
+}
+
+/// Anchor countdowns on the wall clock right away, for a deliberate change such as a simulated time
+/// The next `tick` then sees no jump, so no resync is reported
+pub fn reanchor() {
+    *COUNTDOWN_CLOCK.lock().unwrap() = MonotonicClock::new(clock::now(), Instant::now());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_monotonic_clock_smooths_and_resyncs() {
+        let start = Instant::now();
+        let wall = Utc::now();
+        let mut clock = MonotonicClock::new(wall, start);
+
+        // Small drift is slewed, not applied at once
+        let later = start + std::time::Duration::from_secs(1);
+        let drifted = wall + Duration::milliseconds(1300);
+        assert_eq!(clock.correct(drifted, later), None);
+        assert_eq!(clock.now_at(later), wall + Duration::milliseconds(1050));
+
+        // A large jump re-anchors and reports the correction
+        let jumped = wall + Duration::seconds(61);
+        let resync = clock.correct(jumped, later).unwrap();
+        assert_eq!(resync.correction_ms, 59_950);
+        assert_eq!(clock.now_at(later), jumped);
+    }
+}
@@ -0,0 +1,178 @@
+use chrono::{DateTime, Utc};
+
+use crate::models::{GeoLocation, PrayerName, TravelMode};
+
+/// Distance from the mosque at which the user counts as arrived
+pub const GEOFENCE_RADIUS_M: f64 = 100.0;
+/// Sessions running longer than this were forgotten, not trips to the mosque
+pub const MAX_TRAVEL_SESSION_SECS: i64 = 3 * 60 * 60;
+/// Shortest trip worth learning from; less is a session started at the door
+pub const MIN_LOGGED_TRIP_SECS: i64 = 60;
+/// Logged trips the learned travel time is taken from
+pub const TRAVEL_LEARNING_TRIPS: usize = 10;
+
+/// Trips needed before a learned travel time replaces the saved one
+const MIN_TRIPS: usize = 3;
+/// Roads are longer than the straight line the estimate measures
+const ROUTE_FACTOR: f64 = 1.3;
+/// Distance covered before the observed speed is trusted over the mode's typical one
+const MIN_OBSERVED_DISTANCE_M: f64 = 200.0;
+
+/// Typical speed of a travel mode in town, in metres per second
+pub fn typical_speed_mps(mode: TravelMode) -> f64 {
+    match mode {
+        TravelMode::Walking => 1.35,
+        TravelMode::Cycling => 4.2,
+        TravelMode::Driving => 8.3,
+        TravelMode::Transit => 5.5,
+    }
+}
+
+/// A trip to a mosque in progress, fed with positions from the device
+#[derive(Debug, Clone)]
+pub struct TravelSession {
+    pub mosque_id: String,
+    /// Unknown for mosques without coordinates: no geofence, the session is ended by hand
+    pub mosque_location: Option<GeoLocation>,
+    pub mode: TravelMode,
+    pub prayer: Option<PrayerName>,
+    pub started_at: DateTime<Utc>,
+    /// Saved travel time, counted down while no position is known
+    pub expected_secs: Option<i64>,
+    start: Option<(GeoLocation, DateTime<Utc>)>,
+    last: Option<(GeoLocation, DateTime<Utc>)>,
+}
+
+impl TravelSession {
+    pub fn new(
+        mosque_id: impl Into<String>,
+        mosque_location: Option<GeoLocation>,
+        mode: TravelMode,
+        prayer: Option<PrayerName>,
+        expected_secs: Option<i64>,
+        started_at: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            mosque_id: mosque_id.into(),
+            mosque_location,
+            mode,
+            prayer,
+            started_at,
+            expected_secs,
+            start: None,
+            last: None,
+        }
+    }
+
+    /// Record the device's position; true once it is inside the mosque's geofence
+    pub fn record_location(&mut self, location: GeoLocation, at: DateTime<Utc>) -> bool {
+        if self.start.is_none() {
+            self.start = Some((location, at));
+        }
+        self.last = Some((location, at));
+        self.has_arrived()
+    }
+
+    pub fn has_arrived(&self) -> bool {
+        self.distance_m().is_some_and(|distance| distance <= GEOFENCE_RADIUS_M)
+    }
+
+    /// Straight-line distance from the last position to the mosque
+    pub fn distance_m(&self) -> Option<f64> {
+        let (location, _) = self.last?;
+        Some(location.distance_to(&self.mosque_location?) * 1000.0)
+    }
+
+    /// Seconds left: from the distance at the speed seen so far (or the mode's typical one),
+    /// else the expected travel time minus the time already spent
+    pub fn remaining_secs(&self, now: DateTime<Utc>) -> Option<i64> {
+        let Some(distance) = self.distance_m() else {
+            return self.expected_secs.map(|expected| (expected - self.elapsed_secs(now)).max(0));
+        };
+        let distance = (distance - GEOFENCE_RADIUS_M).max(0.0);
+
+        let secs = match self.observed_speed_mps() {
+            // Measured along the same straight line, so no route detour to add
+            Some(speed) => distance / speed,
+            None => distance * ROUTE_FACTOR / typical_speed_mps(self.mode),
+        };
+        Some(secs.round() as i64)
+    }
+
+    pub fn elapsed_secs(&self, now: DateTime<Utc>) -> i64 {
+        (now - self.started_at).num_seconds().max(0)
+    }
+
+    pub fn is_timed_out(&self, now: DateTime<Utc>) -> bool {
+        self.elapsed_secs(now) > MAX_TRAVEL_SESSION_SECS
+    }
+
+    /// Straight-line progress towards the mosque per second, once the user has covered some ground
+    fn observed_speed_mps(&self) -> Option<f64> {
+        let mosque = self.mosque_location?;
+        let ((start, started), (last, at)) = (self.start?, self.last?);
+        let covered = (start.distance_to(&mosque) - last.distance_to(&mosque)) * 1000.0;
+        let secs = (at - started).num_seconds();
+
+        (covered >= MIN_OBSERVED_DISTANCE_M && secs > 0).then(|| covered / secs as f64)
+    }
+}
+
+/// Typical travel time from logged trips: their median once there are enough of them
+pub fn learn_travel_time(trips: &[i64]) -> Option<i64> {
+    if trips.len() < MIN_TRIPS {
+        return None;
+    }
+
+    let mut sorted = trips.to_vec();
+    sorted.sort_unstable();
+    Some(sorted[sorted.len() / 2])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_session_eta_and_geofence() {
+        let started = Utc::now();
+        // About 1.1 km due north of the start
+        let mosque = GeoLocation::new(48.8666, 2.3522);
+        let start = GeoLocation::new(48.8566, 2.3522);
+        let mut session = TravelSession::new("m1", Some(mosque), TravelMode::Walking, None, Some(900), started);
+
+        // No position yet: the saved travel time counts down
+        assert_eq!(session.remaining_secs(started + Duration::minutes(5)), Some(600));
+
+        assert!(!session.record_location(start, started));
+        let remaining = session.remaining_secs(started).unwrap();
+        assert!((950..1000).contains(&remaining), "typical walking estimate: {}", remaining);
+
+        // 500 m closer after 5 minutes: 1.67 m/s, faster than a typical walk
+        let halfway = GeoLocation::new(48.8611, 2.3522);
+        assert!(!session.record_location(halfway, started + Duration::minutes(5)));
+        let remaining = session.remaining_secs(started + Duration::minutes(5)).unwrap();
+        assert!((280..320).contains(&remaining), "observed speed estimate: {}", remaining);
+
+        assert!(session.record_location(GeoLocation::new(48.8660, 2.3522), started + Duration::minutes(11)));
+        assert!(session.is_timed_out(started + Duration::hours(4)));
+    }
+
+    #[test]
+    fn test_session_without_mosque_location() {
+        let started = Utc::now();
+        let mut session = TravelSession::new("m1", None, TravelMode::Driving, None, None, started);
+
+        assert!(!session.record_location(GeoLocation::new(48.85, 2.35), started));
+        assert_eq!(session.distance_m(), None);
+        assert_eq!(session.remaining_secs(started), None);
+    }
+
+    #[test]
+    fn test_learn_travel_time() {
+        assert_eq!(learn_travel_time(&[600, 660]), None);
+        assert_eq!(learn_travel_time(&[600, 660, 1800]), Some(660));
+        assert_eq!(learn_travel_time(&[540, 600, 620, 700]), Some(620));
+    }
+}
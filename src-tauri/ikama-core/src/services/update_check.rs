@@ -0,0 +1,160 @@
+use std::cmp::Ordering;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+use crate::models::UpdateInfo;
+use crate::providers::{retry_after_secs, ProviderError, ProviderResult};
+
+/// Latest published release of the app; drafts and pre-releases are left out by GitHub
+pub const RELEASES_URL: &str = "https://api.github.com/repos/yassbaat/iqamah.com/releases/latest";
+const UPDATE_CHECK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A release as returned by the GitHub releases API
+#[derive(Debug, Clone, Deserialize)]
+pub struct Release {
+    pub tag_name: String,
+    pub html_url: String,
+    pub body: Option<String>,
+    pub published_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub assets: Vec<ReleaseAsset>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ReleaseAsset {
+    pub name: String,
+    pub browser_download_url: String,
+}
+
+/// Fetch the latest release
+pub async fn fetch_latest_release() -> ProviderResult<Release> {
+    let client = reqwest::Client::builder()
+        .timeout(UPDATE_CHECK_TIMEOUT)
+        // GitHub rejects API requests without a user agent
+        .user_agent(concat!("iqamah/", env!("CARGO_PKG_VERSION")))
+        .build()
+        .map_err(|e| ProviderError::Other(e.to_string()))?;
+
+    let response = client
+        .get(RELEASES_URL)
+        .header("Accept", "application/vnd.github+json")
+        .send()
+        .await
+        .map_err(|e| ProviderError::Network(e.to_string()))?;
+    if !response.status().is_success() {
+        return Err(ProviderError::from_status(
+            response.status().as_u16(),
+            retry_after_secs(response.headers()),
+            "Failed to fetch the latest release",
+        ));
+    }
+
+    response.json().await.map_err(|e| ProviderError::Parse(e.to_string()))
+}
+
+/// Compare a release with the running version and pick the installer for `os` (`std::env::consts::OS`)
+pub fn update_info(current_version: &str, release: &Release, os: &str, self_update: bool) -> UpdateInfo {
+    let latest_version = release.tag_name.trim_start_matches(['v', 'V']).to_string();
+
+    UpdateInfo {
+        current_version: current_version.to_string(),
+        update_available: compare_versions(&latest_version, current_version) == Ordering::Greater,
+        latest_version,
+        download_url: installer_for(&release.assets, os).map(|asset| asset.browser_download_url.clone()),
+        release_url: release.html_url.clone(),
+        release_notes: release.body.clone().filter(|notes| !notes.trim().is_empty()),
+        published_at: release.published_at,
+        self_update,
+    }
+}
+
+/// Compare dotted versions numerically; a pre-release (`1.2.0-beta`) sorts before its release
+pub fn compare_versions(a: &str, b: &str) -> Ordering {
+    let (a_core, a_pre) = split_version(a);
+    let (b_core, b_pre) = split_version(b);
+
+    let len = a_core.len().max(b_core.len());
+    let part = |parts: &[u64], i: usize| parts.get(i).copied().unwrap_or(0);
+    (0..len)
+        .map(|i| part(&a_core, i).cmp(&part(&b_core, i)))
+        .find(|ordering| ordering.is_ne())
+        .unwrap_or_else(|| match (a_pre, b_pre) {
+            (None, None) => Ordering::Equal,
+            (None, Some(_)) => Ordering::Greater,
+            (Some(_), None) => Ordering::Less,
+            (Some(a), Some(b)) => a.cmp(b),
+        })
+}
+
+fn split_version(version: &str) -> (Vec<u64>, Option<&str>) {
+    let version = version.trim().trim_start_matches(['v', 'V']);
+    let version = version.split('+').next().unwrap_or(version);
+    let (core, pre) = match version.split_once('-') {
+        Some((core, pre)) => (core, Some(pre)),
+        None => (version, None),
+    };
+    let parts = core.split('.').map(|part| part.parse().unwrap_or(0)).collect();
+    (parts, pre)
+}
+
+/// Installer asset for an operating system, in order of preference
+fn installer_for<'a>(assets: &'a [ReleaseAsset], os: &str) -> Option<&'a ReleaseAsset> {
+    let suffixes: &[&str] = match os {
+        "windows" => &["-setup.exe", ".msi"],
+        "macos" => &[".dmg"],
+        "linux" => &[".appimage", ".deb", ".rpm"],
+        _ => &[],
+    };
+    suffixes.iter().find_map(|suffix| {
+        assets
+            .iter()
+            .find(|asset| asset.name.to_ascii_lowercase().ends_with(suffix))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compare_versions() {
+        assert_eq!(compare_versions("1.10.0", "1.9.3"), Ordering::Greater);
+        assert_eq!(compare_versions("v1.2", "1.2.0"), Ordering::Equal);
+        assert_eq!(compare_versions("1.2.0-beta.1", "1.2.0"), Ordering::Less);
+        assert_eq!(compare_versions("1.2.0", "1.2.0-beta.1"), Ordering::Greater);
+        assert_eq!(compare_versions("2.0.0+build.5", "2.0.0"), Ordering::Equal);
+    }
+
+    #[test]
+    fn test_update_info() {
+        let release: Release = serde_json::from_str(
+            r#"{
+                "tag_name": "v1.3.0",
+                "html_url": "https://github.com/yassbaat/iqamah.com/releases/tag/v1.3.0",
+                "body": "- Kiosk mode\n- Portable mode",
+                "published_at": "2026-09-01T12:00:00Z",
+                "assets": [
+                    {"name": "iqamah_1.3.0_x64_en-US.msi", "browser_download_url": "https://example.org/a.msi"},
+                    {"name": "iqamah_1.3.0_x64-setup.exe", "browser_download_url": "https://example.org/a.exe"},
+                    {"name": "iqamah_1.3.0_amd64.AppImage", "browser_download_url": "https://example.org/a.AppImage"}
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        let info = update_info("1.2.4", &release, "windows", false);
+        assert!(info.update_available);
+        assert_eq!(info.latest_version, "1.3.0");
+        assert_eq!(info.download_url.as_deref(), Some("https://example.org/a.exe"));
+        assert_eq!(info.release_notes.as_deref(), Some("- Kiosk mode\n- Portable mode"));
+
+        assert_eq!(
+            update_info("1.2.4", &release, "linux", false).download_url.as_deref(),
+            Some("https://example.org/a.AppImage")
+        );
+        assert_eq!(update_info("1.2.4", &release, "macos", false).download_url, None);
+        assert!(!update_info("1.3.0", &release, "windows", false).update_available);
+    }
+}
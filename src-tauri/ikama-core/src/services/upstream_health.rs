@@ -0,0 +1,136 @@
+use std::sync::Mutex;
+
+use once_cell::sync::Lazy;
+
+use crate::models::UpstreamIssue;
+use crate::providers::http_fixtures::fnv1a;
+use crate::services::clock;
+
+/// Reports kept until the next flush to the database; older ones are dropped first
+const MAX_PENDING_EVENTS: usize = 256;
+
+/// A parse outcome reported by a provider
+#[derive(Debug, Clone, PartialEq)]
+pub enum UpstreamEvent {
+    Failure(UpstreamIssue),
+    /// The context parsed again, so its earlier failures are resolved
+    Recovered { provider_id: String, context: String },
+}
+
+// Reports from providers, which have no database handle, waiting to be stored
+static PENDING: Lazy<Mutex<Vec<UpstreamEvent>>> = Lazy::new(|| Mutex::new(Vec::new()));
+
+/// Record that a provider could not parse what upstream sent
+/// `degraded` when a fallback parser still produced data
+pub fn report_parse_failure(provider_id: &str, context: &str, message: &str, degraded: bool) {
+    let now = clock::now();
+    log::warn!("Upstream parse failure in {} ({}): {}", provider_id, context, message);
+
+    push(UpstreamEvent::Failure(UpstreamIssue {
+        provider_id: provider_id.to_string(),
+        context: context.to_string(),
+        fingerprint: failure_fingerprint(provider_id, context, message),
+        message: message.to_string(),
+        degraded,
+        occurrences: 1,
+        first_seen: now,
+        last_seen: now,
+    }));
+}
+
+/// Record that a context parsed normally
+pub fn report_parse_success(provider_id: &str, context: &str) {
+    push(UpstreamEvent::Recovered {
+        provider_id: provider_id.to_string(),
+        context: context.to_string(),
+    });
+}
+
+/// Context of a page belonging to one mosque, so each mosque's health is tracked on its own
+pub fn mosque_context(context: &str, mosque_id: &str) -> String {
+    format!("{}:{}", context, mosque_id)
+}
+
+/// Take every report made since the last call, oldest first
+pub fn take_upstream_events() -> Vec<UpstreamEvent> {
+    std::mem::take(&mut *PENDING.lock().unwrap_or_else(|e| e.into_inner()))
+}
+
+/// Same value for repeats of one failure: numbers (line, column, dates) are ignored
+pub fn failure_fingerprint(provider_id: &str, context: &str, message: &str) -> String {
+    let mut normalized = String::with_capacity(message.len());
+    for c in message.trim().chars() {
+        if c.is_ascii_digit() {
+            if !normalized.ends_with('#') {
+                normalized.push('#');
+            }
+        } else {
+            normalized.extend(c.to_lowercase());
+        }
+    }
+
+    format!("{:08x}", fnv1a(&format!("{}|{}|{}", provider_id, context, normalized)))
+}
+
+fn push(event: UpstreamEvent) {
+    let mut pending = PENDING.lock().unwrap_or_else(|e| e.into_inner());
+
+    // Successes are reported on every fetch; a repeat of the context's latest report adds nothing
+    if let UpstreamEvent::Recovered { provider_id, context } = &event {
+        let latest = pending.iter().rev().find(|pending| match pending {
+            UpstreamEvent::Failure(issue) => issue.provider_id == *provider_id && issue.context == *context,
+            UpstreamEvent::Recovered { provider_id: p, context: c } => p == provider_id && c == context,
+        });
+        if latest == Some(&event) {
+            return;
+        }
+    }
+    if pending.len() >= MAX_PENDING_EVENTS {
+        pending.remove(0);
+    }
+    pending.push(event);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fingerprint_ignores_numbers() {
+        let a = failure_fingerprint("mawaqit", "confData", "missing field `times` at line 1 column 532");
+        let b = failure_fingerprint("mawaqit", "confData", "missing field `times` at line 3 column 87");
+
+        assert_eq!(a, b);
+        assert_ne!(a, failure_fingerprint("mawaqit", "mosque_list", "missing field `times` at line 1 column 532"));
+        assert_ne!(a, failure_fingerprint("mawaqit", "confData", "missing field `calendar` at line 1 column 532"));
+    }
+
+    #[test]
+    fn test_recovery_after_failure_is_kept() {
+        let recovered = |context: &str| UpstreamEvent::Recovered {
+            provider_id: "test-health".to_string(),
+            context: context.to_string(),
+        };
+
+        report_parse_success("test-health", "a");
+        report_parse_success("test-health", "a");
+        report_parse_failure("test-health", "a", "no confData", true);
+        report_parse_success("test-health", "b");
+        report_parse_success("test-health", "a");
+        report_parse_success("test-health", "a");
+        // Other tests report concurrently; keep only this test's provider
+        let pending: Vec<_> = take_upstream_events()
+            .into_iter()
+            .filter(|event| match event {
+                UpstreamEvent::Failure(issue) => issue.provider_id == "test-health",
+                UpstreamEvent::Recovered { provider_id, .. } => provider_id == "test-health",
+            })
+            .collect();
+
+        assert_eq!(pending.len(), 4);
+        assert_eq!(pending[0], recovered("a"));
+        assert!(matches!(pending[1], UpstreamEvent::Failure(_)));
+        assert_eq!(pending[2], recovered("b"));
+        assert_eq!(pending[3], recovered("a"));
+    }
+}
@@ -0,0 +1,184 @@
+use base64::Engine;
+use sha1::{Digest, Sha1};
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+use super::HttpRequest;
+
+/// GUID every server appends to the client's key (RFC 6455, section 1.3)
+const HANDSHAKE_GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+
+/// Largest frame read from a client; dashboards only send pings and close frames
+pub const MAX_CLIENT_FRAME: u64 = 4 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Opcode {
+    Continuation,
+    Text,
+    Binary,
+    Close,
+    Ping,
+    Pong,
+}
+
+impl Opcode {
+    fn from_byte(byte: u8) -> Option<Self> {
+        match byte & 0x0F {
+            0x0 => Some(Self::Continuation),
+            0x1 => Some(Self::Text),
+            0x2 => Some(Self::Binary),
+            0x8 => Some(Self::Close),
+            0x9 => Some(Self::Ping),
+            0xA => Some(Self::Pong),
+            _ => None,
+        }
+    }
+
+    fn byte(self) -> u8 {
+        match self {
+            Self::Continuation => 0x0,
+            Self::Text => 0x1,
+            Self::Binary => 0x2,
+            Self::Close => 0x8,
+            Self::Ping => 0x9,
+            Self::Pong => 0xA,
+        }
+    }
+}
+
+/// A frame received from a client, unmasked
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    pub opcode: Opcode,
+    pub payload: Vec<u8>,
+}
+
+/// The client's `Sec-WebSocket-Key` when the request asks to switch to WebSocket
+pub fn upgrade_key(request: &HttpRequest) -> Option<&str> {
+    let upgrade = request.header("upgrade")?;
+    let connection = request.header("connection")?;
+    let wants_upgrade = upgrade.eq_ignore_ascii_case("websocket")
+        && connection.split(',').any(|token| token.trim().eq_ignore_ascii_case("upgrade"));
+    if request.method != "GET" || !wants_upgrade {
+        return None;
+    }
+    request.header("sec-websocket-key")
+}
+
+/// `Sec-WebSocket-Accept` value proving the server read the client's key
+pub fn accept_key(key: &str) -> String {
+    let mut sha1 = Sha1::new();
+    sha1.update(key.trim().as_bytes());
+    sha1.update(HANDSHAKE_GUID.as_bytes());
+    base64::engine::general_purpose::STANDARD.encode(sha1.finalize())
+}
+
+/// The `101 Switching Protocols` response that opens the connection
+pub fn handshake_response(key: &str) -> Vec<u8> {
+    format!(
+        "HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\nConnection: Upgrade\r\nSec-WebSocket-Accept: {}\r\n\r\n",
+        accept_key(key)
+    )
+    .into_bytes()
+}
+
+/// An unfragmented, unmasked frame as sent by a server
+pub fn encode_frame(opcode: Opcode, payload: &[u8]) -> Vec<u8> {
+    let mut frame = Vec::with_capacity(payload.len() + 10);
+    frame.push(0x80 | opcode.byte());
+    match payload.len() {
+        len if len < 126 => frame.push(len as u8),
+        len if len <= u16::MAX as usize => {
+            frame.push(126);
+            frame.extend_from_slice(&(len as u16).to_be_bytes());
+        }
+        len => {
+            frame.push(127);
+            frame.extend_from_slice(&(len as u64).to_be_bytes());
+        }
+    }
+    frame.extend_from_slice(payload);
+    frame
+}
+
+pub fn text_frame(text: &str) -> Vec<u8> {
+    encode_frame(Opcode::Text, text.as_bytes())
+}
+
+/// Read one frame from a client; clients must mask their frames
+pub async fn read_frame<R: AsyncRead + Unpin>(reader: &mut R) -> Result<Frame, String> {
+    let mut head = [0u8; 2];
+    reader.read_exact(&mut head).await.map_err(|e| e.to_string())?;
+    let opcode = Opcode::from_byte(head[0]).ok_or_else(|| format!("Unknown opcode {:#x}", head[0] & 0x0F))?;
+    if head[1] & 0x80 == 0 {
+        return Err("Client frame is not masked".to_string());
+    }
+
+    let len = match head[1] & 0x7F {
+        126 => {
+            let mut len = [0u8; 2];
+            reader.read_exact(&mut len).await.map_err(|e| e.to_string())?;
+            u16::from_be_bytes(len) as u64
+        }
+        127 => {
+            let mut len = [0u8; 8];
+            reader.read_exact(&mut len).await.map_err(|e| e.to_string())?;
+            u64::from_be_bytes(len)
+        }
+        len => len as u64,
+    };
+    if len > MAX_CLIENT_FRAME {
+        return Err(format!("Client frame of {} bytes is too large", len));
+    }
+
+    let mut mask = [0u8; 4];
+    reader.read_exact(&mut mask).await.map_err(|e| e.to_string())?;
+    let mut payload = vec![0u8; len as usize];
+    reader.read_exact(&mut payload).await.map_err(|e| e.to_string())?;
+    for (i, byte) in payload.iter_mut().enumerate() {
+        *byte ^= mask[i % 4];
+    }
+
+    Ok(Frame { opcode, payload })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_handshake() {
+        // Sample handshake from RFC 6455
+        assert_eq!(accept_key("dGhlIHNhbXBsZSBub25jZQ=="), "s3pPLMBiTxaQ9kYGzzhZRbK+xOo=");
+
+        let request = HttpRequest::parse(
+            "GET /ws HTTP/1.1\r\nHost: localhost\r\nUpgrade: websocket\r\nConnection: keep-alive, Upgrade\r\n\
+             Sec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\nSec-WebSocket-Version: 13\r\n\r\n",
+        )
+        .unwrap();
+        assert_eq!(upgrade_key(&request), Some("dGhlIHNhbXBsZSBub25jZQ=="));
+        let response = String::from_utf8(handshake_response("dGhlIHNhbXBsZSBub25jZQ==")).unwrap();
+        assert!(response.starts_with("HTTP/1.1 101 Switching Protocols\r\n"));
+        assert!(response.contains("Sec-WebSocket-Accept: s3pPLMBiTxaQ9kYGzzhZRbK+xOo=\r\n"));
+
+        let plain = HttpRequest::parse("GET /ws HTTP/1.1\r\nHost: localhost\r\n\r\n").unwrap();
+        assert_eq!(upgrade_key(&plain), None);
+    }
+
+    #[tokio::test]
+    async fn test_frames() {
+        assert_eq!(text_frame("Hi"), [0x81, 0x02, b'H', b'i']);
+        let long = text_frame(&"a".repeat(300));
+        assert_eq!(long[..4], [0x81, 126, 0x01, 0x2C]);
+        assert_eq!(long.len(), 304);
+
+        // Masked "Hello" from RFC 6455, section 5.7
+        let masked = [0x81, 0x85, 0x37, 0xfa, 0x21, 0x3d, 0x7f, 0x9f, 0x4d, 0x51, 0x58];
+        let frame = read_frame(&mut &masked[..]).await.unwrap();
+        assert_eq!(frame.opcode, Opcode::Text);
+        assert_eq!(frame.payload, b"Hello");
+
+        let close = [0x88, 0x80, 0, 0, 0, 0];
+        assert_eq!(read_frame(&mut &close[..]).await.unwrap().opcode, Opcode::Close);
+        assert!(read_frame(&mut &[0x81, 0x02, b'H', b'i'][..]).await.is_err());
+    }
+}
@@ -0,0 +1,64 @@
+//! Mawaqit provider against recorded HTTP fixtures
+//! Refresh the fixtures with `cargo test --test mawaqit_fixtures -- --ignored`
+
+use std::path::PathBuf;
+
+use chrono::NaiveDate;
+use ikama_core::providers::http_cache::HttpCache;
+use ikama_core::providers::http_fixtures::FixtureMode;
+use ikama_core::providers::{MawaqitProvider, PrayerDataProvider};
+
+fn fixture_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/mawaqit")
+}
+
+fn provider(mode: FixtureMode, name: &str) -> MawaqitProvider {
+    let cache_dir = std::env::temp_dir().join(format!("iqamah-fixtures-{}-{}", name, std::process::id()));
+    MawaqitProvider::with_http_cache(HttpCache::new(cache_dir).with_fixtures(mode))
+}
+
+#[tokio::test]
+async fn replays_country_search() {
+    let provider = provider(FixtureMode::Replay(fixture_dir()), "search");
+
+    let mosques = provider.search_mosques("paris", None).await.unwrap();
+
+    let paris = mosques.iter().find(|m| m.id == "mosquee-paris").unwrap();
+    assert!(paris.latitude.is_some() && paris.longitude.is_some());
+    assert!(mosques.iter().all(|m| !m.name.is_empty()));
+}
+
+#[tokio::test]
+async fn replays_mosque_page_calendar() {
+    let provider = provider(FixtureMode::Replay(fixture_dir()), "page");
+    // A Friday early in the year, covered by hand-written and recorded calendars alike
+    let date = NaiveDate::from_ymd_opt(2026, 1, 2).unwrap();
+
+    let times = provider.get_prayer_times("mosquee-paris", Some(date)).await.unwrap();
+
+    assert!(times.mosque_name.is_some_and(|name| name.contains("Paris")));
+    assert!(times.fajr.adhan < times.dhuhr.adhan && times.maghrib.adhan < times.isha.adhan);
+    assert!(times.fajr.iqama.is_some());
+    for prayer in [&times.fajr, &times.dhuhr, &times.asr, &times.maghrib, &times.isha] {
+        assert!(prayer.iqama.is_none_or(|iqama| iqama >= prayer.adhan), "{:?}", prayer);
+    }
+    assert!(times.jumuah.is_some());
+}
+
+#[tokio::test]
+async fn missing_fixture_is_not_fetched() {
+    let provider = provider(FixtureMode::Replay(fixture_dir()), "missing");
+
+    let result = provider.get_prayer_times("no-such-mosque", None).await;
+
+    assert!(result.unwrap_err().to_string().contains("No fixture recorded"));
+}
+
+#[tokio::test]
+#[ignore = "records fixtures from mawaqit.net"]
+async fn record_fixtures() {
+    let provider = provider(FixtureMode::Record(fixture_dir()), "record");
+
+    provider.search_mosques("paris", None).await.unwrap();
+    provider.get_prayer_times("mosquee-paris", None).await.unwrap();
+}
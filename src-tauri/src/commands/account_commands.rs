@@ -0,0 +1,112 @@
+use serde::Serialize;
+use tauri::State;
+use ts_rs::TS;
+
+use crate::commands::error::{CommandError, CommandResult};
+use crate::commands::settings_commands::load_provider_config;
+use crate::db::Database;
+use crate::models::*;
+use crate::providers::{OfficialApiProvider, PrayerDataProvider};
+use crate::services::{clock, SecretStore};
+
+/// Outcome of importing a Mawaqit account
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, optional_fields = nullable)]
+pub struct AccountImport {
+    /// Favorites that were not in the local database yet
+    pub added: u32,
+    /// Favorites matched to mosques already saved locally
+    pub matched: u32,
+    /// Account's preferred mosque, now the selected mosque
+    pub selected_mosque: Option<Mosque>,
+}
+
+/// Keychain entry of the token a Mawaqit account login returns, kept apart from the official provider's own token
+const ACCOUNT_TOKEN_SECRET: &str = "mawaqit_account/api_token";
+
+/// Log into Mawaqit, keep the account's API token in the keychain and import the
+/// account's favorites and preferred mosque. The password itself is not stored.
+#[tauri::command]
+pub async fn import_mawaqit_account(
+    email: String,
+    password: String,
+    db: State<'_, Database>,
+) -> CommandResult<AccountImport> {
+    // Only the endpoint is shared with the official provider, its configured token stays untouched
+    let base_url = load_provider_config(&db, PROVIDER_OFFICIAL_API)
+        .await
+        .map_err(CommandError::database)?
+        .and_then(|config| config.settings.get("base_url").cloned())
+        .unwrap_or(serde_json::Value::Null);
+
+    let mut provider = OfficialApiProvider::new();
+    provider
+        .initialize(serde_json::json!({ "base_url": base_url }))
+        .await
+        .map_err(|e| CommandError::from_provider(PROVIDER_OFFICIAL_API, e))?;
+
+    let token = provider
+        .login(&email, &password)
+        .await
+        .map_err(|e| CommandError::from_provider(PROVIDER_OFFICIAL_API, e))?;
+
+    provider
+        .initialize(serde_json::json!({ "api_token": token, "base_url": base_url }))
+        .await
+        .map_err(|e| CommandError::from_provider(PROVIDER_OFFICIAL_API, e))?;
+
+    if let Err(e) = SecretStore::new().set(ACCOUNT_TOKEN_SECRET, &token) {
+        log::warn!("Could not keep the Mawaqit account token in the keychain: {}", e);
+    }
+
+    let account = provider
+        .get_account()
+        .await
+        .map_err(|e| CommandError::from_provider(PROVIDER_OFFICIAL_API, e))?;
+
+    let mut result = AccountImport {
+        added: 0,
+        matched: 0,
+        selected_mosque: None,
+    };
+
+    for mosque in account.favorites {
+        // Keep existing records (and the user's edits to them), only flag them as favorites
+        match db.get_mosque(&mosque.id).await.map_err(CommandError::database)? {
+            Some(_) => result.matched += 1,
+            None => {
+                db.save_mosque(&Mosque { is_favorite: true, ..mosque.clone() })
+                    .await
+                    .map_err(CommandError::database)?;
+                result.added += 1;
+            }
+        }
+        db.set_favorite(&mosque.id, true)
+            .await
+            .map_err(CommandError::database)?;
+    }
+
+    if let Some(id) = account.preferred_mosque_id {
+        if let Some(mosque) = db.get_mosque(&id).await.map_err(CommandError::database)? {
+            let selected = SelectedMosque {
+                profile: DEFAULT_PROFILE.to_string(),
+                mosque: mosque.clone(),
+                selected_at: clock::now(),
+            };
+            db.save_selected_mosque(&selected)
+                .await
+                .map_err(CommandError::database)?;
+            result.selected_mosque = Some(mosque);
+        } else {
+            log::warn!("Preferred Mawaqit mosque {} is not among the account favorites", id);
+        }
+    }
+
+    log::info!(
+        "Imported Mawaqit account: {} favorites added, {} matched",
+        result.added,
+        result.matched
+    );
+
+    Ok(result)
+}
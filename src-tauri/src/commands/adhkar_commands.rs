@@ -0,0 +1,145 @@
+use tauri::State;
+use tokio::sync::Mutex;
+
+use crate::commands::error::{CommandError, CommandResult};
+use crate::commands::prayer_commands::{user_engine_config, with_start_timing};
+use crate::commands::prayer_log_commands::log_attendance;
+use crate::db::Database;
+use crate::models::PrayerName;
+use crate::services::{clock, AdhkarConfig, AdhkarEvent, AdhkarStatus, AdhkarTimer, PrayerEngine};
+
+const ADHKAR_CONFIG_KEY: &str = "adhkar_config";
+
+/// Post-prayer adhkar timer shared between commands and the background ticker
+pub struct AdhkarState {
+    timer: Mutex<AdhkarTimer>,
+}
+
+impl AdhkarState {
+    pub fn new() -> Self {
+        Self {
+            timer: Mutex::new(AdhkarTimer::default()),
+        }
+    }
+
+    /// Advance the timer and take the events it produced, for emitting to the UI
+    pub async fn tick(&self) -> Vec<AdhkarEvent> {
+        let mut timer = self.timer.lock().await;
+        timer.tick(clock::now());
+        timer.drain_events()
+    }
+}
+
+impl Default for AdhkarState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Mark a prayer as attended; the adhkar window opens once the congregation is expected to finish
+/// The prayer is also logged as prayed in jamaah at the mosque
+#[tauri::command]
+pub async fn mark_prayer_attended(
+    mosque_id: String,
+    prayer_name: String,
+    db: State<'_, Database>,
+    adhkar: State<'_, AdhkarState>,
+) -> CommandResult<AdhkarStatus> {
+    let name: PrayerName = prayer_name.parse().map_err(CommandError::invalid_input)?;
+    attend_prayer(&db, &adhkar, &mosque_id, name).await
+}
+
+pub(crate) async fn attend_prayer(
+    db: &Database,
+    adhkar: &AdhkarState,
+    mosque_id: &str,
+    name: PrayerName,
+) -> CommandResult<AdhkarStatus> {
+    let prayer_times = db
+        .get_prayer_times(mosque_id, clock::today())
+        .await
+        .map_err(CommandError::database)?
+        .ok_or_else(|| CommandError::not_found("No prayer times found"))?;
+
+    let prayer = prayer_times
+        .get_prayer_by_name(name)
+        .ok_or_else(|| CommandError::not_found(format!("Prayer {} not found", name)))?;
+
+    if let Err(e) = log_attendance(db, mosque_id, name).await {
+        log::warn!("Failed to log attendance of {}: {}", name, e.message);
+    }
+
+    let now = clock::now();
+    let engine = with_start_timing(PrayerEngine::new(user_engine_config(db).await), db, mosque_id).await;
+    let prayer_end = engine.estimated_end(prayer).unwrap_or(now);
+
+    let mut timer = adhkar.timer.lock().await;
+    timer.set_config(load_adhkar_config(db).await);
+    timer.attend(name, prayer_end, now);
+
+    Ok(timer.status(now))
+}
+
+/// Current adhkar phase, counter and remaining time
+#[tauri::command]
+pub async fn get_adhkar_status(adhkar: State<'_, AdhkarState>) -> CommandResult<AdhkarStatus> {
+    let now = clock::now();
+    let mut timer = adhkar.timer.lock().await;
+    timer.tick(now);
+
+    Ok(timer.status(now))
+}
+
+/// Count one dhikr in the active session
+#[tauri::command]
+pub async fn increment_adhkar(adhkar: State<'_, AdhkarState>) -> CommandResult<AdhkarStatus> {
+    let now = clock::now();
+    let mut timer = adhkar.timer.lock().await;
+    timer.increment(now);
+
+    Ok(timer.status(now))
+}
+
+/// Dismiss the current adhkar session
+#[tauri::command]
+pub async fn stop_adhkar(adhkar: State<'_, AdhkarState>) -> CommandResult<AdhkarStatus> {
+    let mut timer = adhkar.timer.lock().await;
+    timer.stop();
+
+    Ok(timer.status(clock::now()))
+}
+
+#[tauri::command]
+pub async fn get_adhkar_config(db: State<'_, Database>) -> CommandResult<AdhkarConfig> {
+    Ok(load_adhkar_config(&db).await)
+}
+
+#[tauri::command]
+pub async fn save_adhkar_config(
+    config: AdhkarConfig,
+    db: State<'_, Database>,
+    adhkar: State<'_, AdhkarState>,
+) -> CommandResult<()> {
+    if config.duration_minutes <= 0 || config.steps.iter().any(|s| s.target == 0) {
+        return Err(CommandError::invalid_input(
+            "Adhkar duration and step targets must be positive",
+        ));
+    }
+
+    let json = serde_json::to_string(&config).map_err(|e| CommandError::internal(e.to_string()))?;
+    db.set_setting(ADHKAR_CONFIG_KEY, &json)
+        .await
+        .map_err(CommandError::database)?;
+
+    adhkar.timer.lock().await.set_config(config);
+    Ok(())
+}
+
+async fn load_adhkar_config(db: &Database) -> AdhkarConfig {
+    db.get_setting(ADHKAR_CONFIG_KEY)
+        .await
+        .ok()
+        .flatten()
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
@@ -0,0 +1,304 @@
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use chrono::{Local, NaiveDate};
+use serde::Serialize;
+use tauri::State;
+use ts_rs::TS;
+
+use crate::commands::error::{CommandError, CommandResult};
+use crate::commands::hijri_commands::load_mosque_hijri_info;
+use crate::db::Database;
+use crate::models::*;
+use crate::services::{clock, export_schedule, HijriService, ScheduleExportFormat, SecretStore};
+
+/// Keychain account holding the admin PIN
+const ADMIN_PIN_ACCOUNT: &str = "admin/pin";
+
+/// Admin mode locks itself again after this long
+const ADMIN_SESSION: Duration = Duration::from_secs(30 * 60);
+
+const MIN_PIN_LEN: usize = 4;
+
+/// Unlocked admin session, required to edit mosque timetables
+pub struct AdminState {
+    unlocked_until: Mutex<Option<Instant>>,
+}
+
+impl AdminState {
+    pub fn new() -> Self {
+        Self {
+            unlocked_until: Mutex::new(None),
+        }
+    }
+
+    fn unlocked_secs(&self) -> Option<u64> {
+        let until = (*self.unlocked_until.lock().unwrap())?;
+        until.checked_duration_since(Instant::now()).map(|left| left.as_secs())
+    }
+
+    fn require_unlocked(&self) -> CommandResult<()> {
+        match self.unlocked_secs() {
+            Some(_) => Ok(()),
+            None => Err(CommandError::invalid_input("Admin mode is locked, unlock it with the admin PIN")),
+        }
+    }
+
+    fn set_unlocked(&self, unlocked: bool) {
+        *self.unlocked_until.lock().unwrap() = unlocked.then(|| Instant::now() + ADMIN_SESSION);
+    }
+}
+
+impl Default for AdminState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Whether admin mode is set up and unlocked
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, optional_fields = nullable)]
+pub struct AdminStatus {
+    pub has_pin: bool,
+    pub unlocked: bool,
+    /// Seconds until admin mode locks itself
+    pub expires_in_secs: Option<u32>,
+}
+
+fn admin_status(admin: &AdminState) -> CommandResult<AdminStatus> {
+    let has_pin = SecretStore::new()
+        .get(ADMIN_PIN_ACCOUNT)
+        .map_err(|e| CommandError::internal(e.to_string()))?
+        .is_some();
+    let expires_in_secs = admin.unlocked_secs().map(|secs| secs as u32);
+
+    Ok(AdminStatus {
+        has_pin,
+        unlocked: expires_in_secs.is_some(),
+        expires_in_secs,
+    })
+}
+
+/// Compare PINs without stopping at the first difference
+fn pin_matches(expected: &str, given: &str) -> bool {
+    expected.len() == given.len() && expected.bytes().zip(given.bytes()).fold(0, |diff, (a, b)| diff | (a ^ b)) == 0
+}
+
+fn check_pin(given: &str) -> CommandResult<()> {
+    let expected = SecretStore::new()
+        .get(ADMIN_PIN_ACCOUNT)
+        .map_err(|e| CommandError::internal(e.to_string()))?
+        .ok_or_else(|| CommandError::not_found("No admin PIN set"))?;
+
+    if !pin_matches(&expected, given) {
+        log::warn!("Wrong admin PIN entered");
+        return Err(CommandError::invalid_input("Wrong admin PIN"));
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_admin_status(admin: State<'_, AdminState>) -> CommandResult<AdminStatus> {
+    admin_status(&admin)
+}
+
+/// Set or change the admin PIN; changing it requires the current one
+#[tauri::command]
+pub async fn set_admin_pin(
+    new_pin: String,
+    current_pin: Option<String>,
+    admin: State<'_, AdminState>,
+) -> CommandResult<AdminStatus> {
+    if new_pin.chars().count() < MIN_PIN_LEN || !new_pin.chars().all(|c| c.is_ascii_digit()) {
+        return Err(CommandError::invalid_input(format!(
+            "The admin PIN needs at least {} digits",
+            MIN_PIN_LEN
+        )));
+    }
+    if admin_status(&admin)?.has_pin {
+        check_pin(current_pin.as_deref().unwrap_or_default())?;
+    }
+
+    SecretStore::new()
+        .set(ADMIN_PIN_ACCOUNT, &new_pin)
+        .map_err(|e| CommandError::internal(e.to_string()))?;
+    admin.set_unlocked(false);
+    admin_status(&admin)
+}
+
+#[tauri::command]
+pub async fn unlock_admin_mode(pin: String, admin: State<'_, AdminState>) -> CommandResult<AdminStatus> {
+    check_pin(&pin)?;
+    admin.set_unlocked(true);
+    log::info!("Admin mode unlocked");
+    admin_status(&admin)
+}
+
+#[tauri::command]
+pub async fn lock_admin_mode(admin: State<'_, AdminState>) -> CommandResult<AdminStatus> {
+    admin.set_unlocked(false);
+    admin_status(&admin)
+}
+
+/// Edit a mosque's cached timetable, e.g. every Isha iqama of a month at once
+/// Edited days are marked as admin-written, so refreshes keep them
+#[tauri::command]
+pub async fn admin_edit_schedule(
+    mosque_id: String,
+    edits: Vec<ScheduleEdit>,
+    db: State<'_, Database>,
+    admin: State<'_, AdminState>,
+) -> CommandResult<Vec<PrayerTimes>> {
+    admin.require_unlocked()?;
+    if edits.is_empty() {
+        return Err(CommandError::invalid_input("No edits given"));
+    }
+    for edit in &edits {
+        edit.validate().map_err(CommandError::invalid_input)?;
+    }
+
+    let start = edits.iter().map(|e| e.from).min().unwrap_or_else(clock::today);
+    let end = edits.iter().map(ScheduleEdit::last_day).max().unwrap_or(start);
+    let mut days: HashMap<NaiveDate, PrayerTimes> = db
+        .get_prayer_times_range(&mosque_id, start, end)
+        .await
+        .map_err(CommandError::database)?
+        .into_iter()
+        .map(|t| (t.date.with_timezone(&Local).date_naive(), t))
+        .collect();
+
+    let user_availability = db.get_prayer_availability(&mosque_id).await.map_err(CommandError::database)?;
+    let now = clock::now();
+    let mut touched = BTreeSet::new();
+    for edit in &edits {
+        for date in edit.dates() {
+            let times = days.get_mut(&date).ok_or_else(|| {
+                CommandError::not_found(format!("No schedule cached for {} on {}, load it before editing", mosque_id, date))
+            })?;
+            edit.apply(times, user_availability.as_ref(), now)
+                .map_err(CommandError::invalid_input)?;
+            touched.insert(date);
+        }
+    }
+
+    let edited: Vec<PrayerTimes> = touched.iter().filter_map(|date| days.remove(date)).collect();
+    db.save_prayer_times_bulk(&edited).await.map_err(CommandError::database)?;
+
+    log::info!("Admin edited {} day(s) of {}", edited.len(), mosque_id);
+    Ok(edited)
+}
+
+/// Copy last year's Ramadan timetable onto this year's Ramadan, day for day
+/// Ramadan dates follow the mosque's Hijri adjustment unless `hijri_offset_days` overrides it
+#[tauri::command]
+pub async fn admin_copy_ramadan(
+    mosque_id: String,
+    hijri_offset_days: Option<i32>,
+    db: State<'_, Database>,
+    admin: State<'_, AdminState>,
+) -> CommandResult<Vec<PrayerTimes>> {
+    admin.require_unlocked()?;
+
+    let info = match hijri_offset_days {
+        Some(offset) if !(-3..=3).contains(&offset) => {
+            return Err(CommandError::invalid_input("The Hijri offset must be between -3 and 3 days"));
+        }
+        Some(offset) => Some(MosqueHijriInfo {
+            adjustment_days: offset,
+            ..load_mosque_hijri_info(&db, &mosque_id).await.unwrap_or_default()
+        }),
+        None => load_mosque_hijri_info(&db, &mosque_id).await,
+    };
+    let hijri = HijriService::new(info);
+
+    // This year's Ramadan, or next year's once it is over
+    let today = hijri.day(clock::today()).date;
+    let year = if today.month > 9 { today.year + 1 } else { today.year };
+    let targets = hijri.month_dates(year, 9);
+    let last_ramadan = hijri.month_dates(year - 1, 9);
+    let (Some(&first), Some(&last)) = (last_ramadan.first(), last_ramadan.last()) else {
+        return Err(CommandError::internal("Could not compute last year's Ramadan"));
+    };
+
+    let source = db
+        .get_prayer_times_range(&mosque_id, first, last)
+        .await
+        .map_err(CommandError::database)?;
+    if source.len() < last_ramadan.len() {
+        return Err(CommandError::not_found(format!(
+            "Only {} of {} days of Ramadan {} are cached for {}",
+            source.len(),
+            last_ramadan.len(),
+            year - 1,
+            mosque_id
+        )));
+    }
+
+    let copies = copy_schedule_days(&source, &targets, clock::now());
+    db.save_prayer_times_bulk(&copies).await.map_err(CommandError::database)?;
+
+    log::info!("Admin copied Ramadan {} onto Ramadan {} for {}", year - 1, year, mosque_id);
+    Ok(copies)
+}
+
+/// Write a mosque's cached timetable to an iCal or PDF file for distribution
+/// Dates format: "YYYY-MM-DD", inclusive. Returns the path written.
+#[tauri::command]
+pub async fn export_schedule_file(
+    mosque_id: String,
+    from: String,
+    to: String,
+    format: ScheduleExportFormat,
+    path: String,
+    db: State<'_, Database>,
+) -> CommandResult<String> {
+    let parse = |d: &str| {
+        NaiveDate::parse_from_str(d, "%Y-%m-%d")
+            .map_err(|e| CommandError::invalid_input(format!("Invalid date format. Use YYYY-MM-DD: {}", e)))
+    };
+    let (from, to) = (parse(&from)?, parse(&to)?);
+    if to < from || (to - from).num_days() >= MAX_EDIT_DAYS {
+        return Err(CommandError::invalid_input(format!(
+            "Export between 1 and {} days at a time",
+            MAX_EDIT_DAYS
+        )));
+    }
+
+    let days = db
+        .get_prayer_times_range(&mosque_id, from, to)
+        .await
+        .map_err(CommandError::database)?;
+    if days.is_empty() {
+        return Err(CommandError::not_found(format!("No schedule cached for {} in that range", mosque_id)));
+    }
+
+    let name = match db.get_mosque(&mosque_id).await.map_err(CommandError::database)? {
+        Some(mosque) => mosque.name,
+        None => mosque_id.clone(),
+    };
+    let title = format!("{} {} to {}", name, from, to);
+
+    let path = if path.ends_with(&format!(".{}", format.extension())) {
+        path
+    } else {
+        format!("{}.{}", path, format.extension())
+    };
+    std::fs::write(&path, export_schedule(format, &title, &days, clock::now()))
+        .map_err(|e| CommandError::internal(format!("Failed to write {}: {}", path, e)))?;
+
+    log::info!("Exported {} day(s) of {} to {}", days.len(), mosque_id, path);
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pin_matches() {
+        assert!(pin_matches("1234", "1234"));
+        assert!(!pin_matches("1234", "1235"));
+        assert!(!pin_matches("1234", "12345"));
+    }
+}
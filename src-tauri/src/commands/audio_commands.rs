@@ -0,0 +1,195 @@
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+use chrono::NaiveDate;
+use tauri::State;
+use tokio::sync::{Mutex, RwLock};
+
+use crate::commands::error::{CommandError, CommandResult};
+use crate::commands::prayer_commands::{load_availability, mosque_or_selected, user_engine_config};
+use crate::commands::reminder_commands::notification_config;
+use crate::db::Database;
+use crate::models::*;
+use crate::services::{self, clock, ticker, PlaybackOptions, PrayerEngine};
+
+const ADHAN_SOUND_KEY: &str = "adhan_sound";
+
+/// An adhan is played if the ticker sees its time within this many seconds, e.g. after a slow tick
+const ADHAN_PLAY_WINDOW_SECS: i64 = 60;
+
+/// The user's adhan sounds, and the adhans played today
+pub struct AudioState {
+    sounds: RwLock<Vec<AdhanSound>>,
+    played: Mutex<HashSet<(NaiveDate, PrayerName)>>,
+}
+
+impl AudioState {
+    /// Sounds found in the user's sounds folder at startup
+    pub fn new() -> Self {
+        let sounds = scan_sounds();
+        log::info!("Found {} adhan sound(s)", sounds.len());
+
+        Self {
+            sounds: RwLock::new(sounds),
+            played: Mutex::new(HashSet::new()),
+        }
+    }
+
+    async fn rescan(&self) -> Vec<AdhanSound> {
+        let sounds = scan_sounds();
+        *self.sounds.write().await = sounds.clone();
+        sounds
+    }
+
+    async fn find(&self, id: &str) -> Option<AdhanSound> {
+        self.sounds.read().await.iter().find(|s| s.id == id).cloned()
+    }
+
+    /// Play the adhan of a prayer whose time just came in at the selected mosque, once per prayer and day
+    /// Follows the notification profile in effect, so a silent profile plays nothing
+    pub async fn tick(&self, db: &Database) {
+        let config = load_adhan_sound_config(db).await;
+        if !config.enabled {
+            return;
+        }
+        if matches!(db.get_setting("notification_enabled").await, Ok(Some(v)) if v == "false") {
+            return;
+        }
+        let Ok(mosque_id) = mosque_or_selected(db, None).await else {
+            return;
+        };
+        let today = clock::today();
+        let Some(times) = db.get_prayer_times(&mosque_id, today).await.ok().flatten() else {
+            return;
+        };
+
+        let now = ticker::countdown_now();
+        let engine = PrayerEngine::new(user_engine_config(db).await)
+            .with_availability(load_availability(db, &mosque_id, &times).await);
+        let due = engine.get_all_countdowns(&times, now).into_iter().find(|c| {
+            !c.not_held && (0..ADHAN_PLAY_WINDOW_SECS).contains(&(now - c.adhan_time).num_seconds())
+        });
+        let Some(prayer) = due.map(|c| c.prayer_name) else {
+            return;
+        };
+
+        {
+            let mut played = self.played.lock().await;
+            played.retain(|(date, _)| *date == today);
+            if !played.insert((today, prayer)) {
+                return;
+            }
+        }
+
+        let notifications = notification_config(db).await;
+        if !notifications.notifies(prayer) || !notifications.play_sound {
+            return;
+        }
+        let Some(id) = config.sound_for(prayer) else {
+            return;
+        };
+        match self.find(id).await {
+            Some(sound) => {
+                if let Err(e) = play_sound(&sound, playback_options(&config)).await {
+                    log::warn!("Failed to play {} adhan '{}': {}", prayer, sound.name, e);
+                }
+            }
+            None => log::warn!("Adhan sound {} for {} is gone", id, prayer),
+        }
+    }
+}
+
+impl Default for AudioState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn scan_sounds() -> Vec<AdhanSound> {
+    services::user_sounds_dir()
+        .map(|dir| services::scan_user_sounds(&dir))
+        .unwrap_or_default()
+}
+
+/// The sound files in the user's sounds folder
+/// The folder is scanned at startup; `rescan` picks up files added since
+#[tauri::command]
+pub async fn list_adhan_sounds(rescan: Option<bool>, audio: State<'_, AudioState>) -> CommandResult<Vec<AdhanSound>> {
+    if rescan.unwrap_or(false) {
+        return Ok(audio.rescan().await);
+    }
+    Ok(audio.sounds.read().await.clone())
+}
+
+#[tauri::command]
+pub async fn get_adhan_sound_config(db: State<'_, Database>) -> CommandResult<AdhanSoundConfig> {
+    Ok(load_adhan_sound_config(&db).await)
+}
+
+/// Save the adhan played per prayer; rejected when it names a sound that isn't installed
+#[tauri::command]
+pub async fn set_adhan_sound_config(
+    config: AdhanSoundConfig,
+    audio: State<'_, AudioState>,
+    db: State<'_, Database>,
+) -> CommandResult<AdhanSoundConfig> {
+    config
+        .validate(&audio.sounds.read().await)
+        .map_err(CommandError::invalid_input)?;
+
+    let json = serde_json::to_string(&config).map_err(|e| CommandError::internal(e.to_string()))?;
+    db.set_setting(ADHAN_SOUND_KEY, &json)
+        .await
+        .map_err(CommandError::database)?;
+    Ok(config)
+}
+
+/// Play a sound now on the configured device, to try it before assigning it to a prayer
+#[tauri::command]
+pub async fn test_adhan_sound(
+    sound_id: String,
+    audio: State<'_, AudioState>,
+    db: State<'_, Database>,
+) -> CommandResult<AdhanSound> {
+    let sound = audio
+        .find(&sound_id)
+        .await
+        .ok_or_else(|| CommandError::not_found(format!("Unknown adhan sound: {}", sound_id)))?;
+
+    let options = playback_options(&load_adhan_sound_config(&db).await);
+    play_sound(&sound, options)
+        .await
+        .map_err(|e| CommandError::internal(format!("Could not play {}: {}", sound.name, e)))?;
+    Ok(sound)
+}
+
+/// Outputs the adhan can be played on; empty where the platform doesn't let the app choose
+#[tauri::command]
+pub async fn list_audio_output_devices() -> CommandResult<Vec<AudioOutputDevice>> {
+    tokio::task::spawn_blocking(services::list_output_devices)
+        .await
+        .map_err(|e| CommandError::internal(e.to_string()))?
+        .map_err(|e| CommandError::internal(format!("Could not list audio devices: {}", e)))
+}
+
+/// Play off the async runtime, since ducking waits on pactl
+async fn play_sound(sound: &AdhanSound, options: PlaybackOptions) -> std::io::Result<()> {
+    let path = PathBuf::from(&sound.path);
+    tokio::task::spawn_blocking(move || services::play_sound(&path, &options))
+        .await
+        .map_err(std::io::Error::other)?
+}
+
+fn playback_options(config: &AdhanSoundConfig) -> PlaybackOptions {
+    PlaybackOptions {
+        device: config.output_device.clone(),
+        duck_percent: config.duck_percent(),
+    }
+}
+
+async fn load_adhan_sound_config(db: &Database) -> AdhanSoundConfig {
+    match db.get_setting(ADHAN_SOUND_KEY).await {
+        Ok(Some(json)) => serde_json::from_str(&json).unwrap_or_default(),
+        _ => AdhanSoundConfig::default(),
+    }
+}
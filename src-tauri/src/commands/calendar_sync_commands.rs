@@ -0,0 +1,192 @@
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use tauri::State;
+
+use crate::commands::error::{CommandError, CommandResult};
+use crate::commands::prayer_commands::mosque_or_selected;
+use crate::db::{location, Database};
+use crate::models::*;
+use crate::services::{cancel_dropped_events, clock, same_ics_events, schedule_to_ics};
+
+const CALENDAR_SYNC_KEY: &str = "calendar_sync";
+
+#[tauri::command]
+pub async fn get_calendar_sync_status(db: State<'_, Database>) -> CommandResult<CalendarSyncStatus> {
+    Ok(calendar_sync_status(load_calendar_sync_config(&db).await))
+}
+
+/// Save the calendar integration settings and write the calendar right away when it is on
+#[tauri::command]
+pub async fn set_calendar_sync_config(
+    config: CalendarSyncConfig,
+    db: State<'_, Database>,
+) -> CommandResult<CalendarSyncStatus> {
+    if let Some(mosque_id) = &config.mosque_id {
+        db.get_mosque(mosque_id)
+            .await
+            .map_err(CommandError::database)?
+            .ok_or_else(|| CommandError::not_found(format!("Mosque {} not found", mosque_id)))?;
+    }
+    if config.path.as_deref().is_some_and(|p| !p.ends_with(".ics")) {
+        return Err(CommandError::invalid_input("The calendar file must end in .ics"));
+    }
+
+    let json = serde_json::to_string(&config).map_err(|e| CommandError::internal(e.to_string()))?;
+    db.set_setting(CALENDAR_SYNC_KEY, &json)
+        .await
+        .map_err(CommandError::database)?;
+
+    if config.enabled {
+        write_calendar(&db, &config).await?;
+    }
+    Ok(calendar_sync_status(config))
+}
+
+/// Write next week's iqama times to the calendar file now
+#[tauri::command]
+pub async fn sync_calendar_now(db: State<'_, Database>) -> CommandResult<CalendarSyncStatus> {
+    let config = load_calendar_sync_config(&db).await;
+    if !config.enabled {
+        return Err(CommandError::invalid_input("Calendar sync is turned off"));
+    }
+    write_calendar(&db, &config).await?;
+    Ok(calendar_sync_status(config))
+}
+
+/// Rewrite the calendar after a schedule refresh; does nothing when the integration is off
+pub async fn sync_calendar(db: &Database) -> CommandResult<()> {
+    let config = load_calendar_sync_config(db).await;
+    if config.enabled {
+        write_calendar(db, &config).await?;
+    }
+    Ok(())
+}
+
+/// Write the .ics file when its events changed, and only then hand it to the calendar app if asked to
+async fn write_calendar(db: &Database, config: &CalendarSyncConfig) -> CommandResult<()> {
+    let path = calendar_file(config).ok_or_else(|| CommandError::internal("No app directory for the calendar file"))?;
+    let mosque_id = mosque_or_selected(db, config.mosque_id.clone()).await?;
+
+    let today = clock::today();
+    let days = db
+        .get_prayer_times_range(&mosque_id, today, today + chrono::Duration::days(CALENDAR_SYNC_DAYS - 1))
+        .await
+        .map_err(CommandError::database)?;
+    if days.is_empty() {
+        return Err(CommandError::not_found(format!("No schedule cached for {}", mosque_id)));
+    }
+
+    let name = match db.get_mosque(&mosque_id).await {
+        Ok(Some(mosque)) => mosque.name,
+        _ => days[0].mosque_name.clone().unwrap_or_else(|| mosque_id.clone()),
+    };
+    let now = clock::now();
+    let mut ics = schedule_to_ics(&name, &days, now);
+
+    if let Ok(previous) = std::fs::read_to_string(&path) {
+        // Upcoming events the schedule dropped are cancelled, or an import would leave them behind
+        ics = cancel_dropped_events(&previous, &ics, now);
+        // Unchanged events are left alone so calendar apps don't re-import them
+        if same_ics_events(&previous, &ics) {
+            return Ok(());
+        }
+    }
+
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| CommandError::internal(e.to_string()))?;
+    }
+    std::fs::write(&path, ics)
+        .map_err(|e| CommandError::internal(format!("Cannot write {}: {}", path.display(), e)))?;
+    log::info!("Wrote {} days of iqama times to {}", days.len(), path.display());
+
+    if config.auto_import {
+        open_in_default_app(&path);
+    }
+    Ok(())
+}
+
+fn calendar_file(config: &CalendarSyncConfig) -> Option<PathBuf> {
+    match &config.path {
+        Some(path) => Some(PathBuf::from(path)),
+        None => location::app_dir().map(|dir| dir.join(CALENDAR_SYNC_FILE)),
+    }
+}
+
+fn calendar_sync_status(config: CalendarSyncConfig) -> CalendarSyncStatus {
+    let path = calendar_file(&config);
+    let last_synced = path
+        .as_ref()
+        .and_then(|p| std::fs::metadata(p).ok())
+        .and_then(|m| m.modified().ok())
+        .map(DateTime::<Utc>::from);
+
+    CalendarSyncStatus {
+        file_path: path.map(|p| p.to_string_lossy().to_string()),
+        last_synced,
+        config,
+    }
+}
+
+/// Calendar apps import an .ics file opened with them, updating events with the same UID
+fn open_in_default_app(path: &Path) {
+    let result = if cfg!(target_os = "macos") {
+        std::process::Command::new("open").arg(path).spawn()
+    } else if cfg!(target_os = "windows") {
+        std::process::Command::new("cmd").arg("/C").arg("start").arg("").arg(path).spawn()
+    } else {
+        std::process::Command::new("xdg-open").arg(path).spawn()
+    };
+    if let Err(e) = result {
+        log::warn!("Failed to open {} in the calendar app: {}", path.display(), e);
+    }
+}
+
+async fn load_calendar_sync_config(db: &Database) -> CalendarSyncConfig {
+    match db.get_setting(CALENDAR_SYNC_KEY).await {
+        Ok(Some(json)) => serde_json::from_str(&json).unwrap_or_default(),
+        _ => CalendarSyncConfig::default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::migrations::create_database;
+    use crate::providers::MockProvider;
+
+    #[tokio::test]
+    async fn test_write_calendar_skips_unchanged_events() {
+        let db = Database::new(create_database(":memory:").await.unwrap());
+        db.save_mosque(&Mosque::new("mock-calendar".to_string(), "Calendar Mosque".to_string()))
+            .await
+            .unwrap();
+        let today = clock::today();
+        let days: Vec<_> = (0..CALENDAR_SYNC_DAYS)
+            .map(|d| MockProvider::new().schedule("mock-calendar", today + chrono::Duration::days(d), clock::now()))
+            .collect();
+        db.save_prayer_times_bulk(&days).await.unwrap();
+
+        let path = std::env::temp_dir().join(format!("iqamah-calendar-test-{}.ics", std::process::id()));
+        let config = CalendarSyncConfig {
+            enabled: true,
+            mosque_id: Some("mock-calendar".to_string()),
+            path: Some(path.to_string_lossy().to_string()),
+            auto_import: false,
+        };
+
+        write_calendar(&db, &config).await.unwrap();
+        let first = std::fs::read_to_string(&path).unwrap();
+        assert!(first.contains("X-WR-CALNAME:Calendar Mosque"));
+        assert!(first.matches("BEGIN:VEVENT").count() >= 5 * CALENDAR_SYNC_DAYS as usize);
+
+        // Same events under an older stamp: the file is left as it was
+        let stamp = first.lines().find(|l| l.starts_with("DTSTAMP:")).unwrap();
+        let older = first.replace(stamp, "DTSTAMP:20200101T000000Z");
+        std::fs::write(&path, &older).unwrap();
+        write_calendar(&db, &config).await.unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), older);
+
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,172 @@
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use tauri::State;
+
+use crate::commands::error::{CommandError, CommandResult};
+use crate::commands::settings_commands::load_provider_config;
+use crate::db::Database;
+use crate::models::*;
+use crate::providers::{CommunityWrapperProvider, PrayerDataProvider};
+use crate::services::{clock, learn_iqama_delays, IQAMA_LEARNING_DAYS};
+
+/// Reports older than this no longer describe how busy a prayer is
+const CROWD_HISTORY_WEEKS: i64 = 12;
+
+/// Reports further than this from the published iqama are for another prayer or a typo
+const MAX_REPORTED_DELAY_MINUTES: i64 = 60;
+
+/// Record how full the mosque was for a prayer (date "YYYY-MM-DD", defaults to today)
+/// With `share`, the report is also sent to the community wrapper API when configured
+#[tauri::command]
+pub async fn report_crowd_level(
+    mosque_id: String,
+    prayer_name: String,
+    level: CrowdLevel,
+    date: Option<String>,
+    share: Option<bool>,
+    db: State<'_, Database>,
+) -> CommandResult<()> {
+    let date = match date {
+        Some(d) => NaiveDate::parse_from_str(&d, "%Y-%m-%d")
+            .map_err(|e| CommandError::invalid_input(format!("Invalid date format. Use YYYY-MM-DD: {}", e)))?,
+        None => clock::today(),
+    };
+
+    let report = CrowdReport {
+        mosque_id,
+        prayer: prayer_name.parse().map_err(CommandError::invalid_input)?,
+        date,
+        level,
+        reported_at: clock::now(),
+    };
+
+    db.save_crowd_report(&report)
+        .await
+        .map_err(CommandError::database)?;
+
+    if share.unwrap_or(false) {
+        share_crowd_report(&db, &report).await;
+    }
+
+    Ok(())
+}
+
+/// Sharing is best effort: the local report is what the app relies on
+async fn share_crowd_report(db: &Database, report: &CrowdReport) {
+    let Ok(Some(config)) = load_provider_config(db, PROVIDER_COMMUNITY_WRAPPER).await else {
+        log::debug!("Crowd report not shared: community wrapper is not configured");
+        return;
+    };
+
+    let mut provider = CommunityWrapperProvider::new();
+    let result = match provider.initialize(config.settings).await {
+        Ok(()) => provider.submit_crowd_report(report).await,
+        Err(e) => Err(e),
+    };
+
+    if let Err(e) = result {
+        log::warn!("Failed to share crowd report for {}: {}", report.mosque_id, e);
+    }
+}
+
+/// Average crowd level per prayer over the last weeks
+#[tauri::command]
+pub async fn get_crowd_summary(
+    mosque_id: String,
+    db: State<'_, Database>,
+) -> CommandResult<Vec<CrowdSummary>> {
+    db.get_crowd_summary(&mosque_id, crowd_history_start())
+        .await
+        .map_err(CommandError::database)
+}
+
+/// Average crowd score for one prayer, used to pad travel predictions
+pub(crate) async fn crowd_level(db: &Database, mosque_id: &str, prayer: PrayerName) -> Option<f64> {
+    db.get_crowd_summary(mosque_id, crowd_history_start())
+        .await
+        .ok()?
+        .into_iter()
+        .find(|s| s.prayer == prayer)
+        .and_then(|s| s.average_level)
+}
+
+fn crowd_history_start() -> NaiveDate {
+    clock::today() - Duration::weeks(CROWD_HISTORY_WEEKS)
+}
+
+/// Record when a prayer actually started (`actual_iqama` as RFC3339, date "YYYY-MM-DD", defaults to today)
+/// Mosques that consistently start late get their countdowns and rakah estimates shifted
+#[tauri::command]
+pub async fn report_actual_iqama(
+    mosque_id: String,
+    prayer_name: String,
+    actual_iqama: String,
+    date: Option<String>,
+    db: State<'_, Database>,
+) -> CommandResult<IqamaReport> {
+    let date = match date {
+        Some(d) => NaiveDate::parse_from_str(&d, "%Y-%m-%d")
+            .map_err(|e| CommandError::invalid_input(format!("Invalid date format. Use YYYY-MM-DD: {}", e)))?,
+        None => clock::today(),
+    };
+    let prayer: PrayerName = prayer_name.parse().map_err(CommandError::invalid_input)?;
+    let actual_iqama = DateTime::parse_from_rfc3339(&actual_iqama)
+        .map_err(|e| CommandError::invalid_input(format!("Invalid time: {}", e)))?
+        .with_timezone(&Utc);
+
+    let scheduled_iqama = db
+        .get_prayer_times(&mosque_id, date)
+        .await
+        .map_err(CommandError::database)?
+        .and_then(|schedule| schedule.get_prayer_by_name(prayer)?.iqama)
+        .ok_or_else(|| CommandError::not_found(format!("No iqama time for {} on {}", prayer, date)))?;
+
+    if (actual_iqama - scheduled_iqama).num_minutes().abs() > MAX_REPORTED_DELAY_MINUTES {
+        return Err(CommandError::invalid_input(format!(
+            "Reported time is more than {} minutes from the iqama",
+            MAX_REPORTED_DELAY_MINUTES
+        )));
+    }
+
+    let report = IqamaReport {
+        mosque_id,
+        prayer,
+        date,
+        scheduled_iqama,
+        actual_iqama,
+        reported_at: clock::now(),
+    };
+    db.save_iqama_report(&report)
+        .await
+        .map_err(CommandError::database)?;
+
+    Ok(report)
+}
+
+/// How late the mosque usually starts each reported prayer
+#[tauri::command]
+pub async fn get_iqama_delays(
+    mosque_id: String,
+    db: State<'_, Database>,
+) -> CommandResult<Vec<IqamaDelay>> {
+    let reports = db
+        .get_iqama_report_delays(&mosque_id, iqama_history_start())
+        .await
+        .map_err(CommandError::database)?;
+
+    Ok(learn_iqama_delays(&reports))
+}
+
+/// What iqama reports taught about a mosque, empty when there are none
+pub(crate) async fn learned_delays(db: &Database, mosque_id: &str) -> Vec<IqamaDelay> {
+    match db.get_iqama_report_delays(mosque_id, iqama_history_start()).await {
+        Ok(reports) => learn_iqama_delays(&reports),
+        Err(e) => {
+            log::warn!("Failed to load iqama reports for {}: {}", mosque_id, e);
+            Vec::new()
+        }
+    }
+}
+
+fn iqama_history_start() -> NaiveDate {
+    clock::today() - Duration::days(IQAMA_LEARNING_DAYS)
+}
@@ -0,0 +1,35 @@
+use chrono::{DateTime, Utc};
+
+use crate::commands::error::{CommandError, CommandResult};
+use crate::services::{clock, ticker};
+
+/// Override the clock used by the engine and commands (debug builds only)
+/// Pass an RFC 3339 datetime (e.g. "2026-02-05T12:58:00+01:00"), or null to return to real time
+/// Returns the clock's current time after the change
+#[tauri::command]
+pub fn set_simulated_now(datetime: Option<String>) -> CommandResult<DateTime<Utc>> {
+    if !cfg!(debug_assertions) {
+        return Err(CommandError::invalid_input(
+            "Time simulation is only available in debug builds",
+        ));
+    }
+
+    let start = datetime
+        .map(|d| {
+            DateTime::parse_from_rfc3339(&d)
+                .map(|d| d.with_timezone(&Utc))
+                .map_err(|e| CommandError::invalid_input(format!("Invalid datetime {}: {}", d, e)))
+        })
+        .transpose()?;
+
+    clock::set_simulated_now(start);
+    // Countdowns follow the new time at once, rather than the ticker reporting it as a clock jump
+    ticker::reanchor();
+
+    match start {
+        Some(start) => log::warn!("Clock simulation active, now = {}", start),
+        None => log::info!("Clock simulation disabled"),
+    }
+
+    Ok(clock::now())
+}
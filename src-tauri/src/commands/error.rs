@@ -0,0 +1,178 @@
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use crate::providers::ProviderError;
+
+/// Category of a command failure, so the UI can react without parsing messages
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum CommandErrorKind {
+    /// Network unreachable or request timed out
+    Offline,
+    /// Requested mosque, prayer or data does not exist
+    NotFound,
+    /// Only outdated cached data is available
+    StaleCache,
+    /// Bad argument from the caller (date format, unknown provider...)
+    InvalidInput,
+    /// Provider is missing or misconfigured
+    Configuration,
+    /// Upstream provider returned an error or unreadable data
+    Provider,
+    /// Local database failure
+    Database,
+    /// Request was cancelled in favour of a newer one
+    Cancelled,
+    /// Command called too often; wait `retry_after_secs`
+    RateLimited,
+    Internal,
+}
+
+/// Error returned by every Tauri command
+/// Serialized as `{ kind, message, retryable, provider, retry_after_secs }`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS, thiserror::Error)]
+#[error("{message}")]
+#[ts(export, optional_fields = nullable)]
+pub struct CommandError {
+    pub kind: CommandErrorKind,
+    pub message: String,
+    pub retryable: bool,
+    pub provider: Option<String>,
+    /// Seconds the provider asked us to wait before retrying
+    pub retry_after_secs: Option<u64>,
+}
+
+pub type CommandResult<T> = Result<T, CommandError>;
+
+impl CommandError {
+    pub fn new(kind: CommandErrorKind, message: impl Into<String>) -> Self {
+        let retryable = matches!(
+            kind,
+            CommandErrorKind::Offline | CommandErrorKind::StaleCache | CommandErrorKind::Database
+        );
+
+        Self {
+            kind,
+            message: message.into(),
+            retryable,
+            provider: None,
+            retry_after_secs: None,
+        }
+    }
+
+    pub fn not_found(message: impl Into<String>) -> Self {
+        Self::new(CommandErrorKind::NotFound, message)
+    }
+
+    pub fn invalid_input(message: impl Into<String>) -> Self {
+        Self::new(CommandErrorKind::InvalidInput, message)
+    }
+
+    pub fn internal(message: impl Into<String>) -> Self {
+        Self::new(CommandErrorKind::Internal, message)
+    }
+
+    pub fn cancelled(message: impl Into<String>) -> Self {
+        Self::new(CommandErrorKind::Cancelled, message)
+    }
+
+    pub fn rate_limited(message: impl Into<String>, retry_after_secs: u64) -> Self {
+        let mut error = Self::new(CommandErrorKind::RateLimited, message).retryable(true);
+        error.retry_after_secs = Some(retry_after_secs);
+        error
+    }
+
+    /// Wrap a database error; usable directly in `map_err`
+    pub fn database(e: impl std::fmt::Display) -> Self {
+        Self::new(CommandErrorKind::Database, format!("Database error: {}", e))
+    }
+
+    /// Wrap a provider error and remember which provider raised it
+    pub fn from_provider(provider_id: &str, e: ProviderError) -> Self {
+        Self::from(e).with_provider(provider_id)
+    }
+
+    pub fn with_provider(mut self, provider_id: &str) -> Self {
+        self.provider = Some(provider_id.to_string());
+        self
+    }
+
+    pub fn retryable(mut self, retryable: bool) -> Self {
+        self.retryable = retryable;
+        self
+    }
+}
+
+impl From<ProviderError> for CommandError {
+    fn from(e: ProviderError) -> Self {
+        let message = e.to_string();
+
+        match e {
+            ProviderError::Network(_) => Self::new(CommandErrorKind::Offline, message),
+            ProviderError::NotFound(_) => Self::not_found(message),
+            ProviderError::InvalidConfig(_) => {
+                Self::new(CommandErrorKind::Configuration, message)
+            }
+            ProviderError::Unauthorized(_) | ProviderError::Forbidden(_) => {
+                Self::new(CommandErrorKind::Configuration, message)
+            }
+            ProviderError::RateLimited { retry_after_secs, .. } => {
+                let mut error = Self::new(CommandErrorKind::Provider, message).retryable(true);
+                error.retry_after_secs = retry_after_secs;
+                error
+            }
+            ProviderError::Server { status_code, .. } => {
+                Self::new(CommandErrorKind::Provider, message)
+                    .retryable(status_code >= 500 || status_code == 429)
+            }
+            ProviderError::Parse(_) | ProviderError::Other(_) => {
+                Self::new(CommandErrorKind::Provider, message)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_provider_error_mapping() {
+        let offline = CommandError::from_provider("mawaqit", ProviderError::Network("timeout".into()));
+        assert_eq!(offline.kind, CommandErrorKind::Offline);
+        assert!(offline.retryable);
+        assert_eq!(offline.provider.as_deref(), Some("mawaqit"));
+
+        let missing = CommandError::from(ProviderError::NotFound("mosque".into()));
+        assert_eq!(missing.kind, CommandErrorKind::NotFound);
+        assert!(!missing.retryable);
+
+        let server = CommandError::from(ProviderError::Server {
+            status_code: 503,
+            message: "unavailable".into(),
+        });
+        assert_eq!(server.kind, CommandErrorKind::Provider);
+        assert!(server.retryable);
+
+        let limited = CommandError::from(ProviderError::RateLimited {
+            retry_after_secs: Some(60),
+            message: "Failed to search mosques".into(),
+        });
+        assert!(limited.retryable);
+        assert_eq!(limited.retry_after_secs, Some(60));
+
+        let unauthorized = CommandError::from(ProviderError::Unauthorized("token".into()));
+        assert_eq!(unauthorized.kind, CommandErrorKind::Configuration);
+    }
+
+    #[test]
+    fn test_serialized_shape() {
+        let json = serde_json::to_value(CommandError::not_found("No prayer times found")).unwrap();
+
+        assert_eq!(json["kind"], "not_found");
+        assert_eq!(json["message"], "No prayer times found");
+        assert_eq!(json["retryable"], false);
+        assert!(json["provider"].is_null());
+    }
+}
@@ -0,0 +1,132 @@
+use chrono::Duration;
+use tauri::{AppHandle, State};
+
+use crate::commands::error::{CommandError, CommandResult};
+use crate::commands::prayer_commands::user_formatter;
+use crate::commands::reminder_commands::show_notification;
+use crate::db::Database;
+use crate::models::*;
+use crate::providers::{fetch_ical_events, is_mock_mosque, MawaqitProvider, PrayerDataProvider};
+use crate::services::clock;
+
+/// Upcoming events for a mosque from Mawaqit announcements and its iCal feed
+/// Falls back to the stored events when the sources can't be reached
+#[tauri::command]
+pub async fn get_mosque_events(
+    mosque_id: String,
+    refresh: Option<bool>,
+    db: State<'_, Database>,
+) -> CommandResult<Vec<MosqueEvent>> {
+    if refresh.unwrap_or(true) && !is_mock_mosque(&mosque_id) {
+        refresh_mosque_events(&db, &mosque_id).await;
+    }
+
+    db.get_events(&mosque_id, clock::now())
+        .await
+        .map_err(CommandError::database)
+}
+
+async fn refresh_mosque_events(db: &Database, mosque_id: &str) {
+    match MawaqitProvider::new().get_events(mosque_id).await {
+        Ok(events) => {
+            if let Err(e) = db.save_events(mosque_id, PROVIDER_MAWAQIT, &events).await {
+                log::warn!("Failed to store Mawaqit events for {}: {}", mosque_id, e);
+            }
+        }
+        Err(e) => log::debug!("No Mawaqit events for {}: {}", mosque_id, e),
+    }
+
+    let feed = db.get_setting(&events_feed_key(mosque_id)).await.ok().flatten();
+    if let Some(url) = feed.filter(|url| !url.is_empty()) {
+        match fetch_ical_events(&url, mosque_id).await {
+            Ok(events) => {
+                if let Err(e) = db.save_events(mosque_id, "ical", &events).await {
+                    log::warn!("Failed to store feed events for {}: {}", mosque_id, e);
+                }
+            }
+            Err(e) => log::warn!("Could not load events feed for {}: {}", mosque_id, e),
+        }
+    }
+}
+
+fn events_feed_key(mosque_id: &str) -> String {
+    format!("events_feed:{}", mosque_id)
+}
+
+/// Set the iCal feed URL for a mosque's events, or clear it with `null`
+#[tauri::command]
+pub async fn set_mosque_events_feed(
+    mosque_id: String,
+    url: Option<String>,
+    db: State<'_, Database>,
+) -> CommandResult<()> {
+    let url = url.map(|u| u.trim().to_string()).unwrap_or_default();
+    let is_http = url.starts_with("https://") || url.starts_with("http://");
+    if !url.is_empty() && !is_http {
+        return Err(CommandError::invalid_input("Events feed must be an http(s) URL"));
+    }
+
+    db.set_setting(&events_feed_key(&mosque_id), &url)
+        .await
+        .map_err(CommandError::database)
+}
+
+/// Remind about an event `minutes_before` its start, or clear the reminder with `null`
+#[tauri::command]
+pub async fn set_event_reminder(
+    event_id: String,
+    minutes_before: Option<i64>,
+    db: State<'_, Database>,
+) -> CommandResult<()> {
+    if minutes_before.is_some_and(|m| m < 0) {
+        return Err(CommandError::invalid_input("Reminder must be before the event"));
+    }
+
+    let found = db
+        .set_event_reminder(&event_id, minutes_before)
+        .await
+        .map_err(CommandError::database)?;
+
+    if found {
+        Ok(())
+    } else {
+        Err(CommandError::not_found(format!("Event {} not found", event_id)))
+    }
+}
+
+/// Events whose reminder is due within the next `within_minutes` (default 1) and hasn't been shown yet
+#[tauri::command]
+pub async fn get_due_event_reminders(
+    within_minutes: Option<i64>,
+    db: State<'_, Database>,
+) -> CommandResult<Vec<MosqueEvent>> {
+    let now = clock::now();
+    let window = Duration::minutes(within_minutes.unwrap_or(1).max(1));
+
+    db.get_pending_event_reminders(now, now + window)
+        .await
+        .map_err(CommandError::database)
+}
+
+/// Show event reminders that are due and mark them, so each is shown once; called from the ticker
+pub async fn deliver_event_reminders(app: &AppHandle, db: &Database) {
+    let now = clock::now();
+    let due = match db.get_pending_event_reminders(now, now).await {
+        Ok(due) => due,
+        Err(e) => {
+            log::warn!("Failed to load event reminders: {}", e);
+            return;
+        }
+    };
+    if due.is_empty() {
+        return;
+    }
+
+    let formatter = user_formatter(db).await;
+    for event in due {
+        show_notification(app, &event.title, &formatter.format_time(event.starts_at), true);
+        if let Err(e) = db.mark_event_reminded(&event.id, now).await {
+            log::warn!("Failed to mark reminder of event {}: {}", event.id, e);
+        }
+    }
+}
@@ -0,0 +1,124 @@
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+
+use tauri::{AppHandle, Manager, State};
+use tokio::sync::Mutex;
+
+use crate::commands::error::{CommandError, CommandResult};
+use crate::commands::prayer_commands::{mosque_or_selected, user_formatter};
+use crate::commands::reminder_commands::show_notification;
+use crate::commands::server_commands::broadcast_event;
+use crate::db::Database;
+use crate::models::*;
+use crate::services::{clock, FajrAlarm};
+
+const FAJR_ALARM_KEY: &str = "fajr_alarm";
+
+/// From this ring on the window is brought to the front as well
+const FOCUS_WINDOW_LEVEL: u32 = 3;
+
+/// The Fajr wake-up alarm, driven by the background ticker
+pub struct FajrAlarmState {
+    alarm: Mutex<FajrAlarm>,
+}
+
+impl FajrAlarmState {
+    pub fn new() -> Self {
+        Self {
+            alarm: Mutex::new(FajrAlarm::default()),
+        }
+    }
+
+    /// The alarm's next ring when one is due
+    pub async fn tick(&self, db: &Database) -> Option<FajrAlarmRing> {
+        let config = load_fajr_alarm_config(db).await;
+        let today = clock::today();
+        let fajr_time = match mosque_or_selected(db, None).await {
+            Ok(mosque_id) => db.get_prayer_times(&mosque_id, today).await.ok().flatten().map(|t| t.fajr.adhan),
+            Err(_) => None,
+        };
+
+        let mut alarm = self.alarm.lock().await;
+        match fajr_time {
+            Some(fajr_time) => alarm.tick(&config, today, fajr_time, clock::now(), challenge_seed()),
+            None => None,
+        }
+    }
+}
+
+impl Default for FajrAlarmState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Questions must not be guessable from one morning to the next
+fn challenge_seed() -> u64 {
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_i64(clock::now().timestamp_nanos_opt().unwrap_or_default());
+    hasher.finish()
+}
+
+/// Sound the alarm: a notification, more urgent at each ring, and the window at the front once ignored
+pub async fn ring_fajr_alarm(app: &AppHandle, db: &Database, ring: &FajrAlarmRing) {
+    let formatter = user_formatter(db).await;
+    let minutes = (ring.fajr_time - clock::now()).num_minutes();
+    let title = format!(
+        "{} · {}",
+        formatter.language.prayer_name(PrayerName::Fajr),
+        formatter.format_time(ring.fajr_time)
+    );
+    show_notification(app, &title, &formatter.language.fajr_alarm(ring.level, minutes), true);
+
+    broadcast_event(app, "fajr-alarm", ring);
+    if ring.level >= FOCUS_WINDOW_LEVEL {
+        if let Some(window) = app.get_window("main") {
+            let _ = window.show();
+            let _ = window.unminimize();
+            let _ = window.set_focus();
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn get_fajr_alarm_config(db: State<'_, Database>) -> CommandResult<FajrAlarmConfig> {
+    Ok(load_fajr_alarm_config(&db).await)
+}
+
+#[tauri::command]
+pub async fn set_fajr_alarm_config(
+    config: FajrAlarmConfig,
+    db: State<'_, Database>,
+) -> CommandResult<FajrAlarmConfig> {
+    config.validate().map_err(CommandError::invalid_input)?;
+
+    let json = serde_json::to_string(&config).map_err(|e| CommandError::internal(e.to_string()))?;
+    db.set_setting(FAJR_ALARM_KEY, &json)
+        .await
+        .map_err(CommandError::database)?;
+    Ok(config)
+}
+
+#[tauri::command]
+pub async fn get_fajr_alarm_status(alarm: State<'_, FajrAlarmState>) -> CommandResult<FajrAlarmStatus> {
+    Ok(alarm.alarm.lock().await.status(clock::today()))
+}
+
+/// Stop the ringing alarm for today by answering its question
+/// A wrong answer fails and replaces the question with a new one
+#[tauri::command]
+pub async fn dismiss_fajr_alarm(answer: i64, alarm: State<'_, FajrAlarmState>) -> CommandResult<FajrAlarmStatus> {
+    let mut alarm = alarm.alarm.lock().await;
+    let today = clock::today();
+    alarm
+        .dismiss(today, answer, challenge_seed())
+        .map_err(CommandError::invalid_input)?;
+    Ok(alarm.status(today))
+}
+
+async fn load_fajr_alarm_config(db: &Database) -> FajrAlarmConfig {
+    match db.get_setting(FAJR_ALARM_KEY).await {
+        Ok(Some(json)) => serde_json::from_str(&json).unwrap_or_default(),
+        _ => FajrAlarmConfig::default(),
+    }
+}
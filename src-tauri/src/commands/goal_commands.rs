@@ -0,0 +1,189 @@
+use std::time::Duration;
+
+use chrono::{Datelike, Local, NaiveDate, Timelike, Weekday};
+use tauri::State;
+
+use crate::commands::error::{CommandError, CommandResult};
+use crate::commands::prayer_commands::user_formatter;
+use crate::commands::settings_commands::load_goal_summary_enabled;
+use crate::db::Database;
+use crate::models::*;
+use crate::services::{clock, PrayerNotification};
+
+/// How often the background loop checks whether tonight's goal evaluation is due
+pub const GOAL_CHECK_INTERVAL: Duration = Duration::from_secs(30 * 60);
+
+/// Local hour from which the day's goal progress is recorded
+const GOAL_EVALUATION_HOUR: u32 = 21;
+
+const GOALS_EVALUATED_KEY: &str = "goals_evaluated_on";
+
+/// Add a weekly goal, e.g. Fajr tagged jamaah 5 times a week
+/// Without a prayer every daily prayer counts, without a tag every logged prayer does
+#[tauri::command]
+pub async fn create_goal(
+    prayer_name: Option<String>,
+    tag: Option<PrayerLogTag>,
+    target_per_week: u32,
+    profile: Option<String>,
+    db: State<'_, Database>,
+) -> CommandResult<PrayerGoal> {
+    let prayer: Option<PrayerName> = prayer_name
+        .map(|name| name.parse())
+        .transpose()
+        .map_err(CommandError::invalid_input)?;
+
+    let max = PrayerGoal::max_per_week(prayer);
+    if !(1..=max).contains(&target_per_week) {
+        return Err(CommandError::invalid_input(format!(
+            "Goal must be between 1 and {} prayers a week, got {}",
+            max, target_per_week
+        )));
+    }
+
+    let mut goal = PrayerGoal {
+        id: 0,
+        profile: profile.unwrap_or_else(|| DEFAULT_PROFILE.to_string()),
+        prayer,
+        tag,
+        target_per_week,
+        created_at: clock::now(),
+    };
+    goal.id = db.save_prayer_goal(&goal).await.map_err(CommandError::database)?;
+
+    Ok(goal)
+}
+
+/// Remove a goal and its history; returns whether there was one
+#[tauri::command]
+pub async fn delete_goal(id: i64, db: State<'_, Database>) -> CommandResult<bool> {
+    db.delete_prayer_goal(id).await.map_err(CommandError::database)
+}
+
+/// Progress of a profile's goals (the default one if omitted) in the week holding `date`, as of that day
+/// Date format: "YYYY-MM-DD", defaults to today
+#[tauri::command]
+pub async fn get_goals_status(
+    profile: Option<String>,
+    date: Option<String>,
+    db: State<'_, Database>,
+) -> CommandResult<Vec<GoalProgress>> {
+    let date = match date {
+        Some(d) => NaiveDate::parse_from_str(&d, "%Y-%m-%d")
+            .map_err(|e| CommandError::invalid_input(format!("Invalid date format. Use YYYY-MM-DD: {}", e)))?,
+        None => clock::today(),
+    };
+    goals_status(&db, profile.unwrap_or_else(|| DEFAULT_PROFILE.to_string()), date).await
+}
+
+/// Progress of a profile's goals in the week holding `date`
+pub(crate) async fn goals_status(db: &Database, profile: String, date: NaiveDate) -> CommandResult<Vec<GoalProgress>> {
+    let goals = db
+        .get_prayer_goals(Some(&profile))
+        .await
+        .map_err(CommandError::database)?;
+    let entries = week_log(db, Some(profile), goal_week_start(date), date).await?;
+
+    let mut status = Vec::with_capacity(goals.len());
+    for goal in &goals {
+        status.push(goal_progress(db, goal, &entries, date).await?);
+    }
+    Ok(status)
+}
+
+/// Nightly goal evaluation for the background loop
+/// Once a day after `GOAL_EVALUATION_HOUR`, records last week's and this week's progress of every goal;
+/// on Sundays also returns the end-of-week encouragement when the user opted in
+pub async fn evaluate_goals(db: &Database) -> Vec<PrayerNotification> {
+    let now = clock::now();
+    let today = clock::today();
+    if now.with_timezone(&Local).hour() < GOAL_EVALUATION_HOUR {
+        return Vec::new();
+    }
+    if let Ok(Some(evaluated_on)) = db.get_setting(GOALS_EVALUATED_KEY).await {
+        if evaluated_on == today.to_string() {
+            return Vec::new();
+        }
+    }
+
+    let progress = match record_goal_weeks(db, today).await {
+        Ok(progress) => progress,
+        Err(e) => {
+            log::warn!("Failed to evaluate goals: {}", e.message);
+            return Vec::new();
+        }
+    };
+    db.set_setting(GOALS_EVALUATED_KEY, &today.to_string()).await.ok();
+    log::info!("Evaluated {} goal(s) for the week of {}", progress.len(), goal_week_start(today));
+
+    if today.weekday() != Weekday::Sun || !load_goal_summary_enabled(db).await {
+        return Vec::new();
+    }
+    if let Ok(Some(enabled)) = db.get_setting("notification_enabled").await {
+        if enabled == "false" {
+            return Vec::new();
+        }
+    }
+
+    let formatter = user_formatter(db).await;
+    progress
+        .iter()
+        .map(|p| PrayerNotification::goal_summary(&formatter, p, now))
+        .collect()
+}
+
+/// Store every goal's progress for last week (prayers logged late at night) and this week
+async fn record_goal_weeks(db: &Database, today: NaiveDate) -> CommandResult<Vec<GoalProgress>> {
+    let week_start = goal_week_start(today);
+    let last_week_end = week_start - chrono::Duration::days(1);
+    let goals = db.get_prayer_goals(None).await.map_err(CommandError::database)?;
+    let entries = week_log(db, None, goal_week_start(last_week_end), today).await?;
+
+    let mut progress = Vec::with_capacity(goals.len());
+    for goal in &goals {
+        if goal.created_at.date_naive() <= last_week_end {
+            let last_week = goal_progress(db, goal, &entries, last_week_end).await?;
+            db.save_goal_week(&last_week, clock::now())
+                .await
+                .map_err(CommandError::database)?;
+        }
+
+        let this_week = goal_progress(db, goal, &entries, today).await?;
+        db.save_goal_week(&this_week, clock::now())
+            .await
+            .map_err(CommandError::database)?;
+        progress.push(this_week);
+    }
+
+    Ok(progress)
+}
+
+async fn goal_progress(
+    db: &Database,
+    goal: &PrayerGoal,
+    entries: &[PrayerLogEntry],
+    date: NaiveDate,
+) -> CommandResult<GoalProgress> {
+    let streak = db
+        .get_goal_streak(goal.id, goal_week_start(date))
+        .await
+        .map_err(CommandError::database)?;
+
+    Ok(GoalProgress::evaluate(goal, entries, date, streak))
+}
+
+async fn week_log(
+    db: &Database,
+    profile: Option<String>,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> CommandResult<Vec<PrayerLogEntry>> {
+    let query = PrayerLogQuery {
+        profile,
+        from: Some(from),
+        to: Some(to),
+        ..Default::default()
+    };
+
+    db.get_prayer_log(&query).await.map_err(CommandError::database)
+}
@@ -0,0 +1,146 @@
+use std::time::Duration;
+
+use chrono::{Local, NaiveDate, Timelike};
+use tauri::State;
+
+use crate::commands::error::{CommandError, CommandResult};
+use crate::commands::prayer_commands::{evaluation_time, mosque_or_selected, user_engine_config, user_formatter};
+use crate::commands::settings_commands::load_islamic_event_reminders_enabled;
+use crate::db::Database;
+use crate::models::*;
+use crate::providers::{is_mock_mosque, MawaqitProvider};
+use crate::services::{clock, HijriService, PrayerEngine, PrayerNotification};
+
+/// How often the background loop checks for special days starting tonight
+pub const ISLAMIC_EVENT_CHECK_INTERVAL: Duration = Duration::from_secs(30 * 60);
+
+/// Local hour from which tomorrow's special days are announced; the night begins around Maghrib
+const ISLAMIC_EVENT_REMINDER_HOUR: u32 = 17;
+
+const ISLAMIC_EVENTS_REMINDED_KEY: &str = "islamic_events_reminded_on";
+
+/// Hijri date for a day, following the mosque's announced adjustment when available
+/// Date format: "YYYY-MM-DD", defaults to today
+#[tauri::command]
+pub async fn get_hijri_date(
+    mosque_id: Option<String>,
+    date: Option<String>,
+    db: State<'_, Database>,
+) -> CommandResult<HijriDay> {
+    let target_date = match date {
+        Some(d) => NaiveDate::parse_from_str(&d, "%Y-%m-%d")
+            .map_err(|e| CommandError::invalid_input(format!("Invalid date format. Use YYYY-MM-DD: {}", e)))?,
+        None => clock::today(),
+    };
+
+    let info = match mosque_id {
+        Some(id) if !is_mock_mosque(&id) => load_mosque_hijri_info(&db, &id).await,
+        _ => None,
+    };
+
+    Ok(HijriService::new(info).day(target_date))
+}
+
+/// Special days coming up (Ramadan, Laylat al-Qadr candidates, Eids, Dhul Hijjah, Arafah, Ashura)
+/// Follows the mosque's announced Hijri adjustment when given; looks `days` ahead (60 by default)
+#[tauri::command]
+pub async fn get_islamic_events(
+    mosque_id: Option<String>,
+    days: Option<u32>,
+    db: State<'_, Database>,
+) -> CommandResult<Vec<IslamicEvent>> {
+    let days = days.unwrap_or(60);
+    if !(1..=400).contains(&days) {
+        return Err(CommandError::invalid_input("Look 1 to 400 days ahead"));
+    }
+
+    let info = match mosque_id {
+        Some(id) if !is_mock_mosque(&id) => load_mosque_hijri_info(&db, &id).await,
+        _ => None,
+    };
+
+    Ok(HijriService::new(info).upcoming_events(clock::today(), days as i64))
+}
+
+/// Evening reminders for the background loop
+/// Once a day after `ISLAMIC_EVENT_REMINDER_HOUR`, announces special days starting tomorrow when the user opted in
+pub async fn due_islamic_event_reminders(db: &Database) -> Vec<PrayerNotification> {
+    let now = clock::now();
+    let today = clock::today();
+    if now.with_timezone(&Local).hour() < ISLAMIC_EVENT_REMINDER_HOUR {
+        return Vec::new();
+    }
+    if !load_islamic_event_reminders_enabled(db).await {
+        return Vec::new();
+    }
+    if matches!(db.get_setting("notification_enabled").await, Ok(Some(v)) if v == "false") {
+        return Vec::new();
+    }
+    if matches!(db.get_setting(ISLAMIC_EVENTS_REMINDED_KEY).await, Ok(Some(on)) if on == today.to_string()) {
+        return Vec::new();
+    }
+
+    let info = match mosque_or_selected(db, None).await {
+        Ok(id) if !is_mock_mosque(&id) => load_mosque_hijri_info(db, &id).await,
+        _ => None,
+    };
+    let tomorrow = today + chrono::Duration::days(1);
+    let events = HijriService::new(info).upcoming_events(tomorrow, 1);
+    db.set_setting(ISLAMIC_EVENTS_REMINDED_KEY, &today.to_string()).await.ok();
+
+    let formatter = user_formatter(db).await;
+    events
+        .iter()
+        .map(|event| PrayerNotification::islamic_event(&formatter, event, now))
+        .collect()
+}
+
+/// Whether the user is fasting today, with time since suhoor and time to iftar
+/// Only active on Ramadan days per the mosque's Hijri calendar
+#[tauri::command]
+pub async fn get_fasting_status(
+    mosque_id: String,
+    at: Option<String>,
+    utc_offset_minutes: Option<i32>,
+    db: State<'_, Database>,
+) -> CommandResult<FastingStatus> {
+    let (now, today) = evaluation_time(at, utc_offset_minutes, clock::now())?;
+
+    let prayer_times = db
+        .get_prayer_times(&mosque_id, today)
+        .await
+        .map_err(CommandError::database)?
+        .ok_or_else(|| CommandError::not_found("No prayer times found"))?;
+
+    let info = if is_mock_mosque(&mosque_id) {
+        None
+    } else {
+        load_mosque_hijri_info(&db, &mosque_id).await
+    };
+    let is_ramadan = HijriService::new(info).day(today).is_ramadan;
+
+    let engine = PrayerEngine::new(user_engine_config(&db).await);
+    Ok(engine.fasting_status(&prayer_times, is_ramadan, now))
+}
+
+/// Fetch the mosque's Hijri info from Mawaqit, falling back to the last known value offline
+pub(crate) async fn load_mosque_hijri_info(db: &Database, mosque_id: &str) -> Option<MosqueHijriInfo> {
+    let key = format!("hijri_info:{}", mosque_id);
+
+    match MawaqitProvider::new().get_hijri_info(mosque_id).await {
+        Ok(info) => {
+            if let Ok(json) = serde_json::to_string(&info) {
+                let _ = db.set_setting(&key, &json).await;
+            }
+            Some(info)
+        }
+        Err(e) => {
+            log::debug!("Using cached hijri info for {}: {}", mosque_id, e);
+            db.get_setting(&key)
+                .await
+                .ok()
+                .flatten()
+                .and_then(|json| serde_json::from_str(&json).ok())
+        }
+    }
+}
@@ -0,0 +1,167 @@
+use tauri::{AppHandle, GlobalShortcutManager, Manager, State};
+
+use crate::commands::adhkar_commands::{attend_prayer, AdhkarState};
+use crate::commands::error::{CommandError, CommandResult};
+use crate::commands::prayer_commands::{
+    load_availability, mosque_or_selected, user_engine_config, user_formatter, with_start_timing,
+};
+use crate::commands::reminder_commands::{show_notification, ReminderState};
+use crate::commands::server_commands::broadcast_event;
+use crate::db::Database;
+use crate::models::*;
+use crate::services::{clock, ticker, PrayerEngine, DEFAULT_SNOOZE_MINUTES};
+
+const HOTKEYS_KEY: &str = "hotkeys";
+
+#[tauri::command]
+pub async fn get_hotkeys(db: State<'_, Database>) -> CommandResult<HotkeyBindings> {
+    Ok(load_hotkeys(&db).await)
+}
+
+/// Save global shortcuts and register them with the OS right away
+/// Fails without saving when a shortcut is taken by another app; the previous ones stay active
+#[tauri::command]
+pub async fn set_hotkeys(
+    bindings: HotkeyBindings,
+    app: AppHandle,
+    db: State<'_, Database>,
+) -> CommandResult<HotkeyBindings> {
+    bindings.validate().map_err(CommandError::invalid_input)?;
+
+    if let Err(e) = register_hotkeys(&app, &bindings) {
+        if let Err(e) = register_hotkeys(&app, &load_hotkeys(&db).await) {
+            log::warn!("Failed to restore previous shortcuts: {}", e);
+        }
+        return Err(CommandError::invalid_input(e));
+    }
+
+    let json = serde_json::to_string(&bindings).map_err(|e| CommandError::internal(e.to_string()))?;
+    db.set_setting(HOTKEYS_KEY, &json)
+        .await
+        .map_err(CommandError::database)?;
+    Ok(bindings)
+}
+
+/// Run a shortcut's action without pressing it, e.g. from a Stream Deck button
+/// Returns the confirmation the shortcut shows as a notification
+#[tauri::command]
+pub async fn trigger_hotkey(
+    action: HotkeyAction,
+    app: AppHandle,
+    db: State<'_, Database>,
+    adhkar: State<'_, AdhkarState>,
+    reminders: State<'_, ReminderState>,
+) -> CommandResult<String> {
+    let (_, body) = handle_hotkey(&app, &db, &adhkar, &reminders, action).await?;
+    Ok(body)
+}
+
+/// Register the saved shortcuts at startup
+pub async fn restore_hotkeys(app: &AppHandle, db: &Database) {
+    if let Err(e) = register_hotkeys(app, &load_hotkeys(db).await) {
+        log::warn!("Global shortcuts not registered: {}", e);
+    }
+}
+
+fn register_hotkeys(app: &AppHandle, bindings: &HotkeyBindings) -> Result<(), String> {
+    let mut manager = app.global_shortcut_manager();
+    manager
+        .unregister_all()
+        .map_err(|e| format!("Cannot release shortcuts: {}", e))?;
+
+    for (action, accelerator) in bindings.bindings() {
+        let handle = app.clone();
+        manager
+            .register(accelerator, move || {
+                let app = handle.clone();
+                tauri::async_runtime::spawn(async move { run_hotkey(&app, action).await });
+            })
+            .map_err(|e| format!("Cannot register {}: {}", accelerator, e))?;
+    }
+    Ok(())
+}
+
+/// A pressed shortcut answers with a notification, so it works while the window is hidden
+async fn run_hotkey(app: &AppHandle, action: HotkeyAction) {
+    let db = app.state::<Database>();
+    let adhkar = app.state::<AdhkarState>();
+    let reminders = app.state::<ReminderState>();
+
+    match handle_hotkey(app, &db, &adhkar, &reminders, action).await {
+        Ok((title, body)) => show_notification(app, &title, &body, false),
+        Err(e) => show_notification(app, "Iqamah", &e.message, false),
+    }
+}
+
+/// Carry out a shortcut's action; returns the notification title and body confirming it
+async fn handle_hotkey(
+    app: &AppHandle,
+    db: &Database,
+    adhkar: &AdhkarState,
+    reminders: &ReminderState,
+    action: HotkeyAction,
+) -> CommandResult<(String, String)> {
+    let formatter = user_formatter(db).await;
+    let language = formatter.language;
+
+    match action {
+        HotkeyAction::ShowMiniCountdown => {
+            let (_, countdowns) = selected_countdowns(db).await?;
+            let focus = kiosk_focus(&countdowns).ok_or_else(|| CommandError::not_found("No more prayers today"))?;
+            broadcast_event(app, "mini-countdown", &focus);
+
+            let minutes = |secs: i64| (secs + 59) / 60;
+            let body = match focus.time_until_iqama_secs {
+                Some(secs) if focus.time_until_adhan_secs <= 0 => language.iqama_reminder(focus.prayer_name, minutes(secs)),
+                _ => language.adhan_reminder(focus.prayer_name, minutes(focus.time_until_adhan_secs)),
+            };
+            let title = format!(
+                "{} · {}",
+                language.prayer_name(focus.prayer_name),
+                formatter.format_time(focus.iqama_time.unwrap_or(focus.adhan_time))
+            );
+            Ok((title, body))
+        }
+        HotkeyAction::MarkAttended => {
+            // The prayer in progress, else the last one that started
+            let (mosque_id, countdowns) = selected_countdowns(db).await?;
+            let prayer = countdowns
+                .iter()
+                .rev()
+                .find(|c| !c.not_held && (c.is_active || c.time_until_adhan_secs <= 0))
+                .map(|c| c.prayer_name)
+                .ok_or_else(|| CommandError::not_found("No prayer has started yet today"))?;
+
+            attend_prayer(db, adhkar, &mosque_id, prayer).await?;
+            Ok(("Iqamah".to_string(), language.marked_attended(prayer)))
+        }
+        HotkeyAction::SnoozeReminder => {
+            let snoozed = reminders.snooze(DEFAULT_SNOOZE_MINUTES).await;
+            Ok(("Iqamah".to_string(), language.reminder_snoozed(snoozed.map(|_| DEFAULT_SNOOZE_MINUTES))))
+        }
+    }
+}
+
+/// Today's countdowns at the selected mosque
+async fn selected_countdowns(db: &Database) -> CommandResult<(String, Vec<PrayerCountdown>)> {
+    let mosque_id = mosque_or_selected(db, None).await?;
+    let times = db
+        .get_prayer_times(&mosque_id, clock::today())
+        .await
+        .map_err(CommandError::database)?
+        .ok_or_else(|| CommandError::not_found("No prayer times found"))?;
+
+    let engine = PrayerEngine::new(user_engine_config(db).await)
+        .with_availability(load_availability(db, &mosque_id, &times).await);
+    let countdowns = with_start_timing(engine, db, &mosque_id)
+        .await
+        .get_all_countdowns(&times, ticker::countdown_now());
+    Ok((mosque_id, countdowns))
+}
+
+async fn load_hotkeys(db: &Database) -> HotkeyBindings {
+    match db.get_setting(HOTKEYS_KEY).await {
+        Ok(Some(json)) => serde_json::from_str(&json).unwrap_or_default(),
+        _ => HotkeyBindings::default(),
+    }
+}
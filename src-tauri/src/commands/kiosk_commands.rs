@@ -0,0 +1,237 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use tauri::{AppHandle, Manager, State};
+use tokio::sync::Mutex;
+
+use crate::commands::error::{CommandError, CommandResult};
+use crate::commands::prayer_commands::{load_availability, mosque_or_selected, user_engine_config, with_start_timing};
+use crate::commands::schedule_commands::refresh_day;
+use crate::commands::server_commands::broadcast_event;
+use crate::db::Database;
+use crate::models::*;
+use crate::services::{clock, ticker, PrayerEngine};
+
+pub(crate) const KIOSK_CONFIG_KEY: &str = "kiosk_config";
+
+/// How often a kiosk refetches its schedule while online
+const KIOSK_SYNC_INTERVAL: Duration = Duration::from_secs(10 * 60);
+/// Retry delay after a failed sync, so the screen recovers soon after the network returns
+const KIOSK_RETRY_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Kiosk mode for mosque lobby screens, shared between commands and the background ticker
+pub struct KioskState {
+    inner: Arc<Mutex<KioskRuntime>>,
+}
+
+struct KioskRuntime {
+    config: Option<KioskConfig>,
+    online: bool,
+    last_error: Option<String>,
+    synced_at: Option<DateTime<Utc>>,
+    next_sync: Option<Instant>,
+    syncing: bool,
+}
+
+impl KioskState {
+    pub fn new() -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(KioskRuntime {
+                config: None,
+                online: true,
+                last_error: None,
+                synced_at: None,
+                next_sync: None,
+                syncing: false,
+            })),
+        }
+    }
+
+    /// Resume the kiosk mode saved before a restart or crash
+    pub async fn restore(&self, db: &Database) -> bool {
+        match load_kiosk_config(db).await {
+            Some(config) => {
+                self.enable(config).await;
+                true
+            }
+            None => false,
+        }
+    }
+
+    async fn enable(&self, config: KioskConfig) {
+        let mut runtime = self.inner.lock().await;
+        runtime.config = Some(config);
+        runtime.next_sync = None;
+    }
+
+    async fn disable(&self) {
+        let mut runtime = self.inner.lock().await;
+        runtime.config = None;
+        runtime.last_error = None;
+    }
+
+    async fn status(&self) -> KioskStatus {
+        let runtime = self.inner.lock().await;
+        KioskStatus {
+            enabled: runtime.config.is_some(),
+            config: runtime.config.clone(),
+            online: runtime.online,
+            last_error: runtime.last_error.clone(),
+            synced_at: runtime.synced_at,
+        }
+    }
+
+    /// The frame to draw now, `None` when kiosk mode is off
+    /// Starts a schedule sync in the background when one is due
+    pub async fn tick(&self, db: &Database) -> Option<KioskFrame> {
+        let (config, online, synced_at, sync_due) = {
+            let mut runtime = self.inner.lock().await;
+            let config = runtime.config.clone()?;
+            let sync_due = !runtime.syncing && runtime.next_sync.is_none_or(|at| Instant::now() >= at);
+            if sync_due {
+                runtime.syncing = true;
+            }
+            (config, runtime.online, runtime.synced_at, sync_due)
+        };
+
+        let mosque_id = match mosque_or_selected(db, config.mosque_id.clone()).await {
+            Ok(mosque_id) => mosque_id,
+            Err(e) => {
+                log::warn!("Kiosk has no mosque to show: {}", e.message);
+                self.inner.lock().await.syncing = false;
+                return None;
+            }
+        };
+        if sync_due {
+            self.spawn_sync(db.clone(), mosque_id.clone());
+        }
+
+        let now = ticker::countdown_now();
+        let times = db.get_prayer_times(&mosque_id, clock::today()).await.ok().flatten();
+        let countdowns = match &times {
+            Some(times) => {
+                let engine = PrayerEngine::new(user_engine_config(db).await)
+                    .with_availability(load_availability(db, &mosque_id, times).await);
+                with_start_timing(engine, db, &mosque_id).await.get_all_countdowns(times, now)
+            }
+            None => Vec::new(),
+        };
+
+        Some(KioskFrame {
+            mosque_name: times.as_ref().and_then(|t| t.mosque_name.clone()),
+            mosque_id,
+            now,
+            focus: kiosk_focus(&countdowns),
+            board: if config.show_iqama_board { countdowns } else { Vec::new() },
+            announcement: config.announcement_at(now).map(str::to_string),
+            online,
+            synced_at,
+        })
+    }
+
+    /// Refetch today's schedule; failures keep the cached one on screen and retry soon
+    fn spawn_sync(&self, db: Database, mosque_id: String) {
+        let inner = self.inner.clone();
+        tauri::async_runtime::spawn(async move {
+            let result = refresh_day(&db, &mosque_id, clock::today()).await;
+
+            let mut runtime = inner.lock().await;
+            runtime.syncing = false;
+            match result {
+                Ok(_) => {
+                    if !runtime.online {
+                        log::info!("Kiosk schedule sync recovered for {}", mosque_id);
+                    }
+                    runtime.online = true;
+                    runtime.last_error = None;
+                    runtime.synced_at = Some(clock::now());
+                    runtime.next_sync = Some(Instant::now() + KIOSK_SYNC_INTERVAL);
+                }
+                Err(e) => {
+                    log::warn!("Kiosk schedule sync failed for {}: {}", mosque_id, e.message);
+                    runtime.online = false;
+                    runtime.last_error = Some(e.message);
+                    runtime.next_sync = Some(Instant::now() + KIOSK_RETRY_INTERVAL);
+                }
+            }
+        });
+    }
+}
+
+impl Default for KioskState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Switch the main window to a full-screen display for a mosque lobby
+/// The UI follows "kiosk-mode" and then draws the "kiosk-frame" events; the mode survives restarts
+#[tauri::command]
+pub async fn enter_kiosk_mode(
+    config: KioskConfig,
+    app: AppHandle,
+    db: State<'_, Database>,
+    kiosk: State<'_, KioskState>,
+) -> CommandResult<KioskStatus> {
+    if config.announcement_secs == 0 {
+        return Err(CommandError::invalid_input("Announcements need at least one second on screen"));
+    }
+    if let Some(mosque_id) = &config.mosque_id {
+        db.get_mosque(mosque_id)
+            .await
+            .map_err(CommandError::database)?
+            .ok_or_else(|| CommandError::not_found(format!("Mosque {} is not saved", mosque_id)))?;
+    }
+
+    let json = serde_json::to_string(&config).map_err(|e| CommandError::internal(e.to_string()))?;
+    db.set_setting(KIOSK_CONFIG_KEY, &json)
+        .await
+        .map_err(CommandError::database)?;
+    kiosk.enable(config).await;
+
+    set_kiosk_window(&app, true);
+    Ok(kiosk.status().await)
+}
+
+/// Leave kiosk mode and restore the normal window
+#[tauri::command]
+pub async fn exit_kiosk_mode(
+    app: AppHandle,
+    db: State<'_, Database>,
+    kiosk: State<'_, KioskState>,
+) -> CommandResult<KioskStatus> {
+    db.set_setting(KIOSK_CONFIG_KEY, "")
+        .await
+        .map_err(CommandError::database)?;
+    kiosk.disable().await;
+
+    set_kiosk_window(&app, false);
+    Ok(kiosk.status().await)
+}
+
+#[tauri::command]
+pub async fn get_kiosk_status(kiosk: State<'_, KioskState>) -> CommandResult<KioskStatus> {
+    Ok(kiosk.status().await)
+}
+
+/// Full screen and on top while in kiosk mode; tells the UI to switch layouts
+pub fn set_kiosk_window(app: &AppHandle, enabled: bool) {
+    if let Some(window) = app.get_window("main") {
+        if let Err(e) = window.set_fullscreen(enabled) {
+            log::warn!("Failed to set kiosk full screen: {}", e);
+        }
+        if let Err(e) = window.set_always_on_top(enabled) {
+            log::warn!("Failed to keep kiosk window on top: {}", e);
+        }
+    }
+
+    broadcast_event(app, "kiosk-mode", enabled);
+}
+
+async fn load_kiosk_config(db: &Database) -> Option<KioskConfig> {
+    match db.get_setting(KIOSK_CONFIG_KEY).await {
+        Ok(Some(json)) if !json.is_empty() => serde_json::from_str(&json).ok(),
+        _ => None,
+    }
+}
@@ -0,0 +1,31 @@
+use crate::commands::error::{CommandError, CommandResult};
+use crate::services::{log_dir, recent_logs, set_log_level as apply_log_level, LogEntry};
+
+/// Default number of entries returned by `get_recent_logs`
+const DEFAULT_LOG_LIMIT: usize = 200;
+
+/// Get the most recent log entries, optionally only those at `level` or above
+/// Level is one of "error", "warn", "info", "debug", "trace"
+#[tauri::command]
+pub async fn get_recent_logs(
+    level: Option<String>,
+    limit: Option<usize>,
+) -> CommandResult<Vec<LogEntry>> {
+    let dir = log_dir().ok_or_else(|| CommandError::internal("File logging is not enabled"))?;
+    let limit = limit.unwrap_or(DEFAULT_LOG_LIMIT);
+
+    tokio::task::spawn_blocking(move || recent_logs(dir, level.as_deref(), limit))
+        .await
+        .map_err(|e| CommandError::internal(e.to_string()))?
+        .map_err(|e| CommandError::invalid_input(format!("Failed to read logs: {}", e)))
+}
+
+/// Change the log level at runtime, e.g. "debug" or "info,iqamah_lib=trace"
+#[tauri::command]
+pub fn set_log_level(level: String) -> CommandResult<()> {
+    apply_log_level(&level)
+        .map_err(|e| CommandError::invalid_input(format!("Invalid log level {}: {}", level, e)))?;
+
+    log::info!("Log level set to {}", level);
+    Ok(())
+}
@@ -0,0 +1,36 @@
+use tauri::State;
+
+use crate::commands::error::{CommandError, CommandResult};
+use crate::db::Database;
+use crate::models::*;
+use crate::providers::mosque_cache_stats;
+use crate::services::{self, clock};
+
+/// Provider latencies, cache hit rates, ticker drift and database sizes since the app started
+#[tauri::command]
+pub async fn get_metrics(db: State<'_, Database>) -> CommandResult<AppMetrics> {
+    collect_metrics(&db).await
+}
+
+pub(crate) async fn collect_metrics(db: &Database) -> CommandResult<AppMetrics> {
+    let mosque_pages = mosque_cache_stats();
+    let caches = vec![
+        services::schedule_cache_metrics(),
+        CacheMetrics {
+            name: "mosque_pages".to_string(),
+            hits: mosque_pages.hits,
+            misses: mosque_pages.misses,
+            entries: Some(mosque_pages.entries),
+        },
+    ];
+
+    Ok(AppMetrics {
+        collected_at: clock::now(),
+        uptime_secs: services::uptime().as_secs(),
+        providers: services::provider_metrics(),
+        caches,
+        ticker: services::tick_metrics(),
+        database_bytes: db.size_bytes().await.map_err(CommandError::database)?,
+        tables: db.table_sizes().await.map_err(CommandError::database)?,
+    })
+}
@@ -1,7 +1,69 @@
+pub mod account_commands;
+pub mod admin_commands;
+pub mod adhkar_commands;
+pub mod audio_commands;
+pub mod calendar_sync_commands;
+pub mod crowd_commands;
+pub mod debug_commands;
+pub mod error;
+pub mod event_commands;
+pub mod fajr_alarm_commands;
+pub mod goal_commands;
+pub mod hijri_commands;
+pub mod hotkey_commands;
+pub mod kiosk_commands;
+pub mod log_commands;
+pub mod metrics_commands;
 pub mod mosque_commands;
+pub mod onboarding_commands;
 pub mod prayer_commands;
+pub mod prayer_log_commands;
+pub mod refresh_commands;
+pub mod reminder_commands;
+pub mod request_gate;
+pub mod schedule_commands;
 pub mod settings_commands;
+pub mod search_coordinator;
+pub mod server_commands;
+pub mod speech_commands;
+pub mod startup_commands;
+pub mod storage_commands;
+pub mod summary_commands;
+pub mod travel_commands;
+pub mod update_commands;
+pub mod upstream_commands;
 
+pub use account_commands::*;
+pub use admin_commands::*;
+pub use adhkar_commands::*;
+pub use audio_commands::*;
+pub use calendar_sync_commands::*;
+pub use crowd_commands::*;
+pub use debug_commands::*;
+pub use error::*;
+pub use event_commands::*;
+pub use fajr_alarm_commands::*;
+pub use goal_commands::*;
+pub use hijri_commands::*;
+pub use hotkey_commands::*;
+pub use kiosk_commands::*;
+pub use log_commands::*;
+pub use metrics_commands::*;
 pub use mosque_commands::*;
+pub use onboarding_commands::*;
 pub use prayer_commands::*;
+pub use prayer_log_commands::*;
+pub use refresh_commands::*;
+pub use reminder_commands::*;
+pub use request_gate::*;
+pub use schedule_commands::*;
 pub use settings_commands::*;
+pub use search_coordinator::*;
+pub use server_commands::*;
+pub use speech_commands::*;
+pub use startup_commands::*;
+pub use storage_commands::*;
+pub use summary_commands::*;
+pub use travel_commands::*;
+pub use update_commands::*;
+pub use upstream_commands::*;
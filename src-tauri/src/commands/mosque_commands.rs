@@ -1,23 +1,106 @@
-use tauri::State;
-
-use crate::db::Database;
+use std::collections::HashMap;
+
+use tauri::{AppHandle, Manager, State};
+
+use crate::commands::error::{CommandError, CommandErrorKind, CommandResult};
+use crate::commands::prayer_commands::load_availability;
+use crate::commands::request_gate::{RequestGate, RequestKey};
+use crate::commands::search_coordinator::{SearchCoordinator, SearchKey};
+use crate::commands::server_commands::broadcast_event;
+use crate::commands::schedule_commands::refresh_day;
+use crate::commands::settings_commands::{
+    load_default_country, load_iqama_defaults, load_max_cache_age_hours, load_provider_config,
+};
+use crate::db::{location, Database};
 use crate::models::*;
 use crate::providers::*;
+use crate::services::{
+    annotate_freshness, begin_fetch, clock, record_provider_fetch, record_refresh_failure, record_refresh_success,
+    record_schedule_lookup, refresh_allowed, sort_mosques, try_begin_fetch, MosqueCatalog, SearchQuery,
+};
 
 /// Search for mosques - uses Mawaqit provider by default
+/// Superseded searches are cancelled and identical in-flight ones are coalesced
+/// `facilities` keeps only mosques known to offer every facility set to `true`
+/// `params` pages, filters (country, city, iqama, distance from `location`) and sorts the results
+/// Relevance order ranks by match quality, favorites first among equals and, given `location`, nearest first
 #[tauri::command]
 pub async fn search_mosques(
     query: String,
     country: Option<String>,
+    facilities: Option<MosqueFacilities>,
+    params: Option<MosqueSearchParams>,
+    location: Option<GeoLocation>,
+    db: State<'_, Database>,
+    coordinator: State<'_, SearchCoordinator>,
+) -> CommandResult<MosqueSearchResult> {
+    let params = params.unwrap_or_default();
+    let key = SearchKey::new(&query, country.as_deref(), &params, location.as_ref());
+
+    let mut result = coordinator
+        .run(key, run_mosque_search(query, country, params, location, db.inner().clone()))
+        .await?;
+
+    if let Some(required) = facilities {
+        let before = result.mosques.len();
+        result.mosques.retain(|m| m.facilities.satisfies(&required));
+        result.total = result.total.saturating_sub(before - result.mosques.len());
+    }
+
+    Ok(result)
+}
+
+/// Import the full Mawaqit mosque list of a country into the offline catalog
+/// Emits `catalog-import-progress` while running; re-imports only report what changed by slug
+#[tauri::command]
+pub async fn download_country_catalog(
+    country: String,
+    app: AppHandle,
     db: State<'_, Database>,
-) -> Result<MosqueSearchResult, String> {
+) -> CommandResult<CatalogImportSummary> {
+    let country = country.trim().to_uppercase();
+    if country.is_empty() || !country.chars().all(|c| c.is_ascii_alphabetic()) {
+        return Err(CommandError::invalid_input(format!("Invalid country code: {}", country)));
+    }
+
+    let on_progress = |progress: CatalogImportProgress| {
+        if let Err(e) = app.emit_all("catalog-import-progress", progress) {
+            log::warn!("Failed to emit catalog import progress: {}", e);
+        }
+    };
+
+    MosqueCatalog::new(db.inner().clone())
+        .import(&country, &on_progress)
+        .await
+        .map_err(|e| match e.downcast::<ProviderError>() {
+            Ok(e) => CommandError::from_provider(PROVIDER_MAWAQIT, e),
+            Err(e) => CommandError::database(e),
+        })
+}
+
+async fn run_mosque_search(
+    query: String,
+    country: Option<String>,
+    params: MosqueSearchParams,
+    location: Option<GeoLocation>,
+    db: Database,
+) -> CommandResult<MosqueSearchResult> {
     // Check if user has configured a provider
-    let community_config = db.get_provider_config(PROVIDER_COMMUNITY_WRAPPER).await.ok().flatten();
-    let official_config = db.get_provider_config(PROVIDER_OFFICIAL_API).await.ok().flatten();
+    let community_config = load_provider_config(&db, PROVIDER_COMMUNITY_WRAPPER).await.ok().flatten();
+    let official_config = load_provider_config(&db, PROVIDER_OFFICIAL_API).await.ok().flatten();
+    let islamicfinder_config = load_provider_config(&db, PROVIDER_ISLAMICFINDER).await.ok().flatten();
+    let diyanet_config = load_provider_config(&db, PROVIDER_DIYANET).await.ok().flatten();
+    let timetable_config = load_provider_config(&db, PROVIDER_MASJID_TIMETABLE).await.ok().flatten();
     
-    let provider_config = community_config.or(official_config);
+    let provider_config = load_mock_config(&db)
+        .await
+        .or(community_config)
+        .or(official_config)
+        .or(islamicfinder_config)
+        .or(diyanet_config)
+        .or(timetable_config);
 
-    let mut external_results: Vec<Mosque> = Vec::new();
+    let mut external_results: Option<MosqueSearchResult> = None;
 
     // Try configured provider first
     if let Some(config) = provider_config {
@@ -25,52 +108,97 @@ pub async fn search_mosques(
             PROVIDER_COMMUNITY_WRAPPER => {
                 let mut provider = CommunityWrapperProvider::new();
                 provider.initialize(config.settings).await.ok();
-                provider.search_mosques(&query, None).await.ok()
+                provider.search_mosques_page(&query, None, &params).await.ok()
             }
             PROVIDER_OFFICIAL_API => {
                 let mut provider = OfficialApiProvider::new();
                 provider.initialize(config.settings).await.ok();
-                provider.search_mosques(&query, None).await.ok()
+                provider.search_mosques_page(&query, None, &params).await.ok()
+            }
+            PROVIDER_ISLAMICFINDER => {
+                let mut provider = IslamicFinderProvider::new();
+                provider.initialize(config.settings).await.ok();
+                provider.search_mosques_page(&query, None, &params).await.ok()
+            }
+            PROVIDER_DIYANET => {
+                let mut provider = DiyanetProvider::new();
+                provider.initialize(config.settings).await.ok();
+                provider.search_mosques_page(&query, None, &params).await.ok()
+            }
+            PROVIDER_MASJID_TIMETABLE => {
+                let mut provider = MasjidTimetableProvider::new();
+                provider.initialize(config.settings).await.ok();
+                provider.search_mosques_page(&query, None, &params).await.ok()
+            }
+            PROVIDER_MOCK => {
+                let mut provider = MockProvider::new();
+                provider.initialize(config.settings).await.ok();
+                provider.search_mosques_page(&query, None, &params).await.ok()
             }
             _ => None,
         };
 
-        if let Some(results) = result {
-            external_results = results;
-        }
+        external_results = result.filter(|r| !r.mosques.is_empty());
     }
 
-    // If no external results, try Mawaqit as fallback
-    if external_results.is_empty() {
-        let mut mawaqit = MawaqitProvider::new();
-        // Use provided country or default to FR
-        let country_config = country.map(|c| {
-            serde_json::json!({"default_country": c})
-        }).unwrap_or_else(|| serde_json::json!({"default_country": "FR"}));
-        
-        if mawaqit.initialize(country_config).await.is_ok() {
-            if let Ok(results) = mawaqit.search_mosques(&query, None).await {
-                external_results = results;
+    // Iqama and distance filters, applied to provider, catalog and favorite results alike
+    // Mosques never fetched match neither value: whether they publish iqamas isn't known
+    let publishes_iqama = match params.has_iqama {
+        Some(_) => db.get_mosque_iqama_publishing().await.map_err(CommandError::database)?,
+        None => HashMap::new(),
+    };
+    let keep = |m: &Mosque| {
+        params.within_distance(m, location.as_ref())
+            && params.has_iqama.is_none_or(|wanted| publishes_iqama.get(&m.id) == Some(&wanted))
+    };
+    let sort = params.sort.unwrap_or_default();
+
+    // If no external results, search the stored Mawaqit catalog
+    let MosqueSearchResult { mosques: external_results, mut total } = match external_results {
+        // Providers page server-side, so only the current page can be filtered
+        Some(MosqueSearchResult { mut mosques, total }) => {
+            let before = mosques.len();
+            mosques.retain(|m| keep(m));
+            MosqueSearchResult {
+                total: total.saturating_sub(before - mosques.len()),
+                mosques,
             }
         }
-    }
+        None => {
+            // Use provided country or the configured default
+            let country = match country.or_else(|| params.country.clone()) {
+                Some(country) => country,
+                None => load_default_country(&db).await,
+            };
+            let catalog = MosqueCatalog::new(db.clone());
+
+            // The whole catalog is filtered and sorted so the best matches land on the first page
+            match catalog.search(&country, &query).await {
+                Ok(mut results) => {
+                    results.retain(|m| keep(m));
+                    params.paginate(sort_mosques(results, &query, location.as_ref(), sort))
+                }
+                Err(e) => {
+                    log::warn!("Mosque catalog search failed for {}: {}", country, e);
+                    MosqueSearchResult { mosques: Vec::new(), total: 0 }
+                }
+            }
+        }
+    };
 
     // Also search local favorites
     let favorites = db
         .get_favorite_mosques()
         .await
-        .map_err(|e| format!("Database error: {}", e))?;
+        .map_err(CommandError::database)?;
 
+    // Favorites are merged into the first page only
+    let search = SearchQuery::new(&query);
     let local_results: Vec<Mosque> = favorites
         .clone()
         .into_iter()
-        .filter(|m| {
-            m.name.to_lowercase().contains(&query.to_lowercase())
-                || m.city
-                    .as_ref()
-                    .map(|c| c.to_lowercase().contains(&query.to_lowercase()))
-                    .unwrap_or(false)
-        })
+        .filter(|m| params.page() == 1 && params.matches(m) && keep(m))
+        .filter(|m| search.matches(&[&m.name, m.city.as_deref().unwrap_or_default()]))
         .collect();
 
     // Merge results
@@ -78,30 +206,33 @@ pub async fn search_mosques(
     for fav in local_results {
         if !all_results.iter().any(|m| m.id == fav.id) {
             all_results.push(fav);
+            total += 1;
         }
     }
 
-    // Mark favorites
+    // Mark favorites and apply the user's facility edits
+    let user_facilities = db.get_user_facilities().await.unwrap_or_default();
     for mosque in &mut all_results {
         if favorites.iter().any(|f| f.id == mosque.id) {
             mosque.is_favorite = true;
         }
+        if let Some(user) = user_facilities.get(&mosque.id) {
+            mosque.facilities = mosque.facilities.merged(user);
+        }
     }
 
-    let total = all_results.len();
-
     Ok(MosqueSearchResult {
-        mosques: all_results,
+        mosques: sort_mosques(all_results, &query, location.as_ref(), sort),
         total,
     })
 }
 
 /// Get favorite mosques
 #[tauri::command]
-pub async fn get_favorite_mosques(db: State<'_, Database>) -> Result<Vec<Mosque>, String> {
+pub async fn get_favorite_mosques(db: State<'_, Database>) -> CommandResult<Vec<Mosque>> {
     db.get_favorite_mosques()
         .await
-        .map_err(|e| format!("Database error: {}", e))
+        .map_err(CommandError::database)
 }
 
 /// Add a mosque to favorites
@@ -109,7 +240,7 @@ pub async fn get_favorite_mosques(db: State<'_, Database>) -> Result<Vec<Mosque>
 pub async fn add_favorite_mosque(
     mosque: Mosque,
     db: State<'_, Database>,
-) -> Result<(), String> {
+) -> CommandResult<()> {
     let mosque = Mosque {
         is_favorite: true,
         last_accessed: Some(chrono::Utc::now()),
@@ -118,11 +249,11 @@ pub async fn add_favorite_mosque(
 
     db.save_mosque(&mosque)
         .await
-        .map_err(|e| format!("Database error: {}", e))?;
+        .map_err(CommandError::database)?;
 
     db.set_favorite(&mosque.id, true)
         .await
-        .map_err(|e| format!("Database error: {}", e))
+        .map_err(CommandError::database)
 }
 
 /// Remove a mosque from favorites
@@ -130,10 +261,59 @@ pub async fn add_favorite_mosque(
 pub async fn remove_favorite_mosque(
     mosque_id: String,
     db: State<'_, Database>,
-) -> Result<(), String> {
+) -> CommandResult<()> {
     db.set_favorite(&mosque_id, false)
         .await
-        .map_err(|e| format!("Database error: {}", e))
+        .map_err(CommandError::database)
+}
+
+/// Record facilities for a mosque; values set here take precedence over the provider's
+#[tauri::command]
+pub async fn set_mosque_facilities(
+    mosque_id: String,
+    facilities: MosqueFacilities,
+    db: State<'_, Database>,
+) -> CommandResult<()> {
+    db.set_user_facilities(&mosque_id, &facilities)
+        .await
+        .map_err(CommandError::database)
+}
+
+/// Save the travel time to a favorite mosque for one mode; `None` forgets it
+#[tauri::command]
+pub async fn set_travel_time(
+    mosque_id: String,
+    mode: TravelMode,
+    travel_time_seconds: Option<i64>,
+    db: State<'_, Database>,
+) -> CommandResult<()> {
+    if travel_time_seconds.is_some_and(|secs| secs < 0) {
+        return Err(CommandError::invalid_input("Travel time cannot be negative"));
+    }
+
+    let mosque = db
+        .get_mosque(&mosque_id)
+        .await
+        .map_err(CommandError::database)?
+        .ok_or_else(|| CommandError::not_found(format!("Mosque {} not found", mosque_id)))?;
+    if !mosque.is_favorite {
+        return Err(CommandError::invalid_input("Travel times are only kept for favorite mosques"));
+    }
+
+    db.set_travel_time(&mosque_id, mode, travel_time_seconds)
+        .await
+        .map_err(CommandError::database)
+}
+
+/// Saved travel times to a mosque, most recently set first
+#[tauri::command]
+pub async fn get_travel_times(
+    mosque_id: String,
+    db: State<'_, Database>,
+) -> CommandResult<Vec<TravelTime>> {
+    db.get_travel_times(&mosque_id)
+        .await
+        .map_err(CommandError::database)
 }
 
 /// Get mosque details
@@ -141,47 +321,258 @@ pub async fn remove_favorite_mosque(
 pub async fn get_mosque_details(
     mosque_id: String,
     db: State<'_, Database>,
-) -> Result<Option<Mosque>, String> {
+) -> CommandResult<Option<Mosque>> {
     db.get_mosque(&mosque_id)
         .await
-        .map_err(|e| format!("Database error: {}", e))
+        .map_err(CommandError::database)
+}
+
+/// Compare two mosques' adhan and iqama times on a day, with the distance between them
+/// and, given `location`, to each of them. Differences are mosque B minus mosque A.
+/// Date format: "YYYY-MM-DD", defaults to today
+#[tauri::command]
+pub async fn compare_mosques(
+    mosque_a: String,
+    mosque_b: String,
+    date: Option<String>,
+    location: Option<GeoLocation>,
+    app: AppHandle,
+    db: State<'_, Database>,
+) -> CommandResult<MosqueComparison> {
+    if mosque_a == mosque_b {
+        return Err(CommandError::invalid_input("Pick two different mosques to compare"));
+    }
+    let target_date = match date {
+        Some(d) => chrono::NaiveDate::parse_from_str(&d, "%Y-%m-%d")
+            .map_err(|e| CommandError::invalid_input(format!("Invalid date format. Use YYYY-MM-DD: {}", e)))?,
+        None => clock::today(),
+    };
+
+    let db = db.inner().clone();
+    let (times_a, times_b) = tokio::join!(
+        load_prayer_times_for_mosque(app.clone(), db.clone(), mosque_a.clone(), None, target_date),
+        load_prayer_times_for_mosque(app, db.clone(), mosque_b.clone(), None, target_date),
+    );
+    let (times_a, times_b) = (times_a?, times_b?);
+
+    let mosque_a = saved_mosque_or_named(&db, mosque_a, &times_a).await?;
+    let mosque_b = saved_mosque_or_named(&db, mosque_b, &times_b).await?;
+
+    Ok(MosqueComparison::new(
+        target_date,
+        mosque_a,
+        &times_a,
+        mosque_b,
+        &times_b,
+        location.as_ref(),
+    ))
+}
+
+/// The saved mosque, or one named after its schedule when it isn't saved
+async fn saved_mosque_or_named(db: &Database, mosque_id: String, times: &PrayerTimes) -> CommandResult<Mosque> {
+    let saved = db.get_mosque(&mosque_id).await.map_err(CommandError::database)?;
+    Ok(saved.unwrap_or_else(|| {
+        let name = times.mosque_name.clone().unwrap_or_else(|| mosque_id.clone());
+        Mosque::new(mosque_id, name)
+    }))
 }
 
 /// Get prayer times for a mosque
 /// Optional date parameter in format "YYYY-MM-DD" for future/past dates
+/// A cached day older than the maximum cache age is returned at once and refetched in the background,
+/// followed by a `prayer-times-updated` event
+/// Concurrent calls for the same mosque and day share one lookup
 #[tauri::command]
 pub async fn get_prayer_times_for_mosque(
     mosque_id: String,
     country: Option<String>,
     date: Option<String>,
+    app: AppHandle,
     db: State<'_, Database>,
-) -> Result<PrayerTimes, String> {
+    gate: State<'_, RequestGate>,
+) -> CommandResult<PrayerTimes> {
     use chrono::NaiveDate;
 
     let target_date = match date {
         Some(d) => NaiveDate::parse_from_str(&d, "%Y-%m-%d")
-            .map_err(|e| format!("Invalid date format. Use YYYY-MM-DD: {}", e))?,
-        None => chrono::Local::now().date_naive(),
+            .map_err(|e| CommandError::invalid_input(format!("Invalid date format. Use YYYY-MM-DD: {}", e)))?,
+        None => clock::today(),
     };
 
+    let key = RequestKey::new("get_prayer_times_for_mosque", &[&mosque_id, &target_date.to_string()]);
+    let db = db.inner().clone();
+    gate.coalesce(key, load_prayer_times_for_mosque(app, db, mosque_id, country, target_date))
+        .await
+}
+
+async fn load_prayer_times_for_mosque(
+    app: AppHandle,
+    db: Database,
+    mosque_id: String,
+    country: Option<String>,
+    target_date: chrono::NaiveDate,
+) -> CommandResult<PrayerTimes> {
+    // Mock schedules are generated on each request, so mock settings apply at once, and cached
+    // like any provider result so engine commands find them
+    if let Some(config) = load_mock_config(&db).await.filter(|_| is_mock_mosque(&mosque_id)) {
+        let mut provider = MockProvider::new();
+        provider
+            .initialize(config.settings)
+            .await
+            .map_err(|e| CommandError::from_provider(PROVIDER_MOCK, e))?;
+        let times = provider
+            .get_prayer_times(&mosque_id, Some(target_date))
+            .await
+            .map_err(|e| CommandError::from_provider(PROVIDER_MOCK, e))?;
+        let _ = db.save_prayer_times(&times).await;
+        return Ok(with_freshness(&db, times).await);
+    }
+
     // Try to get from cache first
-    if let Ok(Some(cached)) = db.get_prayer_times(&mosque_id, target_date).await {
+    let cached = db.get_prayer_times(&mosque_id, target_date).await.ok().flatten();
+    record_schedule_lookup(cached.is_some());
+    if let Some(cached) = cached {
+        let mut cached = with_freshness(&db, cached).await;
+        if cached.freshness.as_ref().is_some_and(|f| f.stale) {
+            if let Some(freshness) = cached.freshness.as_mut() {
+                freshness.refreshing |= spawn_stale_refresh(app, db.clone(), &mosque_id, target_date);
+            }
+        }
         return Ok(cached);
     }
 
+    match fetch_from_providers(&db, &mosque_id, country, target_date).await {
+        Ok(times) => {
+            // Cache the result
+            let _ = db.save_prayer_times(&times).await;
+            Ok(with_freshness(&db, times).await)
+        }
+        Err(e) => {
+            log::warn!("No provider returned prayer times for {}: {}", mosque_id, e);
+            let error = CommandError::from_provider(PROVIDER_MAWAQIT, e);
+            let message = match error.kind {
+                CommandErrorKind::Offline => format!(
+                    "Could not reach any provider for {}. Check your connection and try again.",
+                    target_date
+                ),
+                _ => format!(
+                    "No prayer times found for {}. Please configure a provider in settings or try again.",
+                    target_date
+                ),
+            };
+            Err(CommandError { message, ..error })
+        }
+    }
+}
+
+/// Attach the age and source of a schedule before returning it to the UI
+pub(crate) async fn with_freshness(db: &Database, mut times: PrayerTimes) -> PrayerTimes {
+    annotate_freshness(&mut times, clock::now(), load_max_cache_age_hours(db).await);
+    times
+}
+
+/// Refetch a stale cached day unless a fetch for it is already running or the mosque is
+/// backing off after failed refreshes
+/// Returns whether a refresh was started; emits `prayer-times-updated` with the new day once saved
+fn spawn_stale_refresh(app: AppHandle, db: Database, mosque_id: &str, date: chrono::NaiveDate) -> bool {
+    if !refresh_allowed(mosque_id, clock::now()) {
+        return false;
+    }
+    let fetching = match try_begin_fetch(mosque_id, date) {
+        Some(guard) => guard,
+        None => return false,
+    };
+    let mosque_id = mosque_id.to_string();
+
+    tauri::async_runtime::spawn(async move {
+        let _fetching = fetching;
+        if let Err(e) = refresh_day(&db, &mosque_id, date).await {
+            let retry_at = record_refresh_failure(&mosque_id, clock::now());
+            log::debug!(
+                "Background refresh for {} on {} failed, next try after {}: {}",
+                mosque_id,
+                date,
+                retry_at,
+                e.message
+            );
+            return;
+        }
+        record_refresh_success(&mosque_id);
+        if let Ok(Some(times)) = db.get_prayer_times(&mosque_id, date).await {
+            broadcast_event(&app, "prayer-times-updated", with_freshness(&db, times).await);
+        }
+    });
+
+    true
+}
+
+/// Fetch a day from the configured providers, falling back to Mawaqit; bypasses the cache
+/// Adhan-only schedules get the user's default iqama offsets before they're cached, then the day is checked for warnings
+pub(crate) async fn fetch_from_providers(
+    db: &Database,
+    mosque_id: &str,
+    country: Option<String>,
+    target_date: chrono::NaiveDate,
+) -> ProviderResult<PrayerTimes> {
+    // Reported as refreshing on the cached copy while this runs
+    let _fetching = begin_fetch(mosque_id, target_date);
+    let mut times = fetch_raw_from_providers(db, mosque_id, country, target_date).await?;
+    load_iqama_defaults(db).await.apply(&mut times);
+    let availability = load_availability(db, mosque_id, &times).await;
+    times.refresh_warnings(&availability);
+
+    Ok(times)
+}
+
+async fn fetch_raw_from_providers(
+    db: &Database,
+    mosque_id: &str,
+    country: Option<String>,
+    target_date: chrono::NaiveDate,
+) -> ProviderResult<PrayerTimes> {
+    // IslamicFinder locations work without saved settings, which only tune the calculation
+    if is_islamicfinder_id(mosque_id) {
+        let mut provider = IslamicFinderProvider::new();
+        if let Ok(Some(config)) = load_provider_config(db, PROVIDER_ISLAMICFINDER).await {
+            provider.initialize(config.settings).await?;
+        }
+        return timed(PROVIDER_ISLAMICFINDER, provider.get_prayer_times(mosque_id, Some(target_date))).await;
+    }
+
+    if is_diyanet_id(mosque_id) {
+        let config = load_provider_config(db, PROVIDER_DIYANET)
+            .await
+            .ok()
+            .flatten()
+            .ok_or_else(|| ProviderError::InvalidConfig("Diyanet account not configured".to_string()))?;
+        let mut provider = DiyanetProvider::new();
+        provider.initialize(config.settings).await?;
+        return timed(PROVIDER_DIYANET, provider.get_prayer_times(mosque_id, Some(target_date))).await;
+    }
+
+    // Timetable ids carry their feed URL
+    if is_timetable_id(mosque_id) {
+        let provider = MasjidTimetableProvider::new();
+        return timed(PROVIDER_MASJID_TIMETABLE, provider.get_prayer_times(mosque_id, Some(target_date))).await;
+    }
+
     // Try external providers
-    let result = if let Ok(Some(config)) = db.get_provider_config(PROVIDER_COMMUNITY_WRAPPER).await
+    let result = if let Ok(Some(config)) = load_provider_config(db, PROVIDER_COMMUNITY_WRAPPER).await
     {
         let mut provider = CommunityWrapperProvider::new();
         if provider.initialize(config.settings).await.is_ok() {
-            provider.get_prayer_times(&mosque_id, Some(target_date)).await.ok()
+            timed(PROVIDER_COMMUNITY_WRAPPER, provider.get_prayer_times(mosque_id, Some(target_date)))
+                .await
+                .ok()
         } else {
             None
         }
-    } else if let Ok(Some(config)) = db.get_provider_config(PROVIDER_OFFICIAL_API).await {
+    } else if let Ok(Some(config)) = load_provider_config(db, PROVIDER_OFFICIAL_API).await {
         let mut provider = OfficialApiProvider::new();
         if provider.initialize(config.settings).await.is_ok() {
-            provider.get_prayer_times(&mosque_id, Some(target_date)).await.ok()
+            timed(PROVIDER_OFFICIAL_API, provider.get_prayer_times(mosque_id, Some(target_date)))
+                .await
+                .ok()
         } else {
             None
         }
@@ -189,48 +580,48 @@ pub async fn get_prayer_times_for_mosque(
         None
     };
 
-    // Try Mawaqit as fallback
-    let result = if result.is_none() {
-        let mut mawaqit = MawaqitProvider::new();
-        let country_config = country
-            .map(|c| serde_json::json!({"default_country": c}))
-            .unwrap_or_else(|| serde_json::json!({"default_country": "FR"}));
-
-        if mawaqit.initialize(country_config).await.is_ok() {
-            mawaqit.get_prayer_times(&mosque_id, Some(target_date)).await.ok()
-        } else {
-            None
-        }
-    } else {
-        result
-    };
-
+    // Try Mawaqit as fallback, keeping its error so the UI can tell offline from not found
     match result {
-        Some(times) => {
-            // Cache the result
-            let _ = db.save_prayer_times(&times).await;
-            Ok(times)
+        Some(times) => Ok(times),
+        None => {
+            let mut mawaqit = MawaqitProvider::new();
+            let country = match country {
+                Some(country) => country,
+                None => load_default_country(db).await,
+            };
+            let country_config = serde_json::json!({"default_country": country});
+
+            match mawaqit.initialize(country_config).await {
+                Ok(()) => timed(PROVIDER_MAWAQIT, mawaqit.get_prayer_times(mosque_id, Some(target_date))).await,
+                Err(e) => Err(e),
+            }
         }
-        None => Err(
-            format!("No prayer times found for {}. Please configure a provider in settings or try again.", target_date)
-        ),
     }
 }
 
+/// Run a provider fetch, recording its latency and outcome for the metrics
+async fn timed<T>(provider_id: &str, fetch: impl std::future::Future<Output = ProviderResult<T>>) -> ProviderResult<T> {
+    let started = std::time::Instant::now();
+    let result = fetch.await;
+    record_provider_fetch(provider_id, started.elapsed(), result.is_ok());
+    result
+}
+
 /// Fetch prayer times for a specific date from a mawaqit URL
 /// This fetches fresh data from the calendar regardless of cache
+/// Rate limited; concurrent calls for the same mosque and day share one fetch
 #[tauri::command]
 pub async fn fetch_prayer_times_for_date(
     mawaqit_url: String,
     date: String,
-) -> Result<PrayerTimes, String> {
+    db: State<'_, Database>,
+    gate: State<'_, RequestGate>,
+) -> CommandResult<PrayerTimes> {
     use chrono::NaiveDate;
     
     let target_date = NaiveDate::parse_from_str(&date, "%Y-%m-%d")
-        .map_err(|e| format!("Invalid date format. Use YYYY-MM-DD: {}", e))?;
+        .map_err(|e| CommandError::invalid_input(format!("Invalid date format. Use YYYY-MM-DD: {}", e)))?;
 
-    let mut mawaqit = MawaqitProvider::new();
-    
     // Extract slug from URL (e.g., https://mawaqit.net/en/m/mosque-name -> mosque-name)
     let slug = mawaqit_url
         .split('/')
@@ -245,15 +636,23 @@ pub async fn fetch_prayer_times_for_date(
                 .filter(|s| !s.is_empty())
                 .map(|s| s.to_string())
         })
-        .ok_or_else(|| "Invalid mawaqit URL".to_string())?;
+        .ok_or_else(|| CommandError::invalid_input("Invalid mawaqit URL"))?;
 
-    log::info!("Fetching prayer times for {} on date {}", slug, target_date);
+    let key = RequestKey::new("fetch_prayer_times_for_date", &[&slug, &date]);
+    let db = db.inner().clone();
+    gate.coalesce_limited(key, async move {
+        log::info!("Fetching prayer times for {} on date {}", slug, target_date);
 
-    mawaqit.initialize(serde_json::json!({"default_country": "FR"})).await
-        .map_err(|e| format!("Failed to initialize provider: {}", e))?;
-    
-    mawaqit.get_prayer_times(&slug, Some(target_date)).await
-        .map_err(|e| format!("Failed to fetch prayer times: {}", e))
+        let mut mawaqit = MawaqitProvider::new();
+        let country = load_default_country(&db).await;
+        mawaqit.initialize(serde_json::json!({"default_country": country})).await
+            .map_err(|e| CommandError::from_provider(PROVIDER_MAWAQIT, e))?;
+
+        let times = mawaqit.get_prayer_times(&slug, Some(target_date)).await
+            .map_err(|e| CommandError::from_provider(PROVIDER_MAWAQIT, e))?;
+        Ok(with_freshness(&db, times).await)
+    })
+    .await
 }
 
 /// Test provider connection
@@ -261,39 +660,67 @@ pub async fn fetch_prayer_times_for_date(
 pub async fn test_provider_connection(
     provider_id: String,
     config: serde_json::Value,
-) -> Result<ProviderTestResult, String> {
+) -> CommandResult<ProviderTestResult> {
     use crate::providers::*;
 
-    let result: Result<ProviderTestResult, String> = match provider_id.as_str() {
-        "mawaqit" => {
+    let result: CommandResult<ProviderTestResult> = match provider_id.as_str() {
+        PROVIDER_MAWAQIT => {
             let mut provider = MawaqitProvider::new();
             provider.initialize(config).await
-                .map_err(|e| format!("Failed to initialize: {}", e))?;
+                .map_err(|e| CommandError::from_provider(&provider_id, e))?;
             provider.test_connection().await
-                .map_err(|e| format!("Connection test failed: {}", e))
+                .map_err(|e| CommandError::from_provider(&provider_id, e))
         }
         PROVIDER_OFFICIAL_API => {
             let mut provider = OfficialApiProvider::new();
             provider.initialize(config).await
-                .map_err(|e| format!("Failed to initialize: {}", e))?;
+                .map_err(|e| CommandError::from_provider(&provider_id, e))?;
             provider.test_connection().await
-                .map_err(|e| format!("Connection test failed: {}", e))
+                .map_err(|e| CommandError::from_provider(&provider_id, e))
         }
         PROVIDER_COMMUNITY_WRAPPER => {
             let mut provider = CommunityWrapperProvider::new();
             provider.initialize(config).await
-                .map_err(|e| format!("Failed to initialize: {}", e))?;
+                .map_err(|e| CommandError::from_provider(&provider_id, e))?;
             provider.test_connection().await
-                .map_err(|e| format!("Connection test failed: {}", e))
+                .map_err(|e| CommandError::from_provider(&provider_id, e))
         }
         PROVIDER_SCRAPING => {
             let mut provider = ScrapingProvider::new();
             provider.initialize(config).await
-                .map_err(|e| format!("Failed to initialize: {}", e))?;
+                .map_err(|e| CommandError::from_provider(&provider_id, e))?;
+            provider.test_connection().await
+                .map_err(|e| CommandError::from_provider(&provider_id, e))
+        }
+        PROVIDER_ISLAMICFINDER => {
+            let mut provider = IslamicFinderProvider::new();
+            provider.initialize(config).await
+                .map_err(|e| CommandError::from_provider(&provider_id, e))?;
+            provider.test_connection().await
+                .map_err(|e| CommandError::from_provider(&provider_id, e))
+        }
+        PROVIDER_DIYANET => {
+            let mut provider = DiyanetProvider::new();
+            provider.initialize(config).await
+                .map_err(|e| CommandError::from_provider(&provider_id, e))?;
+            provider.test_connection().await
+                .map_err(|e| CommandError::from_provider(&provider_id, e))
+        }
+        PROVIDER_MASJID_TIMETABLE => {
+            let mut provider = MasjidTimetableProvider::new();
+            provider.initialize(config).await
+                .map_err(|e| CommandError::from_provider(&provider_id, e))?;
+            provider.test_connection().await
+                .map_err(|e| CommandError::from_provider(&provider_id, e))
+        }
+        PROVIDER_MOCK => {
+            let mut provider = MockProvider::new();
+            provider.initialize(config).await
+                .map_err(|e| CommandError::from_provider(&provider_id, e))?;
             provider.test_connection().await
-                .map_err(|e| format!("Connection test failed: {}", e))
+                .map_err(|e| CommandError::from_provider(&provider_id, e))
         }
-        _ => Err(format!("Unknown provider: {}", provider_id)),
+        _ => Err(CommandError::invalid_input(format!("Unknown provider: {}", provider_id))),
     };
 
     result
@@ -304,9 +731,9 @@ pub async fn test_provider_connection(
 pub async fn get_available_providers() -> Vec<ProviderInfo> {
     let mawaqit = MawaqitProvider::new();
 
-    vec![
+    let mut providers = vec![
         ProviderInfo {
-            id: "mawaqit".to_string(),
+            id: PROVIDER_MAWAQIT.to_string(),
             name: "Mawaqit (Recommended)".to_string(),
             description: "Official Mawaqit.net - No API key required, works worldwide".to_string(),
             config_schema: mawaqit.config_schema(),
@@ -348,12 +775,48 @@ pub async fn get_available_providers() -> Vec<ProviderInfo> {
                     .description("Delay between requests in milliseconds"),
             ],
         },
-    ]
+    ];
+
+    let islamicfinder = IslamicFinderProvider::new();
+    providers.push(ProviderInfo {
+        id: islamicfinder.id().to_string(),
+        name: islamicfinder.name().to_string(),
+        description: islamicfinder.description().to_string(),
+        config_schema: islamicfinder.config_schema(),
+    });
+
+    let diyanet = DiyanetProvider::new();
+    providers.push(ProviderInfo {
+        id: diyanet.id().to_string(),
+        name: diyanet.name().to_string(),
+        description: diyanet.description().to_string(),
+        config_schema: diyanet.config_schema(),
+    });
+
+    let timetable = MasjidTimetableProvider::new();
+    providers.push(ProviderInfo {
+        id: timetable.id().to_string(),
+        name: timetable.name().to_string(),
+        description: timetable.description().to_string(),
+        config_schema: timetable.config_schema(),
+    });
+
+    if cfg!(debug_assertions) {
+        let mock = MockProvider::new();
+        providers.push(ProviderInfo {
+            id: mock.id().to_string(),
+            name: mock.name().to_string(),
+            description: mock.description().to_string(),
+            config_schema: mock.config_schema(),
+        });
+    }
+
+    providers
 }
 
 /// Get active provider info
 #[tauri::command]
-pub async fn get_active_provider(db: State<'_, Database>) -> Result<Option<ProviderInfo>, String> {
+pub async fn get_active_provider(db: State<'_, Database>) -> CommandResult<Option<ProviderInfo>> {
     // Check Mawaqit (default)
     let mawaqit = MawaqitProvider::new();
     
@@ -378,6 +841,36 @@ pub async fn get_active_provider(db: State<'_, Database>) -> Result<Option<Provi
         }));
     }
 
+    if let Ok(Some(_)) = db.get_provider_config(PROVIDER_ISLAMICFINDER).await {
+        let provider = IslamicFinderProvider::new();
+        return Ok(Some(ProviderInfo {
+            id: provider.id().to_string(),
+            name: provider.name().to_string(),
+            description: provider.description().to_string(),
+            config_schema: provider.config_schema(),
+        }));
+    }
+
+    if let Ok(Some(_)) = db.get_provider_config(PROVIDER_DIYANET).await {
+        let provider = DiyanetProvider::new();
+        return Ok(Some(ProviderInfo {
+            id: provider.id().to_string(),
+            name: provider.name().to_string(),
+            description: provider.description().to_string(),
+            config_schema: provider.config_schema(),
+        }));
+    }
+
+    if let Ok(Some(_)) = db.get_provider_config(PROVIDER_MASJID_TIMETABLE).await {
+        let provider = MasjidTimetableProvider::new();
+        return Ok(Some(ProviderInfo {
+            id: provider.id().to_string(),
+            name: provider.name().to_string(),
+            description: provider.description().to_string(),
+            config_schema: provider.config_schema(),
+        }));
+    }
+
     // Return Mawaqit as default
     Ok(Some(ProviderInfo {
         id: mawaqit.id().to_string(),
@@ -388,86 +881,98 @@ pub async fn get_active_provider(db: State<'_, Database>) -> Result<Option<Provi
 }
 
 /// Save selected mosque for persistence across app restarts
+/// `profile` defaults to the default profile; `travel_time_secs` is saved as the mosque's travel time
+/// for `travel_mode` (walking if omitted), and travel times already saved for it are kept
 #[tauri::command]
 pub async fn save_selected_mosque(
     mosque: Mosque,
+    profile: Option<String>,
+    travel_time_secs: Option<i64>,
+    travel_mode: Option<TravelMode>,
     db: State<'_, Database>,
-) -> Result<(), String> {
+) -> CommandResult<SelectedMosque> {
+    if travel_time_secs.is_some_and(|secs| secs < 0) {
+        return Err(CommandError::invalid_input("Travel time cannot be negative"));
+    }
+
+    let selected = select_mosque(&db, mosque, profile).await?;
+    if let Some(secs) = travel_time_secs {
+        let mode = travel_mode.unwrap_or(TravelMode::Walking);
+        db.set_travel_time(&selected.mosque.id, mode, Some(secs))
+            .await
+            .map_err(CommandError::database)?;
+    }
+    Ok(selected)
+}
+
+pub(crate) async fn select_mosque(
+    db: &Database,
+    mosque: Mosque,
+    profile: Option<String>,
+) -> CommandResult<SelectedMosque> {
     log::info!("Saving selected mosque: {} (id: {})", mosque.name, mosque.id);
-    
-    // Save mosque to database with last_accessed timestamp
+
+    let profile = profile.unwrap_or_else(|| DEFAULT_PROFILE.to_string());
+
+    // Saved with the selection, stamped with last_accessed
     let mosque = Mosque {
         last_accessed: Some(chrono::Utc::now()),
         ..mosque
     };
 
-    match db.save_mosque(&mosque).await {
-        Ok(_) => log::info!("Mosque saved successfully"),
-        Err(e) => {
-            log::error!("Failed to save mosque: {}", e);
-            return Err(format!("Database error: {}", e));
-        }
-    }
+    let selected = SelectedMosque {
+        profile,
+        mosque,
+        selected_at: clock::now(),
+    };
+    db.switch_selected_mosque(&selected)
+        .await
+        .map_err(CommandError::database)?;
 
-    // Store the selected mosque ID in settings
-    match db.set_setting("selected_mosque_id", &mosque.id).await {
-        Ok(_) => {
-            log::info!("Selected mosque ID saved to settings");
-            Ok(())
-        }
-        Err(e) => {
-            log::error!("Failed to save setting: {}", e);
-            Err(format!("Failed to save setting: {}", e))
-        }
-    }
+    log::info!("Selected mosque saved for profile {}", selected.profile);
+    Ok(selected)
 }
 
-/// Get the last selected mosque
+/// Get the last selected mosque of a profile (the default profile if omitted)
 #[tauri::command]
-pub async fn get_selected_mosque(db: State<'_, Database>) -> Result<Option<Mosque>, String> {
-    // Get the selected mosque ID from settings
-    let mosque_id = match db.get_setting("selected_mosque_id").await {
-        Ok(Some(id)) => {
-            log::info!("Found saved mosque ID in settings: {}", id);
-            id
-        }
-        Ok(None) => {
-            log::info!("No saved mosque ID found in settings");
-            return Ok(None);
-        }
-        Err(e) => {
-            log::error!("Error reading settings: {}", e);
-            return Ok(None);
-        }
-    };
+pub async fn get_selected_mosque(
+    profile: Option<String>,
+    db: State<'_, Database>,
+) -> CommandResult<Option<SelectedMosque>> {
+    load_selected_mosque(&db, profile.as_deref()).await
+}
 
-    // Get mosque details
-    match db.get_mosque(&mosque_id).await {
-        Ok(mosque) => {
-            if mosque.is_some() {
-                log::info!("Successfully retrieved saved mosque: {}", mosque_id);
-            } else {
-                log::warn!("Saved mosque ID {} not found in mosques table", mosque_id);
-            }
-            Ok(mosque)
-        }
-        Err(e) => {
-            log::error!("Database error retrieving mosque: {}", e);
-            Err(format!("Database error: {}", e))
-        }
+pub(crate) async fn load_selected_mosque(
+    db: &Database,
+    profile: Option<&str>,
+) -> CommandResult<Option<SelectedMosque>> {
+    db.get_selected_mosque(profile.unwrap_or(DEFAULT_PROFILE))
+        .await
+        .map_err(CommandError::database)
+}
+
+/// Mock provider config, only honoured in debug builds
+async fn load_mock_config(db: &Database) -> Option<ProviderConfig> {
+    if !cfg!(debug_assertions) {
+        return None;
     }
+
+    load_provider_config(db, PROVIDER_MOCK).await.ok().flatten()
+}
+
+/// Get hit/miss statistics for the mosque page cache
+#[tauri::command]
+pub fn get_cache_stats() -> CacheStats {
+    mosque_cache_stats()
 }
 
 /// Check database health and persistence status
 #[tauri::command]
-pub async fn check_database_health() -> Result<serde_json::Value, String> {
-    let app_dir = match dirs::data_dir() {
-        Some(dir) => dir.join("iqamah"),
-        None => return Err("Could not find data directory".to_string()),
+pub async fn check_database_health() -> CommandResult<serde_json::Value> {
+    let (Some(app_dir), Some(db_path)) = (location::app_dir(), location::database_path()) else {
+        return Err(CommandError::internal("Could not find data directory"));
     };
     
-    let db_path = app_dir.join("iqamah.db");
-    
     // Check if database file exists
     let file_exists = db_path.exists();
     let file_size = if file_exists {
@@ -484,5 +989,6 @@ pub async fn check_database_health() -> Result<serde_json::Value, String> {
         "file_size_bytes": file_size,
         "app_dir_exists": app_dir.exists(),
         "app_dir_path": app_dir.to_string_lossy().to_string(),
+        "portable": location::is_portable(),
     }))
 }
@@ -0,0 +1,161 @@
+use tauri::{AppHandle, Manager, State};
+
+use crate::commands::error::{CommandError, CommandResult};
+use crate::commands::mosque_commands::select_mosque;
+use crate::commands::schedule_commands::refresh_day;
+use crate::commands::settings_commands::{is_country_code, load_default_country, DEFAULT_COUNTRY_KEY};
+use crate::db::Database;
+use crate::models::*;
+use crate::providers::ProviderError;
+use crate::services::{clock, LocationService, MosqueCatalog};
+
+/// Radius searched for suggestions unless the wizard asks for another
+const DEFAULT_SUGGESTION_RADIUS_KM: f64 = 25.0;
+const DEFAULT_SUGGESTION_LIMIT: usize = 10;
+const MAX_SUGGESTION_RADIUS_KM: f64 = 200.0;
+
+const ONBOARDING_COMPLETED_KEY: &str = "onboarding_completed_at";
+
+/// Guess the user's country and city from their IP address for the first-run wizard
+/// Falls back to the saved default country when offline or the lookup fails
+#[tauri::command]
+pub async fn detect_region(db: State<'_, Database>) -> CommandResult<DetectedRegion> {
+    Ok(detect_or_default(&db).await)
+}
+
+/// Mosques near `location` (or the detected one), nearest first
+/// Downloads the country's catalog first if needed, emitting `catalog-import-progress` like `download_country_catalog`
+#[tauri::command]
+pub async fn suggest_nearby_mosques(
+    location: Option<GeoLocation>,
+    country: Option<String>,
+    radius_km: Option<f64>,
+    limit: Option<usize>,
+    app: AppHandle,
+    db: State<'_, Database>,
+) -> CommandResult<OnboardingSuggestions> {
+    let radius_km = radius_km.unwrap_or(DEFAULT_SUGGESTION_RADIUS_KM);
+    if !(radius_km > 0.0 && radius_km <= MAX_SUGGESTION_RADIUS_KM) {
+        return Err(CommandError::invalid_input(format!(
+            "Radius must be between 0 and {} km, got {}",
+            MAX_SUGGESTION_RADIUS_KM, radius_km
+        )));
+    }
+    let country = country.map(|c| c.trim().to_uppercase());
+    if let Some(country) = country.as_deref().filter(|c| !is_country_code(c)) {
+        return Err(CommandError::invalid_input(format!("Invalid country code: {}", country)));
+    }
+
+    // Only look the IP up for what the wizard didn't provide
+    let mut region = match (&location, &country) {
+        (Some(_), Some(country)) => DetectedRegion {
+            country: country.clone(),
+            city: None,
+            location: None,
+            timezone: None,
+            source: RegionSource::Device,
+        },
+        _ => detect_or_default(&db).await,
+    };
+    if let Some(country) = country {
+        region.country = country;
+    }
+    if let Some(location) = location {
+        region.location = Some(location);
+        region.source = RegionSource::Device;
+    }
+
+    let Some(location) = region.location else {
+        // Nothing to measure from; the wizard falls back to searching by name
+        return Ok(OnboardingSuggestions { region, mosques: Vec::new() });
+    };
+
+    let catalog = MosqueCatalog::new(db.inner().clone());
+    if !catalog.is_downloaded(&region.country).await.map_err(CommandError::database)? {
+        let on_progress = |progress: CatalogImportProgress| {
+            if let Err(e) = app.emit_all("catalog-import-progress", progress) {
+                log::warn!("Failed to emit catalog import progress: {}", e);
+            }
+        };
+        catalog
+            .import(&region.country, &on_progress)
+            .await
+            .map_err(catalog_error)?;
+    }
+
+    let mosques = catalog
+        .nearby(&region.country, &location, radius_km, limit.unwrap_or(DEFAULT_SUGGESTION_LIMIT))
+        .await
+        .map_err(catalog_error)?;
+    log::info!("Suggesting {} mosques within {} km in {}", mosques.len(), radius_km, region.country);
+
+    Ok(OnboardingSuggestions { region, mosques })
+}
+
+/// Finish the first-run wizard: save the country and the chosen mosque, then fetch today's schedule
+/// A failed fetch doesn't fail onboarding; it is reported and retried by the background refresh
+#[tauri::command]
+pub async fn complete_onboarding(
+    mosque: Mosque,
+    country: Option<String>,
+    profile: Option<String>,
+    db: State<'_, Database>,
+) -> CommandResult<OnboardingResult> {
+    let country = match country.map(|c| c.trim().to_uppercase()) {
+        Some(country) if !is_country_code(&country) => {
+            return Err(CommandError::invalid_input(format!("Invalid country code: {}", country)));
+        }
+        Some(country) => country,
+        None => match mosque.country.as_deref().map(str::to_uppercase) {
+            Some(country) if is_country_code(&country) => country,
+            _ => load_default_country(&db).await,
+        },
+    };
+
+    db.set_setting(DEFAULT_COUNTRY_KEY, &country)
+        .await
+        .map_err(CommandError::database)?;
+    let selected = select_mosque(&db, mosque, profile).await?;
+
+    let fetch_error = match refresh_day(&db, &selected.mosque.id, clock::today()).await {
+        Ok(_) => None,
+        Err(e) => {
+            log::warn!("First schedule fetch for {} failed: {}", selected.mosque.id, e.message);
+            Some(e.message)
+        }
+    };
+
+    db.set_setting(ONBOARDING_COMPLETED_KEY, &clock::now().to_rfc3339())
+        .await
+        .map_err(CommandError::database)?;
+    log::info!("Onboarding completed with {} in {}", selected.mosque.id, country);
+
+    Ok(OnboardingResult {
+        selected,
+        default_country: country,
+        fetch_error,
+    })
+}
+
+async fn detect_or_default(db: &Database) -> DetectedRegion {
+    match LocationService::new().detect_region().await {
+        Ok(region) => region,
+        Err(e) => {
+            log::info!("IP location unavailable, using the default country: {}", e);
+            DetectedRegion {
+                country: load_default_country(db).await,
+                city: None,
+                location: None,
+                timezone: None,
+                source: RegionSource::Settings,
+            }
+        }
+    }
+}
+
+fn catalog_error(e: anyhow::Error) -> CommandError {
+    match e.downcast::<ProviderError>() {
+        Ok(e) => CommandError::from_provider(PROVIDER_MAWAQIT, e),
+        Err(e) => CommandError::database(e),
+    }
+}
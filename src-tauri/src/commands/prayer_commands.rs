@@ -1,30 +1,206 @@
-use chrono::{NaiveDate, Utc};
+use std::collections::HashMap;
+
+use chrono::{DateTime, Datelike, Duration, FixedOffset, NaiveDate, Utc, Weekday};
 use tauri::State;
 
+use crate::commands::crowd_commands::{crowd_level, learned_delays};
+use crate::commands::error::{CommandError, CommandResult};
+use crate::commands::mosque_commands::{load_selected_mosque, with_freshness};
+use crate::commands::schedule_commands::refresh_day;
+use crate::commands::settings_commands::{
+    COUNTDOWN_NOW_THRESHOLD_KEY, COUNTDOWN_SECONDS_THRESHOLD_KEY, IMSAK_MINUTES_KEY,
+};
 use crate::db::Database;
 use crate::models::*;
-use crate::services::PrayerEngine;
+use crate::services::{
+    clock, learned_start_lags, learned_start_spreads, ticker, DisplayFormatter, Language, PrayerEngine,
+};
+
+const MOSQUE_CONTEXTS_KEY: &str = "mosque_contexts";
 
-/// Get next prayer for a mosque
+/// Get next prayer for a mosque, the selected mosque if omitted
 #[tauri::command]
 pub async fn get_next_prayer(
-    mosque_id: String,
+    mosque_id: Option<String>,
+    at: Option<String>,
+    utc_offset_minutes: Option<i32>,
     db: State<'_, Database>,
-) -> Result<NextPrayerResult, String> {
-    let today = chrono::Local::now().date_naive();
+) -> CommandResult<NextPrayerResult> {
+    let mosque_id = mosque_or_selected(&db, mosque_id).await?;
+    let (now, today) = evaluation_time(at, utc_offset_minutes, ticker::countdown_now())?;
+
+    next_prayer_at(&db, &mosque_id, now, today).await
+}
 
+async fn next_prayer_at(
+    db: &Database,
+    mosque_id: &str,
+    now: DateTime<Utc>,
+    today: NaiveDate,
+) -> CommandResult<NextPrayerResult> {
     let prayer_times = db
-        .get_prayer_times(&mosque_id, today)
+        .get_prayer_times(mosque_id, today)
         .await
-        .map_err(|e| format!("Database error: {}", e))?
-        .ok_or_else(|| "No prayer times found".to_string())?;
+        .map_err(CommandError::database)?
+        .ok_or_else(|| CommandError::not_found("No prayer times found"))?;
 
-    let engine = PrayerEngine::with_defaults();
-    let now = Utc::now();
+    let engine = PrayerEngine::new(user_engine_config(db).await)
+        .with_availability(load_availability(db, mosque_id, &prayer_times).await);
+    let engine = with_start_timing(engine, db, mosque_id).await;
 
     Ok(engine.get_next_prayer(&prayer_times, now))
 }
 
+/// Next prayer at the home or work mosque, whichever the user is expected to pray at
+/// A `location` near a place picks it outright; otherwise each place's next prayer counts only
+/// when the schedule puts the user there at its adhan, and the earliest wins
+#[tauri::command]
+pub async fn get_next_prayer_contextual(
+    location: Option<GeoLocation>,
+    at: Option<String>,
+    utc_offset_minutes: Option<i32>,
+    db: State<'_, Database>,
+) -> CommandResult<ContextualNextPrayer> {
+    let contexts = load_mosque_contexts(&db).await;
+    if !contexts.enabled {
+        return Err(CommandError::invalid_input("Home and work mosques are not set up"));
+    }
+    let (now, today) = evaluation_time(at, utc_offset_minutes, ticker::countdown_now())?;
+    // Already validated by evaluation_time
+    let offset = utc_offset_minutes.and_then(|minutes| FixedOffset::east_opt(minutes * 60));
+    let local = |time: DateTime<Utc>| match offset {
+        Some(offset) => time.with_timezone(&offset).naive_local(),
+        None => time.with_timezone(&chrono::Local).naive_local(),
+    };
+
+    if let Some(place) = location.as_ref().and_then(|location| contexts.place_near(location)) {
+        return contextual_next_prayer(&db, place, ContextReason::Location, now, today).await;
+    }
+
+    let mut best: Option<ContextualNextPrayer> = None;
+    for place in &contexts.places {
+        let result = match contextual_next_prayer(&db, place, ContextReason::Default, now, today).await {
+            Ok(result) => result,
+            Err(e) => {
+                log::debug!("No next prayer for {}: {}", place.label, e);
+                continue;
+            }
+        };
+        let Some((owner, reason)) = contexts.place_at(local(result.next.prayer.adhan)) else {
+            continue;
+        };
+        if owner.label != place.label {
+            continue;
+        }
+        if best.as_ref().is_none_or(|b| result.next.prayer.adhan < b.next.prayer.adhan) {
+            best = Some(ContextualNextPrayer { reason, ..result });
+        }
+    }
+    if let Some(best) = best {
+        return Ok(best);
+    }
+
+    // No place's next prayer falls while the user is there: the place they are at now
+    let (place, reason) = contexts
+        .place_at(local(now))
+        .ok_or_else(|| CommandError::invalid_input("No places set up"))?;
+    contextual_next_prayer(&db, place, reason, now, today).await
+}
+
+async fn contextual_next_prayer(
+    db: &Database,
+    place: &MosqueContext,
+    reason: ContextReason,
+    now: DateTime<Utc>,
+    today: NaiveDate,
+) -> CommandResult<ContextualNextPrayer> {
+    let selected = load_selected_mosque(db, Some(&place.profile))
+        .await?
+        .ok_or_else(|| CommandError::not_found(format!("No mosque selected for {}", place.label)))?;
+    let next = next_prayer_at(db, &selected.mosque.id, now, today).await?;
+
+    Ok(ContextualNextPrayer {
+        label: place.label.clone(),
+        profile: place.profile.clone(),
+        mosque_id: selected.mosque.id,
+        mosque_name: selected.mosque.name,
+        reason,
+        next,
+    })
+}
+
+#[tauri::command]
+pub async fn get_mosque_contexts(db: State<'_, Database>) -> CommandResult<MosqueContexts> {
+    Ok(load_mosque_contexts(&db).await)
+}
+
+/// Save the home and work places, each bound to a profile's selected mosque, and the schedule between them
+#[tauri::command]
+pub async fn set_mosque_contexts(contexts: MosqueContexts, db: State<'_, Database>) -> CommandResult<MosqueContexts> {
+    contexts.validate().map_err(CommandError::invalid_input)?;
+
+    let json = serde_json::to_string(&contexts).map_err(|e| CommandError::internal(e.to_string()))?;
+    db.set_setting(MOSQUE_CONTEXTS_KEY, &json)
+        .await
+        .map_err(CommandError::database)?;
+    Ok(contexts)
+}
+
+async fn load_mosque_contexts(db: &Database) -> MosqueContexts {
+    match db.get_setting(MOSQUE_CONTEXTS_KEY).await {
+        Ok(Some(json)) => serde_json::from_str(&json).unwrap_or_default(),
+        _ => MosqueContexts::default(),
+    }
+}
+
+/// The given mosque, or the default profile's selected mosque
+pub(crate) async fn mosque_or_selected(db: &Database, mosque_id: Option<String>) -> CommandResult<String> {
+    match mosque_id {
+        Some(id) => Ok(id),
+        None => load_selected_mosque(db, None)
+            .await?
+            .map(|selected| selected.mosque.id)
+            .ok_or_else(|| CommandError::not_found("No mosque selected")),
+    }
+}
+
+/// Moment an engine-backed command evaluates at, and the local date it falls on
+/// `at` (RFC3339) previews another time, `utc_offset_minutes` replaces the system timezone
+pub(crate) fn evaluation_time(
+    at: Option<String>,
+    utc_offset_minutes: Option<i32>,
+    default_now: DateTime<Utc>,
+) -> CommandResult<(DateTime<Utc>, NaiveDate)> {
+    let now = match at {
+        Some(at) => DateTime::parse_from_rfc3339(&at)
+            .map_err(|e| CommandError::invalid_input(format!("Invalid time '{}': {}", at, e)))?
+            .with_timezone(&Utc),
+        None => default_now,
+    };
+    let offset = match utc_offset_minutes {
+        Some(minutes) => Some(
+            minutes
+                .checked_mul(60)
+                .and_then(FixedOffset::east_opt)
+                .ok_or_else(|| CommandError::invalid_input(format!("Invalid UTC offset: {} minutes", minutes)))?,
+        ),
+        None => None,
+    };
+
+    Ok((now, clock::date_at(now, offset)))
+}
+
+/// Travel time saved for a mosque, for `mode` or the mode set last
+pub(crate) async fn saved_travel_time(
+    db: &Database,
+    mosque_id: &str,
+    mode: Option<TravelMode>,
+) -> CommandResult<Option<i64>> {
+    db.get_travel_time(mosque_id, mode)
+        .await
+        .map_err(CommandError::database)
+}
+
 /// Get all prayer times for a mosque (optionally for a specific date)
 /// Date format: "YYYY-MM-DD" (e.g., "2026-02-05")
 #[tauri::command]
@@ -32,35 +208,42 @@ pub async fn get_prayer_times(
     mosque_id: String,
     date: Option<String>,
     db: State<'_, Database>,
-) -> Result<PrayerTimes, String> {
+) -> CommandResult<PrayerTimes> {
     let target_date = match date {
         Some(d) => NaiveDate::parse_from_str(&d, "%Y-%m-%d")
-            .map_err(|e| format!("Invalid date format. Use YYYY-MM-DD: {}", e))?,
-        None => chrono::Local::now().date_naive(),
+            .map_err(|e| CommandError::invalid_input(format!("Invalid date format. Use YYYY-MM-DD: {}", e)))?,
+        None => clock::today(),
     };
 
-    db.get_prayer_times(&mosque_id, target_date)
+    let times = db
+        .get_prayer_times(&mosque_id, target_date)
         .await
-        .map_err(|e| format!("Database error: {}", e))?
-        .ok_or_else(|| format!("No prayer times found for {}", target_date))
+        .map_err(CommandError::database)?
+        .ok_or_else(|| CommandError::not_found(format!("No prayer times found for {}", target_date)))?;
+
+    Ok(with_freshness(&db, times).await)
 }
 
 /// Get all prayer countdowns
 #[tauri::command]
 pub async fn get_all_countdowns(
-    mosque_id: String,
+    mosque_id: Option<String>,
+    at: Option<String>,
+    utc_offset_minutes: Option<i32>,
     db: State<'_, Database>,
-) -> Result<Vec<PrayerCountdown>, String> {
-    let today = chrono::Local::now().date_naive();
+) -> CommandResult<Vec<PrayerCountdown>> {
+    let mosque_id = mosque_or_selected(&db, mosque_id).await?;
+    let (now, today) = evaluation_time(at, utc_offset_minutes, ticker::countdown_now())?;
 
     let prayer_times = db
         .get_prayer_times(&mosque_id, today)
         .await
-        .map_err(|e| format!("Database error: {}", e))?
-        .ok_or_else(|| "No prayer times found".to_string())?;
+        .map_err(CommandError::database)?
+        .ok_or_else(|| CommandError::not_found("No prayer times found"))?;
 
-    let engine = PrayerEngine::with_defaults();
-    let now = Utc::now();
+    let engine = PrayerEngine::new(user_engine_config(&db).await)
+        .with_availability(load_availability(&db, &mosque_id, &prayer_times).await);
+    let engine = with_start_timing(engine, &db, &mosque_id).await;
 
     Ok(engine.get_all_countdowns(&prayer_times, now))
 }
@@ -70,80 +253,377 @@ pub async fn get_all_countdowns(
 pub async fn estimate_rakah(
     mosque_id: String,
     prayer_name: String,
+    at: Option<String>,
+    utc_offset_minutes: Option<i32>,
     db: State<'_, Database>,
-) -> Result<RakahEstimate, String> {
-    let today = chrono::Local::now().date_naive();
+) -> CommandResult<RakahEstimate> {
+    let (now, today) = evaluation_time(at, utc_offset_minutes, clock::now())?;
 
     let prayer_times = db
         .get_prayer_times(&mosque_id, today)
         .await
-        .map_err(|e| format!("Database error: {}", e))?
-        .ok_or_else(|| "No prayer times found".to_string())?;
+        .map_err(CommandError::database)?
+        .ok_or_else(|| CommandError::not_found("No prayer times found"))?;
 
     let prayer = prayer_times
-        .get_prayer_by_name(&prayer_name)
-        .ok_or_else(|| format!("Prayer {} not found", prayer_name))?;
+        .get_prayer_by_name(parse_prayer_name(&prayer_name)?)
+        .ok_or_else(|| CommandError::not_found(format!("Prayer {} not found", prayer_name)))?;
 
-    let engine = PrayerEngine::with_defaults();
-    let now = Utc::now();
+    let engine = with_start_timing(PrayerEngine::new(user_engine_config(&db).await), &db, &mosque_id).await;
 
     Ok(engine.estimate_rakah(prayer, now))
 }
 
 /// Calculate travel prediction
+/// Without `travel_time_seconds`, uses the time saved for the mosque (for `travel_mode`, or the mode set last),
+/// then the selected mosque's travel time
 #[tauri::command]
 pub async fn calculate_travel_prediction(
     mosque_id: String,
     prayer_name: String,
-    travel_time_seconds: i64,
+    travel_time_seconds: Option<i64>,
+    travel_mode: Option<TravelMode>,
+    at: Option<String>,
+    utc_offset_minutes: Option<i32>,
     db: State<'_, Database>,
-) -> Result<TravelPrediction, String> {
-    let today = chrono::Local::now().date_naive();
+) -> CommandResult<TravelPrediction> {
+    let travel_time_seconds = match travel_time_seconds {
+        Some(secs) => secs,
+        None => saved_travel_time(&db, &mosque_id, travel_mode)
+            .await?
+            .ok_or_else(|| CommandError::invalid_input("No travel time given or saved for this mosque"))?,
+    };
+    let (now, today) = evaluation_time(at, utc_offset_minutes, clock::now())?;
 
     let prayer_times = db
         .get_prayer_times(&mosque_id, today)
         .await
-        .map_err(|e| format!("Database error: {}", e))?
-        .ok_or_else(|| "No prayer times found".to_string())?;
+        .map_err(CommandError::database)?
+        .ok_or_else(|| CommandError::not_found("No prayer times found"))?;
 
     let prayer = prayer_times
-        .get_prayer_by_name(&prayer_name)
-        .ok_or_else(|| format!("Prayer {} not found", prayer_name))?;
+        .get_prayer_by_name(parse_prayer_name(&prayer_name)?)
+        .ok_or_else(|| CommandError::not_found(format!("Prayer {} not found", prayer_name)))?;
 
-    let engine = PrayerEngine::with_defaults();
-    let now = Utc::now();
+    let engine = PrayerEngine::new(user_engine_config(&db).await)
+        .with_crowd_level(crowd_level(&db, &mosque_id, prayer.name).await);
+    let engine = with_start_timing(engine, &db, &mosque_id).await;
 
     Ok(engine.calculate_travel_prediction(prayer, travel_time_seconds, now))
 }
 
+/// Travel prediction for this week's Jumuah, aiming for a seat before the khutbah
+/// The target comes from `arrive_before_minutes`, else the `jumuah_arrive_before_minutes` setting,
+/// else how crowded Jumuah usually is; travel time falls back as in `calculate_travel_prediction`
+#[tauri::command]
+pub async fn calculate_jumuah_prediction(
+    mosque_id: String,
+    travel_time_seconds: Option<i64>,
+    travel_mode: Option<TravelMode>,
+    arrive_before_minutes: Option<i64>,
+    at: Option<String>,
+    utc_offset_minutes: Option<i32>,
+    db: State<'_, Database>,
+) -> CommandResult<JumuahTravelPrediction> {
+    let travel_time_seconds = match travel_time_seconds {
+        Some(secs) => secs,
+        None => saved_travel_time(&db, &mosque_id, travel_mode)
+            .await?
+            .ok_or_else(|| CommandError::invalid_input("No travel time given or saved for this mosque"))?,
+    };
+
+    let (now, today) = evaluation_time(at, utc_offset_minutes, clock::now())?;
+    let friday = today + Duration::days(
+        (Weekday::Fri.num_days_from_monday() as i64 - today.weekday().num_days_from_monday() as i64).rem_euclid(7),
+    );
+    let jumuah = db
+        .get_prayer_times(&mosque_id, friday)
+        .await
+        .map_err(CommandError::database)?
+        .and_then(|schedule| schedule.jumuah)
+        .ok_or_else(|| CommandError::not_found(format!("No Jumuah time found for {}", friday)))?;
+
+    let arrive_before_minutes = match arrive_before_minutes {
+        Some(minutes) => Some(minutes),
+        None => db
+            .get_setting("jumuah_arrive_before_minutes")
+            .await
+            .map_err(CommandError::database)?
+            .and_then(|value| value.parse().ok()),
+    };
+    if arrive_before_minutes.is_some_and(|minutes| minutes < 0) {
+        return Err(CommandError::invalid_input("Arrival target cannot be negative"));
+    }
+
+    let engine = PrayerEngine::new(user_engine_config(&db).await)
+        .with_crowd_level(crowd_level(&db, &mosque_id, PrayerName::Jumuah).await);
+
+    Ok(engine.calculate_jumuah_prediction(&jumuah, travel_time_seconds, arrive_before_minutes, now))
+}
+
+/// Localized snippet to paste into a family group chat, e.g. "Isha iqama 21:45 at An-Nour, leave by 21:25"
+/// Uses today's prayer, tomorrow's once it has started (fetched when not cached yet);
+/// the leave time needs a travel time saved for the mosque
+#[tauri::command]
+pub async fn generate_share_text(mosque_id: String, prayer: String, db: State<'_, Database>) -> CommandResult<String> {
+    let name = parse_prayer_name(&prayer)?;
+    let mosque = db
+        .get_mosque(&mosque_id)
+        .await
+        .map_err(CommandError::database)?
+        .ok_or_else(|| CommandError::not_found(format!("Mosque {} not found", mosque_id)))?;
+
+    let now = clock::now();
+    let today = clock::today();
+    let upcoming = |times: Option<PrayerTimes>| {
+        times?
+            .get_prayer_by_name(name)
+            .filter(|found| found.iqama.unwrap_or(found.adhan) > now)
+            .cloned()
+    };
+
+    let mut target = upcoming(db.get_prayer_times(&mosque_id, today).await.map_err(CommandError::database)?);
+    if let Some(tomorrow) = today.succ_opt().filter(|_| target.is_none()) {
+        let mut times = db.get_prayer_times(&mosque_id, tomorrow).await.map_err(CommandError::database)?;
+        if times.is_none() {
+            if let Err(e) = refresh_day(&db, &mosque_id, tomorrow).await {
+                log::warn!("Could not fetch tomorrow's times of {} to share: {}", mosque_id, e.message);
+            }
+            times = db.get_prayer_times(&mosque_id, tomorrow).await.map_err(CommandError::database)?;
+        }
+        target = upcoming(times);
+    }
+    // A time that has already passed is never shared
+    let target = target.ok_or_else(|| CommandError::not_found(format!("No upcoming {} time found", prayer)))?;
+
+    let leave_by = match saved_travel_time(&db, &mosque_id, None).await? {
+        Some(secs) => {
+            let engine = PrayerEngine::new(user_engine_config(&db).await)
+                .with_crowd_level(crowd_level(&db, &mosque_id, name).await);
+            let engine = with_start_timing(engine, &db, &mosque_id).await;
+            Some(engine.calculate_travel_prediction(&target, secs, now).recommended_leave_time)
+        }
+        None => None,
+    };
+
+    let formatter = user_formatter(&db).await;
+    Ok(formatter.language.share_text(
+        name,
+        target.iqama.is_some(),
+        &formatter.format_time(target.iqama.unwrap_or(target.adhan)),
+        &mosque.name,
+        leave_by.map(|time| formatter.format_time(time)).as_deref(),
+    ))
+}
+
 /// Get countdown to iqama
 #[tauri::command]
 pub async fn get_countdown(
     mosque_id: String,
     prayer_name: String,
+    at: Option<String>,
+    utc_offset_minutes: Option<i32>,
     db: State<'_, Database>,
-) -> Result<Option<i64>, String> {
-    let today = chrono::Local::now().date_naive();
+) -> CommandResult<Option<i64>> {
+    let (now, today) = evaluation_time(at, utc_offset_minutes, ticker::countdown_now())?;
 
     let prayer_times = db
         .get_prayer_times(&mosque_id, today)
         .await
-        .map_err(|e| format!("Database error: {}", e))?
-        .ok_or_else(|| "No prayer times found".to_string())?;
+        .map_err(CommandError::database)?
+        .ok_or_else(|| CommandError::not_found("No prayer times found"))?;
 
     let prayer = prayer_times
-        .get_prayer_by_name(&prayer_name)
-        .ok_or_else(|| format!("Prayer {} not found", prayer_name))?;
+        .get_prayer_by_name(parse_prayer_name(&prayer_name)?)
+        .ok_or_else(|| CommandError::not_found(format!("Prayer {} not found", prayer_name)))?;
 
-    let engine = PrayerEngine::with_defaults();
-    let now = Utc::now();
+    let engine = PrayerEngine::new(user_engine_config(&db).await);
 
     Ok(engine.get_countdown(prayer, now))
 }
 
-/// Format duration for display
+/// Which prayers a mosque holds, derived from today's schedule plus the user's edits
 #[tauri::command]
-pub fn format_duration(seconds: i64) -> String {
-    let engine = PrayerEngine::with_defaults();
-    engine.format_duration(seconds)
+pub async fn get_prayer_availability(
+    mosque_id: String,
+    db: State<'_, Database>,
+) -> CommandResult<PrayerAvailability> {
+    let prayer_times = db
+        .get_prayer_times(&mosque_id, clock::today())
+        .await
+        .map_err(CommandError::database)?;
+
+    Ok(match prayer_times {
+        Some(times) => load_availability(&db, &mosque_id, &times).await,
+        None => user_availability(&db, &mosque_id).await.unwrap_or_default(),
+    })
+}
+
+/// Days ahead whose warnings are rechecked when the user changes a mosque's availability
+const AVAILABILITY_RECHECK_DAYS: i64 = 366;
+
+/// Save the user's open/closed and per-prayer flags for a mosque
+/// Cached days from today on are rechecked, so prayers no longer held stop warning of a missing iqama
+#[tauri::command]
+pub async fn set_prayer_availability(
+    mosque_id: String,
+    availability: PrayerAvailability,
+    db: State<'_, Database>,
+) -> CommandResult<()> {
+    db.set_prayer_availability(&mosque_id, &availability)
+        .await
+        .map_err(CommandError::database)?;
+
+    let today = clock::today();
+    let mut days = db
+        .get_prayer_times_range(&mosque_id, today, today + Duration::days(AVAILABILITY_RECHECK_DAYS))
+        .await
+        .map_err(CommandError::database)?;
+    for times in &mut days {
+        times.refresh_warnings(&PrayerAvailability::for_schedule(times, Some(&availability)));
+    }
+    db.save_prayer_times_bulk(&days).await.map_err(CommandError::database)
+}
+
+async fn user_availability(db: &Database, mosque_id: &str) -> Option<PrayerAvailability> {
+    db.get_prayer_availability(mosque_id).await.ok().flatten()
+}
+
+/// Default engine settings with the user's `asr_method`, `isha_end`, imsak offset and countdown display thresholds
+pub(crate) async fn user_engine_config(db: &Database) -> PrayerEngineConfig {
+    let defaults = PrayerEngineConfig::default();
+
+    PrayerEngineConfig {
+        asr_method: parsed_setting(db, "asr_method").await.unwrap_or_default(),
+        isha_end: parsed_setting(db, "isha_end").await.unwrap_or_default(),
+        imsak_minutes_before_fajr: parsed_setting(db, IMSAK_MINUTES_KEY)
+            .await
+            .unwrap_or(defaults.imsak_minutes_before_fajr),
+        countdown_seconds_threshold_secs: parsed_setting::<i64>(db, COUNTDOWN_SECONDS_THRESHOLD_KEY)
+            .await
+            .map_or(defaults.countdown_seconds_threshold_secs, |minutes| minutes * 60),
+        countdown_now_threshold_secs: parsed_setting(db, COUNTDOWN_NOW_THRESHOLD_KEY)
+            .await
+            .unwrap_or(defaults.countdown_now_threshold_secs),
+        ..defaults
+    }
+}
+
+async fn parsed_setting<T: std::str::FromStr>(db: &Database, key: &str) -> Option<T> {
+    db.get_setting(key).await.ok().flatten()?.parse().ok()
+}
+
+pub(crate) async fn load_availability(
+    db: &Database,
+    mosque_id: &str,
+    schedule: &PrayerTimes,
+) -> PrayerAvailability {
+    PrayerAvailability::for_schedule(schedule, user_availability(db, mosque_id).await.as_ref())
+}
+
+/// Start lag for a mosque, the default for all prayers and optionally per prayer (seconds)
+/// Overrides the lag learned from iqama reports
+#[tauri::command]
+pub async fn set_start_lag(
+    mosque_id: String,
+    start_lag: MosqueStartLag,
+    db: State<'_, Database>,
+) -> CommandResult<()> {
+    if !start_lag.is_valid() {
+        return Err(CommandError::invalid_input(format!(
+            "Start lag must be between 0 and {} seconds",
+            MosqueStartLag::MAX_SECS
+        )));
+    }
+    let json = serde_json::to_string(&start_lag).map_err(|e| CommandError::internal(e.to_string()))?;
+
+    db.set_setting(&start_lag_key(&mosque_id), &json)
+        .await
+        .map_err(CommandError::database)
+}
+
+/// Start lag set for a mosque; empty when none was set
+#[tauri::command]
+pub async fn get_start_lag(mosque_id: String, db: State<'_, Database>) -> CommandResult<MosqueStartLag> {
+    Ok(user_start_lag(&db, &mosque_id).await.unwrap_or_default())
+}
+
+fn start_lag_key(mosque_id: &str) -> String {
+    format!("start_lag:{}", mosque_id)
+}
+
+async fn user_start_lag(db: &Database, mosque_id: &str) -> Option<MosqueStartLag> {
+    let json = db.get_setting(&start_lag_key(mosque_id)).await.ok().flatten()?;
+    serde_json::from_str(&json).ok()
+}
+
+/// When the mosque's prayers start and how reliably: the user's start lag over what iqama reports taught
+pub(crate) async fn with_start_timing(engine: PrayerEngine, db: &Database, mosque_id: &str) -> PrayerEngine {
+    let learned = learned_delays(db, mosque_id).await;
+    let start_lag = user_start_lag(db, mosque_id).await.unwrap_or_default();
+
+    engine
+        .with_start_lags(start_lag.resolve(&learned_start_lags(&learned)))
+        .with_start_spreads(learned_start_spreads(&learned))
+}
+
+pub(crate) fn parse_prayer_name(prayer_name: &str) -> CommandResult<PrayerName> {
+    prayer_name.parse().map_err(CommandError::invalid_input)
+}
+
+/// Format duration for display in the configured language
+#[tauri::command]
+pub async fn format_duration(seconds: i64, db: State<'_, Database>) -> CommandResult<String> {
+    Ok(user_formatter(&db).await.format_duration(seconds))
+}
+
+/// Format an RFC3339 time in local time using the `time_format` and `language` settings
+#[tauri::command]
+pub async fn format_prayer_time(time: String, db: State<'_, Database>) -> CommandResult<String> {
+    let time = chrono::DateTime::parse_from_rfc3339(&time)
+        .map_err(|e| CommandError::invalid_input(format!("Invalid time '{}': {}", time, e)))?;
+
+    Ok(user_formatter(&db)
+        .await
+        .format_time(time.with_timezone(&chrono::Utc)))
+}
+
+/// Localized display names for every prayer, keyed by prayer id
+/// Uses the `language` setting unless a language code is given
+#[tauri::command]
+pub async fn get_prayer_names(
+    language: Option<String>,
+    db: State<'_, Database>,
+) -> CommandResult<HashMap<PrayerName, String>> {
+    let language = match language {
+        Some(code) => Language::from_code(&code),
+        None => user_language(&db).await,
+    };
+
+    Ok(PrayerName::DAILY
+        .iter()
+        .chain(std::iter::once(&PrayerName::Jumuah))
+        .map(|&name| (name, language.prayer_name(name).to_string()))
+        .collect())
+}
+
+/// Tray title counting down to the next prayer at the selected mosque, `None` until its schedule is known
+pub(crate) async fn tray_title(db: &Database) -> Option<String> {
+    let mosque_id = mosque_or_selected(db, None).await.ok()?;
+    let next = next_prayer_at(db, &mosque_id, ticker::countdown_now(), clock::today()).await.ok()?;
+    Some(user_formatter(db).await.tray_title(&next))
+}
+
+pub(crate) async fn user_formatter(db: &Database) -> DisplayFormatter {
+    let language = db.get_setting("language").await.ok().flatten();
+    let time_format = db.get_setting("time_format").await.ok().flatten();
+
+    DisplayFormatter::from_settings(language.as_deref(), time_format.as_deref())
+}
+
+pub(crate) async fn user_language(db: &Database) -> Language {
+    match db.get_setting("language").await {
+        Ok(Some(code)) => Language::from_code(&code),
+        _ => Language::default(),
+    }
 }
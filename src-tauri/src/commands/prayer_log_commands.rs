@@ -0,0 +1,192 @@
+use chrono::NaiveDate;
+use tauri::State;
+
+use crate::commands::error::{CommandError, CommandResult};
+use crate::db::Database;
+use crate::models::*;
+use crate::services::clock;
+
+/// Longest family view, a year
+const MAX_FAMILY_RANGE_DAYS: i64 = 365;
+
+/// Log a prayer for a profile (the default one if omitted), with optional tags and a reflection
+/// Date format: "YYYY-MM-DD", defaults to today; logging the same prayer again replaces the entry
+#[tauri::command]
+pub async fn log_prayer(
+    prayer_name: String,
+    date: Option<String>,
+    mosque_id: Option<String>,
+    tags: Option<Vec<PrayerLogTag>>,
+    note: Option<String>,
+    profile: Option<String>,
+    db: State<'_, Database>,
+) -> CommandResult<PrayerLogEntry> {
+    let entry = PrayerLogEntry {
+        profile: profile.unwrap_or_else(|| DEFAULT_PROFILE.to_string()),
+        prayer: prayer_name.parse().map_err(CommandError::invalid_input)?,
+        date: parse_log_date(date)?,
+        mosque_id,
+        tags: validated_tags(tags.unwrap_or_default())?,
+        note: validated_note(note)?,
+        logged_at: clock::now(),
+    };
+
+    db.save_prayer_log_entry(&entry)
+        .await
+        .map_err(CommandError::database)?;
+
+    Ok(entry)
+}
+
+/// Logged prayers matching the filter, oldest first; everything when omitted
+#[tauri::command]
+pub async fn get_prayer_log(
+    query: Option<PrayerLogQuery>,
+    db: State<'_, Database>,
+) -> CommandResult<Vec<PrayerLogEntry>> {
+    db.get_prayer_log(&query.unwrap_or_default())
+        .await
+        .map_err(CommandError::database)
+}
+
+/// Remove a logged prayer; returns whether there was one
+#[tauri::command]
+pub async fn delete_prayer_log_entry(
+    prayer_name: String,
+    date: String,
+    profile: Option<String>,
+    db: State<'_, Database>,
+) -> CommandResult<bool> {
+    let prayer: PrayerName = prayer_name.parse().map_err(CommandError::invalid_input)?;
+
+    db.delete_prayer_log_entry(profile.as_deref().unwrap_or(DEFAULT_PROFILE), prayer, parse_log_date(Some(date))?)
+        .await
+        .map_err(CommandError::database)
+}
+
+/// Attendance of several profiles side by side, e.g. the kids' Fajr, optionally for one prayer
+/// Dates are "YYYY-MM-DD"; the range defaults to the last 7 days. Each profile keeps its own
+/// log and notification settings, this view only reads them
+#[tauri::command]
+pub async fn get_family_attendance(
+    profiles: Vec<String>,
+    from: Option<String>,
+    to: Option<String>,
+    prayer_name: Option<String>,
+    db: State<'_, Database>,
+) -> CommandResult<FamilyAttendance> {
+    let mut unique: Vec<String> = Vec::new();
+    for profile in profiles {
+        if !unique.contains(&profile) {
+            unique.push(profile);
+        }
+    }
+    if unique.is_empty() {
+        return Err(CommandError::invalid_input("Select at least one profile"));
+    }
+
+    let to = parse_log_date(to)?;
+    let from = match from {
+        Some(from) => parse_log_date(Some(from))?,
+        None => to - chrono::Duration::days(6),
+    };
+    if from > to || (to - from).num_days() > MAX_FAMILY_RANGE_DAYS {
+        return Err(CommandError::invalid_input(format!(
+            "Range must run forwards and cover at most {} days",
+            MAX_FAMILY_RANGE_DAYS + 1
+        )));
+    }
+    let prayer: Option<PrayerName> = prayer_name
+        .map(|name| name.parse())
+        .transpose()
+        .map_err(CommandError::invalid_input)?;
+
+    let mut members = Vec::with_capacity(unique.len());
+    for profile in unique {
+        let selected = db.get_selected_mosque(&profile).await.map_err(CommandError::database)?;
+        members.push(MemberAttendance {
+            profile,
+            mosque_id: selected.map(|s| s.mosque.id),
+            logged: 0,
+            jamaah: 0,
+        });
+    }
+
+    let query = PrayerLogQuery {
+        from: Some(from),
+        to: Some(to),
+        prayer,
+        ..Default::default()
+    };
+    let entries = db.get_prayer_log(&query).await.map_err(CommandError::database)?;
+
+    Ok(FamilyAttendance::aggregate(members, &entries, from, to, prayer))
+}
+
+/// Log attendance at a mosque in congregation, keeping tags and notes already written for the prayer
+pub(crate) async fn log_attendance(db: &Database, mosque_id: &str, prayer: PrayerName) -> CommandResult<()> {
+    let date = clock::today();
+    let existing = db
+        .get_prayer_log_entry(DEFAULT_PROFILE, prayer, date)
+        .await
+        .map_err(CommandError::database)?;
+
+    let mut entry = existing.unwrap_or_else(|| PrayerLogEntry {
+        profile: DEFAULT_PROFILE.to_string(),
+        prayer,
+        date,
+        mosque_id: None,
+        tags: Vec::new(),
+        note: None,
+        logged_at: clock::now(),
+    });
+    entry.mosque_id = Some(mosque_id.to_string());
+    if !entry.has_tag(PrayerLogTag::Jamaah) {
+        entry.tags.push(PrayerLogTag::Jamaah);
+    }
+
+    db.save_prayer_log_entry(&entry)
+        .await
+        .map_err(CommandError::database)
+}
+
+fn parse_log_date(date: Option<String>) -> CommandResult<NaiveDate> {
+    match date {
+        Some(d) => NaiveDate::parse_from_str(&d, "%Y-%m-%d")
+            .map_err(|e| CommandError::invalid_input(format!("Invalid date format. Use YYYY-MM-DD: {}", e))),
+        None => Ok(clock::today()),
+    }
+}
+
+/// Tags without duplicates; a prayer can't be both on time and late
+fn validated_tags(tags: Vec<PrayerLogTag>) -> CommandResult<Vec<PrayerLogTag>> {
+    let mut unique = Vec::new();
+    for tag in tags {
+        if !unique.contains(&tag) {
+            unique.push(tag);
+        }
+    }
+
+    if unique.contains(&PrayerLogTag::OnTime) && unique.contains(&PrayerLogTag::Late) {
+        return Err(CommandError::invalid_input("A prayer can't be tagged both on_time and late"));
+    }
+
+    Ok(unique)
+}
+
+/// Trimmed note, `None` when blank
+fn validated_note(note: Option<String>) -> CommandResult<Option<String>> {
+    let note = note.map(|n| n.trim().to_string()).filter(|n| !n.is_empty());
+
+    if let Some(note) = &note {
+        let chars = note.chars().count();
+        if chars > MAX_PRAYER_NOTE_CHARS {
+            return Err(CommandError::invalid_input(format!(
+                "Note is {} characters, the limit is {}",
+                chars, MAX_PRAYER_NOTE_CHARS
+            )));
+        }
+    }
+
+    Ok(note)
+}
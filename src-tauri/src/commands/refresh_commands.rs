@@ -0,0 +1,115 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use tauri::State;
+use tokio::sync::Mutex;
+
+use crate::commands::error::CommandResult;
+use crate::commands::prayer_commands::{load_availability, user_engine_config};
+use crate::db::Database;
+use crate::models::*;
+use crate::services::{clock, system_on_battery, PrayerEngine, RefreshContext, RefreshPolicy};
+
+/// How long the next-iqama lookup used by the scheduler is reused
+const NEXT_IQAMA_TTL: Duration = Duration::from_secs(60);
+
+/// Power and visibility state the background scheduler adapts to
+pub struct RefreshState {
+    window_visible: AtomicBool,
+    /// Reported by the UI when the OS can't tell
+    reported_on_battery: Mutex<Option<bool>>,
+    next_iqama: Mutex<Option<(Instant, Option<DateTime<Utc>>)>>,
+}
+
+impl RefreshState {
+    pub fn new() -> Self {
+        Self {
+            window_visible: AtomicBool::new(true),
+            reported_on_battery: Mutex::new(None),
+            next_iqama: Mutex::new(None),
+        }
+    }
+
+    pub fn set_window_visible(&self, visible: bool) {
+        self.window_visible.store(visible, Ordering::Relaxed);
+    }
+
+    /// Current policy for the selected mosque's next iqama
+    pub async fn policy(&self, db: &Database) -> RefreshPolicy {
+        RefreshPolicy::for_context(&self.context(db).await)
+    }
+
+    async fn context(&self, db: &Database) -> RefreshContext {
+        let now = clock::now();
+        let on_battery = match system_on_battery() {
+            Some(on_battery) => Some(on_battery),
+            None => *self.reported_on_battery.lock().await,
+        };
+
+        RefreshContext {
+            on_battery,
+            window_visible: self.window_visible.load(Ordering::Relaxed),
+            seconds_to_iqama: self.next_iqama(db).await.map(|iqama| (iqama - now).num_seconds()),
+        }
+    }
+
+    async fn next_iqama(&self, db: &Database) -> Option<DateTime<Utc>> {
+        let mut cached = self.next_iqama.lock().await;
+        let now = clock::now();
+
+        let fresh = matches!(*cached, Some((at, iqama)) if at.elapsed() < NEXT_IQAMA_TTL && iqama.is_none_or(|t| t > now));
+        if !fresh {
+            *cached = Some((Instant::now(), selected_mosque_next_iqama(db).await));
+        }
+
+        cached.and_then(|(_, iqama)| iqama)
+    }
+}
+
+impl Default for RefreshState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Report window visibility and battery state from the UI
+/// Battery state is only used when the OS doesn't expose it to the backend
+#[tauri::command]
+pub async fn report_app_state(
+    visible: Option<bool>,
+    on_battery: Option<bool>,
+    refresh: State<'_, RefreshState>,
+) -> CommandResult<()> {
+    if let Some(visible) = visible {
+        refresh.set_window_visible(visible);
+    }
+    if on_battery.is_some() {
+        *refresh.reported_on_battery.lock().await = on_battery;
+    }
+
+    Ok(())
+}
+
+/// The tick interval and network policy the background scheduler is using
+#[tauri::command]
+pub async fn get_refresh_policy(
+    db: State<'_, Database>,
+    refresh: State<'_, RefreshState>,
+) -> CommandResult<RefreshPolicy> {
+    Ok(refresh.policy(&db).await)
+}
+
+async fn selected_mosque_next_iqama(db: &Database) -> Option<DateTime<Utc>> {
+    let selected = db.get_selected_mosque(DEFAULT_PROFILE).await.ok().flatten()?;
+    let mosque_id = selected.mosque.id;
+    let schedule = db.get_prayer_times(&mosque_id, clock::today()).await.ok().flatten()?;
+
+    let engine = PrayerEngine::new(user_engine_config(db).await)
+        .with_availability(load_availability(db, &mosque_id, &schedule).await);
+    let now = clock::now();
+    let next: NextPrayerResult = engine.get_next_prayer(&schedule, now);
+
+    next.time_until_iqama_secs
+        .map(|secs| now + chrono::Duration::seconds(secs))
+}
@@ -0,0 +1,247 @@
+use chrono::{DateTime, Utc};
+use tauri::{AppHandle, State};
+use tokio::sync::Mutex;
+
+use crate::commands::error::{CommandError, CommandResult};
+use crate::commands::prayer_commands::{
+    load_availability, mosque_or_selected, user_engine_config, user_formatter, with_start_timing,
+};
+use crate::commands::settings_commands::load_reminder_minutes;
+use crate::db::Database;
+use crate::models::{MosqueNotificationOverride, NotificationProfile, NotificationProfiles, NotificationTemplates};
+use crate::services::{
+    clock, ticker, NotificationConfig, NotificationScheduler, PrayerEngine, PrayerNotification, ReminderTracker,
+    TemplateValues, DEFAULT_SNOOZE_MINUTES,
+};
+
+const NOTIFICATION_TEMPLATES_KEY: &str = "notification_templates";
+const NOTIFICATION_PROFILES_KEY: &str = "notification_profiles";
+const FAJR_ENDING_KEY: &str = "fajr_ending_reminder_minutes";
+
+/// Adhan and iqama reminders for the selected mosque, driven by the background ticker
+pub struct ReminderState {
+    tracker: Mutex<ReminderTracker>,
+}
+
+impl ReminderState {
+    pub fn new() -> Self {
+        Self {
+            tracker: Mutex::new(ReminderTracker::default()),
+        }
+    }
+
+    /// Reminders to show now; each one is returned once, a snoozed one again when it is due
+    pub async fn tick(&self, db: &Database) -> Vec<PrayerNotification> {
+        let now = ticker::countdown_now();
+        let due = due_reminders(db, now).await;
+        self.tracker.lock().await.take_due(due, now)
+    }
+
+    /// Put off the last reminder; returns when it comes back
+    pub async fn snooze(&self, minutes: i64) -> Option<DateTime<Utc>> {
+        self.tracker.lock().await.snooze(minutes, clock::now())
+    }
+}
+
+impl Default for ReminderState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+async fn due_reminders(db: &Database, now: DateTime<Utc>) -> Vec<PrayerNotification> {
+    if matches!(db.get_setting("notification_enabled").await, Ok(Some(v)) if v == "false") {
+        return Vec::new();
+    }
+    let Ok(mosque_id) = mosque_or_selected(db, None).await else {
+        return Vec::new();
+    };
+    let Some(times) = db.get_prayer_times(&mosque_id, clock::today()).await.ok().flatten() else {
+        return Vec::new();
+    };
+
+    let engine = PrayerEngine::new(user_engine_config(db).await)
+        .with_availability(load_availability(db, &mosque_id, &times).await);
+    let countdowns = with_start_timing(engine, db, &mosque_id).await.get_all_countdowns(&times, now);
+
+    let mosque = match db.get_mosque(&mosque_id).await {
+        Ok(Some(mosque)) => mosque.name,
+        _ => times.mosque_name.clone().unwrap_or_default(),
+    };
+    NotificationScheduler::new(notification_config_for(db, Some(&mosque_id)).await)
+        .with_templates(load_notification_templates(db).await, TemplateValues::new(mosque, &times))
+        .due_reminders(&user_formatter(db).await, &countdowns)
+}
+
+/// Reminder settings for the selected mosque, read on every tick so a new selection applies at once
+pub async fn notification_config(db: &Database) -> NotificationConfig {
+    let mosque_id = mosque_or_selected(db, None).await.ok();
+    notification_config_for(db, mosque_id.as_deref()).await
+}
+
+/// Reminder settings, with sound, speech and prayers from the notification profile in effect,
+/// narrowed by the mosque's override if it has one
+pub async fn notification_config_for(db: &Database, mosque_id: Option<&str>) -> NotificationConfig {
+    let mut config = NotificationConfig {
+        reminder_minutes_before: load_reminder_minutes(db).await,
+        fajr_ending_minutes: load_fajr_ending_minutes(db).await,
+        ..Default::default()
+    };
+    if let Some(profile) = active_profile(db).await {
+        config = config.with_profile(&profile);
+    }
+    let mosque_override = match mosque_id {
+        Some(id) => db.get_mosque_notification_override(id).await.ok().flatten(),
+        None => None,
+    };
+    match mosque_override {
+        Some(mosque_override) => config.with_mosque_override(&mosque_override),
+        None => config,
+    }
+}
+
+/// Minutes before sunrise to warn that Fajr is ending; off unless the user sets 1 to 120
+async fn load_fajr_ending_minutes(db: &Database) -> Option<i64> {
+    db.get_setting(FAJR_ENDING_KEY)
+        .await
+        .ok()
+        .flatten()
+        .and_then(|minutes| minutes.parse().ok())
+        .filter(|minutes| (1..=120).contains(minutes))
+}
+
+async fn active_profile(db: &Database) -> Option<NotificationProfile> {
+    let local = clock::now().with_timezone(&chrono::Local).naive_local();
+    load_notification_profiles(db).await.active(local).cloned()
+}
+
+#[tauri::command]
+pub async fn get_notification_profiles(db: State<'_, Database>) -> CommandResult<NotificationProfiles> {
+    Ok(load_notification_profiles(&db).await)
+}
+
+/// Save notification profiles and their weekly schedule; `manual` picks one by hand, e.g. when travelling
+#[tauri::command]
+pub async fn set_notification_profiles(
+    profiles: NotificationProfiles,
+    db: State<'_, Database>,
+) -> CommandResult<NotificationProfiles> {
+    profiles.validate().map_err(CommandError::invalid_input)?;
+
+    let json = serde_json::to_string(&profiles).map_err(|e| CommandError::internal(e.to_string()))?;
+    db.set_setting(NOTIFICATION_PROFILES_KEY, &json)
+        .await
+        .map_err(CommandError::database)?;
+    Ok(profiles)
+}
+
+/// The profile reminders follow right now, `None` for the regular behaviour
+#[tauri::command]
+pub async fn get_active_notification_profile(db: State<'_, Database>) -> CommandResult<Option<NotificationProfile>> {
+    Ok(active_profile(&db).await)
+}
+
+async fn load_notification_profiles(db: &Database) -> NotificationProfiles {
+    match db.get_setting(NOTIFICATION_PROFILES_KEY).await {
+        Ok(Some(json)) => serde_json::from_str(&json).unwrap_or_default(),
+        _ => NotificationProfiles::default(),
+    }
+}
+
+/// Mosques whose reminders differ from the notification profile
+#[tauri::command]
+pub async fn get_mosque_notification_overrides(
+    db: State<'_, Database>,
+) -> CommandResult<Vec<MosqueNotificationOverride>> {
+    db.get_mosque_notification_overrides()
+        .await
+        .map_err(CommandError::database)
+}
+
+/// Mute or narrow reminders for a saved mosque, e.g. only Dhuhr near work and nothing at a mosque browsed once
+#[tauri::command]
+pub async fn set_mosque_notification_override(
+    mosque_override: MosqueNotificationOverride,
+    db: State<'_, Database>,
+) -> CommandResult<MosqueNotificationOverride> {
+    mosque_override.validate().map_err(CommandError::invalid_input)?;
+    if db
+        .get_mosque(&mosque_override.mosque_id)
+        .await
+        .map_err(CommandError::database)?
+        .is_none()
+    {
+        return Err(CommandError::not_found(format!("Mosque {} is not saved", mosque_override.mosque_id)));
+    }
+
+    db.set_mosque_notification_override(&mosque_override)
+        .await
+        .map_err(CommandError::database)?;
+    Ok(mosque_override)
+}
+
+/// Let a mosque's reminders follow the notification profile again; false if it had no override
+#[tauri::command]
+pub async fn clear_mosque_notification_override(mosque_id: String, db: State<'_, Database>) -> CommandResult<bool> {
+    db.delete_mosque_notification_override(&mosque_id)
+        .await
+        .map_err(CommandError::database)
+}
+
+#[tauri::command]
+pub async fn get_notification_templates(db: State<'_, Database>) -> CommandResult<NotificationTemplates> {
+    Ok(load_notification_templates(&db).await)
+}
+
+/// Save custom reminder wording; rejected when a template uses an unknown placeholder
+#[tauri::command]
+pub async fn set_notification_templates(
+    templates: NotificationTemplates,
+    db: State<'_, Database>,
+) -> CommandResult<NotificationTemplates> {
+    templates.validate().map_err(CommandError::invalid_input)?;
+
+    let json = serde_json::to_string(&templates).map_err(|e| CommandError::internal(e.to_string()))?;
+    db.set_setting(NOTIFICATION_TEMPLATES_KEY, &json)
+        .await
+        .map_err(CommandError::database)?;
+    Ok(templates)
+}
+
+async fn load_notification_templates(db: &Database) -> NotificationTemplates {
+    match db.get_setting(NOTIFICATION_TEMPLATES_KEY).await {
+        Ok(Some(json)) => serde_json::from_str(&json).unwrap_or_default(),
+        _ => NotificationTemplates::default(),
+    }
+}
+
+/// Repeat the last prayer reminder after `minutes` (5 by default)
+#[tauri::command]
+pub async fn snooze_reminder(
+    minutes: Option<u32>,
+    reminders: State<'_, ReminderState>,
+) -> CommandResult<DateTime<Utc>> {
+    let minutes = minutes.map(i64::from).unwrap_or(DEFAULT_SNOOZE_MINUTES);
+    if !(1..=60).contains(&minutes) {
+        return Err(CommandError::invalid_input("Snooze for 1 to 60 minutes"));
+    }
+
+    reminders
+        .snooze(minutes)
+        .await
+        .ok_or_else(|| CommandError::not_found("No reminder to snooze"))
+}
+
+/// Show a notification from the app, with the system's notification sound or silently
+pub fn show_notification(app: &AppHandle, title: &str, body: &str, sound: bool) {
+    let identifier = app.config().tauri.bundle.identifier.clone();
+    let mut notification = tauri::api::notification::Notification::new(&identifier)
+        .title(title)
+        .body(body);
+    if sound {
+        notification = notification.sound(tauri::api::notification::Sound::Default);
+    }
+    if let Err(e) = notification.show() {
+        log::warn!("Failed to show notification: {}", e);
+    }
+}
@@ -0,0 +1,180 @@
+use std::any::Any;
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use futures::future::{BoxFuture, Shared};
+use futures::FutureExt;
+
+use crate::commands::error::{CommandError, CommandResult};
+
+/// Calls allowed per command within `RATE_WINDOW`
+const RATE_LIMIT: usize = 20;
+const RATE_WINDOW: Duration = Duration::from_secs(10);
+
+type SharedRequest<T> = Shared<BoxFuture<'static, CommandResult<T>>>;
+
+/// Identifies equivalent requests: the command and its arguments (mosque, date...)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RequestKey {
+    pub command: &'static str,
+    pub args: String,
+}
+
+impl RequestKey {
+    pub fn new(command: &'static str, args: &[&str]) -> Self {
+        Self {
+            command,
+            args: args.join("|"),
+        }
+    }
+}
+
+/// Guards commands against UI render loops
+/// Identical concurrent requests share one provider/database call; expensive commands are also
+/// limited to `RATE_LIMIT` new calls per `RATE_WINDOW`, while joining an in-flight request is free
+pub struct RequestGate {
+    /// Shared futures of running requests, type-erased by output
+    in_flight: Mutex<HashMap<RequestKey, Box<dyn Any + Send>>>,
+    calls: Mutex<HashMap<&'static str, VecDeque<Instant>>>,
+}
+
+impl RequestGate {
+    pub fn new() -> Self {
+        Self {
+            in_flight: Mutex::new(HashMap::new()),
+            calls: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Run `request`, or join an identical one already running
+    pub async fn coalesce<T, F>(&self, key: RequestKey, request: F) -> CommandResult<T>
+    where
+        T: Clone + Send + Sync + 'static,
+        F: Future<Output = CommandResult<T>> + Send + 'static,
+    {
+        self.run(key, request, false).await
+    }
+
+    /// Like `coalesce`, but fails with `RateLimited` when the command starts too many new calls
+    pub async fn coalesce_limited<T, F>(&self, key: RequestKey, request: F) -> CommandResult<T>
+    where
+        T: Clone + Send + Sync + 'static,
+        F: Future<Output = CommandResult<T>> + Send + 'static,
+    {
+        self.run(key, request, true).await
+    }
+
+    async fn run<T, F>(&self, key: RequestKey, request: F, limited: bool) -> CommandResult<T>
+    where
+        T: Clone + Send + Sync + 'static,
+        F: Future<Output = CommandResult<T>> + Send + 'static,
+    {
+        let future = {
+            let mut in_flight = self.in_flight.lock().unwrap();
+
+            match in_flight.get(&key).and_then(|f| f.downcast_ref::<SharedRequest<T>>()) {
+                Some(existing) => {
+                    log::debug!("Joining in-flight request: {:?}", key);
+                    existing.clone()
+                }
+                None => {
+                    if limited {
+                        self.check_rate(key.command, Instant::now())?;
+                    }
+                    let future = request.boxed().shared();
+                    in_flight.insert(key.clone(), Box::new(future.clone()));
+                    future
+                }
+            }
+        };
+
+        let result = future.clone().await;
+
+        let mut in_flight = self.in_flight.lock().unwrap();
+        let finished = in_flight
+            .get(&key)
+            .and_then(|f| f.downcast_ref::<SharedRequest<T>>())
+            .is_some_and(|f| f.ptr_eq(&future));
+        if finished {
+            in_flight.remove(&key);
+        }
+
+        result
+    }
+
+    /// Record a call of `command`, failing once it exceeds the rate limit
+    fn check_rate(&self, command: &'static str, now: Instant) -> CommandResult<()> {
+        let mut calls = self.calls.lock().unwrap();
+        let recent = calls.entry(command).or_default();
+        while recent.front().is_some_and(|&at| now.duration_since(at) >= RATE_WINDOW) {
+            recent.pop_front();
+        }
+
+        if recent.len() >= RATE_LIMIT {
+            let retry_after = recent
+                .front()
+                .map(|&oldest| RATE_WINDOW.saturating_sub(now.duration_since(oldest)))
+                .unwrap_or(RATE_WINDOW);
+            log::warn!("Rate limiting {}: {} calls in {:?}", command, recent.len(), RATE_WINDOW);
+            return Err(CommandError::rate_limited(
+                format!("{} was called too often, try again shortly", command),
+                retry_after.as_secs().max(1),
+            ));
+        }
+
+        recent.push_back(now);
+        Ok(())
+    }
+}
+
+impl Default for RequestGate {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::error::CommandErrorKind;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_concurrent_requests_share_one_call() {
+        let gate = RequestGate::new();
+        let calls = Arc::new(AtomicUsize::new(0));
+        let request = || {
+            let calls = calls.clone();
+            async move {
+                calls.fetch_add(1, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                Ok(42)
+            }
+        };
+        let key = || RequestKey::new("get_prayer_times_for_mosque", &["mosque-paris", "2026-03-01"]);
+
+        let (a, b) = tokio::join!(gate.coalesce(key(), request()), gate.coalesce(key(), request()));
+        assert_eq!((a.unwrap(), b.unwrap()), (42, 42));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_rate_limit() {
+        let gate = RequestGate::new();
+        let start = Instant::now();
+
+        for _ in 0..RATE_LIMIT {
+            gate.check_rate("refresh_prayer_times", start).unwrap();
+        }
+        let limited = gate.check_rate("refresh_prayer_times", start).unwrap_err();
+        assert_eq!(limited.kind, CommandErrorKind::RateLimited);
+        assert_eq!(limited.retry_after_secs, Some(RATE_WINDOW.as_secs()));
+
+        // Other commands and later windows are unaffected
+        assert!(gate.check_rate("fetch_prayer_times_for_date", start).is_ok());
+        assert!(gate.check_rate("refresh_prayer_times", start + RATE_WINDOW).is_ok());
+    }
+}
@@ -0,0 +1,227 @@
+use chrono::{Duration, NaiveDate};
+use tauri::State;
+
+use crate::commands::error::{CommandError, CommandResult};
+use crate::commands::mosque_commands::fetch_from_providers;
+use crate::commands::request_gate::{RequestGate, RequestKey};
+use crate::commands::prayer_commands::user_formatter;
+use crate::db::Database;
+use crate::models::*;
+use crate::providers::{is_mock_mosque, MawaqitProvider};
+use crate::services::{clock, forecast_iqama_changes, PrayerNotification, MIN_FORECAST_SHIFT_MINUTES};
+
+/// Days ahead `get_upcoming_schedule_changes` looks by default
+pub(crate) const DEFAULT_FORECAST_DAYS: u32 = 14;
+
+/// How often cached schedules of favorite mosques are re-fetched
+pub const SCHEDULE_REFRESH_INTERVAL: std::time::Duration = std::time::Duration::from_secs(6 * 60 * 60);
+
+/// Retry delay while the refresh policy defers network work (battery, hidden window)
+pub const DEFERRED_REFRESH_RETRY: std::time::Duration = std::time::Duration::from_secs(15 * 60);
+
+/// Deferrals allowed before a refresh runs anyway, so schedules can't go stale for days
+pub const MAX_REFRESH_DEFERRALS: u32 = 4;
+
+/// Interrupted writes older than this are dropped instead of replayed
+const JOURNAL_MAX_AGE_DAYS: i64 = 7;
+
+/// Re-fetch a day from the providers, update the cache and record iqama changes
+/// Date format: "YYYY-MM-DD", defaults to today
+/// Rate limited; concurrent refreshes of the same day share one fetch
+#[tauri::command]
+pub async fn refresh_prayer_times(
+    mosque_id: String,
+    date: Option<String>,
+    db: State<'_, Database>,
+    gate: State<'_, RequestGate>,
+) -> CommandResult<Vec<ScheduleChange>> {
+    let target_date = match date {
+        Some(d) => NaiveDate::parse_from_str(&d, "%Y-%m-%d")
+            .map_err(|e| CommandError::invalid_input(format!("Invalid date format. Use YYYY-MM-DD: {}", e)))?,
+        None => clock::today(),
+    };
+
+    let key = RequestKey::new("refresh_prayer_times", &[&mosque_id, &target_date.to_string()]);
+    let db = db.inner().clone();
+    gate.coalesce_limited(key, async move { refresh_day(&db, &mosque_id, target_date).await })
+        .await
+}
+
+/// Recently detected iqama changes, newest first
+#[tauri::command]
+pub async fn get_schedule_changes(
+    mosque_id: Option<String>,
+    limit: Option<i64>,
+    db: State<'_, Database>,
+) -> CommandResult<Vec<ScheduleChange>> {
+    db.get_schedule_changes(mosque_id.as_deref(), limit.unwrap_or(50))
+        .await
+        .map_err(CommandError::database)
+}
+
+/// Iqama jumps coming up in the mosque's published Mawaqit calendar
+/// e.g. Isha moving 15 minutes later next Monday
+#[tauri::command]
+pub async fn get_upcoming_schedule_changes(
+    mosque_id: String,
+    days: Option<u32>,
+) -> CommandResult<Vec<UpcomingScheduleChange>> {
+    if is_mock_mosque(&mosque_id) {
+        return Ok(Vec::new());
+    }
+
+    let days = days.unwrap_or(DEFAULT_FORECAST_DAYS).clamp(1, 366);
+    let calendar = MawaqitProvider::new()
+        .get_iqama_calendar(&mosque_id, clock::today(), days + 1)
+        .await
+        .map_err(|e| CommandError::from_provider(PROVIDER_MAWAQIT, e))?;
+
+    Ok(forecast_iqama_changes(&calendar, MIN_FORECAST_SHIFT_MINUTES))
+}
+
+/// Refresh today and tomorrow for every favorite mosque
+/// Returns the changes found, for the background task to emit and notify
+pub async fn refresh_favorite_schedules(db: &Database) -> Vec<ScheduleChange> {
+    let favorites = match db.get_favorite_mosques().await {
+        Ok(favorites) => favorites,
+        Err(e) => {
+            log::warn!("Schedule refresh skipped, could not load favorites: {}", e);
+            return Vec::new();
+        }
+    };
+
+    let today = clock::today();
+    let mut changes = Vec::new();
+
+    for mosque in favorites.iter().filter(|m| !is_mock_mosque(&m.id)) {
+        // Each day is saved on its own, so tomorrow failing doesn't discard today
+        for date in [today, today + Duration::days(1)] {
+            match refresh_day(db, &mosque.id, date).await {
+                Ok(found) => changes.extend(found),
+                Err(e) => log::debug!("Schedule refresh for {} on {} failed: {}", mosque.id, date, e.message),
+            }
+        }
+    }
+
+    changes
+}
+
+/// Localized system notifications for changes, empty when notifications are disabled
+pub async fn schedule_change_notifications(
+    db: &Database,
+    changes: &[ScheduleChange],
+) -> Vec<PrayerNotification> {
+    if let Ok(Some(enabled)) = db.get_setting("notification_enabled").await {
+        if enabled == "false" {
+            return Vec::new();
+        }
+    }
+
+    let formatter = user_formatter(db).await;
+    changes
+        .iter()
+        .filter_map(|change| PrayerNotification::schedule_change(&formatter, change))
+        .collect()
+}
+
+/// Refetch a day, store it and record any iqama that moved
+/// Days written in admin mode are kept as they are
+pub(crate) async fn refresh_day(db: &Database, mosque_id: &str, date: NaiveDate) -> CommandResult<Vec<ScheduleChange>> {
+    refresh_days(db, mosque_id, date, date).await
+}
+
+/// Refetch `from..=to` and save every day with its changes in one transaction, or nothing at all
+/// Ranges longer than a day are journaled first, so a crash mid-fetch is finished on the next launch
+pub(crate) async fn refresh_days(
+    db: &Database,
+    mosque_id: &str,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> CommandResult<Vec<ScheduleChange>> {
+    let journal = if to > from {
+        let write = PendingWrite::RefreshSchedule {
+            mosque_id: mosque_id.to_string(),
+            from,
+            to,
+        };
+        Some(db.begin_write(&write).await.map_err(CommandError::database)?)
+    } else {
+        None
+    };
+
+    let result = complete_refresh(db, mosque_id, from, to, journal).await;
+    if let (Err(_), Some(id)) = (&result, journal) {
+        // Nothing was written; the next scheduled refresh tries again
+        db.discard_write(id).await.map_err(CommandError::database)?;
+    }
+    result
+}
+
+async fn complete_refresh(
+    db: &Database,
+    mosque_id: &str,
+    from: NaiveDate,
+    to: NaiveDate,
+    journal: Option<i64>,
+) -> CommandResult<Vec<ScheduleChange>> {
+    let mut days = Vec::new();
+    let mut changes = Vec::new();
+
+    for date in from.iter_days().take_while(|date| *date <= to) {
+        let cached = db.get_prayer_times(mosque_id, date).await.map_err(CommandError::database)?;
+        if cached.as_ref().is_some_and(|c| c.provider.as_deref() == Some(ADMIN_PROVIDER)) {
+            log::debug!("Keeping admin schedule of {} on {}", mosque_id, date);
+            continue;
+        }
+
+        let fresh = fetch_from_providers(db, mosque_id, None, date)
+            .await
+            .map_err(|e| CommandError::from_provider(PROVIDER_MAWAQIT, e))?;
+        if let Some(cached) = cached {
+            changes.extend(ScheduleChange::detect(mosque_id, &cached, &fresh, clock::now()));
+        }
+        days.push(fresh);
+    }
+
+    db.save_schedule_refresh(&days, &changes, journal)
+        .await
+        .map_err(CommandError::database)?;
+    if !changes.is_empty() {
+        log::info!("{} iqama change(s) detected for {} from {} to {}", changes.len(), mosque_id, from, to);
+    }
+
+    Ok(changes)
+}
+
+/// Finish schedule refreshes a crash or power cut interrupted; run once at startup
+/// Entries that fail again stay journaled for the next launch until they're too old to matter
+pub async fn replay_write_journal(db: &Database) {
+    let entries = match db.pending_writes().await {
+        Ok(entries) => entries,
+        Err(e) => {
+            log::warn!("Could not read the write journal: {}", e);
+            return;
+        }
+    };
+
+    for entry in entries {
+        if !entry.is_replayable(clock::today(), clock::now(), Duration::days(JOURNAL_MAX_AGE_DAYS)) {
+            log::info!("Dropping stale {} write from {}", entry.write.kind(), entry.started_at);
+            if let Err(e) = db.discard_write(entry.id).await {
+                log::warn!("Failed to drop write journal entry {}: {}", entry.id, e);
+            }
+            continue;
+        }
+
+        match &entry.write {
+            PendingWrite::RefreshSchedule { mosque_id, from, to } => {
+                // Days already past aren't worth fetching again
+                let from = (*from).max(clock::today());
+                match complete_refresh(db, mosque_id, from, *to, Some(entry.id)).await {
+                    Ok(_) => log::info!("Finished interrupted refresh of {} from {} to {}", mosque_id, from, to),
+                    Err(e) => log::warn!("Interrupted refresh of {} failed again: {}", mosque_id, e.message),
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,223 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use futures::future::{AbortHandle, Abortable, BoxFuture, Shared};
+use futures::FutureExt;
+
+use crate::commands::error::{CommandError, CommandResult};
+use crate::models::{GeoLocation, MosqueSearchParams, MosqueSearchResult};
+
+/// Delay before a search starts, so fast typing only runs the last query
+const SEARCH_DEBOUNCE: Duration = Duration::from_millis(250);
+
+type SearchOutput = CommandResult<MosqueSearchResult>;
+type SharedSearch = Shared<BoxFuture<'static, SearchOutput>>;
+
+/// Identifies equivalent searches: normalized query, country, page and rounded location
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SearchKey {
+    pub query: String,
+    pub country: Option<String>,
+    pub params: MosqueSearchParams,
+    /// Location in thousandths of a degree (about 100 m), as it changes the ranking
+    pub near: Option<(i64, i64)>,
+}
+
+impl SearchKey {
+    pub fn new(
+        query: &str,
+        country: Option<&str>,
+        params: &MosqueSearchParams,
+        location: Option<&GeoLocation>,
+    ) -> Self {
+        Self {
+            query: query.trim().to_lowercase(),
+            country: country.map(|c| c.to_uppercase()),
+            params: params.clone(),
+            near: location.map(|l| ((l.latitude * 1000.0).round() as i64, (l.longitude * 1000.0).round() as i64)),
+        }
+    }
+}
+
+struct InFlightSearch {
+    future: SharedSearch,
+    abort: AbortHandle,
+}
+
+/// Coordinates `search_mosques` calls from the UI
+/// Identical concurrent queries share one search; a different query cancels the ones in flight
+pub struct SearchCoordinator {
+    in_flight: Mutex<HashMap<SearchKey, InFlightSearch>>,
+}
+
+impl SearchCoordinator {
+    pub fn new() -> Self {
+        Self {
+            in_flight: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Run a search, joining an identical in-flight one and cancelling superseded ones
+    pub async fn run<F>(&self, key: SearchKey, search: F) -> SearchOutput
+    where
+        F: Future<Output = SearchOutput> + Send + 'static,
+    {
+        let future = {
+            let mut in_flight = self.in_flight.lock().unwrap();
+
+            in_flight.retain(|k, s| {
+                if *k != key {
+                    log::debug!("Cancelling superseded search: {:?}", k);
+                    s.abort.abort();
+                    false
+                } else {
+                    true
+                }
+            });
+
+            match in_flight.get(&key) {
+                Some(existing) => {
+                    log::debug!("Joining in-flight search: {:?}", key);
+                    existing.future.clone()
+                }
+                None => {
+                    let (abort, registration) = AbortHandle::new_pair();
+                    let debounced = async move {
+                        tokio::time::sleep(SEARCH_DEBOUNCE).await;
+                        search.await
+                    };
+                    let future = Abortable::new(debounced, registration)
+                        .map(|result| {
+                            result.unwrap_or_else(|_| {
+                                Err(CommandError::cancelled("Search superseded by a newer query"))
+                            })
+                        })
+                        .boxed()
+                        .shared();
+
+                    in_flight.insert(
+                        key.clone(),
+                        InFlightSearch {
+                            future: future.clone(),
+                            abort,
+                        },
+                    );
+                    future
+                }
+            }
+        };
+
+        let result = future.clone().await;
+
+        let mut in_flight = self.in_flight.lock().unwrap();
+        if in_flight
+            .get(&key)
+            .map(|s| s.future.ptr_eq(&future))
+            .unwrap_or(false)
+        {
+            in_flight.remove(&key);
+        }
+
+        result
+    }
+}
+
+impl Default for SearchCoordinator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::error::CommandErrorKind;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    fn key(query: &str) -> SearchKey {
+        SearchKey::new(query, Some("fr"), &MosqueSearchParams::default(), None)
+    }
+
+    /// A search that counts its runs and finds `total` mosques
+    fn search(runs: &Arc<AtomicUsize>, total: usize) -> impl Future<Output = SearchOutput> + Send + 'static {
+        let runs = runs.clone();
+        async move {
+            runs.fetch_add(1, Ordering::SeqCst);
+            Ok(MosqueSearchResult {
+                mosques: Vec::new(),
+                total,
+            })
+        }
+    }
+
+    #[test]
+    fn test_equivalent_searches_share_a_key() {
+        let params = MosqueSearchParams::default();
+        let paris = GeoLocation::new(48.8566, 2.3522);
+        let nearby = GeoLocation::new(48.85655, 2.35224);
+
+        assert_eq!(
+            SearchKey::new("  Grande Mosquée ", Some("fr"), &params, Some(&paris)),
+            SearchKey::new("grande mosquée", Some("FR"), &params, Some(&nearby)),
+        );
+        let next_page = MosqueSearchParams {
+            page: Some(2),
+            ..Default::default()
+        };
+        assert_ne!(key("paris"), SearchKey::new("paris", Some("fr"), &next_page, None));
+        assert_ne!(key("paris"), key("lyon"));
+    }
+
+    #[tokio::test]
+    async fn test_identical_searches_merge_into_one() {
+        let coordinator = SearchCoordinator::new();
+        let runs = Arc::new(AtomicUsize::new(0));
+
+        let (first, second) = tokio::join!(
+            coordinator.run(key("Paris"), search(&runs, 3)),
+            coordinator.run(key("paris "), search(&runs, 7)),
+        );
+
+        // The second caller gets the first search's result; its own never runs
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+        assert_eq!(first.unwrap().total, 3);
+        assert_eq!(second.unwrap().total, 3);
+        assert!(coordinator.in_flight.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_newer_query_cancels_superseded_search() {
+        let coordinator = Arc::new(SearchCoordinator::new());
+        let runs = Arc::new(AtomicUsize::new(0));
+
+        let superseded = tokio::spawn({
+            let coordinator = coordinator.clone();
+            let search = search(&runs, 1);
+            async move { coordinator.run(key("par"), search).await }
+        });
+        tokio::time::sleep(SEARCH_DEBOUNCE / 5).await;
+        let latest = coordinator.run(key("paris"), search(&runs, 2)).await;
+
+        let error = superseded.await.unwrap().unwrap_err();
+        assert_eq!(error.kind, CommandErrorKind::Cancelled);
+        assert_eq!(latest.unwrap().total, 2);
+        // Cancelled while debouncing, so only the latest query hit the providers
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_search_starts_after_debounce() {
+        let coordinator = SearchCoordinator::new();
+        let runs = Arc::new(AtomicUsize::new(0));
+
+        let started = std::time::Instant::now();
+        let result = coordinator.run(key("paris"), search(&runs, 1)).await;
+
+        assert!(started.elapsed() >= SEARCH_DEBOUNCE);
+        assert_eq!(result.unwrap().total, 1);
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+    }
+}
@@ -0,0 +1,709 @@
+use std::collections::HashSet;
+use std::time::Duration;
+
+use chrono::NaiveDate;
+use serde::Serialize;
+use tauri::{AppHandle, Manager, State};
+use tokio::io::AsyncWriteExt;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{broadcast, mpsc, oneshot, Mutex};
+
+use crate::commands::error::{CommandError, CommandResult};
+use crate::commands::goal_commands::goals_status;
+use crate::commands::metrics_commands::collect_metrics;
+use crate::commands::prayer_commands::{load_availability, mosque_or_selected, user_engine_config, with_start_timing};
+use crate::commands::schedule_commands::refresh_days;
+use crate::db::Database;
+use crate::models::*;
+use crate::services::websocket::{self, Frame, Opcode};
+use crate::services::{
+    authorize, clock, generate_secret, hash_secret, render_prometheus, schedule_to_ics, ticker, ApiAccess, HttpRequest,
+    HttpResponse, PrayerEngine,
+};
+
+pub(crate) const HTTP_SERVER_CONFIG_KEY: &str = "http_server_config";
+const API_TOKENS_KEY: &str = "api_tokens";
+
+/// Days covered by a calendar feed, starting today
+const CALENDAR_FEED_DAYS: i64 = 60;
+
+/// Slow clients are dropped instead of holding a connection open
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Events a slow WebSocket client can fall behind by before it misses some
+const EVENT_BACKLOG: usize = 64;
+
+/// Tick interval while a dashboard is connected, so its countdown moves every second
+pub const DASHBOARD_TICK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Optional local HTTP server, restarted whenever its configuration changes
+pub struct LocalServerState {
+    inner: Mutex<ServerRuntime>,
+    /// Events for WebSocket clients, open while the server runs
+    events: std::sync::Mutex<Option<broadcast::Sender<String>>>,
+    /// Ids of revoked tokens, so WebSocket clients connected with one are dropped
+    revoked: broadcast::Sender<String>,
+}
+
+struct ServerRuntime {
+    config: HttpServerConfig,
+    address: Option<String>,
+    last_error: Option<String>,
+    running: Option<RunningServer>,
+}
+
+struct RunningServer {
+    stop: oneshot::Sender<()>,
+    task: tauri::async_runtime::JoinHandle<()>,
+}
+
+impl LocalServerState {
+    pub fn new() -> Self {
+        Self {
+            inner: Mutex::new(ServerRuntime {
+                config: HttpServerConfig::default(),
+                address: None,
+                last_error: None,
+                running: None,
+            }),
+            events: std::sync::Mutex::new(None),
+            revoked: broadcast::channel(EVENT_BACKLOG).0,
+        }
+    }
+
+    /// Start the server if it was enabled before the restart
+    pub async fn restore(&self, db: &Database) {
+        let config = load_http_server_config(db).await;
+        if config.enabled {
+            let status = self.apply(db, config).await;
+            if let Some(e) = status.last_error {
+                log::warn!("Local HTTP server did not start: {}", e);
+            }
+        }
+    }
+
+    /// Stop the running server, then start it again with `config` when enabled
+    async fn apply(&self, db: &Database, config: HttpServerConfig) -> HttpServerStatus {
+        let mut runtime = self.inner.lock().await;
+        // Dropping the last sender closes the WebSocket connections of the previous server
+        *self.events.lock().unwrap() = None;
+        if let Some(running) = runtime.running.take() {
+            let _ = running.stop.send(());
+            // Wait for the listener to close so the port can be bound again
+            let _ = running.task.await;
+        }
+        runtime.address = None;
+        runtime.last_error = None;
+        runtime.config = config.clone();
+
+        if config.enabled {
+            match TcpListener::bind(config.bind_address()).await {
+                Ok(listener) => {
+                    let address = listener.local_addr().map(|a| a.to_string()).unwrap_or_else(|_| config.bind_address());
+                    log::info!("Local HTTP server listening on {}", address);
+                    let (stop, stopped) = oneshot::channel();
+                    let (events, _) = broadcast::channel(EVENT_BACKLOG);
+                    *self.events.lock().unwrap() = Some(events.clone());
+                    let task = tauri::async_runtime::spawn(serve(listener, db.clone(), events, self.revoked.clone(), stopped));
+                    runtime.running = Some(RunningServer { stop, task });
+                    runtime.address = Some(address);
+                }
+                Err(e) => {
+                    runtime.last_error = Some(format!("Cannot listen on {}: {}", config.bind_address(), e));
+                }
+            }
+        }
+
+        runtime.status(self.websocket_clients())
+    }
+
+    async fn status(&self) -> HttpServerStatus {
+        self.inner.lock().await.status(self.websocket_clients())
+    }
+
+    fn websocket_clients(&self) -> u32 {
+        self.events.lock().unwrap().as_ref().map_or(0, |events| events.receiver_count() as u32)
+    }
+
+    /// Whether a dashboard is connected to `/ws`
+    pub fn has_subscribers(&self) -> bool {
+        self.websocket_clients() > 0
+    }
+
+    /// Push an event to every connected WebSocket client
+    pub fn publish<S: Serialize>(&self, event: &str, payload: &S) {
+        let events = self.events.lock().unwrap();
+        let Some(events) = events.as_ref().filter(|events| events.receiver_count() > 0) else {
+            return;
+        };
+        let message = ServerEvent {
+            event: event.to_string(),
+            payload: serde_json::to_value(payload).unwrap_or_default(),
+        };
+        match serde_json::to_string(&message) {
+            Ok(json) => {
+                let _ = events.send(json);
+            }
+            Err(e) => log::warn!("Failed to encode {} for WebSocket clients: {}", event, e),
+        }
+    }
+
+    /// Close the WebSocket connections opened with a token
+    fn disconnect_token(&self, token_id: &str) {
+        let _ = self.revoked.send(token_id.to_string());
+    }
+
+    /// Push the selected mosque's countdowns as the "countdown" event, when a dashboard listens
+    pub async fn publish_countdown(&self, db: &Database) {
+        if !self.has_subscribers() {
+            return;
+        }
+        if let Some(snapshot) = countdown_snapshot(db).await {
+            self.publish("countdown", &snapshot);
+        }
+    }
+}
+
+impl ServerRuntime {
+    fn status(&self, websocket_clients: u32) -> HttpServerStatus {
+        HttpServerStatus {
+            config: self.config.clone(),
+            running: self.running.is_some(),
+            address: self.address.clone(),
+            last_error: self.last_error.clone(),
+            websocket_clients,
+        }
+    }
+}
+
+impl Default for LocalServerState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Send an event to the app window and to the dashboards connected to the local server
+pub fn broadcast_event<S: Serialize + Clone>(app: &AppHandle, event: &str, payload: S) {
+    app.state::<LocalServerState>().publish(event, &payload);
+    if let Err(e) = app.emit_all(event, payload) {
+        log::warn!("Failed to emit {}: {}", event, e);
+    }
+}
+
+async fn serve(
+    listener: TcpListener,
+    db: Database,
+    events: broadcast::Sender<String>,
+    revoked: broadcast::Sender<String>,
+    mut stopped: oneshot::Receiver<()>,
+) {
+    loop {
+        tokio::select! {
+            _ = &mut stopped => break,
+            accepted = listener.accept() => match accepted {
+                Ok((stream, _)) => {
+                    let connection = handle_connection(stream, db.clone(), events.clone(), revoked.clone());
+                    tauri::async_runtime::spawn(connection);
+                }
+                Err(e) => log::warn!("Local HTTP server failed to accept a connection: {}", e),
+            },
+        }
+    }
+    log::info!("Local HTTP server stopped");
+}
+
+async fn handle_connection(
+    mut stream: TcpStream,
+    db: Database,
+    events: broadcast::Sender<String>,
+    revoked: broadcast::Sender<String>,
+) {
+    let response = match tokio::time::timeout(REQUEST_TIMEOUT, HttpRequest::read(&mut stream)).await {
+        Ok(Ok(request)) if request.path == "/ws" => match websocket::upgrade_key(&request) {
+            Some(key) => match check_token(&db, &request, ApiScope::ReadTimes).await {
+                Ok(token_id) => {
+                    let key = key.to_string();
+                    let receiver = events.subscribe();
+                    // Only the running server keeps the channel open, so stopping it closes this stream
+                    drop(events);
+                    let revocations = token_id.map(|id| (id, revoked.subscribe()));
+                    return stream_events(stream, &key, receiver, revocations).await;
+                }
+                Err(denied) => denied,
+            },
+            None => HttpResponse::text(400, "Connect to /ws with a WebSocket client"),
+        },
+        Ok(Ok(request)) => route(&db, &request).await,
+        Ok(Err(e)) => HttpResponse::text(400, e),
+        Err(_) => return,
+    };
+
+    if let Err(e) = stream.write_all(&response.to_bytes()).await {
+        log::debug!("Local HTTP client went away: {}", e);
+    }
+    let _ = stream.shutdown().await;
+}
+
+/// Push every event to a WebSocket client until it leaves, the server stops or the token it
+/// connected with (`revocations`) is revoked
+/// Client frames are read on their own task, so a frame is never cut short by an event going out
+async fn stream_events(
+    mut stream: TcpStream,
+    key: &str,
+    mut events: broadcast::Receiver<String>,
+    mut revocations: Option<(String, broadcast::Receiver<String>)>,
+) {
+    if let Err(e) = stream.write_all(&websocket::handshake_response(key)).await {
+        log::debug!("WebSocket client went away during the handshake: {}", e);
+        return;
+    }
+    let (mut reader, mut writer) = stream.into_split();
+
+    // Replies to the client's control frames; closed once the client is gone
+    let (replies, mut pending) = mpsc::channel(4);
+    let reading = tauri::async_runtime::spawn(async move {
+        loop {
+            match websocket::read_frame(&mut reader).await {
+                Ok(frame) if frame.opcode == Opcode::Ping => {
+                    let _ = replies.send(Frame { opcode: Opcode::Pong, payload: frame.payload }).await;
+                }
+                Ok(frame) if frame.opcode == Opcode::Close => {
+                    let _ = replies.send(Frame { opcode: Opcode::Close, payload: Vec::new() }).await;
+                    break;
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    log::debug!("WebSocket client closed: {}", e);
+                    break;
+                }
+            }
+        }
+    });
+
+    loop {
+        let frame = tokio::select! {
+            event = events.recv() => match event {
+                Ok(json) => Frame { opcode: Opcode::Text, payload: json.into_bytes() },
+                Err(broadcast::error::RecvError::Lagged(missed)) => {
+                    log::debug!("WebSocket client missed {} event(s)", missed);
+                    continue;
+                }
+                Err(broadcast::error::RecvError::Closed) => Frame { opcode: Opcode::Close, payload: Vec::new() },
+            },
+            reply = pending.recv() => match reply {
+                Some(reply) => reply,
+                None => break,
+            },
+            revoked = revoked_token(&mut revocations) => {
+                log::info!("Closing a WebSocket client of revoked token {}", revoked);
+                Frame { opcode: Opcode::Close, payload: Vec::new() }
+            }
+        };
+        let bytes = websocket::encode_frame(frame.opcode, &frame.payload);
+        if writer.write_all(&bytes).await.is_err() || frame.opcode == Opcode::Close {
+            break;
+        }
+    }
+
+    reading.abort();
+    let _ = writer.shutdown().await;
+}
+
+/// Resolves once the connection's token is revoked; never for connections made without one
+/// Revocations missed by a lagging client count too, as the token can't be checked again here
+async fn revoked_token(revocations: &mut Option<(String, broadcast::Receiver<String>)>) -> String {
+    let Some((token_id, revoked)) = revocations else {
+        return std::future::pending().await;
+    };
+    loop {
+        match revoked.recv().await {
+            Ok(id) if id == *token_id => return id,
+            Ok(_) => {}
+            Err(_) => return token_id.clone(),
+        }
+    }
+}
+
+async fn route(db: &Database, request: &HttpRequest) -> HttpResponse {
+    let path = request.path.as_str();
+    let scope = match path {
+        "/" => None,
+        "/next" => Some(ApiScope::ReadTimes),
+        "/stats" | "/metrics" => Some(ApiScope::ReadStats),
+        "/notifications" => Some(ApiScope::ControlNotifications),
+        _ if path.starts_with("/calendar/") => Some(ApiScope::ReadTimes),
+        _ => return HttpResponse::text(404, format!("Nothing at {}", path)),
+    };
+    let writable = path == "/notifications";
+    if request.method != "GET" && !(writable && request.method == "POST") {
+        return HttpResponse::text(405, format!("{} is not supported on {}", request.method, path));
+    }
+    if let Some(scope) = scope {
+        if let Err(denied) = check_token(db, request, scope).await {
+            return denied;
+        }
+    }
+
+    if let Some(mosque_id) = path.strip_prefix("/calendar/").and_then(|p| p.strip_suffix(".ics")) {
+        return calendar_feed(db, mosque_id).await;
+    }
+    match path {
+        "/" => HttpResponse::text(
+            200,
+            "Iqamah\n\n\
+             Once tokens are set up, send one as \"Authorization: Bearer <token>\" or ?token=<token>\n\n\
+             GET  /calendar/<mosque>.ics         60-day prayer calendar (read-times)\n\
+             GET  /next                          countdowns of the selected mosque (read-times)\n\
+             GET  /ws                            WebSocket pushing countdowns and app events (read-times)\n\
+             GET  /stats                         this week's prayer goal progress (read-stats)\n\
+             GET  /metrics                       Prometheus metrics, when enabled (read-stats)\n\
+             GET  /notifications                 whether notifications are on (control-notifications)\n\
+             POST /notifications?enabled=<bool>  switch notifications on or off (control-notifications)\n",
+        ),
+        "/next" => match countdown_snapshot(db).await {
+            Some(snapshot) => HttpResponse::json(&snapshot).with_header("Cache-Control", "no-store"),
+            None => HttpResponse::text(404, "No schedule cached for the selected mosque"),
+        },
+        "/stats" => match goals_status(db, DEFAULT_PROFILE.to_string(), clock::today()).await {
+            Ok(goals) => HttpResponse::json(&goals).with_header("Cache-Control", "no-store"),
+            Err(e) => HttpResponse::text(500, e.message),
+        },
+        "/metrics" => metrics(db).await,
+        _ => notifications(db, request).await,
+    }
+}
+
+/// Provider latencies, cache hit rates, ticker drift and database sizes for a Prometheus scraper
+async fn metrics(db: &Database) -> HttpResponse {
+    if !load_http_server_config(db).await.expose_metrics {
+        return HttpResponse::text(404, "Metrics are turned off in the local server settings");
+    }
+    match collect_metrics(db).await {
+        Ok(metrics) => HttpResponse::new(200, "text/plain; version=0.0.4; charset=utf-8", render_prometheus(&metrics)),
+        Err(e) => HttpResponse::text(500, e.message),
+    }
+}
+
+/// Read notifications on or off, or switch them with `?enabled=true|false`
+async fn notifications(db: &Database, request: &HttpRequest) -> HttpResponse {
+    if request.method == "POST" {
+        let Some(enabled) = request.query.get("enabled").and_then(|v| v.parse::<bool>().ok()) else {
+            return HttpResponse::text(400, "Pass ?enabled=true or ?enabled=false");
+        };
+        if let Err(e) = db.set_setting("notification_enabled", &enabled.to_string()).await {
+            log::warn!("Failed to switch notifications from the local API: {}", e);
+            return HttpResponse::text(500, "Could not save the setting");
+        }
+        log::info!("Notifications turned {} from the local API", if enabled { "on" } else { "off" });
+    }
+
+    let enabled = !matches!(db.get_setting("notification_enabled").await, Ok(Some(v)) if v == "false");
+    HttpResponse::json(&serde_json::json!({ "enabled": enabled })).with_header("Cache-Control", "no-store")
+}
+
+/// Token sent as a bearer token, or in the query string for calendar apps and browser WebSockets
+fn presented_token(request: &HttpRequest) -> Option<&str> {
+    request
+        .header("authorization")
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .or_else(|| request.query.get("token").map(String::as_str))
+        .map(str::trim)
+}
+
+/// Id of the token granting `scope`, `None` while the server is open without tokens,
+/// or the response refusing the request
+async fn check_token(db: &Database, request: &HttpRequest, scope: ApiScope) -> Result<Option<String>, HttpResponse> {
+    let tokens = load_api_tokens(db).await;
+    let allow_lan = load_http_server_config(db).await.allow_lan;
+    let presented = presented_token(request);
+    match authorize(&tokens, presented, scope, allow_lan) {
+        ApiAccess::Granted => {
+            let hash = presented.map(hash_secret);
+            Ok(tokens
+                .into_iter()
+                .find(|stored| Some(&stored.secret_hash) == hash.as_ref())
+                .map(|stored| stored.token.id))
+        }
+        ApiAccess::Unauthorized => {
+            Err(HttpResponse::text(401, "A valid API token is required").with_header("WWW-Authenticate", "Bearer"))
+        }
+        ApiAccess::Forbidden => Err(HttpResponse::text(403, format!("This token does not have the {} scope", scope))),
+    }
+}
+
+/// Today's countdowns at the selected mosque, `None` until a mosque and its schedule are known
+async fn countdown_snapshot(db: &Database) -> Option<CountdownSnapshot> {
+    let mosque_id = mosque_or_selected(db, None).await.ok()?;
+    let times = db.get_prayer_times(&mosque_id, clock::today()).await.ok().flatten()?;
+
+    let now = ticker::countdown_now();
+    let engine = PrayerEngine::new(user_engine_config(db).await)
+        .with_availability(load_availability(db, &mosque_id, &times).await);
+    let countdowns = with_start_timing(engine, db, &mosque_id).await.get_all_countdowns(&times, now);
+    Some(CountdownSnapshot {
+        mosque_id,
+        mosque_name: times.mosque_name.clone(),
+        now,
+        focus: kiosk_focus(&countdowns),
+        countdowns,
+    })
+}
+
+/// Rolling calendar of a mosque's times, for phones subscribing to it
+async fn calendar_feed(db: &Database, mosque_id: &str) -> HttpResponse {
+    let today = clock::today();
+    let days = match feed_days(db, mosque_id, today, today + chrono::Duration::days(CALENDAR_FEED_DAYS - 1)).await {
+        Ok(days) => days,
+        Err(e) => {
+            log::warn!("Calendar feed for {} failed: {}", mosque_id, e);
+            return HttpResponse::text(500, "Could not read the schedule");
+        }
+    };
+    if days.is_empty() {
+        return HttpResponse::text(404, format!("No schedule cached for {}", mosque_id));
+    }
+
+    let name = match db.get_mosque(mosque_id).await {
+        Ok(Some(mosque)) => mosque.name,
+        _ => days[0].mosque_name.clone().unwrap_or_else(|| mosque_id.to_string()),
+    };
+
+    HttpResponse::new(200, "text/calendar; charset=utf-8", schedule_to_ics(&name, &days, clock::now()))
+        .with_header("Cache-Control", "max-age=3600")
+}
+
+/// Cached days of `from..=to`, fetching the uncached ones first for a mosque the app knows
+/// A failed fetch is logged and the feed serves what is cached
+async fn feed_days(db: &Database, mosque_id: &str, from: NaiveDate, to: NaiveDate) -> CommandResult<Vec<PrayerTimes>> {
+    let cached = db
+        .get_prayer_times_range(mosque_id, from, to)
+        .await
+        .map_err(CommandError::database)?;
+    let known = db.get_mosque(mosque_id).await.map_err(CommandError::database)?.is_some();
+    let cached_dates: HashSet<NaiveDate> = cached.iter().map(|day| day.date.date_naive()).collect();
+    let mut missing = from.iter_days().take_while(|date| *date <= to).filter(|date| !cached_dates.contains(date));
+    let Some(first) = missing.next().filter(|_| known) else {
+        return Ok(cached);
+    };
+    let last = missing.last().unwrap_or(first);
+
+    if let Err(e) = refresh_days(db, mosque_id, first, last).await {
+        log::warn!("Calendar feed for {} serves cached days only: {}", mosque_id, e);
+        return Ok(cached);
+    }
+    db.get_prayer_times_range(mosque_id, from, to)
+        .await
+        .map_err(CommandError::database)
+}
+
+#[tauri::command]
+pub async fn get_http_server_status(server: State<'_, LocalServerState>) -> CommandResult<HttpServerStatus> {
+    Ok(server.status().await)
+}
+
+/// Save the local HTTP server settings and restart it with them
+/// A port that can't be bound is reported in `last_error`; the settings are kept
+#[tauri::command]
+pub async fn set_http_server_config(
+    config: HttpServerConfig,
+    db: State<'_, Database>,
+    server: State<'_, LocalServerState>,
+) -> CommandResult<HttpServerStatus> {
+    if config.port < 1024 {
+        return Err(CommandError::invalid_input("Use a port between 1024 and 65535"));
+    }
+
+    let json = serde_json::to_string(&config).map_err(|e| CommandError::internal(e.to_string()))?;
+    db.set_setting(HTTP_SERVER_CONFIG_KEY, &json)
+        .await
+        .map_err(CommandError::database)?;
+
+    Ok(server.apply(&db, config).await)
+}
+
+/// Tokens other devices call the local server with; secrets aren't included
+#[tauri::command]
+pub async fn list_api_tokens(db: State<'_, Database>) -> CommandResult<Vec<ApiToken>> {
+    Ok(load_api_tokens(&db).await.into_iter().map(|stored| stored.token).collect())
+}
+
+/// Create a token for a device; its secret is returned this once and only its hash is kept
+#[tauri::command]
+pub async fn create_api_token(
+    name: String,
+    scopes: Vec<ApiScope>,
+    db: State<'_, Database>,
+) -> CommandResult<NewApiToken> {
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err(CommandError::invalid_input("Give the token a name, e.g. the device it's for"));
+    }
+    if scopes.is_empty() {
+        return Err(CommandError::invalid_input("Choose at least one scope"));
+    }
+
+    let secret = generate_secret().map_err(|e| CommandError::internal(format!("No random source: {}", e)))?;
+    let mut scopes = scopes;
+    scopes.sort();
+    scopes.dedup();
+    let token = ApiToken {
+        id: hash_secret(&secret)[..12].to_string(),
+        name,
+        scopes,
+        created_at: clock::now(),
+    };
+
+    let mut tokens = load_api_tokens(&db).await;
+    tokens.push(StoredApiToken {
+        token: token.clone(),
+        secret_hash: hash_secret(&secret),
+    });
+    save_api_tokens(&db, &tokens).await?;
+    log::info!("Created API token '{}' ({})", token.name, token.id);
+
+    Ok(NewApiToken { token, secret })
+}
+
+/// Revoke a token; devices using it are refused from their next request and their
+/// WebSocket connections are closed
+#[tauri::command]
+pub async fn revoke_api_token(
+    id: String,
+    db: State<'_, Database>,
+    server: State<'_, LocalServerState>,
+) -> CommandResult<bool> {
+    let mut tokens = load_api_tokens(&db).await;
+    let before = tokens.len();
+    tokens.retain(|stored| stored.token.id != id);
+    if tokens.len() == before {
+        return Ok(false);
+    }
+
+    save_api_tokens(&db, &tokens).await?;
+    server.disconnect_token(&id);
+    log::info!("Revoked API token {}", id);
+    Ok(true)
+}
+
+async fn load_api_tokens(db: &Database) -> Vec<StoredApiToken> {
+    match db.get_setting(API_TOKENS_KEY).await {
+        Ok(Some(json)) => serde_json::from_str(&json).unwrap_or_default(),
+        _ => Vec::new(),
+    }
+}
+
+async fn save_api_tokens(db: &Database, tokens: &[StoredApiToken]) -> CommandResult<()> {
+    let json = serde_json::to_string(tokens).map_err(|e| CommandError::internal(e.to_string()))?;
+    db.set_setting(API_TOKENS_KEY, &json).await.map_err(CommandError::database)
+}
+
+async fn load_http_server_config(db: &Database) -> HttpServerConfig {
+    match db.get_setting(HTTP_SERVER_CONFIG_KEY).await {
+        Ok(Some(json)) => serde_json::from_str(&json).unwrap_or_default(),
+        _ => HttpServerConfig::default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::migrations::create_database;
+    use crate::providers::MockProvider;
+
+    #[tokio::test]
+    async fn test_calendar_feed_route() {
+        let db = Database::new(create_database(":memory:").await.unwrap());
+        db.save_mosque(&Mosque::new("mock-feed".to_string(), "Feed Mosque".to_string()))
+            .await
+            .unwrap();
+        let today = clock::today();
+        // The whole window is cached, so the feed fetches nothing
+        let days: Vec<PrayerTimes> = (0..CALENDAR_FEED_DAYS)
+            .map(|d| MockProvider::new().schedule("mock-feed", today + chrono::Duration::days(d), clock::now()))
+            .collect();
+        db.save_prayer_times_bulk(&days).await.unwrap();
+
+        let get = |path: &str| HttpRequest::parse(&format!("GET {} HTTP/1.1\r\nHost: localhost\r\n\r\n", path)).unwrap();
+        let feed = route(&db, &get("/calendar/mock-feed.ics")).await;
+        assert_eq!(feed.status, 200);
+        assert!(String::from_utf8(feed.body).unwrap().contains("X-WR-CALNAME:Feed Mosque"));
+
+        assert_eq!(route(&db, &get("/calendar/unknown.ics")).await.status, 404);
+        assert_eq!(route(&db, &get("/nothing")).await.status, 404);
+        // No mosque selected yet
+        assert_eq!(route(&db, &get("/next")).await.status, 404);
+    }
+
+    #[tokio::test]
+    async fn test_token_scopes() {
+        let db = Database::new(create_database(":memory:").await.unwrap());
+        let request = |method: &str, path: &str, token: &str| {
+            HttpRequest::parse(&format!(
+                "{} {} HTTP/1.1\r\nHost: localhost\r\nAuthorization: Bearer {}\r\n\r\n",
+                method, path, token
+            ))
+            .unwrap()
+        };
+
+        // Without tokens reads stay open, but notifications can't be switched
+        assert_eq!(route(&db, &request("GET", "/stats", "")).await.status, 200);
+        assert_eq!(route(&db, &request("POST", "/notifications?enabled=false", "")).await.status, 401);
+        // Except for devices on the network, which always need one
+        let lan = HttpServerConfig {
+            allow_lan: true,
+            ..Default::default()
+        };
+        db.set_setting(HTTP_SERVER_CONFIG_KEY, &serde_json::to_string(&lan).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(route(&db, &request("GET", "/stats", "")).await.status, 401);
+
+        let stored = |secret: &str, scopes: Vec<ApiScope>| StoredApiToken {
+            token: ApiToken {
+                id: secret.to_string(),
+                name: secret.to_string(),
+                scopes,
+                created_at: clock::now(),
+            },
+            secret_hash: hash_secret(secret),
+        };
+        let tokens = [
+            stored("iqm_tablet", vec![ApiScope::ReadTimes]),
+            stored("iqm_phone", vec![ApiScope::ReadStats, ApiScope::ControlNotifications]),
+        ];
+        save_api_tokens(&db, &tokens).await.unwrap();
+
+        assert_eq!(route(&db, &request("GET", "/stats", "")).await.status, 401);
+        assert_eq!(route(&db, &request("GET", "/stats", "iqm_tablet")).await.status, 403);
+        assert_eq!(route(&db, &request("POST", "/notifications?enabled=false", "iqm_tablet")).await.status, 403);
+        assert_eq!(route(&db, &request("GET", "/stats", "iqm_phone")).await.status, 200);
+        let granted = check_token(&db, &request("GET", "/ws", "iqm_tablet"), ApiScope::ReadTimes).await;
+        assert_eq!(granted.ok().flatten().as_deref(), Some("iqm_tablet"));
+
+        let switched = route(&db, &request("POST", "/notifications?enabled=false", "iqm_phone")).await;
+        assert_eq!(switched.status, 200);
+        assert_eq!(db.get_setting("notification_enabled").await.unwrap().as_deref(), Some("false"));
+
+        let by_query = HttpRequest::parse("GET /calendar/none.ics?token=iqm_tablet HTTP/1.1\r\n\r\n").unwrap();
+        assert_eq!(route(&db, &by_query).await.status, 404);
+        assert_eq!(route(&db, &request("POST", "/stats", "iqm_phone")).await.status, 405);
+    }
+
+    #[tokio::test]
+    async fn test_metrics_route() {
+        let db = Database::new(create_database(":memory:").await.unwrap());
+        let get = HttpRequest::parse("GET /metrics HTTP/1.1\r\nHost: localhost\r\n\r\n").unwrap();
+        assert_eq!(route(&db, &get).await.status, 404);
+
+        let config = HttpServerConfig {
+            expose_metrics: true,
+            ..Default::default()
+        };
+        db.set_setting(HTTP_SERVER_CONFIG_KEY, &serde_json::to_string(&config).unwrap())
+            .await
+            .unwrap();
+        let response = route(&db, &get).await;
+        assert_eq!(response.status, 200);
+        assert!(String::from_utf8(response.body).unwrap().contains("iqamah_database_rows{table=\"settings\"} 1\n"));
+    }
+}
@@ -1,23 +1,29 @@
 use serde_json::Value;
 use tauri::State;
 
+use crate::commands::error::{CommandError, CommandResult};
 use crate::db::Database;
-use crate::models::ProviderConfig;
+use crate::models::{validate_reminder_minutes, AsrMethod, IqamaDefaults, IshaEnd, ProviderConfig};
+use crate::providers::provider_config_schema;
+use crate::services::{
+    has_plaintext_secrets, set_payload_archive_enabled, SecretStore, DEFAULT_ARCHIVED_PAYLOADS,
+    DEFAULT_MAX_CACHE_AGE_HOURS, DEFAULT_REMINDER_MINUTES, MAX_ARCHIVED_PAYLOADS,
+};
 
 /// Get setting value
 #[tauri::command]
-pub async fn get_setting(key: String, db: State<'_, Database>) -> Result<Option<String>, String> {
+pub async fn get_setting(key: String, db: State<'_, Database>) -> CommandResult<Option<String>> {
     db.get_setting(&key)
         .await
-        .map_err(|e| format!("Database error: {}", e))
+        .map_err(CommandError::database)
 }
 
 /// Set setting value
 #[tauri::command]
-pub async fn set_setting(key: String, value: String, db: State<'_, Database>) -> Result<(), String> {
+pub async fn set_setting(key: String, value: String, db: State<'_, Database>) -> CommandResult<()> {
     db.set_setting(&key, &value)
         .await
-        .map_err(|e| format!("Database error: {}", e))
+        .map_err(CommandError::database)
 }
 
 /// Get provider configuration
@@ -25,10 +31,10 @@ pub async fn set_setting(key: String, value: String, db: State<'_, Database>) ->
 pub async fn get_provider_config(
     provider_id: String,
     db: State<'_, Database>,
-) -> Result<Option<ProviderConfig>, String> {
-    db.get_provider_config(&provider_id)
+) -> CommandResult<Option<ProviderConfig>> {
+    load_provider_config(&db, &provider_id)
         .await
-        .map_err(|e| format!("Database error: {}", e))
+        .map_err(CommandError::database)
 }
 
 /// Save provider configuration
@@ -36,15 +42,166 @@ pub async fn get_provider_config(
 pub async fn save_provider_config(
     config: ProviderConfig,
     db: State<'_, Database>,
-) -> Result<(), String> {
+) -> CommandResult<()> {
+    let schema = provider_config_schema(&config.provider_id);
+    let config = SecretStore::new().seal(config, &schema);
+
     db.save_provider_config(&config)
         .await
-        .map_err(|e| format!("Database error: {}", e))
+        .map_err(CommandError::database)
+}
+
+/// Load a provider config with its secrets read back from the keychain
+/// Configs saved before keychain support get their plaintext secrets moved there
+pub(crate) async fn load_provider_config(
+    db: &Database,
+    provider_id: &str,
+) -> anyhow::Result<Option<ProviderConfig>> {
+    let Some(config) = db.get_provider_config(provider_id).await? else {
+        return Ok(None);
+    };
+
+    let secrets = SecretStore::new();
+    let schema = provider_config_schema(provider_id);
+
+    if has_plaintext_secrets(&config, &schema) {
+        let sealed = secrets.seal(config.clone(), &schema);
+        if !has_plaintext_secrets(&sealed, &schema) {
+            log::info!("Moved {} secrets from settings to the keychain", provider_id);
+            db.save_provider_config(&sealed).await?;
+        }
+    }
+
+    Ok(Some(secrets.unseal(config)))
+}
+
+const MAX_CACHE_AGE_KEY: &str = "max_cache_age_hours";
+
+/// Hours after which a cached schedule is refetched in the background
+pub(crate) async fn load_max_cache_age_hours(db: &Database) -> i64 {
+    db.get_setting(MAX_CACHE_AGE_KEY)
+        .await
+        .ok()
+        .flatten()
+        .and_then(|hours| hours.parse().ok())
+        .unwrap_or(DEFAULT_MAX_CACHE_AGE_HOURS)
+}
+
+pub(crate) const DEFAULT_COUNTRY_KEY: &str = "default_country";
+
+/// Country searched and fetched from Mawaqit when none is given
+const FALLBACK_COUNTRY: &str = "FR";
+
+pub(crate) async fn load_default_country(db: &Database) -> String {
+    db.get_setting(DEFAULT_COUNTRY_KEY)
+        .await
+        .ok()
+        .flatten()
+        .filter(|country| is_country_code(country))
+        .unwrap_or_else(|| FALLBACK_COUNTRY.to_string())
+}
+
+pub(crate) fn is_country_code(country: &str) -> bool {
+    country.len() == 2 && country.chars().all(|c| c.is_ascii_alphabetic())
+}
+
+const GOAL_SUMMARY_KEY: &str = "goal_summary_notifications";
+
+/// Whether the end-of-week goal summary is shown; off unless the user opts in
+pub(crate) async fn load_goal_summary_enabled(db: &Database) -> bool {
+    matches!(db.get_setting(GOAL_SUMMARY_KEY).await, Ok(Some(value)) if value == "true")
+}
+
+const ISLAMIC_EVENT_REMINDERS_KEY: &str = "islamic_event_reminders";
+
+/// Whether special days are announced the evening before; off unless the user opts in
+pub(crate) async fn load_islamic_event_reminders_enabled(db: &Database) -> bool {
+    matches!(db.get_setting(ISLAMIC_EVENT_REMINDERS_KEY).await, Ok(Some(value)) if value == "true")
+}
+
+const AUTOMATIC_UPDATE_CHECKS_KEY: &str = "automatic_update_checks";
+
+/// Whether new releases are looked up in the background; off unless the user opts in
+pub(crate) async fn load_automatic_update_checks_enabled(db: &Database) -> bool {
+    matches!(db.get_setting(AUTOMATIC_UPDATE_CHECKS_KEY).await, Ok(Some(value)) if value == "true")
+}
+
+const REMINDER_MINUTES_KEY: &str = "reminder_minutes_before";
+
+/// Minutes before adhan and iqama to remind, stored as a JSON list; one reminder unless the user adds more
+pub(crate) async fn load_reminder_minutes(db: &Database) -> Vec<i64> {
+    db.get_setting(REMINDER_MINUTES_KEY)
+        .await
+        .ok()
+        .flatten()
+        .and_then(|json| serde_json::from_str::<Vec<i64>>(&json).ok())
+        .filter(|minutes| !minutes.is_empty() && validate_reminder_minutes(minutes).is_ok())
+        .unwrap_or_else(|| vec![DEFAULT_REMINDER_MINUTES])
+}
+
+/// Minutes before Fajr that the fast starts, when the mosque publishes no imsak time
+pub(crate) const IMSAK_MINUTES_KEY: &str = "imsak_minutes_before_fajr";
+
+/// Countdowns show seconds within this many minutes of the time
+pub(crate) const COUNTDOWN_SECONDS_THRESHOLD_KEY: &str = "countdown_seconds_threshold_minutes";
+/// Countdowns read "now" within this many seconds of the time
+pub(crate) const COUNTDOWN_NOW_THRESHOLD_KEY: &str = "countdown_now_threshold_seconds";
+
+const RAW_PAYLOAD_ARCHIVE_KEY: &str = "raw_payload_archive";
+const RAW_PAYLOAD_ARCHIVE_SIZE_KEY: &str = "raw_payload_archive_size";
+
+/// Whether provider responses are archived for support; off unless the user opts in
+pub(crate) async fn load_raw_payload_archive_enabled(db: &Database) -> bool {
+    matches!(db.get_setting(RAW_PAYLOAD_ARCHIVE_KEY).await, Ok(Some(value)) if value == "true")
+}
+
+/// Responses kept per mosque while archiving is on
+pub(crate) async fn load_raw_payload_archive_size(db: &Database) -> usize {
+    db.get_setting(RAW_PAYLOAD_ARCHIVE_SIZE_KEY)
+        .await
+        .ok()
+        .flatten()
+        .and_then(|size| size.parse().ok())
+        .filter(|size| (1..=MAX_ARCHIVED_PAYLOADS).contains(size))
+        .unwrap_or(DEFAULT_ARCHIVED_PAYLOADS)
+}
+
+const IQAMA_DEFAULTS_KEY: &str = "iqama_defaults";
+
+/// Default iqama offsets applied to providers that publish adhan times only
+#[tauri::command]
+pub async fn get_iqama_defaults(db: State<'_, Database>) -> CommandResult<IqamaDefaults> {
+    Ok(load_iqama_defaults(&db).await)
+}
+
+/// Save default iqama offsets; used for schedules fetched from now on
+#[tauri::command]
+pub async fn save_iqama_defaults(defaults: IqamaDefaults, db: State<'_, Database>) -> CommandResult<()> {
+    if let Some((prayer, minutes)) = defaults.offsets.iter().find(|(_, m)| !(0..=120).contains(*m)) {
+        return Err(CommandError::invalid_input(format!(
+            "{} iqama offset must be between 0 and 120 minutes, got {}",
+            prayer, minutes
+        )));
+    }
+
+    let json = serde_json::to_string(&defaults).map_err(|e| CommandError::internal(e.to_string()))?;
+    db.set_setting(IQAMA_DEFAULTS_KEY, &json)
+        .await
+        .map_err(CommandError::database)
+}
+
+pub(crate) async fn load_iqama_defaults(db: &Database) -> IqamaDefaults {
+    db.get_setting(IQAMA_DEFAULTS_KEY)
+        .await
+        .ok()
+        .flatten()
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
 }
 
 /// Get all settings as JSON
 #[tauri::command]
-pub async fn get_all_settings(db: State<'_, Database>) -> Result<Value, String> {
+pub async fn get_all_settings(db: State<'_, Database>) -> CommandResult<Value> {
     // Get common settings
     let mut settings = serde_json::Map::new();
 
@@ -56,6 +213,26 @@ pub async fn get_all_settings(db: State<'_, Database>) -> Result<Value, String>
         settings.insert("language".to_string(), Value::String(value));
     }
 
+    if let Ok(Some(value)) = db.get_setting("time_format").await {
+        settings.insert("time_format".to_string(), Value::String(value));
+    }
+
+    if let Ok(Some(value)) = db.get_setting("asr_method").await {
+        settings.insert("asr_method".to_string(), Value::String(value));
+    }
+
+    if let Ok(Some(value)) = db.get_setting("isha_end").await {
+        settings.insert("isha_end".to_string(), Value::String(value));
+    }
+
+    settings.insert(DEFAULT_COUNTRY_KEY.to_string(), Value::String(load_default_country(&db).await));
+
+    if let Ok(Some(value)) = db.get_setting(MAX_CACHE_AGE_KEY).await {
+        if let Ok(hours) = value.parse::<i64>() {
+            settings.insert(MAX_CACHE_AGE_KEY.to_string(), Value::from(hours));
+        }
+    }
+
     if let Ok(Some(value)) = db.get_setting("notification_enabled").await {
         settings.insert(
             "notification_enabled".to_string(),
@@ -63,12 +240,38 @@ pub async fn get_all_settings(db: State<'_, Database>) -> Result<Value, String>
         );
     }
 
+    settings.insert(GOAL_SUMMARY_KEY.to_string(), Value::Bool(load_goal_summary_enabled(&db).await));
+    settings.insert(
+        ISLAMIC_EVENT_REMINDERS_KEY.to_string(),
+        Value::Bool(load_islamic_event_reminders_enabled(&db).await),
+    );
+    settings.insert(
+        AUTOMATIC_UPDATE_CHECKS_KEY.to_string(),
+        Value::Bool(load_automatic_update_checks_enabled(&db).await),
+    );
+    settings.insert(REMINDER_MINUTES_KEY.to_string(), Value::from(load_reminder_minutes(&db).await));
+    for key in [IMSAK_MINUTES_KEY, COUNTDOWN_SECONDS_THRESHOLD_KEY, COUNTDOWN_NOW_THRESHOLD_KEY] {
+        if let Ok(Some(value)) = db.get_setting(key).await {
+            if let Ok(value) = value.parse::<i64>() {
+                settings.insert(key.to_string(), Value::from(value));
+            }
+        }
+    }
+    settings.insert(
+        RAW_PAYLOAD_ARCHIVE_KEY.to_string(),
+        Value::Bool(load_raw_payload_archive_enabled(&db).await),
+    );
+    settings.insert(
+        RAW_PAYLOAD_ARCHIVE_SIZE_KEY.to_string(),
+        Value::from(load_raw_payload_archive_size(&db).await),
+    );
+
     Ok(Value::Object(settings))
 }
 
 /// Save all settings from JSON
 #[tauri::command]
-pub async fn save_all_settings(settings: Value, db: State<'_, Database>) -> Result<(), String> {
+pub async fn save_all_settings(settings: Value, db: State<'_, Database>) -> CommandResult<()> {
     if let Some(theme) = settings.get("theme").and_then(|v| v.as_str()) {
         db.set_setting("theme", theme).await.ok();
     }
@@ -77,11 +280,115 @@ pub async fn save_all_settings(settings: Value, db: State<'_, Database>) -> Resu
         db.set_setting("language", lang).await.ok();
     }
 
+    if let Some(format) = settings.get("time_format").and_then(|v| v.as_str()) {
+        db.set_setting("time_format", format).await.ok();
+    }
+
+    if let Some(method) = settings.get("asr_method").and_then(|v| v.as_str()) {
+        let method: AsrMethod = method.parse().map_err(CommandError::invalid_input)?;
+        db.set_setting("asr_method", method.as_str()).await.ok();
+    }
+
+    if let Some(end) = settings.get("isha_end").and_then(|v| v.as_str()) {
+        let end: IshaEnd = end.parse().map_err(CommandError::invalid_input)?;
+        db.set_setting("isha_end", end.as_str()).await.ok();
+    }
+
+    if let Some(country) = settings.get(DEFAULT_COUNTRY_KEY).and_then(|v| v.as_str()) {
+        if !is_country_code(country) {
+            return Err(CommandError::invalid_input(format!(
+                "Default country must be a two-letter country code, got {:?}",
+                country
+            )));
+        }
+        db.set_setting(DEFAULT_COUNTRY_KEY, &country.to_ascii_uppercase()).await.ok();
+    }
+
+    if let Some(hours) = settings.get(MAX_CACHE_AGE_KEY).and_then(|v| v.as_i64()) {
+        if !(1..=24 * 30).contains(&hours) {
+            return Err(CommandError::invalid_input(format!(
+                "Maximum cache age must be between 1 and 720 hours, got {}",
+                hours
+            )));
+        }
+        db.set_setting(MAX_CACHE_AGE_KEY, &hours.to_string()).await.ok();
+    }
+
     if let Some(notif) = settings.get("notification_enabled").and_then(|v| v.as_bool()) {
         db.set_setting("notification_enabled", &notif.to_string())
             .await
             .ok();
     }
 
+    if let Some(summary) = settings.get(GOAL_SUMMARY_KEY).and_then(|v| v.as_bool()) {
+        db.set_setting(GOAL_SUMMARY_KEY, &summary.to_string()).await.ok();
+    }
+
+    if let Some(reminders) = settings.get(ISLAMIC_EVENT_REMINDERS_KEY).and_then(|v| v.as_bool()) {
+        db.set_setting(ISLAMIC_EVENT_REMINDERS_KEY, &reminders.to_string()).await.ok();
+    }
+
+    if let Some(checks) = settings.get(AUTOMATIC_UPDATE_CHECKS_KEY).and_then(|v| v.as_bool()) {
+        db.set_setting(AUTOMATIC_UPDATE_CHECKS_KEY, &checks.to_string()).await.ok();
+    }
+
+    if let Some(minutes) = settings.get(REMINDER_MINUTES_KEY).filter(|v| !v.is_null()) {
+        let mut minutes: Vec<i64> = serde_json::from_value(minutes.clone())
+            .map_err(|_| CommandError::invalid_input("Reminder times must be a list of minutes"))?;
+        if minutes.is_empty() {
+            return Err(CommandError::invalid_input("Set at least one reminder time"));
+        }
+        validate_reminder_minutes(&minutes).map_err(CommandError::invalid_input)?;
+        minutes.sort_unstable_by(|a, b| b.cmp(a));
+        minutes.dedup();
+        let json = serde_json::to_string(&minutes).map_err(|e| CommandError::internal(e.to_string()))?;
+        db.set_setting(REMINDER_MINUTES_KEY, &json).await.ok();
+    }
+
+    if let Some(minutes) = settings.get(IMSAK_MINUTES_KEY).and_then(|v| v.as_i64()) {
+        if !(0..=60).contains(&minutes) {
+            return Err(CommandError::invalid_input(format!(
+                "Imsak must be between 0 and 60 minutes before Fajr, got {}",
+                minutes
+            )));
+        }
+        db.set_setting(IMSAK_MINUTES_KEY, &minutes.to_string()).await.ok();
+    }
+
+    if let Some(minutes) = settings.get(COUNTDOWN_SECONDS_THRESHOLD_KEY).and_then(|v| v.as_i64()) {
+        if !(0..=60).contains(&minutes) {
+            return Err(CommandError::invalid_input(format!(
+                "Countdown seconds threshold must be between 0 and 60 minutes, got {}",
+                minutes
+            )));
+        }
+        db.set_setting(COUNTDOWN_SECONDS_THRESHOLD_KEY, &minutes.to_string()).await.ok();
+    }
+
+    if let Some(seconds) = settings.get(COUNTDOWN_NOW_THRESHOLD_KEY).and_then(|v| v.as_i64()) {
+        if !(0..=300).contains(&seconds) {
+            return Err(CommandError::invalid_input(format!(
+                "Countdown \"now\" threshold must be between 0 and 300 seconds, got {}",
+                seconds
+            )));
+        }
+        db.set_setting(COUNTDOWN_NOW_THRESHOLD_KEY, &seconds.to_string()).await.ok();
+    }
+
+    if let Some(size) = settings.get(RAW_PAYLOAD_ARCHIVE_SIZE_KEY).and_then(|v| v.as_u64()) {
+        if !(1..=MAX_ARCHIVED_PAYLOADS as u64).contains(&size) {
+            return Err(CommandError::invalid_input(format!(
+                "Archived responses per mosque must be between 1 and {}, got {}",
+                MAX_ARCHIVED_PAYLOADS, size
+            )));
+        }
+        db.set_setting(RAW_PAYLOAD_ARCHIVE_SIZE_KEY, &size.to_string()).await.ok();
+    }
+
+    if let Some(archive) = settings.get(RAW_PAYLOAD_ARCHIVE_KEY).and_then(|v| v.as_bool()) {
+        db.set_setting(RAW_PAYLOAD_ARCHIVE_KEY, &archive.to_string()).await.ok();
+        set_payload_archive_enabled(archive);
+    }
+
     Ok(())
 }
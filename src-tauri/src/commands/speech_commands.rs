@@ -0,0 +1,83 @@
+use tauri::{AppHandle, State};
+
+use crate::commands::error::{CommandError, CommandResult};
+use crate::commands::prayer_commands::user_formatter;
+use crate::commands::reminder_commands::{notification_config, show_notification};
+use crate::db::Database;
+use crate::models::*;
+use crate::services::{self, Language, PrayerNotification};
+
+const SPEECH_CONFIG_KEY: &str = "speech";
+
+#[tauri::command]
+pub async fn get_speech_config(db: State<'_, Database>) -> CommandResult<SpeechConfig> {
+    Ok(load_speech_config(&db).await)
+}
+
+#[tauri::command]
+pub async fn set_speech_config(config: SpeechConfig, db: State<'_, Database>) -> CommandResult<SpeechConfig> {
+    let json = serde_json::to_string(&config).map_err(|e| CommandError::internal(e.to_string()))?;
+    db.set_setting(SPEECH_CONFIG_KEY, &json)
+        .await
+        .map_err(CommandError::database)?;
+    Ok(config)
+}
+
+/// Speak a sample announcement, to try a language and the system voice; returns what was said
+#[tauri::command]
+pub async fn test_speech(language: Option<Language>, db: State<'_, Database>) -> CommandResult<String> {
+    let language = match language {
+        Some(language) => language,
+        None => speech_language(&db, &load_speech_config(&db).await).await,
+    };
+    let text = language.iqama_announcement(PrayerName::Isha, 10);
+
+    services::speak(&text, language)
+        .map_err(|e| CommandError::internal(format!("No text-to-speech engine available: {}", e)))?;
+    Ok(text)
+}
+
+/// Show a prayer reminder, read aloud as well (or instead) when announcements are on for its prayer
+/// The active notification profile decides on sound and speech, and may mute the prayer
+pub async fn deliver_reminder(app: &AppHandle, db: &Database, reminder: &PrayerNotification) {
+    let notifications = notification_config(db).await;
+    if let Ok(prayer) = reminder.prayer_name.parse() {
+        if !notifications.notifies(prayer) {
+            return;
+        }
+    }
+
+    let config = load_speech_config(db).await;
+    let language = speech_language(db, &config).await;
+    let announcement = services::announcement(&config, language, reminder).filter(|_| notifications.speak);
+
+    let spoken = match announcement {
+        Some(text) => match services::speak(&text, language) {
+            Ok(()) => true,
+            Err(e) => {
+                log::warn!("Failed to speak reminder: {}", e);
+                false
+            }
+        },
+        None => false,
+    };
+
+    // A failed announcement still falls back to the notification
+    if !spoken || config.with_notification {
+        show_notification(app, &reminder.title, &reminder.body, notifications.play_sound);
+    }
+}
+
+async fn speech_language(db: &Database, config: &SpeechConfig) -> Language {
+    match config.language {
+        Some(language) => language,
+        None => user_formatter(db).await.language,
+    }
+}
+
+async fn load_speech_config(db: &Database) -> SpeechConfig {
+    match db.get_setting(SPEECH_CONFIG_KEY).await {
+        Ok(Some(json)) => serde_json::from_str(&json).unwrap_or_default(),
+        _ => SpeechConfig::default(),
+    }
+}
@@ -0,0 +1,168 @@
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, State};
+use tauri_plugin_autostart::ManagerExt;
+use ts_rs::TS;
+
+use crate::commands::error::{CommandError, CommandResult};
+use crate::commands::kiosk_commands::KIOSK_CONFIG_KEY;
+use crate::commands::server_commands::HTTP_SERVER_CONFIG_KEY;
+use crate::commands::settings_commands::DEFAULT_COUNTRY_KEY;
+use crate::db::{location, Database};
+use crate::models::{ConfigFile, ConfigFileMode, ProviderConfig, CONFIG_FILE_NAME};
+use crate::providers::provider_config_schema;
+use crate::services::SecretStore;
+
+/// Argument the login item launches the app with
+pub const MINIMIZED_ARG: &str = "--minimized";
+
+/// URL scheme of links handed to the app on the command line
+pub const DEEP_LINK_SCHEME: &str = "iqamah://";
+
+const START_MINIMIZED_KEY: &str = "start_minimized";
+
+/// Launch-at-login and start-in-tray preferences
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct StartupSettings {
+    /// Registered as a login item with the OS
+    pub autostart: bool,
+    /// Login launches stay hidden in the tray
+    pub start_minimized: bool,
+}
+
+#[tauri::command]
+pub async fn get_startup_settings(app: AppHandle, db: State<'_, Database>) -> CommandResult<StartupSettings> {
+    let autostart = app
+        .autolaunch()
+        .is_enabled()
+        .map_err(|e| CommandError::internal(format!("Failed to read autostart state: {}", e)))?;
+
+    Ok(StartupSettings {
+        autostart,
+        start_minimized: start_minimized(&db).await,
+    })
+}
+
+/// Register or remove the login item and save the start-in-tray preference
+#[tauri::command]
+pub async fn save_startup_settings(
+    settings: StartupSettings,
+    app: AppHandle,
+    db: State<'_, Database>,
+) -> CommandResult<()> {
+    let autolaunch = app.autolaunch();
+    let result = if settings.autostart {
+        autolaunch.enable()
+    } else {
+        autolaunch.disable()
+    };
+    result.map_err(|e| CommandError::internal(format!("Failed to update autostart: {}", e)))?;
+
+    db.set_setting(START_MINIMIZED_KEY, &settings.start_minimized.to_string())
+        .await
+        .map_err(CommandError::database)
+}
+
+/// Whether this launch should stay in the tray: started at login with the preference on
+pub async fn launch_minimized(db: &Database) -> bool {
+    std::env::args().any(|arg| arg == MINIMIZED_ARG) && start_minimized(db).await
+}
+
+async fn start_minimized(db: &Database) -> bool {
+    matches!(db.get_setting(START_MINIMIZED_KEY).await, Ok(Some(value)) if value == "true")
+}
+
+/// Seed or override settings from `config.toml` in the app directory, before anything reads them
+/// Most installs have no such file; a broken one is logged and skipped so the app still starts
+pub async fn apply_config_file(db: &Database) {
+    let Some(path) = location::app_dir().map(|dir| dir.join(CONFIG_FILE_NAME)) else {
+        return;
+    };
+    let text = match std::fs::read_to_string(&path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return,
+        Err(e) => {
+            log::warn!("Failed to read {}: {}", path.display(), e);
+            return;
+        }
+    };
+    let config = match ConfigFile::parse(&text) {
+        Ok(config) => config,
+        Err(e) => {
+            log::warn!("Ignoring {}: {}", path.display(), e);
+            return;
+        }
+    };
+
+    let mut settings = config.raw_settings();
+    if let Some(language) = &config.language {
+        settings.push(("language".to_string(), language.clone()));
+    }
+    if let Some(country) = &config.default_country {
+        settings.push((DEFAULT_COUNTRY_KEY.to_string(), country.to_ascii_uppercase()));
+    }
+    if let Some(kiosk) = &config.kiosk {
+        // Saved the way entering and leaving kiosk mode saves it
+        let json = if kiosk.enabled {
+            serde_json::to_string(&kiosk.config).unwrap_or_default()
+        } else {
+            String::new()
+        };
+        settings.push((KIOSK_CONFIG_KEY.to_string(), json));
+    }
+    if let Some(server) = &config.http_server {
+        settings.push((HTTP_SERVER_CONFIG_KEY.to_string(), serde_json::to_string(server).unwrap_or_default()));
+    }
+
+    let mut applied = 0;
+    for (key, value) in settings {
+        if config.mode == ConfigFileMode::Seed && matches!(db.get_setting(&key).await, Ok(Some(_))) {
+            continue;
+        }
+        match db.set_setting(&key, &value).await {
+            Ok(()) => applied += 1,
+            Err(e) => log::warn!("Failed to apply {} from {}: {}", key, CONFIG_FILE_NAME, e),
+        }
+    }
+
+    // Saving a provider's config is what makes it the active provider
+    if let (Some(provider), Some(provider_settings)) = (&config.provider, config.provider_settings()) {
+        let saved = matches!(db.get_provider_config(&provider.id).await, Ok(Some(_)));
+        if config.mode == ConfigFileMode::Override || !saved {
+            let schema = provider_config_schema(&provider.id);
+            let provider_config = ProviderConfig {
+                provider_id: provider.id.clone(),
+                settings: provider_settings,
+            };
+            match db.save_provider_config(&SecretStore::new().seal(provider_config, &schema)).await {
+                Ok(()) => applied += 1,
+                Err(e) => log::warn!("Failed to apply provider {} from {}: {}", provider.id, CONFIG_FILE_NAME, e),
+            }
+        }
+    }
+
+    log::info!("Applied {} settings from {}", applied, path.display());
+}
+
+/// First `iqamah://` link among the launch arguments
+pub fn deep_link_arg(args: &[String]) -> Option<&str> {
+    args.iter()
+        .map(String::as_str)
+        .find(|arg| arg.starts_with(DEEP_LINK_SCHEME))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deep_link_arg() {
+        let args = vec![
+            "iqamah".to_string(),
+            MINIMIZED_ARG.to_string(),
+            "iqamah://mosque/abc".to_string(),
+        ];
+        assert_eq!(deep_link_arg(&args), Some("iqamah://mosque/abc"));
+        assert_eq!(deep_link_arg(&args[..2]), None);
+    }
+}
@@ -0,0 +1,151 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
+use serde::Serialize;
+use tauri::State;
+use tokio::sync::Mutex;
+use ts_rs::TS;
+
+use crate::commands::error::{CommandError, CommandResult};
+use crate::db::{location, Database};
+use crate::models::{LegacyImportSummary, DEFAULT_PROFILE};
+use crate::services::{import_legacy_data, read_legacy_source};
+
+/// How often the real database file is retried while running in memory
+pub const STORAGE_RECOVERY_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Whether data written now survives a restart
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, optional_fields = nullable)]
+pub struct StorageStatus {
+    /// False while running on the in-memory fallback
+    pub persistent: bool,
+    pub db_path: String,
+    /// Data kept next to the executable instead of the user's data directory
+    pub portable: bool,
+    /// Why the database file could not be opened
+    pub last_error: Option<String>,
+}
+
+/// Database file location and the last failure to open it
+pub struct StorageState {
+    db_path: Mutex<String>,
+    last_error: Mutex<Option<String>>,
+}
+
+impl StorageState {
+    pub fn new(db_path: String, error: Option<String>) -> Self {
+        Self {
+            db_path: Mutex::new(db_path),
+            last_error: Mutex::new(error),
+        }
+    }
+
+    pub async fn status(&self, db: &Database) -> StorageStatus {
+        StorageStatus {
+            persistent: !db.is_in_memory(),
+            db_path: self.db_path.lock().await.clone(),
+            portable: location::is_portable(),
+            last_error: self.last_error.lock().await.clone(),
+        }
+    }
+
+    /// Try the database file again and move the session's writes into it
+    pub async fn recover(&self, db: &Database) -> StorageStatus {
+        let db_path = self.db_path.lock().await.clone();
+        match db.recover_to(&db_path).await {
+            Ok(replayed) => {
+                log::info!("Database recovered at {}, replayed {} rows", db_path, replayed);
+                *self.last_error.lock().await = None;
+            }
+            Err(e) => {
+                log::warn!("Database still unavailable at {}: {}", db_path, e);
+                *self.last_error.lock().await = Some(e.to_string());
+            }
+        }
+        self.status(db).await
+    }
+}
+
+#[tauri::command]
+pub async fn get_storage_status(
+    db: State<'_, Database>,
+    storage: State<'_, StorageState>,
+) -> CommandResult<StorageStatus> {
+    Ok(storage.status(&db).await)
+}
+
+/// Copy the database to `new_path` (a folder, or a `.db` file) and use it from now on.
+/// The previous file is kept as a backup.
+#[tauri::command]
+pub async fn move_database(
+    new_path: String,
+    db: State<'_, Database>,
+    storage: State<'_, StorageState>,
+) -> CommandResult<StorageStatus> {
+    if new_path.trim().is_empty() {
+        return Err(CommandError::invalid_input("Database location is empty"));
+    }
+
+    let target = location::resolve_target(new_path.trim());
+    let target_str = target.to_string_lossy().to_string();
+
+    db.move_to(&target_str)
+        .await
+        .map_err(|e| CommandError::internal(format!("Failed to move database: {}", e)))?;
+    location::save_database_path(&target)
+        .map_err(|e| CommandError::internal(format!("Database moved but location not saved: {}", e)))?;
+
+    let mut db_path = storage.db_path.lock().await;
+    log::info!("Database moved from {} to {}", db_path, target_str);
+    *db_path = target_str;
+    drop(db_path);
+
+    Ok(storage.status(&db).await)
+}
+
+/// Import favorites and history from an older install, a Mawaqit export or a CSV file into `profile`.
+/// Without a path, imports the databases found in earlier installs' data folders, if any.
+/// Nothing already saved is overwritten, so running it twice is harmless.
+#[tauri::command]
+pub async fn migrate_legacy_data(
+    path: Option<String>,
+    profile: Option<String>,
+    db: State<'_, Database>,
+) -> CommandResult<Vec<LegacyImportSummary>> {
+    let profile = profile.unwrap_or_else(|| DEFAULT_PROFILE.to_string());
+    let paths = match path.as_deref().map(str::trim) {
+        Some("") => return Err(CommandError::invalid_input("Legacy data path is empty")),
+        Some(path) => {
+            let path = PathBuf::from(path);
+            if !path.is_file() {
+                return Err(CommandError::not_found(format!("No file at {}", path.display())));
+            }
+            vec![path]
+        }
+        None => location::legacy_database_paths(),
+    };
+
+    let mut summaries = Vec::new();
+    for path in paths {
+        let data = read_legacy_source(&path, &profile)
+            .await
+            .map_err(|e| CommandError::invalid_input(format!("Could not read {}: {}", path.display(), e)))?;
+        let summary = import_legacy_data(&db, data, &path, &profile)
+            .await
+            .map_err(CommandError::database)?;
+
+        log::info!(
+            "Migrated {} from {}: {} favorites, {} schedule days, {} logged prayers, {} skipped",
+            summary.source,
+            summary.path,
+            summary.favorites,
+            summary.schedules,
+            summary.log_entries,
+            summary.skipped
+        );
+        summaries.push(summary);
+    }
+
+    Ok(summaries)
+}
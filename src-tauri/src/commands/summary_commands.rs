@@ -0,0 +1,112 @@
+use chrono::{DateTime, Duration, FixedOffset, Local, NaiveDate, NaiveTime, Utc};
+use tauri::State;
+
+use crate::commands::error::{CommandError, CommandResult};
+use crate::commands::hijri_commands::load_mosque_hijri_info;
+use crate::commands::mosque_commands::with_freshness;
+use crate::commands::prayer_commands::{
+    evaluation_time, load_availability, mosque_or_selected, user_engine_config, with_start_timing,
+};
+use crate::commands::schedule_commands::DEFAULT_FORECAST_DAYS;
+use crate::db::Database;
+use crate::models::*;
+use crate::providers::is_mock_mosque;
+use crate::services::{
+    clock, forecast_iqama_changes, ticker, DayIqamas, HijriService, PrayerEngine, MIN_FORECAST_SHIFT_MINUTES,
+};
+
+/// Everything the dashboard needs for a day in one call
+/// Falls back to the selected mosque; date format: "YYYY-MM-DD", defaults to the day of `at`
+/// Another day is summarized as it starts, so its countdowns don't run from today's clock
+#[tauri::command]
+pub async fn get_day_summary(
+    mosque_id: Option<String>,
+    date: Option<String>,
+    at: Option<String>,
+    utc_offset_minutes: Option<i32>,
+    db: State<'_, Database>,
+) -> CommandResult<DaySummary> {
+    let mosque_id = mosque_or_selected(&db, mosque_id).await?;
+    let (now, today) = evaluation_time(at, utc_offset_minutes, ticker::countdown_now())?;
+    let target_date = match date {
+        Some(d) => NaiveDate::parse_from_str(&d, "%Y-%m-%d")
+            .map_err(|e| CommandError::invalid_input(format!("Invalid date format. Use YYYY-MM-DD: {}", e)))?,
+        None => today,
+    };
+    let now = if target_date == today {
+        now
+    } else {
+        day_start(target_date, utc_offset_minutes)
+    };
+
+    let prayer_times = db
+        .get_prayer_times(&mosque_id, target_date)
+        .await
+        .map_err(CommandError::database)?
+        .ok_or_else(|| CommandError::not_found(format!("No prayer times found for {}", target_date)))?;
+
+    let latitude = db
+        .get_mosque(&mosque_id)
+        .await
+        .map_err(CommandError::database)?
+        .and_then(|mosque| mosque.latitude);
+    let next_fajr = match target_date.succ_opt() {
+        Some(next_day) => db
+            .get_prayer_times(&mosque_id, next_day)
+            .await
+            .map_err(CommandError::database)?
+            .map(|schedule| schedule.fajr.adhan),
+        None => None,
+    };
+    let engine = PrayerEngine::new(user_engine_config(&db).await)
+        .with_availability(load_availability(&db, &mosque_id, &prayer_times).await)
+        .with_latitude(latitude)
+        .with_next_fajr(next_fajr);
+    let engine = with_start_timing(engine, &db, &mosque_id).await;
+
+    let hijri_info = if is_mock_mosque(&mosque_id) {
+        None
+    } else {
+        load_mosque_hijri_info(&db, &mosque_id).await
+    };
+
+    Ok(DaySummary {
+        countdowns: engine.get_all_countdowns(&prayer_times, now),
+        next_prayer: engine.get_next_prayer(&prayer_times, now),
+        phase: engine.current_phase(&prayer_times, now),
+        windows: engine.prayer_windows(&prayer_times),
+        shuruq: prayer_times.shuruq,
+        hijri: HijriService::new(hijri_info).day(target_date),
+        jumuah_sessions: prayer_times.jumuah.iter().cloned().collect(),
+        next_schedule_change: next_schedule_change(&db, &mosque_id, target_date).await?,
+        mosque_id,
+        date: target_date,
+        prayer_times: with_freshness(&db, prayer_times).await,
+    })
+}
+
+/// Local midnight starting `date`, in the given offset or the system timezone
+fn day_start(date: NaiveDate, utc_offset_minutes: Option<i32>) -> DateTime<Utc> {
+    let midnight = date.and_time(NaiveTime::MIN);
+    match utc_offset_minutes.and_then(|minutes| FixedOffset::east_opt(minutes.checked_mul(60)?)) {
+        Some(offset) => clock::resolve_local(&offset, midnight),
+        None => clock::resolve_local(&Local, midnight),
+    }
+}
+
+/// First iqama jump in the cached schedules following `date`
+async fn next_schedule_change(
+    db: &Database,
+    mosque_id: &str,
+    date: NaiveDate,
+) -> CommandResult<Option<UpcomingScheduleChange>> {
+    let days = db
+        .get_prayer_times_range(mosque_id, date, date + Duration::days(DEFAULT_FORECAST_DAYS as i64))
+        .await
+        .map_err(CommandError::database)?
+        .iter()
+        .map(DayIqamas::from_schedule)
+        .collect::<Vec<_>>();
+
+    Ok(forecast_iqama_changes(&days, MIN_FORECAST_SHIFT_MINUTES).into_iter().next())
+}
@@ -0,0 +1,281 @@
+use std::time::{Duration as StdDuration, Instant};
+
+use chrono::{DateTime, Duration, Utc};
+use tauri::{AppHandle, State};
+use tokio::sync::Mutex;
+
+use crate::commands::crowd_commands::crowd_level;
+use crate::commands::error::{CommandError, CommandResult};
+use crate::commands::prayer_commands::{parse_prayer_name, saved_travel_time, user_engine_config, with_start_timing};
+use crate::commands::server_commands::broadcast_event;
+use crate::db::Database;
+use crate::models::*;
+use crate::services::{
+    clock, learn_travel_time, PrayerEngine, TravelSession, MAX_TRAVEL_SESSION_SECS, MIN_LOGGED_TRIP_SECS,
+    TRAVEL_LEARNING_TRIPS,
+};
+
+/// How often the ticker refreshes a session nobody sent a position to
+const TICK_UPDATE_INTERVAL: StdDuration = StdDuration::from_secs(15);
+
+/// A prayer stays worth heading to for this long after its iqama
+const LATE_ARRIVAL_MINUTES: i64 = 10;
+
+/// The "on my way" session in progress, refreshed by the background ticker between positions
+pub struct TravelSessionState {
+    session: Mutex<Option<TravelSession>>,
+    last_update: Mutex<Option<Instant>>,
+}
+
+impl TravelSessionState {
+    pub fn new() -> Self {
+        Self {
+            session: Mutex::new(None),
+            last_update: Mutex::new(None),
+        }
+    }
+
+    /// A fresh update for the running session, at most every `TICK_UPDATE_INTERVAL`
+    /// Ends the session once it has run too long to be a trip to the mosque
+    pub async fn tick(&self, db: &Database) -> Option<TravelSessionUpdate> {
+        let mut guard = self.session.lock().await;
+        let session = guard.as_ref()?;
+        let now = clock::now();
+
+        if session.is_timed_out(now) {
+            let session = guard.take()?;
+            return Some(finish_session(db, session, TravelSessionStatus::TimedOut, now).await);
+        }
+
+        let mut last_update = self.last_update.lock().await;
+        if last_update.is_some_and(|at| at.elapsed() < TICK_UPDATE_INTERVAL) {
+            return None;
+        }
+        *last_update = Some(Instant::now());
+        Some(session_update(db, session, TravelSessionStatus::Active, now).await)
+    }
+}
+
+impl Default for TravelSessionState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Start heading to a mosque: its arrival prediction is kept up to date until the user gets there
+/// `mode` defaults to the travel mode saved last for the mosque, `prayer_name` to the next prayer
+/// still worth reaching; a session already running is replaced
+#[tauri::command]
+pub async fn start_travel_session(
+    mosque_id: String,
+    mode: Option<TravelMode>,
+    prayer_name: Option<String>,
+    location: Option<GeoLocation>,
+    app: AppHandle,
+    db: State<'_, Database>,
+    state: State<'_, TravelSessionState>,
+) -> CommandResult<TravelSessionUpdate> {
+    let mosque = db
+        .get_mosque(&mosque_id)
+        .await
+        .map_err(CommandError::database)?
+        .ok_or_else(|| CommandError::not_found(format!("Mosque {} not found", mosque_id)))?;
+    let prayer = prayer_name.as_deref().map(parse_prayer_name).transpose()?;
+
+    let mode = match mode {
+        Some(mode) => mode,
+        None => db
+            .get_travel_times(&mosque_id)
+            .await
+            .map_err(CommandError::database)?
+            .first()
+            .map_or(TravelMode::Walking, |travel| travel.mode),
+    };
+    let expected_secs = saved_travel_time(&db, &mosque_id, Some(mode)).await?;
+    let mosque_location = mosque.latitude.zip(mosque.longitude).map(|(lat, lon)| GeoLocation::new(lat, lon));
+
+    let now = clock::now();
+    let mut session = TravelSession::new(mosque_id, mosque_location, mode, prayer, expected_secs, now);
+    if let Some(location) = location {
+        session.record_location(location, now);
+    }
+
+    let update = session_update(&db, &session, TravelSessionStatus::Active, now).await;
+    *state.session.lock().await = Some(session);
+    *state.last_update.lock().await = Some(Instant::now());
+
+    broadcast_event(&app, "travel-session-update", &update);
+    Ok(update)
+}
+
+/// Feed the device's position to the running session; it ends on its own inside the mosque's geofence
+#[tauri::command]
+pub async fn update_travel_session(
+    location: GeoLocation,
+    app: AppHandle,
+    db: State<'_, Database>,
+    state: State<'_, TravelSessionState>,
+) -> CommandResult<TravelSessionUpdate> {
+    let mut guard = state.session.lock().await;
+    let session = guard
+        .as_mut()
+        .ok_or_else(|| CommandError::not_found("No travel session in progress"))?;
+    let now = clock::now();
+
+    let update = if session.record_location(location, now) {
+        let session = guard.take().ok_or_else(|| CommandError::internal("Travel session vanished"))?;
+        finish_session(&db, session, TravelSessionStatus::Arrived, now).await
+    } else {
+        *state.last_update.lock().await = Some(Instant::now());
+        session_update(&db, session, TravelSessionStatus::Active, now).await
+    };
+
+    broadcast_event(&app, "travel-session-update", &update);
+    Ok(update)
+}
+
+/// End the running session without logging the trip
+/// `arrived` ends it as an arrival instead, for mosques without coordinates to geofence
+#[tauri::command]
+pub async fn stop_travel_session(
+    arrived: Option<bool>,
+    app: AppHandle,
+    db: State<'_, Database>,
+    state: State<'_, TravelSessionState>,
+) -> CommandResult<Option<TravelSessionUpdate>> {
+    let Some(session) = state.session.lock().await.take() else {
+        return Ok(None);
+    };
+    let status = if arrived.unwrap_or(false) {
+        TravelSessionStatus::Arrived
+    } else {
+        TravelSessionStatus::Cancelled
+    };
+
+    let update = finish_session(&db, session, status, clock::now()).await;
+    broadcast_event(&app, "travel-session-update", &update);
+    Ok(Some(update))
+}
+
+/// The running session, if any
+#[tauri::command]
+pub async fn get_travel_session(
+    db: State<'_, Database>,
+    state: State<'_, TravelSessionState>,
+) -> CommandResult<Option<TravelSessionUpdate>> {
+    let guard = state.session.lock().await;
+    match guard.as_ref() {
+        Some(session) => Ok(Some(session_update(&db, session, TravelSessionStatus::Active, clock::now()).await)),
+        None => Ok(None),
+    }
+}
+
+/// Where the session stands, with the arrival prediction recalculated for `now`
+async fn session_update(
+    db: &Database,
+    session: &TravelSession,
+    status: TravelSessionStatus,
+    now: DateTime<Utc>,
+) -> TravelSessionUpdate {
+    let remaining_secs = session.remaining_secs(now);
+    let (prayer, prediction) = match remaining_secs {
+        Some(remaining) => predict_arrival(db, session, remaining, now).await,
+        None => (session.prayer, None),
+    };
+
+    TravelSessionUpdate {
+        mosque_id: session.mosque_id.clone(),
+        mode: session.mode,
+        prayer,
+        status,
+        started_at: session.started_at,
+        elapsed_secs: session.elapsed_secs(now),
+        remaining_secs,
+        eta: remaining_secs.map(|secs| now + Duration::seconds(secs)),
+        distance_m: session.distance_m(),
+        prediction,
+        travel_time_secs: None,
+        learned_travel_time_secs: None,
+    }
+}
+
+/// The session's prayer (or the next one still worth reaching) and where it will be on arrival
+async fn predict_arrival(
+    db: &Database,
+    session: &TravelSession,
+    remaining_secs: i64,
+    now: DateTime<Utc>,
+) -> (Option<PrayerName>, Option<TravelPrediction>) {
+    let Ok(Some(times)) = db.get_prayer_times(&session.mosque_id, clock::today()).await else {
+        return (session.prayer, None);
+    };
+    let prayer = match session.prayer {
+        Some(name) => times.get_prayer_by_name(name),
+        None => times.all_prayers().into_iter().find(|prayer| {
+            prayer.iqama.unwrap_or(prayer.adhan) + Duration::minutes(LATE_ARRIVAL_MINUTES) > now
+        }),
+    };
+    let Some(prayer) = prayer else {
+        return (session.prayer, None);
+    };
+
+    let engine = PrayerEngine::new(user_engine_config(db).await)
+        .with_crowd_level(crowd_level(db, &session.mosque_id, prayer.name).await);
+    let engine = with_start_timing(engine, db, &session.mosque_id).await;
+
+    (Some(prayer.name), Some(engine.calculate_travel_prediction(prayer, remaining_secs, now)))
+}
+
+/// Final update of a session; an arrival logs the trip and saves the travel time learned from it
+async fn finish_session(
+    db: &Database,
+    session: TravelSession,
+    status: TravelSessionStatus,
+    now: DateTime<Utc>,
+) -> TravelSessionUpdate {
+    let mut update = session_update(db, &session, status, now).await;
+    update.remaining_secs = None;
+    update.eta = None;
+    if status != TravelSessionStatus::Arrived {
+        return update;
+    }
+
+    let secs = session.elapsed_secs(now);
+    update.travel_time_secs = Some(secs);
+    if !(MIN_LOGGED_TRIP_SECS..=MAX_TRAVEL_SESSION_SECS).contains(&secs) {
+        return update;
+    }
+
+    if let Err(e) = db
+        .save_travel_trip(&session.mosque_id, session.mode, session.started_at, secs)
+        .await
+    {
+        log::warn!("Failed to log trip to {}: {}", session.mosque_id, e);
+        return update;
+    }
+    update.learned_travel_time_secs = learn_and_save(db, &session).await;
+    update
+}
+
+/// Typical travel time from the latest trips, saved for the mosque when it is a favorite
+async fn learn_and_save(db: &Database, session: &TravelSession) -> Option<i64> {
+    let trips = db
+        .get_recent_travel_trips(&session.mosque_id, session.mode, TRAVEL_LEARNING_TRIPS)
+        .await
+        .ok()?;
+    let learned = learn_travel_time(&trips)?;
+
+    // Travel times are only kept for favorites, like when set by hand
+    let favorite = db
+        .get_mosque(&session.mosque_id)
+        .await
+        .ok()
+        .flatten()
+        .is_some_and(|mosque| mosque.is_favorite);
+    if favorite {
+        if let Err(e) = db.set_travel_time(&session.mosque_id, session.mode, Some(learned)).await {
+            log::warn!("Failed to save learned travel time to {}: {}", session.mosque_id, e);
+        }
+    }
+    Some(learned)
+}
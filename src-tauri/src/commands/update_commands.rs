@@ -0,0 +1,57 @@
+use std::time::Duration;
+
+use tauri::AppHandle;
+
+use crate::commands::error::{CommandError, CommandResult};
+use crate::commands::server_commands::broadcast_event;
+use crate::commands::settings_commands::load_automatic_update_checks_enabled;
+use crate::db::Database;
+use crate::models::UpdateInfo;
+use crate::services::{fetch_latest_release, update_info};
+
+/// How often the background check runs while automatic checks are on
+pub const UPDATE_CHECK_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Latest version the user was told about, so each release is announced once
+const UPDATE_NOTIFIED_KEY: &str = "update_notified_version";
+
+/// Compare the running version with the latest release and return its installer and notes
+#[tauri::command]
+pub async fn check_for_updates(app: AppHandle) -> CommandResult<UpdateInfo> {
+    let release = fetch_latest_release()
+        .await
+        .map_err(|e| CommandError::from(e).with_provider("github"))?;
+    let current_version = app.package_info().version.to_string();
+    // Set up in tauri.conf.json; without it updates are downloaded from the release page
+    let self_update = app.config().tauri.updater.active;
+
+    Ok(update_info(&current_version, &release, std::env::consts::OS, self_update))
+}
+
+/// Silent check for the background loop: emits `update-available` once per new release
+/// Does nothing unless the user turned automatic checks on
+pub async fn run_update_check(app: &AppHandle, db: &Database) {
+    if !load_automatic_update_checks_enabled(db).await {
+        return;
+    }
+
+    let info = match check_for_updates(app.clone()).await {
+        Ok(info) => info,
+        Err(e) => {
+            log::debug!("Automatic update check failed: {}", e.message);
+            return;
+        }
+    };
+    if !info.update_available {
+        return;
+    }
+    if matches!(db.get_setting(UPDATE_NOTIFIED_KEY).await, Ok(Some(version)) if version == info.latest_version) {
+        return;
+    }
+
+    log::info!("Update available: {} -> {}", info.current_version, info.latest_version);
+    if let Err(e) = db.set_setting(UPDATE_NOTIFIED_KEY, &info.latest_version).await {
+        log::warn!("Failed to remember announced update: {}", e);
+    }
+    broadcast_event(app, "update-available", info);
+}
@@ -0,0 +1,74 @@
+use tauri::State;
+
+use crate::commands::error::{CommandError, CommandResult};
+use crate::db::Database;
+use crate::commands::settings_commands::{load_raw_payload_archive_enabled, load_raw_payload_archive_size};
+use crate::models::{ArchivedPayload, RawPayloadInfo, UpstreamIssue};
+use crate::services::{decompress_payload, set_payload_archive_enabled, take_raw_payloads, take_upstream_events};
+
+/// Store the parse failures and recoveries providers reported since the last flush
+pub async fn flush_upstream_events(db: &Database) -> anyhow::Result<()> {
+    let events = take_upstream_events();
+    if events.is_empty() {
+        return Ok(());
+    }
+
+    db.save_upstream_events(&events).await
+}
+
+/// Providers whose pages or payloads stopped parsing, most recent first
+/// Lets the UI say e.g. "Mawaqit changed their site" instead of showing a generic error
+#[tauri::command]
+pub async fn get_upstream_issues(db: State<'_, Database>) -> CommandResult<Vec<UpstreamIssue>> {
+    flush_upstream_events(&db).await.map_err(CommandError::database)?;
+
+    db.get_upstream_issues().await.map_err(CommandError::database)
+}
+
+/// Follow the archive setting and store the provider responses recorded since the last flush
+pub async fn flush_raw_payloads(db: &Database) -> anyhow::Result<()> {
+    let enabled = load_raw_payload_archive_enabled(db).await;
+    set_payload_archive_enabled(enabled);
+
+    let payloads = take_raw_payloads();
+    if !enabled || payloads.is_empty() {
+        return Ok(());
+    }
+
+    db.save_raw_payloads(&payloads, load_raw_payload_archive_size(db).await).await
+}
+
+/// Archived provider responses, of one mosque or all, newest first
+/// Lets support see exactly what a provider returned when the app disagrees with the mosque
+#[tauri::command]
+pub async fn get_raw_payloads(
+    mosque_id: Option<String>,
+    db: State<'_, Database>,
+) -> CommandResult<Vec<RawPayloadInfo>> {
+    flush_raw_payloads(&db).await.map_err(CommandError::database)?;
+
+    db.get_raw_payloads(mosque_id.as_deref()).await.map_err(CommandError::database)
+}
+
+/// One archived response, decompressed
+#[tauri::command]
+pub async fn get_raw_payload(id: i64, db: State<'_, Database>) -> CommandResult<ArchivedPayload> {
+    let (info, body) = db
+        .get_raw_payload(id)
+        .await
+        .map_err(CommandError::database)?
+        .ok_or_else(|| CommandError::not_found(format!("Archived response {} not found", id)))?;
+    let body = decompress_payload(&body)
+        .map_err(|e| CommandError::internal(format!("Archived response {} is unreadable: {}", id, e)))?;
+
+    Ok(ArchivedPayload {
+        info,
+        body: String::from_utf8_lossy(&body).into_owned(),
+    })
+}
+
+/// Delete archived responses, of one mosque or all; returns how many were removed
+#[tauri::command]
+pub async fn clear_raw_payloads(mosque_id: Option<String>, db: State<'_, Database>) -> CommandResult<u64> {
+    db.clear_raw_payloads(mosque_id.as_deref()).await.map_err(CommandError::database)
+}
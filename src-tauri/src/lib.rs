@@ -1,6 +1,4 @@
 // Library exports for testing and FFI
 pub mod commands;
-pub mod db;
-pub mod models;
-pub mod providers;
-pub mod services;
+
+pub use ikama_core::{db, models, providers, services};
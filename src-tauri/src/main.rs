@@ -1,58 +1,101 @@
 // Prevents additional console window on Windows in release
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
-use tauri::Manager;
+use tauri::{CustomMenuItem, Manager, SystemTray, SystemTrayEvent, SystemTrayMenu};
+use tauri_plugin_autostart::MacosLauncher;
 
 mod commands;
-mod db;
-mod models;
-mod providers;
-mod services;
+
+use ikama_core::{db, models, providers, services};
 
 use db::Database;
 
 fn main() {
-    env_logger::init();
+    // Next to the executable in portable mode
+    let log_dir = db::location::log_dir().unwrap_or_else(|| std::env::temp_dir().join("iqamah").join("logs"));
+    if let Err(e) = services::init_logging(&log_dir) {
+        // Fall back to console-only logging
+        let _ = tracing_subscriber::fmt().try_init();
+        log::warn!("Failed to initialize file logging: {}", e);
+    }
+    if db::location::is_portable() {
+        log::info!("Portable mode, data kept in {}", log_dir.parent().unwrap_or(&log_dir).display());
+    }
+
+    let tray_menu = SystemTrayMenu::new()
+        .add_item(CustomMenuItem::new("show", "Show Iqamah"))
+        .add_item(CustomMenuItem::new("quit", "Quit"));
 
     tauri::Builder::default()
+        // A second launch focuses this window and forwards its deep link instead of opening the DB twice
+        .plugin(tauri_plugin_single_instance::init(|app, argv, _cwd| {
+            log::info!("Second instance launched, focusing existing window");
+            show_main_window(app);
+            if let Some(link) = commands::deep_link_arg(&argv) {
+                if let Err(e) = app.emit_all("deep-link", link) {
+                    log::warn!("Failed to forward deep link: {}", e);
+                }
+            }
+        }))
+        .plugin(tauri_plugin_autostart::init(
+            MacosLauncher::LaunchAgent,
+            Some(vec![commands::MINIMIZED_ARG]),
+        ))
+        .system_tray(SystemTray::new().with_menu(tray_menu))
+        .on_system_tray_event(|app, event| match event {
+            SystemTrayEvent::LeftClick { .. } => show_main_window(app),
+            SystemTrayEvent::MenuItemClick { id, .. } => match id.as_str() {
+                "show" => show_main_window(app),
+                "quit" => app.exit(0),
+                _ => {}
+            },
+            _ => {}
+        })
+        .manage(commands::SearchCoordinator::new())
+        .manage(commands::RequestGate::new())
+        .manage(commands::AdhkarState::new())
+        .manage(commands::RefreshState::new())
+        .manage(commands::KioskState::new())
+        .manage(commands::AdminState::new())
+        .manage(commands::LocalServerState::new())
+        .manage(commands::ReminderState::new())
+        .manage(commands::FajrAlarmState::new())
+        .manage(commands::TravelSessionState::new())
+        .on_window_event(|event| {
+            if let tauri::WindowEvent::Focused(_) | tauri::WindowEvent::Resized(_) = event.event() {
+                let window = event.window();
+                let visible = window.is_visible().unwrap_or(true) && !window.is_minimized().unwrap_or(false);
+                window.state::<commands::RefreshState>().set_window_visible(visible);
+            }
+        })
         .setup(|app| {
             let app_handle = app.handle();
             
+            let db_path = db::location::database_path().map(|path| path.to_string_lossy().to_string());
+
             // Initialize database with better error handling
-            let database_result: anyhow::Result<(Database, String)> = tauri::async_runtime::block_on(async move {
-                let app_dir = match dirs::data_dir() {
-                    Some(dir) => dir.join("iqamah"),
-                    None => {
-                        return Err(anyhow::anyhow!("Could not find data directory"));
-                    }
+            let database_result: anyhow::Result<Database> = tauri::async_runtime::block_on(async {
+                let Some(db_path) = &db_path else {
+                    return Err(anyhow::anyhow!("Could not find data directory"));
                 };
 
-                // Ensure the directory exists
-                if let Err(e) = std::fs::create_dir_all(&app_dir) {
-                    return Err(anyhow::anyhow!("Failed to create app directory: {}", e));
-                }
+                log::info!("Initializing database at: {}", db_path);
 
-                let db_path = app_dir.join("iqamah.db");
-                let db_path_str = db_path.to_string_lossy().to_string();
-                
-                log::info!("Initializing database at: {}", db_path_str);
-                
-                match db::migrations::create_database(&db_path_str).await {
-                    Ok(pool) => Ok((Database::new(pool), db_path_str)),
+                match db::migrations::create_database(db_path).await {
+                    Ok(pool) => Ok(Database::new(pool)),
                     Err(e) => Err(anyhow::anyhow!("Database creation failed: {}", e)),
                 }
             });
+            let db_path = db_path.unwrap_or_default();
+
+            // Adhan sounds from the user's sounds folder, scanned once here
+            app_handle.manage(commands::AudioState::new());
 
             match database_result {
-                Ok((database, path)) => {
-                    log::info!("Database initialized successfully at: {}", path);
+                Ok(database) => {
+                    log::info!("Database initialized successfully at: {}", db_path);
                     app_handle.manage(database);
-                    
-                    // Store the database path for debugging
-                    let db_path_clone = path.clone();
-                    tauri::async_runtime::spawn(async move {
-                        log::info!("Database persistence path: {}", db_path_clone);
-                    });
+                    app_handle.manage(commands::StorageState::new(db_path, None));
                 }
                 Err(e) => {
                     log::error!("Failed to initialize database: {}", e);
@@ -60,13 +103,15 @@ fn main() {
                     let fallback_result: anyhow::Result<Database> = tauri::async_runtime::block_on(async {
                         let pool = db::migrations::create_database(":memory:").await
                             .map_err(|e| anyhow::anyhow!("Failed to create in-memory DB: {}", e))?;
-                        Ok(Database::new(pool))
+                        Ok(Database::in_memory_fallback(pool))
                     });
-                    
+
                     match fallback_result {
                         Ok(database) => {
-                            log::warn!("Using in-memory database as fallback - data will NOT persist!");
+                            log::warn!("Using in-memory database as fallback - retrying {} periodically", db_path);
                             app_handle.manage(database);
+                            app_handle.manage(commands::StorageState::new(db_path, Some(e.to_string())));
+                            spawn_storage_recovery(app.handle());
                         }
                         Err(e2) => {
                             log::error!("Critical: Failed to create any database: {}", e2);
@@ -75,16 +120,185 @@ fn main() {
                     }
                 }
             }
-            
+
+            // Provisioned screens: config.toml in the app directory seeds or overrides settings
+            let startup_db = app_handle.state::<Database>().inner().clone();
+            tauri::async_runtime::block_on(commands::apply_config_file(&startup_db));
+            // Provider responses are archived from the first fetch when the user opted in
+            if let Err(e) = tauri::async_runtime::block_on(commands::flush_raw_payloads(&startup_db)) {
+                log::warn!("Failed to set up the response archive: {}", e);
+            }
+
+            // Lobby screens come back in kiosk mode after a restart or power cut
+            // Otherwise, launched at login: stay in the tray, notifications keep running
+            let kiosk = app_handle.state::<commands::KioskState>();
+            if tauri::async_runtime::block_on(kiosk.restore(&startup_db)) {
+                log::info!("Resuming kiosk mode");
+                commands::set_kiosk_window(&app_handle, true);
+            } else if tauri::async_runtime::block_on(commands::launch_minimized(&startup_db)) {
+                if let Some(window) = app.get_window("main") {
+                    log::info!("Starting minimized to tray");
+                    let _ = window.hide();
+                }
+            }
+
+            // Optional local HTTP server for calendar subscriptions
+            let server_handle = app.handle();
+            tauri::async_runtime::spawn(async move {
+                let db = server_handle.state::<Database>().inner().clone();
+                server_handle.state::<commands::LocalServerState>().restore(&db).await;
+            });
+
+            // Finish schedule refreshes a crash or power cut interrupted
+            let journal_handle = app.handle();
+            tauri::async_runtime::spawn(async move {
+                let db = journal_handle.state::<Database>().inner().clone();
+                commands::replay_write_journal(&db).await;
+            });
+
+            // Optional daily update check, off unless the user turns it on in settings
+            let update_handle = app.handle();
+            tauri::async_runtime::spawn(async move {
+                loop {
+                    let db = update_handle.state::<Database>().inner().clone();
+                    commands::run_update_check(&update_handle, &db).await;
+                    tokio::time::sleep(commands::UPDATE_CHECK_INTERVAL).await;
+                }
+            });
+
+            // Global shortcuts, also reachable from a Stream Deck
+            let hotkey_handle = app.handle();
+            tauri::async_runtime::spawn(async move {
+                let db = hotkey_handle.state::<Database>().inner().clone();
+                commands::restore_hotkeys(&hotkey_handle, &db).await;
+            });
+
+            // Ticker: keeps countdowns on the monotonic clock, updates the tray title, drives the adhkar timer, kiosk screens,
+            // reminders, event reminders, the adhan sound, the Fajr alarm, travel sessions and dashboards connected over WebSocket
+            // Runs every second while the countdown is visible, iqama is near or a dashboard listens, slower otherwise
+            let adhkar_handle = app.handle();
+            tauri::async_runtime::spawn(async move {
+                loop {
+                    let db = adhkar_handle.state::<Database>().inner().clone();
+                    let policy = adhkar_handle.state::<commands::RefreshState>().policy(&db).await;
+                    let server = adhkar_handle.state::<commands::LocalServerState>();
+                    let interval = if server.has_subscribers() {
+                        policy.tick_interval().min(commands::DASHBOARD_TICK_INTERVAL)
+                    } else {
+                        policy.tick_interval()
+                    };
+                    let slept_from = std::time::Instant::now();
+                    tokio::time::sleep(interval).await;
+                    services::record_tick(interval, slept_from.elapsed());
+
+                    if let Some(resync) = services::ticker::tick() {
+                        log::info!("System clock jumped {} ms, countdowns re-anchored", resync.correction_ms);
+                        commands::broadcast_event(&adhkar_handle, "clock-resync", resync);
+                    }
+                    server.publish_countdown(&db).await;
+                    update_tray_title(&adhkar_handle, &db).await;
+
+                    let events = adhkar_handle.state::<commands::AdhkarState>().tick().await;
+                    for event in events {
+                        commands::broadcast_event(&adhkar_handle, "adhkar-event", event);
+                    }
+
+                    if let Some(frame) = adhkar_handle.state::<commands::KioskState>().tick(&db).await {
+                        commands::broadcast_event(&adhkar_handle, "kiosk-frame", frame);
+                    }
+
+                    for reminder in adhkar_handle.state::<commands::ReminderState>().tick(&db).await {
+                        commands::deliver_reminder(&adhkar_handle, &db, &reminder).await;
+                    }
+
+                    adhkar_handle.state::<commands::AudioState>().tick(&db).await;
+                    commands::deliver_event_reminders(&adhkar_handle, &db).await;
+
+                    if let Some(ring) = adhkar_handle.state::<commands::FajrAlarmState>().tick(&db).await {
+                        commands::ring_fajr_alarm(&adhkar_handle, &db, &ring).await;
+                    }
+
+                    if let Some(update) = adhkar_handle.state::<commands::TravelSessionState>().tick(&db).await {
+                        commands::broadcast_event(&adhkar_handle, "travel-session-update", update);
+                    }
+                }
+            });
+
+            // Re-fetch favorite schedules, alert when a mosque moves its iqama and rewrite the synced calendar
+            let refresh_handle = app.handle();
+            tauri::async_runtime::spawn(async move {
+                let mut deferrals = 0;
+                loop {
+                    let db = refresh_handle.state::<Database>().inner().clone();
+                    let policy = refresh_handle.state::<commands::RefreshState>().policy(&db).await;
+                    if policy.defer_network && deferrals < commands::MAX_REFRESH_DEFERRALS {
+                        deferrals += 1;
+                        tokio::time::sleep(commands::DEFERRED_REFRESH_RETRY).await;
+                        continue;
+                    }
+
+                    deferrals = 0;
+                    let changes = commands::refresh_favorite_schedules(&db).await;
+                    if !changes.is_empty() {
+                        notify_schedule_changes(&refresh_handle, &db, &changes).await;
+                    }
+                    if let Err(e) = commands::flush_upstream_events(&db).await {
+                        log::warn!("Failed to store upstream issues: {}", e);
+                    }
+                    if let Err(e) = commands::flush_raw_payloads(&db).await {
+                        log::warn!("Failed to archive provider responses: {}", e);
+                    }
+                    if let Err(e) = commands::sync_calendar(&db).await {
+                        log::warn!("Calendar sync failed: {}", e.message);
+                    }
+                    tokio::time::sleep(commands::SCHEDULE_REFRESH_INTERVAL).await;
+                }
+            });
+
+            // Record goal progress at night, with an encouraging summary at the end of the week
+            let goals_handle = app.handle();
+            tauri::async_runtime::spawn(async move {
+                loop {
+                    let db = goals_handle.state::<Database>().inner().clone();
+                    let summaries = commands::evaluate_goals(&db).await;
+                    if !summaries.is_empty() {
+                        notify_goal_summaries(&goals_handle, &summaries);
+                    }
+                    tokio::time::sleep(commands::GOAL_CHECK_INTERVAL).await;
+                }
+            });
+
+            // Announce special days of the Islamic calendar the evening their night begins
+            let events_handle = app.handle();
+            tauri::async_runtime::spawn(async move {
+                loop {
+                    let db = events_handle.state::<Database>().inner().clone();
+                    let reminders = commands::due_islamic_event_reminders(&db).await;
+                    if !reminders.is_empty() {
+                        notify_islamic_events(&events_handle, &reminders);
+                    }
+                    tokio::time::sleep(commands::ISLAMIC_EVENT_CHECK_INTERVAL).await;
+                }
+            });
+
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
             // Mosque commands
             commands::search_mosques,
+            commands::download_country_catalog,
             commands::get_favorite_mosques,
             commands::add_favorite_mosque,
             commands::remove_favorite_mosque,
             commands::get_mosque_details,
+            commands::compare_mosques,
+            commands::set_mosque_facilities,
+            commands::set_travel_time,
+            commands::get_travel_times,
+            commands::start_travel_session,
+            commands::update_travel_session,
+            commands::stop_travel_session,
+            commands::get_travel_session,
             commands::get_prayer_times_for_mosque,
             commands::fetch_prayer_times_for_date,
             commands::get_active_provider,
@@ -92,23 +306,221 @@ fn main() {
             commands::test_provider_connection,
             commands::save_selected_mosque,
             commands::get_selected_mosque,
+            commands::detect_region,
+            commands::suggest_nearby_mosques,
+            commands::complete_onboarding,
             commands::check_database_health,
+            commands::get_cache_stats,
+            commands::import_mawaqit_account,
+            commands::get_mosque_events,
+            commands::set_mosque_events_feed,
+            commands::set_event_reminder,
+            commands::get_due_event_reminders,
             // Prayer commands
             commands::get_next_prayer,
+            commands::get_next_prayer_contextual,
+            commands::get_mosque_contexts,
+            commands::set_mosque_contexts,
             commands::get_prayer_times,
             commands::get_all_countdowns,
+            commands::get_day_summary,
             commands::estimate_rakah,
             commands::calculate_travel_prediction,
+            commands::calculate_jumuah_prediction,
+            commands::generate_share_text,
             commands::get_countdown,
+            commands::refresh_prayer_times,
+            commands::get_schedule_changes,
+            commands::get_upcoming_schedule_changes,
+            commands::get_prayer_availability,
+            commands::set_prayer_availability,
+            commands::get_start_lag,
+            commands::set_start_lag,
+            commands::report_crowd_level,
+            commands::get_crowd_summary,
+            commands::report_actual_iqama,
+            commands::get_iqama_delays,
             commands::format_duration,
+            commands::get_prayer_names,
+            commands::format_prayer_time,
+            commands::get_hijri_date,
+            commands::get_islamic_events,
+            commands::get_fasting_status,
+            commands::mark_prayer_attended,
+            commands::get_adhkar_status,
+            commands::increment_adhkar,
+            commands::stop_adhkar,
+            commands::get_adhkar_config,
+            commands::save_adhkar_config,
+            // Prayer log commands
+            commands::log_prayer,
+            commands::get_prayer_log,
+            commands::delete_prayer_log_entry,
+            commands::get_family_attendance,
+            // Goal commands
+            commands::create_goal,
+            commands::delete_goal,
+            commands::get_goals_status,
+            // Kiosk commands
+            commands::enter_kiosk_mode,
+            commands::exit_kiosk_mode,
+            commands::get_kiosk_status,
+            // Admin commands
+            commands::get_admin_status,
+            commands::set_admin_pin,
+            commands::unlock_admin_mode,
+            commands::lock_admin_mode,
+            commands::admin_edit_schedule,
+            commands::admin_copy_ramadan,
+            commands::export_schedule_file,
+            // Local server commands
+            commands::get_http_server_status,
+            commands::set_http_server_config,
+            commands::list_api_tokens,
+            commands::create_api_token,
+            commands::revoke_api_token,
+            // Calendar sync commands
+            commands::get_calendar_sync_status,
+            commands::set_calendar_sync_config,
+            commands::sync_calendar_now,
+            // Fajr alarm commands
+            commands::get_fajr_alarm_config,
+            commands::set_fajr_alarm_config,
+            commands::get_fajr_alarm_status,
+            commands::dismiss_fajr_alarm,
+            // Adhan sound commands
+            commands::list_adhan_sounds,
+            commands::get_adhan_sound_config,
+            commands::set_adhan_sound_config,
+            commands::test_adhan_sound,
+            commands::list_audio_output_devices,
+            // Speech commands
+            commands::get_speech_config,
+            commands::set_speech_config,
+            commands::test_speech,
+            // Hotkey commands
+            commands::get_hotkeys,
+            commands::set_hotkeys,
+            commands::trigger_hotkey,
+            commands::snooze_reminder,
+            // Notification template and profile commands
+            commands::get_notification_templates,
+            commands::set_notification_templates,
+            commands::get_notification_profiles,
+            commands::set_notification_profiles,
+            commands::get_active_notification_profile,
+            commands::get_mosque_notification_overrides,
+            commands::set_mosque_notification_override,
+            commands::clear_mosque_notification_override,
             // Settings commands
             commands::get_setting,
             commands::set_setting,
             commands::get_provider_config,
             commands::save_provider_config,
+            commands::get_iqama_defaults,
+            commands::save_iqama_defaults,
             commands::get_all_settings,
             commands::save_all_settings,
+            commands::get_startup_settings,
+            commands::save_startup_settings,
+            commands::get_storage_status,
+            commands::move_database,
+            commands::migrate_legacy_data,
+            // Diagnostics commands
+            commands::get_recent_logs,
+            commands::set_log_level,
+            commands::set_simulated_now,
+            commands::report_app_state,
+            commands::get_refresh_policy,
+            commands::get_upstream_issues,
+            commands::get_raw_payloads,
+            commands::get_raw_payload,
+            commands::clear_raw_payloads,
+            commands::get_metrics,
+            commands::check_for_updates,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
+
+/// Retry the database file until it opens, reporting degraded storage to the UI on every attempt
+fn spawn_storage_recovery(app: tauri::AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(commands::STORAGE_RECOVERY_INTERVAL).await;
+
+            let db = app.state::<Database>().inner().clone();
+            let status = app.state::<commands::StorageState>().recover(&db).await;
+            let persistent = status.persistent;
+            commands::broadcast_event(&app, "storage-status", status);
+            if persistent {
+                break;
+            }
+        }
+    });
+}
+
+/// Bring the main window back from the tray
+fn show_main_window(app: &tauri::AppHandle) {
+    if let Some(window) = app.get_window("main") {
+        let _ = window.show();
+        let _ = window.unminimize();
+        let _ = window.set_focus();
+    }
+}
+
+/// Count down to the next prayer beside the tray icon; other platforms show it as the tooltip
+async fn update_tray_title(app: &tauri::AppHandle, db: &Database) {
+    let Some(title) = commands::tray_title(db).await else {
+        return;
+    };
+    let tray = app.tray_handle();
+    #[cfg(target_os = "macos")]
+    let result = tray.set_title(&title);
+    #[cfg(not(target_os = "macos"))]
+    let result = tray.set_tooltip(&title);
+    if let Err(e) = result {
+        log::debug!("Failed to update the tray title: {}", e);
+    }
+}
+
+/// Tell the UI and the user that favorite mosques moved their iqama
+async fn notify_schedule_changes(
+    app: &tauri::AppHandle,
+    db: &Database,
+    changes: &[models::ScheduleChange],
+) {
+    commands::broadcast_event(app, "schedule-changed", changes);
+
+    let identifier = app.config().tauri.bundle.identifier.clone();
+    for notification in commands::schedule_change_notifications(db, changes).await {
+        if let Err(e) = tauri::api::notification::Notification::new(&identifier)
+            .title(&notification.title)
+            .body(&notification.body)
+            .show()
+        {
+            log::warn!("Failed to show schedule change notification: {}", e);
+        }
+    }
+}
+
+/// Show the end-of-week goal summaries
+fn notify_goal_summaries(app: &tauri::AppHandle, summaries: &[services::PrayerNotification]) {
+    let identifier = app.config().tauri.bundle.identifier.clone();
+    for summary in summaries {
+        if let Err(e) = tauri::api::notification::Notification::new(&identifier)
+            .title(&summary.title)
+            .body(&summary.body)
+            .show()
+        {
+            log::warn!("Failed to show goal summary notification: {}", e);
+        }
+    }
+}
+
+/// Show reminders of special days starting tonight
+fn notify_islamic_events(app: &tauri::AppHandle, reminders: &[services::PrayerNotification]) {
+    for reminder in reminders {
+        commands::show_notification(app, &reminder.title, &reminder.body, false);
+    }
+}
@@ -1,9 +0,0 @@
-pub mod prayer;
-pub mod mosque;
-pub mod geo_location;
-pub mod provider;
-
-pub use prayer::*;
-pub use mosque::*;
-pub use geo_location::*;
-pub use provider::*;
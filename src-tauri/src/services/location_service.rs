@@ -1,31 +0,0 @@
-use crate::models::GeoLocation;
-
-/// Location service for getting current position
-pub struct LocationService;
-
-impl LocationService {
-    pub fn new() -> Self {
-        Self
-    }
-
-    /// Get current location (placeholder - would use platform-specific APIs)
-    pub async fn get_current_location(&self) -> anyhow::Result<GeoLocation> {
-        // In a real implementation, this would use:
-        // - Web: Geolocation API
-        // - Desktop: Native geolocation APIs or IP-based approximation
-        
-        // For now, return a placeholder
-        Err(anyhow::anyhow!("Location service not implemented"))
-    }
-
-    /// Calculate distance between two points
-    pub fn calculate_distance(&self, loc1: &GeoLocation, loc2: &GeoLocation) -> f64 {
-        loc1.distance_to(loc2)
-    }
-}
-
-impl Default for LocationService {
-    fn default() -> Self {
-        Self::new()
-    }
-}
@@ -1,7 +0,0 @@
-pub mod prayer_engine;
-pub mod notification_service;
-pub mod location_service;
-
-pub use prayer_engine::*;
-pub use notification_service::*;
-pub use location_service::*;
@@ -1,4 +1,4 @@
-import { useEffect } from 'react';
+import { useEffect, useState } from 'react';
 import { Header } from './components/Header';
 import { MosqueSelector } from './components/MosqueSelector';
 import { NextPrayerCard } from './components/NextPrayerCard';
@@ -6,6 +6,7 @@ import { PrayerList } from './components/PrayerList';
 import { TravelTimeCard } from './components/TravelTimeCard';
 import { ErrorDisplay } from './components/ErrorDisplay';
 import { NightPrayerCard } from './components/NightPrayerCard';
+import { KioskScreen } from './components/KioskScreen';
 import { usePrayerTimes } from './hooks/usePrayerTimes';
 import { useStore } from './hooks/useStore';
 import * as tauri from './services/tauri';
@@ -22,6 +23,16 @@ function App() {
     selectedDate
   } = useStore();
   usePrayerTimes();
+  const [kioskMode, setKioskMode] = useState(false);
+
+  // Lobby screens: the backend switches the window in and out of kiosk mode
+  useEffect(() => {
+    tauri.getKioskStatus().then((status) => setKioskMode(status.enabled)).catch(() => {});
+    const unlisten = tauri.onKioskMode(setKioskMode);
+    return () => {
+      unlisten.then((stop) => stop());
+    };
+  }, []);
 
   // Load saved mosque on startup
   useEffect(() => {
@@ -31,7 +42,7 @@ function App() {
 
       try {
         console.log('Loading saved mosque...');
-        const savedMosque = await tauri.getSelectedMosque();
+        const savedMosque = (await tauri.getSelectedMosque())?.mosque;
 
         if (savedMosque) {
           console.log('Found saved mosque:', savedMosque.name);
@@ -64,6 +75,37 @@ function App() {
     loadSavedMosque();
   }, [currentMosque, setCurrentMosque, setCurrentPrayerTimes, setError, selectedDate]);
 
+  // Let the backend slow its ticker and defer refreshes while hidden or on battery
+  useEffect(() => {
+    const reportVisibility = () => {
+      tauri.reportAppState({ visible: document.visibilityState === 'visible' }).catch(() => {});
+    };
+
+    type Battery = EventTarget & { charging: boolean };
+    const nav = navigator as Navigator & { getBattery?: () => Promise<Battery> };
+    let battery: Battery | null = null;
+    const reportBattery = () => {
+      if (battery) tauri.reportAppState({ onBattery: !battery.charging }).catch(() => {});
+    };
+
+    reportVisibility();
+    document.addEventListener('visibilitychange', reportVisibility);
+    nav.getBattery?.().then((b) => {
+      battery = b;
+      reportBattery();
+      b.addEventListener('chargingchange', reportBattery);
+    }).catch(() => {});
+
+    return () => {
+      document.removeEventListener('visibilitychange', reportVisibility);
+      battery?.removeEventListener('chargingchange', reportBattery);
+    };
+  }, []);
+
+  if (kioskMode) {
+    return <KioskScreen />;
+  }
+
   return (
     <div className="min-h-screen bg-gradient-to-b from-gray-900 via-gray-900 to-gray-800">
       <div className="max-w-6xl mx-auto p-4">
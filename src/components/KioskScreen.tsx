@@ -0,0 +1,82 @@
+import { useEffect, useState } from 'react';
+import type { KioskFrame } from '../types';
+import * as tauri from '../services/tauri';
+import { formatCountdownDisplay } from '../services/time';
+
+const formatClock = (iso: string) =>
+  new Date(iso).toLocaleTimeString([], { hour: '2-digit', minute: '2-digit' });
+
+/** Full-screen lobby display; draws whatever frame the backend pushes */
+export const KioskScreen = () => {
+  const [frame, setFrame] = useState<KioskFrame | null>(null);
+
+  useEffect(() => {
+    const unlisten = tauri.onKioskFrame(setFrame);
+    return () => {
+      unlisten.then((stop) => stop());
+    };
+  }, []);
+
+  if (!frame) {
+    return (
+      <div className="min-h-screen bg-gray-900 flex items-center justify-center">
+        <div className="w-12 h-12 border-4 border-primary-500 border-t-transparent rounded-full animate-spin" />
+      </div>
+    );
+  }
+
+  const focus = frame.focus;
+  // After the adhan the large countdown runs to the iqama
+  const untilIqama = focus && focus.time_until_adhan_secs <= 0 ? focus.time_until_iqama_secs ?? 0 : 0;
+  const showIqama = untilIqama > 0;
+
+  return (
+    <div className="min-h-screen bg-gray-900 text-white flex flex-col p-10 cursor-none select-none">
+      <header className="flex justify-between items-baseline">
+        <h1 className="text-5xl font-bold">{frame.mosque_name ?? frame.mosque_id}</h1>
+        <span className="text-5xl tabular-nums text-gray-300">{formatClock(frame.now)}</span>
+      </header>
+
+      <section className="flex-1 flex flex-col items-center justify-center">
+        {focus ? (
+          <>
+            <p className="text-4xl text-gray-400">
+              {focus.prayer_name} {showIqama ? 'iqama' : 'adhan'}
+            </p>
+            <p className="text-[12rem] leading-none font-bold tabular-nums text-primary-400">
+              {formatCountdownDisplay((showIqama && focus.iqama_display) || focus.adhan_display)}
+            </p>
+          </>
+        ) : (
+          <p className="text-4xl text-gray-500">No more prayers today</p>
+        )}
+      </section>
+
+      {frame.board.length > 0 && (
+        <section className="grid grid-cols-6 gap-4 text-center">
+          {frame.board.map((c) => (
+            <div
+              key={c.prayer_name}
+              className={`glass-card p-4 ${c.prayer_name === focus?.prayer_name ? 'ring-4 ring-primary-500' : ''} ${c.not_held ? 'opacity-40' : ''}`}
+            >
+              <p className="text-2xl text-gray-400">{c.prayer_name}</p>
+              <p className="text-4xl font-semibold tabular-nums">{formatClock(c.adhan_time)}</p>
+              <p className="text-3xl tabular-nums text-primary-300">
+                {c.iqama_offset_minutes != null
+                  ? `+${c.iqama_offset_minutes}`
+                  : c.iqama_time
+                    ? formatClock(c.iqama_time)
+                    : '—'}
+              </p>
+            </div>
+          ))}
+        </section>
+      )}
+
+      <footer className="mt-8 flex justify-between items-center text-2xl">
+        <p className="text-gray-200">{frame.announcement ?? ''}</p>
+        {!frame.online && <p className="text-yellow-400">Offline · showing saved times</p>}
+      </footer>
+    </div>
+  );
+};
@@ -133,6 +133,7 @@ export const MosqueSelector = () => {
           city: undefined,
           country: undefined,
           is_favorite: false,
+          facilities: {},
         };
 
         setCurrentMosque(mosque);
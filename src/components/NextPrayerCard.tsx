@@ -198,7 +198,13 @@ export const NextPrayerCard = () => {
               <>
                 <div className="flex items-center justify-between mb-2">
                   <span className="text-gray-300">
-                    Rak'ah {rakahEstimate.current_rakah} / {rakahEstimate.total_rakah}
+                    Rak'ah{' '}
+                    {rakahEstimate.min_rakah !== undefined &&
+                    rakahEstimate.max_rakah !== undefined &&
+                    rakahEstimate.min_rakah !== rakahEstimate.max_rakah
+                      ? `${rakahEstimate.min_rakah}–${rakahEstimate.max_rakah}`
+                      : rakahEstimate.current_rakah}{' '}
+                    / {rakahEstimate.total_rakah}
                   </span>
                   <span className="text-emerald-400">
                     {Math.round(rakahEstimate.progress * 100)}%
@@ -1,10 +1,26 @@
 import { useState } from 'react';
 import { useStore } from '../hooks/useStore';
-import { formatTime, getPrayerIcon, getPrayerColor } from '../services/time';
+import { formatIqama, formatTime, getPrayerIcon, getPrayerColor } from '../services/time';
 import { CompactTimer, IqamaBadge } from './LiveTimer';
-import { Clock, Sun, Sunrise, Calendar, ChevronLeft, ChevronRight, RotateCcw, ChevronDown, ChevronUp, Bell, BellOff, X, Info } from 'lucide-react';
+import { Clock, Sun, Sunrise, Calendar, ChevronLeft, ChevronRight, RotateCcw, ChevronDown, ChevronUp, Bell, BellOff, X, Info, AlertTriangle } from 'lucide-react';
 import * as tauri from '../services/tauri';
-import type { Prayer } from '../types';
+import type { Prayer, ScheduleWarning } from '../types';
+
+// How the day's data may differ from the mosque's board
+const describeWarning = ({ kind, prayer }: ScheduleWarning): string => {
+  switch (kind) {
+    case 'default_iqama':
+      return `Iqama missing for ${prayer}, using your default offset`;
+    case 'missing_iqama':
+      return `The mosque publishes no iqama for ${prayer}`;
+    case 'iqama_before_adhan':
+      return `${prayer} iqama is before its adhan, check with the mosque`;
+    case 'out_of_order':
+      return `${prayer} time looks wrong: it is not after the previous prayer`;
+    case 'calculated':
+      return 'Times are calculated for the location, not published by the mosque';
+  }
+};
 
 // Simple calendar component
 const MiniCalendar = ({ 
@@ -143,6 +159,9 @@ const PrayerDetailsModal = ({
                 <span className="text-sm">Iqama</span>
               </div>
               <p className="text-2xl font-semibold">{formatTime(prayer.iqama)}</p>
+              {prayer.iqama_offset_minutes != null && (
+                <p className="text-xs text-gray-500">{formatIqama(prayer)} after adhan</p>
+              )}
             </div>
           )}
           
@@ -212,7 +231,7 @@ export const PrayerList = () => {
   // Create list of prayers with their data
   const allPrayers: { name: string; prayer: Prayer; isSunrise?: boolean }[] = [
     { name: 'Fajr', prayer: currentPrayerTimes.fajr },
-    { name: 'Sunrise', prayer: { name: 'Sunrise', adhan: currentPrayerTimes.fajr.adhan }, isSunrise: true },
+    { name: 'Sunrise', prayer: currentPrayerTimes.fajr, isSunrise: true },
     { name: 'Dhuhr', prayer: currentPrayerTimes.dhuhr },
     { name: 'Asr', prayer: currentPrayerTimes.asr },
     { name: 'Maghrib', prayer: currentPrayerTimes.maghrib },
@@ -397,6 +416,20 @@ export const PrayerList = () => {
         </div>
       )}
 
+      {/* Data quality notes from the provider's schedule */}
+      {currentPrayerTimes.warnings?.length > 0 && (
+        <div className="mb-4 p-3 bg-amber-900/10 border border-amber-500/20 rounded-lg flex items-start gap-3">
+          <AlertTriangle className="w-5 h-5 text-amber-400 flex-shrink-0 mt-0.5" />
+          <ul className="text-sm text-gray-300 space-y-1">
+            {currentPrayerTimes.warnings.map((warning) => (
+              <li key={`${warning.kind}-${warning.prayer ?? 'day'}`} title={warning.message}>
+                {describeWarning(warning)}
+              </li>
+            ))}
+          </ul>
+        </div>
+      )}
+
       {isLoadingDate && (
         <div className="flex items-center justify-center py-8">
           <div className="w-6 h-6 border-2 border-primary-500 border-t-transparent rounded-full animate-spin" />
@@ -413,6 +446,7 @@ export const PrayerList = () => {
             
             const hasPassed = isToday && countdown && countdown.time_until_adhan_secs <= 0 && !countdown.is_active;
             const isActive = isToday && countdown?.is_active;
+            const notHeld = Boolean(countdown?.not_held);
             
             // Get iqama countdown for this prayer
             const iqamaSeconds = countdown?.time_until_iqama_secs || 0;
@@ -423,7 +457,7 @@ export const PrayerList = () => {
                 onClick={() => !isSunrise && handlePrayerClick(prayer)}
                 className={`prayer-card flex items-center justify-between cursor-pointer transition-all ${
                   isNext ? 'active' : ''
-                } ${hasPassed ? 'opacity-50' : ''} ${!isSunrise ? 'hover:bg-gray-800/70' : ''}`}
+                } ${hasPassed || notHeld ? 'opacity-50' : ''} ${!isSunrise ? 'hover:bg-gray-800/70' : ''}`}
               >
                 <div className="flex items-center gap-4">
                   <div
@@ -451,7 +485,7 @@ export const PrayerList = () => {
                       {!isSunrise && prayer.iqama && (
                         <span className="flex items-center gap-1">
                           <span className="text-gray-600">•</span>
-                          <span>Iqama: {formatTime(prayer.iqama)}</span>
+                          <span>Iqama: {formatIqama(prayer)}</span>
                           {isToday && iqamaSeconds > 0 && iqamaSeconds < 3600 && (
                             <IqamaBadge seconds={iqamaSeconds} />
                           )}
@@ -54,7 +54,7 @@ export const SettingsModal = ({ isOpen, onClose }: SettingsModalProps) => {
     } catch (err) {
       setTestResult({
         success: false,
-        message: err instanceof Error ? err.message : 'Test failed',
+        message: tauri.errorMessage(err, 'Test failed'),
       });
     } finally {
       setTesting(false);
@@ -1,4 +1,4 @@
-import { useState } from 'react';
+import { useEffect, useState } from 'react';
 import { useStore } from '../hooks/useStore';
 import * as tauri from '../services/tauri';
 import { formatCountdown, formatTime, getPrayerIcon } from '../services/time';
@@ -11,11 +11,26 @@ export const TravelTimeCard = () => {
   const [prediction, setPrediction] = useState<TravelPrediction | null>(null);
   const [loading, setLoading] = useState(false);
 
+  // Start from the travel time saved for this mosque, if any
+  useEffect(() => {
+    if (!currentMosque) return;
+    tauri
+      .getTravelTimes(currentMosque.id)
+      .then((times) => {
+        const driving = times.find((t) => t.mode === 'driving');
+        if (driving) setTravelMinutes(Math.max(1, Math.round(driving.travel_time_seconds / 60)));
+      })
+      .catch((err) => console.error('Failed to load travel times:', err));
+  }, [currentMosque?.id]);
+
   const calculatePrediction = async () => {
     if (!currentMosque || !nextPrayer) return;
 
     setLoading(true);
     try {
+      if (currentMosque.is_favorite) {
+        await tauri.setTravelTime(currentMosque.id, 'driving', travelMinutes * 60);
+      }
       const result = await tauri.calculateTravelPrediction(
         currentMosque.id,
         nextPrayer.prayer.name,
@@ -1,10 +1,17 @@
 import { renderHook, act, waitFor } from '@testing-library/react';
 import { usePrayerTimes, useRakahEstimate } from '../usePrayerTimes';
 import { useStore } from '../useStore';
+import * as tauri from '../../services/tauri';
 import { vi, describe, it, expect, beforeEach, afterEach } from 'vitest';
 
-// Mock the store
+// Mock the store and the backend
 vi.mock('../useStore');
+vi.mock('../../services/tauri', () => ({
+  getNextPrayer: vi.fn(),
+  getAllCountdowns: vi.fn(),
+  onClockResync: vi.fn(async () => () => {}),
+  errorMessage: (_err: unknown, fallback: string) => fallback,
+}));
 
 describe('Prayer Status Integration Tests', () => {
   const mockSetNextPrayer = vi.fn();
@@ -29,154 +36,81 @@ describe('Prayer Status Integration Tests', () => {
   });
 
   describe('Prayer Countdown Updates', () => {
-    it('should update countdown every second', async () => {
-      const mockPrayerTimes = {
-        fajr: {
-          name: 'Fajr',
-          adhan: new Date(Date.now() + 3600000).toISOString(), // 1 hour from now
-          iqama: new Date(Date.now() + 3750000).toISOString(), // 1h 2.5m from now
-        },
-        dhuhr: {
-          name: 'Dhuhr',
-          adhan: new Date(Date.now() + 7200000).toISOString(),
-          iqama: new Date(Date.now() + 7350000).toISOString(),
-        },
-        asr: {
-          name: 'Asr',
-          adhan: new Date(Date.now() + 10800000).toISOString(),
-          iqama: new Date(Date.now() + 10950000).toISOString(),
-        },
-        maghrib: {
-          name: 'Maghrib',
-          adhan: new Date(Date.now() + 14400000).toISOString(),
-          iqama: new Date(Date.now() + 14430000).toISOString(),
-        },
-        isha: {
-          name: 'Isha',
-          adhan: new Date(Date.now() + 18000000).toISOString(),
-          iqama: new Date(Date.now() + 18150000).toISOString(),
-        },
-      };
-
+    const display = (secs: number, refresh_in_secs: number) => ({
+      secs,
+      show_seconds: false,
+      is_now: false,
+      refresh_in_secs,
+    });
+    const nextPrayer = {
+      prayer: { name: 'Asr', adhan: new Date(Date.now() + 3600000).toISOString() },
+      time_until_adhan_secs: 3600,
+      is_tomorrow: false,
+      adhan_display: display(3600, 40),
+    };
+    const countdowns = [
+      {
+        prayer_name: 'Asr',
+        adhan_time: nextPrayer.prayer.adhan,
+        time_until_adhan_secs: 3600,
+        is_active: false,
+        not_held: false,
+        adhan_display: display(3600, 40),
+        iqama_display: display(3780, 15),
+      },
+    ];
+
+    beforeEach(() => {
+      vi.mocked(tauri.getNextPrayer).mockResolvedValue(nextPrayer as any);
+      vi.mocked(tauri.getAllCountdowns).mockResolvedValue(countdowns as any);
       (useStore as ReturnType<typeof vi.fn>).mockReturnValue({
         currentMosque: { id: 'test', name: 'Test Mosque' },
-        currentPrayerTimes: mockPrayerTimes,
+        currentPrayerTimes: {},
         setNextPrayer: mockSetNextPrayer,
         setCountdowns: mockSetCountdowns,
         setError: mockSetError,
       });
+    });
 
+    it('should show what the engine computed', async () => {
       renderHook(() => usePrayerTimes());
 
-      // Initial call
-      expect(mockSetNextPrayer).toHaveBeenCalled();
-      expect(mockSetCountdowns).toHaveBeenCalled();
-
-      // Advance 1 second
-      act(() => {
-        vi.advanceTimersByTime(1000);
-      });
-
-      await waitFor(() => {
-        expect(mockSetCountdowns).toHaveBeenCalledTimes(2);
-      });
+      await act(async () => {});
+      expect(tauri.getNextPrayer).toHaveBeenCalledWith('test');
+      expect(mockSetNextPrayer).toHaveBeenCalledWith(nextPrayer);
+      expect(mockSetCountdowns).toHaveBeenCalledWith(countdowns);
     });
 
-    it('should detect active prayer (between adhan and iqama)', async () => {
-      const now = Date.now();
-      const mockPrayerTimes = {
-        fajr: {
-          name: 'Fajr',
-          adhan: new Date(now - 300000).toISOString(), // 5 min ago (passed)
-          iqama: new Date(now - 120000).toISOString(), // 2 min ago (passed)
-        },
-        dhuhr: {
-          name: 'Dhuhr',
-          adhan: new Date(now - 300000).toISOString(), // 5 min ago (active)
-          iqama: new Date(now + 600000).toISOString(), // 10 min from now
-        },
-        asr: {
-          name: 'Asr',
-          adhan: new Date(now + 3600000).toISOString(), // 1 hour from now
-          iqama: new Date(now + 3750000).toISOString(),
-        },
-        maghrib: {
-          name: 'Maghrib',
-          adhan: new Date(now + 7200000).toISOString(),
-          iqama: new Date(now + 7230000).toISOString(),
-        },
-        isha: {
-          name: 'Isha',
-          adhan: new Date(now + 10800000).toISOString(),
-          iqama: new Date(now + 10950000).toISOString(),
-        },
-      };
+    it('should refetch when the first countdown changes, not every second', async () => {
+      renderHook(() => usePrayerTimes());
+      await act(async () => {});
+      expect(mockSetCountdowns).toHaveBeenCalledTimes(1);
 
-      (useStore as ReturnType<typeof vi.fn>).mockReturnValue({
-        currentMosque: { id: 'test', name: 'Test Mosque' },
-        currentPrayerTimes: mockPrayerTimes,
-        setNextPrayer: mockSetNextPrayer,
-        setCountdowns: mockSetCountdowns,
-        setError: mockSetError,
+      await act(async () => {
+        await vi.advanceTimersByTimeAsync(14000);
       });
+      expect(mockSetCountdowns).toHaveBeenCalledTimes(1);
 
-      renderHook(() => usePrayerTimes());
-
-      await waitFor(() => {
-        const countdownsCall = mockSetCountdowns.mock.calls[0][0];
-        const dhuhrCountdown = countdownsCall.find((c: any) => c.prayer_name === 'Dhuhr');
-        
-        expect(dhuhrCountdown.is_active).toBe(true);
-        expect(dhuhrCountdown.time_until_adhan_secs).toBe(0);
+      await act(async () => {
+        await vi.advanceTimersByTimeAsync(1000);
       });
+      expect(mockSetCountdowns).toHaveBeenCalledTimes(2);
     });
 
-    it('should handle midnight crossover', async () => {
-      const now = Date.now();
-      const mockPrayerTimes = {
-        fajr: {
-          name: 'Fajr',
-          adhan: new Date(now + 3600000).toISOString(), // 1 hour from now (tomorrow's Fajr)
-          iqama: new Date(now + 3750000).toISOString(),
-        },
-        dhuhr: {
-          name: 'Dhuhr',
-          adhan: new Date(now - 18000000).toISOString(), // 5 hours ago (passed)
-          iqama: new Date(now - 17850000).toISOString(),
-        },
-        asr: {
-          name: 'Asr',
-          adhan: new Date(now - 14400000).toISOString(),
-          iqama: new Date(now - 14250000).toISOString(),
-        },
-        maghrib: {
-          name: 'Maghrib',
-          adhan: new Date(now - 10800000).toISOString(),
-          iqama: new Date(now - 10770000).toISOString(),
-        },
-        isha: {
-          name: 'Isha',
-          adhan: new Date(now - 7200000).toISOString(), // 2 hours ago (passed)
-          iqama: new Date(now - 7050000).toISOString(),
-        },
-      };
-
-      (useStore as ReturnType<typeof vi.fn>).mockReturnValue({
-        currentMosque: { id: 'test', name: 'Test Mosque' },
-        currentPrayerTimes: mockPrayerTimes,
-        setNextPrayer: mockSetNextPrayer,
-        setCountdowns: mockSetCountdowns,
-        setError: mockSetError,
+    it('should refetch when the backend clock is re-anchored', async () => {
+      let resync: (() => void) | undefined;
+      vi.mocked(tauri.onClockResync).mockImplementation(async (handler) => {
+        resync = () => handler({} as any);
+        return () => {};
       });
 
       renderHook(() => usePrayerTimes());
-
-      await waitFor(() => {
-        const nextPrayerCall = mockSetNextPrayer.mock.calls[0][0];
-        
-        expect(nextPrayerCall.prayer.name).toBe('Fajr');
-        expect(nextPrayerCall.is_tomorrow).toBe(true);
+      await act(async () => {});
+      await act(async () => {
+        resync?.();
       });
+
+      expect(mockSetCountdowns).toHaveBeenCalledTimes(2);
     });
   });
 
@@ -1,6 +1,10 @@
-import { useEffect, useState, useCallback } from 'react';
+import { useEffect, useCallback } from 'react';
 import { useStore } from './useStore';
 import * as tauri from '../services/tauri';
+import { nextRefreshSecs } from '../services/time';
+
+// Seconds before trying again when the backend couldn't give countdowns
+const RETRY_SECS = 30;
 
 export const usePrayerTimes = () => {
   const { 
@@ -10,104 +14,57 @@ export const usePrayerTimes = () => {
     setCountdowns, 
     setError 
   } = useStore();
-  const [refreshInterval, setRefreshInterval] = useState<ReturnType<typeof setInterval> | null>(null);
 
-  const refreshPrayerData = useCallback(async () => {
-    if (!currentMosque || !currentPrayerTimes) return;
+  // The engine computes the countdowns and how they read; returns the seconds until they change
+  const refreshPrayerData = useCallback(async (): Promise<number | null> => {
+    if (!currentMosque || !currentPrayerTimes) return null;
 
     try {
-      // Calculate next prayer from the current prayer times
-      const prayers = [
-        currentPrayerTimes.fajr,
-        currentPrayerTimes.dhuhr,
-        currentPrayerTimes.asr,
-        currentPrayerTimes.maghrib,
-        currentPrayerTimes.isha,
-      ];
-
-      const now = new Date();
-      
-      // Find next prayer
-      let nextPrayer = null;
-      let minDiff = Infinity;
-      
-      for (const prayer of prayers) {
-        const prayerTime = new Date(prayer.adhan);
-        const diff = prayerTime.getTime() - now.getTime();
-        
-        if (diff > 0 && diff < minDiff) {
-          minDiff = diff;
-          nextPrayer = prayer;
-        }
-      }
-
-      // If no future prayer today, use tomorrow's Fajr
-      if (!nextPrayer) {
-        const tomorrowFajr = new Date(currentPrayerTimes.fajr.adhan);
-        tomorrowFajr.setDate(tomorrowFajr.getDate() + 1);
-        nextPrayer = {
-          ...currentPrayerTimes.fajr,
-          adhan: tomorrowFajr.toISOString(),
-        };
-      }
-
-      // Calculate countdowns for all prayers
-      const countdowns = prayers.map((prayer) => {
-        const prayerTime = new Date(prayer.adhan);
-        const iqamaTime = prayer.iqama ? new Date(prayer.iqama) : null;
-        const diff = prayerTime.getTime() - now.getTime();
-        const iqamaDiff = iqamaTime ? iqamaTime.getTime() - now.getTime() : null;
-        
-        return {
-          prayer_name: prayer.name,
-          adhan_time: prayer.adhan,
-          iqama_time: prayer.iqama,
-          time_until_adhan_secs: Math.max(0, Math.floor(diff / 1000)),
-          time_until_iqama_secs: iqamaDiff ? Math.max(0, Math.floor(iqamaDiff / 1000)) : undefined,
-          is_active: Boolean(diff <= 0 && iqamaDiff && iqamaDiff > 0),
-        };
-      });
-
-      // Calculate time until next prayer
-      const nextPrayerTime = new Date(nextPrayer.adhan);
-      const timeUntilAdhan = Math.floor((nextPrayerTime.getTime() - now.getTime()) / 1000);
-      
-      const nextIqamaTime = nextPrayer.iqama ? new Date(nextPrayer.iqama) : null;
-      const timeUntilIqama = nextIqamaTime 
-        ? Math.floor((nextIqamaTime.getTime() - now.getTime()) / 1000)
-        : undefined;
-
-      setNextPrayer({
-        prayer: nextPrayer,
-        time_until_adhan_secs: timeUntilAdhan,
-        time_until_iqama_secs: timeUntilIqama,
-        is_tomorrow: nextPrayer.name === currentPrayerTimes.fajr.name && timeUntilAdhan > 86400 / 2,
-      });
+      const [nextPrayer, countdowns] = await Promise.all([
+        tauri.getNextPrayer(currentMosque.id),
+        tauri.getAllCountdowns(currentMosque.id),
+      ]);
 
+      setNextPrayer(nextPrayer);
       setCountdowns(countdowns);
+
+      return nextRefreshSecs([
+        nextPrayer.adhan_display,
+        nextPrayer.iqama_display,
+        ...countdowns.flatMap((c) => [c.adhan_display, c.iqama_display]),
+      ]);
     } catch (err) {
       console.error('Failed to refresh prayer data:', err);
-      setError(err instanceof Error ? err.message : 'Failed to refresh prayer data');
+      setError(tauri.errorMessage(err, 'Failed to refresh prayer data'));
+      return null;
     }
   }, [currentMosque, currentPrayerTimes, setNextPrayer, setCountdowns, setError]);
 
   useEffect(() => {
-    if (!currentMosque || !currentPrayerTimes) {
-      if (refreshInterval) {
-        clearInterval(refreshInterval);
-        setRefreshInterval(null);
-      }
-      return;
-    }
+    if (!currentMosque || !currentPrayerTimes) return;
 
-    refreshPrayerData();
+    let timer: ReturnType<typeof setTimeout> | undefined;
+    let cancelled = false;
+
+    // Sleep until the displayed value changes instead of polling every second
+    const refresh = async () => {
+      const secs = await refreshPrayerData();
+      if (cancelled) return;
+      clearTimeout(timer);
+      timer = setTimeout(refresh, (secs ?? RETRY_SECS) * 1000);
+    };
+    refresh();
 
-    // Refresh every second for live countdown
-    const interval = setInterval(refreshPrayerData, 1000);
-    setRefreshInterval(interval);
+    // The backend re-anchored its clock, so the countdowns on screen are off
+    const unlisten = tauri.onClockResync(() => {
+      clearTimeout(timer);
+      refresh();
+    });
 
     return () => {
-      clearInterval(interval);
+      cancelled = true;
+      clearTimeout(timer);
+      unlisten.then((stop) => stop()).catch(() => {});
     };
   }, [currentMosque, currentPrayerTimes, refreshPrayerData]);
 
@@ -242,7 +199,7 @@ export const useFavoriteMosques = () => {
       const mosques = await tauri.getFavoriteMosques();
       setFavoriteMosques(mosques);
     } catch (err) {
-      setError(err instanceof Error ? err.message : 'Failed to load favorites');
+      setError(tauri.errorMessage(err, 'Failed to load favorites'));
     } finally {
       setIsLoading(false);
     }
@@ -251,12 +208,13 @@ export const useFavoriteMosques = () => {
   const addFavorite = async (mosque: { id: string; name: string; address?: string }) => {
     try {
       await tauri.addFavoriteMosque({
+        facilities: {},
         ...mosque,
         is_favorite: true,
       });
       await loadFavorites();
     } catch (err) {
-      setError(err instanceof Error ? err.message : 'Failed to add favorite');
+      setError(tauri.errorMessage(err, 'Failed to add favorite'));
     }
   };
 
@@ -265,7 +223,7 @@ export const useFavoriteMosques = () => {
       await tauri.removeFavoriteMosque(mosqueId);
       await loadFavorites();
     } catch (err) {
-      setError(err instanceof Error ? err.message : 'Failed to remove favorite');
+      setError(tauri.errorMessage(err, 'Failed to remove favorite'));
     }
   };
 
@@ -1,6 +1,9 @@
 import {
   formatCountdown,
+  formatCountdownDisplay,
+  nextRefreshSecs,
   formatTime,
+  formatIqama,
   formatTimeUntil,
   formatDuration,
   getTimeRemaining,
@@ -30,6 +33,32 @@ describe('Time Service', () => {
     });
   });
 
+  describe('formatCountdownDisplay', () => {
+    const display = (secs: number, hints: { show_seconds?: boolean; is_now?: boolean } = {}) => ({
+      secs,
+      show_seconds: hints.show_seconds ?? false,
+      is_now: hints.is_now ?? false,
+      refresh_in_secs: 1,
+    });
+
+    it('should follow the display hints', () => {
+      expect(formatCountdownDisplay(display(46 * 60))).toBe('46m');
+      expect(formatCountdownDisplay(display(2 * 3600 + 5 * 60))).toBe('2h 05m');
+      expect(formatCountdownDisplay(display(125, { show_seconds: true }))).toBe('2m 05s');
+      expect(formatCountdownDisplay(display(0, { is_now: true }))).toBe('Now');
+    });
+  });
+
+  describe('nextRefreshSecs', () => {
+    const display = (refresh_in_secs: number) => ({ secs: 600, show_seconds: false, is_now: false, refresh_in_secs });
+
+    it('should wait for the first countdown to change', () => {
+      expect(nextRefreshSecs([display(20), undefined, display(45)])).toBe(20);
+      expect(nextRefreshSecs([display(0)])).toBe(1);
+      expect(nextRefreshSecs([])).toBe(60);
+    });
+  });
+
   describe('formatTime', () => {
     it('should format time correctly', () => {
       const dateStr = new Date('2026-02-05T05:30:00').toISOString();
@@ -37,6 +66,14 @@ describe('Time Service', () => {
     });
   });
 
+  describe('formatIqama', () => {
+    it('should keep the offset a mosque publishes', () => {
+      const iqama = new Date('2026-02-05T20:15:00').toISOString();
+      expect(formatIqama({ iqama, iqama_offset_minutes: 15 })).toBe('+15 min');
+      expect(formatIqama({ iqama })).toMatch(/8:15\s*PM/);
+    });
+  });
+
   describe('formatTimeUntil', () => {
     it('should return "Now" for zero or negative', () => {
       expect(formatTimeUntil(0)).toBe('Now');
@@ -47,6 +47,12 @@ export const searchMosques = async (countryCode: string, query?: string): Promis
             latitude: m.lat,
             longitude: m.lng,
             is_favorite: false,
+            facilities: {
+                womens_area: m.womenSpace ?? undefined,
+                wheelchair_access: m.handicapAccessibility ?? undefined,
+                wudu: m.ablutions ?? undefined,
+                parking: m.parking ?? undefined,
+            },
         }));
     } catch (error) {
         console.error('Error searching mosques:', error);
@@ -1,20 +1,126 @@
 import { invoke } from '@tauri-apps/api/tauri';
+import { listen, type UnlistenFn } from '@tauri-apps/api/event';
 import type {
+  AccountImport,
+  CatalogImportProgress,
+  CatalogImportSummary,
+  GeoLocation,
   Mosque,
   NextPrayerResult,
+  PrayerName,
   PrayerTimes,
   PrayerCountdown,
+  DaySummary,
   RakahEstimate,
+  TravelMode,
   TravelPrediction,
+  TravelSessionUpdate,
+  TravelTime,
   ProviderInfo,
   ProviderTestResult,
   ProviderConfig,
   Settings,
+  CommandError,
+  HijriDay,
+  IslamicEvent,
+  FastingStatus,
+  MosqueEvent,
+  MosqueComparison,
+  MosqueContexts,
+  ContextualNextPrayer,
+  MosqueFacilities,
+  MosqueSearchParams,
+  MosqueSearchResult,
+  MosqueStartLag,
+  PrayerAvailability,
+  PrayerLogEntry,
+  PrayerLogQuery,
+  PrayerLogTag,
+  FamilyAttendance,
+  PrayerGoal,
+  GoalProgress,
+  CrowdLevel,
+  CrowdSummary,
+  AdhkarConfig,
+  AdhkarEvent,
+  AdhkarStatus,
+  KioskConfig,
+  KioskFrame,
+  KioskStatus,
+  HttpServerConfig,
+  HttpServerStatus,
+  ApiScope,
+  ApiToken,
+  NewApiToken,
+  CalendarSyncConfig,
+  CalendarSyncStatus,
+  FajrAlarmConfig,
+  FajrAlarmRing,
+  FajrAlarmStatus,
+  HotkeyAction,
+  HotkeyBindings,
+  Language,
+  NotificationProfile,
+  MosqueNotificationOverride,
+  NotificationProfiles,
+  NotificationTemplates,
+  SpeechConfig,
+  AdhanSound,
+  AdhanSoundConfig,
+  AudioOutputDevice,
+  AdminStatus,
+  ScheduleEdit,
+  ScheduleExportFormat,
+  ScheduleChange,
+  SelectedMosque,
+  DetectedRegion,
+  OnboardingResult,
+  OnboardingSuggestions,
+  UpcomingScheduleChange,
+  IqamaDefaults,
+  IqamaDelay,
+  IqamaReport,
+  JumuahTravelPrediction,
+  ClockResync,
+  RefreshPolicy,
+  StartupSettings,
+  StorageStatus,
+  LegacyImportSummary,
+  UpstreamIssue,
+  RawPayloadInfo,
+  ArchivedPayload,
+  AppMetrics,
+  UpdateInfo,
 } from '../types';
 
+export const isCommandError = (err: unknown): err is CommandError =>
+  typeof err === 'object' && err !== null && 'kind' in err && 'message' in err;
+
+/** Readable message for a rejected command or thrown error */
+export const errorMessage = (err: unknown, fallback: string): string => {
+  if (isCommandError(err)) return err.message;
+  if (err instanceof Error) return err.message;
+  return fallback;
+};
+
 // Mosque commands
-export const searchMosques = async (query: string, country?: string): Promise<{ mosques: Mosque[]; total: number }> => {
-  return invoke('search_mosques', { query, country });
+export const searchMosques = async (
+  query: string,
+  country?: string,
+  facilities?: MosqueFacilities,
+  params?: MosqueSearchParams,
+  location?: GeoLocation
+): Promise<MosqueSearchResult> => {
+  return invoke('search_mosques', { query, country, facilities, params, location });
+};
+
+/** Import a country's full mosque list for offline search; progress arrives through `onCatalogImportProgress` */
+export const downloadCountryCatalog = async (country: string): Promise<CatalogImportSummary> => {
+  return invoke('download_country_catalog', { country });
+};
+
+export const onCatalogImportProgress = (handler: (progress: CatalogImportProgress) => void): Promise<UnlistenFn> => {
+  return listen<CatalogImportProgress>('catalog-import-progress', (e) => handler(e.payload));
 };
 
 export const getFavoriteMosques = async (): Promise<Mosque[]> => {
@@ -29,12 +135,90 @@ export const removeFavoriteMosque = async (mosqueId: string): Promise<void> => {
   return invoke('remove_favorite_mosque', { mosqueId });
 };
 
-export const saveSelectedMosque = async (mosque: Mosque): Promise<void> => {
-  return invoke('save_selected_mosque', { mosque });
+/** Save the travel time to a favorite mosque; `null` forgets it */
+export const setTravelTime = async (
+  mosqueId: string,
+  mode: TravelMode,
+  travelTimeSeconds: number | null
+): Promise<void> => {
+  return invoke('set_travel_time', { mosqueId, mode, travelTimeSeconds });
+};
+
+export const getTravelTimes = async (mosqueId: string): Promise<TravelTime[]> => {
+  return invoke('get_travel_times', { mosqueId });
+};
+
+/** Start heading to a mosque; mode and prayer default to the saved travel mode and the next prayer */
+export const startTravelSession = async (
+  mosqueId: string,
+  mode?: TravelMode,
+  prayerName?: string,
+  location?: GeoLocation
+): Promise<TravelSessionUpdate> => {
+  return invoke('start_travel_session', { mosqueId, mode, prayerName, location });
+};
+
+/** Send the device's position; the session ends as arrived inside the mosque's geofence */
+export const updateTravelSession = async (location: GeoLocation): Promise<TravelSessionUpdate> => {
+  return invoke('update_travel_session', { location });
+};
+
+/** End the session, as an arrival when `arrived` (for mosques without coordinates) */
+export const stopTravelSession = async (arrived?: boolean): Promise<TravelSessionUpdate | null> => {
+  return invoke('stop_travel_session', { arrived });
 };
 
-export const getSelectedMosque = async (): Promise<Mosque | null> => {
-  return invoke('get_selected_mosque');
+export const getTravelSession = async (): Promise<TravelSessionUpdate | null> => {
+  return invoke('get_travel_session');
+};
+
+/** Subscribe to live updates of the travel session, including its final one */
+export const onTravelSessionUpdate = (handler: (update: TravelSessionUpdate) => void): Promise<UnlistenFn> => {
+  return listen<TravelSessionUpdate>('travel-session-update', (e) => handler(e.payload));
+};
+
+/** Select a mosque for a profile (the default one if omitted), optionally saving its travel time */
+export const saveSelectedMosque = async (
+  mosque: Mosque,
+  options?: { profile?: string; travelTimeSecs?: number; travelMode?: TravelMode }
+): Promise<SelectedMosque> => {
+  return invoke('save_selected_mosque', {
+    mosque,
+    profile: options?.profile,
+    travelTimeSecs: options?.travelTimeSecs,
+    travelMode: options?.travelMode,
+  });
+};
+
+export const getSelectedMosque = async (profile?: string): Promise<SelectedMosque | null> => {
+  return invoke('get_selected_mosque', { profile });
+};
+
+/** Country and approximate position from the IP address, or the default country when offline */
+export const detectRegion = async (): Promise<DetectedRegion> => {
+  return invoke('detect_region');
+};
+
+/** Nearest catalog mosques, downloading the country's catalog first if needed */
+export const suggestNearbyMosques = async (options?: {
+  location?: GeoLocation;
+  country?: string;
+  radiusKm?: number;
+  limit?: number;
+}): Promise<OnboardingSuggestions> => {
+  return invoke('suggest_nearby_mosques', {
+    location: options?.location,
+    country: options?.country,
+    radiusKm: options?.radiusKm,
+    limit: options?.limit,
+  });
+};
+
+export const completeOnboarding = async (
+  mosque: Mosque,
+  options?: { country?: string; profile?: string }
+): Promise<OnboardingResult> => {
+  return invoke('complete_onboarding', { mosque, country: options?.country, profile: options?.profile });
 };
 
 export const checkDatabaseHealth = async (): Promise<{
@@ -43,18 +227,61 @@ export const checkDatabaseHealth = async (): Promise<{
   file_size_bytes: number;
   app_dir_exists: boolean;
   app_dir_path: string;
+  portable: boolean;
 }> => {
   return invoke('check_database_health');
 };
 
+/** Whether data is persisted or only kept in memory because the database file couldn't be opened */
+export const getStorageStatus = async (): Promise<StorageStatus> => {
+  return invoke('get_storage_status');
+};
+
+/** Copy the database to a folder (or `.db` path), e.g. a synced folder, and use it from then on */
+export const moveDatabase = async (newPath: string): Promise<StorageStatus> => {
+  return invoke('move_database', { newPath });
+};
+
+/**
+ * Import favorites and history from an older install, a Mawaqit export (JSON) or a CSV file.
+ * Without a path, imports the databases earlier installs left behind; already saved data is kept.
+ */
+export const migrateLegacyData = async (path?: string, profile?: string): Promise<LegacyImportSummary[]> => {
+  return invoke('migrate_legacy_data', { path, profile });
+};
+
+/** Fired while running in memory after each retry of the database file, and once it recovers */
+export const onStorageStatus = (handler: (status: StorageStatus) => void): Promise<UnlistenFn> => {
+  return listen<StorageStatus>('storage-status', (e) => handler(e.payload));
+};
+
 export const getMosqueDetails = async (mosqueId: string): Promise<Mosque | null> => {
   return invoke('get_mosque_details', { mosqueId });
 };
 
+/** Side-by-side adhan and iqama times of two mosques (date "YYYY-MM-DD"); differences are B minus A */
+export const compareMosques = async (
+  mosqueA: string,
+  mosqueB: string,
+  date?: string,
+  location?: GeoLocation
+): Promise<MosqueComparison> => {
+  return invoke('compare_mosques', { mosqueA, mosqueB, date, location });
+};
+
+export const setMosqueFacilities = async (mosqueId: string, facilities: MosqueFacilities): Promise<void> => {
+  return invoke('set_mosque_facilities', { mosqueId, facilities });
+};
+
 export const getPrayerTimesForMosque = async (mosqueId: string, country?: string, date?: string): Promise<PrayerTimes> => {
   return invoke('get_prayer_times_for_mosque', { mosqueId, country, date });
 };
 
+/** Subscribe to schedules refetched in the background after the cached copy went stale */
+export const onPrayerTimesUpdated = (handler: (times: PrayerTimes) => void): Promise<UnlistenFn> => {
+  return listen<PrayerTimes>('prayer-times-updated', (e) => handler(e.payload));
+};
+
 export const fetchPrayerTimesForDate = async (mawaqitUrl: string, date: string): Promise<PrayerTimes> => {
   return invoke('fetch_prayer_times_for_date', { mawaqitUrl, date });
 };
@@ -67,39 +294,501 @@ export const getAvailableProviders = async (): Promise<ProviderInfo[]> => {
   return invoke('get_available_providers');
 };
 
+/** Log into Mawaqit and import the account's favorite and preferred mosques; the password is not stored */
+export const importMawaqitAccount = async (email: string, password: string): Promise<AccountImport> => {
+  return invoke('import_mawaqit_account', { email, password });
+};
+
 // Prayer commands
-export const getNextPrayer = async (mosqueId: string): Promise<NextPrayerResult> => {
-  return invoke('get_next_prayer', { mosqueId });
+/** Omit `mosqueId` to use the selected mosque */
+/** Evaluate engine-backed commands at another moment (RFC3339) and/or UTC offset instead of now */
+export interface EvaluationTime {
+  at?: string;
+  utcOffsetMinutes?: number;
+}
+
+export const getNextPrayer = async (mosqueId?: string, when: EvaluationTime = {}): Promise<NextPrayerResult> => {
+  return invoke('get_next_prayer', { mosqueId, ...when });
+};
+
+/** Next prayer at the home or work mosque; a location near one of them picks it outright */
+export const getNextPrayerContextual = async (
+  location?: GeoLocation,
+  when: EvaluationTime = {},
+): Promise<ContextualNextPrayer> => {
+  return invoke('get_next_prayer_contextual', { location, ...when });
+};
+
+export const getMosqueContexts = async (): Promise<MosqueContexts> => {
+  return invoke('get_mosque_contexts');
+};
+
+/** Save the home and work places and the weekly schedule between them */
+export const setMosqueContexts = async (contexts: MosqueContexts): Promise<MosqueContexts> => {
+  return invoke('set_mosque_contexts', { contexts });
 };
 
 export const getPrayerTimes = async (mosqueId: string): Promise<PrayerTimes> => {
   return invoke('get_prayer_times', { mosqueId });
 };
 
-export const getAllCountdowns = async (mosqueId: string): Promise<PrayerCountdown[]> => {
-  return invoke('get_all_countdowns', { mosqueId });
+export const getAllCountdowns = async (mosqueId?: string, when: EvaluationTime = {}): Promise<PrayerCountdown[]> => {
+  return invoke('get_all_countdowns', { mosqueId, ...when });
+};
+
+export const getDaySummary = async (
+  mosqueId?: string,
+  date?: string,
+  when: EvaluationTime = {}
+): Promise<DaySummary> => {
+  return invoke('get_day_summary', { mosqueId, date, ...when });
 };
 
-export const estimateRakah = async (mosqueId: string, prayerName: string): Promise<RakahEstimate> => {
-  return invoke('estimate_rakah', { mosqueId, prayerName });
+export const estimateRakah = async (
+  mosqueId: string,
+  prayerName: string,
+  when: EvaluationTime = {}
+): Promise<RakahEstimate> => {
+  return invoke('estimate_rakah', { mosqueId, prayerName, ...when });
 };
 
+/** Omit `travelTimeSeconds` to use the travel time saved for the mosque */
 export const calculateTravelPrediction = async (
   mosqueId: string,
   prayerName: string,
-  travelTimeSeconds: number
+  travelTimeSeconds?: number,
+  travelMode?: TravelMode,
+  when: EvaluationTime = {}
 ): Promise<TravelPrediction> => {
-  return invoke('calculate_travel_prediction', { mosqueId, prayerName, travelTimeSeconds });
+  return invoke('calculate_travel_prediction', { mosqueId, prayerName, travelTimeSeconds, travelMode, ...when });
+};
+
+/** This week's Jumuah: when to leave to find a seat before the khutbah */
+export const calculateJumuahPrediction = async (
+  mosqueId: string,
+  options: { travelTimeSeconds?: number; travelMode?: TravelMode; arriveBeforeMinutes?: number } & EvaluationTime = {}
+): Promise<JumuahTravelPrediction> => {
+  return invoke('calculate_jumuah_prediction', { mosqueId, ...options });
+};
+
+/** Localized "iqama at …, leave by …" snippet for pasting into a group chat */
+export const generateShareText = async (mosqueId: string, prayer: string): Promise<string> => {
+  return invoke('generate_share_text', { mosqueId, prayer });
+};
+
+export const getCountdown = async (
+  mosqueId: string,
+  prayerName: string,
+  when: EvaluationTime = {}
+): Promise<number | null> => {
+  return invoke('get_countdown', { mosqueId, prayerName, ...when });
+};
+
+/** Re-fetch a day from the providers; returns iqama times that moved since it was cached */
+export const refreshPrayerTimes = async (mosqueId: string, date?: string): Promise<ScheduleChange[]> => {
+  return invoke('refresh_prayer_times', { mosqueId, date });
+};
+
+export const getScheduleChanges = async (mosqueId?: string, limit?: number): Promise<ScheduleChange[]> => {
+  return invoke('get_schedule_changes', { mosqueId, limit });
+};
+
+/** Iqama jumps in the mosque's published calendar over the next `days` days (default 14) */
+export const getUpcomingScheduleChanges = async (mosqueId: string, days?: number): Promise<UpcomingScheduleChange[]> => {
+  return invoke('get_upcoming_schedule_changes', { mosqueId, days });
+};
+
+/** Subscribe to iqama changes found by the background refresh */
+export const onScheduleChanged = (handler: (changes: ScheduleChange[]) => void): Promise<UnlistenFn> => {
+  return listen<ScheduleChange[]>('schedule-changed', (e) => handler(e.payload));
+};
+
+export const getPrayerAvailability = async (mosqueId: string): Promise<PrayerAvailability> => {
+  return invoke('get_prayer_availability', { mosqueId });
 };
 
-export const getCountdown = async (mosqueId: string, prayerName: string): Promise<number | null> => {
-  return invoke('get_countdown', { mosqueId, prayerName });
+export const setPrayerAvailability = async (mosqueId: string, availability: PrayerAvailability): Promise<void> => {
+  return invoke('set_prayer_availability', { mosqueId, availability });
+};
+
+export const getStartLag = async (mosqueId: string): Promise<MosqueStartLag> => {
+  return invoke('get_start_lag', { mosqueId });
+};
+
+/** How long after iqama the imam starts; overrides what iqama reports have taught */
+export const setStartLag = async (mosqueId: string, startLag: MosqueStartLag): Promise<void> => {
+  return invoke('set_start_lag', { mosqueId, startLag });
+};
+
+export const reportCrowdLevel = async (
+  mosqueId: string,
+  prayerName: string,
+  level: CrowdLevel,
+  options: { date?: string; share?: boolean } = {}
+): Promise<void> => {
+  return invoke('report_crowd_level', { mosqueId, prayerName, level, ...options });
+};
+
+export const getCrowdSummary = async (mosqueId: string): Promise<CrowdSummary[]> => {
+  return invoke('get_crowd_summary', { mosqueId });
+};
+
+/** Report when a prayer actually started; `actualIqama` as ISO 8601, `date` as YYYY-MM-DD (defaults to today) */
+export const reportActualIqama = async (
+  mosqueId: string,
+  prayerName: string,
+  actualIqama: string,
+  date?: string
+): Promise<IqamaReport> => {
+  return invoke('report_actual_iqama', { mosqueId, prayerName, actualIqama, date });
+};
+
+export const getIqamaDelays = async (mosqueId: string): Promise<IqamaDelay[]> => {
+  return invoke('get_iqama_delays', { mosqueId });
 };
 
 export const formatDuration = async (seconds: number): Promise<string> => {
   return invoke('format_duration', { seconds });
 };
 
+/** Format an ISO time with the user's 12h/24h and language settings */
+export const formatPrayerTime = async (time: string): Promise<string> => {
+  return invoke('format_prayer_time', { time });
+};
+
+/** Hijri date, using the mosque's announced adjustment when one is given */
+export const getHijriDate = async (mosqueId?: string, date?: string): Promise<HijriDay> => {
+  return invoke('get_hijri_date', { mosqueId, date });
+};
+
+/** Special days of the Islamic calendar in the next `days` days (60 by default) */
+export const getIslamicEvents = async (mosqueId?: string, days?: number): Promise<IslamicEvent[]> => {
+  return invoke('get_islamic_events', { mosqueId, days });
+};
+
+/** Ramadan fasting state for today: time since suhoor and time to iftar */
+export const getFastingStatus = async (mosqueId: string, when: EvaluationTime = {}): Promise<FastingStatus> => {
+  return invoke('get_fasting_status', { mosqueId, ...when });
+};
+
+export const getPrayerNames = async (language?: string): Promise<Record<PrayerName, string>> => {
+  return invoke('get_prayer_names', { language });
+};
+
+// Adhkar commands
+/** Start the post-prayer adhkar timer for a prayer the user is attending */
+export const markPrayerAttended = async (mosqueId: string, prayerName: string): Promise<AdhkarStatus> => {
+  return invoke('mark_prayer_attended', { mosqueId, prayerName });
+};
+
+export const getAdhkarStatus = async (): Promise<AdhkarStatus> => {
+  return invoke('get_adhkar_status');
+};
+
+export const incrementAdhkar = async (): Promise<AdhkarStatus> => {
+  return invoke('increment_adhkar');
+};
+
+export const stopAdhkar = async (): Promise<AdhkarStatus> => {
+  return invoke('stop_adhkar');
+};
+
+export const getAdhkarConfig = async (): Promise<AdhkarConfig> => {
+  return invoke('get_adhkar_config');
+};
+
+export const saveAdhkarConfig = async (config: AdhkarConfig): Promise<void> => {
+  return invoke('save_adhkar_config', { config });
+};
+
+/** Subscribe to adhkar timer transitions (started, reminder, completed, ...) */
+export const onAdhkarEvent = (handler: (event: AdhkarEvent) => void): Promise<UnlistenFn> => {
+  return listen<AdhkarEvent>('adhkar-event', (e) => handler(e.payload));
+};
+
+// Kiosk commands
+/** Turn the window into a full-screen lobby display; it stays in kiosk mode across restarts */
+export const enterKioskMode = async (config: KioskConfig): Promise<KioskStatus> => {
+  return invoke('enter_kiosk_mode', { config });
+};
+
+export const exitKioskMode = async (): Promise<KioskStatus> => {
+  return invoke('exit_kiosk_mode');
+};
+
+export const getKioskStatus = async (): Promise<KioskStatus> => {
+  return invoke('get_kiosk_status');
+};
+
+/** Subscribe to kiosk mode being switched on or off */
+export const onKioskMode = (handler: (enabled: boolean) => void): Promise<UnlistenFn> => {
+  return listen<boolean>('kiosk-mode', (e) => handler(e.payload));
+};
+
+/** Subscribe to the frames a kiosk screen draws, one per tick */
+export const onKioskFrame = (handler: (frame: KioskFrame) => void): Promise<UnlistenFn> => {
+  return listen<KioskFrame>('kiosk-frame', (e) => handler(e.payload));
+};
+
+// Admin commands
+export const getAdminStatus = async (): Promise<AdminStatus> => {
+  return invoke('get_admin_status');
+};
+
+/** Set the admin PIN; changing an existing PIN requires the current one */
+export const setAdminPin = async (newPin: string, currentPin?: string): Promise<AdminStatus> => {
+  return invoke('set_admin_pin', { newPin, currentPin });
+};
+
+/** Unlock timetable editing for 30 minutes */
+export const unlockAdminMode = async (pin: string): Promise<AdminStatus> => {
+  return invoke('unlock_admin_mode', { pin });
+};
+
+export const lockAdminMode = async (): Promise<AdminStatus> => {
+  return invoke('lock_admin_mode');
+};
+
+/** Apply edits to a mosque's cached timetable; returns the edited days */
+export const adminEditSchedule = async (mosqueId: string, edits: ScheduleEdit[]): Promise<PrayerTimes[]> => {
+  return invoke('admin_edit_schedule', { mosqueId, edits });
+};
+
+/** Copy last year's Ramadan timetable onto this year's, optionally with another Hijri offset */
+export const adminCopyRamadan = async (mosqueId: string, hijriOffsetDays?: number): Promise<PrayerTimes[]> => {
+  return invoke('admin_copy_ramadan', { mosqueId, hijriOffsetDays });
+};
+
+/** Write a timetable to an .ics or .pdf file (dates "YYYY-MM-DD"); resolves to the path written */
+export const exportScheduleFile = async (
+  mosqueId: string,
+  from: string,
+  to: string,
+  format: ScheduleExportFormat,
+  path: string
+): Promise<string> => {
+  return invoke('export_schedule_file', { mosqueId, from, to, format, path });
+};
+
+// Local server commands
+export const getHttpServerStatus = async (): Promise<HttpServerStatus> => {
+  return invoke('get_http_server_status');
+};
+
+/** Save and apply the local HTTP server settings; it serves /calendar/<mosque>.ics feeds */
+export const setHttpServerConfig = async (config: HttpServerConfig): Promise<HttpServerStatus> => {
+  return invoke('set_http_server_config', { config });
+};
+
+export const listApiTokens = async (): Promise<ApiToken[]> => {
+  return invoke('list_api_tokens');
+};
+
+/** Create a token for another device; the returned secret is shown this once */
+export const createApiToken = async (name: string, scopes: ApiScope[]): Promise<NewApiToken> => {
+  return invoke('create_api_token', { name, scopes });
+};
+
+export const revokeApiToken = async (id: string): Promise<boolean> => {
+  return invoke('revoke_api_token', { id });
+};
+
+// Calendar sync commands
+export const getCalendarSyncStatus = async (): Promise<CalendarSyncStatus> => {
+  return invoke('get_calendar_sync_status');
+};
+
+/** Save the calendar integration; when on, next week's iqama times are written to an .ics file right away */
+export const setCalendarSyncConfig = async (config: CalendarSyncConfig): Promise<CalendarSyncStatus> => {
+  return invoke('set_calendar_sync_config', { config });
+};
+
+/** Rewrite the calendar file now; it is also rewritten after each schedule refresh */
+export const syncCalendarNow = async (): Promise<CalendarSyncStatus> => {
+  return invoke('sync_calendar_now');
+};
+
+// Notification template and profile commands
+export const getNotificationTemplates = async (): Promise<NotificationTemplates> => {
+  return invoke('get_notification_templates');
+};
+
+/** Save custom reminder wording using {prayer}, {minutes}, {mosque} and {rakah}; rejects unknown placeholders */
+export const setNotificationTemplates = async (templates: NotificationTemplates): Promise<NotificationTemplates> => {
+  return invoke('set_notification_templates', { templates });
+};
+
+/** Weekly notification profiles (work, weekend, travel) deciding on sound, speech and which prayers notify */
+export const getNotificationProfiles = async (): Promise<NotificationProfiles> => {
+  return invoke('get_notification_profiles');
+};
+
+/** Save notification profiles and their schedule; set `manual` to pick one by hand */
+export const setNotificationProfiles = async (profiles: NotificationProfiles): Promise<NotificationProfiles> => {
+  return invoke('set_notification_profiles', { profiles });
+};
+
+/** The profile reminders follow right now, null for the regular behaviour */
+export const getActiveNotificationProfile = async (): Promise<NotificationProfile | null> => {
+  return invoke('get_active_notification_profile');
+};
+
+/** Mosques whose reminders differ from the notification profile */
+export const getMosqueNotificationOverrides = async (): Promise<MosqueNotificationOverride[]> => {
+  return invoke('get_mosque_notification_overrides');
+};
+
+/** Mute or narrow reminders for a saved mosque; it can't turn on what the active profile silences */
+export const setMosqueNotificationOverride = async (
+  mosqueOverride: MosqueNotificationOverride
+): Promise<MosqueNotificationOverride> => {
+  return invoke('set_mosque_notification_override', { mosqueOverride });
+};
+
+/** Let a mosque's reminders follow the notification profile again; false if it had no override */
+export const clearMosqueNotificationOverride = async (mosqueId: string): Promise<boolean> => {
+  return invoke('clear_mosque_notification_override', { mosqueId });
+};
+
+// Fajr alarm commands
+export const getFajrAlarmConfig = async (): Promise<FajrAlarmConfig> => {
+  return invoke('get_fajr_alarm_config');
+};
+
+/** Save the Fajr wake-up alarm: which days it rings, how early, and how often it repeats */
+export const setFajrAlarmConfig = async (config: FajrAlarmConfig): Promise<FajrAlarmConfig> => {
+  return invoke('set_fajr_alarm_config', { config });
+};
+
+export const getFajrAlarmStatus = async (): Promise<FajrAlarmStatus> => {
+  return invoke('get_fajr_alarm_status');
+};
+
+/** Answer the ringing alarm's question to stop it; a wrong answer rejects and sets a new question */
+export const dismissFajrAlarm = async (answer: number): Promise<FajrAlarmStatus> => {
+  return invoke('dismiss_fajr_alarm', { answer });
+};
+
+/** Subscribe to Fajr alarm rings, each more urgent than the last */
+export const onFajrAlarm = (handler: (ring: FajrAlarmRing) => void): Promise<UnlistenFn> => {
+  return listen<FajrAlarmRing>('fajr-alarm', (e) => handler(e.payload));
+};
+
+// Adhan sound commands
+/** Sounds in the user's sounds folder; `rescan` picks up files added since startup */
+export const listAdhanSounds = async (rescan?: boolean): Promise<AdhanSound[]> => {
+  return invoke('list_adhan_sounds', { rescan });
+};
+
+export const getAdhanSoundConfig = async (): Promise<AdhanSoundConfig> => {
+  return invoke('get_adhan_sound_config');
+};
+
+/** Save the adhan played per prayer, output device and ducking; rejects a sound that isn't installed */
+export const setAdhanSoundConfig = async (config: AdhanSoundConfig): Promise<AdhanSoundConfig> => {
+  return invoke('set_adhan_sound_config', { config });
+};
+
+/** Outputs the adhan can be played on; empty where the platform doesn't let the app choose */
+export const listAudioOutputDevices = async (): Promise<AudioOutputDevice[]> => {
+  return invoke('list_audio_output_devices');
+};
+
+/** Play a sound now on the configured device; resolves to the sound played */
+export const testAdhanSound = async (soundId: string): Promise<AdhanSound> => {
+  return invoke('test_adhan_sound', { soundId });
+};
+
+// Speech commands
+export const getSpeechConfig = async (): Promise<SpeechConfig> => {
+  return invoke('get_speech_config');
+};
+
+/** Save spoken announcements: which prayers are read aloud, in which language, with or without the notification */
+export const setSpeechConfig = async (config: SpeechConfig): Promise<SpeechConfig> => {
+  return invoke('set_speech_config', { config });
+};
+
+/** Speak a sample announcement with the system voice; resolves to the text spoken */
+export const testSpeech = async (language?: Language): Promise<string> => {
+  return invoke('test_speech', { language });
+};
+
+// Hotkey commands
+export const getHotkeys = async (): Promise<HotkeyBindings> => {
+  return invoke('get_hotkeys');
+};
+
+/** Save and register global shortcuts; rejects when one is taken, keeping the previous ones */
+export const setHotkeys = async (bindings: HotkeyBindings): Promise<HotkeyBindings> => {
+  return invoke('set_hotkeys', { bindings });
+};
+
+/** Run a shortcut's action directly, e.g. from a Stream Deck button; resolves to its confirmation */
+export const triggerHotkey = async (action: HotkeyAction): Promise<string> => {
+  return invoke('trigger_hotkey', { action });
+};
+
+/** Repeat the last prayer reminder after `minutes` (5 by default); resolves to when it comes back */
+export const snoozeReminder = async (minutes?: number): Promise<string> => {
+  return invoke('snooze_reminder', { minutes });
+};
+
+/** Subscribe to the countdown the "show mini countdown" shortcut brings up */
+export const onMiniCountdown = (handler: (countdown: PrayerCountdown) => void): Promise<UnlistenFn> => {
+  return listen<PrayerCountdown>('mini-countdown', (e) => handler(e.payload));
+};
+
+// Prayer log commands
+export interface PrayerLogInput {
+  date?: string;
+  mosqueId?: string;
+  tags?: PrayerLogTag[];
+  note?: string;
+  profile?: string;
+}
+
+/** Log a prayer with optional tags and a reflection; logging it again replaces the entry */
+export const logPrayer = async (prayerName: PrayerName, input: PrayerLogInput = {}): Promise<PrayerLogEntry> => {
+  return invoke('log_prayer', { prayerName, ...input });
+};
+
+export const getPrayerLog = async (query?: PrayerLogQuery): Promise<PrayerLogEntry[]> => {
+  return invoke('get_prayer_log', { query });
+};
+
+export const deletePrayerLogEntry = async (prayerName: PrayerName, date: string, profile?: string): Promise<boolean> => {
+  return invoke('delete_prayer_log_entry', { prayerName, date, profile });
+};
+
+/** Attendance of several profiles side by side; the range defaults to the last 7 days */
+export const getFamilyAttendance = async (
+  profiles: string[],
+  options: { from?: string; to?: string; prayerName?: PrayerName } = {}
+): Promise<FamilyAttendance> => {
+  return invoke('get_family_attendance', { profiles, ...options });
+};
+
+// Goal commands
+/** Add a weekly goal; without a prayer every daily prayer counts, without a tag every logged one does */
+export const createGoal = async (
+  targetPerWeek: number,
+  prayerName?: PrayerName,
+  tag?: PrayerLogTag,
+  profile?: string
+): Promise<PrayerGoal> => {
+  return invoke('create_goal', { targetPerWeek, prayerName, tag, profile });
+};
+
+export const deleteGoal = async (id: number): Promise<boolean> => {
+  return invoke('delete_goal', { id });
+};
+
+/** Progress of each goal in the week holding `date` (default today) */
+export const getGoalsStatus = async (profile?: string, date?: string): Promise<GoalProgress[]> => {
+  return invoke('get_goals_status', { profile, date });
+};
+
 // Provider commands
 export const testProviderConnection = async (
   providerId: string,
@@ -108,6 +797,23 @@ export const testProviderConnection = async (
   return invoke('test_provider_connection', { providerId, config });
 };
 
+// Event commands
+export const getMosqueEvents = async (mosqueId: string, refresh?: boolean): Promise<MosqueEvent[]> => {
+  return invoke('get_mosque_events', { mosqueId, refresh });
+};
+
+export const setMosqueEventsFeed = async (mosqueId: string, url: string | null): Promise<void> => {
+  return invoke('set_mosque_events_feed', { mosqueId, url });
+};
+
+export const setEventReminder = async (eventId: string, minutesBefore: number | null): Promise<void> => {
+  return invoke('set_event_reminder', { eventId, minutesBefore });
+};
+
+export const getDueEventReminders = async (withinMinutes?: number): Promise<MosqueEvent[]> => {
+  return invoke('get_due_event_reminders', { withinMinutes });
+};
+
 // Settings commands
 export const getSetting = async (key: string): Promise<string | null> => {
   return invoke('get_setting', { key });
@@ -125,6 +831,29 @@ export const saveProviderConfig = async (config: ProviderConfig): Promise<void>
   return invoke('save_provider_config', { config });
 };
 
+/** Minutes after adhan used as iqama for providers that publish adhan times only */
+export const getIqamaDefaults = async (): Promise<IqamaDefaults> => {
+  return invoke('get_iqama_defaults');
+};
+
+export const saveIqamaDefaults = async (defaults: IqamaDefaults): Promise<void> => {
+  return invoke('save_iqama_defaults', { defaults });
+};
+
+/** Login autostart and whether those launches stay hidden in the tray */
+export const getStartupSettings = async (): Promise<StartupSettings> => {
+  return invoke('get_startup_settings');
+};
+
+export const saveStartupSettings = async (settings: StartupSettings): Promise<void> => {
+  return invoke('save_startup_settings', { settings });
+};
+
+/** Fired with an `iqamah://` link when the app is launched again while already running */
+export const onDeepLink = (handler: (url: string) => void): Promise<UnlistenFn> => {
+  return listen<string>('deep-link', (e) => handler(e.payload));
+};
+
 export const getAllSettings = async (): Promise<Settings> => {
   return invoke('get_all_settings');
 };
@@ -132,3 +861,58 @@ export const getAllSettings = async (): Promise<Settings> => {
 export const saveAllSettings = async (settings: Settings): Promise<void> => {
   return invoke('save_all_settings', { settings });
 };
+
+/** Tell the backend scheduler whether the window is visible and, if known, whether we run on battery */
+export const reportAppState = async (state: { visible?: boolean; onBattery?: boolean }): Promise<void> => {
+  return invoke('report_app_state', state);
+};
+
+export const getRefreshPolicy = async (): Promise<RefreshPolicy> => {
+  return invoke('get_refresh_policy');
+};
+
+/** Providers whose pages stopped parsing, e.g. to tell users "Mawaqit changed their site" */
+export const getUpstreamIssues = async (): Promise<UpstreamIssue[]> => {
+  return invoke('get_upstream_issues');
+};
+
+/** Archived provider responses, of one mosque or all, newest first; see the raw_payload_archive setting */
+export const getRawPayloads = async (mosqueId?: string): Promise<RawPayloadInfo[]> => {
+  return invoke('get_raw_payloads', { mosqueId });
+};
+
+/** One archived response with its body, exactly as the provider sent it */
+export const getRawPayload = async (id: number): Promise<ArchivedPayload> => {
+  return invoke('get_raw_payload', { id });
+};
+
+/** Delete archived responses, of one mosque or all; resolves to how many were removed */
+export const clearRawPayloads = async (mosqueId?: string): Promise<number> => {
+  return invoke('clear_raw_payloads', { mosqueId });
+};
+
+/** Provider latencies, cache hit rates, ticker drift and database sizes, also served at /metrics */
+export const getMetrics = async (): Promise<AppMetrics> => {
+  return invoke('get_metrics');
+};
+
+/** Latest release compared with this version, with its installer and release notes */
+export const checkForUpdates = async (): Promise<UpdateInfo> => {
+  return invoke('check_for_updates');
+};
+
+/** Fired by the background check, once per new release, while automatic checks are on */
+export const onUpdateAvailable = (handler: (info: UpdateInfo) => void): Promise<UnlistenFn> => {
+  return listen<UpdateInfo>('update-available', (e) => handler(e.payload));
+};
+
+/** Fired when the system clock jumped and backend countdowns were re-anchored; refetch countdowns */
+export const onClockResync = (handler: (resync: ClockResync) => void): Promise<UnlistenFn> => {
+  return listen<ClockResync>('clock-resync', (e) => handler(e.payload));
+};
+
+// Debug commands
+/** Override the backend clock (debug builds only); pass null to return to real time */
+export const setSimulatedNow = async (datetime: string | null): Promise<string> => {
+  return invoke('set_simulated_now', { datetime });
+};
@@ -1,4 +1,5 @@
 import { format } from 'date-fns';
+import type { CountdownDisplay } from '../types';
 
 // Format countdown with hours, minutes, seconds
 export const formatCountdown = (seconds: number): string => {
@@ -15,12 +16,34 @@ export const formatCountdown = (seconds: number): string => {
   }
 };
 
+// Render a countdown the way the backend's display hints say: "Now", "9m 05s" close to the time, "1h 05m" or "46m" further out
+export const formatCountdownDisplay = (display: CountdownDisplay): string => {
+  if (display.is_now) return 'Now';
+  if (display.show_seconds) return formatCountdown(display.secs);
+
+  const hours = Math.floor(display.secs / 3600);
+  const minutes = Math.floor((display.secs % 3600) / 60);
+  return hours > 0 ? `${hours}h ${minutes.toString().padStart(2, '0')}m` : `${minutes}m`;
+};
+
+// Seconds until the first of several countdowns reads differently, from one second to a minute
+export const nextRefreshSecs = (displays: Array<CountdownDisplay | undefined>): number => {
+  const waits = displays.flatMap((display) => (display ? [display.refresh_in_secs] : []));
+  return Math.max(1, Math.min(60, ...waits));
+};
+
 // Format time for display (e.g., "5:30 AM")
 export const formatTime = (dateStr: string): string => {
   const date = new Date(dateStr);
   return format(date, 'h:mm a');
 };
 
+// Format an iqama the way the mosque publishes it: "+15 min" after the adhan, or the clock time
+export const formatIqama = (prayer: { iqama?: string; iqama_offset_minutes?: number }): string => {
+  if (prayer.iqama_offset_minutes != null) return `+${prayer.iqama_offset_minutes} min`;
+  return prayer.iqama ? formatTime(prayer.iqama) : '—';
+};
+
 // Format time until (shorter format, no seconds)
 export const formatTimeUntil = (seconds: number): string => {
   if (seconds <= 0) return 'Now';
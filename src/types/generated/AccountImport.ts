@@ -0,0 +1,19 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Mosque } from "./Mosque";
+
+/**
+ * Outcome of importing a Mawaqit account
+ */
+export type AccountImport = { 
+/**
+ * Favorites that were not in the local database yet
+ */
+added: number, 
+/**
+ * Favorites matched to mosques already saved locally
+ */
+matched: number, 
+/**
+ * Account's preferred mosque, now the selected mosque
+ */
+selected_mosque?: Mosque | null, };
@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A sound that can be played when the adhan is called
+ */
+export type AdhanSound = { 
+/**
+ * `user:<file name>`
+ */
+id: string, name: string, path: string, 
+/**
+ * The file was there when the sounds folder was last scanned
+ */
+available: boolean, };
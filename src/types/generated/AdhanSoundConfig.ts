@@ -0,0 +1,27 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { PrayerName } from "./PrayerName";
+
+/**
+ * Adhan played at each prayer's time, e.g. a short tone for Dhuhr at work and the full adhan at home
+ */
+export type AdhanSoundConfig = { enabled: boolean, 
+/**
+ * Sound for prayers without their own; no sound ships with the app, so `none` until the user picks one
+ */
+default_sound: string, 
+/**
+ * Sound id per prayer, `none` to keep one silent
+ */
+per_prayer: { [key in PrayerName]?: string }, 
+/**
+ * Output device id from the device list; the system default when unset
+ */
+output_device: string | null, 
+/**
+ * Turn other applications down while the adhan plays, where the platform allows it
+ */
+duck_others: boolean, 
+/**
+ * Volume other applications are turned down to, in percent of their own
+ */
+duck_volume_percent: number, };
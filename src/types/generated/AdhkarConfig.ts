@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AdhkarStep } from "./AdhkarStep";
+
+/**
+ * Post-prayer adhkar settings, stored in the `adhkar_config` setting
+ */
+export type AdhkarConfig = { enabled: boolean, 
+/**
+ * Length of the adhkar window after the prayer ends
+ */
+duration_minutes: number, 
+/**
+ * Nudge if the counter isn't finished this many minutes into the window
+ */
+reminder_after_minutes?: number, steps: Array<AdhkarStep>, };
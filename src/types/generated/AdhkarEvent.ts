@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { PrayerName } from "./PrayerName";
+
+/**
+ * Transitions emitted by the adhkar timer
+ */
+export type AdhkarEvent = { "type": "started", prayer: PrayerName, } | { "type": "step_completed", prayer: PrayerName, step_index: number, } | { "type": "reminder", prayer: PrayerName, } | { "type": "completed", prayer: PrayerName, } | { "type": "expired", prayer: PrayerName, };
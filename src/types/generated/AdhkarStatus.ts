@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AdhkarPhase } from "./AdhkarPhase";
+import type { AdhkarStep } from "./AdhkarStep";
+import type { PrayerName } from "./PrayerName";
+
+/**
+ * Snapshot of the adhkar timer for the UI
+ */
+export type AdhkarStatus = { phase: AdhkarPhase, prayer?: PrayerName, starts_at?: string, ends_at?: string, remaining_secs?: number, step_index: number, step_count: number, steps: Array<AdhkarStep>, };
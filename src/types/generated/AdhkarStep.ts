@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * One counted dhikr, e.g. 33 x SubhanAllah
+ */
+export type AdhkarStep = { label: string, target: number, };
@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Whether admin mode is set up and unlocked
+ */
+export type AdminStatus = { has_pin: boolean, unlocked: boolean, 
+/**
+ * Seconds until admin mode locks itself
+ */
+expires_in_secs?: number | null, };
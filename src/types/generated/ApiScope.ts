@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * What a local API token lets another device do
+ */
+export type ApiScope = "read-times" | "read-stats" | "control-notifications";
@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ApiScope } from "./ApiScope";
+
+/**
+ * Token a device such as a wall tablet uses to call the local server
+ * The secret itself is only shown once, when the token is created
+ */
+export type ApiToken = { id: string, 
+/**
+ * Label to tell devices apart, e.g. "Hallway tablet"
+ */
+name: string, scopes: Array<ApiScope>, created_at: string, };
@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { CacheMetrics } from "./CacheMetrics";
+import type { ProviderMetrics } from "./ProviderMetrics";
+import type { TableSize } from "./TableSize";
+import type { TickMetrics } from "./TickMetrics";
+
+/**
+ * Everything monitored in kiosk deployments, also served as `/metrics` in the Prometheus text format
+ */
+export type AppMetrics = { collected_at: string, uptime_secs: number, providers: Array<ProviderMetrics>, caches: Array<CacheMetrics>, ticker: TickMetrics, 
+/**
+ * Size of the database, pages in use times page size
+ */
+database_bytes: number, tables: Array<TableSize>, };
@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { RawPayloadInfo } from "./RawPayloadInfo";
+
+/**
+ * An archived response with its body decompressed
+ */
+export type ArchivedPayload = { info: RawPayloadInfo, 
+/**
+ * The response as text; bytes that are not UTF-8 are replaced
+ */
+body: string, };
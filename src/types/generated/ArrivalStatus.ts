@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Where the prayer will be when the user arrives
+ */
+export type ArrivalStatus = "before_start" | "in_progress" | "after_estimated_end" | "iqama_unavailable";
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Juristic convention for the start of Asr
+ */
+export type AsrMethod = "standard" | "hanafi";
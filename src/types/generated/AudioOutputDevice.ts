@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A sound card, headset or other output the adhan can be played on
+ */
+export type AudioOutputDevice = { id: string, name: string, is_default: boolean, };
@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Hits and misses of one cache since the app started
+ */
+export type CacheMetrics = { name: string, hits: number, misses: number, 
+/**
+ * Entries held now, for in-memory caches
+ */
+entries: number, };
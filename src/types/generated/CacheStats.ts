@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Hit/miss counters for the mosque page cache
+ */
+export type CacheStats = { hits: number, misses: number, entries: number, };
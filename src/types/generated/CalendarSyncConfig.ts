@@ -0,0 +1,18 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Keeps next week's iqama times in the user's calendar app through a generated .ics file
+ */
+export type CalendarSyncConfig = { enabled: boolean, 
+/**
+ * Mosque whose times are written; the selected mosque when unset
+ */
+mosque_id?: string, 
+/**
+ * Where the .ics file is written; the app directory when unset
+ */
+path?: string, 
+/**
+ * Open the file in the default calendar app whenever its events change, so it imports them
+ */
+auto_import: boolean, };
@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { CalendarSyncConfig } from "./CalendarSyncConfig";
+
+/**
+ * The calendar integration's settings and its last write
+ */
+export type CalendarSyncStatus = { config: CalendarSyncConfig, 
+/**
+ * The .ics file the events are written to
+ */
+file_path?: string, 
+/**
+ * When the file was last written
+ */
+last_synced?: string, };
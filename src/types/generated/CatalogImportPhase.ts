@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Step of a country catalog import
+ */
+export type CatalogImportPhase = "downloading" | "diffing" | "storing" | "done";
@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { CatalogImportPhase } from "./CatalogImportPhase";
+
+/**
+ * Progress of `download_country_catalog`, emitted as `catalog-import-progress`
+ */
+export type CatalogImportProgress = { country: string, phase: CatalogImportPhase, 
+/**
+ * Mosques handled so far in this phase
+ */
+processed: number, 
+/**
+ * Mosques in the download, once known
+ */
+total?: number, };
@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * What an import changed in the stored catalog of a country
+ */
+export type CatalogImportSummary = { country: string, 
+/**
+ * Mosques in the catalog after the import
+ */
+total: number, added: number, updated: number, removed: number, unchanged: number, imported_at: string, };
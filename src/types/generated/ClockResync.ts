@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Countdowns were re-anchored after the system clock jumped (e.g. an NTP adjustment)
+ */
+export type ClockResync = { 
+/**
+ * Positive when the wall clock jumped forward
+ */
+correction_ms: number, now: string, };
@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { CommandErrorKind } from "./CommandErrorKind";
+
+/**
+ * Error returned by every Tauri command
+ * Serialized as `{ kind, message, retryable, provider, retry_after_secs }`
+ */
+export type CommandError = { kind: CommandErrorKind, message: string, retryable: boolean, provider?: string | null, 
+/**
+ * Seconds the provider asked us to wait before retrying
+ */
+retry_after_secs?: bigint | null, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Category of a command failure, so the UI can react without parsing messages
+ */
+export type CommandErrorKind = "offline" | "not_found" | "stale_cache" | "invalid_input" | "configuration" | "provider" | "database" | "cancelled" | "rate_limited" | "internal";
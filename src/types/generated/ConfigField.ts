@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ConfigFieldType } from "./ConfigFieldType";
+
+/**
+ * Configuration schema field for provider settings
+ */
+export type ConfigField = { key: string, label: string, field_type: ConfigFieldType, required: boolean, description?: string, default_value?: string, options?: Array<string>, };
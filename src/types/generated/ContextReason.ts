@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Why a place's mosque was picked
+ */
+export type ContextReason = "location" | "schedule" | "default";
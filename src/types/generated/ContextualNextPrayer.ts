@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ContextReason } from "./ContextReason";
+import type { NextPrayerResult } from "./NextPrayerResult";
+
+/**
+ * Next prayer at the mosque of the place the user is expected to be
+ */
+export type ContextualNextPrayer = { label: string, profile: string, mosque_id: string, mosque_name: string, reason: ContextReason, next: NextPrayerResult, };
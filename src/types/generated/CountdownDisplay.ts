@@ -0,0 +1,22 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * How a countdown reads, decided once by the engine so the tray, widgets and HTTP API agree
+ */
+export type CountdownDisplay = { 
+/**
+ * Seconds to show: rounded up to whole minutes unless `show_seconds`
+ */
+secs: number, 
+/**
+ * Close enough to count down second by second
+ */
+show_seconds: boolean, 
+/**
+ * Close enough to read as "now"
+ */
+is_now: boolean, 
+/**
+ * Seconds until the displayed value changes, so surfaces can sleep between redraws
+ */
+refresh_in_secs: number, };
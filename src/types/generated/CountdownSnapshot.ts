@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { PrayerCountdown } from "./PrayerCountdown";
+
+/**
+ * The selected mosque's countdowns, pushed as the "countdown" event on every tick
+ */
+export type CountdownSnapshot = { mosque_id: string, mosque_name?: string, now: string, 
+/**
+ * The prayer in progress, else the next one held today
+ */
+focus?: PrayerCountdown, countdowns: Array<PrayerCountdown>, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * How full the mosque was, as reported by the user
+ */
+export type CrowdLevel = "empty" | "light" | "moderate" | "busy" | "packed";
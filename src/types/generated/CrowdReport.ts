@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { CrowdLevel } from "./CrowdLevel";
+import type { PrayerName } from "./PrayerName";
+
+/**
+ * One crowd observation for a prayer at a mosque
+ */
+export type CrowdReport = { mosque_id: string, prayer: PrayerName, date: string, level: CrowdLevel, reported_at: string, };
@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { PrayerName } from "./PrayerName";
+
+/**
+ * Historical crowd level for a prayer
+ */
+export type CrowdSummary = { prayer: PrayerName, 
+/**
+ * Average score (0-4) over recent reports
+ */
+average_level?: number, report_count: number, };
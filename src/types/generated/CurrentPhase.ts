@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { DayPhase } from "./DayPhase";
+import type { PrayerName } from "./PrayerName";
+
+/**
+ * Current phase and the prayer it refers to
+ */
+export type CurrentPhase = { phase: DayPhase, prayer: PrayerName, 
+/**
+ * When the phase ends, if known
+ */
+until?: string, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Where the day stands relative to the prayers
+ */
+export type DayPhase = "before_adhan" | "before_iqama" | "in_congregation" | "prayer_window";
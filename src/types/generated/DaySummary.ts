@@ -0,0 +1,26 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { CurrentPhase } from "./CurrentPhase";
+import type { HijriDay } from "./HijriDay";
+import type { NextPrayerResult } from "./NextPrayerResult";
+import type { Prayer } from "./Prayer";
+import type { PrayerCountdown } from "./PrayerCountdown";
+import type { PrayerTimes } from "./PrayerTimes";
+import type { PrayerWindow } from "./PrayerWindow";
+import type { UpcomingScheduleChange } from "./UpcomingScheduleChange";
+
+/**
+ * Everything the dashboard shows for a day, in one payload
+ */
+export type DaySummary = { mosque_id: string, date: string, prayer_times: PrayerTimes, countdowns: Array<PrayerCountdown>, next_prayer: NextPrayerResult, phase: CurrentPhase, 
+/**
+ * When each prayer can be prayed, following the user's Asr and Isha end settings
+ */
+windows: Array<PrayerWindow>, shuruq?: string, hijri: HijriDay, 
+/**
+ * Jumuah sessions published for the day, first khutbah first
+ */
+jumuah_sessions: Array<Prayer>, 
+/**
+ * Next iqama jump found in the cached schedules
+ */
+next_schedule_change?: UpcomingScheduleChange, };
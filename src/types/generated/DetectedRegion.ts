@@ -0,0 +1,16 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { GeoLocation } from "./GeoLocation";
+import type { RegionSource } from "./RegionSource";
+
+/**
+ * Where the user probably is, to preselect the country and search nearby
+ */
+export type DetectedRegion = { 
+/**
+ * Two-letter country code, upper case
+ */
+country: string, city?: string, 
+/**
+ * City-level accuracy at best; unknown when falling back to settings
+ */
+location?: GeoLocation, timezone?: string, source: RegionSource, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Kind of mosque event, inferred from its title when the source doesn't say
+ */
+export type EventKind = "lecture" | "iftar" | "class" | "announcement";
@@ -0,0 +1,19 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { DayOfWeek } from "./DayOfWeek";
+
+/**
+ * Fajr wake-up alarm that keeps ringing, more urgently each time, until a challenge is solved
+ */
+export type FajrAlarmConfig = { enabled: boolean, 
+/**
+ * Days the alarm rings on
+ */
+days: Array<DayOfWeek>, 
+/**
+ * First ring, in minutes before the Fajr adhan
+ */
+minutes_before: bigint, 
+/**
+ * Minutes between rings until the alarm is dismissed
+ */
+repeat_minutes: bigint, };
@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * One ring of the Fajr alarm; `level` grows with each ring until dismissed
+ */
+export type FajrAlarmRing = { level: number, fajr_time: string, 
+/**
+ * Question to answer to stop the alarm, e.g. "17 + 26"
+ */
+question: string, };
@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { FajrAlarmRing } from "./FajrAlarmRing";
+
+/**
+ * The Fajr alarm's current state
+ */
+export type FajrAlarmStatus = { ringing: boolean, 
+/**
+ * The last ring while the alarm is ringing
+ */
+ring?: FajrAlarmRing, 
+/**
+ * Whether today's alarm was dismissed
+ */
+dismissed: boolean, };
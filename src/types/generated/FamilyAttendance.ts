@@ -0,0 +1,16 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { FamilyPrayerDay } from "./FamilyPrayerDay";
+import type { MemberAttendance } from "./MemberAttendance";
+
+/**
+ * Attendance of several profiles side by side, e.g. to follow the kids' Fajr
+ */
+export type FamilyAttendance = { from: string, to: string, members: Array<MemberAttendance>, 
+/**
+ * Mosque followed by every member, when they share one
+ */
+shared_mosque_id?: string, 
+/**
+ * Prayers at least one member logged, oldest first
+ */
+days: Array<FamilyPrayerDay>, };
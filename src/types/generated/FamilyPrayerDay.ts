@@ -0,0 +1,19 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { PrayerName } from "./PrayerName";
+
+/**
+ * Who prayed one prayer of one day
+ */
+export type FamilyPrayerDay = { date: string, prayer: PrayerName, 
+/**
+ * Profiles that logged the prayer
+ */
+present: Array<string>, 
+/**
+ * Profiles that prayed it in congregation
+ */
+jamaah: Array<string>, 
+/**
+ * Every selected profile logged it
+ */
+everyone: boolean, };
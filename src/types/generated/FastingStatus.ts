@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Ramadan fasting progress for a day
+ */
+export type FastingStatus = { is_ramadan: boolean, 
+/**
+ * Between imsak and Maghrib on a Ramadan day
+ */
+is_fasting: boolean, imsak: string, iftar: string, seconds_since_suhoor?: number, seconds_to_iftar?: number, 
+/**
+ * Time left for suhoor before the fast starts
+ */
+seconds_to_imsak?: number, };
@@ -0,0 +1,19 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { PrayerGoal } from "./PrayerGoal";
+
+/**
+ * How a goal stands for one week
+ */
+export type GoalProgress = { goal: PrayerGoal, week_start: string, completed: number, achieved: boolean, 
+/**
+ * Days of the week after `today`
+ */
+days_left: number, 
+/**
+ * Whether the target can still be met this week
+ */
+reachable: boolean, 
+/**
+ * Achieved weeks in a row before this one
+ */
+streak_weeks: number, };
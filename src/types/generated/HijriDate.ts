@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Date in the Hijri calendar
+ */
+export type HijriDate = { year: number, 
+/**
+ * 1 = Muharram ... 12 = Dhul Hijjah
+ */
+month: number, day: number, };
@@ -0,0 +1,16 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { HijriDate } from "./HijriDate";
+import type { SpecialDay } from "./SpecialDay";
+
+/**
+ * Hijri date for a day, with the flags the UI needs
+ */
+export type HijriDay = { date: HijriDate, is_ramadan: boolean, special_day?: SpecialDay, 
+/**
+ * Adjustment applied on top of the calculation
+ */
+adjustment_days: number, 
+/**
+ * True when the mosque's announcement was applied
+ */
+from_mosque: boolean, eid_prayer_times: Array<string>, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * What a global shortcut (or a Stream Deck button bound to one) does
+ */
+export type HotkeyAction = "show_mini_countdown" | "mark_attended" | "snooze_reminder";
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Global shortcuts in Tauri accelerator syntax, e.g. "CmdOrCtrl+Alt+P"; unset disables one
+ * None are bound until the user picks them, so the app never takes keys other apps use
+ */
+export type HotkeyBindings = { show_mini_countdown?: string, mark_attended?: string, snooze_reminder?: string, };
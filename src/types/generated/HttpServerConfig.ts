@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Optional HTTP server serving calendar feeds and widgets to other devices
+ */
+export type HttpServerConfig = { enabled: boolean, port: number, 
+/**
+ * Listen on every network interface so phones on the same network can connect,
+ * each with an API token; only this computer can connect otherwise
+ */
+allow_lan: boolean, 
+/**
+ * Serve `/metrics` in the Prometheus text format, for monitoring kiosk screens
+ */
+expose_metrics: boolean, };
@@ -0,0 +1,19 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { HttpServerConfig } from "./HttpServerConfig";
+
+/**
+ * Whether the local HTTP server is listening, and where
+ */
+export type HttpServerStatus = { config: HttpServerConfig, running: boolean, 
+/**
+ * Address the server listens on, e.g. "127.0.0.1:8787"
+ */
+address?: string, 
+/**
+ * Why the server could not start, e.g. the port is taken
+ */
+last_error?: string, 
+/**
+ * Dashboards connected to `/ws`
+ */
+websocket_clients: number, };
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { PrayerName } from "./PrayerName";
+
+/**
+ * Minutes after adhan used as iqama when a provider publishes adhan times only
+ * Stored in the `iqama_defaults` setting
+ */
+export type IqamaDefaults = { offsets: { [key in PrayerName]?: number }, };
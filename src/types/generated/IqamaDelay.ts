@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { PrayerName } from "./PrayerName";
+
+/**
+ * How late a mosque usually starts a prayer, learned from iqama reports
+ */
+export type IqamaDelay = { prayer: PrayerName, 
+/**
+ * Typical delay, only once reports agree the mosque starts late
+ */
+delay_secs?: number, 
+/**
+ * How much the start varies between reports (standard deviation)
+ */
+spread_secs?: number, report_count: number, };
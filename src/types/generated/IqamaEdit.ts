@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * New iqama for an edited prayer
+ */
+export type IqamaEdit = { "kind": "at", time: string, } | { "kind": "after_adhan", minutes: number, } | { "kind": "clear" };
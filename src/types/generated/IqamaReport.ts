@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { PrayerName } from "./PrayerName";
+
+/**
+ * When a congregation actually started, as reported by the user after the prayer
+ */
+export type IqamaReport = { mosque_id: string, prayer: PrayerName, date: string, 
+/**
+ * Iqama time published by the mosque
+ */
+scheduled_iqama: string, actual_iqama: string, reported_at: string, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * When Isha's time runs out
+ */
+export type IshaEnd = "fajr" | "islamic_midnight";
@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { HijriDate } from "./HijriDate";
+import type { SpecialDay } from "./SpecialDay";
+
+/**
+ * A special day coming up in the Islamic calendar
+ */
+export type IslamicEvent = { day: SpecialDay, date: string, hijri_date: HijriDate, 
+/**
+ * Days from the start of the search, 0 for today
+ */
+days_until: number, };
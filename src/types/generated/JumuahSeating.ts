@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Whether the user will find a seat for the khutbah
+ */
+export type JumuahSeating = "seats_likely" | "arrive_earlier" | "khutbah_started";
@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { JumuahSeating } from "./JumuahSeating";
+
+/**
+ * Travel prediction for Jumuah, aiming for a seat before the khutbah rather than a rakah
+ */
+export type JumuahTravelPrediction = { khutbah_time: string, 
+/**
+ * Minutes before the khutbah the user aims to arrive
+ */
+arrive_before_minutes: number, recommended_leave_time: string, arrival_time: string, should_leave_now: boolean, time_until_leave_secs?: number, seating: JumuahSeating, };
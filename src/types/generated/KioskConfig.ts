@@ -0,0 +1,18 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Layout of a mosque lobby screen running in kiosk mode
+ */
+export type KioskConfig = { 
+/**
+ * Mosque shown; the selected mosque when unset
+ */
+mosque_id?: string, show_iqama_board: boolean, 
+/**
+ * Messages shown in turn under the countdown
+ */
+announcements: Array<string>, 
+/**
+ * Seconds each announcement stays on screen
+ */
+announcement_secs: number, };
@@ -0,0 +1,19 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { PrayerCountdown } from "./PrayerCountdown";
+
+/**
+ * Everything a kiosk screen draws, pushed by the backend every tick
+ */
+export type KioskFrame = { mosque_id: string, mosque_name?: string, now: string, 
+/**
+ * Large countdown: the prayer in progress, else the next one held today
+ */
+focus?: PrayerCountdown, 
+/**
+ * Today's prayers for the iqama board; empty when the board is hidden or nothing is cached
+ */
+board: Array<PrayerCountdown>, announcement?: string, 
+/**
+ * False while the schedule can't be refreshed; the cached one stays on screen
+ */
+online: boolean, synced_at?: string, };
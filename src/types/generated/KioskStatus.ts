@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { KioskConfig } from "./KioskConfig";
+
+/**
+ * Whether kiosk mode is on, and how its schedule sync is going
+ */
+export type KioskStatus = { enabled: boolean, config?: KioskConfig, online: boolean, last_error?: string, synced_at?: string, };
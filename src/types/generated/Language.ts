@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Languages backend-generated text is available in
+ */
+export type Language = "en" | "ar" | "fr" | "tr" | "ur";
@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { LegacySource } from "./LegacySource";
+
+/**
+ * What a legacy import added; data already present in the current database is left as is
+ */
+export type LegacyImportSummary = { source: LegacySource, path: string, favorites: number, schedules: number, log_entries: number, 
+/**
+ * Mosque now selected for the profile, when the old app had an active one and the profile had none
+ */
+selected_mosque_id?: string, 
+/**
+ * Entries that could not be read or were already present
+ */
+skipped: number, };
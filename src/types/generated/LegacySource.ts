@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Kind of data `migrate_legacy_data` can import
+ */
+export type LegacySource = "ikama_database" | "mawaqit_export" | "csv";
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * One line of the JSON log file
+ */
+export type LogEntry = { timestamp: string, level: string, target: string, message: string, };
@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * One family member's attendance over the range
+ */
+export type MemberAttendance = { profile: string, 
+/**
+ * Mosque the profile follows
+ */
+mosque_id?: string, logged: number, jamaah: number, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { MosqueFacilities } from "./MosqueFacilities";
+
+/**
+ * Mosque entity
+ */
+export type Mosque = { id: string, name: string, address?: string, city?: string, country?: string, latitude?: number, longitude?: number, is_favorite: boolean, last_accessed?: string, facilities: MosqueFacilities, };
@@ -0,0 +1,20 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Mosque } from "./Mosque";
+import type { PrayerDiff } from "./PrayerDiff";
+
+/**
+ * Side-by-side schedules of two mosques on one day, to choose between nearby mosques
+ */
+export type MosqueComparison = { date: string, mosque_a: Mosque, mosque_b: Mosque, 
+/**
+ * Distance between the two mosques in km, when both have coordinates
+ */
+distance_km?: number, 
+/**
+ * Distance from the given location to each mosque in km
+ */
+distance_a_km?: number, distance_b_km?: number, 
+/**
+ * Jumuah is only compared when both mosques hold it
+ */
+prayers: Array<PrayerDiff>, };
@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { GeoLocation } from "./GeoLocation";
+
+/**
+ * A named place with its own active mosque, e.g. "home" or "work"
+ */
+export type MosqueContext = { label: string, 
+/**
+ * Profile whose selected mosque serves the place; home uses the default profile
+ */
+profile: string, 
+/**
+ * Where the place is, so being there picks it without a schedule
+ */
+location: GeoLocation | null, };
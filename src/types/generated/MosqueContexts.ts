@@ -0,0 +1,16 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { MosqueContext } from "./MosqueContext";
+import type { PlaceWindow } from "./PlaceWindow";
+
+/**
+ * Places with their own mosque and the weekly schedule of where the user is, stored in the `mosque_contexts` setting
+ */
+export type MosqueContexts = { enabled: boolean, 
+/**
+ * The first place is where the user is outside every window
+ */
+places: Array<MosqueContext>, 
+/**
+ * The first window covering a prayer picks the place
+ */
+schedule: Array<PlaceWindow>, };
@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { EventKind } from "./EventKind";
+
+/**
+ * Lecture, iftar, class or announcement published by a mosque
+ */
+export type MosqueEvent = { 
+/**
+ * Stable id from the source (e.g. "mawaqit:<slug>:<id>", "ical:<uid>")
+ */
+id: string, mosque_id: string, kind: EventKind, title: string, description?: string, location?: string, url?: string, starts_at: string, ends_at?: string, 
+/**
+ * Remind this many minutes before `starts_at`; set by the user
+ */
+reminder_minutes?: number, };
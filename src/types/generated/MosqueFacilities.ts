@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Facilities a mosque offers; `None` means unknown
+ */
+export type MosqueFacilities = { womens_area?: boolean, wheelchair_access?: boolean, wudu?: boolean, parking?: boolean, };
@@ -0,0 +1,18 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Hijri settings announced by a mosque, overriding pure calculation
+ */
+export type MosqueHijriInfo = { 
+/**
+ * Days added to the calculated Hijri date (usually -1, 0 or 1)
+ */
+adjustment_days: number, 
+/**
+ * Mosque completes the current month to 30 days
+ */
+force_month_30: boolean, 
+/**
+ * Announced Eid prayer times ("HH:MM"), empty outside Eid
+ */
+eid_prayer_times: Array<string>, };
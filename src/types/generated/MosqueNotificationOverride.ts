@@ -0,0 +1,20 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { PrayerName } from "./PrayerName";
+
+/**
+ * Reminder settings for one mosque, e.g. silence a mosque near work that is only browsed now and then
+ * Applied on top of the notification profile; overrides only narrow, so a silent profile stays silent
+ */
+export type MosqueNotificationOverride = { mosque_id: string, 
+/**
+ * Any reminder at all while this mosque is selected
+ */
+enabled: boolean, sound: boolean, speech: boolean, 
+/**
+ * Prayers that get reminders at this mosque; unset keeps the profile's
+ */
+prayers?: Array<PrayerName>, 
+/**
+ * Minutes before adhan and iqama to remind, replacing the usual ones
+ */
+reminder_minutes_before?: Array<number>, };
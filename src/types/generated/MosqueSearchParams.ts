@@ -0,0 +1,17 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { MosqueSort } from "./MosqueSort";
+
+/**
+ * Paging, filters and order of a mosque search
+ * Pages start at 1; without `page` or `limit` every match is returned
+ */
+export type MosqueSearchParams = { page?: number, limit?: number, country?: string, city?: string, 
+/**
+ * Keep only mosques whose cached schedules do (or don't) publish iqama times;
+ * mosques without a cached schedule are left out either way
+ */
+has_iqama?: boolean, 
+/**
+ * Keep only mosques within this many km of the user's location, when it is known
+ */
+max_distance_km?: number, sort?: MosqueSort, };
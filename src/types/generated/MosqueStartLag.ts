@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { PrayerName } from "./PrayerName";
+
+/**
+ * How long after iqama a mosque's imam actually starts, as set by the user
+ */
+export type MosqueStartLag = { 
+/**
+ * Lag for prayers without their own, in seconds
+ */
+default_secs?: number, 
+/**
+ * Per-prayer lag in seconds
+ */
+prayers: { [key in PrayerName]?: number }, };
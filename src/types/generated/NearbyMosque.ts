@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Mosque } from "./Mosque";
+
+/**
+ * A catalog mosque suggested during onboarding, nearest first
+ */
+export type NearbyMosque = { mosque: Mosque, distance_km: number, };
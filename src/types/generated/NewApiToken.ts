@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ApiToken } from "./ApiToken";
+
+/**
+ * A token just created, with the secret to copy to the device
+ */
+export type NewApiToken = { token: ApiToken, secret: string, };
@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { CountdownDisplay } from "./CountdownDisplay";
+import type { Prayer } from "./Prayer";
+
+/**
+ * Result for next prayer calculation
+ */
+export type NextPrayerResult = { prayer: Prayer, time_until_adhan_secs: number, time_until_iqama_secs?: number, 
+/**
+ * When the mosque usually starts late, the time the congregation is expected to start
+ */
+expected_iqama?: string, is_tomorrow: boolean, adhan_display: CountdownDisplay, iqama_display?: CountdownDisplay, };
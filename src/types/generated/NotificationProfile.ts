@@ -0,0 +1,23 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { PrayerName } from "./PrayerName";
+
+/**
+ * How reminders behave while a profile is active
+ */
+export type NotificationProfile = { 
+/**
+ * e.g. "work", "weekend", "travel"
+ */
+name: string, 
+/**
+ * Notifications play a sound; silent (vibrate-only on phones) otherwise
+ */
+sound: boolean, 
+/**
+ * Reminders are read aloud when spoken announcements are on
+ */
+speech: boolean, 
+/**
+ * Prayers that get reminders
+ */
+prayers: Array<PrayerName>, };
@@ -0,0 +1,16 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { NotificationProfile } from "./NotificationProfile";
+import type { ProfileWindow } from "./ProfileWindow";
+
+/**
+ * Notification profiles and the weekly schedule selecting them, stored in the `notification_profiles` setting
+ */
+export type NotificationProfiles = { enabled: boolean, profiles: Array<NotificationProfile>, 
+/**
+ * The first window covering the current time picks the profile
+ */
+schedule: Array<ProfileWindow>, 
+/**
+ * Profile chosen by hand, e.g. "travel"; overrides the schedule
+ */
+manual?: string, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Custom wording for one kind of notification; an unset part keeps the built-in text
+ */
+export type NotificationTemplate = { title?: string, body?: string, };
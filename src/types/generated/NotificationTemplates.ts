@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { NotificationTemplate } from "./NotificationTemplate";
+
+/**
+ * Custom notification wording, stored in the `notification_templates` setting
+ * Templates use `{prayer}`, `{minutes}`, `{mosque}` and `{rakah}`, e.g. "{prayer} at {mosque} in {minutes} min"
+ */
+export type NotificationTemplates = { adhan_reminder?: NotificationTemplate, iqama_reminder?: NotificationTemplate, prayer_start?: NotificationTemplate, };
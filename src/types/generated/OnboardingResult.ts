@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SelectedMosque } from "./SelectedMosque";
+
+/**
+ * What finishing onboarding saved
+ */
+export type OnboardingResult = { selected: SelectedMosque, default_country: string, 
+/**
+ * Today's schedule could not be fetched yet; the app retries on its own
+ */
+fetch_error?: string, };
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { DetectedRegion } from "./DetectedRegion";
+import type { NearbyMosque } from "./NearbyMosque";
+
+/**
+ * Mosques around the detected or given location
+ */
+export type OnboardingSuggestions = { region: DetectedRegion, mosques: Array<NearbyMosque>, };
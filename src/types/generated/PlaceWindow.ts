@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { DayOfWeek } from "./DayOfWeek";
+
+/**
+ * When the user is at a place, e.g. weekday office hours at "work"
+ */
+export type PlaceWindow = { 
+/**
+ * Label of the place; read from `profile` in settings saved before windows named places
+ */
+place: string, days: Array<DayOfWeek>, 
+/**
+ * "HH:MM"; a window ending before it starts runs past midnight
+ */
+start: string, end: string, };
@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { PrayerName } from "./PrayerName";
+
+/**
+ * Prayer data structure
+ */
+export type Prayer = { name: PrayerName, adhan: string, iqama?: string, custom_rakah_count?: number, 
+/**
+ * Set when the mosque publishes the iqama as minutes after the adhan ("+15") rather than a clock time
+ */
+iqama_offset_minutes?: number, };
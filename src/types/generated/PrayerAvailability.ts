@@ -0,0 +1,16 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { PrayerName } from "./PrayerName";
+
+/**
+ * Which congregational prayers a mosque holds
+ * Musallas often only host some prayers, or only Jumuah
+ */
+export type PrayerAvailability = { 
+/**
+ * Temporarily closed: no prayer is held
+ */
+closed: boolean, 
+/**
+ * Explicit per-prayer flags; prayers not listed are assumed held
+ */
+prayers: { [key in PrayerName]?: boolean }, };
@@ -0,0 +1,24 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { CountdownDisplay } from "./CountdownDisplay";
+import type { PrayerName } from "./PrayerName";
+
+/**
+ * Prayer countdown info
+ */
+export type PrayerCountdown = { prayer_name: PrayerName, adhan_time: string, iqama_time?: string, 
+/**
+ * The iqama as the mosque's board shows it, in minutes after the adhan, when published that way
+ */
+iqama_offset_minutes?: number, time_until_adhan_secs: number, time_until_iqama_secs?: number, is_active: boolean, 
+/**
+ * The mosque doesn't hold this prayer (closed, or the prayer is switched off); never active
+ */
+not_held: boolean, 
+/**
+ * Sunrise, which ends Fajr's window; only on Fajr, when the provider publishes it
+ */
+shuruq_time?: string, 
+/**
+ * Seconds left before sunrise, zero once it has passed
+ */
+time_until_shuruq_secs?: number, adhan_display: CountdownDisplay, iqama_display?: CountdownDisplay, };
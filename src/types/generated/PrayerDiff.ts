@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { PrayerName } from "./PrayerName";
+
+/**
+ * One prayer at two mosques; differences are mosque B minus mosque A, in minutes
+ */
+export type PrayerDiff = { prayer: PrayerName, adhan_a: string, adhan_b: string, adhan_diff_minutes: number, iqama_a?: string, iqama_b?: string, 
+/**
+ * Unset unless both mosques hold an iqama for the prayer
+ */
+iqama_diff_minutes: number, };
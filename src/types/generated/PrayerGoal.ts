@@ -0,0 +1,16 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { PrayerLogTag } from "./PrayerLogTag";
+import type { PrayerName } from "./PrayerName";
+
+/**
+ * A weekly target for logged prayers, e.g. Fajr in jamaah 5 times a week
+ */
+export type PrayerGoal = { id: number, profile: string, 
+/**
+ * Prayer counted; any daily prayer when unset
+ */
+prayer?: PrayerName, 
+/**
+ * Tag a logged prayer needs to count, e.g. jamaah
+ */
+tag?: PrayerLogTag, target_per_week: number, created_at: string, };
@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { PrayerLogTag } from "./PrayerLogTag";
+import type { PrayerName } from "./PrayerName";
+
+/**
+ * A prayer the user logged, with optional tags and a private reflection (khushu notes)
+ */
+export type PrayerLogEntry = { profile: string, prayer: PrayerName, date: string, 
+/**
+ * Mosque attended, when prayed there
+ */
+mosque_id?: string, tags: Array<PrayerLogTag>, note?: string, logged_at: string, };
@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { PrayerLogTag } from "./PrayerLogTag";
+import type { PrayerName } from "./PrayerName";
+
+/**
+ * Filter for reading the prayer log; unset fields match everything
+ */
+export type PrayerLogQuery = { profile?: string, from?: string, to?: string, prayer?: PrayerName, tag?: PrayerLogTag, 
+/**
+ * Only entries with a reflection
+ */
+with_note?: boolean, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * How a logged prayer was prayed
+ */
+export type PrayerLogTag = "jamaah" | "on_time" | "late" | "with_family";
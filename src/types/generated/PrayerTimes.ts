@@ -0,0 +1,34 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AsrMethod } from "./AsrMethod";
+import type { Prayer } from "./Prayer";
+import type { ScheduleFreshness } from "./ScheduleFreshness";
+import type { ScheduleWarning } from "./ScheduleWarning";
+
+/**
+ * Prayer times for a day
+ */
+export type PrayerTimes = { date: string, fajr: Prayer, dhuhr: Prayer, asr: Prayer, maghrib: Prayer, isha: Prayer, jumuah?: Prayer, mosque_id?: string, mosque_name?: string, cached_at?: string, 
+/**
+ * Start of the fast, when the provider publishes it
+ */
+imsak?: string, 
+/**
+ * Sunrise, which ends the Fajr window, when the provider publishes it
+ */
+shuruq?: string, 
+/**
+ * Asr convention of calculated times; unset for times a mosque publishes
+ */
+asr_method?: AsrMethod, 
+/**
+ * Provider the schedule was fetched from
+ */
+provider?: string, 
+/**
+ * Age and source of the data, set on schedules returned to the UI
+ */
+freshness?: ScheduleFreshness, 
+/**
+ * Data quality notes, e.g. an iqama filled in from the default offset
+ */
+warnings: Array<ScheduleWarning>, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { PrayerName } from "./PrayerName";
+
+/**
+ * Time during which a prayer can be prayed
+ */
+export type PrayerWindow = { prayer: PrayerName, start: string, end: string, };
@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { DayOfWeek } from "./DayOfWeek";
+
+/**
+ * When a profile applies automatically, e.g. work hours on weekdays
+ */
+export type ProfileWindow = { profile: string, days: Array<DayOfWeek>, 
+/**
+ * "HH:MM"; a window ending before it starts runs past midnight
+ */
+start: string, end: string, };
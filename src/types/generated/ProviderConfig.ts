@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Provider configuration
+ */
+export type ProviderConfig = { provider_id: string, settings: Record<string, unknown>, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ConfigField } from "./ConfigField";
+
+/**
+ * Provider metadata
+ */
+export type ProviderInfo = { id: string, name: string, description: string, config_schema: Array<ConfigField>, };
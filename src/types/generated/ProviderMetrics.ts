@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Schedule fetches from one provider since the app started
+ */
+export type ProviderMetrics = { provider_id: string, requests: number, failures: number, total_latency_ms: number, max_latency_ms: number, last_latency_ms: number, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Provider test result
+ */
+export type ProviderTestResult = { success: boolean, message: string, latency_ms?: number, };
@@ -0,0 +1,19 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { RakahStatus } from "./RakahStatus";
+
+/**
+ * Rakah estimation result
+ */
+export type RakahEstimate = { status: RakahStatus, current_rakah?: number, total_rakah: number, elapsed_secs?: number, remaining_secs?: number, progress: number, is_estimate: boolean, 
+/**
+ * Minutes since prayer ended (only for recently_finished status)
+ */
+ended_minutes_ago?: number, 
+/**
+ * Whether it's still possible to catch the prayer (within catch-up window)
+ */
+can_still_catch: boolean, 
+/**
+ * Plausible rakah range while in progress; widens as time passes and when start times vary
+ */
+min_rakah?: number, max_rakah?: number, };
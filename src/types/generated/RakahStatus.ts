@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Live state of a congregational prayer
+ */
+export type RakahStatus = "not_started" | "in_progress" | "recently_finished" | "likely_finished" | "not_available";
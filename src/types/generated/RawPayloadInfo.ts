@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * An archived response without its body, for listing
+ */
+export type RawPayloadInfo = { id: number, provider_id: string, mosque_id: string, url: string, fetched_at: string, size_bytes: number, compressed_bytes: number, };
@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * What the scheduler knows about the app's surroundings
+ */
+export type RefreshContext = { 
+/**
+ * `None` when the power source is unknown (treated as mains)
+ */
+on_battery?: boolean, window_visible: boolean, seconds_to_iqama?: number, };
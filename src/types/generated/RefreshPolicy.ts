@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { RefreshMode } from "./RefreshMode";
+
+/**
+ * How often the ticker runs and whether network work should wait
+ */
+export type RefreshPolicy = { mode: RefreshMode, tick_interval_secs: number, defer_network: boolean, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { PrayerName } from "./PrayerName";
+
+/**
+ * An iqama time that moved between the cached schedule and a provider refresh
+ */
+export type ScheduleChange = { mosque_id: string, prayer: PrayerName, date: string, old_iqama?: string, new_iqama?: string, detected_at: string, };
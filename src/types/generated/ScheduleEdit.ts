@@ -0,0 +1,16 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { IqamaEdit } from "./IqamaEdit";
+import type { PrayerName } from "./PrayerName";
+
+/**
+ * Change to one prayer over a range of days, e.g. Isha iqama at 21:30 for all of March
+ */
+export type ScheduleEdit = { from: string, 
+/**
+ * Last day edited, inclusive; only `from` when unset
+ */
+to?: string, prayer: PrayerName, 
+/**
+ * New adhan, "HH:MM"
+ */
+adhan?: string, iqama?: IqamaEdit, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * File formats a timetable can be exported to
+ */
+export type ScheduleExportFormat = "ics" | "pdf";
@@ -0,0 +1,22 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * How old a schedule is, so the UI can flag stale iqama times
+ */
+export type ScheduleFreshness = { 
+/**
+ * Unknown for days cached before the provider was recorded
+ */
+provider?: string, cached_at?: string, 
+/**
+ * Seconds since the schedule was fetched
+ */
+age_secs: number, 
+/**
+ * Older than the maximum cache age
+ */
+stale: boolean, 
+/**
+ * A provider fetch for this day is running
+ */
+refreshing: boolean, };
@@ -0,0 +1,16 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { PrayerName } from "./PrayerName";
+import type { ScheduleWarningKind } from "./ScheduleWarningKind";
+
+/**
+ * A data quality note on a schedule, shown so users know how far to trust it
+ */
+export type ScheduleWarning = { kind: ScheduleWarningKind, 
+/**
+ * Unset for warnings about the whole day
+ */
+prayer?: PrayerName, 
+/**
+ * English description for logs and support; the UI words it from `kind`
+ */
+message: string, };
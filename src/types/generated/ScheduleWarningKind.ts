@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * What may make a schedule differ from the mosque's board
+ */
+export type ScheduleWarningKind = "default_iqama" | "missing_iqama" | "iqama_before_adhan" | "out_of_order" | "calculated";
@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Mosque } from "./Mosque";
+
+/**
+ * The mosque a profile follows
+ * Countdowns, travel predictions and the refresh scheduler read it instead of taking a mosque id
+ * Travel times to it are kept per mode in `travel_times`
+ */
+export type SelectedMosque = { profile: string, mosque: Mosque, selected_at: string, };
@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { JsonValue } from "./serde_json/JsonValue";
+
+/**
+ * Message pushed to WebSocket clients: an event the app window receives, with the same payload
+ */
+export type ServerEvent = { 
+/**
+ * Event name, e.g. "countdown" or "schedule-changed"
+ */
+event: string, payload: JsonValue, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Days of the Islamic year the app highlights
+ */
+export type SpecialDay = "ramadan_start" | "laylat_al_qadr" | "eid_al_fitr" | "dhul_hijjah_start" | "dhul_hijjah_day" | "day_of_arafah" | "eid_al_adha" | "ashura";
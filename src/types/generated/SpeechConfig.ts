@@ -0,0 +1,20 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Language } from "./Language";
+import type { PrayerName } from "./PrayerName";
+
+/**
+ * Spoken prayer announcements through the system's text-to-speech engine
+ */
+export type SpeechConfig = { enabled: boolean, 
+/**
+ * Prayers whose reminders are read aloud
+ */
+prayers: Array<PrayerName>, 
+/**
+ * Language spoken; the app's language when unset
+ */
+language?: Language, 
+/**
+ * Show the notification too; announcements replace it otherwise
+ */
+with_notification: boolean, };
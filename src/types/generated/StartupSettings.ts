@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Launch-at-login and start-in-tray preferences
+ */
+export type StartupSettings = { 
+/**
+ * Registered as a login item with the OS
+ */
+autostart: boolean, 
+/**
+ * Login launches stay hidden in the tray
+ */
+start_minimized: boolean, };
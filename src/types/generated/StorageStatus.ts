@@ -0,0 +1,18 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Whether data written now survives a restart
+ */
+export type StorageStatus = { 
+/**
+ * False while running on the in-memory fallback
+ */
+persistent: boolean, db_path: string, 
+/**
+ * Data kept next to the executable instead of the user's data directory
+ */
+portable: boolean, 
+/**
+ * Why the database file could not be opened
+ */
+last_error?: string | null, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * How late the background ticker wakes up compared to the interval it asked for
+ */
+export type TickMetrics = { ticks: number, last_drift_ms: number, max_drift_ms: number, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Clock style for displayed times, stored in the `time_format` setting
+ */
+export type TimeFormat = "12h" | "24h";
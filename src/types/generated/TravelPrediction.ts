@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ArrivalStatus } from "./ArrivalStatus";
+
+/**
+ * Travel prediction result
+ */
+export type TravelPrediction = { recommended_leave_time: string, arrival_time: string, arrival_rakah?: number, arrival_status: ArrivalStatus, should_leave_now: boolean, time_until_leave_secs?: number, is_late: boolean, 
+/**
+ * Extra time added to the arrival buffer because this prayer is usually crowded
+ */
+crowd_buffer_secs: number, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Where an "on my way" session stands
+ */
+export type TravelSessionStatus = "active" | "arrived" | "cancelled" | "timed_out";
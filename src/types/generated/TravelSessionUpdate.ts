@@ -0,0 +1,34 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { PrayerName } from "./PrayerName";
+import type { TravelMode } from "./TravelMode";
+import type { TravelPrediction } from "./TravelPrediction";
+import type { TravelSessionStatus } from "./TravelSessionStatus";
+
+/**
+ * Live state of a trip to a mosque, sent as `travel-session-update` while it runs and once when it ends
+ */
+export type TravelSessionUpdate = { mosque_id: string, mode: TravelMode, 
+/**
+ * Prayer the user is heading to
+ */
+prayer?: PrayerName, status: TravelSessionStatus, started_at: string, elapsed_secs: number, 
+/**
+ * Time left, from the last position or else from the expected travel time
+ */
+remaining_secs?: number, eta?: string, 
+/**
+ * Straight-line distance left from the last position
+ */
+distance_m?: number, 
+/**
+ * Where the prayer will be on arrival, recalculated with every update
+ */
+prediction?: TravelPrediction, 
+/**
+ * Door-to-door time logged on arrival
+ */
+travel_time_secs?: number, 
+/**
+ * Typical travel time learned from the logged trips, saved for the mosque and mode
+ */
+learned_travel_time_secs?: number, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TravelMode } from "./TravelMode";
+
+/**
+ * Saved travel time from home to a favorite mosque for one mode
+ */
+export type TravelTime = { mosque_id: string, mode: TravelMode, travel_time_seconds: number, updated_at: string, };
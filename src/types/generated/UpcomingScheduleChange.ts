@@ -0,0 +1,19 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { PrayerName } from "./PrayerName";
+
+/**
+ * An iqama jump ahead in the mosque's published calendar
+ */
+export type UpcomingScheduleChange = { prayer: PrayerName, 
+/**
+ * First day with the new time
+ */
+date: string, 
+/**
+ * Local wall-clock iqama the day before
+ */
+previous_iqama: string, new_iqama: string, 
+/**
+ * Positive when later
+ */
+shift_minutes: number, };
@@ -0,0 +1,22 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Latest published release compared with the running version
+ */
+export type UpdateInfo = { current_version: string, latest_version: string, update_available: boolean, 
+/**
+ * Installer for this platform, when the release has one
+ */
+download_url?: string, 
+/**
+ * Release page, for platforms without an installer asset
+ */
+release_url: string, 
+/**
+ * Markdown notes of the release
+ */
+release_notes?: string, published_at?: string, 
+/**
+ * Tauri's updater is configured, so the UI can install in place instead of opening the download
+ */
+self_update: boolean, };
@@ -0,0 +1,18 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A provider page or payload that stopped parsing, usually after the upstream site changed
+ */
+export type UpstreamIssue = { provider_id: string, 
+/**
+ * What failed to parse, e.g. "mosque_list", or "confData:<mosque>" for one mosque's page
+ */
+context: string, 
+/**
+ * Stable hash of provider, context and error, so repeats of one failure are counted together
+ */
+fingerprint: string, message: string, 
+/**
+ * Data was still served by a fallback parser
+ */
+degraded: boolean, occurrences: number, first_seen: string, last_seen: string, };
@@ -1,105 +1,179 @@
-export interface Prayer {
-  name: string;
-  adhan: string;
-  iqama?: string;
-  custom_rakah_count?: number;
-}
-
-export interface PrayerTimes {
-  date: string;
-  fajr: Prayer;
-  dhuhr: Prayer;
-  asr: Prayer;
-  maghrib: Prayer;
-  isha: Prayer;
-  jumuah?: Prayer;
-  mosque_id?: string;
-  mosque_name?: string;
-  cached_at?: string;
-}
-
-export interface Mosque {
-  id: string;
-  name: string;
-  address?: string;
-  city?: string;
-  country?: string;
-  latitude?: number;
-  longitude?: number;
-  is_favorite: boolean;
-  last_accessed?: string;
-}
+// Command payload types are generated from the Rust models by ts-rs.
+// Regenerate them with `cargo test` in src-tauri after changing a model.
 
-export interface NextPrayerResult {
-  prayer: Prayer;
-  time_until_adhan_secs: number;
-  time_until_iqama_secs?: number;
-  is_tomorrow: boolean;
-}
-
-export interface RakahEstimate {
-  status: 'not_started' | 'in_progress' | 'likely_finished' | 'recently_finished' | 'not_available';
-  current_rakah?: number;
-  total_rakah: number;
-  elapsed_secs?: number;
-  remaining_secs?: number;
-  progress: number;
-  is_estimate: boolean;
-  /** Minutes since prayer ended (only for recently_finished status) */
-  ended_minutes_ago?: number;
-  /** Whether it's still possible to catch the prayer (within catch-up window) */
-  can_still_catch: boolean;
-}
-
-export interface TravelPrediction {
-  recommended_leave_time: string;
-  arrival_time: string;
-  arrival_rakah?: number;
-  arrival_status: string;
-  should_leave_now: boolean;
-  time_until_leave_secs?: number;
-  is_late: boolean;
-}
+export type { AccountImport } from './generated/AccountImport';
+export type { AdhanSound } from './generated/AdhanSound';
+export type { AdhanSoundConfig } from './generated/AdhanSoundConfig';
+export type { AdminStatus } from './generated/AdminStatus';
+export type { AdhkarConfig } from './generated/AdhkarConfig';
+export type { AdhkarEvent } from './generated/AdhkarEvent';
+export type { AppMetrics } from './generated/AppMetrics';
+export type { ArchivedPayload } from './generated/ArchivedPayload';
+export type { AdhkarPhase } from './generated/AdhkarPhase';
+export type { AdhkarStatus } from './generated/AdhkarStatus';
+export type { AdhkarStep } from './generated/AdhkarStep';
+export type { ArrivalStatus } from './generated/ArrivalStatus';
+export type { AsrMethod } from './generated/AsrMethod';
+export type { AudioOutputDevice } from './generated/AudioOutputDevice';
+export type { CacheStats } from './generated/CacheStats';
+export type { CacheMetrics } from './generated/CacheMetrics';
+export type { CalendarSyncConfig } from './generated/CalendarSyncConfig';
+export type { CalendarSyncStatus } from './generated/CalendarSyncStatus';
+export type { CatalogImportPhase } from './generated/CatalogImportPhase';
+export type { CatalogImportProgress } from './generated/CatalogImportProgress';
+export type { CatalogImportSummary } from './generated/CatalogImportSummary';
+export type { ClockResync } from './generated/ClockResync';
+export type { CommandError } from './generated/CommandError';
+export type { CommandErrorKind } from './generated/CommandErrorKind';
+export type { ConfigField } from './generated/ConfigField';
+export type { ConfigFieldType } from './generated/ConfigFieldType';
+export type { ContextReason } from './generated/ContextReason';
+export type { ContextualNextPrayer } from './generated/ContextualNextPrayer';
+export type { CrowdLevel } from './generated/CrowdLevel';
+export type { CrowdReport } from './generated/CrowdReport';
+export type { CrowdSummary } from './generated/CrowdSummary';
+export type { CurrentPhase } from './generated/CurrentPhase';
+export type { DayOfWeek } from './generated/DayOfWeek';
+export type { DayPhase } from './generated/DayPhase';
+export type { DaySummary } from './generated/DaySummary';
+export type { DetectedRegion } from './generated/DetectedRegion';
+export type { EventKind } from './generated/EventKind';
+export type { FajrAlarmConfig } from './generated/FajrAlarmConfig';
+export type { FajrAlarmRing } from './generated/FajrAlarmRing';
+export type { FajrAlarmStatus } from './generated/FajrAlarmStatus';
+export type { FamilyAttendance } from './generated/FamilyAttendance';
+export type { FamilyPrayerDay } from './generated/FamilyPrayerDay';
+export type { FastingStatus } from './generated/FastingStatus';
+export type { GeoLocation } from './generated/GeoLocation';
+export type { GoalProgress } from './generated/GoalProgress';
+export type { HijriDate } from './generated/HijriDate';
+export type { HijriDay } from './generated/HijriDay';
+export type { IslamicEvent } from './generated/IslamicEvent';
+export type { HotkeyAction } from './generated/HotkeyAction';
+export type { HotkeyBindings } from './generated/HotkeyBindings';
+export type { HttpServerConfig } from './generated/HttpServerConfig';
+export type { HttpServerStatus } from './generated/HttpServerStatus';
+export type { ApiScope } from './generated/ApiScope';
+export type { ApiToken } from './generated/ApiToken';
+export type { NewApiToken } from './generated/NewApiToken';
+export type { IqamaDefaults } from './generated/IqamaDefaults';
+export type { IqamaDelay } from './generated/IqamaDelay';
+export type { IqamaReport } from './generated/IqamaReport';
+export type { IqamaEdit } from './generated/IqamaEdit';
+export type { IshaEnd } from './generated/IshaEnd';
+export type { JumuahSeating } from './generated/JumuahSeating';
+export type { JumuahTravelPrediction } from './generated/JumuahTravelPrediction';
+export type { KioskConfig } from './generated/KioskConfig';
+export type { KioskFrame } from './generated/KioskFrame';
+export type { KioskStatus } from './generated/KioskStatus';
+export type { Language } from './generated/Language';
+export type { LegacyImportSummary } from './generated/LegacyImportSummary';
+export type { LegacySource } from './generated/LegacySource';
+export type { LogEntry } from './generated/LogEntry';
+export type { MemberAttendance } from './generated/MemberAttendance';
+export type { Mosque } from './generated/Mosque';
+export type { MosqueComparison } from './generated/MosqueComparison';
+export type { MosqueContext } from './generated/MosqueContext';
+export type { MosqueContexts } from './generated/MosqueContexts';
+export type { MosqueEvent } from './generated/MosqueEvent';
+export type { MosqueFacilities } from './generated/MosqueFacilities';
+export type { MosqueHijriInfo } from './generated/MosqueHijriInfo';
+export type { MosqueNotificationOverride } from './generated/MosqueNotificationOverride';
+export type { MosqueSearchParams } from './generated/MosqueSearchParams';
+export type { MosqueSearchResult } from './generated/MosqueSearchResult';
+export type { MosqueStartLag } from './generated/MosqueStartLag';
+export type { MosqueSort } from './generated/MosqueSort';
+export type { NearbyMosque } from './generated/NearbyMosque';
+export type { NextPrayerResult } from './generated/NextPrayerResult';
+export type { NotificationProfile } from './generated/NotificationProfile';
+export type { NotificationProfiles } from './generated/NotificationProfiles';
+export type { NotificationTemplate } from './generated/NotificationTemplate';
+export type { NotificationTemplates } from './generated/NotificationTemplates';
+export type { OnboardingResult } from './generated/OnboardingResult';
+export type { OnboardingSuggestions } from './generated/OnboardingSuggestions';
+export type { Prayer } from './generated/Prayer';
+export type { PrayerAvailability } from './generated/PrayerAvailability';
+export type { PrayerCountdown } from './generated/PrayerCountdown';
+export type { ProviderMetrics } from './generated/ProviderMetrics';
+export type { CountdownDisplay } from './generated/CountdownDisplay';
+export type { CountdownSnapshot } from './generated/CountdownSnapshot';
+export type { PrayerDiff } from './generated/PrayerDiff';
+export type { PrayerGoal } from './generated/PrayerGoal';
+export type { PrayerLogEntry } from './generated/PrayerLogEntry';
+export type { PrayerLogQuery } from './generated/PrayerLogQuery';
+export type { PrayerLogTag } from './generated/PrayerLogTag';
+export type { PrayerName } from './generated/PrayerName';
+export type { PrayerTimes } from './generated/PrayerTimes';
+export type { PlaceWindow } from './generated/PlaceWindow';
+export type { PrayerWindow } from './generated/PrayerWindow';
+export type { ProfileWindow } from './generated/ProfileWindow';
+export type { ProviderConfig } from './generated/ProviderConfig';
+export type { ProviderInfo } from './generated/ProviderInfo';
+export type { ProviderTestResult } from './generated/ProviderTestResult';
+export type { RakahEstimate } from './generated/RakahEstimate';
+export type { RawPayloadInfo } from './generated/RawPayloadInfo';
+export type { RakahStatus } from './generated/RakahStatus';
+export type { RefreshContext } from './generated/RefreshContext';
+export type { RefreshMode } from './generated/RefreshMode';
+export type { RefreshPolicy } from './generated/RefreshPolicy';
+export type { RegionSource } from './generated/RegionSource';
+export type { ScheduleChange } from './generated/ScheduleChange';
+export type { ScheduleEdit } from './generated/ScheduleEdit';
+export type { ScheduleExportFormat } from './generated/ScheduleExportFormat';
+export type { ScheduleFreshness } from './generated/ScheduleFreshness';
+export type { ScheduleWarning } from './generated/ScheduleWarning';
+export type { ScheduleWarningKind } from './generated/ScheduleWarningKind';
+export type { SelectedMosque } from './generated/SelectedMosque';
+export type { ServerEvent } from './generated/ServerEvent';
+export type { SpecialDay } from './generated/SpecialDay';
+export type { SpeechConfig } from './generated/SpeechConfig';
+export type { StartupSettings } from './generated/StartupSettings';
+export type { StorageStatus } from './generated/StorageStatus';
+export type { TableSize } from './generated/TableSize';
+export type { TickMetrics } from './generated/TickMetrics';
+export type { TimeFormat } from './generated/TimeFormat';
+export type { TravelMode } from './generated/TravelMode';
+export type { TravelPrediction } from './generated/TravelPrediction';
+export type { TravelSessionStatus } from './generated/TravelSessionStatus';
+export type { TravelSessionUpdate } from './generated/TravelSessionUpdate';
+export type { TravelTime } from './generated/TravelTime';
+export type { UpcomingScheduleChange } from './generated/UpcomingScheduleChange';
+export type { UpdateInfo } from './generated/UpdateInfo';
+export type { UpstreamIssue } from './generated/UpstreamIssue';
 
-export interface PrayerCountdown {
-  prayer_name: string;
-  adhan_time: string;
-  iqama_time?: string;
-  time_until_adhan_secs: number;
-  time_until_iqama_secs?: number;
-  is_active: boolean;
-}
-
-export interface ConfigField {
-  key: string;
-  label: string;
-  field_type: 'string' | 'password' | 'number' | 'boolean' | 'url' | 'select';
-  required: boolean;
-  description?: string;
-  default_value?: string;
-  options?: string[];
-}
-
-export interface ProviderInfo {
-  id: string;
-  name: string;
-  description: string;
-  config_schema: ConfigField[];
-}
-
-export interface ProviderTestResult {
-  success: boolean;
-  message: string;
-  latency_ms?: number;
-}
-
-export interface ProviderConfig {
-  provider_id: string;
-  settings: Record<string, unknown>;
-}
+import type { AsrMethod } from './generated/AsrMethod';
+import type { IshaEnd } from './generated/IshaEnd';
+import type { TimeFormat } from './generated/TimeFormat';
 
 export interface Settings {
   theme: 'light' | 'dark';
   language: string;
+  time_format?: TimeFormat;
+  asr_method?: AsrMethod;
+  isha_end?: IshaEnd;
+  /** Hours before a cached schedule is refetched in the background */
+  max_cache_age_hours?: number;
+  /** Two-letter country code searched when no country is given */
+  default_country?: string;
   notification_enabled: boolean;
+  /** Encouraging summary of goals on Sunday evening */
+  goal_summary_notifications?: boolean;
+  /** Minutes before adhan and iqama to remind, each 1 to 120 (default [10]) */
+  reminder_minutes_before?: number[];
+  /** Minutes before sunrise to warn that Fajr's time is ending; off when unset */
+  fajr_ending_reminder_minutes?: number;
+  /** Reminder the evening before Ramadan, Laylat al-Qadr, Eid and other special days */
+  islamic_event_reminders?: boolean;
+  /** Look for new releases once a day in the background */
+  automatic_update_checks?: boolean;
+  /** Minutes before Fajr that the fast starts when the mosque publishes no imsak, 0 to 60 (default 10) */
+  imsak_minutes_before_fajr?: number;
+  /** Minutes before a prayer from which countdowns show seconds (default 10) */
+  countdown_seconds_threshold_minutes?: number;
+  /** Seconds before a prayer from which countdowns read "Now" (default 0) */
+  countdown_now_threshold_seconds?: number;
+  /** Keep the last responses of each mosque's provider so support can compare them with the mosque */
+  raw_payload_archive?: boolean;
+  /** Responses kept per mosque while archiving, 1 to 50 */
+  raw_payload_archive_size?: number;
 }